@@ -118,6 +118,7 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("GetCollectionInfoRequest.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("CollectionExistsRequest.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("WarmupCollectionRequest.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("CreateCollection.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name\")"),
             ("CreateCollection.hnsw_config", ""),
             ("CreateCollection.wal_config", ""),
@@ -154,6 +155,13 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.ivf_config", ""),
+            ("IvfConfig.lists", "range(min = 1)"),
+            ("IvfConfig.probes", "range(min = 1)"),
+            ("VectorParams.disk_ann_config", ""),
+            ("DiskAnnConfig.max_degree", "range(min = 4)"),
+            ("DiskAnnConfig.build_list_size", "range(min = 4)"),
+            ("DiskAnnConfig.search_list_size", "range(min = 1)"),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
@@ -181,6 +189,7 @@ fn configure_validation(builder: Builder) -> Builder {
             "UpdateCollectionClusterSetupRequest",
             "ProductQuantization",
             "BinaryQuantization",
+            "ResidualQuantization",
             "Disabled",
             "QuantizationConfigDiff",
             "quantization_config_diff::Quantization",
@@ -210,6 +219,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DeletePointVectors.points_selector", ""),
             ("PointVectors.vectors", ""),
             ("GetPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("ExistsPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("ExistsPoints.timeout", "range(min = 1)"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("SetPayloadPoints.points_selector", ""),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
@@ -240,6 +251,11 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "range(min = 1)"),
             ("SearchParams.quantization", ""),
+            ("SearchParams.max_scored_points", "range(min = 1)"),
+            ("SearchParams.acorn", ""),
+            ("SearchParams.ivf_probes", "range(min = 1)"),
+            ("SearchParams.disk_ann_list_size", "range(min = 1)"),
+            ("AcornSearchParams.max_selectivity", "range(min = 0.0, max = 1.0)"),
             ("QuantizationSearchParams.oversampling", "range(min = 1.0)"),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("ScrollPoints.filter", ""),
@@ -311,6 +327,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("Mmr.diversity", "range(min = 0.0, max = 1.0)"),
             ("Mmr.candidates_limit", "range(max = 16_384)"),
             ("Rrf.k", "range(min = 1)"),
+            ("WeightedVectorInput.vector", ""),
+            ("CombineInput.vectors", "nested, length(min = 1)"),
             ("Query.variant", ""),
             ("PrefetchQuery.prefetch", ""),
             ("PrefetchQuery.query", ""),
@@ -344,7 +362,18 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchMatrixPoints.filter", ""),
             ("SearchMatrixPoints.sample", "range(min = 2)"),
             ("SearchMatrixPoints.limit", "range(min = 1)"),
-            ("SearchMatrixPoints.timeout", "range(min = 1)")
+            ("SearchMatrixPoints.timeout", "range(min = 1)"),
+            ("RecallProbePoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("RecallProbePoints.filter", ""),
+            ("RecallProbePoints.sample", "range(max = 10000)"),
+            ("RecallProbePoints.limit", "range(min = 1)"),
+            ("RecallProbePoints.interval", "range(min = 1)"),
+            ("RecallProbePoints.timeout", "range(min = 1)"),
+            ("AggregatePoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("AggregatePoints.key", "length(min = 1)"),
+            ("AggregatePoints.filter", ""),
+            ("AggregatePoints.percentiles", "custom(function = \"crate::rest::validate::validate_percentiles\")"),
+            ("AggregatePoints.timeout", "range(min = 1)")
         ], &[])
         .type_attribute(".", "#[derive(serde::Serialize)]")
         // Service: points_internal_service.proto
@@ -386,6 +415,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPointsInternal.timeout", "range(min = 1)"),
             ("FacetCountsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("FacetCountsInternal.timeout", "range(min = 1)"),
+            ("PointHistoryInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("PointVersionsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("PointVersionsInternal.timeout", "range(min = 1)"),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
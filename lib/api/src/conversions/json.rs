@@ -26,6 +26,8 @@ pub fn json_to_proto(json_value: serde_json::Value) -> Value {
         serde_json::Value::Number(n) => {
             if let Some(int) = n.as_i64() {
                 Kind::IntegerValue(int)
+            } else if let Some(uint) = n.as_u64() {
+                Kind::UnsignedValue(uint)
             } else {
                 Kind::DoubleValue(n.as_f64().unwrap())
             }
@@ -72,11 +74,14 @@ pub fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
         Kind::NullValue(_) => serde_json::Value::Null,
         Kind::DoubleValue(n) => {
             let Some(v) = serde_json::Number::from_f64(n) else {
-                return Err(Status::invalid_argument("cannot convert to json number"));
+                return Err(Status::invalid_argument(format!(
+                    "cannot convert {n} to json number, only finite values are supported",
+                )));
             };
             serde_json::Value::Number(v)
         }
         Kind::IntegerValue(i) => serde_json::Value::Number(i.into()),
+        Kind::UnsignedValue(u) => serde_json::Value::Number(u.into()),
         Kind::StringValue(s) => serde_json::Value::String(s),
         Kind::BoolValue(b) => serde_json::Value::Bool(b),
         Kind::StructValue(s) => serde_json::Value::Object(
@@ -109,6 +114,7 @@ mod tests {
         {
             "f64": 1.0,
             "i64": 1,
+            "u64": 18446744073709551615,
             "string": "s",
             "bool": true,
             "struct": {"i64": 1},
@@ -120,6 +126,7 @@ mod tests {
             ("null", Kind::NullValue(0)),
             ("f64", Kind::DoubleValue(1.0)),
             ("i64", Kind::IntegerValue(1)),
+            ("u64", Kind::UnsignedValue(u64::MAX)),
             ("string", Kind::StringValue("s".to_string())),
             ("bool", Kind::BoolValue(true)),
             (
@@ -192,6 +199,44 @@ mod tests {
         assert_eq!(proto, proto_map);
     }
 
+    #[test]
+    fn test_proto_json_number_types() {
+        let numbers = [
+            (Kind::DoubleValue(1.0), "1.0"),
+            (Kind::IntegerValue(i64::MIN), "-9223372036854775808"),
+            (Kind::IntegerValue(i64::MAX), "9223372036854775807"),
+            (
+                Kind::UnsignedValue(i64::MAX as u64 + 1),
+                "9223372036854775808",
+            ),
+            (Kind::UnsignedValue(u64::MAX), "18446744073709551615"),
+        ];
+
+        for (kind, json) in numbers {
+            let proto = Value { kind: Some(kind) };
+            let json_value = proto_to_json(proto.clone()).unwrap();
+            assert_eq!(serde_json::to_string(&json_value).unwrap(), json);
+            assert_eq!(json_to_proto(serde_json::from_str(json).unwrap()), proto);
+        }
+
+        // Integers in range of `integer_value` are always sent as such
+        let proto = Value {
+            kind: Some(Kind::UnsignedValue(1)),
+        };
+        assert_eq!(
+            json_to_proto(proto_to_json(proto).unwrap()).kind,
+            Some(Kind::IntegerValue(1)),
+        );
+
+        for n in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let proto = Value {
+                kind: Some(Kind::DoubleValue(n)),
+            };
+            let result = proto_to_json(proto);
+            assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+        }
+    }
+
     #[test]
     fn test_proto_to_json_invalid_struct_value() {
         let proto = Value {
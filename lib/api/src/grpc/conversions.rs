@@ -18,6 +18,7 @@ use segment::data_types::{facets as segment_facets, vectors as segment_vectors};
 use segment::index::query_optimization::rescore_formula::parsed_formula::{
     DatetimeExpression, DecayKind, ParsedExpression, ParsedFormula,
 };
+use segment::payload_storage::wasm_condition;
 use segment::types::{DateTimePayloadType, FloatPayloadType, default_quantization_ignore_value};
 use segment::vector_storage::query as segment_query;
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
@@ -26,13 +27,14 @@ use uuid::Uuid;
 
 use super::qdrant::{
     BinaryQuantization, BoolIndexParams, CompressionRatio, DatetimeIndexParams, DatetimeRange,
-    Direction, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal, FieldType,
-    FloatIndexParams, GeoIndexParams, GeoLineString, GroupId, HardwareUsage, HasVectorCondition,
-    KeywordIndexParams, LookupLocation, MaxOptimizationThreads, MultiVectorComparator,
-    MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, RetrievedPoint,
-    SearchMatrixPair, SearchPointGroups, SearchPoints, ShardKeySelector, StartFrom,
-    StrictModeMultivector, StrictModeMultivectorConfig, StrictModeSparse, StrictModeSparseConfig,
-    UuidIndexParams, VectorsOutput, WithLookup, raw_query, start_from,
+    Direction, DiskAnnConfig, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal,
+    FieldType, FloatIndexParams, GeoIndexParams, GeoLineString, GroupId, HardwareUsage,
+    HasVectorCondition, IvfConfig, KeywordIndexParams, LookupLocation, MaxOptimizationThreads,
+    MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, Range, RawVector,
+    RecommendStrategy, RetrievedPoint, SearchMatrixPair, SearchPointGroups, SearchPoints,
+    ShardKeySelector, StartFrom, StrictModeMultivector, StrictModeMultivectorConfig,
+    StrictModeSparse, StrictModeSparseConfig, UuidIndexParams, VectorsOutput, WithLookup,
+    raw_query, start_from,
 };
 use super::stemming_algorithm::StemmingParams;
 use super::{Expression, Formula, RecoQuery, SnowballParams, StemmingAlgorithm, Usage};
@@ -43,21 +45,23 @@ use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    CollectionDescription, CollectionOperationResponse, Condition, Distance, FieldCondition,
-    Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply,
-    HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition, ListCollectionsResponse,
-    Match, MinShould, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
-    PointStruct, PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
+    AcornSearchParams, AggregatePercentile, AggregateResult, CollectionDescription,
+    CollectionOperationResponse, Condition, Distance, FieldCondition, Filter, GeoBoundingBox,
+    GeoPoint, GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff,
+    IntegerIndexParams, IsEmptyCondition, IsNullCondition, ListCollectionsResponse, Match,
+    MinShould, NamedVectors, NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector,
+    PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId, PointStruct,
+    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
     QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
-    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, StopwordsSet,
-    StrictModeConfig, TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal,
-    ValuesCount, VectorsSelector, WithPayloadSelector, WithVectorsSelector, shard_key,
-    with_vectors_selector,
+    RepeatedStrings, ResidualQuantization, ScalarQuantization, ScoredPoint, SearchParams, ShardKey,
+    StopwordsSet, StrictModeConfig, TextIndexParams, TokenizerType, UpdateResult,
+    UpdateResultInternal, ValuesCount, VectorsSelector, WasmCondition, WithPayloadSelector,
+    WithVectorsSelector, shard_key, with_vectors_selector,
 };
 use crate::grpc::{
     self, BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding, DecayParamsExpression,
-    DivExpression, GeoDistance, MultExpression, PowExpression, SumExpression,
+    DivExpression, GeoDistance, MultExpression, PowExpression, QuantizationPlacement,
+    SumExpression,
 };
 use crate::rest::models::{CollectionsResponse, VersionInfo};
 use crate::rest::schema as rest;
@@ -219,6 +223,7 @@ impl From<segment::data_types::index::TextIndexParams> for PayloadIndexParams {
             on_disk,
             stopwords,
             stemmer,
+            ascii_folding,
         } = params;
         let tokenizer = TokenizerType::from(tokenizer);
 
@@ -237,6 +242,7 @@ impl From<segment::data_types::index::TextIndexParams> for PayloadIndexParams {
                 on_disk,
                 stopwords: stopwords_set,
                 stemmer: stemming_algo,
+                ascii_folding,
             })),
         }
     }
@@ -509,6 +515,7 @@ impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
             on_disk,
             stopwords,
             stemmer,
+            ascii_folding,
         } = params;
 
         // Convert stopwords if present
@@ -537,6 +544,7 @@ impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
             on_disk,
             stopwords: stopwords_converted,
             stemmer,
+            ascii_folding,
         })
     }
 }
@@ -758,11 +766,13 @@ impl From<QuantizationSearchParams> for segment::types::QuantizationSearchParams
             ignore,
             rescore,
             oversampling,
+            adaptive_oversampling,
         } = params;
         Self {
             ignore: ignore.unwrap_or(default_quantization_ignore_value()),
             rescore,
             oversampling,
+            adaptive_oversampling,
         }
     }
 }
@@ -773,11 +783,39 @@ impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams
             ignore,
             rescore,
             oversampling,
+            adaptive_oversampling,
         } = params;
         Self {
             ignore: Some(ignore),
             rescore,
             oversampling,
+            adaptive_oversampling,
+        }
+    }
+}
+
+impl From<AcornSearchParams> for segment::types::AcornSearchParams {
+    fn from(params: AcornSearchParams) -> Self {
+        let AcornSearchParams {
+            enable,
+            max_selectivity,
+        } = params;
+        Self {
+            enable: enable.unwrap_or(false),
+            max_selectivity,
+        }
+    }
+}
+
+impl From<segment::types::AcornSearchParams> for AcornSearchParams {
+    fn from(params: segment::types::AcornSearchParams) -> Self {
+        let segment::types::AcornSearchParams {
+            enable,
+            max_selectivity,
+        } = params;
+        Self {
+            enable: Some(enable),
+            max_selectivity,
         }
     }
 }
@@ -789,12 +827,22 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact,
             quantization,
             indexed_only,
+            max_scored_points,
+            acorn,
+            ivf_probes,
+            disk_ann_list_size,
+            deterministic,
         } = params;
         Self {
             hnsw_ef: hnsw_ef.map(|x| x as usize),
             exact: exact.unwrap_or(false),
             quantization: quantization.map(|q| q.into()),
             indexed_only: indexed_only.unwrap_or(false),
+            max_scored_points: max_scored_points.map(|x| x as usize),
+            acorn: acorn.map(|a| a.into()),
+            ivf_probes: ivf_probes.map(|x| x as usize),
+            disk_ann_list_size: disk_ann_list_size.map(|x| x as usize),
+            deterministic: deterministic.unwrap_or(false),
         }
     }
 }
@@ -806,12 +854,22 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact,
             quantization,
             indexed_only,
+            max_scored_points,
+            acorn,
+            ivf_probes,
+            disk_ann_list_size,
+            deterministic,
         } = params;
         Self {
             hnsw_ef: hnsw_ef.map(|x| x as u64),
             exact: Some(exact),
             quantization: quantization.map(|q| q.into()),
             indexed_only: Some(indexed_only),
+            max_scored_points: max_scored_points.map(|x| x as u64),
+            acorn: acorn.map(|a| a.into()),
+            ivf_probes: ivf_probes.map(|x| x as u64),
+            disk_ann_list_size: disk_ann_list_size.map(|x| x as u64),
+            deterministic: Some(deterministic),
         }
     }
 }
@@ -1058,6 +1116,10 @@ impl From<segment::types::ScalarQuantization> for ScalarQuantization {
             },
             quantile: config.quantile,
             always_ram: config.always_ram,
+            auto_quantile: config.auto_quantile,
+            placement: config
+                .placement
+                .map(|placement| i32::from(QuantizationPlacement::from(placement))),
         }
     }
 }
@@ -1070,7 +1132,13 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
             r#type,
             quantile,
             always_ram,
+            auto_quantile,
+            placement,
         } = value;
+        let placement = placement
+            .map(QuantizationPlacement::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Unknown quantization placement"))?;
         Ok(segment::types::ScalarQuantization {
             scalar: segment::types::ScalarQuantizationConfig {
                 r#type: match QuantizationType::try_from(r#type).ok() {
@@ -1081,6 +1149,8 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
                 },
                 quantile,
                 always_ram,
+                placement: placement.map(segment::types::QuantizationPlacement::from),
+                auto_quantile,
             },
         })
     }
@@ -1092,6 +1162,7 @@ impl From<segment::types::ProductQuantization> for ProductQuantization {
         let segment::types::ProductQuantizationConfig {
             compression,
             always_ram,
+            placement,
         } = product;
         ProductQuantization {
             compression: match compression {
@@ -1102,6 +1173,7 @@ impl From<segment::types::ProductQuantization> for ProductQuantization {
                 segment::types::CompressionRatio::X64 => CompressionRatio::X64 as i32,
             },
             always_ram,
+            placement: placement.map(|placement| i32::from(QuantizationPlacement::from(placement))),
         }
     }
 }
@@ -1113,7 +1185,12 @@ impl TryFrom<ProductQuantization> for segment::types::ProductQuantization {
         let ProductQuantization {
             compression,
             always_ram,
+            placement,
         } = value;
+        let placement = placement
+            .map(QuantizationPlacement::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Unknown quantization placement"))?;
         Ok(segment::types::ProductQuantization {
             product: segment::types::ProductQuantizationConfig {
                 compression: match CompressionRatio::try_from(compression) {
@@ -1129,11 +1206,32 @@ impl TryFrom<ProductQuantization> for segment::types::ProductQuantization {
                     Ok(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
                 },
                 always_ram,
+                placement: placement.map(segment::types::QuantizationPlacement::from),
             },
         })
     }
 }
 
+impl From<segment::types::QuantizationPlacement> for QuantizationPlacement {
+    fn from(value: segment::types::QuantizationPlacement) -> Self {
+        match value {
+            segment::types::QuantizationPlacement::Ram => QuantizationPlacement::Ram,
+            segment::types::QuantizationPlacement::Mmap => QuantizationPlacement::Mmap,
+            segment::types::QuantizationPlacement::MmapLocked => QuantizationPlacement::MmapLocked,
+        }
+    }
+}
+
+impl From<QuantizationPlacement> for segment::types::QuantizationPlacement {
+    fn from(value: QuantizationPlacement) -> Self {
+        match value {
+            QuantizationPlacement::Ram => segment::types::QuantizationPlacement::Ram,
+            QuantizationPlacement::Mmap => segment::types::QuantizationPlacement::Mmap,
+            QuantizationPlacement::MmapLocked => segment::types::QuantizationPlacement::MmapLocked,
+        }
+    }
+}
+
 impl From<segment::types::BinaryQuantizationEncoding> for BinaryQuantizationEncoding {
     fn from(value: segment::types::BinaryQuantizationEncoding) -> Self {
         match value {
@@ -1171,6 +1269,7 @@ impl From<segment::types::BinaryQuantization> for BinaryQuantization {
         let segment::types::BinaryQuantization { binary } = value;
         let segment::types::BinaryQuantizationConfig {
             always_ram,
+            placement,
             encoding,
             query_encoding,
         } = binary;
@@ -1179,6 +1278,7 @@ impl From<segment::types::BinaryQuantization> for BinaryQuantization {
             encoding: encoding
                 .map(|encoding| i32::from(BinaryQuantizationEncoding::from(encoding))),
             query_encoding: query_encoding.map(BinaryQuantizationQueryEncoding::from),
+            placement: placement.map(|placement| i32::from(QuantizationPlacement::from(placement))),
         }
     }
 }
@@ -1191,7 +1291,12 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
             always_ram,
             encoding,
             query_encoding,
+            placement,
         } = value;
+        let placement = placement
+            .map(QuantizationPlacement::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Unknown quantization placement"))?;
         let encoding = encoding
             .map(BinaryQuantizationEncoding::try_from)
             .transpose()
@@ -1199,6 +1304,7 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
         Ok(segment::types::BinaryQuantization {
             binary: segment::types::BinaryQuantizationConfig {
                 always_ram,
+                placement: placement.map(segment::types::QuantizationPlacement::from),
                 encoding: encoding.map(segment::types::BinaryQuantizationEncoding::from),
                 query_encoding: query_encoding
                     .map(segment::types::BinaryQuantizationQueryEncoding::try_from)
@@ -1211,6 +1317,41 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
     }
 }
 
+impl From<segment::types::ResidualQuantization> for ResidualQuantization {
+    fn from(value: segment::types::ResidualQuantization) -> Self {
+        let segment::types::ResidualQuantization { residual } = value;
+        let segment::types::ResidualQuantizationConfig {
+            always_ram,
+            placement,
+        } = residual;
+        ResidualQuantization {
+            always_ram,
+            placement: placement.map(|placement| i32::from(QuantizationPlacement::from(placement))),
+        }
+    }
+}
+
+impl TryFrom<ResidualQuantization> for segment::types::ResidualQuantization {
+    type Error = Status;
+
+    fn try_from(value: ResidualQuantization) -> Result<Self, Self::Error> {
+        let ResidualQuantization {
+            always_ram,
+            placement,
+        } = value;
+        let placement = placement
+            .map(QuantizationPlacement::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Unknown quantization placement"))?;
+        Ok(segment::types::ResidualQuantization {
+            residual: segment::types::ResidualQuantizationConfig {
+                always_ram,
+                placement: placement.map(segment::types::QuantizationPlacement::from),
+            },
+        })
+    }
+}
+
 impl From<segment::types::QuantizationConfig> for QuantizationConfig {
     fn from(value: segment::types::QuantizationConfig) -> Self {
         match value {
@@ -1229,6 +1370,11 @@ impl From<segment::types::QuantizationConfig> for QuantizationConfig {
                     binary.into(),
                 )),
             },
+            segment::types::QuantizationConfig::Residual(residual) => Self {
+                quantization: Some(super::qdrant::quantization_config::Quantization::Residual(
+                    residual.into(),
+                )),
+            },
         }
     }
 }
@@ -1250,6 +1396,9 @@ impl TryFrom<QuantizationConfig> for segment::types::QuantizationConfig {
             super::qdrant::quantization_config::Quantization::Binary(config) => Ok(
                 segment::types::QuantizationConfig::Binary(config.try_into()?),
             ),
+            super::qdrant::quantization_config::Quantization::Residual(config) => Ok(
+                segment::types::QuantizationConfig::Residual(config.try_into()?),
+            ),
         }
     }
 }
@@ -1343,6 +1492,56 @@ impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
     }
 }
 
+impl From<segment::types::IvfConfig> for IvfConfig {
+    fn from(value: segment::types::IvfConfig) -> Self {
+        let segment::types::IvfConfig { lists, probes } = value;
+        Self {
+            lists: lists.map(|x| x as u64),
+            probes: probes.map(|x| x as u64),
+        }
+    }
+}
+
+impl From<IvfConfig> for segment::types::IvfConfig {
+    fn from(value: IvfConfig) -> Self {
+        let IvfConfig { lists, probes } = value;
+        Self {
+            lists: lists.map(|x| x as usize),
+            probes: probes.map(|x| x as usize),
+        }
+    }
+}
+
+impl From<segment::types::DiskAnnConfig> for DiskAnnConfig {
+    fn from(value: segment::types::DiskAnnConfig) -> Self {
+        let segment::types::DiskAnnConfig {
+            max_degree,
+            build_list_size,
+            search_list_size,
+        } = value;
+        Self {
+            max_degree: max_degree.map(|x| x as u64),
+            build_list_size: build_list_size.map(|x| x as u64),
+            search_list_size: search_list_size.map(|x| x as u64),
+        }
+    }
+}
+
+impl From<DiskAnnConfig> for segment::types::DiskAnnConfig {
+    fn from(value: DiskAnnConfig) -> Self {
+        let DiskAnnConfig {
+            max_degree,
+            build_list_size,
+            search_list_size,
+        } = value;
+        Self {
+            max_degree: max_degree.map(|x| x as usize),
+            build_list_size: build_list_size.map(|x| x as usize),
+            search_list_size: search_list_size.map(|x| x as usize),
+        }
+    }
+}
+
 impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     fn from(value: MultiVectorComparator) -> Self {
         match value {
@@ -1478,6 +1677,11 @@ pub fn grpc_condition_into_condition(
                 has_vector: has_vector.has_vector,
             },
         )),
+        ConditionOneOf::Wasm(wasm) => Some(segment::types::Condition::Wasm(
+            segment::types::WasmCondition {
+                wasm: wasm.try_into()?,
+            },
+        )),
     };
 
     Ok(condition)
@@ -1512,6 +1716,9 @@ impl From<segment::types::Condition> for Condition {
                     has_vector: has_vector.has_vector,
                 }))
             }
+            segment::types::Condition::Wasm(wasm) => {
+                Some(ConditionOneOf::Wasm(WasmCondition::from(wasm.wasm)))
+            }
         };
 
         Self { condition_one_of }
@@ -1580,6 +1787,32 @@ impl TryFrom<IsNullCondition> for segment::types::IsNullCondition {
     }
 }
 
+impl TryFrom<WasmCondition> for segment::types::WasmPredicate {
+    type Error = Status;
+
+    fn try_from(value: WasmCondition) -> Result<Self, Status> {
+        let WasmCondition { name, key } = value;
+        // Filters are forwarded to other peers over gRPC, a peer without the module must fail
+        // the request instead of silently not matching any point
+        wasm_condition::check_registered(&name)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        Ok(segment::types::WasmPredicate {
+            name,
+            key: key.as_deref().map(json::json_path_from_proto).transpose()?,
+        })
+    }
+}
+
+impl From<segment::types::WasmPredicate> for WasmCondition {
+    fn from(value: segment::types::WasmPredicate) -> Self {
+        let segment::types::WasmPredicate { name, key } = value;
+        Self {
+            name,
+            key: key.map(|key| key.to_string()),
+        }
+    }
+}
+
 impl From<segment::types::IsNullCondition> for IsNullCondition {
     fn from(value: segment::types::IsNullCondition) -> Self {
         let segment::types::IsNullCondition { is_null } = value;
@@ -1899,6 +2132,7 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::TextAny(text_any) => {
                     segment::types::Match::TextAny(segment::types::MatchTextAny { text_any })
                 }
+                MatchValue::Pattern(pattern) => segment::types::Match::Pattern(pattern.into()),
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1942,6 +2176,9 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::TextAny(segment::types::MatchTextAny { text_any }) => {
                 MatchValue::TextAny(text_any)
             }
+            segment::types::Match::Pattern(segment::types::MatchPattern { pattern }) => {
+                MatchValue::Pattern(pattern)
+            }
         };
         Self {
             match_value: Some(match_value),
@@ -2127,6 +2364,8 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             on_disk,
             payload_m,
             copy_vectors,
+            gpu_indexing,
+            seed,
         } = hnsw_config;
         Self {
             m: m.unwrap_or_default() as usize,
@@ -2136,6 +2375,8 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             on_disk,
             payload_m: payload_m.map(|x| x as usize),
             copy_vectors,
+            gpu_indexing,
+            seed,
         }
     }
 }
@@ -2162,6 +2403,7 @@ impl From<StrictModeConfig> for segment::types::StrictModeConfig {
             multivector_config,
             sparse_config,
             max_payload_index_count,
+            enforce_payload_schema,
         } = value;
         Self {
             enabled,
@@ -2185,6 +2427,7 @@ impl From<StrictModeConfig> for segment::types::StrictModeConfig {
                 .map(segment::types::StrictModeMultivectorConfig::from),
             sparse_config: sparse_config.map(segment::types::StrictModeSparseConfig::from),
             max_payload_index_count: max_payload_index_count.map(|i| i as usize),
+            enforce_payload_schema,
         }
     }
 }
@@ -2287,6 +2530,7 @@ impl From<segment::types::StrictModeConfigOutput> for StrictModeConfig {
             multivector_config,
             sparse_config,
             max_payload_index_count,
+            enforce_payload_schema,
         } = value;
         Self {
             enabled,
@@ -2308,6 +2552,7 @@ impl From<segment::types::StrictModeConfigOutput> for StrictModeConfig {
             sparse_config: sparse_config.map(StrictModeSparseConfig::from),
             max_points_count: max_points_count.map(|i| i as u64),
             max_payload_index_count: max_payload_index_count.map(|i| i as u64),
+            enforce_payload_schema,
         }
     }
 }
@@ -2334,6 +2579,7 @@ impl From<StrictModeConfig> for segment::types::StrictModeConfigOutput {
             multivector_config,
             sparse_config,
             max_payload_index_count,
+            enforce_payload_schema,
         } = value;
         Self {
             enabled,
@@ -2357,6 +2603,7 @@ impl From<StrictModeConfig> for segment::types::StrictModeConfigOutput {
                 .map(segment::types::StrictModeMultivectorConfigOutput::from),
             sparse_config: sparse_config.map(segment::types::StrictModeSparseConfigOutput::from),
             max_payload_index_count: max_payload_index_count.map(|i| i as usize),
+            enforce_payload_schema,
         }
     }
 }
@@ -2464,6 +2711,10 @@ impl TryFrom<Distance> for segment::types::Distance {
             Distance::Euclid => segment::types::Distance::Euclid,
             Distance::Dot => segment::types::Distance::Dot,
             Distance::Manhattan => segment::types::Distance::Manhattan,
+            Distance::Hamming => segment::types::Distance::Hamming,
+            Distance::Jaccard => segment::types::Distance::Jaccard,
+            Distance::Custom => segment::types::Distance::Custom,
+            Distance::RawCosine => segment::types::Distance::RawCosine,
         })
     }
 }
@@ -3041,6 +3292,35 @@ impl From<rest::SearchMatrixPair> for SearchMatrixPair {
     }
 }
 
+impl From<rest::AggregateResponse> for AggregateResult {
+    fn from(response: rest::AggregateResponse) -> Self {
+        let rest::AggregateResponse {
+            count,
+            sum,
+            avg,
+            min,
+            max,
+            percentiles,
+        } = response;
+        Self {
+            count: count as u64,
+            sum,
+            avg,
+            min,
+            max,
+            percentiles: percentiles
+                .into_iter()
+                .map(
+                    |rest::AggregatePercentile { percentile, value }| AggregatePercentile {
+                        percentile,
+                        value,
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
 impl From<HwMeasurementAcc> for HardwareUsage {
     fn from(value: HwMeasurementAcc) -> Self {
         Self {
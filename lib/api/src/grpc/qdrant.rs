@@ -26,7 +26,7 @@ pub struct Struct {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Value {
     /// The kind of value.
-    #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6, 7")]
+    #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub kind: ::core::option::Option<value::Kind>,
 }
 /// Nested message and enum types in `Value`.
@@ -57,6 +57,9 @@ pub mod value {
         /// Represents a repeated `Value`.
         #[prost(message, tag = "7")]
         ListValue(super::ListValue),
+        /// Represents an unsigned integer value, exceeding the range of `integer_value`
+        #[prost(uint64, tag = "8")]
+        UnsignedValue(u64),
     }
 }
 /// `ListValue` is a wrapper around a repeated field of values.
@@ -128,6 +131,14 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Configuration of vector IVF index. If set - IVF index is built instead of HNSW graph
+    #[prost(message, optional, tag = "8")]
+    #[validate(nested)]
+    pub ivf_config: ::core::option::Option<IvfConfig>,
+    /// Configuration of vector DiskANN index. If set - DiskANN graph is built on disk instead of HNSW graph
+    #[prost(message, optional, tag = "9")]
+    #[validate(nested)]
+    pub disk_ann_config: ::core::option::Option<DiskAnnConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -242,6 +253,38 @@ pub struct MultiVectorConfig {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IvfConfig {
+    /// Number of clusters vectors are partitioned into. If omitted - square root of the number of vectors in the segment is used
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(range(min = 1))]
+    pub lists: ::core::option::Option<u64>,
+    /// Number of nearest clusters to scan during search. Default is 8
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(range(min = 1))]
+    pub probes: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskAnnConfig {
+    /// Max number of links of each point in the graph. Default is 64
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(range(min = 4))]
+    pub max_degree: ::core::option::Option<u64>,
+    /// Number of candidates considered as links of each point during construction. Default is 100
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(range(min = 4))]
+    pub build_list_size: ::core::option::Option<u64>,
+    /// Number of candidates kept during search. Can be overridden per request with `disk_ann_list_size`. Default is 100
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(range(min = 1))]
+    pub search_list_size: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetCollectionInfoRequest {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -413,6 +456,16 @@ pub struct HnswConfigDiff {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[prost(bool, optional, tag = "7")]
     pub copy_vectors: ::core::option::Option<bool>,
+    /// Use GPU to build the index, if GPU indexing is enabled on the node. Default: true.
+    /// Distances for link selection are computed on GPU, while the graph is assembled on CPU.
+    /// Set to false to always build this index on CPU.
+    #[prost(bool, optional, tag = "8")]
+    pub gpu_indexing: ::core::option::Option<bool>,
+    /// Seed for randomized steps of the index building. If set, the index is built
+    /// deterministically: same data and config produce the same graph on every node.
+    /// Seeded builds run on a single thread, on CPU, and don't reuse existing graphs.
+    #[prost(uint64, optional, tag = "9")]
+    pub seed: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -445,6 +498,12 @@ pub struct WalConfigDiff {
     #[prost(uint64, optional, tag = "3")]
     #[validate(range(min = 1))]
     pub wal_retain_closed: ::core::option::Option<u64>,
+    /// Time window in milliseconds to batch concurrent updates, 0 to disable
+    #[prost(uint64, optional, tag = "4")]
+    pub wal_batch_window_ms: ::core::option::Option<u64>,
+    /// Number of last versions of each point to keep in point history, 0 to disable
+    #[prost(uint64, optional, tag = "5")]
+    pub wal_point_history: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -529,6 +588,12 @@ pub struct ScalarQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "3")]
     pub always_ram: ::core::option::Option<bool>,
+    /// If true - choose the range of quantized values automatically, minimizing the quantization error. `quantile` is ignored
+    #[prost(bool, optional, tag = "4")]
+    pub auto_quantile: ::core::option::Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[prost(enumeration = "QuantizationPlacement", optional, tag = "5")]
+    pub placement: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -541,6 +606,9 @@ pub struct ProductQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "2")]
     pub always_ram: ::core::option::Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[prost(enumeration = "QuantizationPlacement", optional, tag = "3")]
+    pub placement: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -617,13 +685,28 @@ pub struct BinaryQuantization {
     /// It can increase the accuracy of search at the cost of performance.
     #[prost(message, optional, tag = "3")]
     pub query_encoding: ::core::option::Option<BinaryQuantizationQueryEncoding>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[prost(enumeration = "QuantizationPlacement", optional, tag = "4")]
+    pub placement: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResidualQuantization {
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[prost(bool, optional, tag = "1")]
+    pub always_ram: ::core::option::Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[prost(enumeration = "QuantizationPlacement", optional, tag = "2")]
+    pub placement: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantizationConfig {
-    #[prost(oneof = "quantization_config::Quantization", tags = "1, 2, 3")]
+    #[prost(oneof = "quantization_config::Quantization", tags = "1, 2, 3, 4")]
     #[validate(nested)]
     pub quantization: ::core::option::Option<quantization_config::Quantization>,
 }
@@ -639,6 +722,8 @@ pub mod quantization_config {
         Product(super::ProductQuantization),
         #[prost(message, tag = "3")]
         Binary(super::BinaryQuantization),
+        #[prost(message, tag = "4")]
+        Residual(super::ResidualQuantization),
     }
 }
 #[derive(validator::Validate)]
@@ -651,7 +736,7 @@ pub struct Disabled {}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantizationConfigDiff {
-    #[prost(oneof = "quantization_config_diff::Quantization", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "quantization_config_diff::Quantization", tags = "1, 2, 3, 4, 5")]
     #[validate(nested)]
     pub quantization: ::core::option::Option<quantization_config_diff::Quantization>,
 }
@@ -669,6 +754,8 @@ pub mod quantization_config_diff {
         Disabled(super::Disabled),
         #[prost(message, tag = "4")]
         Binary(super::BinaryQuantization),
+        #[prost(message, tag = "5")]
+        Residual(super::ResidualQuantization),
     }
 }
 #[derive(validator::Validate)]
@@ -740,6 +827,9 @@ pub struct StrictModeConfig {
     /// Max number of payload indexes in a collection
     #[prost(uint64, optional, tag = "19")]
     pub max_payload_index_count: ::core::option::Option<u64>,
+    /// Reject payloads with keys without a payload index, or with values not matching the index type
+    #[prost(bool, optional, tag = "20")]
+    pub enforce_payload_schema: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -1090,6 +1180,9 @@ pub struct TextIndexParams {
     /// Set an algorithm for stemming.
     #[prost(message, optional, tag = "8")]
     pub stemmer: ::core::option::Option<StemmingAlgorithm>,
+    /// If true - fold accented latin characters to ASCII.
+    #[prost(bool, optional, tag = "9")]
+    pub ascii_folding: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1643,6 +1736,32 @@ pub struct DeleteShardKeyResponse {
     #[prost(bool, tag = "1")]
     pub result: bool,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WarmupCollectionRequest {
+    /// Name of the collection to warm up
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    /// If true - also lock the loaded files in RAM. Files of segments created by optimizers later on are locked as well. If false - files locked by a previous warmup are released. Default: false
+    #[prost(bool, optional, tag = "2")]
+    pub lock: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WarmupCollectionResponse {
+    #[prost(bool, tag = "1")]
+    pub result: bool,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -1737,6 +1856,10 @@ pub enum Distance {
     Euclid = 2,
     Dot = 3,
     Manhattan = 4,
+    Hamming = 5,
+    Jaccard = 6,
+    Custom = 7,
+    RawCosine = 8,
 }
 impl Distance {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1750,6 +1873,10 @@ impl Distance {
             Distance::Euclid => "Euclid",
             Distance::Dot => "Dot",
             Distance::Manhattan => "Manhattan",
+            Distance::Hamming => "Hamming",
+            Distance::Jaccard => "Jaccard",
+            Distance::Custom => "Custom",
+            Distance::RawCosine => "RawCosine",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1760,6 +1887,10 @@ impl Distance {
             "Euclid" => Some(Self::Euclid),
             "Dot" => Some(Self::Dot),
             "Manhattan" => Some(Self::Manhattan),
+            "Hamming" => Some(Self::Hamming),
+            "Jaccard" => Some(Self::Jaccard),
+            "Custom" => Some(Self::Custom),
+            "RawCosine" => Some(Self::RawCosine),
             _ => None,
         }
     }
@@ -1918,6 +2049,39 @@ impl CompressionRatio {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum QuantizationPlacement {
+    /// Keep quantized vectors in RAM
+    Ram = 0,
+    /// Memory-map quantized vectors from disk, regardless of the original vectors storage
+    Mmap = 1,
+    /// Memory-map quantized vectors from disk and lock them in RAM, so they are never swapped out
+    MmapLocked = 2,
+}
+impl QuantizationPlacement {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            QuantizationPlacement::Ram => "Ram",
+            QuantizationPlacement::Mmap => "Mmap",
+            QuantizationPlacement::MmapLocked => "MmapLocked",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Ram" => Some(Self::Ram),
+            "Mmap" => Some(Self::Mmap),
+            "MmapLocked" => Some(Self::MmapLocked),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum BinaryQuantizationEncoding {
     OneBit = 0,
     TwoBits = 1,
@@ -2550,6 +2714,32 @@ pub mod collections_client {
                 .insert(GrpcMethod::new("qdrant.Collections", "DeleteShardKey"));
             self.inner.unary(req, path, codec).await
         }
+        /// Load vectors, quantized vectors and vector indexes of the local shards of the collection into the page cache
+        pub async fn warmup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WarmupCollectionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::WarmupCollectionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/Warmup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "Warmup"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2663,6 +2853,14 @@ pub mod collections_server {
             tonic::Response<super::DeleteShardKeyResponse>,
             tonic::Status,
         >;
+        /// Load vectors, quantized vectors and vector indexes of the local shards of the collection into the page cache
+        async fn warmup(
+            &self,
+            request: tonic::Request<super::WarmupCollectionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::WarmupCollectionResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsServer<T: Collections> {
@@ -3350,6 +3548,52 @@ pub mod collections_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Collections/Warmup" => {
+                    #[allow(non_camel_case_types)]
+                    struct WarmupSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::WarmupCollectionRequest>
+                    for WarmupSvc<T> {
+                        type Response = super::WarmupCollectionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WarmupCollectionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::warmup(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WarmupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -4416,6 +4660,9 @@ pub struct UpsertPoints {
     #[prost(message, optional, tag = "6")]
     #[validate(nested)]
     pub update_filter: ::core::option::Option<Filter>,
+    /// If true, points with vectors and payload identical to the stored ones are skipped. Re-upserting unchanged points then does not create new versions of them.
+    #[prost(bool, optional, tag = "7")]
+    pub skip_unchanged: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4477,6 +4724,46 @@ pub struct GetPoints {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExistsPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    /// List of points to look for
+    #[prost(message, repeated, tag = "2")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "3")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "4")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(range(min = 1))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExistsResult {
+    /// Requested ids of points, which exist in the collection
+    #[prost(message, repeated, tag = "1")]
+    pub existing: ::prost::alloc::vec::Vec<PointId>,
+    /// Requested ids of points, which do not exist in the collection
+    #[prost(message, repeated, tag = "2")]
+    pub missing: ::prost::alloc::vec::Vec<PointId>,
+    /// Versions of existing points, in the same order as `existing`
+    #[prost(uint64, repeated, tag = "3")]
+    pub versions: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdatePointVectors {
     /// name of the collection
     #[prost(string, tag = "1")]
@@ -4832,6 +5119,27 @@ pub struct QuantizationSearchParams {
     #[prost(double, optional, tag = "3")]
     #[validate(range(min = 1.0))]
     pub oversampling: ::core::option::Option<f64>,
+    /// If true, oversampling factor is chosen automatically for each segment,
+    /// based on the difference between quantized and original scores observed during rescoring.
+    /// If `oversampling` is set as well, it is used as the upper bound.
+    #[prost(bool, optional, tag = "4")]
+    pub adaptive_oversampling: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AcornSearchParams {
+    /// If true, filtered HNSW search also explores links of points, which are rejected by the filter.
+    /// Improves recall for selective filters, at the cost of more vector comparisons. Default is false.
+    #[prost(bool, optional, tag = "1")]
+    pub enable: ::core::option::Option<bool>,
+    /// Max share of points, matching the filter, for which ACORN search is used.
+    /// Based on the cardinality estimation of the filter. Less selective filters are searched
+    /// with the regular filtered HNSW search. Default is 0.4.
+    #[prost(double, optional, tag = "2")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub max_selectivity: ::core::option::Option<f64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4854,6 +5162,32 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    /// Max number of candidate points to score per query. Once it is spent,
+    /// the search returns the best points found so far.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(range(min = 1))]
+    pub max_scored_points: ::core::option::Option<u64>,
+    /// ACORN-style search for filtered queries. Explores links of points rejected by the filter,
+    /// which keeps recall for filters of medium selectivity.
+    #[prost(message, optional, tag = "6")]
+    #[validate(nested)]
+    pub acorn: ::core::option::Option<AcornSearchParams>,
+    /// Params relevant to IVF index. Number of nearest lists to scan.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(range(min = 1))]
+    pub ivf_probes: ::core::option::Option<u64>,
+    /// Params relevant to DiskANN index. Number of candidates kept during graph search.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "8")]
+    #[validate(range(min = 1))]
+    pub disk_ann_list_size: ::core::option::Option<u64>,
+    /// Score dense vectors with plain implementations of distance functions instead of SIMD
+    /// kernels. Identical data and queries then yield bit-identical scores across runs and
+    /// machines, at the cost of slower search. Points with equal scores are ordered by ID.
+    /// Scores of quantized vectors are not affected, enable rescoring to get deterministic scores.
+    #[prost(bool, optional, tag = "9")]
+    pub deterministic: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5087,6 +5421,9 @@ pub struct ScrollPoints {
     /// If set, overrides global timeout setting for this request. Unit is seconds.
     #[prost(uint64, optional, tag = "11")]
     pub timeout: ::core::option::Option<u64>,
+    /// Read points strictly after this ID. Unlike `offset`, the given point itself is not included, so the ID of the last point of the previous page can be used to get the next one.
+    #[prost(message, optional, tag = "12")]
+    pub after_id: ::core::option::Option<PointId>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5693,8 +6030,31 @@ pub struct Rrf {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedVectorInput {
+    /// Vector or id of a point to take the vector from
+    #[prost(message, optional, tag = "1")]
+    #[validate(nested)]
+    pub vector: ::core::option::Option<VectorInput>,
+    /// Multiplier of the vector in the sum. Default: 1.0
+    #[prost(float, optional, tag = "2")]
+    pub weight: ::core::option::Option<f32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CombineInput {
+    /// Vectors to sum up, each one multiplied by its weight. Use negative weights to subtract vectors.
+    #[prost(message, repeated, tag = "1")]
+    #[validate(nested, length(min = 1))]
+    pub vectors: ::prost::alloc::vec::Vec<WeightedVectorInput>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     #[validate(nested)]
     pub variant: ::core::option::Option<query::Variant>,
 }
@@ -5734,6 +6094,9 @@ pub mod query {
         /// Parameterized reciprocal rank fusion
         #[prost(message, tag = "10")]
         Rrf(super::Rrf),
+        /// Find the nearest neighbors to a weighted sum of vectors.
+        #[prost(message, tag = "11")]
+        Combine(super::CombineInput),
     }
 }
 #[derive(validator::Validate)]
@@ -5831,6 +6194,9 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, return results from responsive shards instead of failing the request if some shards time out or are unavailable. Such response is marked as `degraded`.
+    #[prost(bool, optional, tag = "16")]
+    pub allow_partial: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5853,6 +6219,9 @@ pub struct QueryBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, return results from responsive shards instead of failing the request if some shards time out or are unavailable. Such response is marked as `degraded`.
+    #[prost(bool, optional, tag = "5")]
+    pub allow_partial: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6072,33 +6441,161 @@ pub struct SearchMatrixOffsets {
     #[prost(message, repeated, tag = "4")]
     pub ids: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PointsUpdateOperation {
-    #[prost(
-        oneof = "points_update_operation::Operation",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
+pub struct RecallProbePoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
     )]
-    pub operation: ::core::option::Option<points_update_operation::Operation>,
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - sample and search only points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "2")]
+    #[validate(nested)]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many random points to use as queries. Default is 100. 0 stops the probe.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(range(max = 10000))]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per query to compare. Default is 10.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(range(min = 1))]
+    pub limit: ::core::option::Option<u64>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// How often to evaluate recall, in seconds. Default is 60.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(range(min = 1))]
+    pub interval: ::core::option::Option<u64>,
+    /// If set, overrides global timeout for the queries of the probe. Unit is seconds.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(range(min = 1))]
+    pub timeout: ::core::option::Option<u64>,
 }
-/// Nested message and enum types in `PointsUpdateOperation`.
-pub mod points_update_operation {
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
-    #[derive(Clone, PartialEq, ::prost::Message)]
-    pub struct PointStructList {
-        #[prost(message, repeated, tag = "1")]
-        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
-        /// Option for custom sharding to specify used shard keys
-        #[prost(message, optional, tag = "2")]
-        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
-        /// If specified, only points that match this filter will be updated, others will be inserted
-        #[prost(message, optional, tag = "3")]
-        pub update_filter: ::core::option::Option<super::Filter>,
-    }
-    #[derive(serde::Serialize)]
-    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallProbeResult {
+    /// Name of the probed vector
+    #[prost(string, tag = "1")]
+    pub using: ::prost::alloc::string::String,
+    /// Number of sampled queries in the ground truth
+    #[prost(uint64, tag = "2")]
+    pub sample_size: u64,
+    /// Number of neighbours compared per query
+    #[prost(uint64, tag = "3")]
+    pub limit: u64,
+    /// Average fraction of exact neighbours found by the approximate search, in \[0, 1\]
+    #[prost(double, tag = "4")]
+    pub recall: f64,
+    /// Time of the evaluation
+    #[prost(message, optional, tag = "5")]
+    pub evaluated_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Numeric payload key to aggregate values of
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - only consider points that satisfy these conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate(nested)]
+    pub filter: ::core::option::Option<Filter>,
+    /// Percentiles of values to compute, each in range \[0, 100\]. Percentiles of more than 10000 values are estimated from a random sample of them.
+    #[prost(double, repeated, tag = "4")]
+    #[validate(custom(function = "crate::rest::validate::validate_percentiles"))]
+    pub percentiles: ::prost::alloc::vec::Vec<f64>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(range(min = 1))]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePercentile {
+    /// Requested percentile
+    #[prost(double, tag = "1")]
+    pub percentile: f64,
+    /// Value of the percentile, linearly interpolated between the closest values. Absent if there are no values.
+    #[prost(double, optional, tag = "2")]
+    pub value: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResult {
+    /// Number of aggregated values. Each value of an array counts separately.
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// Sum of the values
+    #[prost(double, tag = "2")]
+    pub sum: f64,
+    /// Average of the values, absent if there are no values
+    #[prost(double, optional, tag = "3")]
+    pub avg: ::core::option::Option<f64>,
+    /// Lowest of the values, absent if there are no values
+    #[prost(double, optional, tag = "4")]
+    pub min: ::core::option::Option<f64>,
+    /// Highest of the values, absent if there are no values
+    #[prost(double, optional, tag = "5")]
+    pub max: ::core::option::Option<f64>,
+    /// Requested percentiles of the values, in the order of the request
+    #[prost(message, repeated, tag = "6")]
+    pub percentiles: ::prost::alloc::vec::Vec<AggregatePercentile>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointsUpdateOperation {
+    #[prost(
+        oneof = "points_update_operation::Operation",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10"
+    )]
+    pub operation: ::core::option::Option<points_update_operation::Operation>,
+}
+/// Nested message and enum types in `PointsUpdateOperation`.
+pub mod points_update_operation {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct PointStructList {
+        #[prost(message, repeated, tag = "1")]
+        pub points: ::prost::alloc::vec::Vec<super::PointStruct>,
+        /// Option for custom sharding to specify used shard keys
+        #[prost(message, optional, tag = "2")]
+        pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// If specified, only points that match this filter will be updated, others will be inserted
+        #[prost(message, optional, tag = "3")]
+        pub update_filter: ::core::option::Option<super::Filter>,
+        /// If true, points with vectors and payload identical to the stored ones are skipped. Re-upserting unchanged points then does not create new versions of them.
+        #[prost(bool, optional, tag = "4")]
+        pub skip_unchanged: ::core::option::Option<bool>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct SetPayload {
         #[prost(map = "string, message", tag = "1")]
@@ -6384,6 +6881,15 @@ pub struct QueryResponse {
     pub time: f64,
     #[prost(message, optional, tag = "3")]
     pub usage: ::core::option::Option<Usage>,
+    /// Some search ran out of its `max_scored_points` budget
+    #[prost(bool, optional, tag = "4")]
+    pub truncated: ::core::option::Option<bool>,
+    /// Some shards did not respond, only set if partial results are allowed
+    #[prost(bool, optional, tag = "5")]
+    pub degraded: ::core::option::Option<bool>,
+    /// Shards which did not respond, only set if results are degraded
+    #[prost(uint32, repeated, tag = "6")]
+    pub unreachable_shards: ::prost::alloc::vec::Vec<u32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6396,6 +6902,15 @@ pub struct QueryBatchResponse {
     pub time: f64,
     #[prost(message, optional, tag = "3")]
     pub usage: ::core::option::Option<Usage>,
+    /// Some search ran out of its `max_scored_points` budget
+    #[prost(bool, optional, tag = "4")]
+    pub truncated: ::core::option::Option<bool>,
+    /// Some shards did not respond, only set if partial results are allowed
+    #[prost(bool, optional, tag = "5")]
+    pub degraded: ::core::option::Option<bool>,
+    /// Shards which did not respond, only set if results are degraded
+    #[prost(uint32, repeated, tag = "6")]
+    pub unreachable_shards: ::prost::alloc::vec::Vec<u32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6506,6 +7021,18 @@ pub struct GetResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExistsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<ExistsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<Usage>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RecommendResponse {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
@@ -6611,6 +7138,29 @@ pub struct SearchMatrixOffsetsResponse {
     #[prost(message, optional, tag = "3")]
     pub usage: ::core::option::Option<Usage>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallProbeResponse {
+    /// First evaluation of the recall, absent if the probe was stopped
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<RecallProbeResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<AggregateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<Usage>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6649,7 +7199,7 @@ pub struct MinShould {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     #[validate(nested)]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -6673,6 +7223,8 @@ pub mod condition {
         Nested(super::NestedCondition),
         #[prost(message, tag = "7")]
         HasVector(super::HasVectorCondition),
+        #[prost(message, tag = "8")]
+        Wasm(super::WasmCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -6719,6 +7271,17 @@ pub struct NestedCondition {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WasmCondition {
+    /// Name of the registered condition module
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Payload field to check. If not set - the whole payload is checked
+    #[prost(string, optional, tag = "2")]
+    pub key: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FieldCondition {
     #[prost(string, tag = "1")]
     pub key: ::prost::alloc::string::String,
@@ -6754,7 +7317,7 @@ pub struct FieldCondition {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -6793,6 +7356,9 @@ pub mod r#match {
         /// Match any word in the text
         #[prost(string, tag = "10")]
         TextAny(::prost::alloc::string::String),
+        /// Match keyword by a glob pattern
+        #[prost(string, tag = "11")]
+        Pattern(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -8040,6 +8606,84 @@ pub mod points_client {
                 .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
             self.inner.unary(req, path, codec).await
         }
+        /// Sample random points as queries, compute their exact nearest neighbours and keep evaluating the recall of regular searches against them in background
+        pub async fn recall_probe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecallProbePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::RecallProbeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/RecallProbe",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "RecallProbe"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Compute count, sum, average, min, max and percentiles of values of a numeric payload key over points that satisfy the given filter.
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/Aggregate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "Aggregate"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Check which of the given points exist, without reading their payloads or vectors
+        pub async fn exists(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExistsPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExistsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/Exists",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "Exists"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -8269,6 +8913,30 @@ pub mod points_server {
             tonic::Response<super::SearchMatrixOffsetsResponse>,
             tonic::Status,
         >;
+        /// Sample random points as queries, compute their exact nearest neighbours and keep evaluating the recall of regular searches against them in background
+        async fn recall_probe(
+            &self,
+            request: tonic::Request<super::RecallProbePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::RecallProbeResponse>,
+            tonic::Status,
+        >;
+        /// Compute count, sum, average, min, max and percentiles of values of a numeric payload key over points that satisfy the given filter.
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponse>,
+            tonic::Status,
+        >;
+        /// Check which of the given points exist, without reading their payloads or vectors
+        async fn exists(
+            &self,
+            request: tonic::Request<super::ExistsPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExistsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -9603,6 +10271,144 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/RecallProbe" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecallProbeSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::RecallProbePoints>
+                    for RecallProbeSvc<T> {
+                        type Response = super::RecallProbeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecallProbePoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::recall_probe(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RecallProbeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Aggregate" => {
+                    #[allow(non_camel_case_types)]
+                    struct AggregateSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::AggregatePoints>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AggregatePoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::aggregate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AggregateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Exists" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExistsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ExistsPoints>
+                    for ExistsSvc<T> {
+                        type Response = super::ExistsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExistsPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::exists(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExistsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -10333,6 +11139,9 @@ pub struct QueryBatchResponseInternal {
     pub hardware_usage: ::core::option::Option<HardwareUsage>,
     #[prost(message, optional, tag = "6")]
     pub inference_usage: ::core::option::Option<InferenceUsage>,
+    /// Some search ran out of its `max_scored_points` budget
+    #[prost(bool, optional, tag = "7")]
+    pub truncated: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -10403,6 +11212,73 @@ pub struct FacetResponseInternal {
     #[prost(message, optional, tag = "3")]
     pub usage: ::core::option::Option<HardwareUsage>,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointHistoryInternal {
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(uint32, tag = "3")]
+    pub shard_id: u32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointHistoryResponseInternal {
+    /// Recorded changes of the point, the newest first, serialized with MessagePack
+    #[prost(bytes = "vec", tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<u8>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+    #[prost(uint32, tag = "3")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(range(min = 1))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionInternal {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Number of the last operation, which changed the point
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersionsResponseInternal {
+    /// Versions of the requested points, which exist in the shard
+    #[prost(message, repeated, tag = "1")]
+    pub points: ::prost::alloc::vec::Vec<PointVersionInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -10953,6 +11829,56 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn point_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointHistoryInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointHistoryResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PointHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PointHistory"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn point_versions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointVersionsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointVersionsResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PointVersions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PointVersions"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -11086,6 +12012,20 @@ pub mod points_internal_server {
             tonic::Response<super::FacetResponseInternal>,
             tonic::Status,
         >;
+        async fn point_history(
+            &self,
+            request: tonic::Request<super::PointHistoryInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointHistoryResponseInternal>,
+            tonic::Status,
+        >;
+        async fn point_versions(
+            &self,
+            request: tonic::Request<super::PointVersionsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointVersionsResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -12050,6 +12990,98 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/PointHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct PointHistorySvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PointHistoryInternal>
+                    for PointHistorySvc<T> {
+                        type Response = super::PointHistoryResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointHistoryInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::point_history(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PointHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/PointVersions" => {
+                    #[allow(non_camel_case_types)]
+                    struct PointVersionsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PointVersionsInternal>
+                    for PointVersionsSvc<T> {
+                        type Response = super::PointVersionsResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointVersionsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::point_versions(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PointVersionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -48,6 +48,136 @@ pub struct ApiResponse<D> {
     pub time: f64,
     #[serde(skip_serializing_if = "is_usage_none_or_empty")]
     pub usage: Option<Usage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_details: Option<ErrorDetails>,
+}
+
+/// Machine readable class of an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadInput,
+    BadRequest,
+    NotFound,
+    AlreadyExists,
+    Forbidden,
+    Locked,
+    Timeout,
+    RateLimited,
+    ShardUnavailable,
+    PreconditionFailed,
+    ChecksumMismatch,
+    InferenceFailed,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadInput => "bad_input",
+            ErrorCode::BadRequest => "bad_request",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::AlreadyExists => "already_exists",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::Locked => "locked",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::RateLimited => "rate_limited",
+            ErrorCode::ShardUnavailable => "shard_unavailable",
+            ErrorCode::PreconditionFailed => "precondition_failed",
+            ErrorCode::ChecksumMismatch => "checksum_mismatch",
+            ErrorCode::InferenceFailed => "inference_failed",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    /// Whether the same request may succeed, if retried later
+    pub fn is_retryable(self) -> bool {
+        match self {
+            ErrorCode::Timeout | ErrorCode::RateLimited | ErrorCode::ShardUnavailable => true,
+            ErrorCode::BadInput
+            | ErrorCode::BadRequest
+            | ErrorCode::NotFound
+            | ErrorCode::AlreadyExists
+            | ErrorCode::Forbidden
+            | ErrorCode::Locked
+            | ErrorCode::PreconditionFailed
+            | ErrorCode::ChecksumMismatch
+            | ErrorCode::InferenceFailed
+            | ErrorCode::Internal => false,
+        }
+    }
+}
+
+/// Structured description of an error, to handle it programmatically
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ErrorDetails {
+    pub code: ErrorCode,
+    /// Whether the same request may succeed, if retried later
+    pub retryable: bool,
+    /// Request parameter which caused the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<String>,
+    /// Shard which caused the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_id: Option<u32>,
+    /// Peer on which the error happened, if it was not the peer serving the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<u64>,
+}
+
+impl ErrorDetails {
+    /// gRPC metadata keys, carrying error details in `tonic::Status`
+    pub const CODE_METADATA_KEY: &str = "qdrant-error-code";
+    pub const RETRYABLE_METADATA_KEY: &str = "qdrant-retryable";
+    pub const PARAMETER_METADATA_KEY: &str = "qdrant-error-parameter";
+    pub const SHARD_ID_METADATA_KEY: &str = "qdrant-shard-id";
+    pub const PEER_ID_METADATA_KEY: &str = "qdrant-peer-id";
+
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            retryable: code.is_retryable(),
+            parameter: None,
+            shard_id: None,
+            peer_id: None,
+        }
+    }
+
+    pub fn with_shard_id(mut self, shard_id: u32) -> Self {
+        self.shard_id = Some(shard_id);
+        self
+    }
+
+    pub fn with_peer_id(mut self, peer_id: u64) -> Self {
+        self.peer_id = Some(peer_id);
+        self
+    }
+
+    /// Details as gRPC metadata, key-value pairs
+    pub fn to_metadata(&self) -> Vec<(&'static str, String)> {
+        let Self {
+            code,
+            retryable,
+            parameter,
+            shard_id,
+            peer_id,
+        } = self;
+        let mut metadata = vec![
+            (Self::CODE_METADATA_KEY, code.as_str().to_string()),
+            (Self::RETRYABLE_METADATA_KEY, retryable.to_string()),
+        ];
+        if let Some(parameter) = parameter {
+            metadata.push((Self::PARAMETER_METADATA_KEY, parameter.clone()));
+        }
+        if let Some(shard_id) = shard_id {
+            metadata.push((Self::SHARD_ID_METADATA_KEY, shard_id.to_string()));
+        }
+        if let Some(peer_id) = peer_id {
+            metadata.push((Self::PEER_ID_METADATA_KEY, peer_id.to_string()));
+        }
+        metadata
+    }
 }
 
 /// Usage of the hardware resources, spent to process the request
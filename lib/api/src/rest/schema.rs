@@ -602,6 +602,17 @@ pub struct QueryRequestBatch {
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct QueryResponse {
     pub points: Vec<ScoredPoint>,
+    /// Set if partial results were allowed and some shards did not respond.
+    /// Points of those shards are missing in the result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degraded: Option<bool>,
+    /// Shards which did not respond, only set if results are degraded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unreachable_shards: Option<Vec<u32>>,
+    /// Set if some search ran out of its `max_scored_points` budget.
+    /// Points are the best ones found within the budget, better matches may exist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -627,6 +638,9 @@ pub enum Query {
     /// Return points that live in positive areas.
     Context(ContextQuery),
 
+    /// Find the nearest neighbors to a weighted sum of vectors.
+    Combine(CombineQuery),
+
     /// Order the points by a payload field.
     OrderBy(OrderByQuery),
 
@@ -677,6 +691,15 @@ pub struct ContextQuery {
     pub context: ContextInput,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct CombineQuery {
+    /// Vectors to sum up, each one multiplied by its weight.
+    /// Use negative weights to subtract vectors, e.g. `vector(a) - vector(b) + vector(c)`.
+    #[validate(nested, length(min = 1))]
+    pub combine: Vec<WeightedVectorInput>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct OrderByQuery {
@@ -826,6 +849,16 @@ pub struct DiscoverInput {
     pub context: Option<Vec<ContextPair>>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct WeightedVectorInput {
+    /// Vector or id of a point to take the vector from
+    #[validate(nested)]
+    pub vector: VectorInput,
+
+    /// Multiplier of the vector in the sum. Default: 1.0
+    pub weight: Option<f32>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ContextInput(
     /// Search space will be constrained by these pairs of vectors
@@ -1281,6 +1314,75 @@ pub struct SearchMatrixPairsResponse {
     pub pairs: Vec<SearchMatrixPair>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallProbeRequest {
+    /// Look only for points which satisfies this conditions
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// How many random points to use as queries. Default is 100. 0 stops the probe.
+    #[validate(range(max = 10000))]
+    pub sample: Option<usize>,
+    /// How many neighbours per query to compare. Default is 10.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<VectorNameBuf>,
+    /// How often to evaluate recall, in seconds. Default is 60.
+    #[validate(range(min = 1))]
+    pub interval: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Validate, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreHistogramRequestInternal {
+    /// Vector to score the points against
+    #[validate(nested)]
+    pub vector: NamedVectorStruct,
+    /// Look only for points which satisfies this conditions
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// How many random points to score. Default is 1000.
+    #[validate(range(min = 1))]
+    pub sample: Option<usize>,
+    /// Number of equal-width buckets between the lowest and the highest score. Default is 10.
+    #[validate(range(min = 1, max = 1000))]
+    pub buckets: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreHistogramRequest {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub score_histogram_request: ScoreHistogramRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreHistogramResponse {
+    /// Number of scored points
+    pub sampled: usize,
+    /// Lowest score among the scored points
+    pub min_score: Option<ScoreType>,
+    /// Highest score among the scored points
+    pub max_score: Option<ScoreType>,
+    /// Buckets in ascending order of scores
+    pub buckets: Vec<ScoreHistogramBucket>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// Number of points with scores in `[from, to)`, the last bucket includes `to`
+pub struct ScoreHistogramBucket {
+    pub from: ScoreType,
+    pub to: ScoreType,
+    pub count: usize,
+}
+
 #[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
 pub struct FacetRequestInternal {
     /// Payload key to use for faceting.
@@ -1326,6 +1428,54 @@ pub struct FacetResponse {
     pub hits: Vec<FacetValueHit>,
 }
 
+#[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct AggregateRequestInternal {
+    /// Numeric payload key to aggregate values of.
+    pub key: JsonPath,
+
+    /// Filter conditions - only consider points that satisfy these conditions.
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+
+    /// Percentiles of values to compute, each in range `[0, 100]`. Default is none.
+    /// Percentiles of more than 10000 values are estimated from a random sample of them.
+    #[validate(custom(function = "super::validate::validate_percentiles"))]
+    pub percentiles: Option<Vec<f64>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct AggregateRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub aggregate_request: AggregateRequestInternal,
+
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema, PartialEq)]
+pub struct AggregateResponse {
+    /// Number of aggregated values. Each value of an array counts separately.
+    pub count: usize,
+    /// Sum of the values
+    pub sum: f64,
+    /// Average of the values, absent if there are no values
+    pub avg: Option<f64>,
+    /// Lowest of the values, absent if there are no values
+    pub min: Option<f64>,
+    /// Highest of the values, absent if there are no values
+    pub max: Option<f64>,
+    /// Requested percentiles of the values, in the order of the request
+    pub percentiles: Vec<AggregatePercentile>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+pub struct AggregatePercentile {
+    pub percentile: f64,
+    /// Value of the percentile, linearly interpolated between the closest values.
+    /// Absent if there are no values.
+    pub value: Option<f64>,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointStruct {
@@ -1350,6 +1500,10 @@ pub struct PointsBatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate(nested)]
     pub update_filter: Option<Filter>,
+    /// If true, points with vectors and payload identical to the stored ones are skipped.
+    /// Re-upserting unchanged points then does not create new versions of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_unchanged: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -1384,6 +1538,10 @@ pub struct PointsList {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate(nested)]
     pub update_filter: Option<Filter>,
+    /// If true, points with vectors and payload identical to the stored ones are skipped.
+    /// Re-upserting unchanged points then does not create new versions of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_unchanged: Option<bool>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -9,6 +9,19 @@ use super::{
     OrderByInterface, PointVectors, Query, QueryInterface, RecommendInput, Sample, VectorInput,
 };
 
+/// Validate that each percentile is in range `[0, 100]`
+pub fn validate_percentiles(percentiles: &[f64]) -> Result<(), ValidationError> {
+    if percentiles
+        .iter()
+        .all(|percentile| (0.0..=100.0).contains(percentile))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("range")
+            .with_message(Cow::Borrowed("percentiles must be in range [0, 100]")))
+    }
+}
+
 impl Validate for NamedVectorStruct {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -35,6 +48,7 @@ impl Validate for Query {
             Query::Recommend(recommend) => recommend.validate(),
             Query::Discover(discover) => discover.validate(),
             Query::Context(context) => context.validate(),
+            Query::Combine(combine) => combine.validate(),
             Query::Fusion(fusion) => fusion.validate(),
             Query::Rrf(rrf) => rrf.validate(),
             Query::Formula(formula) => formula.validate(),
@@ -43,6 +43,8 @@ fn setup() -> (TempDir, LocalShard, Runtime) {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let collection_params = CollectionParams {
@@ -69,6 +71,7 @@ fn setup() -> (TempDir, LocalShard, Runtime) {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -0,0 +1,268 @@
+use std::time::{Duration, Instant};
+
+use api::rest::{AggregatePercentile, AggregateRequestInternal, AggregateResponse};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::json_path::JsonPath;
+use segment::types::{Filter, PayloadContainer, WithPayloadInterface, WithVector};
+use serde_json::Value;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionResult, ScrollRequestInternal};
+
+/// Number of points to read per page, while streaming payloads of matched points
+const AGGREGATE_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of values kept to compute percentiles
+///
+/// Percentiles of more values are estimated from a uniform random sample of this size.
+const PERCENTILES_SAMPLE_SIZE: usize = 10_000;
+
+/// Internal representation of the aggregate request, used to convert from REST.
+pub struct CollectionAggregateRequest {
+    pub key: JsonPath,
+    pub filter: Option<Filter>,
+    pub percentiles: Vec<f64>,
+}
+
+impl From<AggregateRequestInternal> for CollectionAggregateRequest {
+    fn from(request: AggregateRequestInternal) -> Self {
+        let AggregateRequestInternal {
+            key,
+            filter,
+            percentiles,
+        } = request;
+        Self {
+            key,
+            filter,
+            percentiles: percentiles.unwrap_or_default(),
+        }
+    }
+}
+
+/// Accumulates numeric values, keeping a sample of them only if percentiles are requested
+#[derive(Default)]
+struct Aggregator {
+    count: usize,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    /// Reservoir sample of up to [`PERCENTILES_SAMPLE_SIZE`] values
+    values: Option<Vec<f64>>,
+}
+
+impl Aggregator {
+    fn new(keep_values: bool) -> Self {
+        Self {
+            values: keep_values.then(Vec::new),
+            ..Default::default()
+        }
+    }
+
+    /// Add numeric values of a payload value, other values are ignored
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Array(values) => values.iter().for_each(|value| self.add(value)),
+            value => {
+                if let Some(value) = value.as_f64() {
+                    self.add_number(value);
+                }
+            }
+        }
+    }
+
+    fn add_number(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        if let Some(values) = &mut self.values {
+            if values.len() < PERCENTILES_SAMPLE_SIZE {
+                values.push(value);
+            } else {
+                // Each of the values seen so far stays in the sample with equal probability
+                let index = rand::random_range(0..self.count);
+                if let Some(sampled) = values.get_mut(index) {
+                    *sampled = value;
+                }
+            }
+        }
+    }
+
+    fn finish(self, percentiles: &[f64]) -> AggregateResponse {
+        let Self {
+            count,
+            sum,
+            min,
+            max,
+            values,
+        } = self;
+
+        let mut values = values.unwrap_or_default();
+        values.sort_unstable_by(f64::total_cmp);
+
+        let percentiles = percentiles
+            .iter()
+            .map(|&percentile| AggregatePercentile {
+                percentile,
+                value: interpolate_percentile(&values, percentile),
+            })
+            .collect();
+
+        AggregateResponse {
+            count,
+            sum,
+            avg: (count > 0).then(|| sum / count as f64),
+            min,
+            max,
+            percentiles,
+        }
+    }
+}
+
+/// Percentile of sorted values, linearly interpolated between the closest ranks
+fn interpolate_percentile(sorted_values: &[f64], percentile: f64) -> Option<f64> {
+    let last = sorted_values.len().checked_sub(1)?;
+    let rank = percentile.clamp(0.0, 100.0) / 100.0 * last as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as f64;
+    Some(sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * fraction)
+}
+
+impl Collection {
+    /// Aggregate values of a numeric payload field over points matching the filter
+    ///
+    /// Payloads of matched points are streamed page by page, reading only the aggregated field.
+    pub async fn aggregate(
+        &self,
+        request: CollectionAggregateRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<AggregateResponse> {
+        let start = Instant::now();
+        let CollectionAggregateRequest {
+            key,
+            filter,
+            percentiles,
+        } = request;
+
+        let mut aggregator = Aggregator::new(!percentiles.is_empty());
+        let mut offset = None;
+
+        loop {
+            let scroll_request = ScrollRequestInternal {
+                offset,
+                after_id: None,
+                limit: Some(AGGREGATE_PAGE_SIZE),
+                filter: filter.clone(),
+                with_payload: Some(WithPayloadInterface::Fields(vec![key.clone()])),
+                with_vector: WithVector::Bool(false),
+                order_by: None,
+            };
+
+            // update timeout
+            let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+
+            let page = self
+                .scroll_by(
+                    scroll_request,
+                    read_consistency,
+                    &shard_selection,
+                    timeout,
+                    hw_measurement_acc.clone(),
+                )
+                .await?;
+
+            for payload in page
+                .points
+                .iter()
+                .filter_map(|point| point.payload.as_ref())
+            {
+                for value in payload.get_value(&key) {
+                    aggregator.add(value);
+                }
+            }
+
+            match page.next_page_offset {
+                Some(next_page_offset) => offset = Some(next_page_offset),
+                None => break,
+            }
+        }
+
+        Ok(aggregator.finish(&percentiles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregator() {
+        let mut aggregator = Aggregator::new(true);
+        aggregator.add(&json!(4));
+        aggregator.add(&json!([1, 2.5]));
+        aggregator.add(&json!("not a number"));
+        aggregator.add(&json!(-0.5));
+
+        let response = aggregator.finish(&[0.0, 50.0, 100.0]);
+        assert_eq!(response.count, 4);
+        assert_eq!(response.sum, 7.0);
+        assert_eq!(response.avg, Some(1.75));
+        assert_eq!(response.min, Some(-0.5));
+        assert_eq!(response.max, Some(4.0));
+        assert_eq!(
+            response.percentiles,
+            vec![
+                AggregatePercentile {
+                    percentile: 0.0,
+                    value: Some(-0.5),
+                },
+                AggregatePercentile {
+                    percentile: 50.0,
+                    value: Some(1.75),
+                },
+                AggregatePercentile {
+                    percentile: 100.0,
+                    value: Some(4.0),
+                },
+            ],
+        );
+
+        let response = Aggregator::new(true).finish(&[50.0]);
+        assert_eq!(response.count, 0);
+        assert_eq!(response.avg, None);
+        assert_eq!(response.percentiles[0].value, None);
+    }
+
+    #[test]
+    fn test_aggregator_samples_percentiles() {
+        let count = PERCENTILES_SAMPLE_SIZE * 10;
+
+        let mut aggregator = Aggregator::new(true);
+        for value in 0..count {
+            aggregator.add_number(value as f64);
+        }
+        assert_eq!(
+            aggregator.values.as_ref().map(Vec::len),
+            Some(PERCENTILES_SAMPLE_SIZE),
+        );
+
+        let response = aggregator.finish(&[50.0, 90.0]);
+        assert_eq!(response.count, count);
+        assert_eq!(response.min, Some(0.0));
+        assert_eq!(response.max, Some((count - 1) as f64));
+
+        // Sampled percentiles are close to the exact ones
+        for (percentile, expected) in response.percentiles.iter().zip([0.5, 0.9]) {
+            let value = percentile.value.unwrap() / count as f64;
+            assert!((value - expected).abs() < 0.02, "{percentile:?}");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
@@ -7,6 +8,7 @@ use indexmap::IndexSet;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use segment::data_types::vectors::{QueryVector, VectorInternal, VectorRef};
+use segment::spaces::wasm_metric::WasmMetricModule;
 use segment::types::{ScoredPoint, VectorNameBuf};
 use segment::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
 use segment::vector_storage::multi_dense::volatile_multi_dense_vector_storage::new_volatile_multi_dense_vector_storage;
@@ -26,6 +28,7 @@ use crate::operations::universal_query::shard_query::MmrInternal;
 /// # Arguments
 ///
 /// * `collection_params` - The parameters of the collection. Used to determine the right distance metric, or multivec config.
+/// * `custom_metric` - The custom metric module of the collection, used by custom distance.
 /// * `points_with_vector` - The points with vectors.
 /// * `mmr` - The MMR parameters.
 /// * `limit` - The maximum number of points to return.
@@ -38,6 +41,7 @@ use crate::operations::universal_query::shard_query::MmrInternal;
 /// A vector of scored points.
 pub async fn mmr_from_points_with_vector(
     collection_params: &CollectionParams,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     points_with_vector: impl IntoIterator<Item = ScoredPoint>,
     mmr: MmrInternal,
     limit: usize,
@@ -67,6 +71,7 @@ pub async fn mmr_from_points_with_vector(
 
     let volatile_storage = create_volatile_storage(
         collection_params,
+        custom_metric,
         &vectors,
         mmr.using,
         hw_measurement_acc.get_counter_cell(),
@@ -106,6 +111,7 @@ pub async fn mmr_from_points_with_vector(
 /// Creates a volatile (in-memory and not persistent) vector storage and inserts the vectors in the provided order.
 fn create_volatile_storage(
     collection_params: &CollectionParams,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     vectors: &[VectorInternal],
     using: VectorNameBuf,
     hw_counter: HardwareCounterCell,
@@ -133,6 +139,8 @@ fn create_volatile_storage(
         }
     };
 
+    volatile_storage.set_custom_metric(custom_metric);
+
     // Populate storage with vectors
     for (key, vector) in (0..).zip(vectors) {
         volatile_storage.insert_vector(key, VectorRef::from(vector), &hw_counter)?;
@@ -465,6 +473,7 @@ mod tests {
 
         let result = mmr_from_points_with_vector(
             &collection_params,
+            None,
             points.clone(),
             mmr,
             3,
@@ -509,6 +518,7 @@ mod tests {
 
         let result = mmr_from_points_with_vector(
             &collection_params,
+            None,
             empty_points,
             mmr.clone(),
             5,
@@ -530,6 +540,7 @@ mod tests {
 
         let result = mmr_from_points_with_vector(
             &collection_params,
+            None,
             single_point,
             mmr,
             5,
@@ -567,6 +578,7 @@ mod tests {
 
         let result = mmr_from_points_with_vector(
             &collection_params,
+            None,
             points,
             mmr,
             5,
@@ -608,6 +620,7 @@ mod tests {
 
         let result = mmr_from_points_with_vector(
             &collection_params,
+            None,
             points,
             mmr,
             5,
@@ -651,6 +664,7 @@ mod tests {
 
             let result = mmr_from_points_with_vector(
                 &collection_params,
+                None,
                 dense_points.clone(),
                 mmr.clone(),
                 3,
@@ -710,6 +724,7 @@ mod tests {
 
         let sparse_result = mmr_from_points_with_vector(
             &sparse_collection_params,
+            None,
             sparse_points,
             sparse_mmr,
             3,
@@ -761,6 +776,7 @@ mod tests {
 
             let multi_result = mmr_from_points_with_vector(
                 &multi_collection_params,
+                None,
                 multi_points.clone(),
                 multi_mmr.clone(),
                 3,
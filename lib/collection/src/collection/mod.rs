@@ -1,3 +1,4 @@
+pub mod aggregate;
 mod clean;
 mod collection_ops;
 pub mod distance_matrix;
@@ -6,8 +7,12 @@ pub mod mmr;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
+pub mod recall;
+pub mod recent_queries;
 mod resharding;
+pub mod score_histogram;
 mod search;
+pub mod search_shadowing;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
@@ -24,25 +29,33 @@ use common::budget::ResourceBudget;
 use common::save_on_disk::SaveOnDisk;
 use common::types::{DetailsLevel, TelemetryDetail};
 use io::storage_version::StorageVersion;
-use segment::types::ShardKey;
+use segment::spaces::wasm_metric;
+use segment::telemetry::QuantizationTelemetry;
+use segment::types::{PointIdType, ShardKey};
 use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::recall::RecallProbe;
+use crate::collection::recent_queries::RecentQueries;
+use crate::collection::search_shadowing::SearchShadowing;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::collection_size_stats::{
     CollectionSizeAtomicStats, CollectionSizeStats, CollectionSizeStatsCache,
 };
+use crate::common::fair_scheduler::{FairScheduler, FairSchedulerPermit};
 use crate::common::is_ready::IsReady;
 use crate::config::CollectionConfigInternal;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType, OptimizersStatus};
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
 use crate::shards::local_shard::clock_map::RecoveryPoint;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::replica_set::ReplicaState::{Active, Dead, Initializing, Listener};
 use crate::shards::replica_set::{
     ChangePeerFromState, ChangePeerState, ReplicaState, ShardReplicaSet,
@@ -50,6 +63,7 @@ use crate::shards::replica_set::{
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_holder::shard_mapping::ShardKeyMapping;
 use crate::shards::shard_holder::{LockedShardHolder, ShardHolder, shard_not_found_error};
+use crate::shards::telemetry::ShardHnswGraphStats;
 use crate::shards::transfer::helpers::check_transfer_conflicts_strict;
 use crate::shards::transfer::transfer_tasks_pool::{TaskResult, TransferTasksPool};
 use crate::shards::transfer::{ShardTransfer, ShardTransferMethod};
@@ -91,6 +105,14 @@ pub struct Collection {
     collection_stats_cache: CollectionSizeStatsCache,
     // Background tasks to clean shards
     shard_clean_tasks: ShardCleanTasks,
+    // Sampled exact-search ground truth and the last measured recall
+    recall_probe: parking_lot::Mutex<RecallProbe>,
+    // Fair scheduling of searches across shard keys, if enabled
+    search_scheduler: Option<Arc<FairScheduler>>,
+    // Sampled recent search queries, if enabled
+    recent_queries: Option<RecentQueries>,
+    // Shadowing of searches with an alternative setup, if enabled
+    search_shadowing: Option<Arc<SearchShadowing>>,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -120,6 +142,10 @@ impl Collection {
     ) -> CollectionResult<Self> {
         let start_time = std::time::Instant::now();
 
+        if let Some(custom_metric) = &collection_config.custom_metric {
+            wasm_metric::register(path, custom_metric)?;
+        }
+
         let mut shard_holder = ShardHolder::new(path)?;
         shard_holder.set_shard_key_mappings(shard_key_mapping.clone().unwrap_or_default())?;
 
@@ -173,6 +199,13 @@ impl Collection {
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
 
+        let search_scheduler = Self::new_search_scheduler(&shared_storage_config);
+        let recent_queries = shared_storage_config
+            .recent_queries
+            .clone()
+            .map(|config| RecentQueries::load(path, config));
+        let search_shadowing = Self::new_search_shadowing(&name, &shared_storage_config);
+
         Ok(Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
@@ -196,6 +229,10 @@ impl Collection {
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            recall_probe: parking_lot::Mutex::new(RecallProbe::load(path)),
+            search_scheduler,
+            recent_queries,
+            search_shadowing,
         })
     }
 
@@ -248,6 +285,15 @@ impl Collection {
         });
         collection_config.validate_and_warn();
 
+        if let Some(custom_metric) = &collection_config.custom_metric {
+            wasm_metric::register(path, custom_metric).unwrap_or_else(|err| {
+                panic!(
+                    "Can't use custom metric module of collection at {}: {err}",
+                    path.display(),
+                )
+            });
+        }
+
         let mut shard_holder = ShardHolder::new(path).expect("Can not create shard holder");
 
         let mut effective_optimizers_config = collection_config.optimizer_config.clone();
@@ -289,6 +335,13 @@ impl Collection {
             Self::estimate_collection_size_stats(&locked_shard_holder).await,
         );
 
+        let search_scheduler = Self::new_search_scheduler(&shared_storage_config);
+        let recent_queries = shared_storage_config
+            .recent_queries
+            .clone()
+            .map(|config| RecentQueries::load(path, config));
+        let search_shadowing = Self::new_search_shadowing(&collection_id, &shared_storage_config);
+
         Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
@@ -312,9 +365,45 @@ impl Collection {
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            recall_probe: parking_lot::Mutex::new(RecallProbe::load(path)),
+            search_scheduler,
+            recent_queries,
+            search_shadowing,
         }
     }
 
+    fn new_search_scheduler(
+        shared_storage_config: &SharedStorageConfig,
+    ) -> Option<Arc<FairScheduler>> {
+        shared_storage_config
+            .fair_search_concurrency
+            .map(|max_concurrent| Arc::new(FairScheduler::new(max_concurrent)))
+    }
+
+    fn new_search_shadowing(
+        name: &str,
+        shared_storage_config: &SharedStorageConfig,
+    ) -> Option<Arc<SearchShadowing>> {
+        shared_storage_config
+            .search_shadowing
+            .iter()
+            .find(|config| config.collection == name)
+            .map(|config| Arc::new(SearchShadowing::new(config.clone())))
+    }
+
+    /// Acquire a turn to run searches with the given shard selection, if scheduling is enabled
+    pub(crate) async fn search_turn(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+    ) -> Option<FairSchedulerPermit> {
+        let scheduler = self.search_scheduler.as_ref()?;
+        Some(
+            scheduler
+                .acquire(FairScheduler::tenant_key(shard_selection))
+                .await,
+        )
+    }
+
     /// Check if stored version have consequent version.
     /// If major version is different, then it is not compatible.
     /// If the difference in consecutive versions is greater than 1 in patch,
@@ -802,6 +891,7 @@ impl Collection {
         };
 
         let shard_clean_tasks = self.clean_local_shards_statuses();
+        let quantization = self.get_quantization_telemetry().await;
 
         CollectionTelemetry {
             id: self.name(),
@@ -811,7 +901,93 @@ impl Collection {
             transfers,
             resharding,
             shard_clean_tasks: (!shard_clean_tasks.is_empty()).then_some(shard_clean_tasks),
+            recall: self.recall_telemetry(),
+            search_scheduler: self
+                .search_scheduler
+                .as_ref()
+                .map(|scheduler| scheduler.get_telemetry_data()),
+            search_shadowing: self.search_shadowing_telemetry(),
+            quantization: (!quantization.is_empty()).then_some(quantization),
+        }
+    }
+
+    /// Quantization telemetry of local shard replicas, aggregated by vector name
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut telemetry = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            telemetry.extend(replica_set.get_quantization_telemetry().await);
+        }
+        QuantizationTelemetry::aggregate(telemetry)
+    }
+
+    /// Structural statistics of HNSW graphs of local shard replicas.
+    ///
+    /// Walks every graph, so it is expensive and only meant for debugging.
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<ShardHnswGraphStats> {
+        let shards_holder = self.shards_holder.read().await;
+        let mut shards_stats = Vec::new();
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(segments) = replica_set.get_hnsw_graph_stats().await {
+                shards_stats.push(ShardHnswGraphStats { shard_id, segments });
+            }
         }
+        shards_stats.sort_unstable_by_key(|stats| stats.shard_id);
+        shards_stats
+    }
+
+    /// Load vectors, quantized vectors and vector indexes of local shard replicas into
+    /// the page cache, so that first searches after a restart don't have to wait for the disk.
+    ///
+    /// If `lock` is true, their files are also locked in RAM, until warmed up again without
+    /// locking. Otherwise previously locked files are released.
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        let shards_holder = self.shards_holder.read().await;
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            replica_set.warmup(lock).await?;
+        }
+        Ok(())
+    }
+
+    /// Recorded changes of the point in all shards, the newest first
+    pub async fn point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<PointHistoryEntry>> {
+        if self
+            .collection_config
+            .read()
+            .await
+            .wal_config
+            .wal_point_history
+            == 0
+        {
+            return Err(CollectionError::bad_request(
+                "Point history is disabled, set wal_config.wal_point_history to enable it",
+            ));
+        }
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut entries = Vec::new();
+        for (_shard_id, replica_set) in shards_holder.get_shards() {
+            entries.extend(replica_set.point_history(point_id).await?);
+        }
+        // Point might have been moved between shards by resharding
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(entries)
+    }
+
+    /// Recorded changes of the point in the local replica of the shard, the newest first
+    pub async fn local_point_history(
+        &self,
+        shard_id: ShardId,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<PointHistoryEntry>> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        replica_set.local_point_history(point_id).await
     }
 
     pub async fn get_aggregated_telemetry_data(&self) -> CollectionsAggregatedTelemetry {
@@ -877,6 +1053,15 @@ impl Collection {
         self.shards_holder.read().await.trigger_optimizers().await;
     }
 
+    /// Persist in-memory state, which is otherwise saved only periodically
+    ///
+    /// Meant to be called on shutdown, before the collection is dropped.
+    pub async fn before_drop(&self) {
+        if let Some(recent_queries) = &self.recent_queries {
+            recent_queries.flush().await;
+        }
+    }
+
     async fn estimate_collection_size_stats(
         shards_holder: &Arc<RwLock<ShardHolder>>,
     ) -> Option<CollectionSizeStats> {
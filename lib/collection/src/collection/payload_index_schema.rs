@@ -95,6 +95,10 @@ impl Collection {
         self.payload_index_schema.read().schema.get(key).cloned()
     }
 
+    pub fn payload_index_schema(&self) -> PayloadIndexSchema {
+        self.payload_index_schema.read().clone()
+    }
+
     /// Returns an arbitrary payload key along with acceptable
     /// schemas used by `filter` which can be indexed but currently is not.
     /// If this function returns `None` all indexable keys in `filter` are indexed.
@@ -7,7 +7,7 @@ use futures::stream::FuturesUnordered;
 use futures::{StreamExt as _, TryFutureExt, TryStreamExt as _, future};
 use itertools::Itertools;
 use segment::data_types::order_by::{Direction, OrderBy};
-use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
+use segment::types::{PointIdType, ShardKey, WithPayload, WithPayloadInterface};
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
@@ -266,6 +266,7 @@ impl Collection {
         let default_request = ScrollRequestInternal::default();
 
         let id_offset = request.offset;
+        let after_id = request.after_id;
         let mut limit = request
             .limit
             .unwrap_or_else(|| default_request.limit.unwrap());
@@ -282,6 +283,18 @@ impl Collection {
             return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `order_by.start_from` and a filter to exclude the IDs that you've already seen for the `order_by.start_from` value".to_string()));
         };
 
+        if order_by.is_some() && after_id.is_some() {
+            return Err(CollectionError::bad_input(
+                "Cannot use `after_id` when using `order_by`".to_string(),
+            ));
+        }
+
+        if id_offset.is_some() && after_id.is_some() {
+            return Err(CollectionError::bad_input(
+                "Cannot use both `offset` and `after_id`".to_string(),
+            ));
+        }
+
         if limit == 0 {
             return Err(CollectionError::BadRequest {
                 description: "Limit cannot be 0".to_string(),
@@ -294,6 +307,15 @@ impl Collection {
             limit = limit.saturating_add(1);
         };
 
+        // Shards read points starting from the given ID inclusively,
+        // so fetch one more point to compensate for skipping `after_id` itself
+        let start_id = id_offset.or(after_id);
+        let shard_limit = if after_id.is_some() {
+            limit.saturating_add(1)
+        } else {
+            limit
+        };
+
         let local_only = shard_selection.is_shard_id();
 
         let retrieved_points: Vec<_> = {
@@ -304,8 +326,8 @@ impl Collection {
                 let shard_key = shard_key.cloned();
                 shard
                     .scroll_by(
-                        id_offset,
-                        limit,
+                        start_id,
+                        shard_limit,
                         &with_payload_interface,
                         &with_vector,
                         request.filter.as_ref(),
@@ -336,6 +358,7 @@ impl Collection {
                 .sorted_unstable_by_key(|point| point.id)
                 // Add each point only once, deduplicate point IDs
                 .dedup_by(|a, b| a.id == b.id)
+                .filter(|point| Some(point.id) != after_id)
                 .take(limit)
                 .map(api::rest::Record::from)
                 .collect_vec(),
@@ -478,4 +501,55 @@ impl Collection {
 
         Ok(points)
     }
+
+    /// Versions of the requested points, which exist in the collection, in the order of `ids`
+    ///
+    /// Existence is checked in id trackers of the shards, without reading payloads or vectors.
+    pub async fn point_versions(
+        &self,
+        ids: Vec<PointIdType>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = Arc::new(ids);
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(shard_selection)?;
+        let mut all_shard_requests = target_shards
+            .into_iter()
+            .map(|(shard, _shard_key)| {
+                shard.point_versions(
+                    ids.clone(),
+                    read_consistency,
+                    timeout,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // pre-allocate hashmap with capped capacity to protect from malevolent input
+        let mut versions = HashMap::with_capacity(ids.len().min(1024));
+        while let Some(response) = all_shard_requests.try_next().await? {
+            for PointVersion { id, version } in response {
+                // Point might be in two shards during resharding, keep the latest version
+                let latest = versions.entry(id).or_insert(version);
+                *latest = (*latest).max(version);
+            }
+        }
+
+        // Collect versions in the same order as points were requested
+        let versions = ids
+            .iter()
+            .filter_map(|&id| {
+                let version = versions.remove(&id)?;
+                Some(PointVersion { id, version })
+            })
+            .collect();
+
+        Ok(versions)
+    }
 }
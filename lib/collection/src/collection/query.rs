@@ -9,6 +9,7 @@ use rand::Rng;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::common::score_fusion::{ScoreFusion, score_fusion};
 use segment::data_types::vectors::VectorStructInternal;
+use segment::spaces::wasm_metric;
 use segment::types::{Order, ScoredPoint, WithPayloadInterface, WithVector};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
@@ -21,6 +22,7 @@ use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
     build_vector_resolver_queries, resolve_referenced_vectors_batch,
 };
+use crate::common::partial_results::PartialResults;
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::common::transpose_iterator::transposed_iter;
 use crate::operations::consistency_params::ReadConsistency;
@@ -61,6 +63,7 @@ impl Collection {
                 shard_selection,
                 timeout,
                 hw_measurement_acc,
+                None,
             )
             .await?;
         Ok(results.into_iter().next().unwrap())
@@ -143,7 +146,35 @@ impl Collection {
         Arc::new(new_requests)
     }
 
+    /// Scoring budget limits the whole request, so each shard gets an equal share of it
+    fn split_scoring_budget_between_shards(
+        batch_request: Arc<Vec<ShardQueryRequest>>,
+        num_shards: usize,
+    ) -> Arc<Vec<ShardQueryRequest>> {
+        if num_shards <= 1
+            || !batch_request
+                .iter()
+                .any(ShardQueryRequest::has_scoring_budget)
+        {
+            return batch_request;
+        }
+
+        let new_requests = batch_request
+            .iter()
+            .map(|request| {
+                let mut new_request = request.clone();
+                new_request.split_scoring_budget(num_shards);
+                new_request
+            })
+            .collect();
+
+        Arc::new(new_requests)
+    }
+
     /// Returns a shape of [shard_id, batch_id, intermediate_response, points]
+    ///
+    /// If `partial` is given, shards failing with a transient error are skipped and reported
+    /// there, instead of failing the whole request.
     async fn batch_query_shards_concurrently(
         &self,
         batch_request: Arc<Vec<ShardQueryRequest>>,
@@ -151,6 +182,7 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
+        partial: Option<&PartialResults>,
     ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
@@ -171,6 +203,8 @@ impl Collection {
             target_shards.len(),
             is_auto_sharding,
         );
+        let batch_request =
+            Self::split_scoring_budget_between_shards(batch_request, target_shards.len());
 
         let all_searches = target_shards.iter().map(|(shard, shard_key)| {
             let shard_key = shard_key.cloned();
@@ -196,7 +230,15 @@ impl Collection {
                     Ok(shard_responses)
                 })
         });
-        future::try_join_all(all_searches).await
+
+        match partial {
+            None => future::try_join_all(all_searches).await,
+            Some(partial) => {
+                let shard_ids = target_shards.iter().map(|(shard, _)| shard.shard_id);
+                let results = future::join_all(all_searches).await;
+                partial.collect(shard_ids.zip(results))
+            }
+        }
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
@@ -207,6 +249,7 @@ impl Collection {
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
+        partial: Option<&PartialResults>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let start = Instant::now();
 
@@ -251,6 +294,7 @@ impl Collection {
                     &shard_selection,
                     timeout,
                     hw_measurement_acc.clone(),
+                    partial,
                 )
                 .await?;
             // update timeout
@@ -277,6 +321,7 @@ impl Collection {
                 &shard_selection,
                 timeout,
                 hw_measurement_acc.clone(),
+                partial,
             )
             .await
         }
@@ -290,11 +335,13 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
+        partial: Option<&PartialResults>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let instant = Instant::now();
 
         let requests_batch = Arc::new(requests_batch);
 
+        let search_turn = self.search_turn(shard_selection).await;
         let all_shards_results = self
             .batch_query_shards_concurrently(
                 requests_batch.clone(),
@@ -302,8 +349,10 @@ impl Collection {
                 shard_selection,
                 timeout,
                 hw_measurement_acc.clone(),
+                partial,
             )
             .await?;
+        drop(search_turn);
 
         let results_f = transposed_iter(all_shards_results)
             .zip(requests_batch.iter())
@@ -381,6 +430,7 @@ impl Collection {
 
                 let mut mmr_result = mmr_from_points_with_vector(
                     &collection_params,
+                    wasm_metric::find(&self.path),
                     points_with_vector,
                     mmr.clone(),
                     *limit,
@@ -425,6 +475,8 @@ impl Collection {
     /// To be called on the user-responding instance. Resolves ids into vectors, and merges the results from local and remote shards.
     ///
     /// This function is used to query the collection. It will return a list of scored points.
+    ///
+    /// If `partial` is given, results of unresponsive shards are skipped and reported there.
     pub async fn query_batch<'a, F, Fut>(
         &self,
         requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
@@ -432,6 +484,7 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
+        partial: Option<&PartialResults>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
     where
         F: Fn(String) -> Fut,
@@ -493,6 +546,7 @@ impl Collection {
                     shard_selection,
                     timeout,
                     hw_measurement_acc.clone(),
+                    partial,
                 ));
 
                 Ok(())
@@ -530,6 +584,7 @@ impl Collection {
                 shard_selection,
                 timeout,
                 hw_measurement_acc,
+                None,
             )
             .await?;
 
@@ -0,0 +1,543 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::grpc::conversions::naive_date_time_to_proto;
+use api::rest::RecallProbeRequest;
+use chrono::{DateTime, Utc};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use io::file_operations::{atomic_save_json, read_json};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, VectorInternal};
+use segment::types::{
+    Condition, Filter, HasVectorCondition, PointIdType, ScoredPoint, SearchParams, VectorNameBuf,
+    WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::point_ops::VectorPersisted;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+use crate::operations::universal_query::collection_query::{
+    CollectionQueryRequest, Query, VectorInputInternal, VectorQuery,
+};
+use crate::operations::universal_query::shard_query::{
+    SampleInternal, ScoringQuery, ShardQueryRequest,
+};
+
+const RECALL_GROUND_TRUTH_FILE: &str = "recall_ground_truth.json";
+
+/// Internal representation of a request to (re)build the recall ground truth.
+pub struct CollectionRecallProbeRequest {
+    pub sample_size: usize,
+    pub limit: usize,
+    pub filter: Option<Filter>,
+    pub using: VectorNameBuf,
+    /// Interval of evaluating recall in background
+    pub interval: Duration,
+}
+
+impl CollectionRecallProbeRequest {
+    pub const DEFAULT_LIMIT: usize = 10;
+    pub const DEFAULT_SAMPLE: usize = 100;
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+}
+
+impl Default for CollectionRecallProbeRequest {
+    fn default() -> Self {
+        Self {
+            sample_size: Self::DEFAULT_SAMPLE,
+            limit: Self::DEFAULT_LIMIT,
+            filter: None,
+            using: DEFAULT_VECTOR_NAME.to_owned(),
+            interval: Self::DEFAULT_INTERVAL,
+        }
+    }
+}
+
+impl From<RecallProbeRequest> for CollectionRecallProbeRequest {
+    fn from(request: RecallProbeRequest) -> Self {
+        let RecallProbeRequest {
+            filter,
+            sample,
+            limit,
+            using,
+            interval,
+        } = request;
+        Self {
+            sample_size: sample.unwrap_or(CollectionRecallProbeRequest::DEFAULT_SAMPLE),
+            limit: limit.unwrap_or(CollectionRecallProbeRequest::DEFAULT_LIMIT),
+            filter,
+            using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+            interval: interval
+                .map(Duration::from_secs)
+                .unwrap_or(CollectionRecallProbeRequest::DEFAULT_INTERVAL),
+        }
+    }
+}
+
+/// Exact nearest neighbours of randomly sampled stored vectors.
+///
+/// Computed once with exact search, then used to evaluate the recall of the regular
+/// (approximate) search path over time. Saved in the collection directory, so the probe
+/// continues after a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecallGroundTruth {
+    using: VectorNameBuf,
+    limit: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filter: Option<Filter>,
+    interval: Duration,
+    samples: Vec<RecallSample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecallSample {
+    query: VectorPersisted,
+    exact_ids: Vec<PointIdType>,
+}
+
+/// State of the recall probe of a collection
+#[derive(Debug)]
+pub(crate) struct RecallProbe {
+    path: PathBuf,
+    /// Incremented whenever the ground truth is replaced or removed
+    generation: u64,
+    ground_truth: Option<RecallGroundTruth>,
+    last_evaluation: Option<RecallTelemetry>,
+    /// Generation of the saved ground truth, also serializes concurrent saves of the file
+    saved_generation: Arc<Mutex<u64>>,
+}
+
+impl RecallProbe {
+    pub fn load(collection_path: &Path) -> Self {
+        let path = collection_path.join(RECALL_GROUND_TRUTH_FILE);
+        let ground_truth = if path.exists() {
+            read_json(&path)
+                .inspect_err(|err| {
+                    log::warn!(
+                        "Failed to load recall ground truth from {}: {err}",
+                        path.display(),
+                    );
+                })
+                .ok()
+        } else {
+            None
+        };
+
+        Self {
+            path,
+            generation: 0,
+            ground_truth,
+            last_evaluation: None,
+            saved_generation: Default::default(),
+        }
+    }
+
+    /// Replace the ground truth, and save it on disk
+    ///
+    /// Returns the generation of the new ground truth.
+    async fn replace(
+        probe: &Mutex<Self>,
+        ground_truth: Option<RecallGroundTruth>,
+    ) -> CollectionResult<u64> {
+        let (path, saved_generation, generation) = {
+            let mut probe = probe.lock();
+            probe.generation += 1;
+            probe.ground_truth = ground_truth.clone();
+            probe.last_evaluation = None;
+            (
+                probe.path.clone(),
+                probe.saved_generation.clone(),
+                probe.generation,
+            )
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let mut saved_generation = saved_generation.lock();
+            // Newer ground truth is saved already
+            if *saved_generation >= generation {
+                return Ok(());
+            }
+            match &ground_truth {
+                Some(ground_truth) => atomic_save_json(&path, ground_truth)?,
+                None if path.exists() => std::fs::remove_file(&path)?,
+                None => {}
+            }
+            *saved_generation = generation;
+            CollectionResult::Ok(())
+        })
+        .await??;
+
+        Ok(generation)
+    }
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct RecallTelemetry {
+    /// Name of the probed vector
+    pub using: String,
+    /// Number of sampled queries in the ground truth
+    pub sample_size: usize,
+    /// Number of neighbours compared per query
+    pub limit: usize,
+    /// Average fraction of exact neighbours found by the approximate search, in `[0, 1]`
+    #[anonymize(false)]
+    pub recall: f64,
+    /// Time of the last evaluation
+    pub evaluated_at: DateTime<Utc>,
+}
+
+impl From<RecallTelemetry> for api::grpc::qdrant::RecallProbeResult {
+    fn from(telemetry: RecallTelemetry) -> Self {
+        let RecallTelemetry {
+            using,
+            sample_size,
+            limit,
+            recall,
+            evaluated_at,
+        } = telemetry;
+        Self {
+            using,
+            sample_size: sample_size as u64,
+            limit: limit as u64,
+            recall,
+            evaluated_at: Some(naive_date_time_to_proto(evaluated_at.naive_utc())),
+        }
+    }
+}
+
+/// Fraction of `exact` ids present in `approximate`
+///
+/// An empty exact result is considered to be fully recalled.
+fn sample_recall(exact: &[PointIdType], approximate: &[ScoredPoint]) -> f64 {
+    if exact.is_empty() {
+        return 1.0;
+    }
+    let found = exact
+        .iter()
+        .filter(|id| approximate.iter().any(|point| point.id == **id))
+        .count();
+    found as f64 / exact.len() as f64
+}
+
+impl Collection {
+    /// Sample random points and compute their exact nearest neighbours.
+    ///
+    /// The result replaces the previous ground truth of this collection and is used by
+    /// [`Collection::evaluate_recall`]. An empty sample or limit removes the ground truth.
+    ///
+    /// Returns the generation of the new ground truth.
+    pub async fn build_recall_ground_truth(
+        &self,
+        request: CollectionRecallProbeRequest,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<u64> {
+        let start = std::time::Instant::now();
+        let CollectionRecallProbeRequest {
+            sample_size,
+            limit,
+            filter,
+            using,
+            interval,
+        } = request;
+
+        if sample_size == 0 || limit == 0 {
+            return RecallProbe::replace(&self.recall_probe, None).await;
+        }
+
+        // make sure the vector is present in the sampled points
+        let has_vector = Filter::new_must(Condition::HasVector(HasVectorCondition::from(
+            using.clone(),
+        )));
+        let sampling_filter = filter
+            .as_ref()
+            .map(|filter| filter.merge(&has_vector))
+            .unwrap_or(has_vector);
+
+        let sampling_query = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(SampleInternal::Random)),
+            filter: Some(sampling_filter),
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.clone()]),
+            with_payload: Default::default(),
+        };
+
+        let sampled_points = self
+            .query(
+                sampling_query,
+                read_consistency,
+                ShardSelectorInternal::All,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+
+        let sampled_vectors: Vec<VectorInternal> = sampled_points
+            .into_iter()
+            .take(sample_size)
+            .filter_map(|point| {
+                point
+                    .vector
+                    .as_ref()
+                    .and_then(|vector| vector.get(&using))
+                    .map(|vector| vector.to_owned())
+            })
+            .collect();
+
+        let exact_params = SearchParams {
+            exact: true,
+            ..Default::default()
+        };
+
+        let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+        let exact_results = self
+            .recall_probe_queries(
+                &sampled_vectors,
+                &using,
+                limit,
+                filter.as_ref(),
+                Some(exact_params),
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        let samples: Vec<_> = sampled_vectors
+            .into_iter()
+            .zip(exact_results)
+            .map(|(query, points)| RecallSample {
+                query: VectorPersisted::from(query),
+                exact_ids: points.into_iter().map(|point| point.id).collect(),
+            })
+            .collect();
+
+        let ground_truth = RecallGroundTruth {
+            using,
+            limit,
+            filter,
+            interval,
+            samples,
+        };
+        RecallProbe::replace(&self.recall_probe, Some(ground_truth)).await
+    }
+
+    /// Run the ground truth queries through the regular search path and measure the recall.
+    ///
+    /// Returns `None` if the ground truth of the given generation was replaced or removed.
+    pub async fn evaluate_recall(
+        &self,
+        generation: u64,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Option<RecallTelemetry>> {
+        let ground_truth = {
+            let recall_probe = self.recall_probe.lock();
+            if recall_probe.generation != generation {
+                return Ok(None);
+            }
+            match &recall_probe.ground_truth {
+                Some(ground_truth) => ground_truth.clone(),
+                None => return Ok(None),
+            }
+        };
+
+        let RecallGroundTruth {
+            using,
+            limit,
+            filter,
+            interval: _,
+            samples,
+        } = ground_truth;
+
+        let queries: Vec<_> = samples
+            .iter()
+            .map(|sample| VectorInternal::from(sample.query.clone()))
+            .collect();
+        let approximate_results = self
+            .recall_probe_queries(
+                &queries,
+                &using,
+                limit,
+                filter.as_ref(),
+                None,
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        let recall = if samples.is_empty() {
+            1.0
+        } else {
+            samples
+                .iter()
+                .zip(&approximate_results)
+                .map(|(sample, approximate)| sample_recall(&sample.exact_ids, approximate))
+                .sum::<f64>()
+                / samples.len() as f64
+        };
+
+        let telemetry = RecallTelemetry {
+            using: using.to_string(),
+            sample_size: samples.len(),
+            limit,
+            recall,
+            evaluated_at: Utc::now(),
+        };
+
+        let mut recall_probe = self.recall_probe.lock();
+        if recall_probe.generation != generation {
+            return Ok(None);
+        }
+        recall_probe.last_evaluation = Some(telemetry.clone());
+
+        Ok(Some(telemetry))
+    }
+
+    /// Result of the most recent [`Collection::evaluate_recall`] call
+    pub fn recall_telemetry(&self) -> Option<RecallTelemetry> {
+        self.recall_probe.lock().last_evaluation.clone()
+    }
+
+    /// Generation and evaluation interval of the current ground truth, if there is one
+    pub fn recall_probe_schedule(&self) -> Option<(u64, Duration)> {
+        let recall_probe = self.recall_probe.lock();
+        recall_probe
+            .ground_truth
+            .as_ref()
+            .map(|ground_truth| (recall_probe.generation, ground_truth.interval))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn recall_probe_queries(
+        &self,
+        vectors: &[VectorInternal],
+        using: &VectorNameBuf,
+        limit: usize,
+        filter: Option<&Filter>,
+        params: Option<SearchParams>,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        if vectors.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let queries = vectors
+            .iter()
+            .map(|vector| {
+                let query = Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(
+                    vector.clone(),
+                )));
+                let request = CollectionQueryRequest {
+                    prefetch: vec![],
+                    query: Some(query),
+                    using: using.clone(),
+                    filter: filter.cloned(),
+                    score_threshold: None,
+                    limit,
+                    offset: 0,
+                    params,
+                    with_vector: WithVector::Bool(false),
+                    with_payload: WithPayloadInterface::Bool(false),
+                    lookup_from: None,
+                };
+                (request, ShardSelectorInternal::All)
+            })
+            .collect();
+
+        // We know by construction that lookup_from is not used in the queries
+        // so can use placeholder closure here
+        let collection_by_name = |_name: String| async move { None };
+
+        self.query_batch(
+            queries,
+            collection_by_name,
+            read_consistency,
+            timeout,
+            hw_measurement_acc,
+            None,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_sample_recall() {
+        let exact: Vec<PointIdType> = vec![1.into(), 2.into(), 3.into(), 4.into()];
+        let approximate = vec![
+            make_scored_point(1),
+            make_scored_point(3),
+            make_scored_point(5),
+            make_scored_point(6),
+        ];
+        assert_eq!(sample_recall(&exact, &approximate), 0.5);
+        assert_eq!(sample_recall(&[], &approximate), 1.0);
+        assert_eq!(sample_recall(&exact, &[]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_ground_truth_is_saved() {
+        let dir = tempfile::Builder::new()
+            .prefix("recall_probe")
+            .tempdir()
+            .unwrap();
+
+        let probe = Mutex::new(RecallProbe::load(dir.path()));
+        assert!(probe.lock().ground_truth.is_none());
+
+        let ground_truth = RecallGroundTruth {
+            using: DEFAULT_VECTOR_NAME.to_owned(),
+            limit: 2,
+            filter: None,
+            interval: Duration::from_secs(30),
+            samples: vec![RecallSample {
+                query: VectorPersisted::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+                exact_ids: vec![1.into(), 2.into()],
+            }],
+        };
+        RecallProbe::replace(&probe, Some(ground_truth))
+            .await
+            .unwrap();
+
+        let loaded = RecallProbe::load(dir.path()).ground_truth.unwrap();
+        assert_eq!(loaded.interval, Duration::from_secs(30));
+        assert_eq!(loaded.samples.len(), 1);
+        assert!(loaded.samples[0].query == VectorPersisted::Dense(vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(loaded.samples[0].exact_ids, vec![1.into(), 2.into()]);
+
+        // Removed ground truth is removed from disk as well
+        RecallProbe::replace(&probe, None).await.unwrap();
+        assert!(RecallProbe::load(dir.path()).ground_truth.is_none());
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use api::rest::{ShardKeySelector, VectorOutput};
+use chrono::{DateTime, Utc};
+use io::file_operations::{atomic_save_json, read_json};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::types::{Filter, SearchParams, VectorNameBuf};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection::Collection;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CoreSearchRequestBatch;
+use crate::operations::universal_query::collection_query::{
+    CollectionQueryRequest, Query, VectorInputInternal, VectorQuery,
+};
+
+const RECENT_QUERIES_FILE: &str = "recent_queries.json";
+
+/// Minimal interval between saving recent queries to disk
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+const fn default_sample_rate() -> f64 {
+    0.01
+}
+
+/// Configuration of recording recent search queries of collections
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct RecentQueriesConfig {
+    /// Maximum number of recent queries kept per collection
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+    /// Fraction of search queries to record
+    #[serde(default = "default_sample_rate")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub sample_rate: f64,
+}
+
+/// Nearest neighbours query, recorded to be replayed later
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct RecentQuery {
+    /// Time the query was received
+    pub timestamp: DateTime<Utc>,
+    /// Name of the queried vector
+    pub using: VectorNameBuf,
+    /// Query vector
+    pub vector: VectorOutput,
+    /// Filter of the query
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    /// Search params of the query
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<SearchParams>,
+    /// Number of requested results
+    pub limit: usize,
+    /// Shard keys the query was restricted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Bounded log of sampled recent queries of a collection, persisted on disk
+///
+/// Meant to replay realistic traffic into benchmarks and recall probes.
+/// Queries are saved at most once per [`SAVE_INTERVAL`], and flushed once more on shutdown, so
+/// the most recent ones survive restarts.
+#[derive(Debug)]
+pub(crate) struct RecentQueries {
+    config: RecentQueriesConfig,
+    path: PathBuf,
+    state: Mutex<RecentQueriesState>,
+    /// Generation of the saved queries, also serializes concurrent saves of the file
+    saved_generation: Arc<Mutex<u64>>,
+}
+
+#[derive(Debug)]
+struct RecentQueriesState {
+    queries: VecDeque<RecentQuery>,
+    /// Incremented whenever queries are recorded
+    generation: u64,
+    last_save: Instant,
+}
+
+impl RecentQueries {
+    pub fn load(collection_path: &Path, config: RecentQueriesConfig) -> Self {
+        let path = collection_path.join(RECENT_QUERIES_FILE);
+        let mut queries: VecDeque<RecentQuery> = if path.exists() {
+            read_json(&path).unwrap_or_else(|err| {
+                log::warn!(
+                    "Failed to load recent queries from {}: {err}",
+                    path.display()
+                );
+                VecDeque::new()
+            })
+        } else {
+            VecDeque::new()
+        };
+
+        // Capacity might have been reduced since the last run
+        let excess = queries.len().saturating_sub(config.capacity);
+        queries.drain(..excess);
+
+        Self {
+            config,
+            path,
+            state: Mutex::new(RecentQueriesState {
+                queries,
+                generation: 0,
+                last_save: Instant::now(),
+            }),
+            saved_generation: Default::default(),
+        }
+    }
+
+    pub fn record(&self, queries: impl IntoIterator<Item = RecentQuery>) {
+        let mut sampled = queries
+            .into_iter()
+            .filter(|_| rand::random_bool(self.config.sample_rate))
+            .peekable();
+        if sampled.peek().is_none() {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        for query in sampled {
+            if state.queries.len() >= self.config.capacity {
+                state.queries.pop_front();
+            }
+            state.queries.push_back(query);
+        }
+        state.generation += 1;
+
+        if state.last_save.elapsed() < SAVE_INTERVAL {
+            return;
+        }
+        state.last_save = Instant::now();
+
+        let queries = state.queries.clone();
+        let generation = state.generation;
+        let path = self.path.clone();
+        let saved_generation = self.saved_generation.clone();
+        tokio::task::spawn_blocking(move || {
+            save(&path, &saved_generation, &queries, generation);
+        });
+    }
+
+    pub fn queries(&self) -> Vec<RecentQuery> {
+        self.state.lock().queries.iter().cloned().collect()
+    }
+
+    /// Save queries recorded since the last save
+    pub async fn flush(&self) {
+        let (queries, generation) = {
+            let state = self.state.lock();
+            (state.queries.clone(), state.generation)
+        };
+        let path = self.path.clone();
+        let saved_generation = self.saved_generation.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            save(&path, &saved_generation, &queries, generation);
+        })
+        .await;
+        if let Err(err) = result {
+            log::warn!("Failed to save recent queries: {err}");
+        }
+    }
+}
+
+/// Save queries of the given generation, unless the same or a newer generation is saved already
+fn save(
+    path: &Path,
+    saved_generation: &Mutex<u64>,
+    queries: &VecDeque<RecentQuery>,
+    generation: u64,
+) {
+    let mut saved_generation = saved_generation.lock();
+    if *saved_generation >= generation {
+        return;
+    }
+    match atomic_save_json(path, queries) {
+        Ok(()) => *saved_generation = generation,
+        Err(err) => log::warn!("Failed to save recent queries to {}: {err}", path.display()),
+    }
+}
+
+fn shard_key_selector(shard_selection: &ShardSelectorInternal) -> Option<ShardKeySelector> {
+    match shard_selection {
+        ShardSelectorInternal::ShardKey(shard_key) => {
+            Some(ShardKeySelector::ShardKey(shard_key.clone()))
+        }
+        ShardSelectorInternal::ShardKeys(shard_keys) => {
+            Some(ShardKeySelector::ShardKeys(shard_keys.clone()))
+        }
+        ShardSelectorInternal::Empty
+        | ShardSelectorInternal::All
+        | ShardSelectorInternal::ShardId(_) => None,
+    }
+}
+
+impl Collection {
+    /// Record nearest neighbours searches of the batch into recent queries, if enabled
+    pub fn record_recent_searches(
+        &self,
+        request: &CoreSearchRequestBatch,
+        shard_selection: &ShardSelectorInternal,
+    ) {
+        let Some(recent_queries) = &self.recent_queries else {
+            return;
+        };
+        let timestamp = Utc::now();
+        recent_queries.record(request.searches.iter().filter_map(|search| {
+            let QueryEnum::Nearest(named_query) = &search.query else {
+                return None;
+            };
+            Some(RecentQuery {
+                timestamp,
+                using: search.query.get_vector_name().to_owned(),
+                vector: VectorOutput::from(named_query.query.clone()),
+                filter: search.filter.clone(),
+                params: search.params,
+                limit: search.limit,
+                shard_key: shard_key_selector(shard_selection),
+            })
+        }));
+    }
+
+    /// Record nearest neighbours queries of the batch into recent queries, if enabled
+    ///
+    /// Only queries with a raw vector are recorded, queries by point ID can't be replayed
+    /// reliably.
+    pub fn record_recent_queries(
+        &self,
+        requests: &[(CollectionQueryRequest, ShardSelectorInternal)],
+    ) {
+        let Some(recent_queries) = &self.recent_queries else {
+            return;
+        };
+        let timestamp = Utc::now();
+        recent_queries.record(requests.iter().filter_map(|(request, shard_selection)| {
+            let Some(Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(vector)))) =
+                &request.query
+            else {
+                return None;
+            };
+            Some(RecentQuery {
+                timestamp,
+                using: request.using.clone(),
+                vector: VectorOutput::from(vector.clone()),
+                filter: request.filter.clone(),
+                params: request.params,
+                limit: request.limit,
+                shard_key: shard_key_selector(shard_selection),
+            })
+        }));
+    }
+
+    /// Sampled recent queries, from the oldest to the newest
+    ///
+    /// Empty if recording of recent queries is not enabled.
+    pub fn recent_queries(&self) -> Vec<RecentQuery> {
+        self.recent_queries
+            .as_ref()
+            .map(RecentQueries::queries)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn query(limit: usize) -> RecentQuery {
+        RecentQuery {
+            timestamp: Utc::now(),
+            using: String::new(),
+            vector: VectorOutput::Dense(vec![1.0, 2.0]),
+            filter: None,
+            params: None,
+            limit,
+            shard_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recent_queries_ring_buffer() {
+        let dir = Builder::new().prefix("recent_queries").tempdir().unwrap();
+        let config = RecentQueriesConfig {
+            capacity: 3,
+            sample_rate: 1.0,
+        };
+
+        let recent_queries = RecentQueries::load(dir.path(), config.clone());
+        recent_queries.record((1..=5).map(query));
+        let limits: Vec<_> = recent_queries.queries().iter().map(|q| q.limit).collect();
+        assert_eq!(limits, vec![3, 4, 5]);
+
+        // Nothing is recorded without sampling
+        let not_sampled = RecentQueries::load(
+            dir.path(),
+            RecentQueriesConfig {
+                capacity: 3,
+                sample_rate: 0.0,
+            },
+        );
+        not_sampled.record((1..=5).map(query));
+        assert!(not_sampled.queries().is_empty());
+
+        // Unsaved queries are flushed, and loaded respecting a reduced capacity
+        let queries = recent_queries.queries();
+        recent_queries.flush().await;
+        drop(recent_queries);
+        let reloaded = RecentQueries::load(
+            dir.path(),
+            RecentQueriesConfig {
+                capacity: 2,
+                ..config
+            },
+        );
+        assert_eq!(reloaded.queries(), queries[1..]);
+    }
+}
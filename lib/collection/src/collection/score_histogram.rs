@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use ahash::AHashSet;
+use api::rest::{ScoreHistogramBucket, ScoreHistogramRequestInternal, ScoreHistogramResponse};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::types::ScoreType;
+use segment::data_types::vectors::{Named, NamedVectorStruct, VectorInternal};
+use segment::types::{
+    Condition, Filter, HasIdCondition, HasVectorCondition, SearchParams, VectorNameBuf,
+    WithPayloadInterface, WithVector,
+};
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+use crate::operations::universal_query::collection_query::{
+    CollectionQueryRequest, Query, VectorInputInternal, VectorQuery,
+};
+use crate::operations::universal_query::shard_query::{
+    SampleInternal, ScoringQuery, ShardQueryRequest,
+};
+
+/// Internal representation of the score histogram request, used to convert from REST.
+pub struct CollectionScoreHistogramRequest {
+    pub vector: VectorInternal,
+    pub using: VectorNameBuf,
+    pub filter: Option<Filter>,
+    pub sample_size: usize,
+    pub buckets: usize,
+}
+
+impl CollectionScoreHistogramRequest {
+    pub const DEFAULT_SAMPLE: usize = 1000;
+    pub const DEFAULT_BUCKETS: usize = 10;
+}
+
+impl From<ScoreHistogramRequestInternal> for CollectionScoreHistogramRequest {
+    fn from(request: ScoreHistogramRequestInternal) -> Self {
+        let ScoreHistogramRequestInternal {
+            vector,
+            filter,
+            sample,
+            buckets,
+        } = request;
+        let vector = NamedVectorStruct::from(vector);
+        let using = vector.get_name().to_owned();
+        Self {
+            vector: vector.to_vector(),
+            using,
+            filter,
+            sample_size: sample.unwrap_or(CollectionScoreHistogramRequest::DEFAULT_SAMPLE),
+            buckets: buckets.unwrap_or(CollectionScoreHistogramRequest::DEFAULT_BUCKETS),
+        }
+    }
+}
+
+/// Split the range between the lowest and the highest score into equal-width buckets
+///
+/// If all scores are equal, a single bucket is returned.
+fn build_histogram(scores: &[ScoreType], buckets: usize) -> ScoreHistogramResponse {
+    let (Some(min_score), Some(max_score)) = (
+        scores.iter().copied().reduce(ScoreType::min),
+        scores.iter().copied().reduce(ScoreType::max),
+    ) else {
+        return ScoreHistogramResponse::default();
+    };
+
+    let width = (max_score - min_score) / buckets as ScoreType;
+    let buckets = if width > 0.0 { buckets } else { 1 };
+
+    let mut counts = vec![0; buckets];
+    for &score in scores {
+        let bucket = if width > 0.0 {
+            ((score - min_score) / width) as usize
+        } else {
+            0
+        };
+        // The highest score belongs to the last bucket
+        counts[bucket.min(buckets - 1)] += 1;
+    }
+
+    let buckets = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| ScoreHistogramBucket {
+            from: min_score + i as ScoreType * width,
+            to: if i + 1 == buckets {
+                max_score
+            } else {
+                min_score + (i + 1) as ScoreType * width
+            },
+            count,
+        })
+        .collect();
+
+    ScoreHistogramResponse {
+        sampled: scores.len(),
+        min_score: Some(min_score),
+        max_score: Some(max_score),
+        buckets,
+    }
+}
+
+impl Collection {
+    /// Score a random sample of points against the given vector and build a histogram
+    /// of the scores.
+    ///
+    /// Scores are computed with exact search, so the histogram reflects the real distribution
+    /// and can be used to choose a sensible `score_threshold`.
+    pub async fn score_histogram(
+        &self,
+        request: CollectionScoreHistogramRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<ScoreHistogramResponse> {
+        let start = std::time::Instant::now();
+        let CollectionScoreHistogramRequest {
+            vector,
+            using,
+            filter,
+            sample_size,
+            buckets,
+        } = request;
+        if sample_size == 0 || buckets == 0 {
+            return Ok(Default::default());
+        }
+
+        // make sure the vector is present in the point
+        let has_vector = Filter::new_must(Condition::HasVector(HasVectorCondition::from(
+            using.clone(),
+        )));
+
+        // merge user's filter with the has_vector filter
+        let filter = Some(
+            filter
+                .map(|filter| filter.merge(&has_vector))
+                .unwrap_or(has_vector),
+        );
+
+        // sample random points
+        let sampling_query = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(SampleInternal::Random)),
+            filter,
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let mut sampled_points = self
+            .query(
+                sampling_query,
+                read_consistency,
+                shard_selection.clone(),
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+
+        if sampled_points.is_empty() {
+            return Ok(ScoreHistogramResponse::default());
+        }
+        sampled_points.truncate(sample_size);
+
+        // score only the sampled points
+        let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            sampled_points
+                .iter()
+                .map(|point| point.id)
+                .collect::<AHashSet<_>>(),
+        )));
+
+        let query = Query::Vector(VectorQuery::Nearest(VectorInputInternal::Vector(vector)));
+        let query_request = CollectionQueryRequest {
+            prefetch: vec![],
+            query: Some(query),
+            using,
+            filter: Some(filter),
+            score_threshold: None,
+            limit: sampled_points.len(),
+            offset: 0,
+            params: Some(SearchParams {
+                exact: true,
+                ..Default::default()
+            }),
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+            lookup_from: None,
+        };
+
+        // update timeout
+        let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+
+        // We know by construction that lookup_from is not used in the queries
+        // so can use placeholder closure here
+        let collection_by_name = |_name: String| async move { None };
+
+        let scored_points = self
+            .query_batch(
+                vec![(query_request, shard_selection)],
+                collection_by_name,
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+                None,
+            )
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        let scores: Vec<_> = scored_points.iter().map(|point| point.score).collect();
+
+        Ok(build_histogram(&scores, buckets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_histogram() {
+        let histogram = build_histogram(&[0.0, 0.1, 0.5, 0.9, 1.0], 2);
+        assert_eq!(histogram.sampled, 5);
+        assert_eq!(histogram.min_score, Some(0.0));
+        assert_eq!(histogram.max_score, Some(1.0));
+        assert_eq!(
+            histogram.buckets,
+            vec![
+                ScoreHistogramBucket {
+                    from: 0.0,
+                    to: 0.5,
+                    count: 2,
+                },
+                ScoreHistogramBucket {
+                    from: 0.5,
+                    to: 1.0,
+                    count: 3,
+                },
+            ],
+        );
+
+        // Equal scores fall into a single bucket
+        let histogram = build_histogram(&[0.3, 0.3, 0.3], 10);
+        assert_eq!(histogram.buckets.len(), 1);
+        assert_eq!(histogram.buckets[0].count, 3);
+
+        assert_eq!(build_histogram(&[], 10), ScoreHistogramResponse::default());
+    }
+}
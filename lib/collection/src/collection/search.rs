@@ -9,6 +9,7 @@ use itertools::{Either, Itertools};
 use segment::types::{
     ExtendedPointId, Filter, Order, ScoredPoint, WithPayloadInterface, WithVector,
 };
+use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::time::Instant;
 
 use super::Collection;
@@ -155,13 +156,15 @@ impl Collection {
 
         // query all shards concurrently
         let all_searches_res = {
+            let _search_turn = self.search_turn(shard_selection).await;
             let shard_holder = self.shards_holder.read().await;
             let target_shards = shard_holder.select_shards(shard_selection)?;
+            let shard_request = split_scoring_budget_between_shards(&request, target_shards.len());
             let all_searches = target_shards.into_iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
                 shard
                     .core_search(
-                        request.clone(),
+                        shard_request.clone(),
                         read_consistency,
                         shard_selection.is_shard_id(),
                         timeout,
@@ -286,9 +289,23 @@ impl Collection {
                 .iter_mut()
                 .map(|res| res.get_mut(batch_index).map_or(Vec::new(), mem::take));
 
+            // In deterministic mode, points with equal scores are ordered by ID
+            let deterministic = request.params.is_some_and(|params| params.deterministic);
+            let compare = move |a: &ScoredPoint, b: &ScoredPoint| {
+                if deterministic {
+                    ScoredPointTies(a).cmp(&ScoredPointTies(b))
+                } else {
+                    a.cmp(b)
+                }
+            };
+
             let merged_iter = match order {
-                Order::LargeBetter => Either::Left(results_from_shards.kmerge_by(|a, b| a > b)),
-                Order::SmallBetter => Either::Right(results_from_shards.kmerge_by(|a, b| a < b)),
+                Order::LargeBetter => {
+                    Either::Left(results_from_shards.kmerge_by(move |a, b| compare(a, b).is_gt()))
+                }
+                Order::SmallBetter => {
+                    Either::Right(results_from_shards.kmerge_by(move |a, b| compare(a, b).is_lt()))
+                }
             }
             .filter(|point| seen_ids.insert(point.id));
 
@@ -329,3 +346,28 @@ impl Collection {
         }
     }
 }
+
+/// Scoring budget limits the whole request, so each shard gets an equal share of it
+fn split_scoring_budget_between_shards(
+    request: &Arc<CoreSearchRequestBatch>,
+    num_shards: usize,
+) -> Arc<CoreSearchRequestBatch> {
+    let has_scoring_budget = request.searches.iter().any(|search| {
+        search
+            .params
+            .is_some_and(|params| params.max_scored_points.is_some())
+    });
+    if num_shards <= 1 || !has_scoring_budget {
+        return Arc::clone(request);
+    }
+
+    let mut shard_request = CoreSearchRequestBatch::clone(request);
+    for params in shard_request
+        .searches
+        .iter_mut()
+        .filter_map(|search| search.params.as_mut())
+    {
+        params.split_scoring_budget(num_shards);
+    }
+    Arc::new(shard_request)
+}
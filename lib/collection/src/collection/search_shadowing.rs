@@ -0,0 +1,277 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::types::{PointIdType, ScoredPoint, SearchParams};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection::Collection;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionResult, CoreSearchRequestBatch};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
+
+/// Configuration of shadowing live searches of a collection with an alternative setup
+///
+/// Sampled searches are repeated in the background with alternative search params or against
+/// a candidate collection. Shadow results are discarded, only compared against the original
+/// ones, so index settings can be evaluated on live traffic without client changes.
+#[derive(Clone, Debug, Deserialize, Validate)]
+pub struct SearchShadowingConfig {
+    /// Name of the collection, searches of which are shadowed
+    pub collection: String,
+    /// Fraction of search requests to shadow
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub sample_rate: f64,
+    /// Search params of shadow searches, replacing params of the original searches.
+    /// If not set - original params are used.
+    #[serde(default)]
+    #[validate(nested)]
+    pub params: Option<SearchParams>,
+    /// Collection to run shadow searches against.
+    /// If not set - shadow searches run against the original collection.
+    #[serde(default)]
+    pub shadow_collection: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct SearchShadowingTelemetry {
+    /// Collection shadow searches run against, if not the original one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_collection: Option<String>,
+    /// Number of shadowed search requests
+    #[anonymize(false)]
+    pub searches: usize,
+    /// Number of shadow search requests which failed
+    #[anonymize(false)]
+    pub failures: usize,
+    /// Average fraction of original results also returned by shadow searches, in `[0, 1]`
+    #[anonymize(false)]
+    pub overlap: f64,
+    /// Average duration of shadowed original search requests, in milliseconds
+    #[anonymize(false)]
+    pub avg_original_duration_ms: f64,
+    /// Average duration of shadow search requests, in milliseconds
+    #[anonymize(false)]
+    pub avg_shadow_duration_ms: f64,
+}
+
+/// Shadowing of searches of a collection, with comparison stats of shadow searches
+#[derive(Debug)]
+pub struct SearchShadowing {
+    config: SearchShadowingConfig,
+    stats: Mutex<ShadowingStats>,
+}
+
+#[derive(Debug, Default)]
+struct ShadowingStats {
+    searches: usize,
+    failures: usize,
+    /// Number of compared queries, shadow search requests might be batches
+    compared_queries: usize,
+    overlap_sum: f64,
+    original_duration_sum: Duration,
+    shadow_duration_sum: Duration,
+}
+
+/// Fraction of `original` results present in `shadow` results
+///
+/// An empty original result is considered to be fully overlapped.
+fn results_overlap(original: &[PointIdType], shadow: &[ScoredPoint]) -> f64 {
+    if original.is_empty() {
+        return 1.0;
+    }
+    let found = original
+        .iter()
+        .filter(|id| shadow.iter().any(|point| point.id == **id))
+        .count();
+    found as f64 / original.len() as f64
+}
+
+impl SearchShadowing {
+    pub fn new(config: SearchShadowingConfig) -> Self {
+        Self {
+            config,
+            stats: Default::default(),
+        }
+    }
+
+    /// Collection to run shadow searches against, if not the original one
+    pub fn shadow_collection(&self) -> Option<&str> {
+        self.config.shadow_collection.as_deref()
+    }
+
+    fn sample(&self) -> bool {
+        rand::random_bool(self.config.sample_rate)
+    }
+
+    fn shadow_params(&self, params: Option<SearchParams>) -> Option<SearchParams> {
+        self.config.params.or(params)
+    }
+
+    fn shadow_prefetch(&self, prefetch: &mut CollectionPrefetch) {
+        prefetch.params = self.shadow_params(prefetch.params);
+        for prefetch in &mut prefetch.prefetch {
+            self.shadow_prefetch(prefetch);
+        }
+    }
+
+    /// Compare results of a shadow search request against the original results
+    ///
+    /// `original` contains ids of the original results of each query of the request.
+    pub fn record(
+        &self,
+        original: &[Vec<PointIdType>],
+        original_duration: Duration,
+        shadow: CollectionResult<Vec<Vec<ScoredPoint>>>,
+        shadow_duration: Duration,
+    ) {
+        let mut stats = self.stats.lock();
+        stats.searches += 1;
+        let shadow = match shadow {
+            Ok(shadow) => shadow,
+            Err(err) => {
+                stats.failures += 1;
+                log::debug!("Shadow search failed: {err}");
+                return;
+            }
+        };
+        stats.compared_queries += original.len();
+        stats.overlap_sum += original
+            .iter()
+            .zip(&shadow)
+            .map(|(original, shadow)| results_overlap(original, shadow))
+            .sum::<f64>();
+        stats.original_duration_sum += original_duration;
+        stats.shadow_duration_sum += shadow_duration;
+    }
+
+    pub fn get_telemetry_data(&self) -> SearchShadowingTelemetry {
+        let stats = self.stats.lock();
+        let succeeded = stats.searches - stats.failures;
+        let avg_duration_ms = |sum: Duration| {
+            if succeeded == 0 {
+                0.0
+            } else {
+                sum.as_secs_f64() * 1000.0 / succeeded as f64
+            }
+        };
+        SearchShadowingTelemetry {
+            shadow_collection: self.config.shadow_collection.clone(),
+            searches: stats.searches,
+            failures: stats.failures,
+            overlap: if stats.compared_queries == 0 {
+                1.0
+            } else {
+                stats.overlap_sum / stats.compared_queries as f64
+            },
+            avg_original_duration_ms: avg_duration_ms(stats.original_duration_sum),
+            avg_shadow_duration_ms: avg_duration_ms(stats.shadow_duration_sum),
+        }
+    }
+}
+
+impl Collection {
+    /// Shadow copy of the search request with alternative params, if sampled for shadowing
+    pub fn shadow_search_batch(
+        &self,
+        request: &CoreSearchRequestBatch,
+    ) -> Option<(Arc<SearchShadowing>, CoreSearchRequestBatch)> {
+        let shadowing = self.search_shadowing.as_ref()?;
+        if request.searches.is_empty() || !shadowing.sample() {
+            return None;
+        }
+        let mut shadow_request = request.clone();
+        for search in &mut shadow_request.searches {
+            search.params = shadowing.shadow_params(search.params);
+        }
+        Some((shadowing.clone(), shadow_request))
+    }
+
+    /// Shadow copy of the query requests with alternative params, if sampled for shadowing
+    #[allow(clippy::type_complexity)]
+    pub fn shadow_query_batch(
+        &self,
+        requests: &[(CollectionQueryRequest, ShardSelectorInternal)],
+    ) -> Option<(
+        Arc<SearchShadowing>,
+        Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+    )> {
+        let shadowing = self.search_shadowing.as_ref()?;
+        if requests.is_empty() || !shadowing.sample() {
+            return None;
+        }
+        let mut shadow_requests = requests.to_vec();
+        for (request, _) in &mut shadow_requests {
+            request.params = shadowing.shadow_params(request.params);
+            for prefetch in &mut request.prefetch {
+                shadowing.shadow_prefetch(prefetch);
+            }
+        }
+        Some((shadowing.clone(), shadow_requests))
+    }
+
+    pub(crate) fn search_shadowing_telemetry(&self) -> Option<SearchShadowingTelemetry> {
+        self.search_shadowing
+            .as_ref()
+            .map(|shadowing| shadowing.get_telemetry_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+    use crate::operations::types::CollectionError;
+
+    fn point(id: u64) -> ScoredPoint {
+        ScoredPoint {
+            id: ExtendedPointId::NumId(id),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_search_shadowing_stats() {
+        let shadowing = SearchShadowing::new(SearchShadowingConfig {
+            collection: "test".to_string(),
+            sample_rate: 1.0,
+            params: None,
+            shadow_collection: None,
+        });
+
+        let original = vec![
+            vec![ExtendedPointId::NumId(1), ExtendedPointId::NumId(2)],
+            vec![],
+        ];
+        shadowing.record(
+            &original,
+            Duration::from_millis(10),
+            Ok(vec![vec![point(2), point(3)], vec![point(4)]]),
+            Duration::from_millis(30),
+        );
+        shadowing.record(
+            &original,
+            Duration::from_millis(10),
+            Err(CollectionError::timeout(1, "shadow search")),
+            Duration::from_millis(1000),
+        );
+
+        let telemetry = shadowing.get_telemetry_data();
+        assert_eq!(telemetry.searches, 2);
+        assert_eq!(telemetry.failures, 1);
+        assert_eq!(telemetry.overlap, 0.75);
+        assert_eq!(telemetry.avg_original_duration_ms, 10.0);
+        assert_eq!(telemetry.avg_shadow_duration_ms, 30.0);
+    }
+}
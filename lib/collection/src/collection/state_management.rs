@@ -136,6 +136,8 @@ impl Collection {
                 strict_mode_config,
                 uuid: _,
                 metadata,
+                // Custom metric module is fixed on collection creation
+                custom_metric: _,
             } = &new_config;
 
             let is_core_config_updated = params != &config.params
@@ -14,6 +14,7 @@ use segment::segment_constructor::simple_segment_constructor::{
     VECTOR1_NAME, VECTOR2_NAME, build_multivec_segment, build_simple_segment,
 };
 use segment::types::{Distance, HnswGlobalConfig, Payload, PointIdType, SeqNumberType};
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
@@ -242,6 +243,7 @@ pub(crate) fn get_merge_optimizer(
         },
         Default::default(),
         HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
         Default::default(),
     )
 }
@@ -268,6 +270,7 @@ pub(crate) fn get_indexing_optimizer(
         },
         Default::default(),
         HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
         Default::default(),
     )
 }
@@ -2,20 +2,29 @@ use std::borrow::Cow;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
+use common::budget::ResourcePermit;
 use parking_lot::Mutex;
+use segment::common::operation_error::{OperationResult, check_process_stopped};
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
+use segment::entry::entry_point::SegmentEntry;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
 use segment::types::{
-    HnswConfig, HnswGlobalConfig, Indexes, QuantizationConfig, SegmentType, VectorName,
+    DiskAnnConfig, HnswConfig, HnswGlobalConfig, Indexes, IvfConfig, QuantizationConfig,
+    SegmentConfig, SegmentType, VectorDataConfig, VectorName, VectorNameBuf,
 };
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
-use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
+use crate::operations::types::CollectionResult;
 
 /// Looks for segments having a mismatch between configured and actual parameters
 ///
@@ -29,11 +38,13 @@ pub struct ConfigMismatchOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     hnsw_global_config: HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     quantization_config: Option<QuantizationConfig>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
 impl ConfigMismatchOptimizer {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
@@ -41,6 +52,7 @@ impl ConfigMismatchOptimizer {
         collection_params: CollectionParams,
         hnsw_config: HnswConfig,
         hnsw_global_config: HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         ConfigMismatchOptimizer {
@@ -50,6 +62,7 @@ impl ConfigMismatchOptimizer {
             collection_params,
             hnsw_config,
             hnsw_global_config,
+            chunked_vectors_config,
             quantization_config,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
@@ -101,6 +114,199 @@ impl ConfigMismatchOptimizer {
         }
     }
 
+    /// IVF config that should be used for a given vector with current configuration
+    ///
+    /// `None` if the vector should be indexed with HNSW, including vectors IVF index doesn't support.
+    fn get_required_ivf_config(
+        &self,
+        vector_name: &VectorName,
+        vector_data: &VectorDataConfig,
+    ) -> Option<IvfConfig> {
+        self.collection_params
+            .vectors
+            .get_params(vector_name)
+            .and_then(|vector_params| vector_params.ivf_config)
+            .filter(|_| {
+                IvfConfig::supports_distance(vector_data.distance)
+                    && vector_data.multivector_config.is_none()
+            })
+    }
+
+    /// DiskANN config that should be used for a given vector with current configuration
+    ///
+    /// `None` if the vector should be indexed with HNSW or IVF, including multivectors.
+    fn get_required_disk_ann_config(
+        &self,
+        vector_name: &VectorName,
+        vector_data: &VectorDataConfig,
+    ) -> Option<DiskAnnConfig> {
+        if self
+            .get_required_ivf_config(vector_name, vector_data)
+            .is_some()
+        {
+            return None;
+        }
+        self.collection_params
+            .vectors
+            .get_params(vector_name)
+            .and_then(|vector_params| vector_params.disk_ann_config)
+            .filter(|_| vector_data.multivector_config.is_none())
+    }
+
+    /// Quantization config that should be used for a given vector with current configuration
+    ///
+    /// Vector-specific quantization config takes precedence over the collection-wide config.
+    fn get_required_quantization_config(
+        &self,
+        vector_name: &VectorName,
+    ) -> Option<&QuantizationConfig> {
+        self.collection_params
+            .vectors
+            .get_params(vector_name)
+            .and_then(|vector_params| vector_params.quantization_config.as_ref())
+            .or(self.quantization_config.as_ref())
+    }
+
+    /// Check if quantization of the vector differs from the required one
+    fn has_quantization_mismatch(
+        &self,
+        vector_name: &VectorName,
+        vector_data: &VectorDataConfig,
+    ) -> bool {
+        let target_quantization = self.get_required_quantization_config(vector_name);
+
+        vector_data
+            .quantization_config
+            .as_ref()
+            .zip(target_quantization)
+            // Rebuild if current parameters differ from target parameters
+            .map(|(current, target)| current.mismatch_requires_rebuild(target))
+            // Or rebuild if we now change the enabled state on an indexed segment
+            .unwrap_or_else(|| {
+                let vector_data_quantization_appendable = vector_data
+                    .quantization_config
+                    .as_ref()
+                    .map(|q| q.supports_appendable())
+                    .unwrap_or(false);
+                let target_quantization_appendable = target_quantization
+                    .map(|q| q.supports_appendable())
+                    .unwrap_or(false);
+                // If segment is unindexed, only appendable quantization is applied.
+                // So that we check if any config is appendable to avoid infinity loop here.
+                let unindexed_changed = common::flags::feature_flags().appendable_quantization
+                    && (vector_data_quantization_appendable || target_quantization_appendable);
+                (vector_data.quantization_config.is_some() != target_quantization.is_some())
+                    && (vector_data.index.is_indexed() || unindexed_changed)
+            })
+    }
+
+    /// Check if the segment has a mismatch other than quantization of dense vectors
+    fn has_non_quantization_mismatch(&self, segment_config: &SegmentConfig) -> bool {
+        if self.collection_params.on_disk_payload
+            != segment_config.payload_storage_type.is_on_disk()
+        {
+            return true;
+        }
+
+        // Determine whether dense data in segment has mismatch
+        let dense_has_mismatch =
+            segment_config
+                .vector_data
+                .iter()
+                .any(|(vector_name, vector_data)| {
+                    // Check index mismatch
+                    let target_ivf = self.get_required_ivf_config(vector_name, vector_data);
+                    let target_disk_ann =
+                        self.get_required_disk_ann_config(vector_name, vector_data);
+                    match (&vector_data.index, target_ivf, target_disk_ann) {
+                        (Indexes::Plain {}, _, _) => {}
+                        (Indexes::Hnsw(effective_hnsw), None, None) => {
+                            // Select segment if we have an HNSW mismatch that requires rebuild
+                            let target_hnsw = self.get_required_hnsw_config(vector_name);
+                            if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
+                                return true;
+                            }
+                        }
+                        (Indexes::Ivf(effective_ivf), Some(target_ivf), _) => {
+                            // Select segment if we have an IVF mismatch that requires rebuild
+                            if effective_ivf.mismatch_requires_rebuild(&target_ivf) {
+                                return true;
+                            }
+                        }
+                        (Indexes::DiskAnn(effective_disk_ann), None, Some(target_disk_ann)) => {
+                            // Select segment if we have a DiskANN mismatch that requires rebuild
+                            if effective_disk_ann.mismatch_requires_rebuild(&target_disk_ann) {
+                                return true;
+                            }
+                        }
+                        // Select segment if the type of the index changed
+                        _ => return true,
+                    }
+
+                    if let Some(is_required_on_disk) = self.check_if_vectors_on_disk(vector_name)
+                        && is_required_on_disk != vector_data.storage_type.is_on_disk()
+                    {
+                        return true;
+                    }
+
+                    false
+                });
+
+        // Determine whether sparse data in segment has mismatch
+        let sparse_has_mismatch =
+            segment_config
+                .sparse_vector_data
+                .iter()
+                .any(|(vector_name, vector_data)| {
+                    let Some(is_required_on_disk) =
+                        self.check_if_sparse_vectors_index_on_disk(vector_name)
+                    else {
+                        return false; // Do nothing if not specified
+                    };
+
+                    match vector_data.index.index_type {
+                        SparseIndexType::MutableRam => false, // Do nothing for mutable RAM
+                        SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
+                        SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
+                    }
+                });
+
+        dense_has_mismatch || sparse_has_mismatch
+    }
+
+    /// Vectors to re-quantize with their required quantization config, if quantization is the
+    /// only mismatch of the segment
+    ///
+    /// Only non-appendable segments are re-quantized in place, which is much cheaper than
+    /// rebuilding them along with their HNSW graphs.
+    fn requantization_plan(
+        &self,
+        segment: &dyn SegmentEntry,
+    ) -> Option<Vec<(VectorNameBuf, Option<QuantizationConfig>)>> {
+        if segment.is_appendable() || segment.segment_type() == SegmentType::Special {
+            return None;
+        }
+
+        let segment_config = segment.config();
+        if self.has_non_quantization_mismatch(segment_config) {
+            return None;
+        }
+
+        let plan: Vec<_> = segment_config
+            .vector_data
+            .iter()
+            .filter(|(vector_name, vector_data)| {
+                self.has_quantization_mismatch(vector_name, vector_data)
+            })
+            .map(|(vector_name, _)| {
+                let target = self.get_required_quantization_config(vector_name).cloned();
+                (vector_name.clone(), target)
+            })
+            .collect();
+
+        (!plan.is_empty()).then_some(plan)
+    }
+
     fn worst_segment(
         &self,
         segments: LockedSegmentHolder,
@@ -124,97 +330,14 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
-                if self.collection_params.on_disk_payload
-                    != segment_config.payload_storage_type.is_on_disk()
-                {
-                    return Some((*idx, vector_size)); // Skip segments with payload mismatch
-                }
-
-                // Determine whether dense data in segment has mismatch
-                let dense_has_mismatch =
-                    segment_config
+                let has_mismatch = self.has_non_quantization_mismatch(segment_config)
+                    || segment_config
                         .vector_data
                         .iter()
                         .any(|(vector_name, vector_data)| {
-                            // Check HNSW mismatch
-                            match &vector_data.index {
-                                Indexes::Plain {} => {}
-                                Indexes::Hnsw(effective_hnsw) => {
-                                    // Select segment if we have an HNSW mismatch that requires rebuild
-                                    let target_hnsw = self.get_required_hnsw_config(vector_name);
-                                    if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
-                                        return true;
-                                    }
-                                }
-                            }
-
-                            if let Some(is_required_on_disk) =
-                                self.check_if_vectors_on_disk(vector_name)
-                                && is_required_on_disk != vector_data.storage_type.is_on_disk()
-                            {
-                                return true;
-                            }
-
-                            // Check quantization mismatch
-                            let target_quantization_collection = self.quantization_config.as_ref();
-                            let target_quantization_vector = self
-                                .collection_params
-                                .vectors
-                                .get_params(vector_name)
-                                .and_then(|vector_params| {
-                                    vector_params.quantization_config.clone()
-                                });
-                            let target_quantization = target_quantization_vector
-                                .as_ref()
-                                .or(target_quantization_collection);
-
-                            vector_data
-                                .quantization_config
-                                .as_ref()
-                                .zip(target_quantization)
-                                // Rebuild if current parameters differ from target parameters
-                                .map(|(current, target)| current.mismatch_requires_rebuild(target))
-                                // Or rebuild if we now change the enabled state on an indexed segment
-                                .unwrap_or_else(|| {
-                                    let vector_data_quantization_appendable = vector_data
-                                        .quantization_config
-                                        .as_ref()
-                                        .map(|q| q.supports_appendable())
-                                        .unwrap_or(false);
-                                    let target_quantization_appendable = target_quantization
-                                        .map(|q| q.supports_appendable())
-                                        .unwrap_or(false);
-                                    // If segment is unindexed, only appendable quantization is applied.
-                                    // So that we check if any config is appendable to avoid infinity loop here.
-                                    let unindexed_changed = common::flags::feature_flags()
-                                        .appendable_quantization
-                                        && (vector_data_quantization_appendable
-                                            || target_quantization_appendable);
-                                    (vector_data.quantization_config.is_some()
-                                        != target_quantization.is_some())
-                                        && (vector_data.index.is_indexed() || unindexed_changed)
-                                })
-                        });
-
-                // Determine whether dense data in segment has mismatch
-                let sparse_has_mismatch =
-                    segment_config
-                        .sparse_vector_data
-                        .iter()
-                        .any(|(vector_name, vector_data)| {
-                            let Some(is_required_on_disk) =
-                                self.check_if_sparse_vectors_index_on_disk(vector_name)
-                            else {
-                                return false; // Do nothing if not specified
-                            };
-
-                            match vector_data.index.index_type {
-                                SparseIndexType::MutableRam => false, // Do nothing for mutable RAM
-                                SparseIndexType::ImmutableRam => is_required_on_disk, // Rebuild if we require on disk
-                                SparseIndexType::Mmap => !is_required_on_disk, // Rebuild if we require in RAM
-                            }
+                            self.has_quantization_mismatch(vector_name, vector_data)
                         });
-                (sparse_has_mismatch || dense_has_mismatch).then_some((*idx, vector_size))
+                has_mismatch.then_some((*idx, vector_size))
             })
             .collect();
 
@@ -253,6 +376,10 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
         &self.hnsw_global_config
     }
 
+    fn chunked_vectors_config(&self) -> ChunkedVectorsConfig {
+        self.chunked_vectors_config
+    }
+
     fn quantization_config(&self) -> Option<QuantizationConfig> {
         self.quantization_config.clone()
     }
@@ -272,6 +399,65 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
         &self.telemetry_durations_aggregator
     }
+
+    /// Re-quantize the segment in place, if quantization is its only mismatch
+    ///
+    /// The segment is proxied while quantizing, so it stays searchable and collects updates.
+    fn optimize_in_place(
+        &self,
+        segments: &LockedSegmentHolder,
+        ids: &[SegmentId],
+        permit: &ResourcePermit,
+        stopped: &AtomicBool,
+    ) -> CollectionResult<Option<usize>> {
+        let &[segment_id] = ids else {
+            return Ok(None);
+        };
+
+        let segments_lock = segments.upgradable_read();
+        let Some(LockedSegment::Original(segment)) = segments_lock.get(segment_id).cloned() else {
+            return Ok(None);
+        };
+        let Some(plan) = self.requantization_plan(&*segment.read()) else {
+            return Ok(None);
+        };
+
+        check_process_stopped(stopped)?;
+
+        let max_threads = permit.num_cpus as usize;
+        let point_count = self.optimize_proxied_in_place(
+            segments,
+            segments_lock,
+            segment_id,
+            segment,
+            |segment| {
+                let quantized = plan
+                    .into_iter()
+                    .map(|(vector_name, quantization_config)| {
+                        let quantized_vectors = quantization_config
+                            .map(|config| {
+                                segment.read().build_quantization(
+                                    &vector_name,
+                                    &config,
+                                    max_threads,
+                                    stopped,
+                                )
+                            })
+                            .transpose()?;
+                        Ok((vector_name, quantized_vectors))
+                    })
+                    .collect::<OperationResult<Vec<_>>>()?;
+
+                let mut segment = segment.write();
+                quantized
+                    .into_iter()
+                    .try_for_each(|(vector_name, quantized_vectors)| {
+                        segment.apply_quantization(&vector_name, quantized_vectors)
+                    })
+            },
+        )?;
+        Ok(Some(point_count))
+    }
 }
 
 #[cfg(test)]
@@ -349,6 +535,8 @@ mod tests {
             on_disk: None,
             payload_m: None,
             copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
         };
 
         // Optimizers used in test
@@ -360,6 +548,7 @@ mod tests {
             collection_params.clone(),
             hnsw_config.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
@@ -369,6 +558,7 @@ mod tests {
             collection_params,
             hnsw_config.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -510,6 +700,8 @@ mod tests {
             on_disk: None,
             payload_m: None,
             copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config_collection.max_indexing_threads);
@@ -525,6 +717,7 @@ mod tests {
             collection_params.clone(),
             hnsw_config_collection.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
@@ -534,6 +727,7 @@ mod tests {
             collection_params,
             hnsw_config_collection.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -644,6 +838,8 @@ mod tests {
                     r#type: ScalarType::Int8,
                     quantile: Some(0.99),
                     always_ram: Some(true),
+                    placement: None,
+                    auto_quantile: None,
                 },
             });
         let collection_params = CollectionParams {
@@ -684,6 +880,8 @@ mod tests {
                     r#type: ScalarType::Int8,
                     quantile: Some(0.91),
                     always_ram: None,
+                    placement: None,
+                    auto_quantile: None,
                 },
             });
 
@@ -696,6 +894,7 @@ mod tests {
             collection_params.clone(),
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Some(quantization_config_collection.clone()),
         );
         let mut config_mismatch_optimizer = ConfigMismatchOptimizer::new(
@@ -705,6 +904,7 @@ mod tests {
             collection_params,
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Some(quantization_config_collection),
         );
 
@@ -739,6 +939,7 @@ mod tests {
             product: ProductQuantizationConfig {
                 compression: CompressionRatio::X32,
                 always_ram: Some(true),
+                placement: None,
             },
         });
         match config_mismatch_optimizer.collection_params.vectors {
@@ -756,6 +957,7 @@ mod tests {
         let suggested_to_optimize =
             config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
         assert_eq!(suggested_to_optimize.len(), 1);
+        let requantized_segment_id = suggested_to_optimize[0];
         let changed = config_mismatch_optimizer
             .optimize(
                 locked_holder.clone(),
@@ -767,6 +969,19 @@ mod tests {
             .unwrap();
         assert!(changed > 0, "optimizer should have rebuilt this segment");
 
+        // Only quantization changed, so the indexed segment is re-quantized in place
+        assert!(
+            matches!(
+                locked_holder.read().get(requantized_segment_id),
+                Some(LockedSegment::Original(_)),
+            ),
+            "re-quantized segment should be kept",
+        );
+        assert_eq!(locked_holder.read().len(), 2);
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
         // Ensure new segment has changed quantization config
         locked_holder
             .read()
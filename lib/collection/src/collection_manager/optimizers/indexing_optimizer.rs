@@ -5,6 +5,7 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::types::{HnswConfig, HnswGlobalConfig, QuantizationConfig, SegmentType};
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
 use crate::collection_manager::holders::segment_holder::{
     LockedSegmentHolder, SegmentHolder, SegmentId,
@@ -29,6 +30,7 @@ pub struct IndexingOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     hnsw_global_config: HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     quantization_config: Option<QuantizationConfig>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
@@ -43,6 +45,7 @@ impl IndexingOptimizer {
         collection_params: CollectionParams,
         hnsw_config: HnswConfig,
         hnsw_global_config: HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         IndexingOptimizer {
@@ -53,6 +56,7 @@ impl IndexingOptimizer {
             collection_params,
             hnsw_config,
             hnsw_global_config,
+            chunked_vectors_config,
             quantization_config,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
@@ -261,6 +265,10 @@ impl SegmentOptimizer for IndexingOptimizer {
         &self.hnsw_global_config
     }
 
+    fn chunked_vectors_config(&self) -> ChunkedVectorsConfig {
+        self.chunked_vectors_config
+    }
+
     fn quantization_config(&self) -> Option<QuantizationConfig> {
         self.quantization_config.clone()
     }
@@ -369,6 +377,7 @@ mod tests {
             },
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
         let locked_holder: Arc<RwLock<_, _>> = Arc::new(RwLock::new(holder));
@@ -479,6 +488,7 @@ mod tests {
             },
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -787,6 +797,7 @@ mod tests {
             },
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -878,6 +889,8 @@ mod tests {
             on_disk: None,
             payload_m: None,
             copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
         };
 
         {
@@ -890,6 +903,7 @@ mod tests {
                 collection_params.clone(),
                 hnsw_config.clone(),
                 HnswGlobalConfig::default(),
+                ChunkedVectorsConfig::default(),
                 Default::default(),
             );
             let config_mismatch_optimizer = ConfigMismatchOptimizer::new(
@@ -899,6 +913,7 @@ mod tests {
                 collection_params.clone(),
                 hnsw_config.clone(),
                 HnswGlobalConfig::default(),
+                ChunkedVectorsConfig::default(),
                 Default::default(),
             );
 
@@ -956,6 +971,7 @@ mod tests {
             collection_params.clone(),
             hnsw_config.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
         let config_mismatch_optimizer = ConfigMismatchOptimizer::new(
@@ -965,6 +981,7 @@ mod tests {
             collection_params,
             hnsw_config,
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
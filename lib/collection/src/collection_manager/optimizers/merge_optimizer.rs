@@ -6,10 +6,14 @@ use itertools::Itertools;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::types::{HnswConfig, HnswGlobalConfig, QuantizationConfig, SegmentType};
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
+use crate::collection_manager::optimizers::merge_planner::{
+    MergePlanner, PlannedMerge, SegmentMergeStats,
+};
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
@@ -19,9 +23,9 @@ const BYTES_IN_KB: usize = 1024;
 
 /// Optimizer that tries to reduce number of segments until it fits configured value.
 ///
-/// It merges 3 smallest segments into a single large segment.
-/// Merging 3 segments instead of 2 guarantees that after the optimization the number of segments
-/// will be less than before.
+/// Groups of segments to merge are chosen by [`MergePlanner`], which scores them by expected
+/// search latency improvement, reclaimed space and build cost. The best scored merge is executed
+/// first.
 pub struct MergeOptimizer {
     default_segments_number: usize,
     thresholds_config: OptimizerThresholds,
@@ -30,8 +34,11 @@ pub struct MergeOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     hnsw_global_config: HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     quantization_config: Option<QuantizationConfig>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
+    /// Merges planned by the last condition check
+    last_plan: Mutex<Vec<PlannedMerge>>,
 }
 
 impl MergeOptimizer {
@@ -44,6 +51,7 @@ impl MergeOptimizer {
         collection_params: CollectionParams,
         hnsw_config: HnswConfig,
         hnsw_global_config: HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         MergeOptimizer {
@@ -54,8 +62,24 @@ impl MergeOptimizer {
             collection_params,
             hnsw_config,
             hnsw_global_config,
+            chunked_vectors_config,
             quantization_config,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
+            last_plan: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn planner(&self) -> MergePlanner {
+        MergePlanner {
+            default_segments_number: self.default_segments_number,
+            max_segment_size_bytes: self
+                .thresholds_config
+                .max_segment_size_kb
+                .saturating_mul(BYTES_IN_KB),
+            indexing_threshold_bytes: self
+                .thresholds_config
+                .indexing_threshold_kb
+                .saturating_mul(BYTES_IN_KB),
         }
     }
 }
@@ -85,6 +109,10 @@ impl SegmentOptimizer for MergeOptimizer {
         &self.hnsw_global_config
     }
 
+    fn chunked_vectors_config(&self) -> ChunkedVectorsConfig {
+        self.chunked_vectors_config
+    }
+
     fn quantization_config(&self) -> Option<QuantizationConfig> {
         self.quantization_config.clone()
     }
@@ -100,56 +128,40 @@ impl SegmentOptimizer for MergeOptimizer {
     ) -> Vec<SegmentId> {
         let read_segments = segments.read();
 
-        let raw_segments = read_segments
+        let segment_stats = read_segments
             .iter()
             .filter(|(sid, segment)| {
                 matches!(segment, LockedSegment::Original(_)) && !excluded_ids.contains(sid)
             })
-            .collect_vec();
-
-        if raw_segments.len() <= self.default_segments_number {
-            return vec![];
-        }
-        let max_candidates = raw_segments.len() - self.default_segments_number + 2;
-
-        // Find at least top-3 smallest segments to join.
-        // We need 3 segments because in this case we can guarantee that total segments number will be less
-
-        let candidates: Vec<_> = raw_segments
-            .iter()
-            .cloned()
             .filter_map(|(idx, segment)| {
                 let segment_entry = segment.get();
                 let read_segment = segment_entry.read();
-                (read_segment.segment_type() != SegmentType::Special).then_some((
-                    *idx,
-                    read_segment
+                let segment_type = read_segment.segment_type();
+                (segment_type != SegmentType::Special).then(|| SegmentMergeStats {
+                    id: *idx,
+                    size_bytes: read_segment
                         .max_available_vectors_size_in_bytes()
                         .unwrap_or_default(),
-                ))
-            })
-            .sorted_by_key(|(_, size)| *size)
-            .scan(0, |size_sum, (sid, size)| {
-                *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
-                Some((sid, *size_sum))
-            })
-            .take_while(|(_, size)| {
-                *size
-                    < self
-                        .thresholds_config
-                        .max_segment_size_kb
-                        .saturating_mul(BYTES_IN_KB)
+                    available_points: read_segment.available_point_count(),
+                    deleted_points: read_segment.deleted_point_count(),
+                    indexed: segment_type == SegmentType::Indexed,
+                })
             })
-            .take(max_candidates)
-            .map(|x| x.0)
-            .collect();
+            .collect_vec();
 
-        if candidates.len() < 3 {
-            return vec![];
-        }
+        let plan = self.planner().plan(&segment_stats);
+        let candidates = plan
+            .first()
+            .map(|merge| merge.segment_ids.clone())
+            .unwrap_or_default();
+        *self.last_plan.lock() = plan;
         candidates
     }
 
+    fn merge_plan(&self) -> Vec<PlannedMerge> {
+        self.last_plan.lock().clone()
+    }
+
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
         &self.telemetry_durations_aggregator
     }
@@ -0,0 +1,224 @@
+use itertools::Itertools;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+
+use crate::collection_manager::holders::segment_holder::SegmentId;
+
+/// Merging 3 segments instead of 2 guarantees that after the optimization the number of segments
+/// will be less than before.
+pub const MIN_SEGMENTS_TO_MERGE: usize = 3;
+
+/// Statistics of a segment, which may be merged
+#[derive(Debug, Clone)]
+pub struct SegmentMergeStats {
+    pub id: SegmentId,
+    /// Size of available vectors in the segment
+    pub size_bytes: usize,
+    pub available_points: usize,
+    pub deleted_points: usize,
+    /// Whether the segment has vector indexes
+    pub indexed: bool,
+}
+
+impl SegmentMergeStats {
+    /// Estimated size of deleted points, which is freed by rebuilding the segment
+    fn deleted_bytes(&self) -> usize {
+        if self.available_points == 0 {
+            return 0;
+        }
+        (self.size_bytes as u128 * self.deleted_points as u128 / self.available_points as u128)
+            as usize
+    }
+}
+
+/// Merge of a group of segments, scored by its expected benefit
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct PlannedMerge {
+    /// Segments to merge into a single one
+    pub segment_ids: Vec<SegmentId>,
+    /// Expected reduction of search latency, relative to the current one.
+    /// Each search visits every segment, and unindexed segments are searched exhaustively.
+    #[anonymize(false)]
+    pub latency_gain: f64,
+    /// Estimated size of deleted points dropped by the merge
+    pub reclaimed_bytes: usize,
+    /// Size of the merged segment, relative to the max segment size
+    #[anonymize(false)]
+    pub build_cost: f64,
+    /// Priority of the merge, higher is merged first
+    #[anonymize(false)]
+    pub score: f64,
+}
+
+/// Plans merges of small segments, ordered by priority.
+#[derive(Debug, Clone)]
+pub struct MergePlanner {
+    /// Merges are only planned while there are more segments than this
+    pub default_segments_number: usize,
+    pub max_segment_size_bytes: usize,
+    /// Merged segments of at least this size are indexed afterwards
+    pub indexing_threshold_bytes: usize,
+}
+
+impl MergePlanner {
+    /// Score candidate merges and select non-overlapping ones, best first.
+    ///
+    /// Candidates are groups of similarly sized segments, which fit into the max segment size.
+    /// Score of a merge is its latency gain plus the share of reclaimed space, divided by its
+    /// build cost.
+    pub fn plan(&self, segments: &[SegmentMergeStats]) -> Vec<PlannedMerge> {
+        if segments.len() <= self.default_segments_number {
+            return vec![];
+        }
+        // Never merge more segments than required to reach the default number
+        let max_group_size = segments.len() - self.default_segments_number + 2;
+
+        let total_points: usize = segments.iter().map(|s| s.available_points).sum();
+        let total_bytes: usize = segments.iter().map(|s| s.size_bytes).sum();
+
+        let sorted = segments
+            .iter()
+            .sorted_by_key(|segment| segment.size_bytes)
+            .collect_vec();
+
+        let mut candidates = Vec::new();
+        for start in 0..sorted.len() {
+            let mut group_bytes = 0;
+            for end in start..sorted.len().min(start + max_group_size) {
+                group_bytes += sorted[end].size_bytes;
+                if group_bytes >= self.max_segment_size_bytes {
+                    break;
+                }
+                let group = &sorted[start..=end];
+                if group.len() >= MIN_SEGMENTS_TO_MERGE {
+                    candidates.push(self.score_merge(
+                        group,
+                        group_bytes,
+                        segments.len(),
+                        total_points,
+                        total_bytes,
+                    ));
+                }
+            }
+        }
+
+        // Stable sort, so smaller segments are preferred on equal scores
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        let mut planned = Vec::new();
+        let mut used_ids = Vec::new();
+        for candidate in candidates {
+            if candidate.segment_ids.iter().any(|id| used_ids.contains(id)) {
+                continue;
+            }
+            used_ids.extend_from_slice(&candidate.segment_ids);
+            planned.push(candidate);
+        }
+        planned
+    }
+
+    fn score_merge(
+        &self,
+        group: &[&SegmentMergeStats],
+        group_bytes: usize,
+        total_segments: usize,
+        total_points: usize,
+        total_bytes: usize,
+    ) -> PlannedMerge {
+        let mut latency_gain = (group.len() - 1) as f64 / total_segments as f64;
+        if group_bytes >= self.indexing_threshold_bytes && total_points > 0 {
+            let unindexed_points: usize = group
+                .iter()
+                .filter(|segment| !segment.indexed)
+                .map(|segment| segment.available_points)
+                .sum();
+            latency_gain += unindexed_points as f64 / total_points as f64;
+        }
+
+        let reclaimed_bytes = group.iter().map(|segment| segment.deleted_bytes()).sum();
+        let reclaimed_share = if total_bytes > 0 {
+            reclaimed_bytes as f64 / total_bytes as f64
+        } else {
+            0.0
+        };
+
+        let build_cost = group_bytes as f64 / self.max_segment_size_bytes.max(1) as f64;
+
+        PlannedMerge {
+            segment_ids: group.iter().map(|segment| segment.id).collect(),
+            latency_gain,
+            reclaimed_bytes,
+            build_cost,
+            score: (latency_gain + reclaimed_share) / (1.0 + build_cost),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(id: SegmentId, size_bytes: usize, deleted_points: usize) -> SegmentMergeStats {
+        SegmentMergeStats {
+            id,
+            size_bytes,
+            available_points: size_bytes / 10,
+            deleted_points,
+            indexed: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_plan() {
+        let planner = MergePlanner {
+            default_segments_number: 6,
+            max_segment_size_bytes: 10_000,
+            indexing_threshold_bytes: usize::MAX,
+        };
+
+        // Not enough segments to merge
+        assert!(
+            planner
+                .plan(&[stats(0, 100, 0), stats(1, 100, 0)])
+                .is_empty()
+        );
+
+        let segments = [
+            stats(0, 1_000, 0),
+            stats(1, 1_000, 0),
+            stats(2, 1_000, 0),
+            stats(3, 1_000, 100),
+            stats(4, 1_000, 100),
+            stats(5, 1_000, 100),
+            stats(6, 9_500, 0),
+        ];
+        let plan = planner.plan(&segments);
+
+        // Segments with deleted points are merged first
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].segment_ids, vec![3, 4, 5]);
+        assert_eq!(plan[0].reclaimed_bytes, 3_000);
+        assert_eq!(plan[1].segment_ids, vec![0, 1, 2]);
+        assert_eq!(plan[1].reclaimed_bytes, 0);
+        assert!(plan[0].score > plan[1].score);
+
+        // The largest segment does not fit into any merge
+        assert!(plan.iter().all(|merge| !merge.segment_ids.contains(&6)));
+    }
+
+    #[test]
+    fn test_merge_plan_indexing_gain() {
+        let planner = MergePlanner {
+            default_segments_number: 1,
+            max_segment_size_bytes: 10_000,
+            indexing_threshold_bytes: 3_000,
+        };
+
+        let segments = [stats(0, 1_000, 0), stats(1, 1_000, 0), stats(2, 1_000, 0)];
+        let plan = planner.plan(&segments);
+        assert_eq!(plan.len(), 1);
+        // Merged segment gets indexed, so no points are searched exhaustively anymore
+        assert!((plan[0].latency_gain - (2.0 / 3.0 + 1.0)).abs() < 1e-9);
+    }
+}
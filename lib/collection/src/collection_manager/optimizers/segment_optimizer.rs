@@ -11,7 +11,7 @@ use common::disk::dir_disk_size;
 use io::storage_version::StorageVersion;
 use itertools::Itertools;
 use parking_lot::lock_api::RwLockWriteGuard;
-use parking_lot::{Mutex, RwLockUpgradableReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, check_process_stopped};
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
@@ -19,16 +19,19 @@ use segment::common::operation_time_statistics::{
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
 use segment::segment::{Segment, SegmentVersion};
-use segment::segment_constructor::build_segment;
+use segment::segment_constructor::build_segment_with_chunked_vectors_config;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, HnswGlobalConfig, Indexes, QuantizationConfig, SegmentConfig, VectorStorageType,
+    HnswConfig, HnswGlobalConfig, Indexes, IvfConfig, QuantizationConfig, SegmentConfig,
+    VectorStorageType,
 };
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
 use crate::collection_manager::holders::proxy_segment::{self, ProxyIndexChange, ProxySegment};
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
+use crate::collection_manager::optimizers::merge_planner::PlannedMerge;
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -69,6 +72,9 @@ pub trait SegmentOptimizer {
     /// Get HNSW global config
     fn hnsw_global_config(&self) -> &HnswGlobalConfig;
 
+    /// Get memory layout of in-memory vector storages
+    fn chunked_vectors_config(&self) -> ChunkedVectorsConfig;
+
     /// Get quantization config
     fn quantization_config(&self) -> Option<QuantizationConfig>;
 
@@ -84,6 +90,95 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator>;
 
+    /// Merges planned by the last condition check, in the order of execution
+    fn merge_plan(&self) -> Vec<PlannedMerge> {
+        Vec::new()
+    }
+
+    /// Apply the optimization to the given segments in place, without rebuilding them
+    ///
+    /// Returns number of points in the optimized segments, or `None` if the segments have to be
+    /// rebuilt, which is the default.
+    fn optimize_in_place(
+        &self,
+        _segments: &LockedSegmentHolder,
+        _ids: &[SegmentId],
+        _permit: &ResourcePermit,
+        _stopped: &AtomicBool,
+    ) -> CollectionResult<Option<usize>> {
+        Ok(None)
+    }
+
+    /// Apply `optimize` to the segment in place, while the segment is proxied
+    ///
+    /// The proxy serves reads and collects updates in a temp segment, so `optimize` may take
+    /// long, as long as it only locks the segment for writing briefly.
+    /// Returns number of available points in the segment.
+    fn optimize_proxied_in_place(
+        &self,
+        segments: &LockedSegmentHolder,
+        segments_lock: RwLockUpgradableReadGuard<'_, SegmentHolder>,
+        segment_id: SegmentId,
+        segment: Arc<RwLock<Segment>>,
+        optimize: impl FnOnce(&RwLock<Segment>) -> OperationResult<()>,
+    ) -> CollectionResult<usize>
+    where
+        Self: Sized,
+    {
+        let hw_counter = HardwareCounterCell::disposable(); // Internal operation, no measurement needed!
+
+        let tmp_segment = self.temp_segment(false)?;
+        let mut proxy = ProxySegment::new(
+            LockedSegment::Original(segment.clone()),
+            tmp_segment.clone(),
+            proxy_segment::LockedRmSet::default(),
+            proxy_segment::LockedIndexChanges::default(),
+        );
+        proxy.replicate_field_indexes(0, &hw_counter)?;
+
+        // Save segment version once all payload indices have been converted
+        // If this ends up not being saved due to a crash, the segment will not be used
+        match &tmp_segment {
+            LockedSegment::Original(tmp_segment) => {
+                SegmentVersion::save(&tmp_segment.read().current_path)?;
+            }
+            LockedSegment::Proxy(_) => unreachable!(),
+        }
+
+        {
+            let mut write_segments = RwLockUpgradableReadGuard::upgrade(segments_lock);
+            // Indexes might have been changed in the meantime
+            proxy.replicate_field_indexes(0, &hw_counter)?;
+            write_segments.replace(segment_id, proxy)?;
+        }
+
+        // SLOW PART: optimize the segment, while the proxy serves reads and collects updates
+        let result = optimize(&segment);
+
+        // Put the segment back in place of the proxy, with all updates propagated
+        let segments_lock = segments.upgradable_read();
+        let proxy = segments_lock
+            .get(segment_id)
+            .cloned()
+            .expect("proxy of optimized segment must be in the segment holder");
+        let segments_lock =
+            match SegmentHolder::try_unproxy_segment(segments_lock, segment_id, proxy) {
+                Ok(segments_lock) | Err(segments_lock) => segments_lock,
+            };
+
+        // Append a temp segment to collection if it is not empty or there is no other appendable segment
+        let mut write_segments = RwLockUpgradableReadGuard::upgrade(segments_lock);
+        if !write_segments.has_appendable_segment() || !tmp_segment.get().read().is_empty() {
+            write_segments.add_new_locked(tmp_segment);
+        } else {
+            drop(write_segments);
+            tmp_segment.drop_data()?;
+        }
+
+        result?;
+        Ok(segment.read().available_point_count())
+    }
+
     /// Build temp segment
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
@@ -93,11 +188,14 @@ pub trait SegmentOptimizer {
             sparse_vector_data: collection_params.to_sparse_vector_data()?,
             payload_storage_type: collection_params.payload_storage_type(),
         };
-        Ok(LockedSegment::new(build_segment(
-            self.segments_path(),
-            &config,
-            save_version,
-        )?))
+        Ok(LockedSegment::new(
+            build_segment_with_chunked_vectors_config(
+                self.segments_path(),
+                &config,
+                self.chunked_vectors_config(),
+                save_version,
+            )?,
+        ))
     }
 
     /// Returns error if segment size is larger than available disk space
@@ -244,19 +342,42 @@ pub trait SegmentOptimizer {
             collection_params.to_base_vector_data(collection_quantization.as_ref())?;
         let mut sparse_vector_data = collection_params.to_sparse_vector_data()?;
 
-        // If indexing, change to HNSW index and quantization
+        // If indexing, change to HNSW, IVF or DiskANN index and quantization
         if threshold_is_indexed {
             let collection_hnsw = self.hnsw_config();
             vector_data.iter_mut().for_each(|(vector_name, config)| {
-                // Assign HNSW index
-                let param_hnsw = collection_params
+                // Assign IVF index if configured and supported by the vector
+                let param_ivf = collection_params
                     .vectors
                     .get_params(vector_name)
-                    .and_then(|params| params.hnsw_config);
-                let vector_hnsw = param_hnsw
-                    .and_then(|c| c.update(collection_hnsw).ok())
-                    .unwrap_or_else(|| collection_hnsw.clone());
-                config.index = Indexes::Hnsw(vector_hnsw);
+                    .and_then(|params| params.ivf_config)
+                    .filter(|_| {
+                        IvfConfig::supports_distance(config.distance)
+                            && config.multivector_config.is_none()
+                    });
+
+                // Or assign DiskANN index if configured and supported by the vector
+                let param_disk_ann = collection_params
+                    .vectors
+                    .get_params(vector_name)
+                    .and_then(|params| params.disk_ann_config)
+                    .filter(|_| config.multivector_config.is_none());
+
+                // Otherwise assign HNSW index
+                config.index = match (param_ivf, param_disk_ann) {
+                    (Some(vector_ivf), _) => Indexes::Ivf(vector_ivf),
+                    (None, Some(vector_disk_ann)) => Indexes::DiskAnn(vector_disk_ann),
+                    (None, None) => {
+                        let param_hnsw = collection_params
+                            .vectors
+                            .get_params(vector_name)
+                            .and_then(|params| params.hnsw_config);
+                        let vector_hnsw = param_hnsw
+                            .and_then(|c| c.update(collection_hnsw).ok())
+                            .unwrap_or_else(|| collection_hnsw.clone());
+                        Indexes::Hnsw(vector_hnsw)
+                    }
+                };
 
                 // Assign quantization config
                 let param_quantization = collection_params
@@ -332,6 +453,7 @@ pub trait SegmentOptimizer {
             self.temp_path(),
             &optimized_config,
             self.hnsw_global_config(),
+            self.chunked_vectors_config(),
         )?)
     }
 
@@ -461,6 +583,10 @@ pub trait SegmentOptimizer {
             segment_builder.set_defragment_keys(defragmentation_keys.into_iter().collect());
         }
 
+        // Copying data is IO-heavy, give way to searches while they are slow
+        let io_throttle = resource_budget.optimizer_io_throttle();
+        segment_builder.set_io_throttle(io_throttle.clone());
+
         {
             let segment_guards = segments.iter().map(|segment| segment.read()).collect_vec();
             segment_builder.update(
@@ -487,6 +613,7 @@ pub trait SegmentOptimizer {
 
         // Before switching from IO to CPU, make sure that vectors cache is heated up,
         // so indexing process won't need to wait for IO.
+        io_throttle.pause();
         segment_builder.populate_vector_storages()?;
 
         // 000 - acquired
@@ -613,6 +740,11 @@ pub trait SegmentOptimizer {
         let mut timer = ScopeDurationMeasurer::new(self.get_telemetry_counter());
         timer.set_success(false);
 
+        if let Some(point_count) = self.optimize_in_place(&segments, &ids, &permit, stopped)? {
+            timer.set_success(true);
+            return Ok(point_count);
+        }
+
         // On the one hand - we want to check consistently if all provided segments are
         // available for optimization (not already under one) and we want to do it before creating a temp segment
         // which is an expensive operation. So we can't not unlock `segments` after the check and before the insert.
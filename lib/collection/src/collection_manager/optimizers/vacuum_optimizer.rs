@@ -1,14 +1,20 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
+use common::budget::ResourcePermit;
 use ordered_float::OrderedFloat;
 use parking_lot::Mutex;
+use segment::common::operation_error::check_process_stopped;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::VectorIndex;
-use segment::types::{HnswConfig, HnswGlobalConfig, QuantizationConfig, SegmentType};
+use segment::types::{
+    HnswConfig, HnswGlobalConfig, QuantizationConfig, SegmentType, VectorNameBuf,
+};
 use segment::vector_storage::VectorStorage;
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
@@ -17,6 +23,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
+use crate::operations::types::CollectionResult;
 
 /// Optimizer which looks for segments with high amount of soft-deleted points or vectors
 ///
@@ -24,6 +31,9 @@ use crate::config::CollectionParams;
 /// results in the index slowly breaking apart, and unnecessary storage usage.
 ///
 /// This optimizer will look for the worst segment to rebuilt the index and minimize storage usage.
+///
+/// Segments with fewer deletions, which don't need to be rebuilt yet, get their HNSW graphs healed
+/// in place instead, so deleted points don't degrade search quality in the meantime.
 pub struct VacuumOptimizer {
     deleted_threshold: f64,
     min_vectors_number: usize,
@@ -33,6 +43,7 @@ pub struct VacuumOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     hnsw_global_config: HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     quantization_config: Option<QuantizationConfig>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
@@ -48,6 +59,7 @@ impl VacuumOptimizer {
         collection_params: CollectionParams,
         hnsw_config: HnswConfig,
         hnsw_global_config: HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
         quantization_config: Option<QuantizationConfig>,
     ) -> Self {
         VacuumOptimizer {
@@ -60,6 +72,7 @@ impl VacuumOptimizer {
             hnsw_config,
             quantization_config,
             hnsw_global_config,
+            chunked_vectors_config,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
@@ -70,7 +83,7 @@ impl VacuumOptimizer {
         excluded_ids: &HashSet<SegmentId>,
     ) -> Option<SegmentId> {
         let segments_read_guard = segments.read();
-        segments_read_guard
+        let worst_littered = segments_read_guard
             .iter()
             // Excluded externally, might already be scheduled for optimization
             .filter(|(idx, _segment)| !excluded_ids.contains(idx))
@@ -84,7 +97,35 @@ impl VacuumOptimizer {
                     .map(|ratio| (*idx, ratio))
             })
             .max_by_key(|(_, ratio)| OrderedFloat(*ratio))
-            .map(|(idx, _)| idx)
+            .map(|(idx, _)| idx);
+
+        // Rebuilding littered segments takes precedence over healing
+        worst_littered.or_else(|| {
+            segments_read_guard
+                .iter()
+                .filter(|(idx, _segment)| !excluded_ids.contains(idx))
+                .find(|(_, segment)| !self.hnsw_graphs_to_heal(segment).is_empty())
+                .map(|(idx, _)| *idx)
+        })
+    }
+
+    /// Vectors of the segment with HNSW graphs to heal in place
+    ///
+    /// Only original, non-special segments are healed.
+    fn hnsw_graphs_to_heal(&self, segment: &LockedSegment) -> Vec<VectorNameBuf> {
+        let segment_entry = match segment {
+            LockedSegment::Original(segment) => segment,
+            LockedSegment::Proxy(_) => return Vec::new(),
+        };
+        let read_segment = segment_entry.read();
+        if read_segment.segment_type() == SegmentType::Special {
+            return Vec::new();
+        }
+
+        read_segment.hnsw_graphs_to_heal(
+            self.hnsw_global_config.deleted_healing_threshold,
+            self.min_vectors_number,
+        )
     }
 
     /// Calculate littered ratio for segment on point level
@@ -190,6 +231,10 @@ impl SegmentOptimizer for VacuumOptimizer {
         &self.hnsw_global_config
     }
 
+    fn chunked_vectors_config(&self) -> ChunkedVectorsConfig {
+        self.chunked_vectors_config
+    }
+
     fn quantization_config(&self) -> Option<QuantizationConfig> {
         self.quantization_config.clone()
     }
@@ -211,6 +256,56 @@ impl SegmentOptimizer for VacuumOptimizer {
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
         &self.telemetry_durations_aggregator
     }
+
+    /// Heal HNSW graphs of the segment in place, if it doesn't have to be rebuilt yet
+    ///
+    /// The segment is proxied while healing, so it stays searchable and collects updates.
+    fn optimize_in_place(
+        &self,
+        segments: &LockedSegmentHolder,
+        ids: &[SegmentId],
+        permit: &ResourcePermit,
+        stopped: &AtomicBool,
+    ) -> CollectionResult<Option<usize>> {
+        let &[segment_id] = ids else {
+            return Ok(None);
+        };
+
+        let segments_lock = segments.upgradable_read();
+        let Some(locked_segment) = segments_lock.get(segment_id).cloned() else {
+            return Ok(None);
+        };
+        let LockedSegment::Original(segment) = &locked_segment else {
+            return Ok(None);
+        };
+        if self.littered_ratio_segment(&locked_segment).is_some()
+            || self.littered_vectors_index_ratio(&locked_segment).is_some()
+        {
+            return Ok(None);
+        }
+        let vector_names = self.hnsw_graphs_to_heal(&locked_segment);
+        if vector_names.is_empty() {
+            return Ok(None);
+        }
+
+        check_process_stopped(stopped)?;
+
+        let max_threads = permit.num_cpus as usize;
+        let point_count = self.optimize_proxied_in_place(
+            segments,
+            segments_lock,
+            segment_id,
+            segment.clone(),
+            |segment| {
+                let healed =
+                    segment
+                        .read()
+                        .heal_hnsw_graphs(&vector_names, max_threads, stopped)?;
+                segment.write().apply_healed_hnsw_graphs(healed)
+            },
+        )?;
+        Ok(Some(point_count))
+    }
 }
 
 #[cfg(test)]
@@ -334,6 +429,7 @@ mod tests {
             },
             Default::default(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -469,6 +565,8 @@ mod tests {
             on_disk: None,
             payload_m: None,
             copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -484,6 +582,7 @@ mod tests {
             collection_params.clone(),
             hnsw_config.clone(),
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
         let vacuum_optimizer = VacuumOptimizer::new(
@@ -495,6 +594,7 @@ mod tests {
             collection_params,
             hnsw_config,
             HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
             Default::default(),
         );
 
@@ -656,4 +756,153 @@ mod tests {
                 });
             });
     }
+
+    /// Segments with few deletions get their HNSW graphs healed in place, instead of being
+    /// rebuilt
+    #[test]
+    fn test_vacuum_heals_hnsw_graphs() {
+        let (point_count, vector1_dim, vector2_dim) = (1000, 10, 20);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size_kb: usize::MAX,
+            memmap_threshold_kb: usize::MAX,
+            indexing_threshold_kb: 10,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Multi(BTreeMap::from([
+                (
+                    VECTOR1_NAME.to_owned(),
+                    VectorParamsBuilder::new(vector1_dim, Distance::Dot).build(),
+                ),
+                (
+                    VECTOR2_NAME.to_owned(),
+                    VectorParamsBuilder::new(vector2_dim, Distance::Dot).build(),
+                ),
+            ])),
+            ..CollectionParams::empty()
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+        let segment_id = holder.add_new(random_multi_vec_segment(
+            dir.path(),
+            100,
+            point_count,
+            vector1_dim as usize,
+            vector2_dim as usize,
+        ));
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let hnsw_config = HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 10,
+            max_indexing_threads: 0,
+            on_disk: None,
+            payload_m: None,
+            copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
+        };
+
+        let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+        let budget = ResourceBudget::new(permit_cpu_count, permit_cpu_count);
+
+        let index_optimizer = IndexingOptimizer::new(
+            2,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params.clone(),
+            hnsw_config.clone(),
+            HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
+            Default::default(),
+        );
+        let vacuum_optimizer = VacuumOptimizer::new(
+            0.2,
+            5,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            hnsw_config,
+            HnswGlobalConfig {
+                deleted_healing_threshold: 0.05,
+                ..HnswGlobalConfig::default()
+            },
+            ChunkedVectorsConfig::default(),
+            Default::default(),
+        );
+
+        let permit = budget.try_acquire(0, permit_cpu_count).unwrap();
+        index_optimizer
+            .optimize(
+                locked_holder.clone(),
+                vec![segment_id],
+                permit,
+                budget.clone(),
+                &false.into(),
+            )
+            .unwrap();
+        let (indexed_segment_id, indexed_segment) = locked_holder
+            .read()
+            .iter()
+            .find_map(|(idx, segment)| match segment {
+                LockedSegment::Original(s) if s.read().total_point_count() > 0 => {
+                    Some((*idx, s.clone()))
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        // Delete 10% of points, below the vacuum threshold, but above the healing one
+        let hw_counter = HardwareCounterCell::new();
+        {
+            let mut segment = indexed_segment.write();
+            let points_to_delete = segment
+                .iter_points()
+                .enumerate()
+                .filter_map(|(i, point_id)| (i % 10 == 3).then_some(point_id))
+                .collect_vec();
+            for &point_id in &points_to_delete {
+                segment.delete_point(201, point_id, &hw_counter).unwrap();
+            }
+            assert_eq!(segment.hnsw_graphs_to_heal(0.05, 5).len(), 2);
+        }
+
+        let suggested_to_optimize =
+            vacuum_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize, vec![indexed_segment_id]);
+
+        let permit = budget.try_acquire(0, permit_cpu_count).unwrap();
+        let changed = vacuum_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                permit,
+                budget.clone(),
+                &false.into(),
+            )
+            .unwrap();
+        assert_eq!(changed, point_count as usize - point_count as usize / 10);
+
+        // Segment is kept, and its graphs don't need healing anymore
+        assert!(
+            matches!(
+                locked_holder.read().get(indexed_segment_id),
+                Some(LockedSegment::Original(_)),
+            ),
+            "healed segment should be kept",
+        );
+        assert!(
+            indexed_segment
+                .read()
+                .hnsw_graphs_to_heal(0.05, 5)
+                .is_empty()
+        );
+        let suggested_to_optimize =
+            vacuum_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+    }
 }
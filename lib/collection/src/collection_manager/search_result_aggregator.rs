@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::cmp::{Ordering, max};
 
 use ahash::{AHashMap, AHashSet};
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
@@ -8,32 +8,74 @@ use segment::types::{PointIdType, ScoredPoint, SeqNumberType};
 /// Avoid excessive memory allocation and allocation failures on huge limits
 const LARGEST_REASONABLE_ALLOCATION_SIZE: usize = 1_048_576;
 
+/// Scored point in the aggregation queue
+struct AggregatedPoint {
+    point: ScoredPoint,
+    /// Break ties between equal scores by point ID, for a stable output in deterministic mode
+    break_ties: bool,
+}
+
+impl Ord for AggregatedPoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ordering = self.point.cmp(&other.point);
+        if self.break_ties {
+            ordering.then_with(|| self.point.id.cmp(&other.point.id))
+        } else {
+            ordering
+        }
+    }
+}
+
+impl PartialOrd for AggregatedPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Eq for AggregatedPoint {}
+
+impl PartialEq for AggregatedPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
 pub struct SearchResultAggregator {
-    queue: FixedLengthPriorityQueue<ScoredPoint>,
+    queue: FixedLengthPriorityQueue<AggregatedPoint>,
     seen: AHashSet<PointIdType>, // Point ids seen
+    break_ties: bool,
 }
 
 impl SearchResultAggregator {
-    pub fn new(limit: usize) -> Self {
+    /// If `break_ties` is set, points with equal scores are ordered by ID
+    pub fn new(limit: usize, break_ties: bool) -> Self {
         SearchResultAggregator {
             queue: FixedLengthPriorityQueue::new(limit),
             seen: AHashSet::with_capacity(limit.min(LARGEST_REASONABLE_ALLOCATION_SIZE)),
+            break_ties,
         }
     }
 
     pub fn push(&mut self, point: ScoredPoint) {
         // track new value in `queue`
         if self.seen.insert(point.id) {
-            self.queue.push(point);
+            self.queue.push(AggregatedPoint {
+                point,
+                break_ties: self.break_ties,
+            });
         }
     }
 
     pub fn into_vec(self) -> Vec<ScoredPoint> {
-        self.queue.into_sorted_vec()
+        self.queue
+            .into_sorted_vec()
+            .into_iter()
+            .map(|aggregated| aggregated.point)
+            .collect()
     }
 
     pub fn lowest(&self) -> Option<&ScoredPoint> {
-        self.queue.top()
+        self.queue.top().map(|aggregated| &aggregated.point)
     }
 }
 
@@ -45,10 +87,11 @@ pub struct BatchResultAggregator {
 }
 
 impl BatchResultAggregator {
-    pub fn new(tops: impl Iterator<Item = usize>) -> Self {
+    /// Takes the limit of each batched request, and whether its ties are broken by point ID
+    pub fn new(tops: impl Iterator<Item = (usize, bool)>) -> Self {
         let mut merged_results_per_batch = vec![];
-        for top in tops {
-            merged_results_per_batch.push(SearchResultAggregator::new(top));
+        for (top, break_ties) in tops {
+            merged_results_per_batch.push(SearchResultAggregator::new(top, break_ties));
         }
 
         BatchResultAggregator {
@@ -81,12 +81,14 @@ impl SegmentsSearcher {
     /// # Arguments
     /// * `search_result` - `[segment_size x batch_size]`
     /// * `limits` - `[batch_size]` - how many results to return for each batched request
+    /// * `break_ties` - `[batch_size]` - whether to order points with equal scores by ID, for each batched request
     /// * `further_searches` - `[segment_size x batch_size]` - whether we can search further in the segment
     ///
     /// Returns batch results aggregated by `[batch_size]` and list of queries, grouped by segment to re-run
     pub(crate) fn process_search_result_step1(
         search_result: BatchSearchResult,
         limits: Vec<usize>,
+        break_ties: &[bool],
         further_results: &[Vec<bool>],
     ) -> (
         BatchResultAggregator,
@@ -105,7 +107,8 @@ impl SegmentsSearcher {
         // In that case, we need to re-run the search without sampling on that segment.
 
         // Initialize result aggregators for each batched request
-        let mut result_aggregator = BatchResultAggregator::new(limits.iter().copied());
+        let mut result_aggregator =
+            BatchResultAggregator::new(limits.iter().copied().zip(break_ties.iter().copied()));
         result_aggregator.update_point_versions(search_result.iter().flatten().flatten());
 
         // Therefore we need to track the lowest scored element per segment for each batch
@@ -301,6 +304,11 @@ impl SegmentsSearcher {
                 .iter()
                 .map(|request| request.limit + request.offset)
                 .collect(),
+            &batch_request
+                .searches
+                .iter()
+                .map(|request| request.params.is_some_and(|params| params.deterministic))
+                .collect::<Vec<_>>(),
             &further_results,
         );
         // The second step of the search is to re-run the search without sampling on some segments
@@ -474,6 +482,50 @@ impl SegmentsSearcher {
         Ok(point_records)
     }
 
+    /// Latest versions of the points, which exist in any of the segments
+    ///
+    /// Only id trackers are read, payloads and vectors of the points are not loaded.
+    pub async fn point_versions(
+        segments: LockedSegmentHolder,
+        points: &[PointIdType],
+        runtime_handle: &Handle,
+    ) -> CollectionResult<AHashMap<PointIdType, SeqNumberType>> {
+        let stopping_guard = StoppingGuard::new();
+        runtime_handle
+            .spawn_blocking({
+                let points = points.to_vec();
+                let is_stopped = stopping_guard.get_is_stopped();
+                move || Self::point_versions_blocking(segments, &points, &is_stopped)
+            })
+            .await?
+    }
+
+    pub fn point_versions_blocking(
+        segments: LockedSegmentHolder,
+        points: &[PointIdType],
+        is_stopped: &AtomicBool,
+    ) -> CollectionResult<AHashMap<PointIdType, SeqNumberType>> {
+        let mut point_versions: AHashMap<PointIdType, SeqNumberType> = Default::default();
+
+        segments
+            .read()
+            .read_points(points, is_stopped, |id, segment| {
+                let version = segment.point_version(id).ok_or_else(|| {
+                    OperationError::service_error(format!("No version for point {id}"))
+                })?;
+
+                // Point might be in multiple segments, while it's being moved by an optimizer
+                point_versions
+                    .entry(id)
+                    .and_modify(|latest| *latest = (*latest).max(version))
+                    .or_insert(version);
+
+                Ok(true)
+            })?;
+
+        Ok(point_versions)
+    }
+
     pub async fn read_filtered(
         segments: LockedSegmentHolder,
         filter: Option<&Filter>,
@@ -542,7 +594,7 @@ impl SegmentsSearcher {
         }
 
         // use aggregator with only one "batch"
-        let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+        let mut aggregator = BatchResultAggregator::new(std::iter::once((limit, false)));
         aggregator.update_point_versions(segments_results.iter().flatten());
         aggregator.update_batch_results(0, segments_results.into_iter().flatten());
         let top =
@@ -759,7 +811,7 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &VectorName) -> Op
         .vector_data
         .get(vector_name)
         .and_then(|config| match &config.index {
-            Indexes::Plain {} => None,
+            Indexes::Plain {} | Indexes::Ivf(_) | Indexes::DiskAnn(_) => None,
             Indexes::Hnsw(hnsw) => Some(hnsw),
         })
         .map(|hnsw| hnsw.ef_construct)
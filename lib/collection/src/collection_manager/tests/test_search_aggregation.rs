@@ -84,6 +84,7 @@ fn test_aggregation_of_batch_search_results() {
     let (aggregator, re_request) = SegmentsSearcher::process_search_result_step1(
         search_results,
         result_limits,
+        &[false, false],
         &further_results,
     );
 
@@ -136,6 +137,44 @@ fn test_batch_search_aggregation_high_limit() {
     let (_aggregator, _re_request) = SegmentsSearcher::process_search_result_step1(
         search_results,
         result_limits,
+        &[false, false],
         &further_results,
     );
 }
+
+#[test]
+fn test_aggregation_breaks_ties_by_id() {
+    let segment_results = || {
+        vec![
+            vec![vec![score_point(3, 0.5, 1), score_point(1, 0.5, 1)]],
+            vec![vec![score_point(2, 0.5, 1), score_point(4, 0.5, 1)]],
+        ]
+    };
+    let further_results = vec![vec![false], vec![false]];
+
+    // Points with equal scores are ordered by ID, regardless of the order of segments
+    for search_results in [
+        segment_results(),
+        segment_results().into_iter().rev().collect(),
+    ] {
+        let (aggregator, _re_request) = SegmentsSearcher::process_search_result_step1(
+            search_results,
+            vec![3],
+            &[true],
+            &further_results,
+        );
+
+        let ids: Vec<_> = aggregator.into_topk()[0]
+            .iter()
+            .map(|point| point.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                PointIdType::NumId(4),
+                PointIdType::NumId(3),
+                PointIdType::NumId(2),
+            ],
+        );
+    }
+}
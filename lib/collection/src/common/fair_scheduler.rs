@@ -0,0 +1,248 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::types::ShardKey;
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+
+/// Tenant of a search request, requests without a single shard key share one tenant
+pub type TenantKey = Option<ShardKey>;
+
+/// Limits the number of concurrent searches in a collection, scheduling queued searches of
+/// different tenants in round-robin order.
+///
+/// A burst of searches of one tenant only queues up behind its own searches, so it can't
+/// monopolize the search pool.
+#[derive(Debug)]
+pub struct FairScheduler {
+    max_concurrent: usize,
+    state: Mutex<SchedulerState>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    running: usize,
+    /// Queued searches of each tenant
+    queues: AHashMap<TenantKey, VecDeque<oneshot::Sender<FairSchedulerPermit>>>,
+    /// Tenants with queued searches, in the order of scheduling
+    order: VecDeque<TenantKey>,
+}
+
+/// Permit to run a search, releasing it schedules the next queued search
+#[derive(Debug)]
+pub struct FairSchedulerPermit {
+    scheduler: Arc<FairScheduler>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct FairSchedulerTelemetry {
+    /// Maximum number of concurrent searches
+    #[anonymize(false)]
+    pub max_concurrent: usize,
+    /// Number of currently running searches
+    #[anonymize(false)]
+    pub running: usize,
+    /// Queued searches of tenants which are waiting for their turn
+    pub queued: Vec<TenantQueueTelemetry>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct TenantQueueTelemetry {
+    /// Shard key of the tenant, not set for searches without a single shard key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Number of queued searches
+    #[anonymize(false)]
+    pub queue_depth: usize,
+}
+
+impl FairScheduler {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            state: Default::default(),
+        }
+    }
+
+    /// Tenant of searches with the given shard selection
+    pub fn tenant_key(shard_selection: &ShardSelectorInternal) -> TenantKey {
+        match shard_selection {
+            ShardSelectorInternal::ShardKey(shard_key) => Some(shard_key.clone()),
+            ShardSelectorInternal::ShardKeys(shard_keys) if shard_keys.len() == 1 => {
+                shard_keys.first().cloned()
+            }
+            ShardSelectorInternal::Empty
+            | ShardSelectorInternal::All
+            | ShardSelectorInternal::ShardKeys(_)
+            | ShardSelectorInternal::ShardId(_) => None,
+        }
+    }
+
+    /// Wait for the turn of the tenant to run a search
+    ///
+    /// Cancel safe, a slot handed over to a cancelled waiter is passed on to the next one.
+    pub async fn acquire(self: &Arc<Self>, tenant: TenantKey) -> FairSchedulerPermit {
+        let receiver = {
+            let mut state_guard = self.state.lock();
+            let state = &mut *state_guard;
+            if state.running < self.max_concurrent && state.order.is_empty() {
+                state.running += 1;
+                return FairSchedulerPermit {
+                    scheduler: self.clone(),
+                };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let queue = state.queues.entry(tenant.clone()).or_default();
+            queue.push_back(sender);
+            if queue.len() == 1 {
+                state.order.push_back(tenant);
+            }
+            receiver
+        };
+
+        receiver
+            .await
+            .expect("queued searches are never dropped without a permit")
+    }
+
+    /// Take the next waiting search in round-robin order of tenants
+    ///
+    /// Releases the running slot if there are no more waiting searches.
+    fn next_waiter(&self) -> Option<oneshot::Sender<FairSchedulerPermit>> {
+        let mut state_guard = self.state.lock();
+        let state = &mut *state_guard;
+        while let Some(tenant) = state.order.pop_front() {
+            let Some(queue) = state.queues.get_mut(&tenant) else {
+                continue;
+            };
+            let waiter = queue.pop_front();
+            if queue.is_empty() {
+                state.queues.remove(&tenant);
+            } else {
+                state.order.push_back(tenant);
+            }
+            match waiter {
+                // Skip waiters which are already cancelled
+                Some(waiter) if !waiter.is_closed() => return Some(waiter),
+                _ => continue,
+            }
+        }
+        state.running -= 1;
+        None
+    }
+
+    pub fn get_telemetry_data(&self) -> FairSchedulerTelemetry {
+        let state = self.state.lock();
+        FairSchedulerTelemetry {
+            max_concurrent: self.max_concurrent,
+            running: state.running,
+            queued: state
+                .order
+                .iter()
+                .map(|tenant| TenantQueueTelemetry {
+                    shard_key: tenant.clone(),
+                    queue_depth: state.queues.get(tenant).map_or(0, VecDeque::len),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Drop for FairSchedulerPermit {
+    /// Hand the running slot over to the next tenant in turn
+    fn drop(&mut self) {
+        let Some(waiter) = self.scheduler.next_waiter() else {
+            return;
+        };
+        let permit = FairSchedulerPermit {
+            scheduler: self.scheduler.clone(),
+        };
+        // If the waiter was cancelled in the meantime, the returned permit is dropped here,
+        // which hands the slot over to the next waiter
+        let _ = waiter.send(permit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fair_scheduler_round_robin() {
+        let scheduler = Arc::new(FairScheduler::new(1));
+        let tenant_a: TenantKey = Some("a".into());
+        let tenant_b: TenantKey = Some("b".into());
+
+        let running = scheduler.acquire(tenant_a.clone()).await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Burst of tenant `a`, followed by a single search of tenant `b`
+        let mut handles = Vec::new();
+        for (i, tenant) in [&tenant_a, &tenant_a, &tenant_a, &tenant_b]
+            .into_iter()
+            .enumerate()
+        {
+            let scheduler = scheduler.clone();
+            let order = order.clone();
+            let tenant = tenant.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = scheduler.acquire(tenant.clone()).await;
+                order.lock().push(tenant);
+            }));
+            // Make sure searches are queued in order
+            while scheduler
+                .get_telemetry_data()
+                .queued
+                .iter()
+                .map(|q| q.queue_depth)
+                .sum::<usize>()
+                <= i
+            {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+
+        let telemetry = scheduler.get_telemetry_data();
+        assert_eq!(telemetry.running, 1);
+        assert_eq!(telemetry.queued.len(), 2);
+        assert_eq!(telemetry.queued[0].queue_depth, 3);
+        assert_eq!(telemetry.queued[1].queue_depth, 1);
+
+        drop(running);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        // Tenant `b` does not wait for the whole burst of tenant `a`
+        assert_eq!(
+            *order.lock(),
+            vec![tenant_a.clone(), tenant_b, tenant_a.clone(), tenant_a],
+        );
+        assert_eq!(scheduler.get_telemetry_data().running, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fair_scheduler_cancelled_waiter() {
+        let scheduler = Arc::new(FairScheduler::new(1));
+        let running = scheduler.acquire(None).await;
+
+        // Cancelled while waiting
+        let waiting =
+            tokio::time::timeout(Duration::from_millis(10), scheduler.acquire(None)).await;
+        assert!(waiting.is_err());
+
+        drop(running);
+        assert_eq!(scheduler.get_telemetry_data().running, 0);
+        let _permit = scheduler.acquire(None).await;
+        assert_eq!(scheduler.get_telemetry_data().running, 1);
+    }
+}
@@ -1,9 +1,11 @@
 pub mod batching;
 pub mod collection_size_stats;
 pub mod eta_calculator;
+pub mod fair_scheduler;
 pub mod fetch_vectors;
 pub mod file_utils;
 pub mod is_ready;
+pub mod partial_results;
 pub mod retrieve_request_trait;
 pub mod sha_256;
 pub mod snapshot_stream;
@@ -0,0 +1,116 @@
+use parking_lot::Mutex;
+
+use crate::operations::types::CollectionError;
+use crate::shards::shard::ShardId;
+
+/// Collects shards which failed to respond to a read request.
+///
+/// If passed to a read operation, shards which time out or are unavailable are skipped instead
+/// of failing the whole request, and results of the responsive shards are returned. Any other
+/// error still fails the request.
+#[derive(Debug, Default)]
+pub struct PartialResults {
+    unreachable_shards: Mutex<Vec<ShardId>>,
+}
+
+impl PartialResults {
+    /// Whether the error of a single shard can be tolerated in partial mode
+    ///
+    /// Only errors of a shard not responding in time are tolerated. Other transient errors, like
+    /// service errors, are not a reason to return incomplete results.
+    pub fn is_tolerated(error: &CollectionError) -> bool {
+        matches!(
+            error.origin(),
+            CollectionError::Timeout { .. } | CollectionError::ShardUnavailable { .. },
+        )
+    }
+
+    pub fn add_unreachable_shard(&self, shard_id: ShardId) {
+        let mut unreachable_shards = self.unreachable_shards.lock();
+        if !unreachable_shards.contains(&shard_id) {
+            unreachable_shards.push(shard_id);
+        }
+    }
+
+    /// Results are degraded if at least one shard did not respond
+    pub fn is_degraded(&self) -> bool {
+        !self.unreachable_shards.lock().is_empty()
+    }
+
+    /// Sorted list of shards which did not respond
+    pub fn unreachable_shards(&self) -> Vec<ShardId> {
+        let mut unreachable_shards = self.unreachable_shards.lock().clone();
+        unreachable_shards.sort_unstable();
+        unreachable_shards
+    }
+
+    /// Collect results of individual shards, skipping tolerated failures
+    ///
+    /// Fails with the first error if it is not tolerated, or if no shard responded at all.
+    pub fn collect<T>(
+        &self,
+        shard_results: impl IntoIterator<Item = (ShardId, Result<T, CollectionError>)>,
+    ) -> Result<Vec<T>, CollectionError> {
+        let mut responses = Vec::new();
+        let mut first_error = None;
+
+        for (shard_id, result) in shard_results {
+            match result {
+                Ok(response) => responses.push(response),
+                Err(err) if Self::is_tolerated(&err) => {
+                    log::debug!("Shard {shard_id} skipped in partial read: {err}");
+                    self.add_unreachable_shard(shard_id);
+                    first_error.get_or_insert(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        match first_error {
+            Some(err) if responses.is_empty() => Err(err),
+            _ => Ok(responses),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_partial_results() {
+        let partial = PartialResults::default();
+        let results = partial
+            .collect([
+                (2, Err(CollectionError::timeout(1, "query"))),
+                (0, Ok(10)),
+                (1, Ok(20)),
+            ])
+            .unwrap();
+        assert_eq!(results, vec![10, 20]);
+        assert!(partial.is_degraded());
+        assert_eq!(partial.unreachable_shards(), vec![2]);
+
+        // Only timeouts and unavailable shards are tolerated
+        let partial = PartialResults::default();
+        let result = partial.collect([
+            (0, Ok(10)),
+            (1, Err(CollectionError::bad_request("invalid"))),
+        ]);
+        assert!(result.is_err());
+
+        let partial = PartialResults::default();
+        let result = partial.collect([
+            (0, Ok(10)),
+            (1, Err(CollectionError::service_error("internal"))),
+        ]);
+        assert!(result.is_err());
+        assert!(!partial.is_degraded());
+
+        // Fail if no shard responded
+        let partial = PartialResults::default();
+        let result: Result<Vec<u32>, _> =
+            partial.collect([(0, Err(CollectionError::timeout(1, "query")))]);
+        assert!(result.is_err());
+    }
+}
@@ -38,6 +38,30 @@ pub struct S3Config {
     pub endpoint_url: Option<String>,
 }
 
+impl S3Config {
+    /// Apply this config on top of `builder`
+    pub(crate) fn configure(&self, mut builder: AmazonS3Builder) -> AmazonS3Builder {
+        builder = builder.with_bucket_name(&self.bucket);
+
+        if let Some(access_key) = &self.access_key {
+            builder = builder.with_access_key_id(access_key);
+        }
+        if let Some(secret_key) = &self.secret_key {
+            builder = builder.with_secret_access_key(secret_key);
+        }
+        if let Some(region) = &self.region {
+            builder = builder.with_region(region);
+        }
+        if let Some(endpoint_url) = &self.endpoint_url {
+            builder = builder.with_endpoint(endpoint_url);
+            if endpoint_url.starts_with("http://") {
+                builder = builder.with_allow_http(true);
+            }
+        }
+        builder
+    }
+}
+
 pub struct SnapshotStorageCloud {
     client: Box<dyn object_store::ObjectStore>,
 }
@@ -62,23 +86,7 @@ impl SnapshotStorageManager {
             SnapshotsStorageConfig::S3 => {
                 let mut builder = AmazonS3Builder::from_env();
                 if let Some(s3_config) = &snapshots_config.s3_config {
-                    builder = builder.with_bucket_name(&s3_config.bucket);
-
-                    if let Some(access_key) = &s3_config.access_key {
-                        builder = builder.with_access_key_id(access_key);
-                    }
-                    if let Some(secret_key) = &s3_config.secret_key {
-                        builder = builder.with_secret_access_key(secret_key);
-                    }
-                    if let Some(region) = &s3_config.region {
-                        builder = builder.with_region(region);
-                    }
-                    if let Some(endpoint_url) = &s3_config.endpoint_url {
-                        builder = builder.with_endpoint(endpoint_url);
-                        if endpoint_url.starts_with("http://") {
-                            builder = builder.with_allow_http(true);
-                        }
-                    }
+                    builder = s3_config.configure(builder);
                 }
                 let client: Box<dyn object_store::ObjectStore> =
                     Box::new(builder.build().map_err(|e| {
@@ -10,6 +10,7 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
+use segment::spaces::wasm_metric::WasmMetricSource;
 use segment::types::{
     Distance, HnswConfig, Indexes, Payload, PayloadStorageType, QuantizationConfig, SegmentConfig,
     SparseVectorDataConfig, StrictModeConfig, VectorDataConfig, VectorName, VectorNameBuf,
@@ -42,6 +43,18 @@ pub struct WalConfig {
     #[validate(range(min = 1))]
     #[serde(default = "default_wal_retain_closed")]
     pub wal_retain_closed: usize,
+    /// Time window in milliseconds to collect concurrent updates into a single batch.
+    /// Concurrent upserts are merged into a single WAL record, operations of a batch share
+    /// a single WAL flush and are applied in one pass,
+    /// trading a slight latency increase for higher throughput of small updates.
+    /// Disabled if 0.
+    #[serde(default)]
+    pub wal_batch_window_ms: u64,
+    /// Number of last versions of each point to keep in the point history, queryable by point ID.
+    /// History records payload changes and hashes of vectors, it is not included into snapshots.
+    /// Disabled if 0.
+    #[serde(default)]
+    pub wal_point_history: usize,
 }
 
 fn default_wal_retain_closed() -> usize {
@@ -54,6 +67,8 @@ impl From<&WalConfig> for WalOptions {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            wal_batch_window_ms: _,
+            wal_point_history: _,
         } = config;
         WalOptions {
             segment_capacity: wal_capacity_mb * 1024 * 1024,
@@ -69,6 +84,8 @@ impl Default for WalConfig {
             wal_capacity_mb: 32,
             wal_segments_ahead: 0,
             wal_retain_closed: default_wal_retain_closed(),
+            wal_batch_window_ms: 0,
+            wal_point_history: 0,
         }
     }
 }
@@ -239,6 +256,9 @@ pub struct CollectionConfigInternal {
     /// such as creation time, migration data, inference model info, etc.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Payload>,
+    /// Custom metric module used by vectors with custom distance, same on all peers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_metric: Option<WasmMetricSource>,
 }
 
 impl CollectionConfigInternal {
@@ -433,6 +453,9 @@ impl CollectionParams {
                     QuantizationConfigDiff::Binary(binary) => {
                         Some(QuantizationConfig::Binary(binary))
                     }
+                    QuantizationConfigDiff::Residual(residual) => {
+                        Some(QuantizationConfig::Residual(residual))
+                    }
                     QuantizationConfigDiff::Disabled(_) => None,
                 }
             }
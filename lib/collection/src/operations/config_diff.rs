@@ -6,7 +6,8 @@ use api::rest::MaxOptimizationThreads;
 use merge::Merge;
 use schemars::JsonSchema;
 use segment::types::{
-    BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization, StrictModeConfig,
+    BinaryQuantization, HnswConfig, ProductQuantization, ResidualQuantization, ScalarQuantization,
+    StrictModeConfig,
 };
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -96,6 +97,16 @@ pub struct HnswConfigDiff {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub copy_vectors: Option<bool>,
+    /// Use GPU to build the index, if GPU indexing is enabled on the node. Default: true.
+    /// Distances for link selection are computed on GPU, while the graph is assembled on CPU.
+    /// Set to false to always build this index on CPU.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_indexing: Option<bool>,
+    /// Seed for randomized steps of the index building. If set, the index is built
+    /// deterministically: same data and config produce the same graph on every node.
+    /// Seeded builds run on a single thread, on CPU, and don't reuse existing graphs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 #[derive(
@@ -109,6 +120,12 @@ pub struct WalConfigDiff {
     pub wal_segments_ahead: Option<usize>,
     /// Number of closed WAL segments to retain
     pub wal_retain_closed: Option<usize>,
+    /// Time window in milliseconds to collect concurrent updates into a single batch.
+    /// Disabled if 0.
+    pub wal_batch_window_ms: Option<u64>,
+    /// Number of last versions of each point to keep in the point history.
+    /// Disabled if 0.
+    pub wal_point_history: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
@@ -374,6 +391,7 @@ pub enum QuantizationConfigDiff {
     Scalar(ScalarQuantization),
     Product(ProductQuantization),
     Binary(BinaryQuantization),
+    Residual(ResidualQuantization),
     Disabled(Disabled),
 }
 
@@ -389,6 +407,7 @@ impl Validate for QuantizationConfigDiff {
             QuantizationConfigDiff::Scalar(scalar) => scalar.validate(),
             QuantizationConfigDiff::Product(product) => product.validate(),
             QuantizationConfigDiff::Binary(binary) => binary.validate(),
+            QuantizationConfigDiff::Residual(residual) => residual.validate(),
             QuantizationConfigDiff::Disabled(_) => Ok(()),
         }
     }
@@ -17,8 +17,8 @@ use itertools::Itertools;
 use segment::common::operation_error::OperationError;
 use segment::data_types::vectors::{VectorInternal, VectorStructInternal};
 use segment::types::{
-    Distance, HnswConfig, MultiVectorConfig, QuantizationConfig, StrictModeConfigOutput,
-    WithPayloadInterface,
+    DiskAnnConfig, Distance, HnswConfig, IvfConfig, MultiVectorConfig, QuantizationConfig,
+    StrictModeConfigOutput, WithPayloadInterface,
 };
 use tonic::Status;
 
@@ -51,8 +51,8 @@ use crate::operations::point_ops::{FilterSelector, PointIdsList, PointsSelector,
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, RecordInternal, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    LocalShardInfo, OptimizersStatus, PointVersion, RecommendRequestInternal, RecordInternal,
+    RemoteShardInfo, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionCoreSearchRequest;
@@ -241,6 +241,8 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             on_disk,
             payload_m,
             copy_vectors,
+            gpu_indexing,
+            seed,
         } = value;
         Self {
             m: m.map(|v| v as usize),
@@ -250,6 +252,8 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             on_disk,
             payload_m: payload_m.map(|v| v as usize),
             copy_vectors,
+            gpu_indexing,
+            seed,
         }
     }
 }
@@ -264,6 +268,8 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             on_disk,
             payload_m,
             copy_vectors,
+            gpu_indexing,
+            seed,
         } = value;
         Self {
             m: m.map(|v| v as u64),
@@ -273,6 +279,8 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             on_disk,
             payload_m: payload_m.map(|v| v as u64),
             copy_vectors,
+            gpu_indexing,
+            seed,
         }
     }
 }
@@ -283,11 +291,15 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            wal_batch_window_ms,
+            wal_point_history,
         } = value;
         Self {
             wal_capacity_mb: wal_capacity_mb.map(|v| v as usize),
             wal_segments_ahead: wal_segments_ahead.map(|v| v as usize),
             wal_retain_closed: wal_retain_closed.map(|v| v as usize),
+            wal_batch_window_ms,
+            wal_point_history: wal_point_history.map(|v| v as usize),
         }
     }
 }
@@ -369,6 +381,7 @@ impl TryFrom<api::grpc::qdrant::QuantizationConfigDiff> for QuantizationConfigDi
                 Quantization::Scalar(scalar) => Ok(Self::Scalar(scalar.try_into()?)),
                 Quantization::Product(product) => Ok(Self::Product(product.try_into()?)),
                 Quantization::Binary(binary) => Ok(Self::Binary(binary.try_into()?)),
+                Quantization::Residual(residual) => Ok(Self::Residual(residual.try_into()?)),
                 Quantization::Disabled(_) => Ok(Self::new_disabled()),
             },
         }
@@ -417,6 +430,8 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             on_disk,
             payload_m,
             copy_vectors,
+            gpu_indexing,
+            seed,
         } = hnsw_config;
 
         let CollectionParams {
@@ -499,6 +514,8 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     on_disk,
                     payload_m: payload_m.map(|v| v as u64),
                     copy_vectors,
+                    gpu_indexing,
+                    seed,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(deleted_threshold),
@@ -516,12 +533,16 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         wal_capacity_mb,
                         wal_segments_ahead,
                         wal_retain_closed,
+                        wal_batch_window_ms,
+                        wal_point_history,
                     } = wal_config;
 
                     api::grpc::qdrant::WalConfigDiff {
                         wal_capacity_mb: Some(wal_capacity_mb as u64),
                         wal_segments_ahead: Some(wal_segments_ahead as u64),
                         wal_retain_closed: Some(wal_retain_closed as u64),
+                        wal_batch_window_ms: Some(wal_batch_window_ms),
+                        wal_point_history: Some(wal_point_history as u64),
                     }
                 }),
                 quantization_config: quantization_config.map(|x| x.into()),
@@ -558,6 +579,28 @@ impl From<RecordInternal> for api::grpc::qdrant::RetrievedPoint {
     }
 }
 
+impl From<PointVersion> for api::grpc::qdrant::PointVersionInternal {
+    fn from(value: PointVersion) -> Self {
+        let PointVersion { id, version } = value;
+        Self {
+            id: Some(id.into()),
+            version,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PointVersionInternal> for PointVersion {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointVersionInternal) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PointVersionInternal { id, version } = value;
+        let id = id
+            .ok_or_else(|| Status::invalid_argument("Missing id of point version"))?
+            .try_into()?;
+        Ok(Self { id, version })
+    }
+}
+
 impl TryFrom<i32> for CollectionStatus {
     type Error = Status;
 
@@ -625,11 +668,15 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
             wal_capacity_mb,
             wal_segments_ahead,
             wal_retain_closed,
+            wal_batch_window_ms,
+            wal_point_history,
         } = wal_config;
         Self {
             wal_capacity_mb: wal_capacity_mb.unwrap_or_default() as usize,
             wal_segments_ahead: wal_segments_ahead.unwrap_or_default() as usize,
             wal_retain_closed: wal_retain_closed.unwrap_or_default() as usize,
+            wal_batch_window_ms: wal_batch_window_ms.unwrap_or_default(),
+            wal_point_history: wal_point_history.unwrap_or_default() as usize,
         }
     }
 }
@@ -687,17 +734,39 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
             on_disk,
             datatype,
             multivector_config,
+            ivf_config,
+            disk_ann_config,
         } = vector_params;
+        let distance = from_grpc_dist(distance)?;
+        if ivf_config.is_some() && disk_ann_config.is_some() {
+            return Err(Status::invalid_argument(
+                "Only one of ivf_config and disk_ann_config can be set",
+            ));
+        }
+        if ivf_config.is_some()
+            && (!IvfConfig::supports_distance(distance) || multivector_config.is_some())
+        {
+            return Err(Status::invalid_argument(
+                "IVF index supports only dense vectors with Cosine, Euclid, Dot or Manhattan distance",
+            ));
+        }
+        if disk_ann_config.is_some() && multivector_config.is_some() {
+            return Err(Status::invalid_argument(
+                "DiskANN index does not support multivectors",
+            ));
+        }
         Ok(Self {
             size: NonZeroU64::new(size).ok_or_else(|| {
                 Status::invalid_argument("VectorParams size must be greater than zero")
             })?,
-            distance: from_grpc_dist(distance)?,
+            distance,
             hnsw_config: hnsw_config.map(Into::into),
             quantization_config: quantization_config
                 .map(grpc_to_segment_quantization_config)
                 .transpose()?,
             on_disk,
+            ivf_config: ivf_config.map(IvfConfig::from),
+            disk_ann_config: disk_ann_config.map(DiskAnnConfig::from),
             datatype: convert_datatype_from_proto(datatype)?,
             multivector_config: multivector_config
                 .map(MultiVectorConfig::try_from)
@@ -824,6 +893,9 @@ fn grpc_to_segment_quantization_config(
         api::grpc::qdrant::quantization_config::Quantization::Binary(config) => {
             Ok(QuantizationConfig::Binary(config.try_into()?))
         }
+        api::grpc::qdrant::quantization_config::Quantization::Residual(config) => {
+            Ok(QuantizationConfig::Residual(config.try_into()?))
+        }
     }
 }
 
@@ -1338,6 +1410,8 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             hnsw_config,
             quantization_config,
             on_disk,
+            ivf_config,
+            disk_ann_config,
             datatype,
             multivector_config,
         } = value;
@@ -1348,6 +1422,10 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
                 Distance::Euclid => api::grpc::qdrant::Distance::Euclid,
                 Distance::Dot => api::grpc::qdrant::Distance::Dot,
                 Distance::Manhattan => api::grpc::qdrant::Distance::Manhattan,
+                Distance::Hamming => api::grpc::qdrant::Distance::Hamming,
+                Distance::Jaccard => api::grpc::qdrant::Distance::Jaccard,
+                Distance::Custom => api::grpc::qdrant::Distance::Custom,
+                Distance::RawCosine => api::grpc::qdrant::Distance::RawCosine,
             }
             .into(),
             hnsw_config: hnsw_config.map(Into::into),
@@ -1355,6 +1433,8 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             on_disk,
             datatype: datatype.map(|dt| api::grpc::qdrant::Datatype::from(dt).into()),
             multivector_config: multivector_config.map(api::grpc::qdrant::MultiVectorConfig::from),
+            ivf_config: ivf_config.map(api::grpc::qdrant::IvfConfig::from),
+            disk_ann_config: disk_ann_config.map(api::grpc::qdrant::DiskAnnConfig::from),
         }
     }
 }
@@ -5,6 +5,7 @@ impl Generalizer for ScrollRequestInternal {
     fn remove_details(&self) -> Self {
         let ScrollRequestInternal {
             offset,
+            after_id,
             limit,
             filter,
             with_payload,
@@ -14,6 +15,7 @@ impl Generalizer for ScrollRequestInternal {
 
         Self {
             offset: *offset,
+            after_id: *after_id,
             limit: *limit,
             filter: filter.clone(),
             with_payload: with_payload.clone(),
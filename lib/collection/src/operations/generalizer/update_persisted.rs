@@ -105,11 +105,13 @@ impl Generalizer for ConditionalInsertOperationInternal {
         let Self {
             points_op,
             condition,
+            skip_unchanged,
         } = self;
 
         Self {
             condition: condition.clone(),
             points_op: points_op.remove_details(),
+            skip_unchanged: *skip_unchanged,
         }
     }
 }
@@ -100,6 +100,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
         let ConditionalInsertOperationInternal {
             points_op,
             condition,
+            skip_unchanged,
         } = self;
 
         let points_op = points_op.split_by_shard(ring);
@@ -113,6 +114,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
                             ConditionalInsertOperationInternal {
                                 points_op: upsert_operation,
                                 condition: condition.clone(),
+                                skip_unchanged,
                             },
                         )
                     })
@@ -121,6 +123,7 @@ impl SplitByShard for ConditionalInsertOperationInternal {
             OperationToShard::ToAll(upsert_operation) => OperationToShard::ToAll(Self {
                 points_op: upsert_operation,
                 condition,
+                skip_unchanged,
             }),
         }
     }
@@ -394,6 +397,7 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            skip_unchanged: None,
         });
         assert!(batch.validate().is_err());
 
@@ -405,6 +409,7 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            skip_unchanged: None,
         });
         assert!(batch.validate().is_ok());
 
@@ -416,6 +421,7 @@ mod tests {
             },
             shard_key: None,
             update_filter: None,
+            skip_unchanged: None,
         });
         assert!(batch.validate().is_err());
     }
@@ -3,9 +3,13 @@ use std::num::NonZeroUsize;
 use std::time::Duration;
 
 use segment::types::HnswGlobalConfig;
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 
+use crate::collection::recent_queries::RecentQueriesConfig;
+use crate::collection::search_shadowing::SearchShadowingConfig;
 use crate::common::snapshots_manager::SnapshotsConfig;
 use crate::operations::types::NodeType;
+use crate::shards::local_shard::wal_offload::WalOffloadConfig;
 use crate::shards::transfer::ShardTransferMethod;
 
 /// Default timeout for search requests.
@@ -34,7 +38,17 @@ pub struct SharedStorageConfig {
     pub snapshots_path: String,
     pub snapshots_config: SnapshotsConfig,
     pub hnsw_global_config: HnswGlobalConfig,
+    /// Memory layout of in-memory vector storages
+    pub chunked_vectors_config: ChunkedVectorsConfig,
     pub search_thread_count: usize,
+    /// Maximum number of concurrent searches per collection, scheduled fairly across shard keys
+    pub fair_search_concurrency: Option<usize>,
+    /// Recording of recent search queries, if enabled
+    pub recent_queries: Option<RecentQueriesConfig>,
+    /// Shadowing of searches of collections with an alternative setup
+    pub search_shadowing: Vec<SearchShadowingConfig>,
+    /// Offloading of closed WAL segments to object storage, if enabled
+    pub wal_offload: Option<WalOffloadConfig>,
 }
 
 impl Default for SharedStorageConfig {
@@ -53,7 +67,12 @@ impl Default for SharedStorageConfig {
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
             hnsw_global_config: HnswGlobalConfig::default(),
+            chunked_vectors_config: ChunkedVectorsConfig::default(),
             search_thread_count: common::defaults::search_thread_count(common::cpu::get_num_cpus()),
+            fair_search_concurrency: None,
+            recent_queries: None,
+            search_shadowing: Vec::new(),
+            wal_offload: None,
         }
     }
 }
@@ -74,7 +93,12 @@ impl SharedStorageConfig {
         snapshots_path: String,
         snapshots_config: SnapshotsConfig,
         hnsw_global_config: HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
         search_thread_count: usize,
+        fair_search_concurrency: Option<usize>,
+        recent_queries: Option<RecentQueriesConfig>,
+        search_shadowing: Vec<SearchShadowingConfig>,
+        wal_offload: Option<WalOffloadConfig>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -94,7 +118,12 @@ impl SharedStorageConfig {
             snapshots_path,
             snapshots_config,
             hnsw_global_config,
+            chunked_vectors_config,
             search_thread_count,
+            fair_search_concurrency,
+            recent_queries,
+            search_shadowing,
+            wal_offload,
         }
     }
 }
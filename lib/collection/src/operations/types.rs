@@ -1,5 +1,5 @@
 use std::backtrace::Backtrace;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error as _;
 use std::fmt::{Debug, Write as _};
 use std::iter;
@@ -29,10 +29,11 @@ use segment::data_types::vectors::{
     DEFAULT_VECTOR_NAME, DenseVector, NamedQuery, NamedVectorStruct, VectorRef,
     VectorStructInternal,
 };
+use segment::spaces::wasm_metric;
 use segment::types::{
-    Distance, Filter, HnswConfig, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType,
-    PointIdType, QuantizationConfig, SearchParams, SeqNumberType, ShardKey,
-    SparseVectorStorageType, StrictModeConfigOutput, VectorName, VectorNameBuf,
+    DiskAnnConfig, Distance, Filter, HnswConfig, IvfConfig, MultiVectorConfig, Payload,
+    PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig, SearchParams, SeqNumberType,
+    ShardKey, SparseVectorStorageType, StrictModeConfigOutput, VectorName, VectorNameBuf,
     VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
@@ -58,6 +59,7 @@ use crate::operations::point_ops::{PointStructPersisted, VectorStructPersisted};
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::internal_features::InternalFeature;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::ShardTransferMethod;
@@ -198,6 +200,8 @@ impl From<CollectionConfigInternal> for CollectionConfig {
             // Internal UUID to identify unique collections in consensus snapshots
             uuid: _,
             metadata,
+            // Internal module of custom distance, replicated to all peers
+            custom_metric: _,
         } = config;
 
         CollectionConfig {
@@ -440,6 +444,10 @@ pub struct ScrollRequestInternal {
     /// Start ID to read points from.
     pub offset: Option<PointIdType>,
 
+    /// Read points strictly after this ID. Unlike `offset`, the given point itself is not included,
+    /// so the ID of the last point of the previous page can be used to get the next one.
+    pub after_id: Option<PointIdType>,
+
     /// Page size. Default: 10
     #[validate(range(min = 1))]
     pub limit: Option<usize>,
@@ -504,6 +512,7 @@ impl Default for ScrollRequestInternal {
     fn default() -> Self {
         ScrollRequestInternal {
             offset: None,
+            after_id: None,
             limit: Some(Self::default_limit()),
             filter: None,
             with_payload: Some(Self::default_with_payload()),
@@ -594,6 +603,34 @@ pub struct PointRequest {
     pub shard_key: Option<ShardKeySelector>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct PointsExistRequest {
+    /// Look for points with ids
+    pub ids: Vec<PointIdType>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PointsExistResult {
+    /// Requested ids of points, which exist in the collection
+    pub existing: Vec<PointIdType>,
+    /// Requested ids of points, which do not exist in the collection
+    pub missing: Vec<PointIdType>,
+    /// Versions of existing points, in the same order as `existing`
+    pub versions: Vec<SeqNumberType>,
+}
+
+/// Version of an existing point, the number of the last operation which changed it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointVersion {
+    pub id: PointIdType,
+    pub version: SeqNumberType,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct PointRequestInternal {
@@ -974,6 +1011,12 @@ pub enum CollectionError {
     },
     #[error("Remote shard on {peer_id} failed during forward proxy operation: {error}")]
     ForwardProxyError { peer_id: PeerId, error: Box<Self> },
+    #[error("Shard {shard_id} on peer {peer_id} failed: {error}")]
+    RemoteShardError {
+        peer_id: PeerId,
+        shard_id: ShardId,
+        error: Box<Self>,
+    },
     #[error("Out of memory, free: {free}, {description}")]
     OutOfMemory { description: String, free: u64 },
     #[error("Timeout error: {description}")]
@@ -993,6 +1036,8 @@ pub enum CollectionError {
     },
     #[error("Shard temporarily unavailable: {description}")]
     ShardUnavailable { description: String },
+    #[error("Corrupted segment: {description}")]
+    CorruptedSegment { description: String },
 }
 
 impl CollectionError {
@@ -1043,9 +1088,31 @@ impl CollectionError {
         }
     }
 
+    /// Error of a shard replica on a remote peer, with the peer and shard it came from
+    pub fn remote_shard_error(peer_id: PeerId, shard_id: ShardId, error: Self) -> Self {
+        match error {
+            // Keep the context of the replica, which failed in the first place
+            error @ Self::RemoteShardError { .. } => error,
+            error => Self::RemoteShardError {
+                peer_id,
+                shard_id,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Error as it was raised by the shard, without the context of where it happened
+    pub fn origin(&self) -> &Self {
+        match self {
+            Self::RemoteShardError { error, .. } => error.origin(),
+            _ => self,
+        }
+    }
+
     pub fn remote_peer_id(&self) -> Option<PeerId> {
         match self {
             Self::ForwardProxyError { peer_id, .. } => Some(*peer_id),
+            Self::RemoteShardError { peer_id, .. } => Some(*peer_id),
             _ => None,
         }
     }
@@ -1127,19 +1194,21 @@ impl CollectionError {
             Self::BadShardSelection { .. } => false,
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
+            Self::RemoteShardError { error, .. } => error.is_transient(),
             Self::ObjectStoreError { .. } => false,
             Self::StrictMode { .. } => false,
             Self::InferenceError { .. } => false,
             Self::RateLimitExceeded { .. } => false,
+            Self::CorruptedSegment { .. } => false,
         }
     }
 
     pub fn is_pre_condition_failed(&self) -> bool {
-        matches!(self, Self::PreConditionFailed { .. })
+        matches!(self.origin(), Self::PreConditionFailed { .. })
     }
 
     pub fn is_missing_point(&self) -> bool {
-        match self {
+        match self.origin() {
             Self::NotFound { what } => what.contains("No point with id"),
             Self::PointNotFound { .. } => true,
             _ => false,
@@ -1198,6 +1267,9 @@ impl From<OperationError> for CollectionError {
                 error: format!("{err}"),
                 backtrace: None,
             },
+            OperationError::CorruptedSegment { description } => {
+                Self::CorruptedSegment { description }
+            }
             OperationError::ValidationError { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
@@ -1470,12 +1542,14 @@ impl From<Datatype> for VectorStorageDatatype {
     Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Anonymize, Clone, PartialEq, Eq,
 )]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_vector_params"))]
 #[anonymize(false)]
 pub struct VectorParams {
     /// Size of a vectors used
     #[validate(custom(function = "validate_nonzerou64_range_min_1_max_65536"))]
     pub size: NonZeroU64,
     /// Type of distance function used for measuring distance between vectors
+    #[validate(custom(function = "validate_distance"))]
     pub distance: Distance,
     /// Custom params for HNSW index. If none - values from collection configuration are used.
     #[serde(default, skip_serializing_if = "is_hnsw_diff_empty")]
@@ -1493,6 +1567,16 @@ pub struct VectorParams {
     /// Default: false
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Custom params for IVF index. If set - IVF index is built instead of HNSW graph.
+    /// IVF index is cheaper to build and maintain, but usually less accurate at the same speed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub ivf_config: Option<IvfConfig>,
+    /// Custom params for DiskANN index. If set - DiskANN graph is built instead of HNSW graph.
+    /// DiskANN graph is always stored on disk, and needs little RAM, if vectors are on disk too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub disk_ann_config: Option<DiskAnnConfig>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Defines which datatype should be used to represent vectors in the storage.
@@ -1517,6 +1601,71 @@ pub fn validate_nonzerou64_range_min_1_max_65536(
     validate_range_generic(value.get(), Some(1), Some(65536))
 }
 
+/// IVF index supports only plain dense vectors with a distance that can be averaged,
+/// DiskANN index supports only plain dense vectors,
+/// and only one alternative to HNSW index can be selected.
+/// Hamming distance expects bytes of bit-packed vectors, which are not quantized.
+/// Custom metric module scores `float32` vectors as stored, without conversion.
+fn validate_vector_params(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.distance == Distance::Custom
+        && !matches!(params.datatype, None | Some(Datatype::Float32))
+    {
+        let mut error = ValidationError::new("custom_metric_unsupported_datatype");
+        error.message = Some("Custom distance requires float32 datatype".into());
+        return Err(error);
+    }
+    if params.distance == Distance::Hamming {
+        if params.datatype != Some(Datatype::Uint8) {
+            let mut error = ValidationError::new("hamming_unsupported_datatype");
+            error.message = Some(
+                "Hamming distance requires uint8 datatype, each element holding 8 bits of a bit-packed vector"
+                    .into(),
+            );
+            return Err(error);
+        }
+        if params.quantization_config.is_some() {
+            let mut error = ValidationError::new("hamming_unsupported_quantization");
+            error.message = Some("Quantization is not supported for Hamming distance".into());
+            return Err(error);
+        }
+    }
+    if params.ivf_config.is_some() && params.disk_ann_config.is_some() {
+        let mut error = ValidationError::new("conflicting_index_configs");
+        error.message = Some("Only one of ivf_config and disk_ann_config can be set".into());
+        return Err(error);
+    }
+    if params.disk_ann_config.is_some() && params.multivector_config.is_some() {
+        let mut error = ValidationError::new("disk_ann_unsupported_multivector");
+        error.message = Some("DiskANN index does not support multivectors".into());
+        return Err(error);
+    }
+    if params.ivf_config.is_none() {
+        return Ok(());
+    }
+    if !IvfConfig::supports_distance(params.distance) {
+        let mut error = ValidationError::new("ivf_unsupported_distance");
+        error.message =
+            Some(format!("IVF index does not support {:?} distance", params.distance).into());
+        return Err(error);
+    }
+    if params.multivector_config.is_some() {
+        let mut error = ValidationError::new("ivf_unsupported_multivector");
+        error.message = Some("IVF index does not support multivectors".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Custom distance can only be used if the custom metric module is configured on the node
+fn validate_distance(distance: &Distance) -> Result<(), ValidationError> {
+    if *distance == Distance::Custom && wasm_metric::configured().is_none() {
+        let mut error = ValidationError::new("custom_metric_not_registered");
+        error.message = Some("Custom metric module is not registered".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
 /// Is considered empty if `None` or if diff has no field specified
 fn is_hnsw_diff_empty(hnsw_config: &Option<HnswConfigDiff>) -> bool {
     hnsw_config
@@ -1668,6 +1817,46 @@ impl VectorsConfig {
         }
     }
 
+    /// Check that quantization of each vector, configured on the vector itself or for the whole
+    /// collection, is supported by the vector params
+    pub fn check_quantization_support(
+        &self,
+        collection_quantization: Option<&QuantizationConfig>,
+    ) -> CollectionResult<()> {
+        for (vector_name, params) in self.params_iter() {
+            let Some(quantization) = params
+                .quantization_config
+                .as_ref()
+                .or(collection_quantization)
+            else {
+                continue;
+            };
+
+            if matches!(params.distance, Distance::Hamming | Distance::RawCosine) {
+                return Err(CollectionError::bad_input(format!(
+                    "Quantization is not supported for vector `{vector_name}` with {:?} distance",
+                    params.distance,
+                )));
+            }
+
+            if matches!(quantization, QuantizationConfig::Residual(_))
+                && params.multivector_config.is_some()
+            {
+                return Err(CollectionError::bad_input(format!(
+                    "Residual quantization is not supported for multivector `{vector_name}`",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether any vector is configured with the given distance
+    pub fn uses_distance(&self, distance: Distance) -> bool {
+        self.params_iter()
+            .any(|(_, params)| params.distance == distance)
+    }
+
     // TODO: Further unify `check_compatible` and `check_compatible_with_segment_config`?
     pub fn check_compatible(&self, other: &Self) -> CollectionResult<()> {
         match (self, other) {
@@ -1833,6 +2022,8 @@ impl From<&VectorParams> for VectorParamsBase {
             hnsw_config: _,
             quantization_config: _,
             on_disk: _,
+            ivf_config: _,
+            disk_ann_config: _,
             datatype: _,
             multivector_config: _,
         } = params;
@@ -2047,12 +2238,16 @@ pub struct PeerMetadata {
     /// Peer Qdrant version
     #[schemars(schema_with = "String::json_schema")]
     pub(crate) version: Version,
+    /// Internal API features supported by the peer
+    #[serde(default)]
+    pub(crate) features: BTreeSet<String>,
 }
 
 impl PeerMetadata {
     pub fn current() -> Self {
         Self {
             version: defaults::QDRANT_VERSION.clone(),
+            features: InternalFeature::supported_names(),
         }
     }
 
@@ -2060,4 +2255,14 @@ impl PeerMetadata {
     pub fn is_different_version(&self) -> bool {
         self.version != *defaults::QDRANT_VERSION
     }
+
+    /// Whether this metadata differs from the metadata of our current Qdrant instance.
+    pub fn is_outdated(&self) -> bool {
+        self.is_different_version() || self.features != InternalFeature::supported_names()
+    }
+
+    /// Whether the peer supports the given internal API feature
+    pub fn supports(&self, feature: InternalFeature) -> bool {
+        self.features.contains(feature.name())
+    }
 }
@@ -23,7 +23,7 @@ use crate::lookup::WithLookup;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::shard_query::MmrInternal;
-use crate::recommendations::avg_vector_for_recommendation;
+use crate::recommendations::{avg_vector_for_recommendation, weighted_sum_vector};
 
 const DEFAULT_MMR_LAMBDA: f32 = 0.5;
 
@@ -165,6 +165,8 @@ pub enum VectorQuery<T> {
     RecommendSumScores(RecoQuery<T>),
     Discover(DiscoveryQuery<T>),
     Context(ContextQuery<T>),
+    /// Search for the weighted sum of the given vectors
+    Combine(Vec<WeightedVector<T>>),
 }
 
 impl<T> VectorQuery<T> {
@@ -178,10 +180,17 @@ impl<T> VectorQuery<T> {
             | VectorQuery::RecommendSumScores(query) => Box::new(query.flat_iter()),
             VectorQuery::Discover(query) => Box::new(query.flat_iter()),
             VectorQuery::Context(query) => Box::new(query.flat_iter()),
+            VectorQuery::Combine(terms) => Box::new(terms.iter().map(|term| &term.vector)),
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedVector<T> {
+    pub vector: T,
+    pub weight: f32,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct NearestWithMmr<T> {
     pub nearest: T,
@@ -307,6 +316,19 @@ impl VectorQuery<VectorInputInternal> {
 
                 Ok(VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr }))
             }
+            VectorQuery::Combine(terms) => {
+                let terms = terms
+                    .into_iter()
+                    .map(|WeightedVector { vector, weight }| {
+                        let vector = ids_to_vectors
+                            .resolve_reference(lookup_collection, lookup_vector_name, vector)
+                            .ok_or_else(|| vector_not_found_error(lookup_vector_name))?;
+                        Ok(WeightedVector { vector, weight })
+                    })
+                    .collect::<CollectionResult<_>>()?;
+
+                Ok(VectorQuery::Combine(terms))
+            }
         }
     }
 
@@ -381,6 +403,9 @@ impl VectorQuery<VectorInternal> {
             VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr: _ }) => {
                 nearest.preprocess();
             }
+            VectorQuery::Combine(terms) => {
+                terms.iter_mut().for_each(|term| term.vector.preprocess());
+            }
         }
         self
     }
@@ -418,6 +443,14 @@ impl VectorQuery<VectorInternal> {
                 query: context,
                 using: Some(using),
             }),
+            VectorQuery::Combine(terms) => {
+                let search_vector = weighted_sum_vector(
+                    terms
+                        .iter()
+                        .map(|term| (term.weight, VectorRef::from(&term.vector))),
+                )?;
+                QueryEnum::Nearest(NamedQuery::new_from_vector(search_vector, using))
+            }
             VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr }) => {
                 let Mmr {
                     diversity,
@@ -216,6 +216,26 @@ impl ShardQueryRequest {
 
         filters
     }
+
+    /// Whether any search of the request, including prefetches, limits the number of scored points
+    pub fn has_scoring_budget(&self) -> bool {
+        self.params
+            .is_some_and(|params| params.max_scored_points.is_some())
+            || self
+                .prefetches
+                .iter()
+                .any(ShardPrefetch::has_scoring_budget)
+    }
+
+    /// Split scoring budgets of all searches of the request, see [`SearchParams::split_scoring_budget`]
+    pub fn split_scoring_budget(&mut self, parts: usize) {
+        if let Some(params) = self.params.as_mut() {
+            params.split_scoring_budget(parts);
+        }
+        for prefetch in &mut self.prefetches {
+            prefetch.split_scoring_budget(parts);
+        }
+    }
 }
 
 impl ShardPrefetch {
@@ -230,6 +250,24 @@ impl ShardPrefetch {
 
         filters
     }
+
+    fn has_scoring_budget(&self) -> bool {
+        self.params
+            .is_some_and(|params| params.max_scored_points.is_some())
+            || self
+                .prefetches
+                .iter()
+                .any(ShardPrefetch::has_scoring_budget)
+    }
+
+    fn split_scoring_budget(&mut self, parts: usize) {
+        if let Some(params) = self.params.as_mut() {
+            params.split_scoring_budget(parts);
+        }
+        for prefetch in &mut self.prefetches {
+            prefetch.split_scoring_budget(parts);
+        }
+    }
 }
 
 impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
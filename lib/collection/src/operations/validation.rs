@@ -25,6 +25,14 @@ pub fn label_errors(label: impl AsRef<str>, errs: &ValidationErrors) -> String {
     )
 }
 
+/// Name of the first invalid field of the given validation errors.
+pub fn first_invalid_field(errs: &ValidationErrors) -> Option<String> {
+    flatten_errors(errs)
+        .into_iter()
+        .next()
+        .map(|(_, name, _)| name)
+}
+
 /// Describe the given validation errors.
 ///
 /// Returns a list of error messages for fields: `(field, message)`
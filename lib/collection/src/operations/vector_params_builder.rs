@@ -1,6 +1,6 @@
 use std::num::NonZeroU64;
 
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
+use segment::types::{DiskAnnConfig, Distance, IvfConfig, MultiVectorConfig, QuantizationConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
 use crate::operations::types::{Datatype, VectorParams};
@@ -18,6 +18,8 @@ impl VectorParamsBuilder {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                ivf_config: None,
+                disk_ann_config: None,
                 datatype: None,
                 multivector_config: None,
             },
@@ -39,6 +41,16 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_ivf_config(mut self, ivf_config: IvfConfig) -> Self {
+        self.vector_params.ivf_config = Some(ivf_config);
+        self
+    }
+
+    pub fn with_disk_ann_config(mut self, disk_ann_config: DiskAnnConfig) -> Self {
+        self.vector_params.disk_ann_config = Some(disk_ann_config);
+        self
+    }
+
     pub fn with_datatype(mut self, datatype: Datatype) -> Self {
         self.vector_params.datatype = Some(datatype);
         self
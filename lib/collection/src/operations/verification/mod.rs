@@ -1,3 +1,4 @@
+mod aggregate;
 mod count;
 mod discovery;
 mod facet;
@@ -6,6 +7,7 @@ mod matrix;
 pub mod operation_rate_cost;
 mod query;
 mod recommend;
+mod score_histogram;
 mod search;
 mod update;
 
@@ -14,6 +16,7 @@ use std::fmt::Display;
 use itertools::Itertools;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, SearchParams, StrictModeConfig};
+use segment::vector_storage::quantized::quantization_error::MAX_ADAPTIVE_OVERSAMPLING;
 
 use super::types::{CollectionError, CollectionResult};
 use crate::collection::Collection;
@@ -264,8 +267,21 @@ impl StrictModeVerification for SearchParams {
         _collection: &Collection,
         strict_mode_config: &StrictModeConfig,
     ) -> CollectionResult<()> {
+        // Adaptive oversampling is only bounded by the explicit value
+        let max_oversampling = self.quantization.and_then(|quantization| {
+            if quantization.adaptive_oversampling == Some(true) {
+                Some(
+                    quantization
+                        .oversampling
+                        .unwrap_or(MAX_ADAPTIVE_OVERSAMPLING),
+                )
+            } else {
+                quantization.oversampling
+            }
+        });
+
         check_limit_opt(
-            self.quantization.and_then(|i| i.oversampling),
+            max_oversampling,
             strict_mode_config.search_max_oversampling,
             "oversampling",
         )?;
@@ -275,6 +291,13 @@ impl StrictModeVerification for SearchParams {
             strict_mode_config.search_max_hnsw_ef,
             "hnsw_ef",
         )?;
+
+        // Search list of DiskANN is the same kind of beam as `hnsw_ef`, so it has the same limit
+        check_limit_opt(
+            self.disk_ann_list_size,
+            strict_mode_config.search_max_hnsw_ef,
+            "disk_ann_list_size",
+        )?;
         Ok(())
     }
 
@@ -512,6 +535,7 @@ mod test {
             strict_mode_config: Some(strict_mode_config.clone()),
             uuid: None,
             metadata: None,
+            custom_metric: None,
         };
 
         let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
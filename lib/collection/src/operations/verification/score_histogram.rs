@@ -0,0 +1,25 @@
+use api::rest::ScoreHistogramRequestInternal;
+
+use super::StrictModeVerification;
+
+impl StrictModeVerification for ScoreHistogramRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        self.sample
+    }
+
+    fn indexed_filter_read(&self) -> Option<&segment::types::Filter> {
+        self.filter.as_ref()
+    }
+
+    fn indexed_filter_write(&self) -> Option<&segment::types::Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&segment::types::SearchParams> {
+        None
+    }
+}
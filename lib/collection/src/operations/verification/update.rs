@@ -1,16 +1,22 @@
+use std::str::FromStr;
+
 use api::rest::{
     BatchVectorStruct, MultiDenseVector, PointInsertOperations, PointsBatch, PointsList,
     UpdateVectors, Vector, VectorStruct,
 };
 use segment::data_types::tiny_map::TinyMap;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, StrictModeConfig, StrictModeMultivectorConfig, StrictModeSparseConfig, VectorName,
-    VectorNameBuf,
+    DateTimePayloadType, Filter, GeoPoint, PayloadContainer, PayloadSchemaType, StrictModeConfig,
+    StrictModeMultivectorConfig, StrictModeSparseConfig, VectorName, VectorNameBuf,
 };
+use serde_json::{Map, Value};
+use uuid::Uuid;
 
 use super::{StrictModeVerification, check_limit_opt};
 use crate::collection::Collection;
+use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::common::collection_size_stats::CollectionSizeAtomicStats;
 use crate::operations::payload_ops::{DeletePayload, SetPayload};
 use crate::operations::point_ops::PointsSelector;
@@ -76,6 +82,14 @@ impl StrictModeVerification for SetPayload {
             check_collection_payload_size_limit(payload_size_limit_bytes, local_stats)?;
         }
 
+        if strict_mode_config.enforce_payload_schema == Some(true) {
+            let schema = collection.payload_index_schema();
+            // Payload is set under `key`, so check it as it will be stored in the point
+            let mut payload = Map::new();
+            JsonPath::value_set(self.key.as_ref(), &mut payload, &self.payload.0);
+            check_payload_schema(&payload, &schema)?;
+        }
+
         Ok(())
     }
 
@@ -144,6 +158,10 @@ impl StrictModeVerification for PointInsertOperations {
             check_sparse_vector_limits_insert(self, sparse_config).await?;
         }
 
+        if strict_mode_config.enforce_payload_schema == Some(true) {
+            check_payload_schema_insert(self, &collection.payload_index_schema())?;
+        }
+
         Ok(())
     }
 
@@ -164,11 +182,13 @@ impl StrictModeVerification for PointInsertOperations {
                 batch: _,
                 shard_key: _,
                 update_filter: _,
+                skip_unchanged: _,
             }) => None,
             PointInsertOperations::PointsList(PointsList {
                 points: _,
                 shard_key: _,
                 update_filter: _,
+                skip_unchanged: _,
             }) => None,
         }
     }
@@ -317,6 +337,106 @@ fn check_collection_payload_size_limit(
     Ok(())
 }
 
+fn check_payload_schema_insert(
+    point_insert: &PointInsertOperations,
+    schema: &PayloadIndexSchema,
+) -> CollectionResult<()> {
+    match point_insert {
+        PointInsertOperations::PointsBatch(batch) => {
+            for payload in batch.batch.payloads.iter().flatten().flatten() {
+                check_payload_schema(&payload.0, schema)?;
+            }
+        }
+        PointInsertOperations::PointsList(list) => {
+            for payload in list
+                .points
+                .iter()
+                .filter_map(|point| point.payload.as_ref())
+            {
+                check_payload_schema(&payload.0, schema)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that all keys of the payload have a payload index, and values match the index type.
+///
+/// Keys are checked on the top level only, a key is declared if any index is built on it or
+/// on any of its nested fields.
+fn check_payload_schema(
+    payload: &Map<String, Value>,
+    schema: &PayloadIndexSchema,
+) -> CollectionResult<()> {
+    for key in payload.keys() {
+        if !schema.schema.keys().any(|path| &path.first_key == key) {
+            return Err(CollectionError::strict_mode(
+                format!("Payload key '{key}' has no payload index"),
+                format!(
+                    "Create a payload index for '{key}' or disable strict mode `enforce_payload_schema` option"
+                ),
+            ));
+        }
+    }
+
+    for (path, field_schema) in &schema.schema {
+        let schema_type = field_schema.kind();
+        let mismatch = payload
+            .get_value(path)
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(items) => items.iter().collect::<Vec<_>>(),
+                value => vec![value],
+            })
+            .find(|value| !value_matches_schema_type(value, schema_type));
+
+        if let Some(value) = mismatch {
+            let type_name = schema_type.name();
+            return Err(CollectionError::strict_mode(
+                format!(
+                    "Value {value} of payload key '{path}' doesn't match its index type '{type_name}'"
+                ),
+                format!(
+                    "Use a '{type_name}' value or disable strict mode `enforce_payload_schema` option"
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the payload index of the given type is able to index the value.
+/// Nulls are always accepted, as they are treated as missing values.
+fn value_matches_schema_type(value: &Value, schema_type: PayloadSchemaType) -> bool {
+    match (schema_type, value) {
+        (_, Value::Null) => true,
+        (PayloadSchemaType::Keyword | PayloadSchemaType::Text, Value::String(_)) => true,
+        (PayloadSchemaType::Integer, Value::Number(number)) => number.is_i64(),
+        (PayloadSchemaType::Float, Value::Number(_)) => true,
+        (PayloadSchemaType::Bool, Value::Bool(_)) => true,
+        (PayloadSchemaType::Geo, Value::Object(_)) => {
+            serde_json::from_value::<GeoPoint>(value.clone()).is_ok()
+        }
+        (PayloadSchemaType::Datetime, Value::String(string)) => {
+            DateTimePayloadType::from_str(string).is_ok()
+        }
+        (PayloadSchemaType::Uuid, Value::String(string)) => Uuid::parse_str(string).is_ok(),
+        (
+            PayloadSchemaType::Keyword
+            | PayloadSchemaType::Integer
+            | PayloadSchemaType::Float
+            | PayloadSchemaType::Geo
+            | PayloadSchemaType::Text
+            | PayloadSchemaType::Bool
+            | PayloadSchemaType::Datetime
+            | PayloadSchemaType::Uuid,
+            _,
+        ) => false,
+    }
+}
+
 /// Compute a non-empty mapping of multivector limits by name.
 ///
 /// Uses a tiny map as we expect a small number of multivectors to be configured per collection in strict mode.
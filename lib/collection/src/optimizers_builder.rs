@@ -5,6 +5,9 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::types::{HnswConfig, HnswGlobalConfig, QuantizationConfig};
+use segment::utils::disk::{DiskKind, disk_kind};
+use segment::utils::mem::Mem;
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -18,6 +21,8 @@ use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 256_000;
 pub const DEFAULT_INDEXING_THRESHOLD_KB: usize = 10_000;
+/// Auto-tuned segments are limited to this fraction of the total memory
+const AUTO_TUNE_SEGMENT_MEMORY_FRACTION: u64 = 4;
 const SEGMENTS_PATH: &str = "segments";
 const TEMP_SEGMENTS_PATH: &str = "temp_segments";
 
@@ -140,6 +145,63 @@ impl OptimizersConfig {
             num_indexing_threads.saturating_mul(DEFAULT_MAX_SEGMENT_PER_CPU_KB)
         }
     }
+
+    /// Config with segment number, max segment size and indexing threshold picked for the
+    /// resources of the node
+    ///
+    /// Other parameters are kept as is.
+    pub fn auto_tuned(&self, resources: &NodeResources) -> Self {
+        let num_cpus = resources.num_cpus.max(1);
+        let is_hdd = resources.disk_kind == Some(DiskKind::Hdd);
+
+        // Same as automatic selection, but prefer fewer and larger segments on spinning disks,
+        // to reduce the amount of random reads during search
+        let max_segments = if is_hdd { 4 } else { 8 };
+        let default_segment_number = (num_cpus / 2).clamp(2, max_segments);
+
+        // Segment is loaded into RAM for indexing, so keep it within a fraction of the memory
+        let memory_limit_kb = usize::try_from(
+            resources.total_memory_bytes / AUTO_TUNE_SEGMENT_MEMORY_FRACTION / 1024,
+        )
+        .unwrap_or(usize::MAX);
+        let max_segment_size = num_cpus
+            .saturating_mul(DEFAULT_MAX_SEGMENT_PER_CPU_KB)
+            .min(memory_limit_kb)
+            .max(DEFAULT_INDEXING_THRESHOLD_KB);
+
+        // With few CPUs, indexing competes with serving requests, so index less eagerly
+        let indexing_threshold = if num_cpus <= 2 {
+            DEFAULT_INDEXING_THRESHOLD_KB * 2
+        } else {
+            DEFAULT_INDEXING_THRESHOLD_KB
+        };
+
+        Self {
+            default_segment_number,
+            max_segment_size: Some(max_segment_size),
+            indexing_threshold: Some(indexing_threshold.min(max_segment_size)),
+            ..self.clone()
+        }
+    }
+}
+
+/// Hardware resources of the node, optimizer parameters can be auto-tuned for
+#[derive(Debug, Clone, Copy)]
+pub struct NodeResources {
+    pub num_cpus: usize,
+    pub total_memory_bytes: u64,
+    pub disk_kind: Option<DiskKind>,
+}
+
+impl NodeResources {
+    /// Detect resources of the node, with the disk holding `storage_path`
+    pub fn detect(storage_path: &Path) -> Self {
+        Self {
+            num_cpus: common::cpu::get_num_cpus(),
+            total_memory_bytes: Mem::new().total_memory_bytes(),
+            disk_kind: disk_kind(storage_path),
+        }
+    }
 }
 
 pub fn clear_temp_segments(shard_path: &Path) {
@@ -160,6 +222,7 @@ pub fn build_optimizers(
     optimizers_config: &OptimizersConfig,
     hnsw_config: &HnswConfig,
     hnsw_global_config: &HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     quantization_config: &Option<QuantizationConfig>,
 ) -> Arc<Vec<Arc<Optimizer>>> {
     let num_indexing_threads = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -176,6 +239,7 @@ pub fn build_optimizers(
             collection_params.clone(),
             hnsw_config.clone(),
             hnsw_global_config.clone(),
+            chunked_vectors_config,
             quantization_config.clone(),
         )),
         Arc::new(IndexingOptimizer::new(
@@ -186,6 +250,7 @@ pub fn build_optimizers(
             collection_params.clone(),
             hnsw_config.clone(),
             hnsw_global_config.clone(),
+            chunked_vectors_config,
             quantization_config.clone(),
         )),
         Arc::new(VacuumOptimizer::new(
@@ -197,6 +262,7 @@ pub fn build_optimizers(
             collection_params.clone(),
             hnsw_config.clone(),
             hnsw_global_config.clone(),
+            chunked_vectors_config,
             quantization_config.clone(),
         )),
         Arc::new(ConfigMismatchOptimizer::new(
@@ -206,7 +272,54 @@ pub fn build_optimizers(
             collection_params.clone(),
             hnsw_config.clone(),
             hnsw_global_config.clone(),
+            chunked_vectors_config,
             quantization_config.clone(),
         )),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_tuned_optimizers_config() {
+        let config = OptimizersConfig::fixture();
+
+        let large_node = NodeResources {
+            num_cpus: 32,
+            total_memory_bytes: 64 * 1024 * 1024 * 1024,
+            disk_kind: Some(DiskKind::Ssd),
+        };
+        let tuned = config.auto_tuned(&large_node);
+        assert_eq!(tuned.default_segment_number, 8);
+        assert_eq!(
+            tuned.max_segment_size,
+            Some(32 * DEFAULT_MAX_SEGMENT_PER_CPU_KB)
+        );
+        assert_eq!(
+            tuned.indexing_threshold,
+            Some(DEFAULT_INDEXING_THRESHOLD_KB)
+        );
+        assert_eq!(tuned.deleted_threshold, config.deleted_threshold);
+
+        let small_node = NodeResources {
+            num_cpus: 2,
+            total_memory_bytes: 1024 * 1024 * 1024,
+            disk_kind: Some(DiskKind::Hdd),
+        };
+        let tuned = config.auto_tuned(&small_node);
+        assert_eq!(tuned.default_segment_number, 2);
+        assert_eq!(tuned.max_segment_size, Some(256 * 1024));
+        assert_eq!(
+            tuned.indexing_threshold,
+            Some(2 * DEFAULT_INDEXING_THRESHOLD_KB)
+        );
+
+        let hdd_node = NodeResources {
+            disk_kind: Some(DiskKind::Hdd),
+            ..large_node
+        };
+        assert_eq!(config.auto_tuned(&hdd_node).default_segment_number, 4);
+    }
+}
@@ -230,6 +230,7 @@ fn infer_index_from_field_condition(field_condition: &FieldCondition) -> Vec<Fie
             Match::Value(match_value) => infer_index_from_match_value(match_value),
             Match::Text(_match_text) => vec![FieldIndexType::Text],
             Match::Phrase(_match_text) => vec![FieldIndexType::TextPhrase],
+            Match::Pattern(_match_pattern) => vec![FieldIndexType::KeywordMatch],
             Match::Any(match_any) => infer_index_from_any_variants(&match_any.any),
             Match::Except(match_except) => infer_index_from_any_variants(&match_except.except),
             Match::TextAny(_match_text_any) => vec![FieldIndexType::Text],
@@ -384,6 +385,8 @@ impl<'a> Extractor<'a> {
             Condition::HasId(_) => return,
             Condition::CustomIdChecker(_) => return,
             Condition::HasVector(_) => return,
+            // Custom conditions always read payload
+            Condition::Wasm(_) => return,
         };
 
         let full_key = JsonPath::extend_or_new(nested_prefix, key);
@@ -144,6 +144,59 @@ pub fn avg_vector_for_recommendation<'a>(
     Ok(search_vector)
 }
 
+/// Weighted sum of the given vectors, e.g. `a - b + c` for analogy-style queries.
+///
+/// All vectors must be of the same type. Multi-dense vectors can't be combined.
+pub fn weighted_sum_vector<'a>(
+    terms: impl IntoIterator<Item = (f32, VectorRef<'a>)>,
+) -> CollectionResult<VectorInternal> {
+    let mut sum: Option<VectorInternal> = None;
+    for (weight, vector) in terms {
+        let combined = match (sum, vector) {
+            (None, VectorRef::Dense(vector)) => {
+                let vector: DenseVector = vector.iter().map(|x| x * weight).collect();
+                VectorInternal::from(vector)
+            }
+            (None, VectorRef::Sparse(vector)) => {
+                let mut vector = vector.clone();
+                vector.values.iter_mut().for_each(|x| *x *= weight);
+                VectorInternal::from(vector)
+            }
+            (Some(VectorInternal::Dense(mut sum)), VectorRef::Dense(vector)) => {
+                if sum.len() != vector.len() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Can't combine dense vectors of different dimensions: {} and {}",
+                        sum.len(),
+                        vector.len(),
+                    )));
+                }
+                sum.iter_mut()
+                    .zip(vector)
+                    .for_each(|(acc, x)| *acc += x * weight);
+                VectorInternal::Dense(sum)
+            }
+            (Some(VectorInternal::Sparse(sum)), VectorRef::Sparse(vector)) => {
+                VectorInternal::from(sum.combine_aggregate(vector, |acc, x| acc + x * weight))
+            }
+            (_, VectorRef::MultiDense(_)) | (Some(VectorInternal::MultiDense(_)), _) => {
+                return Err(CollectionError::bad_input(
+                    "Multi-dense vectors can't be combined".to_owned(),
+                ));
+            }
+            (Some(_), _) => {
+                return Err(CollectionError::bad_input(
+                    "Can't combine vectors of different types".to_owned(),
+                ));
+            }
+        };
+        sum = Some(combined);
+    }
+
+    sum.ok_or_else(|| {
+        CollectionError::bad_input("Vectors to combine should not be empty".to_owned())
+    })
+}
+
 pub async fn recommend_by<'a, F, Fut>(
     request: RecommendRequestInternal,
     collection: &Collection,
@@ -472,7 +525,7 @@ mod tests {
     use segment::data_types::vectors::{VectorInternal, VectorRef};
     use sparse::common::sparse_vector::SparseVector;
 
-    use super::avg_vectors;
+    use super::{avg_vectors, weighted_sum_vector};
 
     #[test]
     fn test_avg_vectors() {
@@ -509,4 +562,50 @@ mod tests {
         ];
         assert!(avg_vectors(vectors.iter().map(VectorRef::from)).is_err());
     }
+    #[test]
+    fn test_weighted_sum_vector() {
+        let vectors: Vec<VectorInternal> = vec![
+            vec![1.0, 2.0, 3.0].into(),
+            vec![1.0, 1.0, 1.0].into(),
+            vec![0.5, 0.5, 0.5].into(),
+        ];
+        let weights = [1.0, -1.0, 2.0];
+        assert_eq!(
+            weighted_sum_vector(weights.into_iter().zip(vectors.iter().map(VectorRef::from)))
+                .unwrap(),
+            vec![1.0, 2.0, 3.0].into(),
+        );
+
+        let vectors: Vec<VectorInternal> = vec![
+            SparseVector::new(vec![0, 1], vec![1.0, 2.0])
+                .unwrap()
+                .into(),
+            SparseVector::new(vec![1, 2], vec![1.0, 3.0])
+                .unwrap()
+                .into(),
+        ];
+        assert_eq!(
+            weighted_sum_vector(
+                [1.0, -1.0]
+                    .into_iter()
+                    .zip(vectors.iter().map(VectorRef::from))
+            )
+            .unwrap(),
+            SparseVector::new(vec![0, 1, 2], vec![1.0, 1.0, -3.0])
+                .unwrap()
+                .into(),
+        );
+
+        let vectors: Vec<VectorInternal> = vec![vec![1.0, 2.0, 3.0].into(), vec![1.0, 2.0].into()];
+        assert!(
+            weighted_sum_vector(
+                [1.0, 1.0]
+                    .into_iter()
+                    .zip(vectors.iter().map(VectorRef::from))
+            )
+            .is_err()
+        );
+
+        assert!(weighted_sum_vector(Vec::<(f32, VectorRef)>::new()).is_err());
+    }
 }
@@ -14,6 +14,7 @@ use tonic::{Request, Status};
 use url::Url;
 
 use crate::operations::types::{CollectionError, CollectionResult, PeerMetadata};
+use crate::shards::internal_features::InternalFeature;
 use crate::shards::shard::PeerId;
 
 #[derive(Clone)]
@@ -187,6 +188,41 @@ impl ChannelService {
             .is_some_and(|metadata| &metadata.version >= version)
     }
 
+    /// Qdrant version of the specified peer, if known
+    pub fn peer_version(&self, peer_id: PeerId) -> Option<Version> {
+        self.id_to_metadata
+            .read()
+            .get(&peer_id)
+            .map(|metadata| metadata.version.clone())
+    }
+
+    /// Check whether the specified peer supports the given internal API feature
+    ///
+    /// If the metadata is not known for the peer, this returns `false`.
+    pub fn peer_supports(&self, peer_id: PeerId, feature: InternalFeature) -> bool {
+        self.id_to_metadata
+            .read()
+            .get(&peer_id)
+            .is_some_and(|metadata| metadata.supports(feature))
+    }
+
+    /// Check whether all peers support the given internal API feature
+    ///
+    /// If the metadata is not known for any peer, this returns `false`.
+    pub fn all_peers_support(&self, feature: InternalFeature) -> bool {
+        let id_to_address = self.id_to_address.read();
+        let id_to_metadata = self.id_to_metadata.read();
+
+        // Ensure there aren't more peer addresses than metadata
+        if id_to_address.len() > id_to_metadata.len() {
+            return false;
+        }
+
+        id_to_metadata
+            .values()
+            .all(|metadata| metadata.supports(feature))
+    }
+
     /// Get the REST address for the current peer.
     pub fn current_rest_address(&self, this_peer_id: PeerId) -> CollectionResult<Url> {
         // Get local peer URI
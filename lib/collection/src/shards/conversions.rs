@@ -80,6 +80,7 @@ pub fn internal_upsert_points(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             update_filter: None,
+            skip_unchanged: None,
         }),
     })
 }
@@ -95,6 +96,7 @@ pub fn internal_conditional_upsert_points(
     let ConditionalInsertOperationInternal {
         points_op: point_insert_operations,
         condition,
+        skip_unchanged,
     } = point_condition_upsert_operations;
 
     Ok(UpsertPointsInternal {
@@ -113,6 +115,7 @@ pub fn internal_conditional_upsert_points(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
             update_filter: Some(api::grpc::Filter::from(condition)),
+            skip_unchanged: Some(skip_unchanged),
         }),
     })
 }
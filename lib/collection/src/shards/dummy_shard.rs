@@ -9,8 +9,9 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::data_types::order_by::OrderBy;
 use segment::index::field_index::CardinalityEstimation;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
 };
 use tokio::runtime::Handle;
@@ -18,12 +19,13 @@ use tokio::runtime::Handle;
 use crate::operations::OperationWithClockTag;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, RecordInternal,
-    ShardStatus, UpdateResult,
+    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, PointVersion,
+    RecordInternal, ShardStatus, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, SegmentHnswGraphStats};
 
 #[derive(Clone, Debug)]
 pub struct DummyShard {
@@ -81,6 +83,23 @@ impl DummyShard {
         SizeStats::default()
     }
 
+    pub fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        vec![]
+    }
+
+    /// Nothing to warm up, dummy shard has no data
+    pub fn warmup(&self) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    pub fn point_history(&self, _: PointIdType) -> Vec<PointHistoryEntry> {
+        vec![]
+    }
+
+    pub fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        vec![]
+    }
+
     pub fn estimate_cardinality(
         &self,
         _: Option<&Filter>,
@@ -156,6 +175,15 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn point_versions(
+        &self,
+        _: Arc<Vec<PointIdType>>,
+        _: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        self.dummy()
+    }
+
     async fn query_batch(
         &self,
         _requests: Arc<Vec<ShardQueryRequest>>,
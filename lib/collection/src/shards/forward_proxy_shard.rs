@@ -10,6 +10,7 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::data_types::order_by::OrderBy;
 use segment::index::field_index::CardinalityEstimation;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
@@ -25,8 +26,8 @@ use crate::operations::point_ops::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, RecordInternal,
-    UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, PointVersion,
+    RecordInternal, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
@@ -34,9 +35,10 @@ use crate::operations::{
     OperationWithClockTag, SplitByShard as _,
 };
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, SegmentHnswGraphStats};
 
 /// ForwardProxyShard
 ///
@@ -353,6 +355,22 @@ impl ForwardProxyShard {
         self.wrapped_shard.get_size_stats().await
     }
 
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        self.wrapped_shard.get_hnsw_graph_stats().await
+    }
+
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        self.wrapped_shard.warmup(lock).await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        self.wrapped_shard.point_history(point_id)
+    }
+
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        self.wrapped_shard.get_quantization_telemetry().await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -553,6 +571,18 @@ impl ShardOperation for ForwardProxyShard {
             .await
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .point_versions(ids, search_runtime_handle, timeout)
+            .await
+    }
+
     async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
@@ -0,0 +1,317 @@
+use std::collections::{BTreeSet, HashMap};
+
+use api::grpc::qdrant::query_shard_points::Prefetch;
+use api::grpc::qdrant::value::Kind;
+use api::grpc::qdrant::{
+    Filter, ListValue, SearchParams, SetPayloadPointsInternal, Struct, SyncPointsInternal,
+    UpsertPointsInternal, Value,
+};
+use api::grpc::update_operation::Update;
+use segment::vector_storage::quantized::quantization_error::INITIAL_OVERSAMPLING;
+
+/// Capabilities of the internal gRPC API, which may not be supported by all peers.
+///
+/// Each peer advertises supported features in its metadata, distributed through consensus.
+/// During a rolling upgrade the cluster runs mixed versions, so before sending a message
+/// relying on a newer capability, the sender checks if the receiving peer supports it. If not,
+/// the message is downgraded, or the request fails if it can't be served without the feature.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum InternalFeature {
+    /// `adaptive_oversampling` of quantization search params
+    AdaptiveOversampling,
+    /// `unsigned_value` kind of payload values
+    UnsignedPayloadValues,
+    /// `PointHistory` request
+    PointHistory,
+    /// `skip_unchanged` of upserts
+    SkipUnchanged,
+    /// `deterministic` search params
+    DeterministicScoring,
+    /// `PointVersions` request
+    PointVersions,
+}
+
+impl InternalFeature {
+    /// All features supported by this peer
+    pub const ALL: &[InternalFeature] = &[
+        InternalFeature::AdaptiveOversampling,
+        InternalFeature::UnsignedPayloadValues,
+        InternalFeature::PointHistory,
+        InternalFeature::SkipUnchanged,
+        InternalFeature::DeterministicScoring,
+        InternalFeature::PointVersions,
+    ];
+
+    /// Name of the feature in peer metadata
+    ///
+    /// Features are stored by name, so peers can read metadata containing features
+    /// unknown to them.
+    pub fn name(self) -> &'static str {
+        match self {
+            InternalFeature::AdaptiveOversampling => "adaptive_oversampling",
+            InternalFeature::UnsignedPayloadValues => "unsigned_payload_values",
+            InternalFeature::PointHistory => "point_history",
+            InternalFeature::SkipUnchanged => "skip_unchanged",
+            InternalFeature::DeterministicScoring => "deterministic_scoring",
+            InternalFeature::PointVersions => "point_versions",
+        }
+    }
+
+    pub fn supported_names() -> BTreeSet<String> {
+        Self::ALL
+            .iter()
+            .map(|feature| feature.name().to_string())
+            .collect()
+    }
+}
+
+/// Replace adaptive oversampling with the fixed initial oversampling, for peers not supporting it
+///
+/// Such peers ignore the unknown field and would search without any oversampling.
+pub(crate) fn downgrade_adaptive_oversampling(params: Option<&mut SearchParams>) {
+    let Some(quantization) = params.and_then(|params| params.quantization.as_mut()) else {
+        return;
+    };
+    if quantization.adaptive_oversampling.take() == Some(true) {
+        quantization
+            .oversampling
+            .get_or_insert(INITIAL_OVERSAMPLING);
+    }
+}
+
+pub(crate) fn downgrade_prefetch_adaptive_oversampling(prefetches: &mut [Prefetch]) {
+    for prefetch in prefetches {
+        downgrade_adaptive_oversampling(prefetch.params.as_mut());
+        downgrade_prefetch_adaptive_oversampling(&mut prefetch.prefetch);
+    }
+}
+
+/// Whether the search params request deterministic scoring
+///
+/// Peers not supporting it would ignore the unknown field and score with SIMD kernels, which
+/// can't be prevented by downgrading the request, so such requests require the feature.
+pub(crate) fn uses_deterministic_scoring(params: Option<&SearchParams>) -> bool {
+    params.is_some_and(|params| params.deterministic == Some(true))
+}
+
+pub(crate) fn prefetch_uses_deterministic_scoring(prefetches: &[Prefetch]) -> bool {
+    prefetches.iter().any(|prefetch| {
+        uses_deterministic_scoring(prefetch.params.as_ref())
+            || prefetch_uses_deterministic_scoring(&prefetch.prefetch)
+    })
+}
+
+/// Send upserts with `skip_unchanged` as plain upserts, for peers not supporting it
+///
+/// Such peers ignore the unknown field and would evaluate the empty update filter, which comes
+/// with it, for every point. Unchanged points get new versions on such peers instead of being
+/// skipped, their content is the same as on other replicas.
+pub(crate) fn downgrade_skip_unchanged(request: &mut UpsertPointsInternal) {
+    let Some(upsert) = request.upsert_points.as_mut() else {
+        return;
+    };
+    if upsert.skip_unchanged.take() == Some(true)
+        && upsert.update_filter.as_ref() == Some(&Filter::default())
+    {
+        upsert.update_filter = None;
+    }
+}
+
+/// Replace unsigned integer payload values with doubles, for peers not supporting them
+///
+/// Such peers don't know the value kind and would read these values as null. Before unsigned
+/// values were supported, they were sent as doubles.
+pub fn downgrade_unsigned_values<'a>(
+    payloads: impl IntoIterator<Item = &'a mut HashMap<String, Value>>,
+) {
+    for payload in payloads {
+        payload.values_mut().for_each(downgrade_unsigned_value);
+    }
+}
+
+pub(crate) fn downgrade_upsert_unsigned_values(request: &mut UpsertPointsInternal) {
+    downgrade_unsigned_values(
+        request
+            .upsert_points
+            .iter_mut()
+            .flat_map(|upsert| &mut upsert.points)
+            .map(|point| &mut point.payload),
+    );
+}
+
+pub(crate) fn downgrade_sync_unsigned_values(request: &mut SyncPointsInternal) {
+    downgrade_unsigned_values(
+        request
+            .sync_points
+            .iter_mut()
+            .flat_map(|sync| &mut sync.points)
+            .map(|point| &mut point.payload),
+    );
+}
+
+pub(crate) fn downgrade_set_payload_unsigned_values(request: &mut SetPayloadPointsInternal) {
+    downgrade_unsigned_values(
+        request
+            .set_payload_points
+            .iter_mut()
+            .map(|set_payload| &mut set_payload.payload),
+    );
+}
+
+pub(crate) fn downgrade_update_unsigned_values(update: &mut Update) {
+    match update {
+        Update::Upsert(request) => downgrade_upsert_unsigned_values(request),
+        Update::Sync(request) => downgrade_sync_unsigned_values(request),
+        Update::SetPayload(request) | Update::OverwritePayload(request) => {
+            downgrade_set_payload_unsigned_values(request)
+        }
+        Update::Delete(_)
+        | Update::UpdateVectors(_)
+        | Update::DeleteVectors(_)
+        | Update::DeletePayload(_)
+        | Update::ClearPayload(_)
+        | Update::CreateFieldIndex(_)
+        | Update::DeleteFieldIndex(_) => {}
+    }
+}
+
+fn downgrade_unsigned_value(value: &mut Value) {
+    let Some(kind) = &mut value.kind else {
+        return;
+    };
+    match kind {
+        Kind::UnsignedValue(uint) => {
+            let double = *uint as f64;
+            *kind = Kind::DoubleValue(double);
+        }
+        Kind::StructValue(Struct { fields }) => {
+            fields.values_mut().for_each(downgrade_unsigned_value);
+        }
+        Kind::ListValue(ListValue { values }) => {
+            values.iter_mut().for_each(downgrade_unsigned_value);
+        }
+        Kind::NullValue(_)
+        | Kind::DoubleValue(_)
+        | Kind::IntegerValue(_)
+        | Kind::StringValue(_)
+        | Kind::BoolValue(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::grpc::qdrant::{Condition, QuantizationSearchParams, UpsertPoints};
+
+    use super::*;
+    use crate::operations::types::PeerMetadata;
+
+    #[test]
+    fn test_peer_metadata_features() {
+        // Metadata of peers without feature negotiation
+        let metadata: PeerMetadata = serde_json::from_str(r#"{"version": "1.15.0"}"#).unwrap();
+        assert!(!metadata.supports(InternalFeature::AdaptiveOversampling));
+        assert!(metadata.is_outdated());
+
+        // Unknown features of newer peers are accepted
+        let metadata: PeerMetadata = serde_json::from_str(
+            r#"{"version": "99.0.0", "features": ["adaptive_oversampling", "unknown"]}"#,
+        )
+        .unwrap();
+        assert!(metadata.supports(InternalFeature::AdaptiveOversampling));
+    }
+
+    #[test]
+    fn test_downgrade_adaptive_oversampling() {
+        let mut params = SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                adaptive_oversampling: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        downgrade_adaptive_oversampling(Some(&mut params));
+        let quantization = params.quantization.unwrap();
+        assert_eq!(quantization.adaptive_oversampling, None);
+        assert_eq!(quantization.oversampling, Some(INITIAL_OVERSAMPLING));
+
+        // Explicit oversampling is kept
+        let mut params = SearchParams {
+            quantization: Some(QuantizationSearchParams {
+                oversampling: Some(3.0),
+                adaptive_oversampling: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        downgrade_adaptive_oversampling(Some(&mut params));
+        assert_eq!(params.quantization.unwrap().oversampling, Some(3.0));
+    }
+
+    #[test]
+    fn test_downgrade_skip_unchanged() {
+        let request = |update_filter| UpsertPointsInternal {
+            upsert_points: Some(UpsertPoints {
+                update_filter,
+                skip_unchanged: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sent as a plain upsert
+        let mut skip_unchanged = request(Some(Filter::default()));
+        downgrade_skip_unchanged(&mut skip_unchanged);
+        let upsert = skip_unchanged.upsert_points.unwrap();
+        assert_eq!(upsert.skip_unchanged, None);
+        assert_eq!(upsert.update_filter, None);
+
+        // Update filter is kept
+        let filter = Filter {
+            must: vec![Condition::default()],
+            ..Default::default()
+        };
+        let mut conditional = request(Some(filter.clone()));
+        downgrade_skip_unchanged(&mut conditional);
+        let upsert = conditional.upsert_points.unwrap();
+        assert_eq!(upsert.skip_unchanged, None);
+        assert_eq!(upsert.update_filter, Some(filter));
+    }
+
+    #[test]
+    fn test_downgrade_unsigned_values() {
+        let unsigned = |uint| Value {
+            kind: Some(Kind::UnsignedValue(uint)),
+        };
+        let double = |double| Value {
+            kind: Some(Kind::DoubleValue(double)),
+        };
+        let integer = Value {
+            kind: Some(Kind::IntegerValue(1)),
+        };
+
+        let mut payload = HashMap::from([
+            ("unsigned".to_string(), unsigned(u64::MAX)),
+            ("integer".to_string(), integer.clone()),
+            (
+                "nested".to_string(),
+                Value {
+                    kind: Some(Kind::ListValue(ListValue {
+                        values: vec![unsigned(u64::MAX), integer.clone()],
+                    })),
+                },
+            ),
+        ]);
+        downgrade_unsigned_values([&mut payload]);
+
+        assert_eq!(payload["unsigned"], double(u64::MAX as f64));
+        assert_eq!(payload["integer"], integer);
+        assert_eq!(
+            payload["nested"],
+            Value {
+                kind: Some(Kind::ListValue(ListValue {
+                    values: vec![double(u64::MAX as f64), integer],
+                })),
+            },
+        );
+    }
+}
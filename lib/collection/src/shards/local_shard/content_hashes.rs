@@ -0,0 +1,179 @@
+//! Content hashes of points upserted with `skip_unchanged`
+//!
+//! A hash of vectors and payload is kept for each point upserted with `skip_unchanged`. Hashes
+//! are updated when operations are written to WAL, in WAL order, so unchanged points can be
+//! dropped from an upsert before it is written. Such points are not written to WAL at all, and
+//! their version is not bumped. The upsert itself is still written, even if no points are left,
+//! because WAL delta recovery needs a record with the clock tag of every operation.
+//!
+//! Any other operation changing a point drops its hash. Operations selecting points by filter,
+//! or deleting points in a range, drop all hashes.
+//!
+//! Hashes are kept in memory only. After a restart, unchanged points are still skipped when the
+//! upsert is applied, by comparing them with the stored points.
+
+use std::hash::{Hash as _, Hasher as _};
+
+use ahash::{AHashMap, AHashSet};
+use fnv::FnvHasher;
+use segment::types::{Filter, PointIdType};
+
+use crate::operations::CollectionUpdateOperations;
+use crate::operations::point_ops::{
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+};
+
+#[derive(Debug, Default)]
+pub struct ContentHashes {
+    hashes: AHashMap<PointIdType, u64>,
+}
+
+impl ContentHashes {
+    /// Drop points from the upsert, which were last written to WAL with the same content
+    ///
+    /// Only affects upserts with `skip_unchanged` set.
+    pub fn skip_unchanged(&self, operation: &mut CollectionUpdateOperations) {
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+            ConditionalInsertOperationInternal {
+                points_op,
+                condition: _,
+                skip_unchanged: true,
+            },
+        )) = operation
+        else {
+            return;
+        };
+
+        if self.hashes.is_empty() {
+            return;
+        }
+
+        let unchanged: AHashSet<_> = content_hashes(points_op)
+            .into_iter()
+            .filter(|(id, hash)| self.hashes.get(id) == Some(hash))
+            .map(|(id, _)| id)
+            .collect();
+
+        if !unchanged.is_empty() {
+            points_op.retain_point_ids(|id| !unchanged.contains(id));
+        }
+    }
+
+    /// Update hashes of points changed by the operation, which was written to WAL
+    pub fn record(&mut self, operation: &CollectionUpdateOperations) {
+        match operation {
+            // Content of the points is known only if no condition can exclude them
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    condition,
+                    skip_unchanged: true,
+                }),
+            ) if *condition == Filter::default() => {
+                self.hashes.extend(content_hashes(points_op));
+            }
+
+            CollectionUpdateOperations::FieldIndexOperation(_) => {}
+
+            _ if self.hashes.is_empty() => {}
+
+            // Sync deletes points missing in the operation within its range
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(_)) => {
+                self.hashes.clear();
+            }
+
+            operation => match operation.point_ids() {
+                Some(ids) => {
+                    for id in ids {
+                        self.hashes.remove(&id);
+                    }
+                }
+                None => self.hashes.clear(),
+            },
+        }
+    }
+}
+
+/// Hash of vectors and payload of each point, the last one if a point is upserted multiple times
+fn content_hashes(points_op: &PointInsertOperationsInternal) -> AHashMap<PointIdType, u64> {
+    points_op
+        .clone()
+        .into_point_vec()
+        .into_iter()
+        .map(|point| {
+            let mut hasher = FnvHasher::default();
+            point.vector.hash(&mut hasher);
+            point.payload.hash(&mut hasher);
+            (point.id, hasher.finish())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::VectorStructInternal;
+    use segment::payload_json;
+
+    use super::*;
+    use crate::operations::point_ops::PointStructPersisted;
+
+    fn upsert(points: &[(u64, &str)], skip_unchanged: bool) -> CollectionUpdateOperations {
+        let points = points
+            .iter()
+            .map(|&(id, color)| PointStructPersisted {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0]).into(),
+                payload: Some(payload_json! {"color": color}),
+            })
+            .collect();
+
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+            ConditionalInsertOperationInternal {
+                points_op: PointInsertOperationsInternal::PointsList(points),
+                condition: Filter::default(),
+                skip_unchanged,
+            },
+        ))
+    }
+
+    fn point_ids(operation: &CollectionUpdateOperations) -> Vec<PointIdType> {
+        operation.point_ids().unwrap()
+    }
+
+    #[test]
+    fn test_skip_unchanged_points() {
+        let mut hashes = ContentHashes::default();
+
+        hashes.record(&upsert(&[(1, "red"), (2, "red")], true));
+
+        // Point 1 is unchanged, point 2 gets a new payload and point 3 is new
+        let mut operation = upsert(&[(1, "red"), (2, "blue"), (3, "red")], true);
+        hashes.skip_unchanged(&mut operation);
+        assert_eq!(point_ids(&operation), vec![2.into(), 3.into()]);
+        hashes.record(&operation);
+
+        // Upserts without `skip_unchanged` are written as is, and drop hashes of their points
+        let mut operation = upsert(&[(2, "blue"), (3, "red")], false);
+        hashes.skip_unchanged(&mut operation);
+        assert_eq!(point_ids(&operation), vec![2.into(), 3.into()]);
+        hashes.record(&operation);
+
+        let mut operation = upsert(&[(1, "red"), (2, "blue"), (3, "red")], true);
+        hashes.skip_unchanged(&mut operation);
+        assert_eq!(point_ids(&operation), vec![2.into(), 3.into()]);
+    }
+
+    #[test]
+    fn test_filter_operations_drop_all_hashes() {
+        let mut hashes = ContentHashes::default();
+
+        hashes.record(&upsert(&[(1, "red"), (2, "red")], true));
+        hashes.record(&CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(Filter::default()),
+        ));
+
+        let mut operation = upsert(&[(1, "red"), (2, "red")], true);
+        hashes.skip_unchanged(&mut operation);
+        assert_eq!(point_ids(&operation), vec![1.into(), 2.into()]);
+    }
+}
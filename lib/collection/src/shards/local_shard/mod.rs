@@ -1,13 +1,18 @@
 pub mod clock_map;
+pub(super) mod content_hashes;
 pub mod disk_usage_watcher;
 pub(super) mod facet;
 pub(super) mod formula_rescore;
+pub mod point_history;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
 mod snapshot;
 mod telemetry;
+pub(super) mod upsert_batch;
+pub mod wal_offload;
+pub(crate) mod warmup;
 
 #[cfg(test)]
 mod snapshot_tests;
@@ -33,9 +38,12 @@ use futures::stream::FuturesUnordered;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::common::warmup::LockedFiles;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
-use segment::segment_constructor::{build_segment, load_segment};
+use segment::segment_constructor::{
+    build_segment_with_chunked_vectors_config, load_segment_with_chunked_vectors_config,
+};
 use segment::types::{
     Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, SegmentConfig, SegmentType,
 };
@@ -46,7 +54,11 @@ use tokio::sync::mpsc::Sender;
 use tokio::sync::{Mutex, RwLock as TokioRwLock, mpsc};
 
 use self::clock_map::{ClockMap, RecoveryPoint};
+use self::content_hashes::ContentHashes;
 use self::disk_usage_watcher::DiskUsageWatcher;
+use self::point_history::{PointHistory, PointHistoryEntry, operation_timestamp};
+use self::upsert_batch::UpsertBatcher;
+use self::wal_offload::WalOffloader;
 use super::update_tracker::UpdateTracker;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
@@ -100,10 +112,20 @@ pub struct LocalShard {
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
     pub(super) total_optimized_points: Arc<AtomicUsize>,
+    /// History of changes of each point, if enabled in WAL config
+    pub(super) point_history: Option<Arc<PointHistory>>,
+    /// Merges concurrent upserts into a single WAL record, if batching window is set
+    upsert_batcher: Option<UpsertBatcher>,
+    /// Content of points upserted with `skip_unchanged`, locked while writing to WAL
+    content_hashes: Arc<Mutex<ContentHashes>>,
+    /// Segment files locked in RAM by warmup, kept in sync with segments by the optimizers
+    locked_files: Arc<LockedFiles>,
     update_runtime: Handle,
     pub(super) search_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
     read_rate_limiter: Option<ParkingMutex<RateLimiter>>,
+    /// Global optimization budget, search latencies are reported to it to throttle optimizer IO
+    optimizer_resource_budget: ResourceBudget,
 
     /// Update operation lock
     /// The lock, which must prevent updates critical sections of other operations, which
@@ -141,6 +163,20 @@ impl LocalShard {
         move_dir(wal_from, wal_to).await?;
         move_dir(segments_from, segments_to).await?;
 
+        let point_history_from = PointHistory::path(from);
+        if point_history_from.exists() {
+            move_file(point_history_from, PointHistory::path(to)).await?;
+        }
+
+        let wal_offload_pending_from = WalOffloader::pending_path(from);
+        if wal_offload_pending_from.exists() {
+            move_dir(wal_offload_pending_from, WalOffloader::pending_path(to)).await?;
+        }
+        let wal_offload_state_from = WalOffloader::state_path(from);
+        if wal_offload_state_from.exists() {
+            move_file(wal_offload_state_from, WalOffloader::state_path(to)).await?;
+        }
+
         LocalShardClocks::move_data(from, to).await?;
 
         Ok(())
@@ -169,6 +205,22 @@ impl LocalShard {
             remove_dir_all(segments_path).await?;
         }
 
+        // Delete point history
+        let point_history_path = PointHistory::path(shard_path);
+        if point_history_path.exists() {
+            remove_file(point_history_path).await?;
+        }
+
+        // Delete WAL segments pending for offloading
+        let wal_offload_pending_path = WalOffloader::pending_path(shard_path);
+        if wal_offload_pending_path.exists() {
+            remove_dir_all(wal_offload_pending_path).await?;
+        }
+        let wal_offload_state_path = WalOffloader::state_path(shard_path);
+        if wal_offload_state_path.exists() {
+            remove_file(wal_offload_state_path).await?;
+        }
+
         LocalShardClocks::delete_data(shard_path).await?;
 
         Ok(())
@@ -208,6 +260,19 @@ impl LocalShard {
         let scroll_read_lock = Arc::new(tokio::sync::RwLock::new(()));
         let update_tracker = UpdateTracker::default();
 
+        let point_history = (config.wal_config.wal_point_history > 0).then(|| {
+            Arc::new(PointHistory::load(
+                shard_path,
+                config.wal_config.wal_point_history,
+            ))
+        });
+
+        let upsert_batcher = (config.wal_config.wal_batch_window_ms > 0).then(|| {
+            UpsertBatcher::new(Duration::from_millis(config.wal_config.wal_batch_window_ms))
+        });
+
+        let locked_files = Arc::new(LockedFiles::default());
+
         let mut update_handler = UpdateHandler::new(
             collection_name.clone(),
             shared_storage_config.clone(),
@@ -220,11 +285,14 @@ impl LocalShard {
             segment_holder.clone(),
             locked_wal.clone(),
             config.optimizer_config.flush_interval_sec,
+            Duration::from_millis(config.wal_config.wal_batch_window_ms),
             config.optimizer_config.max_optimization_threads,
             clocks.clone(),
             shard_path.into(),
             scroll_read_lock.clone(),
             update_tracker.clone(),
+            point_history.clone(),
+            locked_files.clone(),
         );
 
         let (update_sender, update_receiver) =
@@ -256,8 +324,13 @@ impl LocalShard {
             optimizers,
             optimizers_log,
             total_optimized_points,
+            point_history,
+            upsert_batcher,
+            content_hashes: Default::default(),
+            locked_files,
             disk_usage_watcher,
             read_rate_limiter,
+            optimizer_resource_budget,
             update_operation_lock: scroll_read_lock,
         }
     }
@@ -266,6 +339,16 @@ impl LocalShard {
         self.segments.deref()
     }
 
+    /// Recorded changes of the point, the newest first
+    ///
+    /// Empty if point history is disabled.
+    pub fn point_history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        self.point_history
+            .as_ref()
+            .map(|point_history| point_history.history(point_id))
+            .unwrap_or_default()
+    }
+
     /// Recovers shard from disk.
     #[allow(clippy::too_many_arguments)]
     pub async fn load(
@@ -286,7 +369,20 @@ impl LocalShard {
         let wal_path = Self::wal_path(shard_path);
         let segments_path = Self::segments_path(shard_path);
 
-        let wal: SerdeWal<OperationWithClockTag> = SerdeWal::new(
+        // Local WAL is lost, restore its history from offloaded segments to replay it
+        if !wal_path.exists()
+            && let Some(wal_offload) = &shared_storage_config.wal_offload
+        {
+            let restored = WalOffloader::new(wal_offload, &collection_id, shard_path)?
+                .restore(&wal_path)
+                .await?;
+            log::info!(
+                "Restored {restored} offloaded WAL segments of shard {}",
+                shard_path.display(),
+            );
+        }
+
+        let mut wal: SerdeWal<OperationWithClockTag> = SerdeWal::new(
             wal_path.to_str().unwrap(),
             (&collection_config_read.wal_config).into(),
         )
@@ -336,11 +432,17 @@ impl LocalShard {
             })
             .map(|entry| entry.path());
 
+        let chunked_vectors_config = shared_storage_config.chunked_vectors_config;
+
         let mut segment_stream = futures::stream::iter(segment_paths)
             .map(|segment_path| {
                 let payload_index_schema = Arc::clone(&payload_index_schema);
                 tokio::task::spawn_blocking(move || {
-                    let segment = load_segment(&segment_path, &AtomicBool::new(false))?;
+                    let segment = load_segment_with_chunked_vectors_config(
+                        &segment_path,
+                        chunked_vectors_config,
+                        &AtomicBool::new(false),
+                    )?;
 
                     let Some(mut segment) = segment else {
                         std::fs::remove_dir_all(&segment_path).map_err(|err| {
@@ -401,6 +503,44 @@ impl LocalShard {
             log::debug!("Deduplicated {res} points");
         }
 
+        // Segments are older than the local WAL, e.g. restored from a backup. Operations they
+        // miss were truncated locally, restore them from offloaded segments to replay them.
+        // WAL acknowledged past segment versions by no-op operations is restored needlessly, but
+        // replaying it again is harmless.
+        let segments_version = segment_holder
+            .iter()
+            .map(|(_, segment)| segment.get().read().version())
+            .max()
+            .unwrap_or_default();
+        let mut replay_from = None;
+        if wal.first_index() > segments_version + 1
+            && let Some(wal_offload) = &shared_storage_config.wal_offload
+        {
+            drop(wal);
+            let restored = WalOffloader::new(wal_offload, &collection_id, shard_path)?
+                .restore(&wal_path)
+                .await?;
+            log::info!(
+                "Restored {restored} offloaded WAL segments of shard {} to replay from version {segments_version}",
+                shard_path.display(),
+            );
+
+            wal = SerdeWal::new(
+                wal_path.to_str().unwrap(),
+                (&collection_config_read.wal_config).into(),
+            )
+            .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+
+            if wal.first_closed_index() > segments_version + 1 {
+                log::warn!(
+                    "WAL of shard {} is missing operations since version {segments_version}, replaying from {}",
+                    shard_path.display(),
+                    wal.first_closed_index(),
+                );
+            }
+            replay_from = Some(segments_version.max(wal.first_closed_index()));
+        }
+
         clear_temp_segments(shard_path);
         let optimizers = build_optimizers(
             shard_path,
@@ -408,6 +548,7 @@ impl LocalShard {
             &effective_optimizers_config,
             &collection_config_read.hnsw_config,
             &shared_storage_config.hnsw_global_config,
+            shared_storage_config.chunked_vectors_config,
             &collection_config_read.quantization_config,
         );
 
@@ -450,7 +591,9 @@ impl LocalShard {
         .await;
 
         // Apply outstanding operations from WAL
-        local_shard.load_from_wal(collection_id).await?;
+        local_shard
+            .load_from_wal(collection_id, replay_from)
+            .await?;
 
         Ok(local_shard)
     }
@@ -547,9 +690,17 @@ impl LocalShard {
                 sparse_vector_data: sparse_vector_params.clone(),
                 payload_storage_type: config.params.payload_storage_type(),
             };
+            let chunked_vectors_config = shared_storage_config.chunked_vectors_config;
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
-                .spawn(move || build_segment(&path_clone, &segment_config, true))
+                .spawn(move || {
+                    build_segment_with_chunked_vectors_config(
+                        &path_clone,
+                        &segment_config,
+                        chunked_vectors_config,
+                        true,
+                    )
+                })
                 .unwrap();
             build_handlers.push(segment);
         }
@@ -581,6 +732,7 @@ impl LocalShard {
             &effective_optimizers_config,
             &config.hnsw_config,
             &shared_storage_config.hnsw_global_config,
+            shared_storage_config.chunked_vectors_config,
             &config.quantization_config,
         );
 
@@ -616,10 +768,18 @@ impl LocalShard {
     }
 
     /// Loads latest collection operations from WAL
-    pub async fn load_from_wal(&self, collection_id: CollectionId) -> CollectionResult<()> {
+    ///
+    /// Operations are replayed from `replay_from` if given, instead of the first unacknowledged one.
+    pub async fn load_from_wal(
+        &self,
+        collection_id: CollectionId,
+        replay_from: Option<u64>,
+    ) -> CollectionResult<()> {
         let mut newest_clocks = self.wal.newest_clocks.lock().await;
         let wal = self.wal.wal.lock().await;
-        let bar = ProgressBar::new(wal.len(false));
+        let replay_from = replay_from.unwrap_or_else(|| wal.first_index());
+        let replay_len = (wal.first_index() + wal.len(false)).saturating_sub(replay_from);
+        let bar = ProgressBar::new(replay_len);
 
         let progress_style = ProgressStyle::default_bar()
             .template("{msg} [{elapsed_precise}] {wide_bar} {pos}/{len} (eta:{eta})")
@@ -627,9 +787,8 @@ impl LocalShard {
         bar.set_style(progress_style);
 
         log::debug!(
-            "Recovering shard {} starting reading WAL from {}",
+            "Recovering shard {} starting reading WAL from {replay_from}",
             self.path.display(),
-            wal.first_index(),
         );
 
         bar.set_message(format!("Recovering collection {collection_id}"));
@@ -640,9 +799,8 @@ impl LocalShard {
         let mut last_progress_report = Instant::now();
         if !show_progress_bar {
             log::info!(
-                "Recovering shard {}: 0/{} (0%)",
+                "Recovering shard {}: 0/{replay_len} (0%)",
                 self.path.display(),
-                wal.len(false),
             );
         }
 
@@ -652,27 +810,50 @@ impl LocalShard {
         // `SerdeWal` wrapper persists/keeps track of this index (in addition to any handling
         // in the `wal` crate itself).
         //
-        // `SerdeWal::read_all` starts reading WAL from the first "un-truncated" index,
+        // Unless `replay_from` is given, WAL is read from the first "un-truncated" index,
         // so no additional handling required to "skip" any potentially applied entries.
         //
         // Note, that it's not guaranteed that some operation won't be re-applied to the storage.
-        // (`SerdeWal::read_all` may even start reading WAL from some already truncated
-        // index *occasionally*), but the storage can handle it.
+        // (WAL may even be read from some already truncated index *occasionally*, or from
+        // restored offloaded segments), but the storage can handle it.
 
-        for (op_num, update) in wal.read_all(false) {
-            if let Some(clock_tag) = update.clock_tag {
+        for (op_num, update) in wal.read(replay_from) {
+            for clock_tag in update.clock_tags() {
                 newest_clocks.advance_clock(clock_tag);
             }
 
-            // Propagate `CollectionError::ServiceError`, but skip other error types.
-            match &CollectionUpdater::update(
+            let point_changes = self
+                .point_history
+                .as_ref()
+                .map(|_| PointHistory::pending_changes(&segments.read(), &update.operation));
+
+            let result = CollectionUpdater::update(
                 segments,
                 op_num,
                 update.operation,
                 self.update_operation_lock.clone(),
                 self.update_tracker.clone(),
                 &HardwareCounterCell::disposable(), // Internal operation, no measurement needed.
-            ) {
+            );
+
+            if let (Some(point_history), Some(point_changes)) = (&self.point_history, point_changes)
+            {
+                let point_changes = if result.is_ok() {
+                    point_changes
+                } else {
+                    Vec::new()
+                };
+                // Time of the original operation, not of the replay
+                point_history.record(
+                    &segments.read(),
+                    op_num,
+                    operation_timestamp(update.timestamp),
+                    point_changes,
+                );
+            }
+
+            // Propagate `CollectionError::ServiceError`, but skip other error types.
+            match &result {
                 Err(err @ CollectionError::ServiceError { error, backtrace }) => {
                     let path = self.path.display();
 
@@ -703,9 +884,8 @@ impl LocalShard {
             if !show_progress_bar && last_progress_report.elapsed() >= WAL_LOAD_REPORT_EVERY {
                 let progress = bar.position();
                 log::info!(
-                    "{progress}/{} ({}%)",
-                    wal.len(false),
-                    (progress as f32 / wal.len(false) as f32 * 100.0) as usize,
+                    "{progress}/{replay_len} ({}%)",
+                    (progress as f32 / replay_len as f32 * 100.0) as usize,
                 );
                 last_progress_report = Instant::now();
             }
@@ -722,10 +902,7 @@ impl LocalShard {
 
         bar.finish();
         if !show_progress_bar {
-            log::info!(
-                "Recovered collection {collection_id}: {0}/{0} (100%)",
-                wal.len(false),
-            );
+            log::info!("Recovered collection {collection_id}: {replay_len}/{replay_len} (100%)",);
         }
 
         // The storage is expected to be consistent after WAL recovery
@@ -782,6 +959,7 @@ impl LocalShard {
             &config.optimizer_config,
             &config.hnsw_config,
             &self.shared_storage_config.hnsw_global_config,
+            self.shared_storage_config.chunked_vectors_config,
             &config.quantization_config,
         );
         update_handler.optimizers = new_optimizers;
@@ -0,0 +1,801 @@
+//! History of changes of individual points
+//!
+//! Keeps the last versions of each point changed by update operations, so it is possible to
+//! find out which operation changed a point, and how. Vectors are not stored, only their hashes.
+//!
+//! Changes are appended to a log file next to the shard, one record per operation. The log is
+//! rewritten with only the kept changes once most of its records are outdated. Only a limited
+//! number of deleted points is kept, history of the oldest deleted points is evicted.
+//!
+//! History is best-effort: it is flushed by the flush worker, but WAL is acknowledged without
+//! waiting for it. Changes not flushed before a crash are recorded again on WAL replay, if the
+//! operations are still in WAL, and lost otherwise. Every replica records changes it applies.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher as _};
+use std::io::{BufRead as _, BufReader, BufWriter, Read as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use ahash::AHashSet;
+use chrono::{DateTime, Utc};
+use common::counter::hardware_counter::HardwareCounterCell;
+use fnv::FnvHasher;
+use io::file_operations::atomic_save;
+use itertools::Itertools as _;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::json_path::JsonPath;
+use segment::types::{Filter, Payload, PayloadKeyType, PointIdType, SeqNumberType, VectorNameBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::CollectionUpdateOperations;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, VectorStructPersisted,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::vector_ops::VectorOperations;
+
+const POINT_HISTORY_FILE: &str = "point_history.log";
+
+/// Number of deleted points, which history is kept for
+const MAX_DELETED_POINTS: usize = 10_000;
+
+/// Log is not rewritten until it has at least this number of changes
+const MIN_COMPACTION_ENTRIES: usize = 10_000;
+
+/// Single change of a point
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct PointHistoryEntry {
+    /// Number of the operation, which made the change
+    pub version: SeqNumberType,
+    /// Time the operation was accepted by this peer
+    pub timestamp: DateTime<Utc>,
+    /// What was changed
+    pub change: PointChange,
+}
+
+/// Change of a point made by an update operation
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PointChange {
+    /// Point was inserted or fully replaced
+    Upsert {
+        /// Hashes of the new vectors, by vector name
+        vectors: BTreeMap<VectorNameBuf, String>,
+        /// New payload
+        payload: Option<Payload>,
+    },
+    /// Some vectors of the point were replaced
+    UpdateVectors {
+        /// Hashes of the new vectors, by vector name
+        vectors: BTreeMap<VectorNameBuf, String>,
+    },
+    /// Some vectors of the point were deleted
+    DeleteVectors { vectors: Vec<VectorNameBuf> },
+    /// Payload values were set, keeping other values
+    SetPayload {
+        payload: Payload,
+        /// Nested key the payload was set under
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key: Option<JsonPath>,
+    },
+    /// Payload was replaced entirely
+    OverwritePayload { payload: Payload },
+    /// Payload keys were deleted
+    DeletePayload { keys: Vec<PayloadKeyType> },
+    /// Whole payload was deleted
+    ClearPayload,
+    /// Point was deleted
+    Delete,
+}
+
+/// Changes, which an operation is going to make, collected before the operation is applied
+pub type PendingChanges = Vec<(PointIdType, PointChange)>;
+
+/// Bounded history of changes of each point of a shard, persisted on disk
+#[derive(Debug)]
+pub struct PointHistory {
+    /// Maximum number of changes kept per point
+    max_versions: usize,
+    path: PathBuf,
+    state: Mutex<PointHistoryState>,
+}
+
+#[derive(Debug, Default)]
+struct PointHistoryState {
+    points: HashMap<PointIdType, VecDeque<PointHistoryEntry>>,
+    /// Deleted points with the version of the deletion, the oldest first
+    deleted: VecDeque<(PointIdType, SeqNumberType)>,
+    /// Number of the last operation recorded into the history
+    last_version: Option<SeqNumberType>,
+    /// Number of changes kept in memory
+    entries: usize,
+    /// Number of changes in the log file, including outdated ones
+    file_entries: usize,
+    /// Appends records to the log file, `None` if the file must be rewritten
+    writer: Option<BufWriter<File>>,
+}
+
+/// Changes of a single operation, as stored in the log file
+#[derive(Debug, Serialize, Deserialize)]
+struct PointHistoryRecord {
+    version: SeqNumberType,
+    timestamp: DateTime<Utc>,
+    changes: PendingChanges,
+}
+
+impl PointHistory {
+    pub fn load(shard_path: &Path, max_versions: usize) -> Self {
+        let path = Self::path(shard_path);
+        let mut state = PointHistoryState::default();
+
+        let complete = if path.exists() {
+            Self::read(&path, &mut state, max_versions).unwrap_or_else(|err| {
+                log::warn!(
+                    "Failed to load point history from {}: {err}",
+                    path.display(),
+                );
+                false
+            })
+        } else {
+            true
+        };
+
+        // Partially written or unreadable log is rewritten on the next flush
+        if complete {
+            state.writer = Self::open_writer(&path)
+                .inspect_err(|err| {
+                    log::warn!(
+                        "Failed to open point history file {}: {err}",
+                        path.display(),
+                    );
+                })
+                .ok();
+        }
+
+        Self {
+            max_versions,
+            path,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn path(shard_path: &Path) -> PathBuf {
+        shard_path.join(POINT_HISTORY_FILE)
+    }
+
+    /// Read records of the log file into the state
+    ///
+    /// Returns `false` if the log ends with a partially written record.
+    fn read(
+        path: &Path,
+        state: &mut PointHistoryState,
+        max_versions: usize,
+    ) -> CollectionResult<bool> {
+        let file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        loop {
+            if reader.fill_buf()?.is_empty() {
+                return Ok(true);
+            }
+
+            let mut len = [0; 4];
+            if reader.read_exact(&mut len).is_err() {
+                return Ok(false);
+            }
+            let len = u32::from_le_bytes(len);
+            if u64::from(len) > file_len {
+                return Ok(false);
+            }
+
+            let mut bytes = vec![0; len as usize];
+            if reader.read_exact(&mut bytes).is_err() {
+                return Ok(false);
+            }
+            let Ok(record) = rmp_serde::from_slice::<PointHistoryRecord>(&bytes) else {
+                return Ok(false);
+            };
+
+            state.file_entries += record.changes.len();
+            state.apply(record, max_versions);
+        }
+    }
+
+    fn open_writer(path: &Path) -> std::io::Result<BufWriter<File>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(BufWriter::new(file))
+    }
+
+    /// Collect changes, which the operation is going to make
+    ///
+    /// Must be called before the operation is applied, to resolve filters and deleted points.
+    pub fn pending_changes(
+        segments: &SegmentHolder,
+        operation: &CollectionUpdateOperations,
+    ) -> PendingChanges {
+        match operation {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::UpsertPoints(points) => upsert_changes(points),
+                PointOperations::UpsertPointsConditional(operation) => {
+                    upsert_changes(&operation.points_op)
+                }
+                PointOperations::DeletePoints { ids } => {
+                    delete_changes(segments, ids.iter().copied())
+                }
+                PointOperations::DeletePointsByFilter(filter) => {
+                    delete_changes(segments, filtered_points(segments, filter))
+                }
+                PointOperations::SyncPoints(operation) => {
+                    let synced: AHashSet<_> =
+                        operation.points.iter().map(|point| point.id).collect();
+                    let removed = segments
+                        .iter()
+                        .flat_map(|(_, segment)| {
+                            segment
+                                .get()
+                                .read()
+                                .read_range(operation.from_id, operation.to_id)
+                        })
+                        .filter(|point_id| !synced.contains(point_id))
+                        .unique()
+                        .map(|point_id| (point_id, PointChange::Delete));
+                    operation
+                        .points
+                        .iter()
+                        .map(|point| {
+                            let change = PointChange::Upsert {
+                                vectors: vector_hashes(&point.vector),
+                                payload: point.payload.clone(),
+                            };
+                            (point.id, change)
+                        })
+                        .chain(removed)
+                        .collect()
+                }
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::UpdateVectors(operation) => operation
+                    .points
+                    .iter()
+                    .map(|point| {
+                        let change = PointChange::UpdateVectors {
+                            vectors: vector_hashes(&point.vector),
+                        };
+                        (point.id, change)
+                    })
+                    .collect(),
+                VectorOperations::DeleteVectors(points, vectors) => {
+                    let change = PointChange::DeleteVectors {
+                        vectors: vectors.clone(),
+                    };
+                    same_changes(points.points.iter().copied(), change)
+                }
+                VectorOperations::DeleteVectorsByFilter(filter, vectors) => {
+                    let change = PointChange::DeleteVectors {
+                        vectors: vectors.clone(),
+                    };
+                    same_changes(filtered_points(segments, filter), change)
+                }
+            },
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                PayloadOps::SetPayload(operation) => {
+                    let change = PointChange::SetPayload {
+                        payload: operation.payload.clone(),
+                        key: operation.key.clone(),
+                    };
+                    let points = selected_points(
+                        segments,
+                        operation.points.as_deref(),
+                        operation.filter.as_ref(),
+                    );
+                    same_changes(points, change)
+                }
+                PayloadOps::DeletePayload(operation) => {
+                    let change = PointChange::DeletePayload {
+                        keys: operation.keys.clone(),
+                    };
+                    let points = selected_points(
+                        segments,
+                        operation.points.as_deref(),
+                        operation.filter.as_ref(),
+                    );
+                    same_changes(points, change)
+                }
+                PayloadOps::ClearPayload { points } => {
+                    same_changes(points.iter().copied(), PointChange::ClearPayload)
+                }
+                PayloadOps::ClearPayloadByFilter(filter) => {
+                    same_changes(filtered_points(segments, filter), PointChange::ClearPayload)
+                }
+                PayloadOps::OverwritePayload(operation) => {
+                    let change = PointChange::OverwritePayload {
+                        payload: operation.payload.clone(),
+                    };
+                    let points = selected_points(
+                        segments,
+                        operation.points.as_deref(),
+                        operation.filter.as_ref(),
+                    );
+                    same_changes(points, change)
+                }
+            },
+            CollectionUpdateOperations::FieldIndexOperation(_) => Vec::new(),
+        }
+    }
+
+    /// Record changes of an applied operation
+    ///
+    /// Only changes, which were actually applied, are recorded: the point must have the version
+    /// of the operation, or be deleted for delete changes. This skips points not matching the
+    /// condition of a conditional update. Pass no changes if the operation failed.
+    ///
+    /// `timestamp` is the time the operation was written to WAL, so it is the same on replay.
+    /// Changes of operations recorded before are ignored, so WAL can be replayed safely.
+    pub fn record(
+        &self,
+        segments: &SegmentHolder,
+        op_num: SeqNumberType,
+        timestamp: DateTime<Utc>,
+        changes: PendingChanges,
+    ) {
+        let changes = changes
+            .into_iter()
+            .filter(|(point_id, change)| {
+                let version = segments
+                    .iter()
+                    .filter_map(|(_, segment)| segment.get().read().point_version(*point_id))
+                    .max();
+                match change {
+                    PointChange::Delete => version.is_none(),
+                    // Version might be ahead of the operation on WAL replay
+                    _ => version.is_some_and(|version| version >= op_num),
+                }
+            })
+            .collect_vec();
+
+        let mut state = self.state.lock();
+
+        if state.last_version.is_some_and(|version| version >= op_num) {
+            return;
+        }
+
+        let record = PointHistoryRecord {
+            version: op_num,
+            timestamp,
+            changes,
+        };
+
+        if !record.changes.is_empty()
+            && let Some(writer) = &mut state.writer
+        {
+            if let Err(err) = append_record(writer, &record) {
+                log::warn!(
+                    "Failed to append to point history file {}: {err}",
+                    self.path.display(),
+                );
+                state.writer = None;
+            }
+            state.file_entries += record.changes.len();
+        }
+
+        state.apply(record, self.max_versions);
+    }
+
+    /// Recorded changes of the point, the newest first
+    pub fn history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        self.state
+            .lock()
+            .points
+            .get(&point_id)
+            .map(|entries| entries.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Flush appended records to disk
+    ///
+    /// Rewrites the log file with only the kept changes, if most of its records are outdated or
+    /// appending failed before.
+    pub fn flush(&self) -> CollectionResult<()> {
+        let mut state = self.state.lock();
+
+        let outdated =
+            state.file_entries >= MIN_COMPACTION_ENTRIES && state.file_entries > state.entries * 2;
+
+        if let Some(writer) = &mut state.writer
+            && !outdated
+        {
+            return Ok(writer.flush()?);
+        }
+
+        state.writer = None;
+        atomic_save(&self.path, |writer| {
+            for record in state.records() {
+                append_record(writer, &record)?;
+            }
+            Ok::<_, CollectionError>(())
+        })?;
+        state.file_entries = state.entries;
+        state.writer = Some(Self::open_writer(&self.path)?);
+
+        Ok(())
+    }
+}
+
+impl PointHistoryState {
+    fn apply(&mut self, record: PointHistoryRecord, max_versions: usize) {
+        let PointHistoryRecord {
+            version,
+            timestamp,
+            changes,
+        } = record;
+
+        for (point_id, change) in changes {
+            if change == PointChange::Delete {
+                self.deleted.push_back((point_id, version));
+            }
+
+            let entries = self.points.entry(point_id).or_default();
+            while entries.len() >= max_versions {
+                entries.pop_front();
+                self.entries -= 1;
+            }
+            entries.push_back(PointHistoryEntry {
+                version,
+                timestamp,
+                change,
+            });
+            self.entries += 1;
+        }
+
+        while self.deleted.len() > MAX_DELETED_POINTS {
+            let Some((point_id, deleted_version)) = self.deleted.pop_front() else {
+                break;
+            };
+            // Point might have been inserted again after the deletion
+            let is_still_deleted = self.points.get(&point_id).is_some_and(|entries| {
+                entries
+                    .back()
+                    .is_some_and(|entry| entry.version == deleted_version)
+            });
+            if is_still_deleted && let Some(entries) = self.points.remove(&point_id) {
+                self.entries -= entries.len();
+            }
+        }
+
+        self.last_version = Some(version);
+    }
+
+    /// Kept changes grouped into records, ordered by version
+    fn records(&self) -> Vec<PointHistoryRecord> {
+        self.points
+            .iter()
+            .flat_map(|(point_id, entries)| entries.iter().map(move |entry| (*point_id, entry)))
+            .sorted_by_key(|(_, entry)| entry.version)
+            .chunk_by(|(_, entry)| (entry.version, entry.timestamp))
+            .into_iter()
+            .map(|((version, timestamp), changes)| PointHistoryRecord {
+                version,
+                timestamp,
+                changes: changes
+                    .map(|(point_id, entry)| (point_id, entry.change.clone()))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Serialize history entries, to send them to another peer
+pub fn serialize_entries(entries: &[PointHistoryEntry]) -> CollectionResult<Vec<u8>> {
+    rmp_serde::to_vec_named(entries).map_err(|err| {
+        CollectionError::service_error(format!("Failed to serialize point history: {err}"))
+    })
+}
+
+pub fn deserialize_entries(bytes: &[u8]) -> CollectionResult<Vec<PointHistoryEntry>> {
+    rmp_serde::from_slice(bytes).map_err(|err| {
+        CollectionError::service_error(format!("Failed to deserialize point history: {err}"))
+    })
+}
+
+/// Time of an operation, as stored in WAL, or the current time if it was not stored
+pub fn operation_timestamp(timestamp_ms: Option<u64>) -> DateTime<Utc> {
+    timestamp_ms
+        .and_then(|timestamp_ms| DateTime::from_timestamp_millis(timestamp_ms as i64))
+        .unwrap_or_else(Utc::now)
+}
+
+/// Current time to store with an operation in WAL, in milliseconds since the Unix epoch
+pub fn current_timestamp_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+fn append_record(
+    writer: &mut impl std::io::Write,
+    record: &PointHistoryRecord,
+) -> CollectionResult<()> {
+    let bytes = rmp_serde::to_vec_named(record).map_err(|err| {
+        CollectionError::service_error(format!("Failed to serialize point history: {err}"))
+    })?;
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        CollectionError::service_error("Point history record is too large".to_string())
+    })?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn upsert_changes(points: &PointInsertOperationsInternal) -> PendingChanges {
+    points
+        .clone()
+        .into_point_vec()
+        .into_iter()
+        .map(|point| {
+            let change = PointChange::Upsert {
+                vectors: vector_hashes(&point.vector),
+                payload: point.payload,
+            };
+            (point.id, change)
+        })
+        .collect()
+}
+
+/// Delete changes of points, which exist before the operation
+fn delete_changes(
+    segments: &SegmentHolder,
+    point_ids: impl IntoIterator<Item = PointIdType>,
+) -> PendingChanges {
+    point_ids
+        .into_iter()
+        .filter(|point_id| {
+            segments
+                .iter()
+                .any(|(_, segment)| segment.get().read().has_point(*point_id))
+        })
+        .map(|point_id| (point_id, PointChange::Delete))
+        .collect()
+}
+
+fn same_changes(
+    point_ids: impl IntoIterator<Item = PointIdType>,
+    change: PointChange,
+) -> PendingChanges {
+    point_ids
+        .into_iter()
+        .map(|point_id| (point_id, change.clone()))
+        .collect()
+}
+
+fn selected_points(
+    segments: &SegmentHolder,
+    points: Option<&[PointIdType]>,
+    filter: Option<&Filter>,
+) -> Vec<PointIdType> {
+    match (points, filter) {
+        (Some(points), _) => points.to_vec(),
+        (None, Some(filter)) => filtered_points(segments, filter),
+        (None, None) => Vec::new(),
+    }
+}
+
+fn filtered_points(segments: &SegmentHolder, filter: &Filter) -> Vec<PointIdType> {
+    // Internal operation, no measurement needed
+    let hw_counter = HardwareCounterCell::disposable();
+    let is_stopped = AtomicBool::new(false);
+    segments
+        .iter()
+        .flat_map(|(_, segment)| {
+            segment
+                .get()
+                .read()
+                .read_filtered(None, None, Some(filter), &is_stopped, &hw_counter)
+        })
+        .unique()
+        .collect()
+}
+
+fn vector_hashes(vector: &VectorStructPersisted) -> BTreeMap<VectorNameBuf, String> {
+    match vector {
+        VectorStructPersisted::Single(_) | VectorStructPersisted::MultiDense(_) => {
+            BTreeMap::from([(DEFAULT_VECTOR_NAME.to_owned(), vector_hash(vector))])
+        }
+        VectorStructPersisted::Named(vectors) => vectors
+            .iter()
+            .map(|(name, vector)| (name.clone(), vector_hash(vector)))
+            .collect(),
+    }
+}
+
+fn vector_hash(vector: &impl Hash) -> String {
+    let mut hasher = FnvHasher::default();
+    vector.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+    use segment::payload_json;
+    use segment::types::PayloadContainer as _;
+    use serde_json::json;
+    use shard::update::{process_payload_operation, process_point_operation};
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::build_test_holder;
+    use crate::operations::payload_ops::SetPayloadOp;
+    use crate::operations::point_ops::PointStructPersisted;
+
+    fn apply(
+        history: &PointHistory,
+        segments: &RwLock<SegmentHolder>,
+        op_num: SeqNumberType,
+        operation: CollectionUpdateOperations,
+    ) {
+        let hw_counter = HardwareCounterCell::new();
+        let changes = PointHistory::pending_changes(&segments.read(), &operation);
+        match operation {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                process_point_operation(segments, op_num, operation, &hw_counter)
+            }
+            CollectionUpdateOperations::PayloadOperation(operation) => {
+                process_payload_operation(segments, op_num, operation, &hw_counter)
+            }
+            _ => unreachable!(),
+        }
+        .unwrap();
+        history.record(&segments.read(), op_num, Utc::now(), changes);
+    }
+
+    #[test]
+    fn test_point_history() {
+        let dir = Builder::new().prefix("point_history").tempdir().unwrap();
+        let segments_dir = Builder::new().prefix("segments").tempdir().unwrap();
+        let segments = build_test_holder(segments_dir.path());
+
+        let history = PointHistory::load(dir.path(), 2);
+        let point_id = PointIdType::from(1);
+
+        let upsert =
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(vec![
+                PointStructPersisted {
+                    id: point_id,
+                    vector: VectorStructPersisted::Single(vec![1.0, 0.0, 1.0, 1.0]),
+                    payload: None,
+                },
+            ]));
+        apply(
+            &history,
+            &segments,
+            100,
+            CollectionUpdateOperations::PointOperation(upsert),
+        );
+
+        for (op_num, color) in [(101, "red"), (102, "blue")] {
+            let set_payload = PayloadOps::SetPayload(SetPayloadOp {
+                payload: payload_json! { "color": color },
+                points: Some(vec![point_id]),
+                filter: None,
+                key: None,
+            });
+            apply(
+                &history,
+                &segments,
+                op_num,
+                CollectionUpdateOperations::PayloadOperation(set_payload),
+            );
+        }
+
+        // Only the last 2 changes are kept, the newest first
+        let entries = history.history(point_id);
+        assert_eq!(
+            entries.iter().map(|entry| entry.version).collect_vec(),
+            vec![102, 101],
+        );
+        let PointChange::SetPayload { payload, .. } = &entries[0].change else {
+            panic!("unexpected change: {:?}", entries[0].change);
+        };
+        assert_eq!(
+            payload.get_value(&JsonPath::new("color")).as_slice(),
+            [&json!("blue")],
+        );
+
+        // Deleting a missing point is not a change
+        let missing_id = PointIdType::from(1000);
+        let delete = |point_id| {
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                ids: vec![point_id],
+            })
+        };
+        apply(&history, &segments, 103, delete(missing_id));
+        assert!(history.history(missing_id).is_empty());
+
+        apply(&history, &segments, 104, delete(point_id));
+        assert_eq!(history.history(point_id)[0].change, PointChange::Delete);
+
+        // History is restored from disk, replayed operations are not recorded twice
+        history.flush().unwrap();
+        let loaded = PointHistory::load(dir.path(), 2);
+        assert_eq!(loaded.history(point_id), history.history(point_id));
+        loaded.record(
+            &segments.read(),
+            104,
+            Utc::now(),
+            vec![(point_id, PointChange::Delete)],
+        );
+        assert_eq!(loaded.history(point_id), history.history(point_id));
+
+        // Partially written record is dropped, and the log is rewritten on flush
+        drop(loaded);
+        let path = PointHistory::path(dir.path());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[100, 0, 0, 0, 1, 2]).unwrap();
+        drop(file);
+        let loaded = PointHistory::load(dir.path(), 2);
+        assert_eq!(loaded.history(point_id), history.history(point_id));
+        loaded.flush().unwrap();
+        let loaded = PointHistory::load(dir.path(), 2);
+        assert_eq!(loaded.history(point_id), history.history(point_id));
+    }
+
+    #[test]
+    fn test_deleted_points_eviction() {
+        let mut state = PointHistoryState::default();
+        let timestamp = Utc::now();
+        let change = |point_id: u64, change| (PointIdType::from(point_id), change);
+
+        let set_payload = PointChange::ClearPayload;
+        state.apply(
+            PointHistoryRecord {
+                version: 1,
+                timestamp,
+                changes: (0..=MAX_DELETED_POINTS as u64 + 1)
+                    .map(|point_id| change(point_id, set_payload.clone()))
+                    .collect(),
+            },
+            2,
+        );
+
+        // Point 1 is inserted again after the deletion, so it is not evicted with it
+        let deleted =
+            (0..=MAX_DELETED_POINTS as u64).map(|point_id| change(point_id, PointChange::Delete));
+        state.apply(
+            PointHistoryRecord {
+                version: 2,
+                timestamp,
+                changes: deleted.collect(),
+            },
+            2,
+        );
+        state.apply(
+            PointHistoryRecord {
+                version: 3,
+                timestamp,
+                changes: vec![change(1, set_payload.clone())],
+            },
+            2,
+        );
+        state.apply(
+            PointHistoryRecord {
+                version: 4,
+                timestamp,
+                changes: vec![change(MAX_DELETED_POINTS as u64 + 1, PointChange::Delete)],
+            },
+            2,
+        );
+
+        assert_eq!(state.deleted.len(), MAX_DELETED_POINTS);
+        assert!(!state.points.contains_key(&PointIdType::from(0)));
+        assert!(state.points.contains_key(&PointIdType::from(1)));
+        assert!(state.points.contains_key(&PointIdType::from(2)));
+        assert_eq!(
+            state.entries,
+            state.points.values().map(VecDeque::len).sum::<usize>(),
+        );
+    }
+}
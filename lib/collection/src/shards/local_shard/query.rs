@@ -10,6 +10,7 @@ use ordered_float::OrderedFloat;
 use parking_lot::Mutex;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::common::score_fusion::{ScoreFusion, score_fusion};
+use segment::spaces::wasm_metric;
 use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
 use tokio::runtime::Handle;
 use tokio::time::error::Elapsed;
@@ -460,6 +461,7 @@ impl LocalShard {
         // Even if we have fewer points than requested, still calculate MMR.
         let mut top_mmr = mmr_from_points_with_vector(
             collection_params,
+            wasm_metric::find(&self.path),
             points_with_vector,
             mmr,
             limit,
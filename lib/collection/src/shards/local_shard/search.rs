@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::types::ScoredPoint;
@@ -54,9 +54,10 @@ impl LocalShard {
         };
 
         let is_stopped_guard = StoppingGuard::new();
+        let start = Instant::now();
 
         if skip_batching {
-            return self
+            let result = self
                 .do_search_impl(
                     core_request,
                     search_runtime_handle,
@@ -65,6 +66,9 @@ impl LocalShard {
                     &is_stopped_guard,
                 )
                 .await;
+            self.optimizer_resource_budget
+                .observe_search_latency(start.elapsed());
+            return result;
         }
 
         // Batch if we have many searches, allows for more parallelism
@@ -86,13 +90,11 @@ impl LocalShard {
             })
             .collect::<Vec<_>>();
 
-        let results = futures::future::try_join_all(chunk_futures)
-            .await?
-            .into_iter()
-            .flatten()
-            .collect();
+        let results = futures::future::try_join_all(chunk_futures).await;
+        self.optimizer_resource_budget
+            .observe_search_latency(start.elapsed());
 
-        Ok(results)
+        Ok(results?.into_iter().flatten().collect())
     }
 
     async fn do_search_impl(
@@ -6,7 +6,8 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
@@ -18,14 +19,16 @@ use crate::operations::OperationWithClockTag;
 use crate::operations::generalizer::Generalizer;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, RecordInternal, UpdateResult,
-    UpdateStatus,
+    CountRequestInternal, CountResult, PointRequestInternal, PointVersion, RecordInternal,
+    UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::verification::operation_rate_cost::{BASE_COST, filter_rate_cost};
 use crate::profiling::interface::log_request_to_collector;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::current_timestamp_ms;
+use crate::shards::local_shard::upsert_batch::UpsertBatcher;
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
 
@@ -40,78 +43,20 @@ impl ShardOperation for LocalShard {
     /// This method is cancel safe.
     async fn update(
         &self,
-        mut operation: OperationWithClockTag,
+        operation: OperationWithClockTag,
         wait: bool,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
-        // `LocalShard::update` only has a single cancel safe `await`, WAL operations are blocking,
-        // and update is applied by a separate task, so, surprisingly, this method is cancel safe. :D
-
-        let (callback_sender, callback_receiver) = if wait {
-            let (tx, rx) = oneshot::channel();
-            (Some(tx), Some(rx))
-        } else {
-            (None, None)
-        };
-
-        if self
-            .disk_usage_watcher
-            .is_disk_full()
-            .await?
-            .unwrap_or(false)
+        if let Some(upsert_batcher) = &self.upsert_batcher
+            && UpsertBatcher::can_merge(&operation)
         {
-            return Err(CollectionError::service_error(
-                "No space left on device: WAL buffer size exceeds available disk space".to_string(),
-            ));
+            return self
+                .update_merged(upsert_batcher, operation, wait, hw_measurement_acc)
+                .await;
         }
 
-        let operation_id = {
-            let update_sender = self.update_sender.load();
-            let channel_permit = update_sender.reserve().await?;
-
-            // It is *critical* to hold `_wal_lock` while sending operation to the update handler!
-            //
-            // TODO: Refactor `lock_and_write`, so this is less terrible? :/
-            let (operation_id, _wal_lock) = match self.wal.lock_and_write(&mut operation).await {
-                Ok(id_and_lock) => id_and_lock,
-
-                Err(shard::wal::WalError::ClockRejected) => {
-                    // Propagate clock rejection to operation sender
-                    return Ok(UpdateResult {
-                        operation_id: None,
-                        status: UpdateStatus::ClockRejected,
-                        clock_tag: operation.clock_tag,
-                    });
-                }
-
-                Err(err) => return Err(err.into()),
-            };
-
-            channel_permit.send(UpdateSignal::Operation(OperationData {
-                op_num: operation_id,
-                operation: operation.operation,
-                sender: callback_sender,
-                wait,
-                hw_measurements: hw_measurement_acc.clone(),
-            }));
-
-            operation_id
-        };
-
-        if let Some(receiver) = callback_receiver {
-            let _res = receiver.await??;
-            Ok(UpdateResult {
-                operation_id: Some(operation_id),
-                status: UpdateStatus::Completed,
-                clock_tag: operation.clock_tag,
-            })
-        } else {
-            Ok(UpdateResult {
-                operation_id: Some(operation_id),
-                status: UpdateStatus::Acknowledged,
-                clock_tag: operation.clock_tag,
-            })
-        }
+        self.update_unmerged(operation, wait, hw_measurement_acc)
+            .await
     }
 
     /// This call is rate limited by the read rate limiter.
@@ -261,6 +206,33 @@ impl ShardOperation for LocalShard {
         Ok(ordered_records)
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        let versions = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::point_versions(self.segments.clone(), &ids, search_runtime_handle),
+        )
+        .await
+        .map_err(|_: Elapsed| {
+            CollectionError::timeout(timeout.as_secs() as usize, "point_versions")
+        })??;
+
+        let ordered_versions = ids
+            .iter()
+            .filter_map(|&id| {
+                let version = *versions.get(&id)?;
+                Some(PointVersion { id, version })
+            })
+            .collect();
+
+        Ok(ordered_versions)
+    }
+
     /// This call is rate limited by the read rate limiter.
     async fn query_batch(
         &self,
@@ -323,3 +295,104 @@ impl ShardOperation for LocalShard {
         Ok(FacetResponse { hits })
     }
 }
+
+impl LocalShard {
+    /// Check that there is enough disk space to write to WAL
+    pub(super) async fn check_disk_space(&self) -> CollectionResult<()> {
+        if self
+            .disk_usage_watcher
+            .is_disk_full()
+            .await?
+            .unwrap_or(false)
+        {
+            return Err(CollectionError::service_error(
+                "No space left on device: WAL buffer size exceeds available disk space".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Write operation to WAL as a separate record and send it to the update handler
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub(super) async fn update_unmerged(
+        &self,
+        mut operation: OperationWithClockTag,
+        wait: bool,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<UpdateResult> {
+        // `LocalShard::update` only has a single cancel safe `await`, WAL operations are blocking,
+        // and update is applied by a separate task, so, surprisingly, this method is cancel safe. :D
+
+        let (callback_sender, callback_receiver) = if wait {
+            let (tx, rx) = oneshot::channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+
+        self.check_disk_space().await?;
+
+        if self.point_history.is_some() {
+            operation.timestamp = Some(current_timestamp_ms());
+        }
+
+        let operation_id = {
+            let update_sender = self.update_sender.load();
+            let channel_permit = update_sender.reserve().await?;
+
+            // Unchanged points are dropped and content hashes are updated in WAL order
+            let mut content_hashes = self.content_hashes.lock().await;
+            content_hashes.skip_unchanged(&mut operation.operation);
+
+            // It is *critical* to hold `_wal_lock` while sending operation to the update handler!
+            //
+            // TODO: Refactor `lock_and_write`, so this is less terrible? :/
+            let (operation_id, _wal_lock) = match self.wal.lock_and_write(&mut operation).await {
+                Ok(id_and_lock) => id_and_lock,
+
+                Err(shard::wal::WalError::ClockRejected) => {
+                    // Propagate clock rejection to operation sender
+                    return Ok(UpdateResult {
+                        operation_id: None,
+                        status: UpdateStatus::ClockRejected,
+                        clock_tag: operation.clock_tag,
+                    });
+                }
+
+                Err(err) => return Err(err.into()),
+            };
+
+            content_hashes.record(&operation.operation);
+
+            channel_permit.send(UpdateSignal::Operation(OperationData {
+                op_num: operation_id,
+                operation: operation.operation,
+                timestamp: operation.timestamp,
+                sender: callback_sender,
+                wait,
+                hw_measurements: hw_measurement_acc.clone(),
+            }));
+
+            operation_id
+        };
+
+        if let Some(receiver) = callback_receiver {
+            let _res = receiver.await??;
+            Ok(UpdateResult {
+                operation_id: Some(operation_id),
+                status: UpdateStatus::Completed,
+                clock_tag: operation.clock_tag,
+            })
+        } else {
+            Ok(UpdateResult {
+                operation_id: Some(operation_id),
+                status: UpdateStatus::Acknowledged,
+                clock_tag: operation.clock_tag,
+            })
+        }
+    }
+}
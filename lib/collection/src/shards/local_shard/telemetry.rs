@@ -1,12 +1,13 @@
 use std::sync::atomic::Ordering;
 
 use common::types::{DetailsLevel, TelemetryDetail};
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::SizeStats;
 use segment::vector_storage::common::get_async_scorer;
 
 use crate::operations::types::OptimizersStatus;
 use crate::shards::local_shard::LocalShard;
-use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
+use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry, SegmentHnswGraphStats};
 
 impl LocalShard {
     pub async fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
@@ -72,6 +73,12 @@ impl LocalShard {
                 optimizations,
                 log: (detail.level >= DetailsLevel::Level4)
                     .then(|| self.optimizers_log.lock().to_telemetry()),
+                merge_plan: (detail.level >= DetailsLevel::Level4).then(|| {
+                    self.optimizers
+                        .iter()
+                        .flat_map(|optimizer| optimizer.merge_plan())
+                        .collect()
+                }),
             },
             async_scorer: Some(get_async_scorer()),
         }
@@ -132,4 +139,53 @@ impl LocalShard {
 
         stats.unwrap_or_default()
     }
+
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        let segments = self.segments.clone();
+
+        let stats = tokio::task::spawn_blocking(move || {
+            let segments = segments.read(); // blocking sync lock
+
+            segments
+                .iter()
+                .map(|(_id, segment)| {
+                    let segment = segment.get();
+                    let segment = segment.read();
+                    SegmentHnswGraphStats {
+                        num_points: segment.available_point_count(),
+                        vectors: segment.hnsw_graph_stats(),
+                    }
+                })
+                .filter(|stats| !stats.vectors.is_empty())
+                .collect()
+        })
+        .await;
+
+        if let Err(err) = &stats {
+            log::error!("failed to get HNSW graph stats: {err}");
+        }
+
+        stats.unwrap_or_default()
+    }
+
+    /// Quantization telemetry aggregated over segments, by vector name
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        let segments = self.segments.clone();
+
+        let telemetry = tokio::task::spawn_blocking(move || {
+            let segments = segments.read(); // blocking sync lock
+
+            let telemetry = segments
+                .iter()
+                .flat_map(|(_id, segment)| segment.get().read().quantization_telemetry());
+            QuantizationTelemetry::aggregate(telemetry)
+        })
+        .await;
+
+        if let Err(err) = &telemetry {
+            log::error!("failed to get quantization telemetry: {err}");
+        }
+
+        telemetry.unwrap_or_default()
+    }
 }
@@ -0,0 +1,323 @@
+//! Merging of concurrent upserts into a single WAL record
+//!
+//! If `wal_batch_window_ms` is set, upserts received by the local shard within the window are
+//! merged into a single upsert, which is written to WAL as one record and applied to segments in
+//! one pass. Clock tag of each upsert is checked separately and kept in the record, so clocks can
+//! be recovered from WAL and the record can be split again when WAL is transferred.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use parking_lot::Mutex;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex as TokioMutex, oneshot};
+use tokio::time::Instant;
+
+use super::LocalShard;
+use super::content_hashes::ContentHashes;
+use super::point_history::current_timestamp_ms;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::update_handler::{OperationData, UpdateSignal};
+use crate::wal_delta::RecoverableWal;
+
+/// Maximum number of upserts merged into a single WAL record
+const MAX_UPSERT_BATCH_SIZE: usize = 256;
+
+/// Upserts collected within the batching window
+pub struct UpsertBatcher {
+    window: Duration,
+    pending: Mutex<Option<PendingBatch>>,
+}
+
+struct PendingBatch {
+    /// End of the batching window
+    deadline: Instant,
+    upserts: Vec<PendingUpsert>,
+}
+
+struct PendingUpsert {
+    operation: OperationWithClockTag,
+    wait: bool,
+    hw_measurement_acc: HwMeasurementAcc,
+    sender: oneshot::Sender<CollectionResult<UpdateResult>>,
+}
+
+impl UpsertBatcher {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Mutex::new(None),
+        }
+    }
+
+    /// Whether the operation can be merged with other operations
+    ///
+    /// Only plain upserts are merged, as the last upsert of a point wins when they are applied
+    /// together. Conditional upserts depend on the stored state and must be applied one by one.
+    /// Untagged operations are not merged, so a merged record has a clock tag of each upsert.
+    pub fn can_merge(operation: &OperationWithClockTag) -> bool {
+        operation.clock_tag.is_some()
+            && matches!(
+                operation.operation,
+                CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(_)),
+            )
+    }
+
+    /// Add the upsert into the pending batch, starting a new batch if there is none
+    ///
+    /// Returns the end of the batching window, or gives the upsert back if the batch is full.
+    fn push(&self, upsert: PendingUpsert) -> Result<Instant, PendingUpsert> {
+        let mut pending = self.pending.lock();
+
+        let batch = pending.get_or_insert_with(|| PendingBatch {
+            deadline: Instant::now() + self.window,
+            upserts: Vec::new(),
+        });
+
+        if batch.upserts.len() >= MAX_UPSERT_BATCH_SIZE {
+            return Err(upsert);
+        }
+
+        batch.upserts.push(upsert);
+        Ok(batch.deadline)
+    }
+
+    /// Take the pending batch, if its batching window has ended
+    fn take_due(&self) -> Option<Vec<PendingUpsert>> {
+        let mut pending = self.pending.lock();
+
+        if pending
+            .as_ref()
+            .is_some_and(|batch| batch.deadline <= Instant::now())
+        {
+            pending.take().map(|batch| batch.upserts)
+        } else {
+            None
+        }
+    }
+}
+
+impl LocalShard {
+    /// Apply the upsert merged with other upserts received within the batching window
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    ///
+    /// Any upsert of the batch takes it after the window ends, so the batch is written as long
+    /// as at least one of its requests is not cancelled until then. The batch is written by a
+    /// separate task, so cancelling a request after that doesn't cancel the write.
+    pub(super) async fn update_merged(
+        &self,
+        upsert_batcher: &UpsertBatcher,
+        operation: OperationWithClockTag,
+        wait: bool,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<UpdateResult> {
+        self.check_disk_space().await?;
+
+        let (sender, mut receiver) = oneshot::channel();
+
+        let upsert = PendingUpsert {
+            operation,
+            wait,
+            hw_measurement_acc,
+            sender,
+        };
+
+        let deadline = match upsert_batcher.push(upsert) {
+            Ok(deadline) => deadline,
+            Err(upsert) => {
+                return self
+                    .update_unmerged(upsert.operation, wait, upsert.hw_measurement_acc)
+                    .await;
+            }
+        };
+
+        tokio::select! {
+            result = &mut receiver => return Self::merged_update_result(result),
+            () = tokio::time::sleep_until(deadline) => {}
+        }
+
+        if let Some(upserts) = upsert_batcher.take_due() {
+            let writer = MergedUpsertWriter {
+                wal: self.wal.clone(),
+                content_hashes: self.content_hashes.clone(),
+                update_sender: self.update_sender.load_full(),
+                with_timestamp: self.point_history.is_some(),
+            };
+            self.update_runtime.spawn(writer.write(upserts));
+        }
+
+        Self::merged_update_result(receiver.await)
+    }
+
+    fn merged_update_result(
+        result: Result<CollectionResult<UpdateResult>, oneshot::error::RecvError>,
+    ) -> CollectionResult<UpdateResult> {
+        result.map_err(|_| {
+            CollectionError::service_error("Batch of upserts was cancelled before it was written")
+        })?
+    }
+}
+
+/// Parts of the shard writing a batch of upserts, owned by the task writing it
+struct MergedUpsertWriter {
+    wal: RecoverableWal,
+    content_hashes: Arc<TokioMutex<ContentHashes>>,
+    update_sender: Arc<Sender<UpdateSignal>>,
+    with_timestamp: bool,
+}
+
+impl MergedUpsertWriter {
+    /// Write upserts of the batch as a single WAL record and report the result to each of them
+    async fn write(self, mut upserts: Vec<PendingUpsert>) {
+        let channel_permit = match self.update_sender.reserve().await {
+            Ok(channel_permit) => channel_permit,
+            Err(err) => {
+                let err = CollectionError::from(err);
+                for upsert in upserts {
+                    let _ = upsert.sender.send(Err(err.clone()));
+                }
+                return;
+            }
+        };
+
+        // Check clock of each upsert, rejected ones are not merged
+        let accepted = self
+            .wal
+            .advance_clocks(
+                upserts
+                    .iter_mut()
+                    .map(|upsert| &mut upsert.operation.clock_tag),
+            )
+            .await;
+
+        let (upserts, rejected): (Vec<_>, Vec<_>) = upserts
+            .into_iter()
+            .zip(accepted)
+            .partition(|(_, accepted)| *accepted);
+
+        for (upsert, _) in rejected {
+            // Propagate clock rejection to operation sender
+            let _ = upsert.sender.send(Ok(UpdateResult {
+                operation_id: None,
+                status: UpdateStatus::ClockRejected,
+                clock_tag: upsert.operation.clock_tag,
+            }));
+        }
+
+        // Hardware usage of the merged upsert is measured by the first request of the batch
+        let Some(hw_measurements) = upserts
+            .first()
+            .map(|(upsert, _)| upsert.hw_measurement_acc.clone())
+        else {
+            return;
+        };
+
+        let mut points = Vec::new();
+        let mut merged_clock_tags = Vec::with_capacity(upserts.len());
+        let mut senders = Vec::with_capacity(upserts.len());
+
+        for (upsert, _) in upserts {
+            let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                upsert_points,
+            )) = upsert.operation.operation
+            else {
+                unreachable!("only upserts are merged");
+            };
+
+            points.extend(upsert_points.into_point_vec());
+            merged_clock_tags.extend(upsert.operation.clock_tag);
+            senders.push((upsert.sender, upsert.wait, upsert.operation.clock_tag));
+        }
+
+        let mut operation = OperationWithClockTag::new(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            None,
+        );
+        operation.merged_clock_tags = merged_clock_tags;
+
+        if self.with_timestamp {
+            operation.timestamp = Some(current_timestamp_ms());
+        }
+
+        let wait = senders.iter().any(|(_, wait, _)| *wait);
+
+        let (callback_sender, callback_receiver) = if wait {
+            let (tx, rx) = oneshot::channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+
+        let operation_id = {
+            let mut content_hashes = self.content_hashes.lock().await;
+
+            // Clocks are already advanced, so the record is written without checking them again.
+            // It is *critical* to hold `_wal_lock` while sending operation to the update handler!
+            let (operation_id, _wal_lock) = match self.wal.lock_and_write(&mut operation).await {
+                Ok(id_and_lock) => id_and_lock,
+                Err(err) => {
+                    let err = CollectionError::from(err);
+                    for (sender, _, _) in senders {
+                        let _ = sender.send(Err(err.clone()));
+                    }
+                    return;
+                }
+            };
+
+            content_hashes.record(&operation.operation);
+
+            channel_permit.send(UpdateSignal::Operation(OperationData {
+                op_num: operation_id,
+                operation: operation.operation,
+                timestamp: operation.timestamp,
+                sender: callback_sender,
+                wait,
+                hw_measurements,
+            }));
+
+            operation_id
+        };
+
+        // Upserts, which don't wait for the result, are acknowledged right away
+        let mut waiting = Vec::with_capacity(senders.len());
+        for (sender, wait, clock_tag) in senders {
+            if wait {
+                waiting.push((sender, clock_tag));
+            } else {
+                let _ = sender.send(Ok(UpdateResult {
+                    operation_id: Some(operation_id),
+                    status: UpdateStatus::Acknowledged,
+                    clock_tag,
+                }));
+            }
+        }
+
+        let Some(receiver) = callback_receiver else {
+            return;
+        };
+
+        let result = receiver
+            .await
+            .map_err(CollectionError::from)
+            .and_then(|result| result);
+
+        for (sender, clock_tag) in waiting {
+            let _ = sender.send(match &result {
+                Ok(_) => Ok(UpdateResult {
+                    operation_id: Some(operation_id),
+                    status: UpdateStatus::Completed,
+                    clock_tag,
+                }),
+                Err(err) => Err(err.clone()),
+            });
+        }
+    }
+}
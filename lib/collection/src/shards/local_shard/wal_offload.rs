@@ -0,0 +1,432 @@
+//! Offloading of closed WAL segments to object storage
+//!
+//! Closed segments of the WAL are handed over for offloading before the WAL is allowed to
+//! truncate them locally. Handing over only hard links a segment into the pending directory of
+//! the shard, so acknowledging the WAL never waits for object storage. Pending segments are
+//! uploaded by a separate worker, which retries until the upload succeeds.
+//!
+//! This keeps local disk usage of the WAL bounded on ingest-heavy nodes, while the complete
+//! history of operations stays available for replay and point-in-time recovery. Offloaded
+//! segments keep their original file names, so they can be restored into a WAL directory as is.
+//! A shard restores them when it is loaded, if its segments are older than the local WAL.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+use io::file_operations::{atomic_save_json, read_json};
+use object_store::ObjectStore;
+use object_store::aws::AmazonS3Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::common::snapshots_manager::S3Config;
+use crate::operations::snapshot_storage_ops::{
+    download_snapshot, multipart_upload, trim_dot_slash,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::PeerId;
+
+/// Prefix of file names of closed WAL segments, followed by the first index of the segment
+const CLOSED_SEGMENT_PREFIX: &str = "closed-";
+
+/// Directory of the shard with segments handed over for offloading, but not uploaded yet
+const PENDING_DIR: &str = "wal_offload";
+
+/// File of the shard with the state of handed over segments
+const STATE_FILE: &str = "wal_offload.json";
+
+const DEFAULT_PREFIX: &str = "wal";
+
+/// Configuration of offloading closed WAL segments to object storage
+#[derive(Clone, Debug, Deserialize)]
+pub struct WalOffloadConfig {
+    /// Object storage to offload segments to
+    pub s3_config: S3Config,
+    /// Path prefix of offloaded segments in the bucket.
+    /// Segments of each shard replica are stored under `{prefix}/{collection}/{shard_id}/{peer_id}/`.
+    #[serde(default = "default_prefix")]
+    pub prefix: String,
+    /// ID of this peer, replicas of the same shard on different peers are offloaded separately
+    #[serde(skip)]
+    pub peer_id: Option<PeerId>,
+}
+
+impl WalOffloadConfig {
+    pub fn with_peer_id(mut self, peer_id: PeerId) -> Self {
+        self.peer_id = Some(peer_id);
+        self
+    }
+}
+
+fn default_prefix() -> String {
+    DEFAULT_PREFIX.to_string()
+}
+
+/// Sizes of closed segments, which were handed over for offloading
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct WalOffloadState {
+    /// Only segments, which are still present in the local WAL, are kept
+    staged: HashMap<String, u64>,
+}
+
+/// Hands over closed segments of a single shard WAL for offloading, before they are truncated
+pub struct WalOffloadStager {
+    wal_path: PathBuf,
+    pending_path: PathBuf,
+    state_path: PathBuf,
+    state: WalOffloadState,
+}
+
+impl WalOffloadStager {
+    pub fn load(wal_path: PathBuf, shard_path: &Path) -> CollectionResult<Self> {
+        let state_path = WalOffloader::state_path(shard_path);
+        let state = if state_path.exists() {
+            read_json(&state_path)?
+        } else {
+            WalOffloadState::default()
+        };
+
+        Ok(Self {
+            wal_path,
+            pending_path: WalOffloader::pending_path(shard_path),
+            state_path,
+            state,
+        })
+    }
+
+    /// Hand over closed segments of the WAL, which are not handed over yet
+    ///
+    /// Once this succeeds, all closed segments of the WAL may be truncated locally.
+    /// Returns the number of handed over segments.
+    pub fn stage(&mut self) -> CollectionResult<usize> {
+        let closed_segments = closed_segments(&self.wal_path)?;
+
+        // Forget segments, which were truncated from the local WAL
+        let known = self.state.staged.len();
+        self.state.staged.retain(|name, _| {
+            closed_segments
+                .iter()
+                .any(|(closed_name, _)| closed_name == name)
+        });
+        let mut changed = known != self.state.staged.len();
+
+        let mut staged = 0;
+        for (name, size) in closed_segments {
+            // Closed segments are immutable, unless the WAL is truncated from the back
+            if self.state.staged.get(&name) == Some(&size) {
+                continue;
+            }
+
+            std::fs::create_dir_all(&self.pending_path)?;
+            let pending_path = self.pending_path.join(&name);
+            if pending_path.exists() {
+                std::fs::remove_file(&pending_path)?;
+            }
+            // Hard link keeps the segment data after truncation of the WAL, without copying it
+            link_or_copy(&self.wal_path.join(&name), &pending_path)?;
+
+            self.state.staged.insert(name, size);
+            changed = true;
+            staged += 1;
+        }
+
+        if changed {
+            atomic_save_json(&self.state_path, &self.state)?;
+        }
+
+        Ok(staged)
+    }
+}
+
+/// Uploads closed segments of a single shard WAL to object storage
+pub struct WalOffloader {
+    client: Box<dyn ObjectStore>,
+    /// Path of segments of the shard replica in object storage
+    remote_path: PathBuf,
+    /// Segments handed over by [`WalOffloadStager`], which are not uploaded yet
+    pending_path: PathBuf,
+}
+
+impl WalOffloader {
+    pub fn new(
+        config: &WalOffloadConfig,
+        collection_name: &str,
+        shard_path: &Path,
+    ) -> CollectionResult<Self> {
+        let client = config
+            .s3_config
+            .configure(AmazonS3Builder::from_env())
+            .build()
+            .map_err(|err| {
+                CollectionError::service_error(format!("Failed to create S3 client: {err}"))
+            })?;
+        let shard_id = shard_path.file_name().ok_or_else(|| {
+            CollectionError::service_error(format!("Invalid shard path {}", shard_path.display(),))
+        })?;
+        let peer_id = config.peer_id.ok_or_else(|| {
+            CollectionError::service_error("Peer ID is not set for WAL offloading")
+        })?;
+        let remote_path = Path::new(&config.prefix)
+            .join(collection_name)
+            .join(shard_id)
+            .join(peer_id.to_string());
+
+        Ok(Self::with_client(
+            Box::new(client),
+            remote_path,
+            Self::pending_path(shard_path),
+        ))
+    }
+
+    fn with_client(
+        client: Box<dyn ObjectStore>,
+        remote_path: PathBuf,
+        pending_path: PathBuf,
+    ) -> Self {
+        Self {
+            client,
+            remote_path,
+            pending_path,
+        }
+    }
+
+    pub fn pending_path(shard_path: &Path) -> PathBuf {
+        shard_path.join(PENDING_DIR)
+    }
+
+    pub fn state_path(shard_path: &Path) -> PathBuf {
+        shard_path.join(STATE_FILE)
+    }
+
+    /// Upload handed over segments, and remove them locally once uploaded
+    ///
+    /// Segments, which failed to upload, are kept and uploaded on the next call.
+    /// Returns the number of uploaded segments.
+    pub async fn upload_pending(&self) -> CollectionResult<usize> {
+        if !self.pending_path.exists() {
+            return Ok(0);
+        }
+
+        let mut uploaded = 0;
+        for (name, size) in closed_segments(&self.pending_path)? {
+            let pending_path = self.pending_path.join(&name);
+            multipart_upload(
+                self.client.as_ref(),
+                &pending_path,
+                &self.remote_path.join(&name),
+            )
+            .await?;
+
+            // Segment may have been handed over again in the meantime, if the WAL was truncated
+            // from the back. A new version always has a different size, keep it for the next call.
+            if std::fs::metadata(&pending_path)?.len() == size {
+                std::fs::remove_file(&pending_path)?;
+            }
+            uploaded += 1;
+        }
+
+        Ok(uploaded)
+    }
+
+    /// Restore offloaded segments into `target_dir`, skipping ones already present there
+    ///
+    /// Segments, which are handed over but not uploaded yet, are taken from the pending directory,
+    /// all others are downloaded. Restores the complete history of the WAL, to replay it from an
+    /// earlier point.
+    /// Returns the number of restored segments.
+    pub async fn restore(&self, target_dir: &Path) -> CollectionResult<usize> {
+        std::fs::create_dir_all(target_dir)?;
+
+        let mut restored = 0;
+        if self.pending_path.exists() {
+            for (name, _) in closed_segments(&self.pending_path)? {
+                let target_path = target_dir.join(&name);
+                if !target_path.exists() {
+                    link_or_copy(&self.pending_path.join(&name), &target_path)?;
+                    restored += 1;
+                }
+            }
+        }
+
+        let prefix = trim_dot_slash(&self.remote_path)?;
+        let mut list_stream = self.client.list(Some(&prefix));
+
+        while let Some(meta) = list_stream.next().await.transpose().map_err(|err| {
+            CollectionError::service_error(format!("Failed to list WAL segments: {err}"))
+        })? {
+            let Some(name) = meta.location.filename() else {
+                continue;
+            };
+            if !name.starts_with(CLOSED_SEGMENT_PREFIX) {
+                continue;
+            }
+
+            let target_path = target_dir.join(name);
+            if target_path.exists() {
+                continue;
+            }
+
+            download_snapshot(
+                self.client.as_ref(),
+                &self.remote_path.join(name),
+                &target_path,
+            )
+            .await?;
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+}
+
+fn link_or_copy(from: &Path, to: &Path) -> CollectionResult<()> {
+    if std::fs::hard_link(from, to).is_err() {
+        std::fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+/// Names and sizes of closed segments in the directory, ordered by their first index
+fn closed_segments(dir: &Path) -> CollectionResult<Vec<(String, u64)>> {
+    let mut segments = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        let Some(first_index) = name
+            .strip_prefix(CLOSED_SEGMENT_PREFIX)
+            .and_then(|index| index.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        segments.push((first_index, name, entry.metadata()?.len()));
+    }
+    segments.sort_unstable_by_key(|(first_index, _, _)| *first_index);
+
+    Ok(segments
+        .into_iter()
+        .map(|(_, name, size)| (name, size))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use object_store::memory::InMemory;
+    use shard::wal::SerdeWal;
+    use tempfile::Builder;
+    use wal::WalOptions;
+
+    use super::*;
+
+    fn open_wal(wal_path: &Path) -> SerdeWal<String> {
+        let options = WalOptions {
+            segment_capacity: 1024 * 1024,
+            segment_queue_len: 0,
+            retain_closed: NonZeroUsize::new(1).unwrap(),
+        };
+        SerdeWal::new(wal_path.to_str().unwrap(), options).unwrap()
+    }
+
+    /// Write records large enough to close a few WAL segments, hand them over and truncate them
+    fn write_and_truncate(
+        wal: &mut SerdeWal<String>,
+        stager: &mut WalOffloadStager,
+        records: std::ops::Range<usize>,
+    ) {
+        for record in records {
+            wal.write(&format!("{record}:{}", "x".repeat(100 * 1024)))
+                .unwrap();
+        }
+        stager.stage().unwrap();
+        wal.ack(wal.last_index()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_offload_and_restore_closed_segments() {
+        let shard_dir = Builder::new().prefix("shard").tempdir().unwrap();
+        let wal_path = shard_dir.path().join("wal");
+        std::fs::create_dir(&wal_path).unwrap();
+        std::fs::write(wal_path.join("closed-0"), b"first segment").unwrap();
+        std::fs::write(wal_path.join("closed-10"), b"second segment").unwrap();
+        std::fs::write(wal_path.join("open-1"), b"open segment").unwrap();
+
+        let mut stager = WalOffloadStager::load(wal_path.clone(), shard_dir.path()).unwrap();
+        let offloader = WalOffloader::with_client(
+            Box::new(InMemory::new()),
+            PathBuf::from("wal/test/0/1"),
+            WalOffloader::pending_path(shard_dir.path()),
+        );
+
+        assert_eq!(stager.stage().unwrap(), 2);
+        // Already handed over segments are not staged again
+        assert_eq!(stager.stage().unwrap(), 0);
+
+        // Truncated segments are still uploaded
+        std::fs::remove_file(wal_path.join("closed-0")).unwrap();
+        assert_eq!(offloader.upload_pending().await.unwrap(), 2);
+        assert_eq!(offloader.upload_pending().await.unwrap(), 0);
+
+        // Staged state survives restarts
+        let mut stager = WalOffloadStager::load(wal_path.clone(), shard_dir.path()).unwrap();
+        std::fs::write(wal_path.join("closed-20"), b"third segment").unwrap();
+        assert_eq!(stager.stage().unwrap(), 1);
+        assert_eq!(offloader.upload_pending().await.unwrap(), 1);
+
+        let restore_dir = Builder::new().prefix("wal-restore").tempdir().unwrap();
+        assert_eq!(offloader.restore(restore_dir.path()).await.unwrap(), 3);
+        assert_eq!(
+            std::fs::read(restore_dir.path().join("closed-0")).unwrap(),
+            b"first segment",
+        );
+        assert!(!restore_dir.path().join("open-1").exists());
+
+        // Segments present in the target directory are skipped
+        assert_eq!(offloader.restore(restore_dir.path()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_restored_wal_after_restart() {
+        let shard_dir = Builder::new().prefix("shard").tempdir().unwrap();
+        let wal_path = shard_dir.path().join("wal");
+        std::fs::create_dir(&wal_path).unwrap();
+
+        let offloader = WalOffloader::with_client(
+            Box::new(InMemory::new()),
+            PathBuf::from("wal/test/0/1"),
+            WalOffloader::pending_path(shard_dir.path()),
+        );
+
+        {
+            let mut wal = open_wal(&wal_path);
+            let mut stager = WalOffloadStager::load(wal_path.clone(), shard_dir.path()).unwrap();
+
+            write_and_truncate(&mut wal, &mut stager, 0..30);
+            assert!(offloader.upload_pending().await.unwrap() > 0);
+
+            // Restart before the last handed over segments are uploaded
+            write_and_truncate(&mut wal, &mut stager, 30..60);
+            assert!(wal.first_closed_index() > 0);
+        }
+
+        // Local WAL has lost its history, until it is restored
+        let wal = open_wal(&wal_path);
+        assert!(wal.first_closed_index() > 0);
+        drop(wal);
+
+        assert!(offloader.restore(&wal_path).await.unwrap() > 0);
+
+        let wal = open_wal(&wal_path);
+        assert_eq!(wal.first_closed_index(), 0);
+        let replayed: Vec<_> = wal
+            .read(0)
+            .map(|(op_num, record)| {
+                let (record_num, _) = record.split_once(':').unwrap();
+                (op_num, record_num.parse::<u64>().unwrap())
+            })
+            .collect();
+        assert_eq!(replayed, (0..60).map(|num| (num, num)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use segment::common::warmup::populate_files;
+use segment::entry::entry_point::SegmentEntry as _;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+use crate::operations::types::CollectionResult;
+
+impl LocalShard {
+    /// Read on-disk vectors, quantized vectors and vector indexes of all segments into the page cache
+    ///
+    /// If `lock` is true, their files are also locked in RAM, including files of segments
+    /// created by optimizers later on. Otherwise files locked by a previous warmup are released.
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        let segments = self.segments.clone();
+        let locked_files = self.locked_files.clone();
+        tokio::task::spawn_blocking(move || {
+            let files = warmup_files(&segments);
+            if lock {
+                locked_files.lock(files)?;
+            } else {
+                locked_files.unlock();
+                populate_files(&files)?;
+            }
+            CollectionResult::Ok(())
+        })
+        .await??;
+        Ok(())
+    }
+}
+
+/// Files of all segments to warm up
+///
+/// Segment locks are only held while listing the files, not while reading them.
+pub(crate) fn warmup_files(segments: &LockedSegmentHolder) -> Vec<PathBuf> {
+    let segments: Vec<_> = segments
+        .read() // blocking sync lock
+        .iter()
+        .map(|(_id, segment)| segment.clone())
+        .collect();
+    segments
+        .iter()
+        .flat_map(|segment| segment.get().read().warmup_files()) // blocking sync lock
+        .collect()
+}
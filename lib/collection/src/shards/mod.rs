@@ -3,9 +3,11 @@ pub mod collection_shard_distribution;
 mod conversions;
 pub mod dummy_shard;
 pub mod forward_proxy_shard;
+pub mod internal_features;
 pub mod local_shard;
 pub mod proxy_shard;
 pub mod queue_proxy_shard;
+pub mod read_load;
 pub mod remote_shard;
 pub mod replica_set;
 pub mod resharding;
@@ -12,6 +12,7 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::data_types::order_by::OrderBy;
 use segment::index::field_index::CardinalityEstimation;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
@@ -27,13 +28,14 @@ use crate::operations::operation_effect::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, RecordInternal,
-    UpdateResult,
+    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, PointVersion,
+    RecordInternal, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, SegmentHnswGraphStats};
 use crate::update_handler::UpdateSignal;
 
 type ChangedPointsSet = Arc<RwLock<AHashSet<PointIdType>>>;
@@ -156,6 +158,22 @@ impl ProxyShard {
         self.wrapped_shard.get_size_stats().await
     }
 
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        self.wrapped_shard.get_hnsw_graph_stats().await
+    }
+
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        self.wrapped_shard.warmup(lock).await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        self.wrapped_shard.point_history(point_id)
+    }
+
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        self.wrapped_shard.get_quantization_telemetry().await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -320,6 +338,18 @@ impl ShardOperation for ProxyShard {
             .await
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .point_versions(ids, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`
     async fn query_batch(
         &self,
@@ -12,8 +12,9 @@ use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::manifest::SnapshotManifest;
 use segment::data_types::order_by::OrderBy;
 use segment::index::field_index::CardinalityEstimation;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
     WithPayloadInterface, WithVector,
 };
 use semver::Version;
@@ -28,13 +29,14 @@ use crate::operations::OperationWithClockTag;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, RecordInternal,
-    UpdateResult,
+    CountRequestInternal, CountResult, OptimizersStatus, PointRequestInternal, PointVersion,
+    RecordInternal, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, SegmentHnswGraphStats};
 
 /// Number of operations in batch when syncing
 const BATCH_SIZE: usize = 10;
@@ -204,6 +206,28 @@ impl QueueProxyShard {
         self.inner_unchecked().wrapped_shard.get_size_stats().await
     }
 
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .get_hnsw_graph_stats()
+            .await
+    }
+
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        self.inner_unchecked().wrapped_shard.warmup(lock).await
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        self.inner_unchecked().wrapped_shard.point_history(point_id)
+    }
+
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .get_quantization_telemetry()
+            .await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.inner_unchecked().wrapped_shard.update_tracker()
     }
@@ -347,6 +371,17 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        self.inner_unchecked()
+            .point_versions(ids, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`
     async fn query_batch(
         &self,
@@ -677,6 +712,18 @@ impl ShardOperation for Inner {
             .await
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .point_versions(ids, search_runtime_handle, timeout)
+            .await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`
     async fn query_batch(
         &self,
@@ -725,11 +772,15 @@ async fn transfer_operations_batch(
     let supports_update_batching =
         remote_shard.check_version(&MINIMAL_VERSION_FOR_BATCH_WAL_TRANSFER);
 
+    // Records of merged upserts are split, so the remote advances clock of each merged upsert
+    let operations = batch
+        .iter()
+        .flat_map(|(_idx, operation)| operation.clone().split_merged());
+
     if supports_update_batching {
         let mut batch_upd = Vec::with_capacity(batch.len());
 
-        for (_idx, operation) in batch {
-            let mut operation = operation.clone();
+        for mut operation in operations {
             // Set force flag because operations from WAL may be unordered if another node is sending
             // new operations at the same time
             if let Some(clock_tag) = &mut operation.clock_tag {
@@ -751,9 +802,7 @@ async fn transfer_operations_batch(
     }
 
     // Fallback to one-by-one transfer, in case the remote shard doesn't support batch updates
-    for (_idx, operation) in batch {
-        let mut operation = operation.clone();
-
+    for mut operation in operations {
         // Set force flag because operations from WAL may be unordered if another node is sending
         // new operations at the same time
         if let Some(clock_tag) = &mut operation.clock_tag {
@@ -0,0 +1,126 @@
+//! Read load of shard replicas
+//!
+//! Each remote replica tracks reads in flight and a moving average of latency of its recent
+//! successful reads. Fan-out reads are routed to replicas with the lowest expected completion
+//! time first, so slower or busier replicas, for example on weaker hardware, receive fewer reads.
+//! The average decays while a replica receives no reads, so a replica which was slow once gets
+//! reads again later on.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use common::decaying_average::DecayingAverage;
+
+/// Weight of the latest read in the moving average of latency
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// Time for the average latency to halve while a replica receives no reads
+const LATENCY_HALF_LIFE: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct ReadLoad {
+    in_flight: AtomicUsize,
+    /// Moving average of latency of successful reads, in microseconds
+    latency_micros: DecayingAverage,
+}
+
+impl Default for ReadLoad {
+    fn default() -> Self {
+        Self {
+            in_flight: AtomicUsize::new(0),
+            latency_micros: DecayingAverage::new(LATENCY_SMOOTHING, LATENCY_HALF_LIFE),
+        }
+    }
+}
+
+impl ReadLoad {
+    /// Track a read, until the returned guard is dropped
+    pub fn start(self: &Arc<Self>) -> ReadLoadGuard {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        ReadLoadGuard {
+            load: self.clone(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Moving average of latency of recent reads, `None` if nothing is read yet
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency_micros
+            .get()
+            .map(|micros| Duration::from_micros(micros as u64))
+    }
+
+    /// Expected time for a new read to complete, in microseconds
+    ///
+    /// Replicas with unknown latency cost nothing, so they are tried first.
+    pub fn cost(&self) -> u64 {
+        let latency_micros = self.latency_micros.get().unwrap_or(0.0);
+        (latency_micros * (self.in_flight() + 1) as f64) as u64
+    }
+
+    fn record_latency(&self, latency: Duration) {
+        self.latency_micros.observe(latency.as_micros() as f64);
+    }
+}
+
+/// Read in flight
+///
+/// Latency is only recorded for successfully finished reads, so cancelled and failed reads
+/// don't skew it. A failed read can return much faster or slower than a real one.
+pub struct ReadLoadGuard {
+    load: Arc<ReadLoad>,
+    started: Instant,
+}
+
+impl ReadLoadGuard {
+    pub fn finish(self) {
+        self.load.record_latency(self.started.elapsed());
+    }
+}
+
+impl Drop for ReadLoadGuard {
+    fn drop(&mut self) {
+        self.load.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_load() {
+        let load = Arc::new(ReadLoad::default());
+        assert_eq!(load.cost(), 0);
+        assert_eq!(load.latency(), None);
+
+        // Latency decays with time, so it is only checked approximately
+        let assert_latency_near = |expected_micros: u64| {
+            let latency = load.latency().unwrap().as_micros() as u64;
+            assert!(latency <= expected_micros && latency > expected_micros * 99 / 100);
+        };
+
+        load.record_latency(Duration::from_millis(10));
+        assert_latency_near(10_000);
+
+        load.record_latency(Duration::from_millis(20));
+        assert_latency_near(12_000);
+
+        let first = load.start();
+        let second = load.start();
+        assert_eq!(load.in_flight(), 2);
+        let cost = load.cost();
+        assert!(cost <= 36_000 && cost > 35_000);
+
+        // Cancelled reads don't affect latency
+        drop(first);
+        second.finish();
+        assert_eq!(load.in_flight(), 0);
+        assert!(load.latency().unwrap() < Duration::from_millis(12));
+    }
+}
@@ -9,19 +9,22 @@ use api::grpc::qdrant::qdrant_client::QdrantClient;
 use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
-    CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    CountResponse, FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
-    GetPoints, GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
-    InitiateShardTransferRequest, QueryBatchPointsInternal, QueryBatchResponseInternal,
-    QueryShardPoints, RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints,
-    ScrollPointsInternal, SearchBatchResponse, ShardSnapshotLocation,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    CollectionOperationResponse, CoreSearchBatchPointsInternal, CoreSearchPoints, CountPoints,
+    CountPointsInternal, CountResponse, FacetCountsInternal, GetCollectionInfoRequest,
+    GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal, GetShardRecoveryPointRequest,
+    HealthCheckRequest, InitiateShardTransferRequest, PointHistoryInternal,
+    PointHistoryResponseInternal, PointVersionsInternal, PointVersionsResponseInternal,
+    QueryBatchPointsInternal, QueryBatchResponseInternal, QueryShardPoints,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
+    SearchBatchResponse, ShardSnapshotLocation, UpdateShardCutoffPointRequest,
+    WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use api::grpc::update_operation::Update;
 use api::grpc::{UpdateBatchInternal, UpdateOperation};
 use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::defaults;
 use common::types::TelemetryDetail;
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -31,7 +34,8 @@ use segment::common::operation_time_statistics::{
 use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use semver::Version;
 use tokio::runtime::Handle;
@@ -45,6 +49,7 @@ use super::conversions::{
     internal_update_vectors,
 };
 use super::local_shard::clock_map::RecoveryPoint;
+use super::local_shard::point_history::{PointHistoryEntry, deserialize_entries};
 use super::replica_set::ReplicaState;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
@@ -52,7 +57,8 @@ use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, RecordInternal, UpdateResult,
+    CountRequestInternal, CountResult, PointRequestInternal, PointVersion, RecordInternal,
+    UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::vector_ops::VectorOperations;
@@ -65,6 +71,14 @@ use crate::shards::conversions::{
     internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
     internal_upsert_points, try_scored_point_from_grpc,
 };
+use crate::shards::internal_features::{
+    InternalFeature, downgrade_adaptive_oversampling, downgrade_prefetch_adaptive_oversampling,
+    downgrade_set_payload_unsigned_values, downgrade_skip_unchanged,
+    downgrade_sync_unsigned_values, downgrade_update_unsigned_values,
+    downgrade_upsert_unsigned_values, prefetch_uses_deterministic_scoring,
+    uses_deterministic_scoring,
+};
+use crate::shards::read_load::ReadLoad;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::RemoteShardTelemetry;
@@ -83,6 +97,7 @@ pub struct RemoteShard {
     pub channel_service: ChannelService,
     telemetry_search_durations: Arc<Mutex<OperationDurationsAggregator>>,
     telemetry_update_durations: Arc<Mutex<OperationDurationsAggregator>>,
+    read_load: Arc<ReadLoad>,
 }
 
 impl RemoteShard {
@@ -100,9 +115,15 @@ impl RemoteShard {
             channel_service,
             telemetry_search_durations: OperationDurationsAggregator::new(),
             telemetry_update_durations: OperationDurationsAggregator::new(),
+            read_load: Arc::default(),
         }
     }
 
+    /// Load of reads from this replica, issued by this peer
+    pub fn read_load(&self) -> &Arc<ReadLoad> {
+        &self.read_load
+    }
+
     /// Checks that remote shard is at least at the given version
     /// - Returns `true` if we know that the peer is at least at the given version
     /// - Returns `false` if we know that the peer not at the given version or version is unknown
@@ -111,6 +132,35 @@ impl RemoteShard {
             .peer_is_at_version(self.peer_id, version)
     }
 
+    /// Checks that remote shard supports the given internal API feature
+    pub fn check_feature(&self, feature: InternalFeature) -> bool {
+        self.channel_service.peer_supports(self.peer_id, feature)
+    }
+
+    /// Fails if remote shard does not support the given internal API feature
+    ///
+    /// For features, which can't be downgraded without changing the result of the request.
+    /// The error is transient, so reads are retried on other replicas.
+    pub fn require_feature(&self, feature: InternalFeature) -> CollectionResult<()> {
+        if self.check_feature(feature) {
+            return Ok(());
+        }
+
+        let peer_version = self.channel_service.peer_version(self.peer_id).map_or_else(
+            || "unknown version".to_string(),
+            |version| version.to_string(),
+        );
+        Err(CollectionError::PreConditionFailed {
+            description: format!(
+                "Peer {} running Qdrant {peer_version} does not support {}, \
+                 all peers must be upgraded to Qdrant {} to use it",
+                self.peer_id,
+                feature.name(),
+                *defaults::QDRANT_VERSION,
+            ),
+        })
+    }
+
     pub fn restore_snapshot(_snapshot_path: &Path) {
         // NO extra actions needed for remote shards
     }
@@ -209,6 +259,11 @@ impl RemoteShard {
                 .telemetry_update_durations
                 .lock()
                 .get_statistics(detail),
+            in_flight_reads: self.read_load.in_flight(),
+            recent_read_latency_micros: self
+                .read_load
+                .latency()
+                .map(|latency| latency.as_micros() as f32),
         }
     }
 
@@ -240,8 +295,11 @@ impl RemoteShard {
         let collection_name = &self.collection_id;
         let ordering = Some(ordering);
 
+        let supports_unsigned_values = self.check_feature(InternalFeature::UnsignedPayloadValues);
+        let supports_skip_unchanged = self.check_feature(InternalFeature::SkipUnchanged);
+
         for operation in operations {
-            let update_op = match operation.operation {
+            let mut update_op = match operation.operation {
                 CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
                     PointOperations::UpsertPoints(point_insert_operations) => {
                         let request = internal_upsert_points(
@@ -256,7 +314,7 @@ impl RemoteShard {
                         Update::Upsert(request)
                     }
                     PointOperations::UpsertPointsConditional(conditional_upsert) => {
-                        let request = internal_conditional_upsert_points(
+                        let mut request = internal_conditional_upsert_points(
                             shard_id,
                             operation.clock_tag,
                             collection_name.clone(),
@@ -264,6 +322,9 @@ impl RemoteShard {
                             wait,
                             ordering,
                         )?;
+                        if !supports_skip_unchanged {
+                            downgrade_skip_unchanged(&mut request);
+                        }
                         Update::Upsert(request)
                     }
                     PointOperations::DeletePoints { ids } => {
@@ -421,6 +482,9 @@ impl RemoteShard {
                     }
                 }
             };
+            if !supports_unsigned_values {
+                downgrade_update_unsigned_values(&mut update_op);
+            }
             updates.push(UpdateOperation {
                 update: Some(update_op),
             });
@@ -492,10 +556,13 @@ impl RemoteShard {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_update_durations);
         timer.set_success(false);
 
+        let supports_unsigned_values = self.check_feature(InternalFeature::UnsignedPayloadValues);
+        let supports_skip_unchanged = self.check_feature(InternalFeature::SkipUnchanged);
+
         let point_operation_response = match operation.operation {
             CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
                 PointOperations::UpsertPoints(point_insert_operations) => {
-                    let request = &internal_upsert_points(
+                    let mut request = internal_upsert_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -503,6 +570,10 @@ impl RemoteShard {
                         wait,
                         ordering,
                     )?;
+                    if !supports_unsigned_values {
+                        downgrade_upsert_unsigned_values(&mut request);
+                    }
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.upsert(tonic::Request::new(request.clone())).await
                     })
@@ -510,7 +581,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::UpsertPointsConditional(conditional_upsert) => {
-                    let request = &internal_conditional_upsert_points(
+                    let mut request = internal_conditional_upsert_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -518,6 +589,13 @@ impl RemoteShard {
                         wait,
                         ordering,
                     )?;
+                    if !supports_skip_unchanged {
+                        downgrade_skip_unchanged(&mut request);
+                    }
+                    if !supports_unsigned_values {
+                        downgrade_upsert_unsigned_values(&mut request);
+                    }
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.upsert(tonic::Request::new(request.clone())).await
                     })
@@ -555,7 +633,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PointOperations::SyncPoints(operation) => {
-                    let request = &internal_sync_points(
+                    let mut request = internal_sync_points(
                         shard_id,
                         None, // TODO!?
                         collection_name,
@@ -563,6 +641,10 @@ impl RemoteShard {
                         wait,
                         ordering,
                     )?;
+                    if !supports_unsigned_values {
+                        downgrade_sync_unsigned_values(&mut request);
+                    }
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client.sync(tonic::Request::new(request.clone())).await
                     })
@@ -627,7 +709,7 @@ impl RemoteShard {
             },
             CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
                 PayloadOps::SetPayload(set_payload) => {
-                    let request = &internal_set_payload(
+                    let mut request = internal_set_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -635,6 +717,10 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    if !supports_unsigned_values {
+                        downgrade_set_payload_unsigned_values(&mut request);
+                    }
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .set_payload(tonic::Request::new(request.clone()))
@@ -695,7 +781,7 @@ impl RemoteShard {
                     .into_inner()
                 }
                 PayloadOps::OverwritePayload(set_payload) => {
-                    let request = &internal_set_payload(
+                    let mut request = internal_set_payload(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
@@ -703,6 +789,10 @@ impl RemoteShard {
                         wait,
                         ordering,
                     );
+                    if !supports_unsigned_values {
+                        downgrade_set_payload_unsigned_values(&mut request);
+                    }
+                    let request = &request;
                     self.with_points_client(|mut client| async move {
                         client
                             .overwrite_payload(tonic::Request::new(request.clone()))
@@ -891,6 +981,28 @@ impl RemoteShard {
         Ok(())
     }
 
+    /// Recorded changes of the point in this replica, the newest first
+    pub async fn point_history(
+        &self,
+        point_id: ExtendedPointId,
+    ) -> CollectionResult<Vec<PointHistoryEntry>> {
+        let request = &PointHistoryInternal {
+            collection_name: self.collection_id.clone(),
+            id: Some(point_id.into()),
+            shard_id: self.id,
+        };
+        let PointHistoryResponseInternal { entries, time: _ } = self
+            .with_points_client(|mut client| async move {
+                client
+                    .point_history(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        deserialize_entries(&entries)
+    }
+
     /// Validate timeout before making a read operation.
     /// - detect elapsed timeouts early to avoid unnecessary traffic
     /// - round up to the nearest second to well with our internal timeout handling
@@ -965,6 +1077,7 @@ impl ShardOperation for RemoteShard {
             shard_key_selector: None,
             order_by: order_by.map(|o| o.clone().into()),
             timeout: processed_timeout.map(|t| t.as_secs()),
+            after_id: None,
         };
         let scroll_request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -1025,11 +1138,27 @@ impl ShardOperation for RemoteShard {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
 
+        let supports_adaptive_oversampling =
+            self.check_feature(InternalFeature::AdaptiveOversampling);
         let search_points = batch_request
             .searches
             .iter()
-            .map(|s| CollectionCoreSearchRequest((self.collection_id.clone(), s)).into())
-            .collect();
+            .map(|s| {
+                let mut search_points: CoreSearchPoints =
+                    CollectionCoreSearchRequest((self.collection_id.clone(), s)).into();
+                if !supports_adaptive_oversampling {
+                    downgrade_adaptive_oversampling(search_points.params.as_mut());
+                }
+                search_points
+            })
+            .collect::<Vec<_>>();
+
+        if search_points
+            .iter()
+            .any(|search_points| uses_deterministic_scoring(search_points.params.as_ref()))
+        {
+            self.require_feature(InternalFeature::DeterministicScoring)?;
+        }
 
         let request = &CoreSearchBatchPointsInternal {
             collection_name: self.collection_id.clone(),
@@ -1183,6 +1312,39 @@ impl ShardOperation for RemoteShard {
         result.map_err(|e| e.into())
     }
 
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        self.require_feature(InternalFeature::PointVersions)?;
+
+        let processed_timeout = Self::process_read_timeout(timeout, "point_versions")?;
+        let request = &PointVersionsInternal {
+            collection_name: self.collection_id.clone(),
+            ids: ids.iter().copied().map(From::from).collect(),
+            shard_id: self.id,
+            timeout: processed_timeout.map(|t| t.as_secs()),
+        };
+
+        let PointVersionsResponseInternal { points, time: _ } = self
+            .with_points_client(|mut client| async move {
+                let mut request = tonic::Request::new(request.clone());
+                if let Some(timeout) = processed_timeout {
+                    request.set_timeout(timeout);
+                }
+                client.point_versions(request).await
+            })
+            .await?
+            .into_inner();
+
+        let versions: Result<Vec<PointVersion>, Status> =
+            points.into_iter().map(PointVersion::try_from).collect();
+
+        versions.map_err(|e| e.into())
+    }
+
     async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
@@ -1194,22 +1356,36 @@ impl ShardOperation for RemoteShard {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
 
-        let requests = requests.as_ref();
+        let supports_adaptive_oversampling =
+            self.check_feature(InternalFeature::AdaptiveOversampling);
+        let query_points: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                let mut query_points = QueryShardPoints::from(request.clone());
+                if !supports_adaptive_oversampling {
+                    downgrade_adaptive_oversampling(query_points.params.as_mut());
+                    downgrade_prefetch_adaptive_oversampling(&mut query_points.prefetch);
+                }
+                query_points
+            })
+            .collect();
 
-        let batch_response = self
-            .with_points_client(|mut client| async move {
-                let query_points = requests
-                    .iter()
-                    .map(|request| QueryShardPoints::from(request.clone()))
-                    .collect();
+        if query_points.iter().any(|query_points| {
+            uses_deterministic_scoring(query_points.params.as_ref())
+                || prefetch_uses_deterministic_scoring(&query_points.prefetch)
+        }) {
+            self.require_feature(InternalFeature::DeterministicScoring)?;
+        }
 
-                let request = &QueryBatchPointsInternal {
-                    collection_name: self.collection_id.clone(),
-                    query_points,
-                    shard_id: Some(self.id),
-                    timeout: processed_timeout.map(|t| t.as_secs()),
-                };
+        let request = &QueryBatchPointsInternal {
+            collection_name: self.collection_id.clone(),
+            query_points,
+            shard_id: Some(self.id),
+            timeout: processed_timeout.map(|t| t.as_secs()),
+        };
 
+        let batch_response = self
+            .with_points_client(|mut client| async move {
                 let mut request = tonic::Request::new(request.clone());
 
                 if let Some(timeout) = processed_timeout {
@@ -1226,12 +1402,17 @@ impl ShardOperation for RemoteShard {
             time: _,
             hardware_usage,
             inference_usage: _, // Remote shards don't have inference usage, so we can ignore it
+            truncated,
         } = batch_response;
 
         if let Some(hw_usage) = hardware_usage {
             hw_measurement_acc.accumulate_request(hw_usage);
         }
 
+        if truncated == Some(true) {
+            hw_measurement_acc.mark_search_truncated();
+        }
+
         let result = results
             .into_iter()
             .zip(requests.iter())
@@ -191,7 +191,7 @@ impl ShardReplicaSet {
                 read_operation(local.get()).await
             };
 
-            Some(local_operation.map(|result| (result, true)).left_future())
+            Some(local_operation.map(|result| (result, None)).left_future())
         } else {
             None
         };
@@ -202,11 +202,21 @@ impl ShardReplicaSet {
             .filter(|remote| self.peer_is_active(remote.peer_id))
             .collect();
 
+        // Prefer least loaded replicas, shuffle to spread reads between equally loaded ones
         active_remotes.shuffle(&mut rand::rng());
+        active_remotes.sort_by_cached_key(|remote| remote.read_load().cost());
 
         let remote_operations = active_remotes.into_iter().map(|remote| {
+            let peer_id = remote.peer_id;
+            let read_load = remote.read_load().start();
             read_operation(remote)
-                .map(|result| (result, false))
+                .map(move |result| {
+                    // Failed reads are not representative of the replica latency
+                    if result.is_ok() {
+                        read_load.finish();
+                    }
+                    (result, Some(peer_id))
+                })
                 .right_future()
         });
 
@@ -266,13 +276,17 @@ impl ShardReplicaSet {
                         break;
                     };
 
-                    let (operation_result, is_local_operation) = operation_result;
-
-                    result = operation_result;
+                    let (operation_result, remote_peer_id) = operation_result;
 
-                    if is_local_operation {
-                        is_local_operation_resolved = true;
-                    }
+                    result = match remote_peer_id {
+                        Some(peer_id) => operation_result.map_err(|err| {
+                            CollectionError::remote_shard_error(peer_id, self.shard_id, err)
+                        }),
+                        None => {
+                            is_local_operation_resolved = true;
+                            operation_result
+                        }
+                    };
                 }
 
                 _ = &mut update_watcher, if local_is_active && !is_local_operation_resolved => {
@@ -815,6 +815,16 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Load data of the local replica into the page cache, if there is a local replica
+    pub(crate) async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.warmup(lock).await
+        } else {
+            Ok(())
+        }
+    }
+
     /// Apply shard's strict mode configuration update
     /// - Update read and write rate limiters
     pub(crate) async fn on_strict_mode_config_update(&mut self) -> CollectionResult<()> {
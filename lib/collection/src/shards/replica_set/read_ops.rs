@@ -11,6 +11,8 @@ use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::internal_features::InternalFeature;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -160,6 +162,26 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        local_only: bool,
+    ) -> CollectionResult<Vec<PointVersion>> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let ids = ids.clone();
+                let search_runtime = self.search_runtime.clone();
+
+                async move { shard.point_versions(ids, &search_runtime, timeout).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
     pub async fn info(&self, local_only: bool) -> CollectionResult<CollectionInfo> {
         self.execute_read_operation(
             |shard| async move { shard.info().await }.boxed(),
@@ -236,4 +258,50 @@ impl ShardReplicaSet {
         )
         .await
     }
+
+    /// Recorded changes of the point, the newest first
+    ///
+    /// Every replica records changes it applies, so the history is read from a single replica.
+    /// The local replica is preferred, if it is active.
+    pub async fn point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<PointHistoryEntry>> {
+        {
+            let local = self.local.read().await;
+            if let Some(local) = local.as_ref()
+                && self.peer_is_active(self.this_peer_id())
+            {
+                return Ok(local.point_history(point_id));
+            }
+        }
+
+        let remotes = self.remotes.read().await;
+        let remote = remotes.iter().find(|remote| {
+            self.peer_is_active(remote.peer_id)
+                && remote.check_feature(InternalFeature::PointHistory)
+        });
+        match remote {
+            Some(remote) => remote.point_history(point_id).await,
+            None => Err(CollectionError::service_error(format!(
+                "No active replica of shard {} to read point history from",
+                self.shard_id,
+            ))),
+        }
+    }
+
+    /// Recorded changes of the point in the local replica, the newest first
+    pub async fn local_point_history(
+        &self,
+        point_id: PointIdType,
+    ) -> CollectionResult<Vec<PointHistoryEntry>> {
+        let local = self.local.read().await;
+        let local = local.as_ref().ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "No local replica of shard {} to read point history from",
+                self.shard_id,
+            ))
+        })?;
+        Ok(local.point_history(point_id))
+    }
 }
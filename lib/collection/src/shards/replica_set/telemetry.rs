@@ -1,11 +1,14 @@
 use std::ops::Deref as _;
 
 use common::types::TelemetryDetail;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::SizeStats;
 
 use crate::operations::types::OptimizersStatus;
 use crate::shards::replica_set::ShardReplicaSet;
-use crate::shards::telemetry::{PartialSnapshotTelemetry, ReplicaSetTelemetry};
+use crate::shards::telemetry::{
+    PartialSnapshotTelemetry, ReplicaSetTelemetry, SegmentHnswGraphStats,
+};
 
 impl ShardReplicaSet {
     pub(crate) async fn get_telemetry_data(&self, detail: TelemetryDetail) -> ReplicaSetTelemetry {
@@ -58,4 +61,26 @@ impl ShardReplicaSet {
 
         local.get_size_stats().await
     }
+
+    /// HNSW graph statistics of the local replica, if there is one
+    pub(crate) async fn get_hnsw_graph_stats(&self) -> Option<Vec<SegmentHnswGraphStats>> {
+        let local_shard = self.local.read().await;
+
+        let Some(local) = local_shard.deref() else {
+            return None;
+        };
+
+        Some(local.get_hnsw_graph_stats().await)
+    }
+
+    /// Quantization telemetry of the local replica, aggregated over its segments
+    pub(crate) async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        let local_shard = self.local.read().await;
+
+        let Some(local) = local_shard.deref() else {
+            return vec![];
+        };
+
+        local.get_quantization_telemetry().await
+    }
 }
@@ -505,8 +505,15 @@ impl ShardReplicaSet {
                     update_only_existing,
                 );
 
-                let (_peer_id, err) = failures.into_iter().next().unwrap();
-                return Err(err);
+                let (peer_id, err) = failures.into_iter().next().unwrap();
+                if peer_id == this_peer_id {
+                    return Err(err);
+                }
+                return Err(CollectionError::remote_shard_error(
+                    peer_id,
+                    self.shard_id,
+                    err,
+                ));
             }
         }
 
@@ -693,6 +700,7 @@ impl ShardReplicaSet {
                 hw_measurement_acc,
             ) // `clock_tag` *has to* be `None`!
             .await
+            .map_err(|err| CollectionError::remote_shard_error(leader_peer, self.shard_id, err))
     }
 }
 
@@ -754,6 +762,8 @@ mod tests {
             wal_capacity_mb: 1,
             wal_segments_ahead: 0,
             wal_retain_closed: 1,
+            wal_batch_window_ms: 0,
+            wal_point_history: 0,
         };
 
         let collection_params = CollectionParams {
@@ -773,6 +783,7 @@ mod tests {
             strict_mode_config: None,
             uuid: None,
             metadata: None,
+            custom_metric: None,
         };
 
         let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
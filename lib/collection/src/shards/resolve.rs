@@ -9,7 +9,7 @@ use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
 use crate::common::transpose_iterator::transposed_iter;
-use crate::operations::types::{CountResult, RecordInternal};
+use crate::operations::types::{CountResult, PointVersion, RecordInternal};
 use crate::operations::universal_query::shard_query::ShardQueryResponse;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -138,6 +138,12 @@ impl Resolve for Vec<RecordInternal> {
     }
 }
 
+impl Resolve for Vec<PointVersion> {
+    fn resolve(versions: Vec<Self>, condition: ResolveCondition) -> Self {
+        Resolver::resolve(versions, |point| point.id, PartialEq::eq, condition)
+    }
+}
+
 impl Resolve for Vec<Vec<ScoredPoint>> {
     fn resolve(batches: Vec<Self>, condition: ResolveCondition) -> Self {
         // batches: <replica_id, <batch_id, ScoredPoints>>
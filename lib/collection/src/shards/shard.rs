@@ -7,7 +7,8 @@ use common::tar_ext;
 use common::types::TelemetryDetail;
 use segment::data_types::manifest::SnapshotManifest;
 use segment::index::field_index::CardinalityEstimation;
-use segment::types::{Filter, SizeStats, SnapshotFormat};
+use segment::telemetry::QuantizationTelemetry;
+use segment::types::{Filter, PointIdType, SizeStats, SnapshotFormat};
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
@@ -16,10 +17,11 @@ use crate::operations::types::{CollectionError, CollectionResult, OptimizersStat
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
+use crate::shards::local_shard::point_history::PointHistoryEntry;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
 use crate::shards::shard_trait::ShardOperation;
-use crate::shards::telemetry::LocalShardTelemetry;
+use crate::shards::telemetry::{LocalShardTelemetry, SegmentHnswGraphStats};
 
 pub type ShardId = u32;
 
@@ -111,6 +113,48 @@ impl Shard {
         }
     }
 
+    pub async fn get_hnsw_graph_stats(&self) -> Vec<SegmentHnswGraphStats> {
+        match self {
+            Shard::Local(local_shard) => local_shard.get_hnsw_graph_stats().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.get_hnsw_graph_stats().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.get_hnsw_graph_stats().await,
+            Shard::QueueProxy(queue_proxy_shard) => queue_proxy_shard.get_hnsw_graph_stats().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.get_hnsw_graph_stats(),
+        }
+    }
+
+    pub async fn warmup(&self, lock: bool) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.warmup(lock).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.warmup(lock).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.warmup(lock).await,
+            Shard::QueueProxy(queue_proxy_shard) => queue_proxy_shard.warmup(lock).await,
+            Shard::Dummy(dummy_shard) => dummy_shard.warmup(),
+        }
+    }
+
+    pub fn point_history(&self, point_id: PointIdType) -> Vec<PointHistoryEntry> {
+        match self {
+            Shard::Local(local_shard) => local_shard.point_history(point_id),
+            Shard::Proxy(proxy_shard) => proxy_shard.point_history(point_id),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.point_history(point_id),
+            Shard::QueueProxy(queue_proxy_shard) => queue_proxy_shard.point_history(point_id),
+            Shard::Dummy(dummy_shard) => dummy_shard.point_history(point_id),
+        }
+    }
+
+    pub async fn get_quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        match self {
+            Shard::Local(local_shard) => local_shard.get_quantization_telemetry().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.get_quantization_telemetry().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.get_quantization_telemetry().await,
+            Shard::QueueProxy(queue_proxy_shard) => {
+                queue_proxy_shard.get_quantization_telemetry().await
+            }
+            Shard::Dummy(dummy_shard) => dummy_shard.get_quantization_telemetry(),
+        }
+    }
+
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
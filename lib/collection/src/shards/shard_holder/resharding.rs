@@ -525,9 +525,11 @@ impl OperationsByMode {
             CollectionUpdateOperations::PointOperation(point_operation) => match point_operation {
                 PointOperations::UpsertPoints(operation) => operation.into_update_only(None),
                 PointOperations::UpsertPointsConditional(operation) => {
+                    // Unchanged points are not skipped when migrating, which is harmless
                     let ConditionalInsertOperationInternal {
                         points_op,
                         condition,
+                        skip_unchanged: _,
                     } = operation;
                     points_op.into_update_only(Some(condition))
                 }
@@ -186,6 +186,7 @@ mod test {
             strict_mode_config: None,
             uuid: None,
             metadata: None,
+            custom_metric: None,
         };
 
         let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
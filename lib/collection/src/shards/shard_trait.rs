@@ -63,6 +63,14 @@ pub trait ShardOperation {
         hardware_accumulator: HwMeasurementAcc,
     ) -> CollectionResult<Vec<RecordInternal>>;
 
+    /// Versions of the requested points, which exist in the shard, in the order of `ids`
+    async fn point_versions(
+        &self,
+        ids: Arc<Vec<PointIdType>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<PointVersion>>;
+
     async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
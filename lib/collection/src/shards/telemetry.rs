@@ -3,11 +3,13 @@ use std::collections::HashMap;
 use schemars::JsonSchema;
 use segment::common::anonymize::{Anonymize, anonymize_collection_values};
 use segment::common::operation_time_statistics::OperationDurationStatistics;
+use segment::index::hnsw_index::graph_stats::HnswGraphStats;
 use segment::telemetry::SegmentTelemetry;
-use segment::types::ShardKey;
+use segment::types::{ShardKey, VectorNameBuf};
 use serde::Serialize;
 
 use crate::collection_manager::optimizers::TrackerTelemetry;
+use crate::collection_manager::optimizers::merge_planner::PlannedMerge;
 use crate::operations::types::{OptimizersStatus, ShardStatus};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -33,6 +35,12 @@ pub struct RemoteShardTelemetry {
     pub peer_id: Option<PeerId>,
     pub searches: OperationDurationStatistics,
     pub updates: OperationDurationStatistics,
+    /// Number of reads from this replica currently in flight
+    pub in_flight_reads: usize,
+    /// Moving average of latency of recent reads from this replica
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[anonymize(false)]
+    pub recent_read_latency_micros: Option<f32>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -73,6 +81,9 @@ pub struct OptimizerTelemetry {
     pub optimizations: OperationDurationStatistics,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub log: Option<Vec<TrackerTelemetry>>,
+    /// Planned merges of segments, highest priority first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_plan: Option<Vec<PlannedMerge>>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, JsonSchema, Anonymize)]
@@ -84,3 +95,19 @@ pub struct PartialSnapshotTelemetry {
     #[anonymize(false)]
     pub recovery_timestamp: u64,
 }
+
+/// HNSW graph statistics of a local shard replica
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct ShardHnswGraphStats {
+    pub shard_id: ShardId,
+    /// Only segments with at least one HNSW index are listed
+    pub segments: Vec<SegmentHnswGraphStats>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct SegmentHnswGraphStats {
+    /// Number of available points in the segment
+    pub num_points: usize,
+    /// Graph statistics by vector name
+    pub vectors: HashMap<VectorNameBuf, HnswGraphStats>,
+}
@@ -2,10 +2,14 @@ use std::collections::HashMap;
 
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::telemetry::QuantizationTelemetry;
 use segment::types::{HnswConfig, Payload, QuantizationConfig, StrictModeConfigOutput};
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::collection::recall::RecallTelemetry;
+use crate::collection::search_shadowing::SearchShadowingTelemetry;
+use crate::common::fair_scheduler::FairSchedulerTelemetry;
 use crate::config::{CollectionConfigInternal, CollectionParams, WalConfig};
 use crate::operations::types::{OptimizersStatus, ReshardingInfo, ShardTransferInfo};
 use crate::optimizers_builder::OptimizersConfig;
@@ -33,6 +37,22 @@ pub struct CollectionTelemetry {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[anonymize(false)]
     pub shard_clean_tasks: Option<HashMap<ShardId, ShardCleanStatusTelemetry>>,
+
+    /// Recall of the approximate search, measured against sampled exact-search ground truth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recall: Option<RecallTelemetry>,
+
+    /// Fair scheduling of searches across shard keys, if enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_scheduler: Option<FairSchedulerTelemetry>,
+
+    /// Comparison of sampled searches against shadow searches with an alternative setup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_shadowing: Option<SearchShadowingTelemetry>,
+
+    /// Size and estimated recall of quantized vectors of local shards, by vector
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<Vec<QuantizationTelemetry>>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -103,6 +123,7 @@ impl From<CollectionConfigInternal> for CollectionConfigTelemetry {
             strict_mode_config,
             uuid,
             metadata,
+            custom_metric: _,
         } = config;
         CollectionConfigTelemetry {
             params,
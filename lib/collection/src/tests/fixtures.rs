@@ -30,6 +30,8 @@ pub fn create_collection_config_with_dim(dim: usize) -> CollectionConfigInternal
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let collection_params = CollectionParams {
@@ -51,6 +53,7 @@ pub fn create_collection_config_with_dim(dim: usize) -> CollectionConfigInternal
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     }
 }
 
@@ -8,6 +8,7 @@ mod sha_256_test;
 mod shard_query;
 mod snapshot_test;
 mod sparse_vectors_validation_tests;
+mod upsert_batch_test;
 mod wal_recovery_test;
 
 use std::sync::Arc;
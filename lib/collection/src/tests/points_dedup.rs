@@ -43,6 +43,8 @@ async fn fixture() -> Collection {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let collection_params = CollectionParams {
@@ -62,6 +64,7 @@ async fn fixture() -> Collection {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -161,6 +164,7 @@ async fn test_scroll_dedup() {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(usize::MAX),
                 filter: None,
                 with_payload: Some(false.into()),
@@ -189,6 +193,7 @@ async fn test_scroll_dedup() {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(usize::MAX),
                 filter: None,
                 with_payload: Some(false.into()),
@@ -41,6 +41,8 @@ async fn fixture() -> Collection {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let collection_params = CollectionParams {
@@ -60,6 +62,7 @@ async fn fixture() -> Collection {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
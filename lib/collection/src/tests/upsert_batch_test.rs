@@ -0,0 +1,82 @@
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::budget::ResourceBudget;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::save_on_disk::SaveOnDisk;
+use segment::data_types::vectors::VectorStructInternal;
+use tempfile::Builder;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStructPersisted,
+};
+use crate::operations::types::UpdateStatus;
+use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::*;
+
+const BATCH_WINDOW_MS: u64 = 100;
+
+fn tagged_upsert(peer_id: u64, point_id: u64) -> OperationWithClockTag {
+    let points = vec![PointStructPersisted {
+        id: point_id.into(),
+        vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0]).into(),
+        payload: None,
+    }];
+    OperationWithClockTag::new(
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::from(points),
+        )),
+        Some(ClockTag::new(peer_id, 0, 1)),
+    )
+}
+
+/// Merged batch is written, even if the request which took it is cancelled
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merged_upserts_outlive_cancelled_request() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.wal_config.wal_batch_window_ms = BATCH_WINDOW_MS;
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        "test".to_string(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        current_runtime.clone(),
+        ResourceBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let hw_acc = HwMeasurementAcc::new();
+
+    // Join the batch, and stop polling the request before the window ends
+    let mut waiting = pin!(shard.update(tagged_upsert(1, 1), true, hw_acc.clone()));
+    let joined = tokio::time::timeout(Duration::from_millis(BATCH_WINDOW_MS / 5), &mut waiting);
+    assert!(joined.await.is_err());
+
+    // The other request takes the batch once the window ends, and is cancelled while it's written
+    let cancelled = shard.update(tagged_upsert(2, 2), true, hw_acc.clone());
+    let _ = tokio::time::timeout(Duration::from_millis(BATCH_WINDOW_MS), cancelled).await;
+
+    let result = waiting.await.unwrap();
+    assert_eq!(result.status, UpdateStatus::Completed);
+    assert_eq!(shard.info().await.unwrap().points_count, Some(2));
+}
@@ -10,9 +10,11 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::panic;
 use common::save_on_disk::SaveOnDisk;
+use common::search_pressure::IoThrottle;
 use itertools::Itertools;
 use parking_lot::Mutex;
 use segment::common::operation_error::{OperationError, OperationResult};
+use segment::common::warmup::LockedFiles;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::types::{QuantizationConfig, SeqNumberType};
 use shard::wal::WalError;
@@ -38,7 +40,10 @@ use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::profiling::interface::log_request_to_collector;
 use crate::shards::CollectionId;
-use crate::shards::local_shard::LocalShardClocks;
+use crate::shards::local_shard::point_history::{PointHistory, operation_timestamp};
+use crate::shards::local_shard::wal_offload::{WalOffloadStager, WalOffloader};
+use crate::shards::local_shard::warmup::warmup_files;
+use crate::shards::local_shard::{LocalShard, LocalShardClocks};
 use crate::shards::update_tracker::UpdateTracker;
 use crate::wal_delta::LockedWal;
 
@@ -47,6 +52,9 @@ use crate::wal_delta::LockedWal;
 /// The longer the duration, the longer it  takes for panicked tasks to be reported.
 const OPTIMIZER_CLEANUP_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Maximum number of operations applied in a single batch of the update worker
+const MAX_UPDATE_BATCH_SIZE: usize = 256;
+
 pub type Optimizer = dyn SegmentOptimizer + Sync + Send;
 
 /// Information, required to perform operation and notify regarding the result
@@ -56,6 +64,8 @@ pub struct OperationData {
     pub op_num: SeqNumberType,
     /// Operation
     pub operation: CollectionUpdateOperations,
+    /// Time the operation was written to WAL, if stored for point history
+    pub timestamp: Option<u64>,
     /// If operation was requested to wait for result
     pub wait: bool,
     /// Callback notification channel
@@ -105,6 +115,9 @@ pub struct UpdateHandler {
     /// How frequent can we flush data
     /// This parameter depends on the optimizer config and should be updated accordingly.
     pub flush_interval_sec: u64,
+    /// Time window to collect concurrent update operations into a single batch.
+    /// Batching is disabled if zero.
+    update_batch_window: Duration,
     segments: LockedSegmentHolder,
     /// Process, that listens updates signals and perform updates
     update_worker: Option<JoinHandle<()>>,
@@ -114,6 +127,10 @@ pub struct UpdateHandler {
     flush_worker: Option<JoinHandle<()>>,
     /// Sender to stop flush worker
     flush_stop: Option<oneshot::Sender<()>>,
+    /// Process that uploads closed WAL segments handed over by the flush worker, if enabled
+    wal_offload_worker: Option<JoinHandle<()>>,
+    /// Sender to stop WAL offload worker
+    wal_offload_stop: Option<oneshot::Sender<()>>,
     runtime_handle: Handle,
     /// WAL, required for operations
     wal: LockedWal,
@@ -141,6 +158,12 @@ pub struct UpdateHandler {
     scroll_read_lock: Arc<tokio::sync::RwLock<()>>,
 
     update_tracker: UpdateTracker,
+
+    /// History of changes of each point, recorded for applied operations if enabled
+    point_history: Option<Arc<PointHistory>>,
+
+    /// Segment files locked in RAM by warmup, updated after each optimization
+    locked_files: Arc<LockedFiles>,
 }
 
 impl UpdateHandler {
@@ -157,11 +180,14 @@ impl UpdateHandler {
         segments: LockedSegmentHolder,
         wal: LockedWal,
         flush_interval_sec: u64,
+        update_batch_window: Duration,
         max_optimization_threads: Option<usize>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
         scroll_read_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        point_history: Option<Arc<PointHistory>>,
+        locked_files: Arc<LockedFiles>,
     ) -> UpdateHandler {
         UpdateHandler {
             collection_name,
@@ -176,10 +202,13 @@ impl UpdateHandler {
             optimizer_resource_budget,
             flush_worker: None,
             flush_stop: None,
+            wal_offload_worker: None,
+            wal_offload_stop: None,
             runtime_handle,
             wal,
             wal_keep_from: Arc::new(u64::MAX.into()),
             flush_interval_sec,
+            update_batch_window,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
             clocks,
@@ -187,6 +216,8 @@ impl UpdateHandler {
             has_triggered_optimizers: Default::default(),
             scroll_read_lock,
             update_tracker,
+            point_history,
+            locked_files,
         }
     }
 
@@ -208,6 +239,7 @@ impl UpdateHandler {
             self.payload_index_schema.clone(),
             self.scroll_read_lock.clone(),
             self.update_tracker.clone(),
+            self.locked_files.clone(),
         )));
 
         let wal = self.wal.clone();
@@ -215,6 +247,10 @@ impl UpdateHandler {
         let scroll_read_lock = self.scroll_read_lock.clone();
         let update_tracker = self.update_tracker.clone();
         let collection_name = self.collection_name.clone();
+        let update_batch_window = self.update_batch_window;
+        let runtime_handle = self.runtime_handle.clone();
+        let point_history = self.point_history.clone();
+        let io_throttle = self.optimizer_resource_budget.update_io_throttle();
         self.update_worker = Some(self.runtime_handle.spawn_blocking(move || {
             Self::update_worker_fn(
                 collection_name,
@@ -224,6 +260,10 @@ impl UpdateHandler {
                 segments,
                 scroll_read_lock,
                 update_tracker,
+                update_batch_window,
+                runtime_handle,
+                point_history,
+                io_throttle,
             )
         }));
 
@@ -234,6 +274,36 @@ impl UpdateHandler {
         let flush_interval_sec = self.flush_interval_sec;
         let runtime_handle = self.runtime_handle.clone();
         let shard_path = self.shard_path.clone();
+        let point_history = self.point_history.clone();
+        let wal_offload = self
+            .shared_storage_config
+            .wal_offload
+            .as_ref()
+            .and_then(|config| {
+                WalOffloadStager::load(LocalShard::wal_path(&shard_path), &shard_path)
+                    .and_then(|stager| {
+                        let offloader =
+                            WalOffloader::new(config, &self.collection_name, &shard_path)?;
+                        Ok((stager, offloader))
+                    })
+                    .inspect_err(|err| {
+                        log::error!(
+                            "Failed to set up WAL offloading for shard {}: {err}",
+                            shard_path.display(),
+                        );
+                    })
+                    .ok()
+            });
+        let wal_offload_stager = match wal_offload {
+            Some((stager, offloader)) => {
+                let (offload_tx, offload_rx) = oneshot::channel();
+                let worker = Self::wal_offload_worker_fn(offloader, flush_interval_sec, offload_rx);
+                self.wal_offload_worker = Some(self.runtime_handle.spawn(worker));
+                self.wal_offload_stop = Some(offload_tx);
+                Some(stager)
+            }
+            None => None,
+        };
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn_blocking(move || {
             Self::flush_worker_fn(
@@ -245,6 +315,8 @@ impl UpdateHandler {
                 runtime_handle,
                 flush_rx,
                 shard_path,
+                wal_offload_stager,
+                point_history,
             )
         }));
 
@@ -257,6 +329,11 @@ impl UpdateHandler {
         {
             log::warn!("Failed to stop flush worker as it is already stopped.");
         }
+        if let Some(wal_offload_stop) = self.wal_offload_stop.take()
+            && let Err(()) = wal_offload_stop.send(())
+        {
+            log::warn!("Failed to stop WAL offload worker as it is already stopped.");
+        }
     }
 
     /// Gracefully wait before all optimizations stop
@@ -274,6 +351,10 @@ impl UpdateHandler {
         if let Some(handle) = maybe_handle {
             handle.await?;
         }
+        let maybe_handle = self.wal_offload_worker.take();
+        if let Some(handle) = maybe_handle {
+            handle.await?;
+        }
 
         let mut opt_handles_guard = self.optimization_handles.lock().await;
         let opt_handles = std::mem::take(&mut *opt_handles_guard);
@@ -563,7 +644,9 @@ impl UpdateHandler {
         optimizer_resource_budget: &ResourceBudget,
         sender: Sender<OptimizerSignal>,
         limit: usize,
+        locked_files: Arc<LockedFiles>,
     ) {
+        let optimized_segments = segments.clone();
         let mut new_handles = Self::launch_optimization(
             optimizers.clone(),
             optimizers_log,
@@ -571,6 +654,13 @@ impl UpdateHandler {
             optimizer_resource_budget,
             segments.clone(),
             move |_optimization_result| {
+                // Optimized segments replace locked ones, lock their files instead
+                if locked_files.is_enabled()
+                    && let Err(err) = locked_files.update(warmup_files(&optimized_segments))
+                {
+                    log::error!("Failed to lock files of optimized segments in RAM: {err}");
+                }
+
                 // After optimization is finished, we still need to check if there are
                 // some further optimizations possible.
                 // If receiver is already dead - we do not care.
@@ -632,6 +722,7 @@ impl UpdateHandler {
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
         update_operation_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        locked_files: Arc<LockedFiles>,
     ) {
         let max_handles = max_handles.unwrap_or(usize::MAX);
         let max_indexing_threads = optimizers
@@ -755,11 +846,13 @@ impl UpdateHandler {
                 &optimizer_resource_budget,
                 sender.clone(),
                 limit,
+                locked_files.clone(),
             )
             .await;
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn update_worker_fn(
         collection_name: CollectionId,
         mut receiver: Receiver<UpdateSignal>,
@@ -768,64 +861,40 @@ impl UpdateHandler {
         segments: LockedSegmentHolder,
         update_operation_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
+        update_batch_window: Duration,
+        runtime: Handle,
+        point_history: Option<Arc<PointHistory>>,
+        io_throttle: IoThrottle,
     ) {
-        while let Some(signal) = receiver.blocking_recv() {
-            match signal {
-                UpdateSignal::Operation(OperationData {
-                    op_num,
-                    operation,
-                    sender,
-                    wait,
-                    hw_measurements,
-                }) => {
-                    let flush_res = if wait {
-                        wal.blocking_lock().flush().map_err(|err| {
-                            CollectionError::service_error(format!(
-                                "Can't flush WAL before operation {op_num} - {err}"
-                            ))
-                        })
-                    } else {
-                        Ok(())
-                    };
-
-                    let operation_result = flush_res.and_then(|_| {
-                        let start_time = Instant::now();
-
-                        // This represents the operation without vectors and payloads for logging purposes
-                        // Do not use for anything else
-                        let loggable_operation = operation.remove_details();
+        // Signal received while collecting a batch, which must be handled after the batch
+        let mut pending_signal = None;
 
-                        let result = CollectionUpdater::update(
-                            &segments,
-                            op_num,
-                            operation,
-                            update_operation_lock.clone(),
-                            update_tracker.clone(),
-                            &hw_measurements.get_counter_cell(),
+        while let Some(signal) = pending_signal.take().or_else(|| receiver.blocking_recv()) {
+            match signal {
+                UpdateSignal::Operation(operation) => {
+                    let mut batch = vec![operation];
+                    if !update_batch_window.is_zero() {
+                        pending_signal = Self::collect_update_batch(
+                            &mut receiver,
+                            &mut batch,
+                            update_batch_window,
+                            &runtime,
                         );
+                    }
 
-                        let duration = start_time.elapsed();
-
-                        log_request_to_collector(&collection_name, duration, move || {
-                            loggable_operation
-                        });
-
-                        result
-                    });
-
-                    let res = match operation_result {
-                        Ok(update_res) => optimize_sender
-                            .blocking_send(OptimizerSignal::Operation(op_num))
-                            .and(Ok(update_res))
-                            .map_err(|send_err| send_err.into()),
-                        Err(err) => Err(err),
-                    };
+                    // Give way to searches while they are slow, updates are queued meanwhile
+                    io_throttle.pause();
 
-                    if let Some(feedback) = sender {
-                        feedback.send(res).unwrap_or_else(|_| {
-                            log::debug!("Can't report operation {op_num} result. Assume already not required");
-                        });
-                    };
+                    Self::apply_update_batch(
+                        &collection_name,
+                        batch,
+                        &optimize_sender,
+                        &wal,
+                        &segments,
+                        &update_operation_lock,
+                        &update_tracker,
+                        point_history.as_deref(),
+                    );
                 }
                 UpdateSignal::Stop => {
                     optimize_sender
@@ -853,6 +922,135 @@ impl UpdateHandler {
             .unwrap_or_else(|_| log::debug!("Optimizer already stopped"));
     }
 
+    /// Collect operations received within the batching window into the batch.
+    ///
+    /// Returns other signal, if it was received before the window ended. It must be handled
+    /// right after the batch, to preserve the order of signals.
+    fn collect_update_batch(
+        receiver: &mut Receiver<UpdateSignal>,
+        batch: &mut Vec<OperationData>,
+        update_batch_window: Duration,
+        runtime: &Handle,
+    ) -> Option<UpdateSignal> {
+        let deadline = tokio::time::Instant::now() + update_batch_window;
+        while batch.len() < MAX_UPDATE_BATCH_SIZE {
+            match runtime.block_on(tokio::time::timeout_at(deadline, receiver.recv())) {
+                Ok(Some(UpdateSignal::Operation(operation))) => batch.push(operation),
+                Ok(Some(signal)) => return Some(signal),
+                // Window elapsed, or channel closed, which is handled by the worker loop
+                Ok(None) | Err(Elapsed { .. }) => break,
+            }
+        }
+        None
+    }
+
+    /// Apply a batch of operations in order.
+    ///
+    /// WAL is flushed once for the whole batch, if any operation waits for the result,
+    /// and optimizers are notified once after the batch is applied.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_update_batch(
+        collection_name: &CollectionId,
+        batch: Vec<OperationData>,
+        optimize_sender: &Sender<OptimizerSignal>,
+        wal: &LockedWal,
+        segments: &LockedSegmentHolder,
+        update_operation_lock: &Arc<tokio::sync::RwLock<()>>,
+        update_tracker: &UpdateTracker,
+        point_history: Option<&PointHistory>,
+    ) {
+        let wal_flush_res = if batch.iter().any(|operation| operation.wait) {
+            wal.blocking_lock().flush()
+        } else {
+            Ok(())
+        };
+
+        let mut last_applied_op_num = None;
+        let mut results = Vec::with_capacity(batch.len());
+
+        for OperationData {
+            op_num,
+            operation,
+            timestamp,
+            sender,
+            wait,
+            hw_measurements,
+        } in batch
+        {
+            let flush_res = match &wal_flush_res {
+                Err(err) if wait => Err(CollectionError::service_error(format!(
+                    "Can't flush WAL before operation {op_num} - {err}"
+                ))),
+                _ => Ok(()),
+            };
+
+            let operation_result = flush_res.and_then(|_| {
+                let start_time = Instant::now();
+
+                // This represents the operation without vectors and payloads for logging purposes
+                // Do not use for anything else
+                let loggable_operation = operation.remove_details();
+
+                let point_changes = point_history
+                    .map(|_| PointHistory::pending_changes(&segments.read(), &operation));
+
+                let result = CollectionUpdater::update(
+                    segments,
+                    op_num,
+                    operation,
+                    update_operation_lock.clone(),
+                    update_tracker.clone(),
+                    &hw_measurements.get_counter_cell(),
+                );
+
+                if let (Some(point_history), Some(point_changes)) = (point_history, point_changes) {
+                    let point_changes = if result.is_ok() {
+                        point_changes
+                    } else {
+                        Vec::new()
+                    };
+                    point_history.record(
+                        &segments.read(),
+                        op_num,
+                        operation_timestamp(timestamp),
+                        point_changes,
+                    );
+                }
+
+                let duration = start_time.elapsed();
+
+                log_request_to_collector(collection_name, duration, move || loggable_operation);
+
+                result
+            });
+
+            if operation_result.is_ok() {
+                last_applied_op_num = Some(op_num);
+            }
+            results.push((op_num, sender, operation_result));
+        }
+
+        let optimize_res: CollectionResult<()> = match last_applied_op_num {
+            Some(op_num) => optimize_sender
+                .blocking_send(OptimizerSignal::Operation(op_num))
+                .map_err(|send_err| send_err.into()),
+            None => Ok(()),
+        };
+
+        for (op_num, sender, operation_result) in results {
+            let res =
+                operation_result.and_then(|update_res| optimize_res.clone().map(|()| update_res));
+
+            if let Some(feedback) = sender {
+                feedback.send(res).unwrap_or_else(|_| {
+                    log::debug!(
+                        "Can't report operation {op_num} result. Assume already not required"
+                    );
+                });
+            };
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn flush_worker_fn(
         segments: LockedSegmentHolder,
@@ -863,6 +1061,8 @@ impl UpdateHandler {
         runtime: tokio::runtime::Handle,
         mut stop_receiver: oneshot::Receiver<()>,
         shard_path: PathBuf,
+        mut wal_offload_stager: Option<WalOffloadStager>,
+        point_history: Option<Arc<PointHistory>>,
     ) {
         loop {
             // Stop flush worker on signal or if sender was dropped
@@ -928,11 +1128,32 @@ impl UpdateHandler {
 
             let ack = confirmed_version.min(keep_from.saturating_sub(1));
 
+            // Point history is best-effort, acknowledging WAL does not depend on it
+            if let Some(point_history) = &point_history
+                && let Err(err) = point_history.flush()
+            {
+                log::warn!("Failed to flush point history: {err}");
+            }
+
             if let Err(err) = clocks.store_if_changed(&shard_path) {
                 log::warn!("Failed to store clock maps to disk: {err}");
                 segments.write().report_optimizer_error(err);
             }
 
+            // Closed WAL segments must be handed over for offloading before acknowledging, as
+            // acknowledging may truncate them. Uploading is done by the WAL offload worker.
+            if let Some(wal_offload_stager) = &mut wal_offload_stager {
+                match wal_offload_stager.stage() {
+                    Ok(staged) => {
+                        log::trace!("Handed over {staged} closed WAL segments for offloading");
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to hand over closed WAL segments for offloading: {err}");
+                        continue;
+                    }
+                }
+            }
+
             if let Err(err) = wal.blocking_lock().ack(ack) {
                 log::warn!("Failed to acknowledge WAL version: {err}");
                 segments.write().report_optimizer_error(err);
@@ -940,6 +1161,29 @@ impl UpdateHandler {
         }
     }
 
+    /// Periodically upload closed WAL segments handed over by the flush worker
+    ///
+    /// Failed uploads are retried on the next run, segments stay in the pending directory of the
+    /// shard until then, also across restarts.
+    async fn wal_offload_worker_fn(
+        wal_offloader: WalOffloader,
+        interval_sec: u64,
+        mut stop_receiver: oneshot::Receiver<()>,
+    ) {
+        loop {
+            match wal_offloader.upload_pending().await {
+                Ok(0) => {}
+                Ok(uploaded) => log::debug!("Offloaded {uploaded} closed WAL segments"),
+                Err(err) => log::warn!("Failed to offload closed WAL segments: {err}"),
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(interval_sec)) => {}
+                _ = &mut stop_receiver => return,
+            }
+        }
+    }
+
     /// Returns confirmed version after flush of all segments
     ///
     /// # Errors
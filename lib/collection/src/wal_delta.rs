@@ -11,6 +11,7 @@ use crate::shards::local_shard::clock_map::{ClockMap, RecoveryPoint};
 pub(crate) type LockedWal = Arc<Mutex<SerdeWal<OperationWithClockTag>>>;
 
 /// A WAL that is recoverable, with operations having clock tags and a corresponding clock map.
+#[derive(Clone)]
 pub struct RecoverableWal {
     pub(super) wal: LockedWal,
 
@@ -69,6 +70,26 @@ impl RecoverableWal {
         wal_lock.write(operation).map(|op_num| (op_num, wal_lock))
     }
 
+    /// Update last seen clock map with clock tags of operations, which are written to WAL as a
+    /// single record, and correct clock tags if necessary
+    ///
+    /// Returns whether each operation is accepted. Tags of accepted operations must be stored
+    /// in the record as merged clock tags.
+    pub async fn advance_clocks<'a>(
+        &self,
+        clock_tags: impl IntoIterator<Item = &'a mut Option<ClockTag>>,
+    ) -> Vec<bool> {
+        let mut newest_clocks = self.newest_clocks.lock().await;
+
+        clock_tags
+            .into_iter()
+            .map(|clock_tag| match clock_tag {
+                Some(clock_tag) => newest_clocks.advance_clock_and_correct_tag(clock_tag),
+                None => true,
+            })
+            .collect()
+    }
+
     /// Update the cutoff clock map based on the given recovery point
     ///
     /// This can only increase clock ticks in the cutoff clock map. If there already are higher
@@ -111,7 +132,7 @@ impl RecoverableWal {
                 .lock()
                 .await
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|(op_num, op)| (op_num, op.clock_tags().collect::<Vec<_>>())),
             recovery_point,
             newest_clocks,
             oldest_clocks,
@@ -159,8 +180,8 @@ impl RecoverableWal {
 /// If `Some` - the remote WAL can be recovered by sending the local WAL from that record number.
 /// If `None` - the remote WAL is already equal, and we don't have to send any records.
 /// If `Err` - no delta can be resolved.
-fn resolve_wal_delta(
-    operations: impl DoubleEndedIterator<Item = (u64, Option<ClockTag>)>,
+fn resolve_wal_delta<T: IntoIterator<Item = ClockTag>>(
+    operations: impl DoubleEndedIterator<Item = (u64, T)>,
     mut recovery_point: RecoveryPoint,
     mut newest_clocks: RecoveryPoint,
     mut oldest_clocks: RecoveryPoint,
@@ -212,14 +233,20 @@ fn resolve_wal_delta(
 
     let mut last_op_num = None;
 
-    for (op_num, clock_tag) in operations.rev() {
+    for (op_num, clock_tags) in operations.rev() {
+        let mut clock_tags = clock_tags.into_iter().peekable();
+
         // We cannot resolve a delta if we have untagged records
-        let Some(clock_tag) = clock_tag else {
+        if clock_tags.peek().is_none() {
             return Err(WalDeltaError::UntaggedRecords);
-        };
+        }
 
         // Keep scrolling until we have no clocks left
-        let removed_equal = recovery_point.remove_clock_if_newer_or_equal_to_tag(clock_tag);
+        // A record of merged upserts has a clock tag of each of them
+        let mut removed_equal = false;
+        for clock_tag in clock_tags {
+            removed_equal |= recovery_point.remove_clock_if_newer_or_equal_to_tag(clock_tag);
+        }
 
         if recovery_point.is_empty() {
             // If we only removed newer clocks, delta-ing from the previous record is good enough
@@ -1478,6 +1505,55 @@ mod tests {
         assert_eq!(resolve_result.unwrap_err(), WalDeltaError::UntaggedRecords);
     }
 
+    /// A record of merged upserts resolves a delta for each of its clocks.
+    #[tokio::test]
+    async fn test_resolve_delta_over_merged_record() {
+        let (wal, _wal_dir) = fixture_empty_wal();
+
+        let (_, _) = wal
+            .lock_and_write(&mut OperationWithClockTag::new(
+                mock_operation(1),
+                Some(ClockTag::new(1, 0, 1)),
+            ))
+            .await
+            .unwrap();
+
+        // Merge operations of two clocks into a single record
+        let mut clock_tags = [Some(ClockTag::new(1, 0, 2)), Some(ClockTag::new(1, 1, 1))];
+        let accepted = wal.advance_clocks(clock_tags.iter_mut()).await;
+        assert_eq!(accepted, vec![true, true]);
+
+        let mut operation = OperationWithClockTag::new(mock_operation(2), None);
+        operation.merged_clock_tags = clock_tags.into_iter().flatten().collect();
+        let (_, _) = wal.lock_and_write(&mut operation).await.unwrap();
+
+        let (_, _) = wal
+            .lock_and_write(&mut OperationWithClockTag::new(
+                mock_operation(3),
+                Some(ClockTag::new(1, 0, 3)),
+            ))
+            .await
+            .unwrap();
+
+        // Recovery point has not seen the merged record, recover from it
+        let mut recovery_point = RecoveryPoint::default();
+        recovery_point.insert(1, 0, 1);
+        let resolve_result = wal.resolve_wal_delta(recovery_point).await.unwrap();
+        assert_eq!(resolve_result, Some(1));
+
+        // Recovery point has seen the merged record, recover after it
+        let mut recovery_point = RecoveryPoint::default();
+        recovery_point.insert(1, 0, 2);
+        recovery_point.insert(1, 1, 1);
+        let resolve_result = wal.resolve_wal_delta(recovery_point).await.unwrap();
+        assert_eq!(resolve_result, Some(2));
+
+        // Clock tags, which are already seen, are rejected and not merged
+        let mut clock_tags = [Some(ClockTag::new(1, 0, 2))];
+        let accepted = wal.advance_clocks(clock_tags.iter_mut()).await;
+        assert_eq!(accepted, vec![false]);
+    }
+
     /// Empty recovery point should not resolve any diff.
     #[test]
     fn test_empty_recovery_point() {
@@ -107,6 +107,7 @@ async fn test_collection_payload_reloading_with_shards(shard_number: u32) {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -190,6 +191,7 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Fields(vec![JsonPath::new("k2")])),
@@ -229,6 +231,7 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(PayloadSelectorExclude::new(vec![JsonPath::new("k1")]).into()),
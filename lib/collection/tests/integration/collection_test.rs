@@ -453,6 +453,7 @@ async fn test_read_api_with_shards(shard_number: u32) {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(2),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -588,6 +589,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
             .scroll_by(
                 ScrollRequestInternal {
                     offset: None,
+                    after_id: None,
                     limit: Some(3),
                     filter: None,
                     with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -620,6 +622,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
             .scroll_by(
                 ScrollRequestInternal {
                     offset: None,
+                    after_id: None,
                     limit: Some(5),
                     filter: None,
                     with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -659,6 +662,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
             .scroll_by(
                 ScrollRequestInternal {
                     offset: None,
+                    after_id: None,
                     limit: Some(5),
                     filter: Some(Filter::new_must_not(Condition::HasId(
                         HasIdCondition::from(asc_already_seen),
@@ -699,6 +703,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
             .scroll_by(
                 ScrollRequestInternal {
                     offset: None,
+                    after_id: None,
                     limit: Some(4),
                     filter: Some(Filter::new_must_not(Condition::HasId(
                         HasIdCondition::from(desc_already_seen),
@@ -742,6 +747,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(100),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -844,6 +850,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
         .scroll_by(
             ScrollRequestInternal {
                 offset: None,
+                after_id: None,
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
@@ -39,6 +39,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let collection_params = CollectionParams {
@@ -56,6 +58,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -39,6 +39,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
         wal_retain_closed: 1,
+        wal_batch_window_ms: 0,
+        wal_point_history: 0,
     };
 
     let vector_params1 = VectorParamsBuilder::new(4, Distance::Dot).build();
@@ -64,6 +66,7 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        custom_metric: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -6,6 +6,7 @@ use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
 use tokio::time;
 
 use crate::cpu;
+use crate::search_pressure::{IoPriority, IoThrottle, SearchPressure};
 
 /// Get IO budget to use for optimizations as number of parallel IO operations.
 pub fn get_io_budget(io_budget: usize, cpu_budget: usize) -> usize {
@@ -31,6 +32,13 @@ pub struct ResourceBudget {
     io_semaphore: Arc<Semaphore>,
     /// Total IO budget, available and leased out.
     io_budget: usize,
+
+    /// Search latency tracker, used to throttle background IO while searches are slow.
+    search_pressure: Arc<SearchPressure>,
+    /// Priority of IO of optimizations relative to searches.
+    optimizer_io_priority: IoPriority,
+    /// Priority of IO of updates relative to searches.
+    update_io_priority: IoPriority,
 }
 
 impl ResourceBudget {
@@ -40,9 +48,42 @@ impl ResourceBudget {
             cpu_budget,
             io_semaphore: Arc::new(Semaphore::new(io_budget)),
             io_budget,
+            search_pressure: Arc::new(SearchPressure::default()),
+            optimizer_io_priority: IoPriority::default(),
+            update_io_priority: IoPriority::default(),
         }
     }
 
+    /// Throttle IO of low priority while p99 search latency is above the given threshold.
+    ///
+    /// If `None`, IO is never throttled based on search latency.
+    pub fn with_io_throttling(
+        mut self,
+        threshold: Option<Duration>,
+        optimizer_io_priority: IoPriority,
+        update_io_priority: IoPriority,
+    ) -> Self {
+        self.search_pressure = Arc::new(SearchPressure::new(threshold));
+        self.optimizer_io_priority = optimizer_io_priority;
+        self.update_io_priority = update_io_priority;
+        self
+    }
+
+    /// Register the latency of a finished search, to be considered for IO throttling.
+    pub fn observe_search_latency(&self, latency: Duration) {
+        self.search_pressure.observe(latency);
+    }
+
+    /// Throttle of IO of running optimizations.
+    pub fn optimizer_io_throttle(&self) -> IoThrottle {
+        IoThrottle::new(self.search_pressure.clone(), self.optimizer_io_priority)
+    }
+
+    /// Throttle of IO of applying updates.
+    pub fn update_io_throttle(&self) -> IoThrottle {
+        IoThrottle::new(self.search_pressure.clone(), self.update_io_priority)
+    }
+
     /// Returns the total CPU budget.
     pub fn available_cpu_budget(&self) -> usize {
         self.cpu_budget
@@ -91,6 +132,12 @@ impl ResourceBudget {
 
     fn try_acquire_io(&self, desired_io: usize) -> Option<(usize, Option<OwnedSemaphorePermit>)> {
         let min_required_io = self.min_io_permits(desired_io) as u32;
+        // While searches are slow, only hand out the minimal number of IO permits
+        let desired_io = if self.optimizer_io_throttle().is_throttled() {
+            min_required_io as usize
+        } else {
+            desired_io
+        };
         let num_io = self.io_semaphore.available_permits().min(desired_io) as u32;
         if num_io < min_required_io {
             return None;
@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use super::hardware_counter::HardwareCounterCell;
 use super::hardware_data::HardwareData;
@@ -104,6 +104,9 @@ pub struct HwMeasurementAcc {
     metrics_drain: HwSharedDrain,
     /// If this is set to true, the accumulator will not accumulate any values.
     disposable: bool,
+    /// Set if any search of the request stopped early, because it ran out of its
+    /// `max_scored_points` budget.
+    search_truncated: Arc<AtomicBool>,
 }
 
 impl HwMeasurementAcc {
@@ -113,6 +116,7 @@ impl HwMeasurementAcc {
             request_drain: HwSharedDrain::default(),
             metrics_drain: HwSharedDrain::default(),
             disposable: false,
+            search_truncated: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -124,6 +128,7 @@ impl HwMeasurementAcc {
             request_drain: HwSharedDrain::default(),
             metrics_drain: HwSharedDrain::default(),
             disposable: true,
+            search_truncated: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -141,6 +146,7 @@ impl HwMeasurementAcc {
             request_drain: HwSharedDrain::default(),
             metrics_drain,
             disposable: false,
+            search_truncated: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -158,6 +164,16 @@ impl HwMeasurementAcc {
         self.request_drain.accumulate_from_hw_data(src);
     }
 
+    pub fn mark_search_truncated(&self) {
+        self.search_truncated.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether results of the request may be incomplete, because some search ran out of
+    /// its scoring budget.
+    pub fn is_search_truncated(&self) -> bool {
+        self.search_truncated.load(Ordering::Relaxed)
+    }
+
     pub fn get_cpu(&self) -> usize {
         self.request_drain.get_cpu()
     }
@@ -222,6 +238,7 @@ impl Clone for HwMeasurementAcc {
             request_drain: self.request_drain.clone(),
             metrics_drain: self.metrics_drain.clone(),
             disposable: self.disposable,
+            search_truncated: self.search_truncated.clone(),
         }
     }
 }
@@ -93,6 +93,13 @@ impl HardwareCounterCell {
             .unwrap_or_else(HwMeasurementAcc::disposable)
     }
 
+    /// Report to the parent accumulator that a search stopped early on its scoring budget.
+    pub fn mark_search_truncated(&self) {
+        if let Some(accumulator) = &self.accumulator {
+            accumulator.mark_search_truncated();
+        }
+    }
+
     /// Create a copy of the current counter cell with the same accumulator and config,
     /// but with empty counter.
     /// Allows independent counting within different segments.
@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Marker of [`DecayingAverage::updated_micros`] for an average without samples.
+const NO_SAMPLES: u64 = u64::MAX;
+
+/// Exponentially weighted moving average, which also decays towards zero over time.
+///
+/// Each sample moves the average towards it by the smoothing factor. In between samples the
+/// average halves every `half_life`, so it goes down once samples stop coming, instead of
+/// keeping the value of the last busy period forever.
+///
+/// Updates are racy read-modify-writes, concurrent samples may be lost. This is fine for the
+/// approximate averages it is used for, and keeps observing samples lock-free.
+#[derive(Debug)]
+pub struct DecayingAverage {
+    /// Weight of the latest sample
+    smoothing: f64,
+    half_life: Duration,
+    /// Reference point of [`DecayingAverage::updated_micros`]
+    created: Instant,
+    /// Average at the time of the last update, stored as `f64` bits
+    value: AtomicU64,
+    /// Time of the last update since creation, in microseconds
+    updated_micros: AtomicU64,
+}
+
+impl DecayingAverage {
+    /// New average without samples, the first sample is taken as is.
+    pub fn new(smoothing: f64, half_life: Duration) -> Self {
+        Self {
+            smoothing,
+            half_life,
+            created: Instant::now(),
+            value: AtomicU64::new(0f64.to_bits()),
+            updated_micros: AtomicU64::new(NO_SAMPLES),
+        }
+    }
+
+    /// Start from the given value instead, so the first sample is smoothed as well.
+    pub fn with_initial(self, value: f64) -> Self {
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+        self.updated_micros.store(0, Ordering::Relaxed);
+        self
+    }
+
+    /// Register a new sample.
+    pub fn observe(&self, sample: f64) {
+        self.observe_at(sample, Instant::now());
+    }
+
+    /// Current average, `None` if there are no samples yet.
+    pub fn get(&self) -> Option<f64> {
+        self.get_at(Instant::now())
+    }
+
+    fn observe_at(&self, sample: f64, now: Instant) {
+        let value = match self.get_at(now) {
+            Some(current) => current + self.smoothing * (sample - current),
+            None => sample,
+        };
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+        self.updated_micros
+            .store(self.micros_since_created(now), Ordering::Relaxed);
+    }
+
+    fn get_at(&self, now: Instant) -> Option<f64> {
+        let updated_micros = self.updated_micros.load(Ordering::Relaxed);
+        if updated_micros == NO_SAMPLES {
+            return None;
+        }
+
+        let value = f64::from_bits(self.value.load(Ordering::Relaxed));
+        let elapsed_micros = self
+            .micros_since_created(now)
+            .saturating_sub(updated_micros);
+        let half_lives = elapsed_micros as f64 / self.half_life.as_micros().max(1) as f64;
+        Some(value * 0.5f64.powf(half_lives))
+    }
+
+    fn micros_since_created(&self, now: Instant) -> u64 {
+        let micros = now.saturating_duration_since(self.created).as_micros();
+        // Leave `NO_SAMPLES` as a marker
+        micros.min(u128::from(NO_SAMPLES - 1)) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decaying_average() {
+        let average = DecayingAverage::new(0.5, Duration::from_secs(10));
+        let now = average.created;
+        assert_eq!(average.get_at(now), None);
+
+        average.observe_at(100.0, now);
+        assert_eq!(average.get_at(now), Some(100.0));
+
+        average.observe_at(200.0, now);
+        assert_eq!(average.get_at(now), Some(150.0));
+
+        // Halves every half-life without samples
+        assert_eq!(average.get_at(now + Duration::from_secs(10)), Some(75.0));
+        assert_eq!(average.get_at(now + Duration::from_secs(20)), Some(37.5));
+
+        // Samples are applied to the decayed average
+        average.observe_at(25.0, now + Duration::from_secs(20));
+        assert_eq!(average.get_at(now + Duration::from_secs(20)), Some(31.25));
+    }
+
+    #[test]
+    fn test_decaying_average_with_initial() {
+        let average = DecayingAverage::new(0.5, Duration::from_secs(10)).with_initial(0.0);
+        let now = average.created;
+        assert_eq!(average.get_at(now), Some(0.0));
+
+        average.observe_at(100.0, now);
+        assert_eq!(average.get_at(now), Some(50.0));
+    }
+}
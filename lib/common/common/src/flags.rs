@@ -59,6 +59,15 @@ pub struct FeatureFlags {
     /// Use appendable quantization in appendable plain segments.
     // TODO(1.16.0): enable by default
     pub appendable_quantization: bool,
+
+    /// Store checksums of immutable segment files, when segments are built.
+    pub segment_checksums: bool,
+
+    /// Verify stored checksums of segment files when loading segments.
+    ///
+    /// Segments with mismatching files fail to load with a corruption error. All files of each
+    /// segment are read in full, which slows down startup.
+    pub verify_segment_checksums: bool,
 }
 
 impl Default for FeatureFlags {
@@ -74,6 +83,8 @@ impl Default for FeatureFlags {
             migrate_rocksdb_payload_storage: false,
             migrate_rocksdb_payload_indices: false,
             appendable_quantization: false,
+            segment_checksums: false,
+            verify_segment_checksums: false,
         }
     }
 }
@@ -99,6 +110,8 @@ pub fn init_feature_flags(mut flags: FeatureFlags) {
         migrate_rocksdb_payload_storage,
         migrate_rocksdb_payload_indices,
         appendable_quantization,
+        segment_checksums,
+        verify_segment_checksums,
     } = &mut flags;
 
     // If all is set, explicitly set all feature flags
@@ -112,6 +125,8 @@ pub fn init_feature_flags(mut flags: FeatureFlags) {
         *migrate_rocksdb_payload_storage = true;
         *migrate_rocksdb_payload_indices = true;
         *appendable_quantization = true;
+        *segment_checksums = true;
+        *verify_segment_checksums = true;
     }
 
     let res = FEATURE_FLAGS.set(flags);
@@ -6,6 +6,7 @@ pub mod bytes;
 pub mod counter;
 pub mod cow;
 pub mod cpu;
+pub mod decaying_average;
 pub mod defaults;
 pub mod delta_pack;
 pub mod disk;
@@ -21,6 +22,7 @@ pub mod num_traits;
 pub mod panic;
 pub mod rate_limiting;
 pub mod save_on_disk;
+pub mod search_pressure;
 pub mod small_uint;
 pub mod stable_hash;
 pub mod tar_ext;
@@ -0,0 +1,277 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Percentile of search latency compared to the threshold.
+const LATENCY_PERCENTILE: f64 = 0.99;
+
+/// Number of one-second slots in the window of search latencies.
+const WINDOW_SLOTS: usize = 10;
+
+/// Tail latency of fewer searches within the window is not reliable, and is not considered.
+const MIN_WINDOW_SAMPLES: u32 = 20;
+
+/// Number of latency buckets per doubling of latency, gives about 19% precision.
+const BUCKETS_PER_DOUBLING: usize = 4;
+
+/// Latency buckets up to about 19 hours in microseconds.
+const LATENCY_BUCKETS: usize = 36 * BUCKETS_PER_DOUBLING;
+
+/// Longest single pause of throttled IO, so throttled work keeps making progress.
+const MAX_THROTTLE_PAUSE: Duration = Duration::from_millis(500);
+
+/// Interval of checking whether throttled IO can continue.
+const THROTTLE_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Priority of background IO relative to searches.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IoPriority {
+    /// Throttled while search tail latency is above the threshold
+    #[default]
+    Low,
+    /// Never throttled in favor of searches
+    High,
+}
+
+/// Tracks search latency to detect when searches are suffering from background IO.
+///
+/// Latencies of the last [`WINDOW_SLOTS`] seconds are counted in a histogram, and the pressure
+/// is high while their 99th percentile is above the threshold. A few slow searches do not
+/// trigger throttling, while a slow tail does. Old latencies leave the window, so throttling
+/// stops once the node is idle.
+/// If no threshold is configured, the search pressure is never considered high.
+#[derive(Debug)]
+pub struct SearchPressure {
+    /// Tail search latency threshold above which background IO should be throttled.
+    threshold: Option<Duration>,
+    /// Reference point of the window slots.
+    created: Instant,
+    window: Mutex<[WindowSlot; WINDOW_SLOTS]>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WindowSlot {
+    /// Second since creation, which is counted in this slot.
+    second: u64,
+    /// Number of searches per latency bucket.
+    counts: [u32; LATENCY_BUCKETS],
+}
+
+impl SearchPressure {
+    pub fn new(threshold: Option<Duration>) -> Self {
+        let slot = WindowSlot {
+            second: 0,
+            counts: [0; LATENCY_BUCKETS],
+        };
+        Self {
+            threshold,
+            created: Instant::now(),
+            window: Mutex::new([slot; WINDOW_SLOTS]),
+        }
+    }
+
+    /// Register the latency of a finished search.
+    pub fn observe(&self, latency: Duration) {
+        if self.threshold.is_none() {
+            return;
+        }
+
+        self.observe_at(latency, Instant::now());
+    }
+
+    /// 99th percentile of search latency within the window.
+    ///
+    /// `None` if there are too few searches to tell.
+    pub fn tail_latency(&self) -> Option<Duration> {
+        self.tail_latency_at(Instant::now())
+    }
+
+    /// Whether searches are currently slower than the configured threshold.
+    pub fn is_high(&self) -> bool {
+        self.is_high_at(Instant::now())
+    }
+
+    fn observe_at(&self, latency: Duration, now: Instant) {
+        let second = self.second_at(now);
+        let mut window = self.window.lock();
+        let slot = &mut window[second as usize % WINDOW_SLOTS];
+        // Slot is reused for a new second
+        if slot.second < second {
+            slot.second = second;
+            slot.counts = [0; LATENCY_BUCKETS];
+        }
+        slot.counts[latency_bucket(latency)] += 1;
+    }
+
+    fn tail_latency_at(&self, now: Instant) -> Option<Duration> {
+        let second = self.second_at(now);
+        let mut counts = [0u32; LATENCY_BUCKETS];
+        for slot in self.window.lock().iter() {
+            if second.saturating_sub(slot.second) < WINDOW_SLOTS as u64 {
+                for (count, slot_count) in counts.iter_mut().zip(slot.counts) {
+                    *count += slot_count;
+                }
+            }
+        }
+
+        let total: u32 = counts.iter().sum();
+        if total < MIN_WINDOW_SAMPLES {
+            return None;
+        }
+
+        let rank = (f64::from(total) * LATENCY_PERCENTILE).ceil() as u32;
+        let mut seen = 0;
+        let bucket = counts.iter().position(|&count| {
+            seen += count;
+            seen >= rank
+        })?;
+        Some(bucket_upper_bound(bucket))
+    }
+
+    fn is_high_at(&self, now: Instant) -> bool {
+        let Some(threshold) = self.threshold else {
+            return false;
+        };
+        self.tail_latency_at(now)
+            .is_some_and(|latency| latency > threshold)
+    }
+
+    fn second_at(&self, now: Instant) -> u64 {
+        // Window slots start from the first second, so unused slots are never in the window
+        now.saturating_duration_since(self.created).as_secs() + WINDOW_SLOTS as u64
+    }
+}
+
+impl Default for SearchPressure {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+fn latency_bucket(latency: Duration) -> usize {
+    let micros = latency.as_micros().max(1) as f64;
+    let bucket = (micros.log2() * BUCKETS_PER_DOUBLING as f64) as usize;
+    bucket.min(LATENCY_BUCKETS - 1)
+}
+
+fn bucket_upper_bound(bucket: usize) -> Duration {
+    let micros = 2f64.powf((bucket + 1) as f64 / BUCKETS_PER_DOUBLING as f64);
+    Duration::from_micros(micros as u64)
+}
+
+/// Throttles background IO of the given priority while search pressure is high.
+#[derive(Debug, Clone)]
+pub struct IoThrottle {
+    search_pressure: Arc<SearchPressure>,
+    priority: IoPriority,
+}
+
+impl IoThrottle {
+    pub fn new(search_pressure: Arc<SearchPressure>, priority: IoPriority) -> Self {
+        Self {
+            search_pressure,
+            priority,
+        }
+    }
+
+    /// Throttle that never pauses.
+    pub fn disabled() -> Self {
+        Self::new(Default::default(), IoPriority::High)
+    }
+
+    /// Whether IO of this priority should currently give way to searches.
+    pub fn is_throttled(&self) -> bool {
+        match self.priority {
+            IoPriority::Low => self.search_pressure.is_high(),
+            IoPriority::High => false,
+        }
+    }
+
+    /// Block while IO is throttled, for at most [`MAX_THROTTLE_PAUSE`].
+    ///
+    /// Meant to be called between chunks of IO-heavy work, to spread it out while searches are
+    /// slow. The pause is bounded, so the work is slowed down but never stalled.
+    pub fn pause(&self) {
+        let start = Instant::now();
+        while self.is_throttled() && start.elapsed() < MAX_THROTTLE_PAUSE {
+            std::thread::sleep(THROTTLE_CHECK_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_pressure() {
+        let pressure = SearchPressure::new(Some(Duration::from_millis(100)));
+        let now = pressure.created;
+        assert!(!pressure.is_high_at(now));
+
+        // Few slow searches are not reliable enough
+        for _ in 0..MIN_WINDOW_SAMPLES - 1 {
+            pressure.observe_at(Duration::from_millis(500), now);
+        }
+        assert!(!pressure.is_high_at(now));
+
+        // Slow tail raises the pressure, even if most searches are fast
+        for _ in 0..200 {
+            pressure.observe_at(Duration::from_millis(1), now);
+        }
+        assert!(pressure.is_high_at(now));
+
+        // Fast searches push slow ones out of the tail
+        for _ in 0..2000 {
+            pressure.observe_at(Duration::from_millis(1), now);
+        }
+        assert!(!pressure.is_high_at(now));
+        let tail_latency = pressure.tail_latency_at(now).unwrap();
+        assert!(tail_latency >= Duration::from_millis(1));
+        assert!(tail_latency < Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_search_pressure_window() {
+        let pressure = SearchPressure::new(Some(Duration::from_millis(100)));
+        let now = pressure.created;
+
+        for _ in 0..100 {
+            pressure.observe_at(Duration::from_millis(500), now);
+        }
+        assert!(pressure.is_high_at(now));
+
+        let later = now + Duration::from_secs(WINDOW_SLOTS as u64 - 1);
+        assert!(pressure.is_high_at(later));
+
+        // Slow searches leave the window while the node is idle
+        let idle = now + Duration::from_secs(WINDOW_SLOTS as u64);
+        assert!(!pressure.is_high_at(idle));
+        assert_eq!(pressure.tail_latency_at(idle), None);
+    }
+
+    #[test]
+    fn test_search_pressure_disabled() {
+        let pressure = SearchPressure::default();
+        for _ in 0..50 {
+            pressure.observe(Duration::from_secs(10));
+        }
+        assert!(!pressure.is_high());
+        assert_eq!(pressure.tail_latency(), None);
+    }
+
+    #[test]
+    fn test_io_throttle_priority() {
+        let pressure = Arc::new(SearchPressure::new(Some(Duration::from_millis(100))));
+        for _ in 0..100 {
+            pressure.observe(Duration::from_millis(500));
+        }
+
+        assert!(IoThrottle::new(pressure.clone(), IoPriority::Low).is_throttled());
+        assert!(!IoThrottle::new(pressure, IoPriority::High).is_throttled());
+        assert!(!IoThrottle::disabled().is_throttled());
+    }
+}
@@ -211,6 +211,42 @@ pub fn will_need_multiple_pages(region: &[u8]) {
 #[cfg(not(unix))]
 pub fn will_need_multiple_pages(_region: &[u8]) {}
 
+/// Advise the OS to back a memory region with transparent huge pages by calling
+/// `madvise(MADV_HUGEPAGE)` on it.
+///
+/// Only the pages fully covered by the region are advised, so the region should span
+/// at least a few megabytes to benefit from it. On non-Linux platforms this is a no-op.
+#[cfg(target_os = "linux")]
+pub fn advise_huge_pages(addr: *const u8, length: usize) {
+    let Some(page_mask) = *PAGE_SIZE_MASK else {
+        return;
+    };
+
+    // `madvise()` requires the address to be page-aligned, round it up to stay inside the region.
+    let start = (addr.addr() + page_mask) & !page_mask;
+    let end = (addr.addr() + length) & !page_mask;
+    if end <= start {
+        return;
+    }
+
+    // Safety: madvise(MADV_HUGEPAGE) only changes how the kernel backs the pages, it does not
+    // change their content. Invalid ranges result in an error, not in an undefined behavior.
+    let res = unsafe {
+        nix::libc::madvise(
+            addr.with_addr(start) as *mut _,
+            end - start,
+            nix::libc::MADV_HUGEPAGE,
+        )
+    };
+    if res != 0 {
+        let err = io::Error::last_os_error();
+        log::debug!("Failed to call madvise(MADV_HUGEPAGE): {err}");
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn advise_huge_pages(_addr: *const u8, _length: usize) {}
+
 /// Page size mask. Typically 0xfff for 4KiB pages.
 #[cfg(unix)]
 static PAGE_SIZE_MASK: std::sync::LazyLock<Option<usize>> =
@@ -61,6 +61,40 @@ pub fn open_read_mmap(path: &Path, advice: AdviceSetting, populate: bool) -> io:
     Ok(mmap)
 }
 
+/// Read the file into the page cache
+///
+/// Unlike [`open_read_mmap`], the file is opened read-only and is never created.
+pub fn populate_file(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().read(true).open(path)?;
+
+    // Empty files can't be mapped
+    if file.metadata()?.len() == 0 {
+        return Ok(());
+    }
+
+    let mmap = unsafe { Mmap::map(&file)? };
+    mmap.populate();
+
+    Ok(())
+}
+
+/// Map the file read-only and lock its pages in RAM
+///
+/// Pages stay locked while the returned map is alive. Locking fails if the file doesn't fit into
+/// the amount of memory the process is allowed to lock (`RLIMIT_MEMLOCK`).
+pub fn open_locked_mmap(path: &Path) -> io::Result<Mmap> {
+    let file = OpenOptions::new().read(true).open(path)?;
+
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    #[cfg(unix)]
+    mmap.lock()?;
+    #[cfg(not(unix))]
+    log::debug!("Ignore locking of {} on this platform", path.display());
+
+    Ok(mmap)
+}
+
 pub fn open_write_mmap(path: &Path, advice: AdviceSetting, populate: bool) -> io::Result<MmapMut> {
     let file = OpenOptions::new().read(true).write(true).open(path)?;
 
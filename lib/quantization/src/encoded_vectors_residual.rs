@@ -0,0 +1,496 @@
+use std::alloc::Layout;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::typelevel::True;
+use common::types::PointOffsetType;
+use io::file_operations::atomic_save_json;
+use memory::mmap_type::MmapFlusher;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::EncodingError;
+use crate::encoded_storage::{EncodedStorage, EncodedStorageBuilder};
+use crate::encoded_vectors::{
+    DistanceType, EncodedVectors, VectorParameters, validate_vector_parameters,
+};
+use crate::encoded_vectors_pq::{
+    CENTROIDS_COUNT, KMEANS_ACCURACY, KMEANS_MAX_ITERATIONS, KMEANS_SAMPLE_SIZE,
+};
+use crate::kmeans::kmeans;
+
+/// Number of levels of quantized residual values
+const RESIDUAL_LEVELS: f32 = 255.0;
+
+/// Size of the encoded vector header: index of the coarse centroid and squared norm of the
+/// reconstructed vector
+const HEADER_SIZE: usize = size_of::<u8>() + size_of::<f32>();
+
+/// Number of vectors encoded in parallel at once
+const ENCODING_BATCH_SIZE: usize = 1024;
+
+/// Residual quantization
+///
+/// Each vector is encoded as the index of its nearest coarse centroid plus the residual from
+/// that centroid, quantized to `u8` per dimension. Residuals have a much smaller range than
+/// original values, so they are quantized more precisely than with plain scalar quantization.
+pub struct EncodedVectorsResidual<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    metadata: Metadata,
+    metadata_path: Option<PathBuf>,
+}
+
+pub struct EncodedQueryResidual {
+    query: Vec<f32>,
+    /// Dot product of the query with each centroid
+    centroid_products: Vec<f32>,
+    /// Dot product of the query with a residual of all zero codes
+    offset_product: f32,
+    /// Squared norm of the query
+    norm_sq: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Metadata {
+    pub centroids: Vec<Vec<f32>>,
+    /// Scale of quantized residual values
+    pub alpha: f32,
+    /// Minimal residual value
+    pub offset: f32,
+    pub vector_parameters: VectorParameters,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsResidual<TStorage> {
+    pub fn storage(&self) -> &TStorage {
+        &self.encoded_vectors
+    }
+
+    /// Encode vector data using residual quantization.
+    ///
+    /// # Arguments
+    /// * `data` - iterator over original vector data
+    /// * `storage_builder` - encoding result storage builder
+    /// * `vector_parameters` - parameters of original vector data (dimension, distance, etc)
+    /// * `count` - number of vectors in `data`
+    /// * `max_threads` - Max allowed threads for kmeans and encoding process
+    /// * `stopped` - Atomic bool that indicates if encoding should be stopped
+    pub fn encode<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + Send + Sync + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage>,
+        vector_parameters: &VectorParameters,
+        count: usize,
+        max_threads: usize,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        debug_assert!(validate_vector_parameters(data.clone(), vector_parameters).is_ok());
+        let max_threads = max_threads.max(1);
+
+        let sample = Self::sample_indices(count);
+        let centroids = Self::find_centroids(
+            data.clone(),
+            &sample,
+            vector_parameters.dim,
+            count,
+            max_threads,
+            stopped,
+        )?;
+        let (alpha, offset) = Self::find_residual_range(data.clone(), &sample, &centroids);
+
+        let metadata = Metadata {
+            centroids,
+            alpha,
+            offset,
+            vector_parameters: vector_parameters.clone(),
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("residual-encoding-{idx}"))
+            .num_threads(max_threads)
+            .build()
+            .map_err(|e| {
+                EncodingError::EncodingError(format!(
+                    "Failed residual encoding while thread pool init: {e}"
+                ))
+            })?;
+        let mut data = data.peekable();
+        while data.peek().is_some() {
+            if stopped.load(Ordering::Relaxed) {
+                return Err(EncodingError::Stopped);
+            }
+            let batch: Vec<_> = data.by_ref().take(ENCODING_BATCH_SIZE).collect();
+            let encoded: Vec<Vec<u8>> = pool.install(|| {
+                batch
+                    .par_iter()
+                    .map(|vector| metadata.encode_vector(vector.as_ref()))
+                    .collect()
+            });
+            for encoded_vector in &encoded {
+                storage_builder
+                    .push_vector_data(encoded_vector)
+                    .map_err(|e| {
+                        EncodingError::EncodingError(format!("Failed to push encoded vector: {e}",))
+                    })?;
+            }
+        }
+
+        let encoded_vectors = storage_builder
+            .build()
+            .map_err(|e| EncodingError::EncodingError(format!("Failed to build storage: {e}",)))?;
+
+        if let Some(meta_path) = meta_path {
+            meta_path
+                .parent()
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Path must have a parent directory",
+                    )
+                })
+                .and_then(std::fs::create_dir_all)
+                .map_err(|e| {
+                    EncodingError::EncodingError(format!(
+                        "Failed to create metadata directory: {e}",
+                    ))
+                })?;
+            atomic_save_json(meta_path, &metadata).map_err(|e| {
+                EncodingError::EncodingError(format!("Failed to save metadata: {e}",))
+            })?;
+        }
+
+        Ok(Self {
+            encoded_vectors,
+            metadata,
+            metadata_path: meta_path.map(PathBuf::from),
+        })
+    }
+
+    pub fn load(encoded_vectors: TStorage, meta_path: &Path) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(meta_path)?;
+        let metadata: Metadata = serde_json::from_str(&contents)?;
+        let result = Self {
+            encoded_vectors,
+            metadata,
+            metadata_path: Some(meta_path.to_path_buf()),
+        };
+        Ok(result)
+    }
+
+    pub fn get_quantized_vector_size(vector_parameters: &VectorParameters) -> usize {
+        HEADER_SIZE + vector_parameters.dim
+    }
+
+    /// Sorted indexes of vectors, used to train centroids and the residual range
+    fn sample_indices(count: usize) -> Vec<usize> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let sample_size = KMEANS_SAMPLE_SIZE.min(count);
+        let permutor = permutation_iterator::Permutor::new(count as u64);
+        let mut sample: Vec<usize> = permutor.map(|i| i as usize).take(sample_size).collect();
+        sample.sort_unstable();
+        sample
+    }
+
+    fn sampled<T>(data: impl Iterator<Item = T>, sample: &[usize]) -> impl Iterator<Item = T> {
+        let mut sample = sample.iter().peekable();
+        data.enumerate().filter_map(move |(index, vector)| {
+            sample.next_if_eq(&&index)?;
+            Some(vector)
+        })
+    }
+
+    fn find_centroids<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        sample: &[usize],
+        dim: usize,
+        count: usize,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> Result<Vec<Vec<f32>>, EncodingError> {
+        // if there are not enough vectors, use them as centroids
+        if count <= CENTROIDS_COUNT {
+            return Ok(data.map(|vector| vector.as_ref().to_vec()).collect());
+        }
+
+        let mut data_subset = Vec::with_capacity(sample.len() * dim);
+        for vector in Self::sampled(data, sample) {
+            data_subset.extend_from_slice(vector.as_ref());
+        }
+
+        let centroids = kmeans(
+            &data_subset,
+            CENTROIDS_COUNT,
+            dim,
+            KMEANS_MAX_ITERATIONS,
+            max_threads,
+            KMEANS_ACCURACY,
+            stopped,
+        )?;
+        Ok(centroids
+            .chunks_exact(dim)
+            .map(|centroid| centroid.to_vec())
+            .collect())
+    }
+
+    /// Find scale and offset of quantized residuals from sampled vectors
+    ///
+    /// Residuals of vectors outside of the sample may exceed the range, they are clipped.
+    fn find_residual_range<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        sample: &[usize],
+        centroids: &[Vec<f32>],
+    ) -> (f32, f32) {
+        let (min, max) =
+            Self::sampled(data, sample).fold((f32::MAX, f32::MIN), |(min, max), vector| {
+                let vector = vector.as_ref();
+                let centroid = &centroids[nearest_centroid(vector, centroids)];
+                vector
+                    .iter()
+                    .zip(centroid)
+                    .map(|(value, center)| value - center)
+                    .fold((min, max), |(min, max), residual| {
+                        (min.min(residual), max.max(residual))
+                    })
+            });
+        if min < max {
+            ((max - min) / RESIDUAL_LEVELS, min)
+        } else {
+            // all residuals are equal or there are no vectors
+            (1.0, min.min(0.0))
+        }
+    }
+
+    fn decode_vector(&self, encoded: &[u8]) -> Vec<f32> {
+        let (centroid_index, _, codes) = split_encoded(encoded);
+        let centroid = &self.metadata.centroids[centroid_index];
+        centroid
+            .iter()
+            .zip(codes)
+            .map(|(center, &code)| {
+                center + self.metadata.offset + self.metadata.alpha * f32::from(code)
+            })
+            .collect()
+    }
+
+    pub fn get_quantized_vector(&self, i: PointOffsetType) -> &[u8] {
+        self.encoded_vectors.get_vector_data(i)
+    }
+
+    pub fn layout(&self) -> Layout {
+        Layout::from_size_align(self.quantized_vector_size(), align_of::<u8>()).unwrap()
+    }
+
+    pub fn get_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    fn postprocess(&self, score: f32) -> f32 {
+        if self.metadata.vector_parameters.invert {
+            -score
+        } else {
+            score
+        }
+    }
+}
+
+impl Metadata {
+    /// Encode vector as `[centroid index: u8][reconstructed squared norm: f32][codes: u8 * dim]`
+    fn encode_vector(&self, vector: &[f32]) -> Vec<u8> {
+        let centroid_index = nearest_centroid(vector, &self.centroids);
+        let centroid = &self.centroids[centroid_index];
+
+        let mut norm_sq = 0.0;
+        let codes: Vec<u8> = vector
+            .iter()
+            .zip(centroid)
+            .map(|(value, center)| {
+                let code = ((value - center - self.offset) / self.alpha)
+                    .round()
+                    .clamp(0.0, RESIDUAL_LEVELS);
+                let reconstructed = center + self.offset + self.alpha * code;
+                norm_sq += reconstructed * reconstructed;
+                code as u8
+            })
+            .collect();
+
+        let mut encoded = Vec::with_capacity(HEADER_SIZE + codes.len());
+        encoded.push(centroid_index as u8);
+        encoded.extend_from_slice(&f32::to_le_bytes(norm_sq));
+        encoded.extend_from_slice(&codes);
+        encoded
+    }
+}
+
+fn nearest_centroid(vector: &[f32], centroids: &[Vec<f32>]) -> usize {
+    // like in product quantization, use euclid metric for any similarity function
+    centroids
+        .iter()
+        .map(|centroid| DistanceType::L2.distance(vector, centroid))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(index, _)| index)
+}
+
+fn split_encoded(encoded: &[u8]) -> (usize, f32, &[u8]) {
+    let (header, codes) = encoded.split_at(HEADER_SIZE);
+    let norm_sq = f32::from_le_bytes(header[1..HEADER_SIZE].try_into().unwrap());
+    (header[0] as usize, norm_sq, codes)
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsResidual<TStorage> {
+    type EncodedQuery = EncodedQueryResidual;
+
+    fn is_on_disk(&self) -> bool {
+        self.encoded_vectors.is_on_disk()
+    }
+
+    fn encode_query(&self, query: &[f32]) -> EncodedQueryResidual {
+        let centroid_products = self
+            .metadata
+            .centroids
+            .iter()
+            .map(|centroid| DistanceType::Dot.distance(query, centroid))
+            .collect();
+        EncodedQueryResidual {
+            query: query.to_vec(),
+            centroid_products,
+            offset_product: self.metadata.offset * query.iter().sum::<f32>(),
+            norm_sq: DistanceType::Dot.distance(query, query),
+        }
+    }
+
+    fn score_point(
+        &self,
+        query: &EncodedQueryResidual,
+        i: PointOffsetType,
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        let encoded = self.encoded_vectors.get_vector_data(i);
+        self.score_bytes(True, query, encoded, hw_counter)
+    }
+
+    /// Score two points inside encoded data by their indexes
+    /// To find score, this method decodes both encoded vectors.
+    fn score_internal(
+        &self,
+        i: PointOffsetType,
+        j: PointOffsetType,
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        let encoded_i = self.encoded_vectors.get_vector_data(i);
+        let encoded_j = self.encoded_vectors.get_vector_data(j);
+
+        hw_counter
+            .vector_io_read()
+            .incr_delta(self.quantized_vector_size() * 2);
+        hw_counter
+            .cpu_counter()
+            .incr_delta(self.metadata.vector_parameters.dim);
+
+        let distance = self.metadata.vector_parameters.distance_type.distance(
+            &self.decode_vector(encoded_i),
+            &self.decode_vector(encoded_j),
+        );
+        self.postprocess(distance)
+    }
+
+    fn quantized_vector_size(&self) -> usize {
+        Self::get_quantized_vector_size(&self.metadata.vector_parameters)
+    }
+
+    fn encode_internal_vector(&self, id: PointOffsetType) -> Option<EncodedQueryResidual> {
+        let encoded = self.encoded_vectors.get_vector_data(id);
+        Some(self.encode_query(&self.decode_vector(encoded)))
+    }
+
+    fn upsert_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: &[f32],
+        _hw_counter: &HardwareCounterCell,
+    ) -> std::io::Result<()> {
+        debug_assert!(
+            false,
+            "Residual quantization does not support upsert_vector",
+        );
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Residual quantization does not support upsert_vector",
+        ))
+    }
+
+    fn vectors_count(&self) -> usize {
+        self.encoded_vectors.vectors_count()
+    }
+
+    fn flusher(&self) -> MmapFlusher {
+        self.encoded_vectors.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.encoded_vectors.files();
+        if let Some(meta_path) = &self.metadata_path {
+            files.push(meta_path.clone());
+        }
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = self.encoded_vectors.immutable_files();
+        if let Some(meta_path) = &self.metadata_path {
+            files.push(meta_path.clone());
+        }
+        files
+    }
+
+    type SupportsBytes = True;
+    fn score_bytes(
+        &self,
+        _: Self::SupportsBytes,
+        query: &Self::EncodedQuery,
+        bytes: &[u8],
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        hw_counter
+            .cpu_counter()
+            .incr_delta(self.metadata.vector_parameters.dim);
+
+        let (centroid_index, norm_sq, codes) = split_encoded(bytes);
+
+        let distance = match self.metadata.vector_parameters.distance_type {
+            DistanceType::Dot | DistanceType::L2 => {
+                let codes_product: f32 = query
+                    .query
+                    .iter()
+                    .zip(codes)
+                    .map(|(value, &code)| value * f32::from(code))
+                    .sum();
+                let product = query.centroid_products[centroid_index]
+                    + query.offset_product
+                    + self.metadata.alpha * codes_product;
+                if self.metadata.vector_parameters.distance_type == DistanceType::Dot {
+                    product
+                } else {
+                    query.norm_sq - 2.0 * product + norm_sq
+                }
+            }
+            DistanceType::L1 => {
+                let centroid = &self.metadata.centroids[centroid_index];
+                query
+                    .query
+                    .iter()
+                    .zip(centroid)
+                    .zip(codes)
+                    .map(|((value, center), &code)| {
+                        let reconstructed =
+                            center + self.metadata.offset + self.metadata.alpha * f32::from(code);
+                        (value - reconstructed).abs()
+                    })
+                    .sum()
+            }
+        };
+        self.postprocess(distance)
+    }
+}
@@ -14,7 +14,9 @@ use crate::encoded_storage::{EncodedStorage, EncodedStorageBuilder};
 use crate::encoded_vectors::{
     DistanceType, EncodedVectors, VectorParameters, validate_vector_parameters,
 };
-use crate::quantile::{find_min_max_from_iter, find_quantile_interval};
+use crate::quantile::{
+    Quantile, find_min_max_from_iter, find_optimal_interval, find_quantile_interval,
+};
 
 pub const ALIGNMENT: usize = 16;
 
@@ -39,6 +41,9 @@ struct Metadata {
 }
 
 impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
+    /// Highest quantization level, values of the range are mapped to `0..=LEVELS`
+    const LEVELS: usize = 127;
+
     pub fn storage(&self) -> &TStorage {
         &self.encoded_vectors
     }
@@ -48,7 +53,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage>,
         vector_parameters: &VectorParameters,
         count: usize,
-        quantile: Option<f32>,
+        quantile: Option<Quantile>,
         meta_path: Option<&Path>,
         stopped: &AtomicBool,
     ) -> Result<Self, EncodingError> {
@@ -92,14 +97,21 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
 
         debug_assert!(validate_vector_parameters(orig_data.clone(), vector_parameters).is_ok());
         let (alpha, offset) = Self::find_alpha_offset_size_dim(orig_data.clone());
-        let (alpha, offset) = if let Some(quantile) = quantile {
-            if let Some((min, max)) =
+        let interval = match quantile {
+            Some(Quantile::Fixed(quantile)) => {
                 find_quantile_interval(orig_data.clone(), vector_parameters.dim, count, quantile)
-            {
-                Self::alpha_offset_from_min_max(min, max)
-            } else {
-                (alpha, offset)
             }
+            Some(Quantile::Auto) => find_optimal_interval(
+                orig_data.clone(),
+                vector_parameters.dim,
+                count,
+                Self::LEVELS,
+            ),
+            None => None,
+        };
+        // Chosen range is persisted in the metadata as `alpha` and `offset`
+        let (alpha, offset) = if let Some((min, max)) = interval {
+            Self::alpha_offset_from_min_max(min, max)
         } else {
             (alpha, offset)
         };
@@ -301,14 +313,14 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
     }
 
     fn alpha_offset_from_min_max(min: f32, max: f32) -> (f32, f32) {
-        let alpha = (max - min) / 127.0;
+        let alpha = (max - min) / Self::LEVELS as f32;
         let offset = min;
         (alpha, offset)
     }
 
     fn f32_to_u8(i: f32, alpha: f32, offset: f32) -> u8 {
         let i = (i - offset) / alpha;
-        i.clamp(0.0, 127.0) as u8
+        i.clamp(0.0, Self::LEVELS as f32) as u8
     }
 
     #[inline]
@@ -2,6 +2,24 @@ use permutation_iterator::Permutor;
 
 pub const QUANTILE_SAMPLE_SIZE: usize = 100_000;
 
+/// Maximum number of sampled values used to choose the quantization range automatically
+pub const AUTO_QUANTILE_SAMPLE_VALUES: usize = 1_000_000;
+
+/// Number of candidate ranges evaluated when choosing the quantization range automatically
+const AUTO_QUANTILE_CANDIDATES: usize = 256;
+
+/// Maximum fraction of values clipped on each side of an automatically chosen range
+const AUTO_QUANTILE_MAX_CLIPPED: f64 = 0.25;
+
+/// Range of values covered by scalar quantization, values outside of it are clipped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantile {
+    /// Cover the given fraction of values
+    Fixed(f32),
+    /// Choose the range, which minimizes the reconstruction error of sampled values
+    Auto,
+}
+
 pub(crate) fn find_min_max_from_iter<'a>(
     iter: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
 ) -> (f32, f32) {
@@ -29,21 +47,7 @@ pub(crate) fn find_quantile_interval<'a>(
     }
 
     let slice_size = std::cmp::min(count, QUANTILE_SAMPLE_SIZE);
-    let permutor = Permutor::new(count as u64);
-    let mut selected_vectors: Vec<usize> = permutor.map(|i| i as usize).take(slice_size).collect();
-    selected_vectors.sort_unstable();
-
-    let mut data_slice = Vec::with_capacity(slice_size * dim);
-    let mut selected_index: usize = 0;
-    for (vector_index, vector_data) in vector_data.into_iter().enumerate() {
-        if vector_index == selected_vectors[selected_index] {
-            data_slice.extend_from_slice(vector_data.as_ref());
-            selected_index += 1;
-            if selected_index == slice_size {
-                break;
-            }
-        }
-    }
+    let mut data_slice = sample_values(vector_data, dim, count, slice_size);
 
     let data_slice_len = data_slice.len();
     if data_slice_len < 4 {
@@ -69,3 +73,89 @@ pub(crate) fn find_quantile_interval<'a>(
         selected_values.iter().map(|v| &v[..]),
     ))
 }
+
+/// Values of `sample_size` randomly selected vectors
+fn sample_values<'a>(
+    vector_data: impl Iterator<Item = impl AsRef<[f32]> + 'a>,
+    dim: usize,
+    count: usize,
+    sample_size: usize,
+) -> Vec<f32> {
+    let permutor = Permutor::new(count as u64);
+    let mut selected_vectors: Vec<usize> = permutor.map(|i| i as usize).take(sample_size).collect();
+    selected_vectors.sort_unstable();
+
+    let mut data_slice = Vec::with_capacity(sample_size * dim);
+    let mut selected_index: usize = 0;
+    for (vector_index, vector_data) in vector_data.into_iter().enumerate() {
+        if vector_index == selected_vectors[selected_index] {
+            data_slice.extend_from_slice(vector_data.as_ref());
+            selected_index += 1;
+            if selected_index == sample_size {
+                break;
+            }
+        }
+    }
+    data_slice
+}
+
+/// Choose the quantization range, which minimizes the reconstruction error of sampled values
+///
+/// Wider range clips less values, but makes the quantization step coarser. Candidate ranges
+/// symmetrically cut up to [`AUTO_QUANTILE_MAX_CLIPPED`] of the sorted values on each side,
+/// the total squared error of each is estimated from prefix sums of the sorted values.
+pub(crate) fn find_optimal_interval<'a>(
+    vector_data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+    dim: usize,
+    count: usize,
+    levels: usize,
+) -> Option<(f32, f32)> {
+    if count < 127 || dim == 0 || levels == 0 {
+        return None;
+    }
+
+    let sample_size = (AUTO_QUANTILE_SAMPLE_VALUES / dim).clamp(1, count);
+    let mut values = sample_values(vector_data, dim, count, sample_size);
+    values.retain(|value| value.is_finite());
+    if values.len() < 4 {
+        return None;
+    }
+    values.sort_unstable_by(f32::total_cmp);
+
+    // Prefix sums of values and squared values
+    let mut sums = Vec::with_capacity(values.len() + 1);
+    let mut squared_sums = Vec::with_capacity(values.len() + 1);
+    sums.push(0.0f64);
+    squared_sums.push(0.0f64);
+    for &value in &values {
+        let value = f64::from(value);
+        sums.push(sums.last().unwrap() + value);
+        squared_sums.push(squared_sums.last().unwrap() + value * value);
+    }
+
+    let len = values.len();
+    let max_cut = (len as f64 * AUTO_QUANTILE_MAX_CLIPPED) as usize;
+    let error = |cut: usize| {
+        let min = f64::from(values[cut]);
+        let max = f64::from(values[len - 1 - cut]);
+
+        // Clipped values below the range are restored as `min`
+        let below = cut as f64 * min * min - 2.0 * min * sums[cut] + squared_sums[cut];
+        // Clipped values above the range are restored as `max`
+        let above_sum = sums[len] - sums[len - cut];
+        let above_squared_sum = squared_sums[len] - squared_sums[len - cut];
+        let above = above_squared_sum - 2.0 * max * above_sum + cut as f64 * max * max;
+        // Values within the range are truncated to the lower level, uniformly distributed
+        // error within a step has a mean square of `step^2 / 3`
+        let step = (max - min) / levels as f64;
+        let within = (len - 2 * cut) as f64 * step * step / 3.0;
+
+        below + above + within
+    };
+
+    let best_cut = (0..=AUTO_QUANTILE_CANDIDATES)
+        .map(|candidate| max_cut * candidate / AUTO_QUANTILE_CANDIDATES)
+        .min_by(|&a, &b| error(a).total_cmp(&error(b)))?;
+
+    Some((values[best_cut], values[len - 1 - best_cut]))
+}
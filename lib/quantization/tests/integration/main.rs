@@ -15,6 +15,8 @@ pub mod test_neon;
 #[cfg(test)]
 pub mod test_pq;
 #[cfg(test)]
+pub mod test_residual;
+#[cfg(test)]
 pub mod test_simple;
 #[cfg(test)]
 pub mod test_sse;
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use common::counter::hardware_counter::HardwareCounterCell;
+    use quantization::encoded_storage::{TestEncodedStorage, TestEncodedStorageBuilder};
+    use quantization::encoded_vectors::{DistanceType, EncodedVectors, VectorParameters};
+    use quantization::encoded_vectors_residual::EncodedVectorsResidual;
+    use rand::{Rng, SeedableRng};
+
+    use crate::metrics::{dot_similarity, l1_similarity, l2_similarity};
+
+    const VECTORS_COUNT: usize = 513;
+    const VECTOR_DIM: usize = 65;
+    const ERROR: f32 = VECTOR_DIM as f32 * 0.01;
+
+    fn check_residual(
+        distance_type: DistanceType,
+        invert: bool,
+        metric: fn(&[f32], &[f32]) -> f32,
+    ) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut vector_data: Vec<Vec<_>> = vec![];
+        for _ in 0..VECTORS_COUNT {
+            vector_data.push((0..VECTOR_DIM).map(|_| rng.random()).collect());
+        }
+        let query: Vec<_> = (0..VECTOR_DIM).map(|_| rng.random()).collect();
+
+        let vector_parameters = VectorParameters {
+            dim: VECTOR_DIM,
+            deprecated_count: None,
+            distance_type,
+            invert,
+        };
+        let quantized_vector_size =
+            EncodedVectorsResidual::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+            );
+        let encoded = EncodedVectorsResidual::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &vector_parameters,
+            VECTORS_COUNT,
+            2,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        let encoded_query = encoded.encode_query(&query);
+        let sign = if invert { -1.0 } else { 1.0 };
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&encoded_query, index as u32, &counter);
+            let original_score = sign * metric(&query, vector);
+            assert!((score - original_score).abs() < ERROR);
+
+            let score = encoded.score_internal(0, index as u32, &counter);
+            let original_score = sign * metric(&vector_data[0], vector);
+            assert!((score - original_score).abs() < ERROR);
+        }
+    }
+
+    #[test]
+    fn test_residual_dot() {
+        check_residual(DistanceType::Dot, false, dot_similarity);
+    }
+
+    #[test]
+    fn test_residual_l2() {
+        check_residual(DistanceType::L2, false, l2_similarity);
+    }
+
+    #[test]
+    fn test_residual_l1() {
+        check_residual(DistanceType::L1, false, l1_similarity);
+    }
+
+    #[test]
+    fn test_residual_l2_inverted() {
+        check_residual(DistanceType::L2, true, l2_similarity);
+    }
+}
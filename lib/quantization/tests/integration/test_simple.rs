@@ -6,6 +6,7 @@ mod tests {
     use quantization::encoded_storage::{TestEncodedStorage, TestEncodedStorageBuilder};
     use quantization::encoded_vectors::{DistanceType, EncodedVectors, VectorParameters};
     use quantization::encoded_vectors_u8::EncodedVectorsU8;
+    use quantization::quantile::Quantile;
     use rand::{Rng, SeedableRng};
 
     use crate::metrics::{dot_similarity, l1_similarity, l2_similarity};
@@ -371,7 +372,7 @@ mod tests {
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
             &vector_parameters,
             vectors_count,
-            Some(1.0 - f32::EPSILON), // almost 1.0 value, but not 1.0
+            Some(Quantile::Fixed(1.0 - f32::EPSILON)), // almost 1.0 value, but not 1.0
             None,
             &AtomicBool::new(false),
         )
@@ -385,6 +386,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_u8_auto_quantile() {
+        let vectors_count = 1000;
+        let vector_dim = 64;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut vector_data: Vec<Vec<f32>> = Vec::new();
+        for _ in 0..vectors_count {
+            let vector: Vec<f32> = (0..vector_dim)
+                .map(|_| 2.0 * rng.random::<f32>() - 1.0)
+                .collect();
+            vector_data.push(vector);
+        }
+        // A few outliers stretch the whole range of values
+        for vector in vector_data.iter_mut().step_by(100) {
+            vector[0] = 100.0;
+            vector[1] = -100.0;
+        }
+        let query: Vec<f32> = (0..vector_dim)
+            .map(|_| 2.0 * rng.random::<f32>() - 1.0)
+            .collect();
+
+        let vector_parameters = VectorParameters {
+            dim: vector_dim,
+            deprecated_count: None,
+            distance_type: DistanceType::Dot,
+            invert: false,
+        };
+        let quantized_vector_size =
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+
+        let mean_error = |quantile| {
+            let encoded = EncodedVectorsU8::encode(
+                vector_data.iter(),
+                TestEncodedStorageBuilder::new(None, quantized_vector_size),
+                &vector_parameters,
+                vectors_count,
+                quantile,
+                None,
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+            let query_u8 = encoded.encode_query(&query);
+            let errors: Vec<f32> = vector_data
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| index % 100 != 0)
+                .map(|(index, vector)| {
+                    let score = encoded.score_point_simple(&query_u8, index as u32);
+                    (score - dot_similarity(&query, vector)).abs()
+                })
+                .collect();
+            errors.iter().sum::<f32>() / errors.len() as f32
+        };
+
+        // Clipping the outliers gives more precision to the regular values
+        let full_range_error = mean_error(None);
+        let auto_error = mean_error(Some(Quantile::Auto));
+        assert!(
+            auto_error < full_range_error / 10.0,
+            "{auto_error} {full_range_error}",
+        );
+    }
+
     #[test]
     fn test_sq_u8_encode_internal() {
         let vectors_count = 129;
@@ -417,7 +482,7 @@ mod tests {
                 TestEncodedStorageBuilder::new(None, quantized_vector_size),
                 &vector_parameters,
                 vectors_count,
-                Some(1.0 - f32::EPSILON), // almost 1.0 value, but not 1.0
+                Some(Quantile::Fixed(1.0 - f32::EPSILON)), // almost 1.0 value, but not 1.0
                 None,
                 &AtomicBool::new(false),
             )
@@ -364,6 +364,8 @@ fn build_hnsw_index<R: Rng + ?Sized>(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let open_args = HnswIndexOpenArgs {
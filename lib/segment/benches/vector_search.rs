@@ -14,6 +14,7 @@ use segment::fixtures::payload_context_fixture::FixtureIdTracker;
 use segment::id_tracker::IdTrackerSS;
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::types::{Distance, VectorStorageDatatype};
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use segment::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_vector_storage;
 use segment::vector_storage::{DEFAULT_STOPPED, VectorStorage, VectorStorageEnum};
 use tempfile::Builder;
@@ -41,6 +42,7 @@ fn init_vector_storage(
         DB_VECTOR_CF,
         dim,
         dist,
+        ChunkedVectorsConfig::default(),
         &AtomicBool::new(false),
     )
     .unwrap();
@@ -0,0 +1,53 @@
+use std::cell::Cell;
+
+thread_local! {
+    static DETERMINISTIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with deterministic scoring enabled or disabled on the current thread.
+///
+/// Dense vectors are scored with plain implementations of distance functions, which reduce in a
+/// fixed order, instead of SIMD kernels selected by available CPU features. So identical data and
+/// queries yield bit-identical scores across runs and machines, at the cost of slower scoring.
+///
+/// Requested with [`SearchParams::deterministic`](crate::types::SearchParams::deterministic).
+pub fn with_deterministic<T>(deterministic: bool, f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DETERMINISTIC.set(self.0);
+        }
+    }
+
+    let _restore = Restore(DETERMINISTIC.replace(deterministic));
+    f()
+}
+
+#[inline]
+pub fn is_deterministic() -> bool {
+    DETERMINISTIC.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_deterministic() {
+        assert!(!is_deterministic());
+
+        with_deterministic(true, || {
+            assert!(is_deterministic());
+            with_deterministic(false, || assert!(!is_deterministic()));
+            assert!(is_deterministic());
+
+            // Other threads are not affected
+            std::thread::spawn(|| assert!(!is_deterministic()))
+                .join()
+                .unwrap();
+        });
+
+        assert!(!is_deterministic());
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ahash::AHashMap;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+
+use crate::data_types::vectors::VectorInternal;
+use crate::types::{Payload, VectorName, VectorNameBuf};
+
+/// Fraction of cache misses, which are counted towards the access frequency of a point
+const ACCESS_SAMPLE_RATE: f64 = 0.1;
+
+/// Number of sampled accesses, after which a point is admitted into the cache
+const ADMISSION_THRESHOLD: u8 = 2;
+
+/// Maximum number of points, access frequency of which is tracked by a cache
+///
+/// Once exceeded, all frequencies are halved, so only recently hot points are kept.
+const MAX_TRACKED_POINTS: usize = 100_000;
+
+/// Number of independently locked shards of a cache
+const NUM_SHARDS: usize = 16;
+
+/// RAM budget of the cache of each segment in bytes, 0 disables caching
+static BUDGET_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Set RAM budget of the [`HotPointsCache`] of each segment in bytes, 0 disables caching.
+///
+/// Only applies to segments loaded afterwards.
+pub fn set_budget(budget_bytes: usize) {
+    BUDGET_BYTES.store(budget_bytes, Ordering::Relaxed);
+}
+
+/// LRU cache of vectors and payloads of frequently retrieved points of an on-disk segment
+///
+/// Access frequency of points is sampled on cache misses. Only points accessed repeatedly are
+/// admitted, so a scan over many points does not evict hot ones.
+/// Cached points must be invalidated on every change of the point.
+///
+/// Points are split into shards by their offset, each shard is locked and evicted on its own,
+/// so concurrent reads of different points rarely wait for each other.
+#[derive(Debug)]
+pub struct HotPointsCache {
+    /// RAM budget of each shard
+    shard_budget_bytes: usize,
+    sample_rate: f64,
+    /// Empty if caching is disabled
+    shards: Vec<Mutex<CacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    points: AHashMap<PointOffsetType, CachedPoint>,
+    /// Offsets of cached points by their last access, oldest first
+    recency: BTreeMap<u64, PointOffsetType>,
+    /// Sampled access counts of points which are not cached yet
+    frequencies: AHashMap<PointOffsetType, u8>,
+    size_bytes: usize,
+    tick: u64,
+}
+
+#[derive(Debug, Default)]
+struct CachedPoint {
+    payload: Option<Payload>,
+    vectors: HashMap<VectorNameBuf, VectorInternal>,
+    size_bytes: usize,
+    last_access: u64,
+}
+
+impl Default for HotPointsCache {
+    /// Cache with the configured budget, see [`set_budget`]
+    fn default() -> Self {
+        Self::new(
+            BUDGET_BYTES.load(Ordering::Relaxed),
+            ACCESS_SAMPLE_RATE,
+            NUM_SHARDS,
+        )
+    }
+}
+
+impl HotPointsCache {
+    fn new(budget_bytes: usize, sample_rate: f64, num_shards: usize) -> Self {
+        let shards = if budget_bytes > 0 {
+            (0..num_shards).map(|_| Mutex::default()).collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            shard_budget_bytes: budget_bytes / num_shards,
+            sample_rate,
+            shards,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.shards.is_empty()
+    }
+
+    fn shard(&self, offset: PointOffsetType) -> Option<&Mutex<CacheState>> {
+        if self.shards.is_empty() {
+            return None;
+        }
+        self.shards.get(offset as usize % self.shards.len())
+    }
+
+    pub fn get_payload(&self, offset: PointOffsetType) -> Option<Payload> {
+        self.get(offset, |point| point.payload.clone())
+    }
+
+    pub fn get_vector(
+        &self,
+        offset: PointOffsetType,
+        vector_name: &VectorName,
+    ) -> Option<VectorInternal> {
+        self.get(offset, |point| point.vectors.get(vector_name).cloned())
+    }
+
+    /// Cache payload loaded on a cache miss, if the point is hot enough
+    pub fn put_payload(&self, offset: PointOffsetType, payload: &Payload) {
+        self.put(offset, |point| {
+            if point.payload.is_some() {
+                return 0;
+            }
+            point.payload = Some(payload.clone());
+            serde_json::to_vec(payload).map_or(0, |bytes| bytes.len())
+        });
+    }
+
+    /// Cache vector loaded on a cache miss, if the point is hot enough
+    pub fn put_vector(
+        &self,
+        offset: PointOffsetType,
+        vector_name: &VectorName,
+        vector: &VectorInternal,
+        size_bytes: usize,
+    ) {
+        self.put(offset, |point| {
+            if point.vectors.contains_key(vector_name) {
+                return 0;
+            }
+            point.vectors.insert(vector_name.to_owned(), vector.clone());
+            size_bytes
+        });
+    }
+
+    /// Remove the point from the cache, must be called on every change of the point
+    pub fn invalidate(&self, offset: PointOffsetType) {
+        let Some(shard) = self.shard(offset) else {
+            return;
+        };
+        let mut state = shard.lock();
+        state.remove(offset);
+        state.frequencies.remove(&offset);
+    }
+
+    fn get<T>(
+        &self,
+        offset: PointOffsetType,
+        read: impl FnOnce(&CachedPoint) -> Option<T>,
+    ) -> Option<T> {
+        let mut state = self.shard(offset)?.lock();
+        let value = read(state.points.get(&offset)?)?;
+        state.touch(offset);
+        Some(value)
+    }
+
+    /// Add an item of the point, admitting the point after repeated sampled accesses
+    ///
+    /// `write` adds the item to the point and returns its size in bytes, or 0 if the item is
+    /// already cached.
+    fn put(&self, offset: PointOffsetType, write: impl FnOnce(&mut CachedPoint) -> usize) {
+        let Some(shard) = self.shard(offset) else {
+            return;
+        };
+
+        let mut state = shard.lock();
+        if !state.points.contains_key(&offset) {
+            // Only count sampled misses, to keep tracking cheap
+            if !rand::random_bool(self.sample_rate) {
+                return;
+            }
+            if state.frequencies.len() >= MAX_TRACKED_POINTS / self.shards.len() {
+                state.frequencies.retain(|_, count| {
+                    *count /= 2;
+                    *count > 0
+                });
+            }
+            let count = state.frequencies.entry(offset).or_default();
+            *count = count.saturating_add(1);
+            if *count < ADMISSION_THRESHOLD {
+                return;
+            }
+            state.frequencies.remove(&offset);
+            state.points.insert(offset, CachedPoint::default());
+        }
+
+        let point = state
+            .points
+            .get_mut(&offset)
+            .expect("point is inserted above");
+        let size_bytes = write(point);
+        point.size_bytes += size_bytes;
+        state.size_bytes += size_bytes;
+        state.touch(offset);
+        state.evict(self.shard_budget_bytes);
+    }
+}
+
+impl CacheState {
+    fn touch(&mut self, offset: PointOffsetType) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(point) = self.points.get_mut(&offset) {
+            self.recency.remove(&point.last_access);
+            point.last_access = tick;
+            self.recency.insert(tick, offset);
+        }
+    }
+
+    fn remove(&mut self, offset: PointOffsetType) {
+        if let Some(point) = self.points.remove(&offset) {
+            self.recency.remove(&point.last_access);
+            self.size_bytes -= point.size_bytes;
+        }
+    }
+
+    /// Evict least recently used points until the cache fits into the budget
+    fn evict(&mut self, budget_bytes: usize) {
+        while self.size_bytes > budget_bytes {
+            let Some((_, offset)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(point) = self.points.remove(&offset) {
+                self.size_bytes -= point.size_bytes;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload_json;
+
+    #[test]
+    fn test_hot_points_cache() {
+        let vector = VectorInternal::Dense(vec![1.0; 4]);
+
+        // Disabled without budget
+        let cache = HotPointsCache::new(0, 1.0, 1);
+        cache.put_vector(0, "", &vector, 16);
+        cache.put_vector(0, "", &vector, 16);
+        assert_eq!(cache.get_vector(0, ""), None);
+
+        let cache = HotPointsCache::new(40, 1.0, 1);
+
+        // Admitted on repeated access only
+        cache.put_vector(0, "", &vector, 16);
+        assert_eq!(cache.get_vector(0, ""), None);
+        cache.put_vector(0, "", &vector, 16);
+        assert_eq!(cache.get_vector(0, ""), Some(vector.clone()));
+
+        let payload = payload_json! {"a": 1};
+        cache.put_payload(0, &payload);
+        assert_eq!(cache.get_payload(0), Some(payload));
+
+        // Least recently used point is evicted when exceeding the budget
+        for offset in [1, 2] {
+            cache.put_vector(offset, "", &vector, 16);
+            cache.put_vector(offset, "", &vector, 16);
+        }
+        assert_eq!(cache.get_vector(0, ""), None);
+        assert_eq!(cache.get_vector(1, ""), Some(vector.clone()));
+        assert_eq!(cache.get_vector(2, ""), Some(vector.clone()));
+
+        cache.invalidate(1);
+        assert_eq!(cache.get_vector(1, ""), None);
+        assert_eq!(cache.shards[0].lock().size_bytes, 16);
+    }
+
+    #[test]
+    fn test_hot_points_cache_shards() {
+        let vector = VectorInternal::Dense(vec![1.0; 4]);
+        let cache = HotPointsCache::new(64, 1.0, 2);
+
+        // Each shard evicts its own points within its half of the budget
+        for offset in 0..4 {
+            cache.put_vector(offset, "", &vector, 16);
+            cache.put_vector(offset, "", &vector, 16);
+        }
+        for offset in 0..4 {
+            assert_eq!(cache.get_vector(offset, ""), Some(vector.clone()));
+        }
+
+        cache.put_vector(4, "", &vector, 16);
+        cache.put_vector(4, "", &vector, 16);
+        assert_eq!(cache.get_vector(0, ""), None);
+        assert_eq!(cache.get_vector(1, ""), Some(vector.clone()));
+        assert_eq!(cache.get_vector(4, ""), Some(vector.clone()));
+    }
+}
@@ -1,6 +1,8 @@
 pub mod anonymize;
+pub mod determinism;
 pub mod error_logging;
 pub mod flags;
+pub mod hot_points_cache;
 pub mod macros;
 pub mod mmap_bitslice_buffered_update_wrapper;
 pub mod mmap_slice_buffered_update_wrapper;
@@ -17,6 +19,8 @@ pub mod score_fusion;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
+pub mod warmup;
+pub mod wasm_module;
 
 use std::sync::atomic::AtomicBool;
 
@@ -44,6 +44,9 @@ pub enum OperationError {
     },
     #[error("Inconsistent storage: {description}")]
     InconsistentStorage { description: String },
+    /// Segment files don't match their stored checksums.
+    #[error("Segment data is corrupted: {description}")]
+    CorruptedSegment { description: String },
     #[error("Out of memory, free: {free}, {description}")]
     OutOfMemory { description: String, free: u64 },
     #[error("Operation cancelled: {description}")]
@@ -107,6 +110,12 @@ impl OperationError {
             description: description.into(),
         }
     }
+
+    pub fn corrupted_segment(description: impl Into<String>) -> OperationError {
+        OperationError::CorruptedSegment {
+            description: description.into(),
+        }
+    }
 }
 
 /// Contains information regarding last operation error, which should be fixed before next operation could be processed
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use parking_lot::RwLock;
 //use atomic_refcell::{AtomicRef, AtomicRefCell};
-use rocksdb::{ColumnFamily, DB, DBRecoveryMode, LogLevel, Options, WriteOptions};
+use rocksdb::{ColumnFamily, DB, DBRecoveryMode, LogLevel, Options, WriteBatch, WriteOptions};
 
 use crate::common::Flusher;
 //use crate::common::arc_rwlock_iterator::ArcRwLockIterator;
@@ -155,6 +155,27 @@ impl DatabaseColumnWrapper {
         Ok(())
     }
 
+    /// Put multiple key-value pairs with a single atomic write
+    pub fn put_batch<K, V>(&self, items: impl IntoIterator<Item = (K, V)>) -> OperationResult<()>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let db = self.database.read();
+        let cf_handle = self.get_column_family(&db)?;
+        let mut batch = WriteBatch::default();
+        for (key, value) in items {
+            batch.put_cf(cf_handle, key, value);
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+        db.write_opt(batch, &self.write_options).map_err(|err| {
+            OperationError::service_error(format!("RocksDB write batch error: {err}"))
+        })?;
+        Ok(())
+    }
+
     pub fn get<K>(&self, key: K) -> OperationResult<Vec<u8>>
     where
         K: AsRef<[u8]>,
@@ -0,0 +1,115 @@
+//! Reading of segment files into RAM before the first searches
+//!
+//! Files are read one at a time by path, so no segment lock has to be held while waiting for
+//! the disk. Files, which disappear in the meantime, belong to segments removed by optimizers
+//! and are skipped.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use memmap2::Mmap;
+use memory::mmap_ops::{open_locked_mmap, populate_file};
+use parking_lot::Mutex;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Read the given files into the page cache
+pub fn populate_files(files: &[PathBuf]) -> OperationResult<()> {
+    for file in files {
+        match populate_file(file) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(OperationError::service_error(format!(
+                    "Failed to read {} into page cache: {err}",
+                    file.display(),
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Files locked in RAM, by path
+///
+/// Once enabled, the set of locked files is meant to be updated whenever segments change, so
+/// files of new segments get locked and files of removed segments are released.
+#[derive(Default)]
+pub struct LockedFiles {
+    enabled: AtomicBool,
+    files: Mutex<HashMap<PathBuf, Mmap>>,
+}
+
+impl LockedFiles {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Lock the given files in RAM, release all other files
+    pub fn lock(&self, files: Vec<PathBuf>) -> OperationResult<()> {
+        self.enabled.store(true, Ordering::Relaxed);
+        self.update(files)
+    }
+
+    /// Release all files and stop locking new ones
+    pub fn unlock(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+        self.files.lock().clear();
+    }
+
+    /// Lock the given files in RAM, release all other files
+    ///
+    /// Does nothing if locking is not enabled.
+    pub fn update(&self, files: Vec<PathBuf>) -> OperationResult<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let files: HashSet<_> = files.into_iter().collect();
+        self.files.lock().retain(|path, _| files.contains(path));
+
+        for file in files {
+            if self.files.lock().contains_key(&file) {
+                continue;
+            }
+
+            // Don't hold the lock while reading the file from disk
+            let Some(mmap) = Self::open_locked(&file)? else {
+                continue;
+            };
+
+            // Locking might have been disabled in the meantime
+            if !self.is_enabled() {
+                return Ok(());
+            }
+            self.files.lock().insert(file, mmap);
+        }
+
+        Ok(())
+    }
+
+    fn open_locked(file: &Path) -> OperationResult<Option<Mmap>> {
+        let to_error = |err: io::Error| {
+            OperationError::service_error(format!(
+                "Failed to lock {} in RAM: {err}",
+                file.display(),
+            ))
+        };
+
+        match std::fs::metadata(file) {
+            // Empty files can't be mapped, and there is nothing to lock in them
+            Ok(metadata) if metadata.len() == 0 => return Ok(None),
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(to_error(err)),
+        }
+
+        match open_locked_mmap(file) {
+            Ok(mmap) => Ok(Some(mmap)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(to_error(err)),
+        }
+    }
+}
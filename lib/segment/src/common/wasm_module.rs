@@ -0,0 +1,300 @@
+//! Shared runtime of sandboxed WebAssembly modules
+//!
+//! A module must export its linear `memory`, `alloc(len: i32) -> i32` to reserve `len` bytes of
+//! memory for the input, and an entry function, which receives the input. Memory reserved by
+//! `alloc` is reused by following calls of the same instance, as long as it is large enough.
+//!
+//! Modules are sandboxed: no imports are provided, so modules have no access to the host.
+//! Each call is limited in executed instructions, and memory of each instance is limited.
+//!
+//! Every thread keeps its own instance of a module, so calls never wait on each other. An
+//! instance is dropped if a call traps, for example by running out of fuel. Instances of dropped
+//! modules are evicted by each thread on its next call of any module.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use wasmi::{
+    Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc, WasmParams,
+    WasmResults,
+};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+pub const DEFAULT_FUEL_LIMIT: u64 = 1_000_000;
+
+pub const DEFAULT_MEMORY_LIMIT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Source of unique IDs of compiled modules, to find instances of a module in threads
+static NEXT_MODULE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Number of dropped modules, threads evict instances of dropped modules once it changes
+static DROPPED_MODULES: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Instances of modules created by this thread
+    static INSTANCES: RefCell<ThreadInstances> = RefCell::new(ThreadInstances::default());
+}
+
+#[derive(Default)]
+struct ThreadInstances {
+    /// Instances by module ID, with the flag telling if the module is still alive
+    instances: HashMap<u64, (Arc<AtomicBool>, Box<dyn Any>)>,
+    /// Value of [`DROPPED_MODULES`] at the last eviction
+    dropped_modules: u64,
+}
+
+impl ThreadInstances {
+    fn evict_dropped(&mut self) {
+        let dropped_modules = DROPPED_MODULES.load(Ordering::Acquire);
+        if dropped_modules != self.dropped_modules {
+            self.instances
+                .retain(|_, (alive, _)| alive.load(Ordering::Relaxed));
+            self.dropped_modules = dropped_modules;
+        }
+    }
+}
+
+/// Resource limits of a single call of a module
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Maximum amount of fuel, roughly a number of executed instructions
+    pub fuel_limit: u64,
+    /// Maximum size of linear memory of a module instance, in bytes
+    pub memory_limit_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel_limit: DEFAULT_FUEL_LIMIT,
+            memory_limit_bytes: DEFAULT_MEMORY_LIMIT_BYTES,
+        }
+    }
+}
+
+/// Compiled module with entry function taking `Params` and returning `Results`
+pub struct WasmModule<Params, Results> {
+    id: u64,
+    /// Cleared on drop, so threads evict instances of the module
+    alive: Arc<AtomicBool>,
+    engine: Engine,
+    module: Module,
+    entry: &'static str,
+    limits: WasmLimits,
+    _entry_type: PhantomData<fn(Params) -> Results>,
+}
+
+struct WasmInstance<Params, Results> {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    entry: TypedFunc<Params, Results>,
+    /// Offset and size of memory reserved for the input
+    input_buffer: Option<(i32, usize)>,
+}
+
+impl<Params, Results> WasmModule<Params, Results>
+where
+    Params: WasmParams + 'static,
+    Results: WasmResults + 'static,
+{
+    pub fn new(wasm: &[u8], entry: &'static str, limits: WasmLimits) -> OperationResult<Self> {
+        let mut config = wasmi::Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(wasm_error)?;
+
+        let module = Self {
+            id: NEXT_MODULE_ID.fetch_add(1, Ordering::Relaxed),
+            alive: Arc::new(AtomicBool::new(true)),
+            engine,
+            module,
+            entry,
+            limits,
+            _entry_type: PhantomData,
+        };
+        // Check exports of the module early
+        module.instantiate()?;
+        Ok(module)
+    }
+
+    pub fn limits(&self) -> WasmLimits {
+        self.limits
+    }
+
+    fn instantiate(&self) -> OperationResult<WasmInstance<Params, Results>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.memory_limit_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.limits.fuel_limit).map_err(wasm_error)?;
+
+        let instance = Linker::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(wasm_error)?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| OperationError::service_error("Module does not export `memory`"))?;
+        let alloc = instance
+            .get_typed_func(&store, "alloc")
+            .map_err(wasm_error)?;
+        let entry = instance
+            .get_typed_func(&store, self.entry)
+            .map_err(wasm_error)?;
+
+        Ok(WasmInstance {
+            store,
+            memory,
+            alloc,
+            entry,
+            input_buffer: None,
+        })
+    }
+
+    /// Call the entry function with an input of `input_len` bytes
+    ///
+    /// `write_input` fills the input in instance memory, `params` builds entry function
+    /// parameters from the offset of the input.
+    pub fn call(
+        &self,
+        input_len: usize,
+        write_input: impl FnOnce(&mut [u8]),
+        params: impl FnOnce(i32) -> Params,
+    ) -> OperationResult<Results> {
+        INSTANCES.with_borrow_mut(|thread_instances| {
+            thread_instances.evict_dropped();
+
+            let instances = &mut thread_instances.instances;
+            let (_, instance) = match instances.entry(self.id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert((self.alive.clone(), Box::new(self.instantiate()?)))
+                }
+            };
+            let instance = instance
+                .downcast_mut::<WasmInstance<Params, Results>>()
+                .expect("instance type matches module");
+
+            let result = self.call_with(instance, input_len, write_input, params);
+            if result.is_err() {
+                // State of the instance is unknown after a failed call, start over with a new one
+                instances.remove(&self.id);
+            }
+            result
+        })
+    }
+
+    fn call_with(
+        &self,
+        instance: &mut WasmInstance<Params, Results>,
+        input_len: usize,
+        write_input: impl FnOnce(&mut [u8]),
+        params: impl FnOnce(i32) -> Params,
+    ) -> OperationResult<Results> {
+        let WasmInstance {
+            store,
+            memory,
+            alloc,
+            entry,
+            input_buffer,
+        } = instance;
+        store.set_fuel(self.limits.fuel_limit).map_err(wasm_error)?;
+
+        let ptr = match *input_buffer {
+            Some((ptr, capacity)) if capacity >= input_len => ptr,
+            _ => {
+                let len = i32::try_from(input_len)
+                    .map_err(|_| OperationError::service_error("Module input is too large"))?;
+                let ptr = alloc.call(&mut *store, len).map_err(wasm_error)?;
+                *input_buffer = Some((ptr, input_len));
+                ptr
+            }
+        };
+
+        let start = ptr as u32 as usize;
+        let input = memory
+            .data_mut(&mut *store)
+            .get_mut(start..start + input_len)
+            .ok_or_else(|| OperationError::service_error("Module input is out of memory bounds"))?;
+        write_input(input);
+
+        entry.call(&mut *store, params(ptr)).map_err(wasm_error)
+    }
+}
+
+impl<Params, Results> Drop for WasmModule<Params, Results> {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+        DROPPED_MODULES.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl<Params, Results> std::fmt::Debug for WasmModule<Params, Results> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmModule")
+            .field("entry", &self.entry)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+fn wasm_error(err: impl std::fmt::Display) -> OperationError {
+    OperationError::service_error(format!("WebAssembly error: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the first byte of the input
+    const FIRST_BYTE_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "run") (param $input i32) (result i32)
+                (i32.load8_u (local.get $input))))
+    "#;
+
+    fn thread_instance_ids() -> Vec<u64> {
+        INSTANCES.with_borrow(|thread_instances| {
+            let mut ids = thread_instances
+                .instances
+                .keys()
+                .copied()
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids
+        })
+    }
+
+    #[test]
+    fn test_wasm_module_evicts_instances_of_dropped_modules() {
+        let wasm = wat::parse_str(FIRST_BYTE_MODULE).unwrap();
+        let new_module =
+            || WasmModule::<i32, i32>::new(&wasm, "run", WasmLimits::default()).unwrap();
+        let call = |module: &WasmModule<i32, i32>| {
+            module.call(1, |input| input[0] = 7, |ptr| ptr).unwrap()
+        };
+
+        let first = new_module();
+        let second = new_module();
+        assert_eq!(call(&first), 7);
+        assert_eq!(call(&second), 7);
+        assert_eq!(thread_instance_ids(), vec![first.id, second.id]);
+
+        // Instance of the dropped module is evicted on the next call
+        drop(first);
+        assert_eq!(call(&second), 7);
+        assert_eq!(thread_instance_ids(), vec![second.id]);
+    }
+}
@@ -157,6 +157,8 @@ mod tests {
                             on_disk: None,
                             payload_m: Some(10),
                             copy_vectors: None,
+                            gpu_indexing: None,
+                            seed: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -173,6 +175,8 @@ mod tests {
                                 r#type: Default::default(),
                                 quantile: Some(0.99),
                                 always_ram: Some(true),
+                                placement: None,
+                                auto_quantile: None,
                             },
                         })),
                         on_disk: None,
@@ -189,6 +193,8 @@ mod tests {
                 on_disk: None,
                 payload_m: None,
                 copy_vectors: None,
+                gpu_indexing: None,
+                seed: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -200,14 +206,18 @@ mod tests {
         eprintln!("new = {new_segment:#?}");
 
         match &new_segment.vector_data.get("vec1").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Ivf(_) | Indexes::DiskAnn(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 20);
             }
         }
 
         match &new_segment.vector_data.get("vec2").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Ivf(_) | Indexes::DiskAnn(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 25);
             }
@@ -249,6 +259,8 @@ mod tests {
                                 r#type: Default::default(),
                                 quantile: Some(0.99),
                                 always_ram: Some(true),
+                                placement: None,
+                                auto_quantile: None,
                             },
                         })),
                         on_disk: None,
@@ -265,6 +277,8 @@ mod tests {
                 on_disk: None,
                 payload_m: None,
                 copy_vectors: None,
+                gpu_indexing: None,
+                seed: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -273,6 +287,8 @@ mod tests {
                     r#type: Default::default(),
                     quantile: Some(0.95),
                     always_ram: Some(true),
+                    placement: None,
+                    auto_quantile: None,
                 },
             })),
         };
@@ -304,7 +320,7 @@ mod tests {
                 QuantizationConfig::Product(_) => {
                     panic!("expected scalar quantization")
                 }
-                QuantizationConfig::Binary(_) => {
+                QuantizationConfig::Binary(_) | QuantizationConfig::Residual(_) => {
                     panic!("expected scalar quantization")
                 }
             },
@@ -214,6 +214,11 @@ pub struct TextIndexParams {
     /// Algorithm for stemming. Default: disabled.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stemmer: Option<StemmingAlgorithm>,
+
+    /// If true, fold accented latin characters to their ASCII equivalents, e.g. "café" -> "cafe".
+    /// Applied after stopwords filtering and stemming. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii_folding: Option<bool>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
@@ -197,6 +197,11 @@ impl VectorQueryContext<'_> {
         self.search_optimized_threshold_kb
     }
 
+    /// Report that a search stopped early, because it ran out of its scoring budget.
+    pub fn mark_search_truncated(&self) {
+        self.hardware_counter.mark_search_truncated();
+    }
+
     pub fn deleted_points(&self) -> Option<&BitSlice> {
         self.deleted_points
     }
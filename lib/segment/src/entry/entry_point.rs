@@ -15,13 +15,16 @@ use crate::data_types::query_context::{FormulaContext, QueryContext, SegmentQuer
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::snapshot_entry::SnapshotEntry;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::hnsw_index::graph_stats::HnswGraphStats;
+use crate::index::hnsw_index::hnsw::HealedGraph;
 use crate::json_path::JsonPath;
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{QuantizationTelemetry, SegmentTelemetry};
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, VectorName,
-    VectorNameBuf, WithPayload, WithVector,
+    QuantizationConfig, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType,
+    SeqNumberType, VectorName, VectorNameBuf, WithPayload, WithVector,
 };
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
 /// Define all operations which can be performed with Segment or Segment-like entity.
 ///
@@ -321,25 +324,29 @@ pub trait SegmentEntry: SnapshotEntry {
             });
         };
 
-        self.delete_field_index_if_incompatible(op_num, key, field_schema)?;
-
-        let (schema, indexes) =
-            match self.build_field_index(op_num, key, field_schema, hw_counter)? {
-                BuildFieldIndexResult::SkippedByVersion => {
-                    return Ok(false);
-                }
-                BuildFieldIndexResult::AlreadyExists => {
-                    return Ok(false);
-                }
-                BuildFieldIndexResult::IncompatibleSchema => {
-                    // This is a service error, as we should have just removed the old index
-                    // So it should not be possible to get this error
-                    return Err(OperationError::service_error(format!(
-                        "Incompatible schema for field index on field {key}",
-                    )));
-                }
-                BuildFieldIndexResult::Built { schema, indexes } => (schema, indexes),
-            };
+        // Existing index with another schema is rebuilt next to the old one, if possible
+        let mut build_result = self.build_field_index(op_num, key, field_schema, hw_counter)?;
+        if let BuildFieldIndexResult::IncompatibleSchema = build_result {
+            self.delete_field_index_if_incompatible(op_num, key, field_schema)?;
+            build_result = self.build_field_index(op_num, key, field_schema, hw_counter)?;
+        }
+
+        let (schema, indexes) = match build_result {
+            BuildFieldIndexResult::SkippedByVersion => {
+                return Ok(false);
+            }
+            BuildFieldIndexResult::AlreadyExists => {
+                return Ok(false);
+            }
+            BuildFieldIndexResult::IncompatibleSchema => {
+                // This is a service error, as we should have just removed the old index
+                // So it should not be possible to get this error
+                return Err(OperationError::service_error(format!(
+                    "Incompatible schema for field index on field {key}",
+                )));
+            }
+            BuildFieldIndexResult::Built { schema, indexes } => (schema, indexes),
+        };
 
         self.apply_field_index(op_num, key.to_owned(), schema, indexes)
     }
@@ -353,5 +360,63 @@ pub trait SegmentEntry: SnapshotEntry {
     // Get collected telemetry data of segment
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> SegmentTelemetry;
 
+    /// Structural statistics of HNSW graphs of this segment, by vector name.
+    /// Vectors without an HNSW index are not included.
+    fn hnsw_graph_stats(&self) -> HashMap<VectorNameBuf, HnswGraphStats>;
+
+    /// Files of on-disk vector storages, quantized vectors and vector indexes of this segment
+    ///
+    /// These are the files worth reading into the page cache before the first searches.
+    /// Components kept in RAM are not included, they are loaded with the segment already.
+    fn warmup_files(&self) -> Vec<PathBuf>;
+
+    /// Size and estimated recall of quantized vectors of this segment.
+    /// Vectors without quantization are not included.
+    fn quantization_telemetry(&self) -> Vec<QuantizationTelemetry>;
+
+    /// Quantize vectors with the given config, without changing the segment yet
+    ///
+    /// The result is applied with [`SegmentEntry::apply_quantization`].
+    fn build_quantization(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectors>;
+
+    /// Replace quantized vectors and quantization config of the vector in place
+    ///
+    /// `None` removes quantization of the vector.
+    fn apply_quantization(
+        &mut self,
+        vector_name: &VectorName,
+        quantized_vectors: Option<QuantizedVectors>,
+    ) -> OperationResult<()>;
+
+    /// Vectors with HNSW graphs to heal, for which at least `deleted_threshold` ratio and
+    /// `min_deleted_count` of indexed points are deleted since the graph was built or last healed
+    fn hnsw_graphs_to_heal(
+        &self,
+        deleted_threshold: f64,
+        min_deleted_count: usize,
+    ) -> Vec<VectorNameBuf>;
+
+    /// Reroute links of HNSW graphs around deleted points, without changing the segment yet
+    ///
+    /// The result is applied with [`SegmentEntry::apply_healed_hnsw_graphs`].
+    fn heal_hnsw_graphs(
+        &self,
+        vector_names: &[VectorNameBuf],
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Vec<(VectorNameBuf, HealedGraph)>>;
+
+    /// Replace HNSW graphs of the vectors with healed ones
+    fn apply_healed_hnsw_graphs(
+        &mut self,
+        healed: Vec<(VectorNameBuf, HealedGraph)>,
+    ) -> OperationResult<()>;
+
     fn fill_query_context(&self, query_context: &mut QueryContext);
 }
@@ -2,12 +2,14 @@ use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitVec;
 use common::counter::hardware_counter::HardwareCounterCell;
+use common::cow::BoxCow;
 use common::types::PointOffsetType;
 use rand::Rng;
 
 use crate::data_types::vectors::{DenseVector, QueryVector, VectorElementType, VectorRef};
 use crate::index::hnsw_index::graph_links::StorageGraphLinksVectors;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::payload_storage::FilterContext;
 use crate::types::{Distance, ScalarQuantizationConfig};
 use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::{
@@ -50,6 +52,8 @@ impl TestRawScorerProducer {
                     r#type: Default::default(),
                     quantile: None,
                     always_ram: Some(true),
+                    placement: None,
+                    auto_quantile: None,
                 }
                 .into(),
                 QuantizedVectorsStorageType::Immutable,
@@ -96,6 +100,22 @@ impl TestRawScorerProducer {
         .unwrap()
     }
 
+    pub fn filtered_scorer<'a>(
+        &'a self,
+        query: impl Into<QueryVector>,
+        filter_context: &'a dyn FilterContext,
+    ) -> FilteredScorer<'a> {
+        FilteredScorer::new(
+            query.into(),
+            &self.storage,
+            self.quantized_vectors.as_ref(),
+            Some(BoxCow::Borrowed(filter_context)),
+            &self.deleted_points,
+            HardwareCounterCell::new(),
+        )
+        .unwrap()
+    }
+
     pub fn internal_scorer(&self, point_id: PointOffsetType) -> FilteredScorer<'_> {
         FilteredScorer::new_internal(
             point_id,
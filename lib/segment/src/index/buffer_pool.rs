@@ -0,0 +1,211 @@
+//! Thread-local pool of buffers, which are reused among multiple consequent searches
+//!
+//! Each search needs a few temporary buffers: a heap of candidates, lists of point ids, scores.
+//! Searches are executed on long-living threads, so keeping released buffers per thread allows
+//! to reuse their memory without any synchronization.
+
+use std::cell::RefCell;
+use std::collections::BinaryHeap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::LocalKey;
+
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+
+use crate::index::visited_pool::VISITED_LISTS_COUNTERS;
+use crate::telemetry::SearchPoolsTelemetry;
+
+/// Max number of buffers of the same type, which are kept by a single thread.
+const THREAD_KEEP_LIMIT: usize = 8;
+
+/// Buffers with larger capacity are not returned to the pool,
+/// so a single search with a huge `ef` doesn't keep the memory forever.
+const MAX_KEPT_CAPACITY: usize = 64 * 1024;
+
+static BUFFERS_COUNTERS: PoolCounters = PoolCounters::new();
+
+thread_local! {
+    static POINT_IDS_POOL: RefCell<Vec<Vec<PointOffsetType>>> = const { RefCell::new(Vec::new()) };
+    static SCORES_POOL: RefCell<Vec<Vec<ScoreType>>> = const { RefCell::new(Vec::new()) };
+    static CANDIDATES_POOL: RefCell<Vec<BinaryHeap<ScoredPointOffset>>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Process-wide counters of requests to a pool
+#[derive(Debug)]
+pub(super) struct PoolCounters {
+    reused: AtomicUsize,
+    allocated: AtomicUsize,
+}
+
+impl PoolCounters {
+    pub const fn new() -> Self {
+        PoolCounters {
+            reused: AtomicUsize::new(0),
+            allocated: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn reused(&self) {
+        self.reused.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn allocated(&self) {
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> (usize, usize) {
+        (
+            self.reused.load(Ordering::Relaxed),
+            self.allocated.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Buffer, which could be kept in the thread-local pool
+pub trait PoolableBuffer: Default + Sized + 'static {
+    fn pool() -> &'static LocalKey<RefCell<Vec<Self>>>;
+
+    fn clear(&mut self);
+
+    fn capacity(&self) -> usize;
+}
+
+impl PoolableBuffer for Vec<PointOffsetType> {
+    fn pool() -> &'static LocalKey<RefCell<Vec<Self>>> {
+        &POINT_IDS_POOL
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+impl PoolableBuffer for Vec<ScoreType> {
+    fn pool() -> &'static LocalKey<RefCell<Vec<Self>>> {
+        &SCORES_POOL
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+}
+
+impl PoolableBuffer for BinaryHeap<ScoredPointOffset> {
+    fn pool() -> &'static LocalKey<RefCell<Vec<Self>>> {
+        &CANDIDATES_POOL
+    }
+
+    fn clear(&mut self) {
+        BinaryHeap::clear(self)
+    }
+
+    fn capacity(&self) -> usize {
+        BinaryHeap::capacity(self)
+    }
+}
+
+/// Buffer, taken from the thread-local pool. It is cleared and returned back on drop.
+#[derive(Debug)]
+pub struct PooledBuffer<T: PoolableBuffer> {
+    buffer: T,
+}
+
+impl<T: PoolableBuffer> PooledBuffer<T> {
+    pub fn get() -> Self {
+        let pooled = T::pool()
+            .try_with(|pool| pool.borrow_mut().pop())
+            .ok()
+            .flatten();
+        let buffer = match pooled {
+            Some(buffer) => {
+                BUFFERS_COUNTERS.reused();
+                buffer
+            }
+            None => {
+                BUFFERS_COUNTERS.allocated();
+                T::default()
+            }
+        };
+        PooledBuffer { buffer }
+    }
+}
+
+impl<T: PoolableBuffer> Default for PooledBuffer<T> {
+    fn default() -> Self {
+        PooledBuffer::get()
+    }
+}
+
+impl<T: PoolableBuffer> Drop for PooledBuffer<T> {
+    fn drop(&mut self) {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        if buffer.capacity() == 0 || buffer.capacity() > MAX_KEPT_CAPACITY {
+            return;
+        }
+        buffer.clear();
+        // Pool is not available if the thread is being destroyed, the buffer is just dropped then
+        let _ = T::pool().try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < THREAD_KEEP_LIMIT {
+                pool.push(buffer);
+            }
+        });
+    }
+}
+
+impl<T: PoolableBuffer> Deref for PooledBuffer<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.buffer
+    }
+}
+
+impl<T: PoolableBuffer> DerefMut for PooledBuffer<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.buffer
+    }
+}
+
+/// Reuse of visited lists and search buffers, accumulated over all searches of the process
+pub fn get_telemetry_data() -> SearchPoolsTelemetry {
+    let (visited_lists_reused, visited_lists_allocated) = VISITED_LISTS_COUNTERS.get();
+    let (buffers_reused, buffers_allocated) = BUFFERS_COUNTERS.get();
+    SearchPoolsTelemetry {
+        visited_lists_reused,
+        visited_lists_allocated,
+        buffers_reused,
+        buffers_allocated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pooled_buffer_reuse() {
+        let capacity = {
+            let mut buffer = PooledBuffer::<Vec<PointOffsetType>>::get();
+            buffer.extend(0..100);
+            buffer.capacity()
+        };
+
+        // Buffer is returned to the pool of this thread, cleared, but with the same memory
+        let buffer = PooledBuffer::<Vec<PointOffsetType>>::get();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), capacity);
+
+        let telemetry = get_telemetry_data();
+        assert!(telemetry.buffers_reused > 0);
+    }
+}
@@ -0,0 +1,404 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use itertools::Itertools;
+use log::debug;
+use memory::fadvise::clear_disk_cache;
+use rand::Rng;
+
+use super::graph::DiskGraph;
+use super::vamana::VamanaBuilder;
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{QueryVector, VectorRef};
+use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::CardinalityEstimation;
+use crate::index::hnsw_index::point_scorer::ScoringBudget;
+use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_search_common::{
+    IndexSearchesTelemetry, IndexedStorages, get_oversampled_top, new_search_scorer,
+    postprocess_search_result,
+};
+use crate::index::visited_pool::VisitedPool;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{DiskAnnConfig, Filter, SearchParams};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+const DISK_ANN_GRAPH_FILE: &str = "disk_ann_graph.bin";
+
+/// DiskANN index
+///
+/// Vamana graph with a single layer and a fixed max degree, stored on disk. Search is a greedy
+/// beam search from the medoid of the vectors, which reads links of a few hundreds of points.
+/// Combined with vectors stored on disk and quantized vectors in RAM, it has a small RAM footprint.
+#[derive(Debug)]
+pub struct DiskAnnIndex {
+    storages: IndexedStorages,
+    config: DiskAnnConfig,
+    path: PathBuf,
+    graph: DiskGraph,
+    visited_pool: VisitedPool,
+    searches_telemetry: IndexSearchesTelemetry,
+}
+
+pub struct DiskAnnIndexOpenArgs<'a> {
+    pub path: &'a Path,
+    pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    pub disk_ann_config: DiskAnnConfig,
+}
+
+impl DiskAnnIndex {
+    pub fn open(args: DiskAnnIndexOpenArgs<'_>) -> OperationResult<Self> {
+        let DiskAnnIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            disk_ann_config,
+        } = args;
+
+        let graph = DiskGraph::load(&Self::get_graph_path(path), false)?;
+
+        Ok(DiskAnnIndex {
+            storages: IndexedStorages {
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+            },
+            config: disk_ann_config,
+            path: path.to_owned(),
+            graph,
+            visited_pool: VisitedPool::new(),
+            searches_telemetry: IndexSearchesTelemetry::new(),
+        })
+    }
+
+    pub fn build<R: Rng + ?Sized>(
+        open_args: DiskAnnIndexOpenArgs<'_>,
+        build_args: VectorIndexBuildArgs<'_, R>,
+    ) -> OperationResult<Self> {
+        if Self::get_graph_path(open_args.path).exists() {
+            log::warn!(
+                "DiskANN index already exists at {:?}, skipping building",
+                open_args.path
+            );
+            debug_assert!(false);
+            return Self::open(open_args);
+        }
+
+        let DiskAnnIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            disk_ann_config,
+        } = open_args;
+        let VectorIndexBuildArgs {
+            permit,
+            old_indices: _,
+            gpu_device: _,
+            rng,
+            stopped,
+            hnsw_global_config: _,
+            feature_flags: _,
+        } = build_args;
+
+        create_dir_all(path)?;
+
+        let id_tracker_ref = id_tracker.borrow();
+        let vector_storage_ref = vector_storage.borrow();
+
+        let point_ids = id_tracker_ref
+            .iter_ids_excluding(vector_storage_ref.deleted_vector_bitslice())
+            .collect_vec();
+        let max_degree = disk_ann_config.max_degree();
+
+        debug!(
+            "building DiskANN graph with max degree {max_degree} for {} vectors with {} CPUs",
+            point_ids.len(),
+            permit.num_cpus,
+        );
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("disk-ann-build-{idx}"))
+            .num_threads(permit.num_cpus as usize)
+            .build()?;
+
+        let indexed_count = point_ids.len();
+        let builder = VamanaBuilder::new(
+            &vector_storage_ref,
+            id_tracker_ref.deleted_point_bitslice(),
+            &point_ids,
+            max_degree,
+            disk_ann_config.build_list_size(),
+            rng,
+        )?;
+        let graph = builder.build(&pool, point_ids, rng, stopped)?;
+
+        check_process_stopped(stopped)?;
+
+        let graph_path = Self::get_graph_path(path);
+        DiskGraph::save(
+            &graph_path,
+            &graph.links,
+            indexed_count,
+            max_degree,
+            graph.entry_point,
+        )?;
+        drop(graph);
+        let graph = DiskGraph::load(&graph_path, false)?;
+
+        drop(vector_storage_ref);
+        drop(id_tracker_ref);
+
+        Ok(DiskAnnIndex {
+            storages: IndexedStorages {
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+            },
+            config: disk_ann_config,
+            path: path.to_owned(),
+            graph,
+            visited_pool: VisitedPool::new(),
+            searches_telemetry: IndexSearchesTelemetry::new(),
+        })
+    }
+
+    fn get_graph_path(path: &Path) -> PathBuf {
+        path.join(DISK_ANN_GRAPH_FILE)
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        true
+    }
+
+    pub fn populate(&self) -> OperationResult<()> {
+        self.graph.populate();
+        Ok(())
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        clear_disk_cache(&Self::get_graph_path(&self.path))?;
+        Ok(())
+    }
+
+    fn list_size(&self, params: Option<&SearchParams>, top: usize) -> usize {
+        params
+            .and_then(|params| params.disk_ann_list_size)
+            .unwrap_or_else(|| self.config.search_list_size())
+            .max(top)
+    }
+
+    /// Size of the search list for a filtered search
+    ///
+    /// Only a fraction of traversed points passes the filter,
+    /// so more candidates are kept to find enough of them.
+    fn filtered_list_size(
+        &self,
+        list_size: usize,
+        query_cardinality: &CardinalityEstimation,
+    ) -> usize {
+        let available_vector_count = self
+            .storages
+            .vector_storage
+            .borrow()
+            .available_vector_count();
+        list_size
+            .saturating_mul(available_vector_count)
+            .checked_div(query_cardinality.exp)
+            .unwrap_or(available_vector_count)
+            .clamp(list_size, available_vector_count.max(list_size))
+    }
+
+    /// Beam search over the graph
+    ///
+    /// Points rejected by the filter are still traversed, but not included in the result.
+    fn search_graph(
+        &self,
+        vector: &QueryVector,
+        filter: Option<&Filter>,
+        top: usize,
+        list_size: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        if top == 0 || self.graph.indexed_count() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let id_tracker = self.storages.id_tracker.borrow();
+        let vector_storage = self.storages.vector_storage.borrow();
+        let quantized_vectors = self.storages.quantized_vectors.borrow();
+        let payload_index = self.storages.payload_index.borrow();
+
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+        let budget = ScoringBudget::from_params(params);
+
+        let filter_context = filter.map(|f| payload_index.filter_context(f, &hw_counter));
+
+        let mut scorer = new_search_scorer(
+            vector,
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            deleted_points,
+            params,
+            query_context.hardware_counter(),
+        )?
+        .with_budget(budget.as_ref());
+        let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
+
+        let is_result = |point_id: PointOffsetType| {
+            filter_context
+                .as_ref()
+                .is_none_or(|filter_context| filter_context.check(point_id))
+        };
+
+        let entry_point = self.graph.entry_point();
+        let entry = ScoredPointOffset {
+            idx: entry_point,
+            score: scorer.score_point(entry_point),
+        };
+
+        let mut visited_list = self.visited_pool.get(self.graph.point_count());
+        visited_list.check_and_update_visited(entry_point);
+        let mut search_context = SearchContext::new(list_size);
+        search_context.process_candidate(entry);
+        let mut result = FixedLengthPriorityQueue::new(oversampled_top);
+        if scorer.filters().check_vector(entry_point) && is_result(entry_point) {
+            result.push(entry);
+        }
+
+        let mut points_ids = Vec::with_capacity(self.graph.max_degree());
+        while let Some(candidate) = search_context.candidates.pop() {
+            check_process_stopped(&is_stopped)?;
+            if candidate.score < search_context.lower_bound() {
+                break;
+            }
+
+            points_ids.clear();
+            self.graph.for_each_link(candidate.idx, |link| {
+                if !visited_list.check_and_update_visited(link) {
+                    points_ids.push(link);
+                }
+            });
+
+            scorer
+                .score_points(&mut points_ids, 0)
+                .for_each(|score_point| {
+                    search_context.process_candidate(score_point);
+                    if is_result(score_point.idx) {
+                        result.push(score_point);
+                    }
+                });
+        }
+
+        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+            query_context.mark_search_truncated();
+        }
+
+        postprocess_search_result(
+            result.into_sorted_vec(),
+            id_tracker.deleted_point_bitslice(),
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            vector,
+            params,
+            top,
+            query_context.hardware_counter(),
+        )
+    }
+}
+
+impl VectorIndex for DiskAnnIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let list_size = self.list_size(params, top);
+
+        // Graph search scores about `list_size * max_degree` points
+        self.storages.search_with_plain_fallback(
+            &self.searches_telemetry,
+            vectors,
+            filter,
+            top,
+            params,
+            query_context,
+            list_size.saturating_mul(self.graph.max_degree()),
+            |vector, filter| match filter {
+                None => self.search_graph(vector, None, top, list_size, params, query_context),
+                Some((filter, query_cardinality)) => self.search_graph(
+                    vector,
+                    Some(filter),
+                    top,
+                    self.filtered_list_size(list_size, query_cardinality),
+                    params,
+                    query_context,
+                ),
+            },
+        )
+    }
+
+    fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            unfiltered_disk_ann: tm.unfiltered_index.lock().get_statistics(detail),
+            filtered_disk_ann: tm.filtered_index.lock().get_statistics(detail),
+            ..tm.plain_telemetry_data(detail)
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![Self::get_graph_path(&self.path)]
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        self.files()
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.graph.indexed_count()
+    }
+
+    fn size_of_searchable_vectors_in_bytes(&self) -> usize {
+        self.storages
+            .vector_storage
+            .borrow()
+            .size_of_available_vectors_in_bytes()
+    }
+
+    fn update_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: Option<VectorRef>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update DiskANN index"))
+    }
+}
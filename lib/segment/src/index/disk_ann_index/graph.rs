@@ -0,0 +1,152 @@
+use std::io::Write;
+use std::mem::size_of;
+use std::path::Path;
+
+use common::types::PointOffsetType;
+use io::file_operations::atomic_save;
+use memmap2::Mmap;
+use memory::madvise::{Advice, AdviceSetting, Madviseable};
+use memory::mmap_ops::open_read_mmap;
+use zerocopy::little_endian::{U32 as LittleU32, U64 as LittleU64};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Should be stored in [`DiskGraphHeader::version`].
+const DISK_GRAPH_VERSION: u64 = 1;
+
+/// File header of the on-disk graph.
+#[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
+#[repr(C)]
+struct DiskGraphHeader {
+    version: LittleU64,
+    /// Number of records, including ones of points which are not in the graph.
+    point_count: LittleU64,
+    /// Number of points in the graph.
+    indexed_count: LittleU64,
+    max_degree: LittleU64,
+    entry_point: LittleU64,
+}
+
+/*
+Links of each point are stored in a fixed-size record, so links of any point
+are read with a single random access, and only pages touched by searches are
+loaded into RAM.
+
+record:  | degree | link 0 | link 1 | ... | link max_degree-1 |
+           u32      u32      u32            u32
+
+Unused link slots are filled with zeroes.
+*/
+
+/// Vamana graph, stored on disk
+#[derive(Debug)]
+pub struct DiskGraph {
+    mmap: Mmap,
+    point_count: usize,
+    indexed_count: usize,
+    max_degree: usize,
+    entry_point: PointOffsetType,
+}
+
+impl DiskGraph {
+    pub fn save(
+        path: &Path,
+        links: &[Vec<PointOffsetType>],
+        indexed_count: usize,
+        max_degree: usize,
+        entry_point: PointOffsetType,
+    ) -> OperationResult<()> {
+        let header = DiskGraphHeader {
+            version: LittleU64::new(DISK_GRAPH_VERSION),
+            point_count: LittleU64::new(links.len() as u64),
+            indexed_count: LittleU64::new(indexed_count as u64),
+            max_degree: LittleU64::new(max_degree as u64),
+            entry_point: LittleU64::new(u64::from(entry_point)),
+        };
+
+        atomic_save(path, |writer| -> OperationResult<()> {
+            writer.write_all(header.as_bytes())?;
+            let mut record = vec![LittleU32::ZERO; max_degree + 1];
+            for point_links in links {
+                debug_assert!(point_links.len() <= max_degree);
+                record.fill(LittleU32::ZERO);
+                record[0] = LittleU32::new(point_links.len() as u32);
+                for (slot, &link) in record[1..].iter_mut().zip(point_links) {
+                    *slot = LittleU32::new(link);
+                }
+                writer.write_all(record.as_bytes())?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn load(path: &Path, populate: bool) -> OperationResult<Self> {
+        let mmap = open_read_mmap(path, AdviceSetting::Advice(Advice::Random), populate)?;
+
+        let (header, _) = DiskGraphHeader::read_from_prefix(&mmap).map_err(|_| {
+            OperationError::service_error(format!(
+                "DiskANN graph file {} is too small",
+                path.display(),
+            ))
+        })?;
+        if header.version.get() != DISK_GRAPH_VERSION {
+            return Err(OperationError::service_error(format!(
+                "Unsupported DiskANN graph version {}",
+                header.version.get(),
+            )));
+        }
+
+        let point_count = header.point_count.get() as usize;
+        let max_degree = header.max_degree.get() as usize;
+        let expected_len =
+            size_of::<DiskGraphHeader>() + point_count * (max_degree + 1) * size_of::<u32>();
+        if mmap.len() != expected_len {
+            return Err(OperationError::service_error(format!(
+                "DiskANN graph file {} has size {}, expected {expected_len}",
+                path.display(),
+                mmap.len(),
+            )));
+        }
+
+        Ok(Self {
+            point_count,
+            indexed_count: header.indexed_count.get() as usize,
+            max_degree,
+            entry_point: header.entry_point.get() as PointOffsetType,
+            mmap,
+        })
+    }
+
+    pub fn point_count(&self) -> usize {
+        self.point_count
+    }
+
+    pub fn indexed_count(&self) -> usize {
+        self.indexed_count
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    pub fn entry_point(&self) -> PointOffsetType {
+        self.entry_point
+    }
+
+    pub fn for_each_link(&self, point_id: PointOffsetType, mut f: impl FnMut(PointOffsetType)) {
+        if point_id as usize >= self.point_count {
+            return;
+        }
+        let record_size = (self.max_degree + 1) * size_of::<u32>();
+        let start = size_of::<DiskGraphHeader>() + point_id as usize * record_size;
+        let record = <[LittleU32]>::ref_from_bytes(&self.mmap[start..start + record_size])
+            .expect("record size is a multiple of u32 size");
+        let degree = (record[0].get() as usize).min(self.max_degree);
+        record[1..=degree].iter().for_each(|link| f(link.get()));
+    }
+
+    pub fn populate(&self) {
+        self.mmap.populate();
+    }
+}
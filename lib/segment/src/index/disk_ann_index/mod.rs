@@ -0,0 +1,6 @@
+pub mod disk_ann;
+mod graph;
+mod vamana;
+
+#[cfg(test)]
+mod tests;
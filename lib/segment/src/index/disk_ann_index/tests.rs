@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::flags::FeatureFlags;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::{Builder, TempDir};
+
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, QueryVector, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::id_tracker::IdTracker;
+use crate::index::VectorIndex;
+use crate::index::disk_ann_index::disk_ann::{DiskAnnIndex, DiskAnnIndexOpenArgs};
+use crate::index::disk_ann_index::vamana::is_dominated;
+use crate::segment::Segment;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{
+    Condition, DiskAnnConfig, Distance, ExtendedPointId, Filter, HasIdCondition, HnswGlobalConfig,
+    SearchParams, SeqNumberType,
+};
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 2_000;
+
+fn build_segment(dir: &TempDir, rng: &mut StdRng) -> Segment {
+    let hw_counter = HardwareCounterCell::new();
+    let mut segment = build_simple_segment(dir.path(), DIM, Distance::Cosine).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(rng, DIM);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+    segment
+}
+
+fn build_index(
+    segment: &Segment,
+    disk_ann_config: DiskAnnConfig,
+    rng: &mut StdRng,
+) -> (TempDir, DiskAnnIndex) {
+    let stopped = AtomicBool::new(false);
+    let disk_ann_dir = Builder::new().prefix("disk_ann_dir").tempdir().unwrap();
+    let permit = Arc::new(ResourcePermit::dummy(4));
+
+    let index = DiskAnnIndex::build(
+        DiskAnnIndexOpenArgs {
+            path: disk_ann_dir.path(),
+            id_tracker: segment.id_tracker.clone(),
+            vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            quantized_vectors: Default::default(),
+            payload_index: segment.payload_index.clone(),
+            disk_ann_config,
+        },
+        VectorIndexBuildArgs {
+            permit,
+            old_indices: &[],
+            gpu_device: None,
+            rng,
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+        },
+    )
+    .unwrap();
+    (disk_ann_dir, index)
+}
+
+#[test]
+fn test_disk_ann_search() {
+    let top = 10;
+    let attempts = 20;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment = build_segment(&dir, &mut rng);
+
+    let disk_ann_config = DiskAnnConfig {
+        max_degree: Some(16),
+        build_list_size: Some(64),
+        search_list_size: None,
+    };
+    let (disk_ann_dir, index) = build_index(&segment, disk_ann_config, &mut rng);
+    assert_eq!(index.indexed_vector_count(), NUM_VECTORS as usize);
+
+    let large_list = SearchParams {
+        disk_ann_list_size: Some(200),
+        ..Default::default()
+    };
+    let exact = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+
+    let mut hits = 0;
+    for _ in 0..attempts {
+        let query = QueryVector::from(random_vector(&mut rng, DIM));
+
+        let approximate = index
+            .search(&[&query], None, top, Some(&large_list), &Default::default())
+            .unwrap();
+        let expected = index
+            .search(&[&query], None, top, Some(&exact), &Default::default())
+            .unwrap();
+        assert_eq!(approximate[0].len(), top);
+        hits += approximate[0]
+            .iter()
+            .filter(|found| expected[0].iter().any(|point| point.idx == found.idx))
+            .count();
+    }
+    let recall = hits as f64 / (attempts * top) as f64;
+    assert!(recall > 0.9, "recall is too low: {recall}");
+
+    // Filtered search over the graph only returns matching points
+    let small_list = SearchParams {
+        disk_ann_list_size: Some(20),
+        ..Default::default()
+    };
+    let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+        (0..NUM_VECTORS)
+            .step_by(3)
+            .map(Into::into)
+            .collect::<ahash::AHashSet<_>>(),
+    )));
+    let query = QueryVector::from(random_vector(&mut rng, DIM));
+    let filtered = index
+        .search(
+            &[&query],
+            Some(&filter),
+            top,
+            Some(&small_list),
+            &Default::default(),
+        )
+        .unwrap();
+    assert_eq!(filtered[0].len(), top);
+    let id_tracker = segment.id_tracker.borrow();
+    for scored in &filtered[0] {
+        let ExtendedPointId::NumId(external_id) = id_tracker.external_id(scored.idx).unwrap()
+        else {
+            panic!("unexpected point id");
+        };
+        assert_eq!(external_id % 3, 0);
+    }
+    drop(id_tracker);
+
+    // Reopened graph gives the same results
+    drop(index);
+    let reopened = DiskAnnIndex::open(DiskAnnIndexOpenArgs {
+        path: disk_ann_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: segment.payload_index.clone(),
+        disk_ann_config,
+    })
+    .unwrap();
+    let filtered_reopened = reopened
+        .search(
+            &[&query],
+            Some(&filter),
+            top,
+            Some(&small_list),
+            &Default::default(),
+        )
+        .unwrap();
+    assert_eq!(filtered, filtered_reopened);
+}
+
+#[test]
+fn test_is_dominated_dot() {
+    // Candidate closer to the selected link than to the point is dominated
+    assert!(is_dominated(Distance::Dot, 1.2, 5.0, 3.0));
+    assert!(!is_dominated(Distance::Dot, 1.2, 3.0, 5.0));
+
+    // Scores above 1 must not turn the comparison around
+    assert!(is_dominated(Distance::Dot, 1.2, 10.0, 8.0));
+    assert!(!is_dominated(Distance::Dot, 1.2, 8.0, 10.0));
+
+    // For Cosine, `alpha` keeps candidates which are only slightly closer to the selected link
+    assert!(is_dominated(Distance::Cosine, 1.2, 0.9, 0.5));
+    assert!(!is_dominated(Distance::Cosine, 1.2, 0.6, 0.55));
+}
@@ -0,0 +1,264 @@
+use std::sync::atomic::AtomicBool;
+
+use bitvec::slice::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use itertools::Itertools;
+use parking_lot::RwLock;
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
+use crate::common::operation_error::{OperationResult, check_process_stopped};
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::visited_pool::VisitedPool;
+use crate::types::Distance;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Pruning parameter of the second pass, greater values keep more long-range links.
+const PRUNE_ALPHA: f32 = 1.2;
+
+/// Number of points sampled to find the medoid, which is used as the entry point.
+const MEDOID_SAMPLE_SIZE: usize = 64;
+
+/// Builder of a Vamana graph, as described in the DiskANN paper (Subramanya et al., NeurIPS 2019)
+///
+/// Points are inserted in two passes over a random order, with strict pruning at first,
+/// and relaxed pruning at second, which adds long-range links.
+pub(super) struct VamanaBuilder<'a> {
+    vector_storage: &'a VectorStorageEnum,
+    point_deleted: &'a BitSlice,
+    distance: Distance,
+    links: Vec<RwLock<Vec<PointOffsetType>>>,
+    max_degree: usize,
+    list_size: usize,
+    entry_point: PointOffsetType,
+    visited_pool: VisitedPool,
+}
+
+/// Graph, built by [`VamanaBuilder`]
+pub(super) struct VamanaGraph {
+    pub links: Vec<Vec<PointOffsetType>>,
+    pub entry_point: PointOffsetType,
+}
+
+impl<'a> VamanaBuilder<'a> {
+    pub fn new<R: Rng + ?Sized>(
+        vector_storage: &'a VectorStorageEnum,
+        point_deleted: &'a BitSlice,
+        point_ids: &[PointOffsetType],
+        max_degree: usize,
+        list_size: usize,
+        rng: &mut R,
+    ) -> OperationResult<Self> {
+        let total_vector_count = vector_storage.total_vector_count();
+        let mut builder = Self {
+            vector_storage,
+            point_deleted,
+            distance: vector_storage.distance(),
+            links: (0..total_vector_count)
+                .map(|_| RwLock::new(Vec::new()))
+                .collect(),
+            max_degree,
+            list_size: list_size.max(max_degree),
+            entry_point: 0,
+            visited_pool: VisitedPool::new(),
+        };
+        builder.entry_point = builder.approximate_medoid(point_ids, rng)?;
+        Ok(builder)
+    }
+
+    pub fn build<R: Rng + ?Sized>(
+        self,
+        pool: &ThreadPool,
+        mut point_ids: Vec<PointOffsetType>,
+        rng: &mut R,
+        stopped: &AtomicBool,
+    ) -> OperationResult<VamanaGraph> {
+        for alpha in [1.0, PRUNE_ALPHA] {
+            point_ids.shuffle(rng);
+            pool.install(|| {
+                point_ids
+                    .par_iter()
+                    .try_for_each(|&point_id| self.insert(point_id, alpha, stopped))
+            })?;
+        }
+
+        Ok(VamanaGraph {
+            links: self.links.into_iter().map(RwLock::into_inner).collect(),
+            entry_point: self.entry_point,
+        })
+    }
+
+    fn scorer(&self, point_id: PointOffsetType) -> OperationResult<FilteredScorer<'a>> {
+        FilteredScorer::new_internal(
+            point_id,
+            self.vector_storage,
+            None,
+            None,
+            self.point_deleted,
+            HardwareCounterCell::disposable(),
+        )
+    }
+
+    /// Point of a random sample with the best total score to the rest of the sample
+    fn approximate_medoid<R: Rng + ?Sized>(
+        &self,
+        point_ids: &[PointOffsetType],
+        rng: &mut R,
+    ) -> OperationResult<PointOffsetType> {
+        let sample = point_ids
+            .choose_multiple(rng, MEDOID_SAMPLE_SIZE)
+            .copied()
+            .collect_vec();
+
+        let mut medoid = None;
+        for &candidate in &sample {
+            let scorer = self.scorer(candidate)?;
+            let total_score: ScoreType = sample.iter().map(|&id| scorer.score_point(id)).sum();
+            if medoid.is_none_or(|(_, best_score)| total_score > best_score) {
+                medoid = Some((candidate, total_score));
+            }
+        }
+        Ok(medoid.map_or(0, |(point_id, _)| point_id))
+    }
+
+    fn insert(
+        &self,
+        point_id: PointOffsetType,
+        alpha: f32,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        check_process_stopped(stopped)?;
+
+        let mut scorer = self.scorer(point_id)?;
+        let mut candidates = self.greedy_search(&mut scorer, stopped)?;
+        for &link in self.links[point_id as usize].read().iter() {
+            candidates.push(ScoredPointOffset {
+                idx: link,
+                score: scorer.score_point(link),
+            });
+        }
+        candidates.retain(|candidate| candidate.idx != point_id);
+
+        let new_links = self.robust_prune(&scorer, candidates, alpha);
+        *self.links[point_id as usize].write() = new_links.clone();
+
+        // Add backward links, pruning neighbors which have too many of them
+        for link in new_links {
+            let mut neighbor_links = self.links[link as usize].write();
+            if neighbor_links.contains(&point_id) {
+                continue;
+            }
+            if neighbor_links.len() < self.max_degree {
+                neighbor_links.push(point_id);
+                continue;
+            }
+            let neighbor_scorer = self.scorer(link)?;
+            let candidates = neighbor_links
+                .iter()
+                .copied()
+                .chain(std::iter::once(point_id))
+                .map(|idx| ScoredPointOffset {
+                    idx,
+                    score: neighbor_scorer.score_point(idx),
+                })
+                .collect();
+            *neighbor_links = self.robust_prune(&neighbor_scorer, candidates, alpha);
+        }
+
+        Ok(())
+    }
+
+    /// Search the graph for the point of `scorer`, returns all expanded points
+    fn greedy_search(
+        &self,
+        scorer: &mut FilteredScorer,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let mut visited_list = self.visited_pool.get(self.links.len());
+        visited_list.check_and_update_visited(self.entry_point);
+        let mut search_context = SearchContext::new(self.list_size);
+        search_context.process_candidate(ScoredPointOffset {
+            idx: self.entry_point,
+            score: scorer.score_point(self.entry_point),
+        });
+
+        let mut expanded = Vec::new();
+        let mut points_ids = Vec::with_capacity(self.max_degree);
+        while let Some(candidate) = search_context.candidates.pop() {
+            check_process_stopped(stopped)?;
+            if candidate.score < search_context.lower_bound() {
+                break;
+            }
+            expanded.push(candidate);
+
+            points_ids.clear();
+            for &link in self.links[candidate.idx as usize].read().iter() {
+                if !visited_list.check_and_update_visited(link) {
+                    points_ids.push(link);
+                }
+            }
+            scorer
+                .score_points(&mut points_ids, 0)
+                .for_each(|score_point| search_context.process_candidate(score_point));
+        }
+
+        Ok(expanded)
+    }
+
+    /// Select up to `max_degree` diverse links out of `candidates`, RobustPrune of Vamana
+    fn robust_prune(
+        &self,
+        scorer: &FilteredScorer,
+        mut candidates: Vec<ScoredPointOffset>,
+        alpha: f32,
+    ) -> Vec<PointOffsetType> {
+        candidates.sort_unstable_by(|a, b| b.score.total_cmp(&a.score).then(a.idx.cmp(&b.idx)));
+        candidates.dedup_by_key(|candidate| candidate.idx);
+
+        let mut selected: Vec<PointOffsetType> = Vec::with_capacity(self.max_degree);
+        for candidate in candidates {
+            if selected.len() >= self.max_degree {
+                break;
+            }
+            let is_dominated = selected.iter().any(|&selected_id| {
+                let score_to_selected = scorer.score_internal(selected_id, candidate.idx);
+                is_dominated(self.distance, alpha, score_to_selected, candidate.score)
+            });
+            if !is_dominated {
+                selected.push(candidate.idx);
+            }
+        }
+        selected
+    }
+}
+
+/// Whether a candidate is dominated by an already selected link
+///
+/// Candidate is dominated, if it is `alpha` times closer to the selected link than to the point.
+/// For Euclid distance, `alpha` applies to squared distances.
+///
+/// Dot product of not normalized vectors is unbounded, `1 - score` can be negative and scaling it
+/// by `alpha` would turn the comparison around. As for custom metrics, which have unknown scale,
+/// candidates are dominated if they are simply closer to the selected link.
+pub(super) fn is_dominated(
+    distance: Distance,
+    alpha: f32,
+    score_to_selected: ScoreType,
+    score_to_point: ScoreType,
+) -> bool {
+    let to_distance = |score: ScoreType| match distance {
+        Distance::Cosine | Distance::RawCosine => Some(1.0 - score),
+        Distance::Euclid | Distance::Manhattan | Distance::Hamming | Distance::Jaccard => {
+            Some(-score)
+        }
+        Distance::Dot | Distance::Custom => None,
+    };
+    match (to_distance(score_to_selected), to_distance(score_to_point)) {
+        (Some(to_selected), Some(to_point)) => alpha * to_selected <= to_point,
+        _ => score_to_selected >= score_to_point,
+    }
+}
@@ -164,6 +164,7 @@ fn test_prefix_search() {
         stopwords: None,
         on_disk: None,
         stemmer: None,
+        ascii_folding: None,
     };
 
     let mut index =
@@ -213,6 +214,7 @@ fn test_phrase_matching() {
         phrase_matching: Some(true), // Enable phrase matching
         stopwords: None,
         stemmer: None,
+        ascii_folding: None,
     };
 
     let mut mutable_index =
@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// Folds accented latin characters of the token to their ASCII equivalents, e.g. "café" -> "cafe".
+///
+/// Characters, which don't have an ASCII equivalent, are kept as-is.
+pub fn fold(token: Cow<'_, str>) -> Cow<'_, str> {
+    if token.is_ascii() {
+        return token;
+    }
+
+    let mut folded = String::with_capacity(token.len());
+    for c in token.chars() {
+        fold_char(c, &mut folded);
+    }
+    Cow::Owned(folded)
+}
+
+fn fold_char(c: char, out: &mut String) {
+    if c.is_ascii() {
+        out.push(c);
+        return;
+    }
+
+    // Latin letters, which are not decomposed into a base letter and a diacritic mark
+    let replacement = match c {
+        'ß' => Some("ss"),
+        'æ' => Some("ae"),
+        'Æ' => Some("AE"),
+        'œ' => Some("oe"),
+        'Œ' => Some("OE"),
+        'ø' => Some("o"),
+        'Ø' => Some("O"),
+        'ł' => Some("l"),
+        'Ł' => Some("L"),
+        'đ' | 'ð' => Some("d"),
+        'Đ' | 'Ð' => Some("D"),
+        'þ' => Some("th"),
+        'Þ' => Some("TH"),
+        'ı' => Some("i"),
+        _ => None,
+    };
+    if let Some(replacement) = replacement {
+        out.push_str(replacement);
+        return;
+    }
+
+    // Decompose into a base character and combining marks, e.g. "é" -> "e" + "\u{301}".
+    // Only keep the result if the base is ASCII, so non-latin scripts are left untouched.
+    let mut base = String::new();
+    let mut foldable = true;
+    decompose_compatible(c, |part| {
+        if part.is_ascii() {
+            base.push(part);
+        } else if !is_combining_mark(part) {
+            foldable = false;
+        }
+    });
+
+    if foldable && !base.is_empty() {
+        out.push_str(&base);
+    } else {
+        out.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_folding() {
+        let cases = [
+            ("cafe", "cafe"),
+            ("café", "cafe"),
+            ("Ærøskøbing", "AEroskobing"),
+            ("straße", "strasse"),
+            ("Łódź", "Lodz"),
+            ("naïve", "naive"),
+            ("ﬁne", "fine"),
+            ("привет", "привет"),
+            ("日本語", "日本語"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(fold(Cow::Borrowed(input)), expected, "input: {input}");
+        }
+
+        assert!(matches!(fold(Cow::Borrowed("ascii")), Cow::Borrowed(_)));
+    }
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::sync::Arc;
+mod ascii_folding;
 mod japanese;
 mod multilingual;
 mod stemmer;
@@ -110,6 +111,7 @@ impl PrefixTokenizer {
                 };
 
                 let word_cow = tokens_processor.stem_if_enabled(word_cow);
+                let word_cow = tokens_processor.fold_if_enabled(word_cow);
 
                 if tokens_processor
                     .min_token_len
@@ -168,6 +170,7 @@ impl Tokenizer {
             phrase_matching: _,
             stopwords,
             stemmer,
+            ascii_folding,
         } = params;
 
         let lowercase = lowercase.unwrap_or(true);
@@ -177,6 +180,7 @@ impl Tokenizer {
             lowercase,
             stopwords_filter,
             stemmer.as_ref().map(Stemmer::from_algorithm),
+            ascii_folding.unwrap_or_default(),
             *min_token_len,
             *max_token_len,
         );
@@ -278,7 +282,7 @@ mod tests {
     fn test_prefix_tokenizer() {
         let text = "hello, мир!";
         let tokens_processor =
-            TokensProcessor::new(true, Default::default(), None, Some(1), Some(4));
+            TokensProcessor::new(true, Default::default(), None, false, Some(1), Some(4));
 
         let mut tokens = Vec::new();
         PrefixTokenizer::tokenize(text, &tokens_processor, |token| tokens.push(token));
@@ -296,7 +300,8 @@ mod tests {
     #[test]
     fn test_prefix_query_tokenizer() {
         let text = "hello, мир!";
-        let tokens_processor = TokensProcessor::new(true, Default::default(), None, None, Some(4));
+        let tokens_processor =
+            TokensProcessor::new(true, Default::default(), None, false, None, Some(4));
 
         let mut tokens = Vec::new();
         PrefixTokenizer::tokenize_query(text, &tokens_processor, |token| tokens.push(token));
@@ -324,7 +329,8 @@ mod tests {
         // Test stopwords getting applied
         let filter =
             StopwordsFilter::new(&Some(StopwordsInterface::new_custom(&["の", "は"])), false);
-        let tokens_processor = TokensProcessor::new(true, Arc::new(filter), None, None, None);
+        let tokens_processor =
+            TokensProcessor::new(true, Arc::new(filter), None, false, None, None);
         MultilingualTokenizer::tokenize(text, &tokens_processor, |token| tokens.push(token));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 2);
@@ -349,7 +355,8 @@ mod tests {
 
         // Test stopwords getting applied
         let filter = StopwordsFilter::new(&Some(StopwordsInterface::new_custom(&["是"])), false);
-        let tokens_processor = TokensProcessor::new(true, Arc::new(filter), None, None, None);
+        let tokens_processor =
+            TokensProcessor::new(true, Arc::new(filter), None, false, None, None);
         MultilingualTokenizer::tokenize(text, &tokens_processor, |token| tokens.push(token));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 3);
@@ -401,6 +408,7 @@ mod tests {
             phrase_matching: None,
             stopwords: None,
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -432,6 +440,7 @@ mod tests {
             phrase_matching: None,
             stopwords: Some(StopwordsInterface::Language(Language::English)),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -474,6 +483,7 @@ mod tests {
                 phrase_matching: None,
                 stopwords: Some(StopwordsInterface::Language(Language::English)),
                 stemmer: None,
+                ascii_folding: None,
             };
 
             let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -509,6 +519,7 @@ mod tests {
                 &["quick", "fox"],
             )),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -544,6 +555,7 @@ mod tests {
             phrase_matching: None,
             stopwords: Some(StopwordsInterface::new_custom(&["as", "the", "a"])),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -582,6 +594,7 @@ mod tests {
             phrase_matching: None,
             stopwords: Some(StopwordsInterface::Language(Language::English)),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -620,6 +633,7 @@ mod tests {
                 &["I'd"],
             )),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -661,6 +675,7 @@ mod tests {
             phrase_matching: None,
             stopwords: Some(StopwordsInterface::new_custom(&["the", "The", "LAZY"])),
             stemmer: None,
+            ascii_folding: None,
         };
 
         let tokenizer = Tokenizer::new_from_text_index_params(&params);
@@ -691,6 +706,7 @@ mod tests {
             true,
             Default::default(),
             Some(make_stemmer(SnowballLanguage::English)),
+            false,
             None,
             None,
         );
@@ -188,6 +188,7 @@ mod test {
                     language: SnowballLanguage::English,
                 },
             ))),
+            false,
             None,
             None,
         );
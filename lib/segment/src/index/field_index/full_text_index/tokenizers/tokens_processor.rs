@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::sync::Arc;
 
+use super::ascii_folding;
 use super::stemmer::Stemmer;
 use crate::index::field_index::full_text_index::stop_words::StopwordsFilter;
 
@@ -10,6 +11,7 @@ pub struct TokensProcessor {
     pub lowercase: bool,
     stopwords_filter: Arc<StopwordsFilter>, // TDOO(rocksdb): Remove once rocksdb has been removed!
     stemmer: Option<Stemmer>,
+    pub ascii_folding: bool,
     pub min_token_len: Option<usize>,
     pub max_token_len: Option<usize>,
 }
@@ -19,6 +21,7 @@ impl TokensProcessor {
         lowercase: bool,
         stopwords_filter: Arc<StopwordsFilter>,
         stemmer: Option<Stemmer>,
+        ascii_folding: bool,
         min_token_len: Option<usize>,
         max_token_len: Option<usize>,
     ) -> Self {
@@ -26,6 +29,7 @@ impl TokensProcessor {
             lowercase,
             stopwords_filter,
             stemmer,
+            ascii_folding,
             min_token_len,
             max_token_len,
         }
@@ -46,6 +50,15 @@ impl TokensProcessor {
         stemmer.stem(input)
     }
 
+    /// Folds accented latin characters to ASCII if enabled. Does nothing if folding is disabled.
+    pub fn fold_if_enabled<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
+        if !self.ascii_folding {
+            return input;
+        }
+
+        ascii_folding::fold(input)
+    }
+
     pub fn is_stopword(&self, token: &str) -> bool {
         self.stopwords_filter.is_stopword(token)
     }
@@ -59,6 +72,7 @@ impl TokensProcessor {
             lowercase,
             stopwords_filter,
             stemmer,
+            ascii_folding,
             min_token_len,
             max_token_len,
         } = self;
@@ -84,6 +98,11 @@ impl TokensProcessor {
             token_cow = stemmer.stem(token_cow);
         };
 
+        // Handle ASCII folding
+        if *ascii_folding {
+            token_cow = ascii_folding::fold(token_cow);
+        }
+
         // Handle token length
         if min_token_len.is_some_and(|min_len| token_cow.chars().count() < min_len)
             || (check_max_len
@@ -1,4 +1,4 @@
-use std::borrow::Borrow as _;
+use std::borrow::{Borrow as _, Cow};
 use std::collections::HashMap;
 use std::iter;
 use std::ops::Range;
@@ -10,8 +10,10 @@ use bitvec::vec::BitVec;
 use common::mmap_hashmap::Key;
 use common::types::PointOffsetType;
 use gridstore::Blob;
+use itertools::Either;
 #[cfg(feature = "rocksdb")]
 use parking_lot::RwLock;
+use roaring::RoaringBitmap;
 #[cfg(feature = "rocksdb")]
 use rocksdb::DB;
 
@@ -29,12 +31,14 @@ use crate::index::field_index::immutable_point_to_values::ImmutablePointToValues
 use crate::index::field_index::mmap_point_to_values::MmapValue;
 use crate::index::payload_config::StorageType;
 
+/// Max number of distinct values of a field, for which postings are kept as bitmaps
+///
+/// With few distinct values, posting lists are long and dense, so bitmaps are compact and allow
+/// fast set operations between them.
+const MAX_BITMAP_POSTINGS_VALUES: usize = 256;
+
 pub struct ImmutableMapIndex<N: MapIndexKey + Key + ?Sized> {
-    value_to_points: HashMap<N::Owned, ContainerSegment>,
-    /// Container holding a slice of point IDs per value. `value_to_point` holds the range per value.
-    /// Each slice MUST be sorted so that we can binary search over it.
-    value_to_points_container: Vec<PointOffsetType>,
-    deleted_value_to_points_container: BitVec,
+    postings: Postings<N>,
     point_to_values: ImmutablePointToValues<N::Owned>,
     /// Amount of point which have at least one indexed payload value
     indexed_points: usize,
@@ -49,6 +53,20 @@ enum Storage<N: MapIndexKey + Key + ?Sized> {
     Mmap(Box<MmapMapIndex<N>>),
 }
 
+/// Point IDs per value
+enum Postings<N: MapIndexKey + Key + ?Sized> {
+    Container {
+        value_to_points: HashMap<N::Owned, ContainerSegment>,
+        /// Container holding a slice of point IDs per value. `value_to_point` holds the range per value.
+        /// Each slice MUST be sorted so that we can binary search over it.
+        value_to_points_container: Vec<PointOffsetType>,
+        deleted_value_to_points_container: BitVec,
+    },
+    /// Bitmap of point IDs per value, for fields with few distinct values.
+    /// See [`MapIndexKey::bitmap_postings`].
+    Bitmaps(HashMap<N::Owned, RoaringBitmap>),
+}
+
 pub(super) struct ContainerSegment {
     /// Range in the container which holds point IDs for the value.
     range: Range<u32>,
@@ -81,55 +99,22 @@ where
             return Ok(None);
         };
         let MutableMapIndex::<N> {
-            map,
+            mut map,
             point_to_values,
             indexed_points,
             values_count,
             ..
         } = mutable;
 
-        let mut value_to_points = HashMap::new();
-        let mut value_to_points_container = Vec::with_capacity(values_count);
-
-        // flatten values-to-points map
-        for (value, points) in map {
-            let points = points.into_iter().collect::<Vec<_>>();
-            let container_len = value_to_points_container.len() as u32;
-            let range = container_len..container_len + points.len() as u32;
-            value_to_points.insert(
-                value,
-                ContainerSegment {
-                    count: range.len() as u32,
-                    range,
-                },
-            );
-            value_to_points_container.extend(points);
-        }
-
-        value_to_points.shrink_to_fit();
-
-        // Sort IDs in each slice of points
-        // This is very important because we binary search
-        for value in value_to_points.keys() {
-            if let Some((slice, _offset)) = Self::get_mut_point_ids_slice(
-                &value_to_points,
-                &mut value_to_points_container,
-                value.borrow(),
-            ) {
-                slice.sort_unstable();
-            } else {
-                debug_assert!(
-                    false,
-                    "value {} not found in value_to_points",
-                    value.borrow(),
-                );
-            }
-        }
+        let postings = if Self::use_bitmap_postings(map.len()) {
+            map.shrink_to_fit();
+            Postings::Bitmaps(map)
+        } else {
+            Self::flatten_postings(map, values_count)
+        };
 
         Ok(Some(Self {
-            value_to_points,
-            value_to_points_container,
-            deleted_value_to_points_container: BitVec::new(),
+            postings,
             point_to_values: ImmutablePointToValues::new(point_to_values),
             indexed_points,
             values_count,
@@ -154,7 +139,6 @@ where
 
         let mut indexed_points = 0;
         let mut values_count = 0;
-        let mut value_to_points = HashMap::new();
 
         // Create points to values mapping
         let mut point_to_values: Vec<Vec<N::Owned>> = vec![];
@@ -175,21 +159,62 @@ where
         }
         let point_to_values = ImmutablePointToValues::new(point_to_values);
 
-        // Create flattened values-to-points mapping
+        let postings = if Self::use_bitmap_postings(index.get_unique_values_count()) {
+            let mut map: HashMap<_, RoaringBitmap> = mapping()
+                .map(|(value, points)| (value.to_owned(), points.collect()))
+                .collect();
+            map.shrink_to_fit();
+            Postings::Bitmaps(map)
+        } else {
+            Self::flatten_postings(
+                mapping().map(|(value, points)| (value.to_owned(), points)),
+                values_count,
+            )
+        };
+
+        debug_assert_eq!(indexed_points, index.get_indexed_points());
+
+        // Index is now loaded into memory, clear cache of backing mmap storage
+        if let Err(err) = index.clear_cache() {
+            log::warn!("Failed to clear mmap cache of ram mmap map index: {err}");
+        }
+
+        Self {
+            postings,
+            point_to_values,
+            indexed_points,
+            values_count,
+            storage: Storage::Mmap(Box::new(index)),
+        }
+    }
+
+    /// Whether to keep postings of a field with `unique_values` distinct values as bitmaps
+    fn use_bitmap_postings(unique_values: usize) -> bool {
+        N::bitmap_postings() && unique_values <= MAX_BITMAP_POSTINGS_VALUES
+    }
+
+    /// Flatten point IDs of each value into a single container
+    fn flatten_postings<I: IntoIterator<Item = PointOffsetType>>(
+        postings: impl IntoIterator<Item = (N::Owned, I)>,
+        values_count: usize,
+    ) -> Postings<N> {
+        let mut value_to_points = HashMap::new();
         let mut value_to_points_container = Vec::with_capacity(values_count);
-        for (value, points) in mapping() {
-            let points = points.into_iter().collect::<Vec<_>>();
+
+        // flatten values-to-points map
+        for (value, points) in postings {
             let container_len = value_to_points_container.len() as u32;
-            let range = container_len..container_len + points.len() as u32;
+            value_to_points_container.extend(points);
+            let range = container_len..value_to_points_container.len() as u32;
             value_to_points.insert(
-                value.to_owned(),
+                value,
                 ContainerSegment {
                     count: range.len() as u32,
                     range,
                 },
             );
-            value_to_points_container.extend(points);
         }
+
         value_to_points.shrink_to_fit();
 
         // Sort IDs in each slice of points
@@ -210,21 +235,10 @@ where
             }
         }
 
-        debug_assert_eq!(indexed_points, index.get_indexed_points());
-
-        // Index is now loaded into memory, clear cache of backing mmap storage
-        if let Err(err) = index.clear_cache() {
-            log::warn!("Failed to clear mmap cache of ram mmap map index: {err}");
-        }
-
-        Self {
+        Postings::Container {
             value_to_points,
             value_to_points_container,
             deleted_value_to_points_container: BitVec::new(),
-            point_to_values,
-            indexed_points,
-            values_count,
-            storage: Storage::Mmap(Box::new(index)),
         }
     }
 
@@ -325,13 +339,28 @@ where
         if let Some(removed_values) = self.point_to_values.get_values(idx) {
             let mut removed_values_count = 0;
             for value in removed_values {
-                Self::remove_idx_from_value_list(
-                    &mut self.value_to_points,
-                    &mut self.value_to_points_container,
-                    &mut self.deleted_value_to_points_container,
-                    value.borrow(),
-                    idx,
-                );
+                match &mut self.postings {
+                    Postings::Container {
+                        value_to_points,
+                        value_to_points_container,
+                        deleted_value_to_points_container,
+                    } => Self::remove_idx_from_value_list(
+                        value_to_points,
+                        value_to_points_container,
+                        deleted_value_to_points_container,
+                        value.borrow(),
+                        idx,
+                    ),
+                    Postings::Bitmaps(value_to_points) => {
+                        let value: &N = value.borrow();
+                        if let Some(points) = value_to_points.get_mut(value) {
+                            points.remove(idx);
+                            if points.is_empty() {
+                                value_to_points.remove(value);
+                            }
+                        }
+                    }
+                }
 
                 // Update persisted storage
                 match self.storage {
@@ -443,55 +472,105 @@ where
     }
 
     pub fn get_unique_values_count(&self) -> usize {
-        self.value_to_points.len()
+        match &self.postings {
+            Postings::Container {
+                value_to_points, ..
+            } => value_to_points.len(),
+            Postings::Bitmaps(value_to_points) => value_to_points.len(),
+        }
     }
 
     pub fn get_count_for_value(&self, value: &N) -> Option<usize> {
-        self.value_to_points
-            .get(value)
-            .map(|entry| entry.count as usize)
+        match &self.postings {
+            Postings::Container {
+                value_to_points, ..
+            } => value_to_points.get(value).map(|entry| entry.count as usize),
+            Postings::Bitmaps(value_to_points) => value_to_points
+                .get(value)
+                .map(|points| points.len() as usize),
+        }
     }
 
     pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
-        self.value_to_points
-            .iter()
-            .map(|(k, entry)| (k.borrow(), entry.count as usize))
+        match &self.postings {
+            Postings::Container {
+                value_to_points, ..
+            } => Either::Left(
+                value_to_points
+                    .iter()
+                    .map(|(k, entry)| (k.borrow(), entry.count as usize)),
+            ),
+            Postings::Bitmaps(value_to_points) => Either::Right(
+                value_to_points
+                    .iter()
+                    .map(|(k, points)| (k.borrow(), points.len() as usize)),
+            ),
+        }
     }
 
     pub fn iter_values_map(&self) -> impl Iterator<Item = (&N, IdIter<'_>)> {
-        self.value_to_points.keys().map(move |k| {
-            (
-                k.borrow(),
-                Box::new(self.get_iterator(k.borrow())) as IdIter,
-            )
-        })
+        self.iter_values()
+            .map(move |value| (value, self.get_iterator(value)))
     }
 
     pub fn get_iterator(&self, value: &N) -> IdIter<'_> {
-        if let Some(entry) = self.value_to_points.get(value) {
-            let range = entry.range.start as usize..entry.range.end as usize;
-
-            let deleted_flags = self
-                .deleted_value_to_points_container
-                .iter()
-                .by_vals()
-                .skip(range.start)
-                .chain(std::iter::repeat(false));
-
-            let values = self.value_to_points_container[range]
-                .iter()
-                .zip(deleted_flags)
-                .filter(|(_, is_deleted)| !is_deleted)
-                .map(|(idx, _)| *idx);
-
-            Box::new(values)
-        } else {
-            Box::new(iter::empty::<PointOffsetType>())
+        match &self.postings {
+            Postings::Container {
+                value_to_points,
+                value_to_points_container,
+                deleted_value_to_points_container,
+            } => {
+                let Some(entry) = value_to_points.get(value) else {
+                    return Box::new(iter::empty::<PointOffsetType>());
+                };
+                let range = entry.range.start as usize..entry.range.end as usize;
+
+                let deleted_flags = deleted_value_to_points_container
+                    .iter()
+                    .by_vals()
+                    .skip(range.start)
+                    .chain(std::iter::repeat(false));
+
+                let values = value_to_points_container[range]
+                    .iter()
+                    .zip(deleted_flags)
+                    .filter(|(_, is_deleted)| !is_deleted)
+                    .map(|(idx, _)| *idx);
+
+                Box::new(values)
+            }
+            Postings::Bitmaps(value_to_points) => match value_to_points.get(value) {
+                Some(points) => Box::new(points.iter()),
+                None => Box::new(iter::empty::<PointOffsetType>()),
+            },
+        }
+    }
+
+    pub fn has_bitmap_postings(&self) -> bool {
+        matches!(self.postings, Postings::Bitmaps(_))
+    }
+
+    /// Points having the `value`, if postings are kept as bitmaps
+    pub fn get_bitmap(&self, value: &N) -> Option<Cow<'_, RoaringBitmap>> {
+        match &self.postings {
+            Postings::Container { .. } => None,
+            Postings::Bitmaps(value_to_points) => Some(
+                value_to_points
+                    .get(value)
+                    .map_or_else(|| Cow::Owned(RoaringBitmap::new()), Cow::Borrowed),
+            ),
         }
     }
 
     pub fn iter_values(&self) -> Box<dyn Iterator<Item = &N> + '_> {
-        Box::new(self.value_to_points.keys().map(|v| v.borrow()))
+        match &self.postings {
+            Postings::Container {
+                value_to_points, ..
+            } => Box::new(value_to_points.keys().map(|v| v.borrow())),
+            Postings::Bitmaps(value_to_points) => {
+                Box::new(value_to_points.keys().map(|v| v.borrow()))
+            }
+        }
     }
 
     pub fn storage_type(&self) -> StorageType {
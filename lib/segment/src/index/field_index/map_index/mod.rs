@@ -1,4 +1,4 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::collections::hash_map::Entry;
 use std::fmt::{Debug, Display};
 use std::hash::{BuildHasher, Hash};
@@ -9,6 +9,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use ahash::HashMap;
+use bitvec::vec::BitVec;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::mmap_hashmap::Key;
 use common::types::PointOffsetType;
@@ -19,6 +20,7 @@ use itertools::Itertools;
 use mmap_map_index::MmapMapIndex;
 #[cfg(feature = "rocksdb")]
 use parking_lot::RwLock;
+use roaring::RoaringBitmap;
 #[cfg(feature = "rocksdb")]
 use rocksdb::DB;
 use serde_json::Value;
@@ -40,8 +42,8 @@ use crate::index::payload_config::{IndexMutability, StorageType};
 use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, UuidIntType, ValueVariants,
+    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchPattern,
+    MatchValue, PayloadKeyType, UuidIntType, ValueVariants,
 };
 
 pub mod immutable_map_index;
@@ -65,6 +67,11 @@ pub trait MapIndexKey: Key + MmapValue + Eq + Display + Debug {
     fn gridstore_block_size() -> usize {
         size_of::<Self::Owned>()
     }
+
+    /// Whether immutable indexes keep postings as bitmaps, if the field has few distinct values
+    fn bitmap_postings() -> bool {
+        false
+    }
 }
 
 impl MapIndexKey for str {
@@ -77,6 +84,10 @@ impl MapIndexKey for str {
     fn gridstore_block_size() -> usize {
         BLOCK_SIZE_KEYWORD
     }
+
+    fn bitmap_postings() -> bool {
+        true
+    }
 }
 
 impl MapIndexKey for IntPayloadType {
@@ -248,6 +259,51 @@ where
         }
     }
 
+    /// Points having any of the `values`, as a union of their posting lists
+    ///
+    /// Bit of a point is set if it has any of the values. Bits beyond the end of the bitmap are
+    /// considered unset.
+    pub fn get_any_bitmap<'a>(
+        &self,
+        values: impl IntoIterator<Item = &'a N>,
+        hw_counter: &HardwareCounterCell,
+    ) -> BitVec
+    where
+        N: 'a,
+    {
+        let mut bitmap = BitVec::new();
+        for value in values {
+            for idx in self.get_iterator(value, hw_counter) {
+                let idx = idx as usize;
+                if idx >= bitmap.len() {
+                    bitmap.resize(idx + 1, false);
+                }
+                bitmap.set(idx, true);
+            }
+        }
+        bitmap
+    }
+
+    /// Whether [`Self::get_bitmap`] is available
+    pub fn has_bitmap_postings(&self) -> bool {
+        match self {
+            MapIndex::Mutable(_) => true,
+            MapIndex::Immutable(index) => index.has_bitmap_postings(),
+            MapIndex::Mmap(_) => false,
+        }
+    }
+
+    /// Points having the `value`, if the index keeps postings as bitmaps
+    ///
+    /// Bitmaps allow to evaluate filters with set operations, instead of checking each point.
+    pub fn get_bitmap(&self, value: &N) -> Option<Cow<'_, RoaringBitmap>> {
+        match self {
+            MapIndex::Mutable(index) => Some(index.get_bitmap(value)),
+            MapIndex::Immutable(index) => index.get_bitmap(value),
+            MapIndex::Mmap(_) => None,
+        }
+    }
+
     pub fn iter_values(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         match self {
             MapIndex::Mutable(index) => index.iter_values(),
@@ -723,6 +779,62 @@ where
     }
 }
 
+/// Max number of keywords checked against a pattern.
+///
+/// Keywords are not sorted, so each of them has to be checked. For larger vocabularies, matching
+/// points are only estimated from a sample of keywords, and the pattern is checked against
+/// values of each point instead.
+const PATTERN_MAX_CHECKED_VALUES: usize = 10_000;
+
+impl MapIndex<str> {
+    /// Keywords matching the `pattern`
+    ///
+    /// Returns `None` if the pattern has no literal prefix, as it can't narrow down the values then,
+    /// or if there are more than [`PATTERN_MAX_CHECKED_VALUES`] keywords to check.
+    fn pattern_values<'a>(
+        &'a self,
+        pattern: &'a MatchPattern,
+    ) -> Option<impl Iterator<Item = &'a str> + 'a> {
+        let prefix = pattern.literal_prefix();
+        if prefix.is_empty() || self.get_unique_values_count() > PATTERN_MAX_CHECKED_VALUES {
+            return None;
+        }
+        Some(
+            self.iter_values()
+                .filter(move |value| value.starts_with(prefix.as_str()) && pattern.is_match(value)),
+        )
+    }
+
+    /// Estimate number of points matching the `pattern` from a sample of keywords
+    ///
+    /// The estimation has no primary clause, so the index is not used to find matching points.
+    fn sampled_pattern_cardinality(&self, pattern: &MatchPattern) -> CardinalityEstimation {
+        let mut sampled_values = 0;
+        let mut sampled_matches = 0;
+        for (value, count) in self
+            .iter_counts_per_value()
+            .take(PATTERN_MAX_CHECKED_VALUES)
+        {
+            sampled_values += 1;
+            if pattern.is_match(value) {
+                sampled_matches += count;
+            }
+        }
+
+        let indexed_points = self.get_indexed_points();
+        let expected_matches = sampled_matches
+            .saturating_mul(self.get_unique_values_count())
+            .checked_div(sampled_values)
+            .unwrap_or(0);
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: expected_matches.min(indexed_points),
+            max: indexed_points,
+        }
+    }
+}
+
 impl PayloadFieldIndex for MapIndex<str> {
     fn count_indexed_points(&self) -> usize {
         self.get_indexed_points()
@@ -772,6 +884,11 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(Match::Pattern(pattern)) => Some(Box::new(
+                self.pattern_values(pattern)?
+                    .flat_map(move |keyword| self.get_iterator(keyword, hw_counter))
+                    .unique(),
+            )),
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(keywords) => Some(self.except_set(keywords, hw_counter)),
                 AnyVariants::Integers(other) => {
@@ -830,6 +947,27 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(Match::Pattern(pattern)) => {
+                if pattern.literal_prefix().is_empty() {
+                    return None;
+                }
+                let Some(values) = self.pattern_values(pattern) else {
+                    return Some(self.sampled_pattern_cardinality(pattern));
+                };
+                let estimations = values
+                    .map(|keyword| self.match_cardinality(keyword, hw_counter))
+                    .collect::<Vec<_>>();
+                let estimation = if estimations.is_empty() {
+                    CardinalityEstimation::exact(0)
+                } else {
+                    combine_should_estimations(&estimations, self.get_indexed_points())
+                };
+                Some(
+                    estimation.with_primary_clause(PrimaryCondition::Condition(Box::new(
+                        condition.clone(),
+                    ))),
+                )
+            }
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(keywords) => {
                     Some(self.except_cardinality(keywords.iter().map(|k| k.as_str()), hw_counter))
@@ -1619,4 +1757,163 @@ mod tests {
                 .equals_min_exp_max(&CardinalityEstimation::exact(0))
         );
     }
+
+    #[rstest]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Mutable))]
+    #[case(IndexType::MutableGridstore)]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Immutable))]
+    #[case(IndexType::Mmap)]
+    #[case(IndexType::RamMmap)]
+    fn test_any_bitmap(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![EcoString::from("AABB"), EcoString::from("UUFF")],
+            vec![EcoString::from("PPMM")],
+            vec![EcoString::from("IIBB")],
+            vec![EcoString::from("UUFF"), EcoString::from("IICC")],
+            vec![EcoString::from("PPGG")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let bitmap = index.get_any_bitmap(["UUFF", "PPMM", "missing"], &hw_counter);
+        assert_eq!(bitmap.iter_ones().collect_vec(), vec![0, 1, 3]);
+
+        let bitmap = index.get_any_bitmap(["missing"], &hw_counter);
+        assert!(bitmap.is_empty());
+    }
+
+    #[rstest]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Mutable))]
+    #[case(IndexType::MutableGridstore)]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Immutable))]
+    #[case(IndexType::Mmap)]
+    #[case(IndexType::RamMmap)]
+    fn test_bitmap_postings(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![EcoString::from("AABB"), EcoString::from("UUFF")],
+            vec![EcoString::from("PPMM")],
+            vec![EcoString::from("UUFF")],
+            vec![EcoString::from("UUFF"), EcoString::from("PPMM")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let mut index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        // Postings on disk are not kept as bitmaps
+        if let IndexType::Mmap = index_type {
+            assert!(!index.has_bitmap_postings());
+            assert!(index.get_bitmap("UUFF").is_none());
+            return;
+        }
+        assert!(index.has_bitmap_postings());
+
+        let get_bitmap =
+            |index: &MapIndex<str>, value| index.get_bitmap(value).unwrap().iter().collect_vec();
+        assert_eq!(get_bitmap(&index, "UUFF"), vec![0, 2, 3]);
+        assert_eq!(get_bitmap(&index, "PPMM"), vec![1, 3]);
+        assert_eq!(get_bitmap(&index, "missing"), Vec::<PointOffsetType>::new());
+
+        // Postings are iterated from bitmaps as well
+        let hw_counter = HardwareCounterCell::new();
+        assert_eq!(
+            index.get_iterator("UUFF", &hw_counter).collect_vec(),
+            vec![0, 2, 3],
+        );
+        assert_eq!(index.get_count_for_value("UUFF", &hw_counter), Some(3));
+
+        index.remove_point(3).unwrap();
+        assert_eq!(get_bitmap(&index, "UUFF"), vec![0, 2]);
+        assert_eq!(get_bitmap(&index, "PPMM"), vec![1]);
+        assert_eq!(index.get_count_for_value("PPMM", &hw_counter), Some(1));
+    }
+
+    #[rstest]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Mutable))]
+    #[case(IndexType::MutableGridstore)]
+    #[cfg_attr(feature = "rocksdb", case(IndexType::Immutable))]
+    #[case(IndexType::Mmap)]
+    #[case(IndexType::RamMmap)]
+    fn test_pattern_filter(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![EcoString::from("neuroscience"), EcoString::from("biology")],
+            vec![EcoString::from("neurons")],
+            vec![EcoString::from("euro")],
+            vec![EcoString::from("neuro"), EcoString::from("neurons")],
+            vec![EcoString::from("physics")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let hw_counter = HardwareCounterCell::new();
+        let condition = |pattern: &str| {
+            FieldCondition::new_match(
+                crate::json_path::JsonPath::new("test"),
+                Match::new_pattern(pattern),
+            )
+        };
+
+        let neuro = condition("neuro*");
+        let points = index
+            .filter(&neuro, &hw_counter)
+            .unwrap()
+            .sorted()
+            .collect_vec();
+        assert_eq!(points, vec![0, 1, 3]);
+        let estimation = index.estimate_cardinality(&neuro, &hw_counter).unwrap();
+        assert!(estimation.min <= 3 && 3 <= estimation.max);
+
+        let neurons = condition("neuro?s");
+        let points = index
+            .filter(&neurons, &hw_counter)
+            .unwrap()
+            .sorted()
+            .collect_vec();
+        assert_eq!(points, vec![1, 3]);
+
+        // Without a literal prefix, the pattern is checked against payload values instead
+        let no_prefix = condition("*euro*");
+        assert!(index.filter(&no_prefix, &hw_counter).is_none());
+        assert!(
+            index
+                .estimate_cardinality(&no_prefix, &hw_counter)
+                .is_none()
+        );
+    }
+
+    #[rstest]
+    #[case(IndexType::MutableGridstore)]
+    #[case(IndexType::Mmap)]
+    fn test_pattern_filter_large_vocabulary(#[case] index_type: IndexType) {
+        let data = (0..PATTERN_MAX_CHECKED_VALUES * 2)
+            .map(|i| vec![EcoString::from(format!("key_{i}"))])
+            .collect_vec();
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let hw_counter = HardwareCounterCell::new();
+        let condition = FieldCondition::new_match(
+            crate::json_path::JsonPath::new("test"),
+            Match::new_pattern("key_1*"),
+        );
+
+        // Too many keywords to check each of them, so matching points are only estimated
+        assert!(index.filter(&condition, &hw_counter).is_none());
+        let estimation = index.estimate_cardinality(&condition, &hw_counter).unwrap();
+        assert!(estimation.primary_clauses.is_empty());
+        let matching = data
+            .iter()
+            .filter(|values| values[0].starts_with("key_1"))
+            .count();
+        assert!(estimation.min <= matching && matching <= estimation.max);
+        assert!(estimation.exp > 0);
+    }
 }
@@ -1,4 +1,4 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
 use std::iter;
 use std::path::PathBuf;
@@ -418,6 +418,13 @@ where
             .unwrap_or_else(|| Box::new(iter::empty::<PointOffsetType>()))
     }
 
+    /// Points having the `value`
+    pub fn get_bitmap(&self, value: &N) -> Cow<'_, RoaringBitmap> {
+        self.map
+            .get(value)
+            .map_or_else(|| Cow::Owned(RoaringBitmap::new()), Cow::Borrowed)
+    }
+
     pub fn iter_values(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.map.keys().map(|v| v.borrow()))
     }
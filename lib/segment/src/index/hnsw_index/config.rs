@@ -29,6 +29,12 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Time it took to build the graph, in milliseconds
+    #[serde(default)]
+    pub build_duration_ms: Option<u64>,
+    /// Number of deleted points, which the graph was last healed for
+    #[serde(default)]
+    pub healed_deleted_count: usize,
 }
 
 impl HnswGraphConfig {
@@ -50,6 +56,8 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            build_duration_ms: None,
+            healed_deleted_count: 0,
         }
     }
 
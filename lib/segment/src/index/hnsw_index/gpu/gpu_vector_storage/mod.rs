@@ -110,6 +110,12 @@ impl ShaderBuilderParameters for GpuVectorStorage {
             Distance::Manhattan => {
                 defines.insert("MANHATTAN_DISTANCE".to_owned(), None);
             }
+            distance @ (Distance::Hamming
+            | Distance::Jaccard
+            | Distance::Custom
+            | Distance::RawCosine) => {
+                unreachable!("GPU vector storage is never created for {distance:?} distance")
+            }
         }
 
         if let Some(quantization) = &self.quantization {
@@ -138,13 +144,18 @@ impl GpuVectorStorage {
         force_half_precision: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<Self> {
+        let distance = vector_storage.distance();
+        if matches!(
+            distance,
+            Distance::Hamming | Distance::Jaccard | Distance::Custom | Distance::RawCosine
+        ) {
+            return Err(OperationError::from(gpu::GpuError::NotSupported(format!(
+                "{distance:?} distance is not supported on GPU",
+            ))));
+        }
+
         if let Some(quantized_storage) = quantized_storage {
-            Self::new_quantized(
-                device,
-                vector_storage.distance(),
-                quantized_storage.get_storage(),
-                stopped,
-            )
+            Self::new_quantized(device, distance, quantized_storage.get_storage(), stopped)
         } else {
             Self::new_from_vector_storage(device, vector_storage, force_half_precision, stopped)
         }
@@ -229,6 +240,11 @@ impl GpuVectorStorage {
                 None,
                 stopped,
             ),
+            QuantizedVectorStorage::ResidualRam(_) | QuantizedVectorStorage::ResidualMmap(_) => {
+                Err(OperationError::from(gpu::GpuError::NotSupported(
+                    "Residual quantization is not supported on GPU".to_string(),
+                )))
+            }
             QuantizedVectorStorage::ScalarRamMulti(quantized_storage) => Self::new_sq(
                 device.clone(),
                 distance,
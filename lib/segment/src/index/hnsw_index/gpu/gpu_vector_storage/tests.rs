@@ -20,6 +20,7 @@ use crate::types::{
     ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
     ScalarQuantizationConfig,
 };
+use crate::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use crate::vector_storage::dense::simple_dense_vector_storage::{
     open_simple_dense_byte_vector_storage, open_simple_dense_full_vector_storage,
     open_simple_dense_half_vector_storage,
@@ -122,6 +123,8 @@ fn test_gpu_vector_storage_sq(
     let quantization_config = QuantizationConfig::Scalar(ScalarQuantization {
         scalar: ScalarQuantizationConfig {
             always_ram: Some(true),
+            placement: None,
+            auto_quantile: None,
             r#type: crate::types::ScalarType::Int8,
             quantile: Some(0.99),
         },
@@ -222,6 +225,7 @@ fn test_gpu_vector_storage_bq(
     let quantization_config = QuantizationConfig::Binary(BinaryQuantization {
         binary: BinaryQuantizationConfig {
             always_ram: Some(true),
+            placement: None,
             encoding: Some(encoding),
             query_encoding: None,
         },
@@ -312,6 +316,7 @@ fn test_gpu_vector_storage_pq(
     let quantization_config = QuantizationConfig::Product(ProductQuantization {
         product: ProductQuantizationConfig {
             always_ram: Some(true),
+            placement: None,
             compression: crate::types::CompressionRatio::X8,
         },
     });
@@ -512,6 +517,9 @@ fn get_precision(storage_type: TestStorageType, dim: usize, distance: Distance)
         Distance::Dot => 0.01,
         Distance::Euclid => dim as f32 * 0.001,
         Distance::Manhattan => dim as f32 * 0.001,
+        Distance::Hamming | Distance::Jaccard | Distance::Custom | Distance::RawCosine => {
+            unreachable!("{distance:?} distance is not supported on GPU")
+        }
     };
     match storage_type.element_type() {
         TestElementType::Float32 => distance_persision,
@@ -557,9 +565,15 @@ fn create_vector_storage_f32(
     distance: Distance,
 ) -> VectorStorageEnum {
     let mut rnd = StdRng::seed_from_u64(42);
-    let mut vector_storage =
-        open_simple_dense_full_vector_storage(db, DB_VECTOR_CF, dim, distance, &false.into())
-            .unwrap();
+    let mut vector_storage = open_simple_dense_full_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        dim,
+        distance,
+        ChunkedVectorsConfig::default(),
+        &false.into(),
+    )
+    .unwrap();
     for i in 0..num_vectors {
         let vec = random_vector(&mut rnd, dim);
         let vec = distance.preprocess_vector::<VectorElementType>(vec);
@@ -578,9 +592,15 @@ fn create_vector_storage_f16(
     distance: Distance,
 ) -> VectorStorageEnum {
     let mut rnd = StdRng::seed_from_u64(42);
-    let mut vector_storage =
-        open_simple_dense_half_vector_storage(db, DB_VECTOR_CF, dim, distance, &false.into())
-            .unwrap();
+    let mut vector_storage = open_simple_dense_half_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        dim,
+        distance,
+        ChunkedVectorsConfig::default(),
+        &false.into(),
+    )
+    .unwrap();
     for i in 0..num_vectors {
         let vec = random_vector(&mut rnd, dim);
         let vec = distance.preprocess_vector::<VectorElementTypeHalf>(vec);
@@ -599,9 +619,15 @@ fn create_vector_storage_u8(
     distance: Distance,
 ) -> VectorStorageEnum {
     let mut rnd = StdRng::seed_from_u64(42);
-    let mut vector_storage =
-        open_simple_dense_byte_vector_storage(db, DB_VECTOR_CF, dim, distance, &false.into())
-            .unwrap();
+    let mut vector_storage = open_simple_dense_byte_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        dim,
+        distance,
+        ChunkedVectorsConfig::default(),
+        &false.into(),
+    )
+    .unwrap();
     for i in 0..num_vectors {
         let vec = random_dense_byte_vector(&mut rnd, dim);
         let vec = distance.preprocess_vector::<VectorElementTypeByte>(vec);
@@ -627,6 +653,7 @@ fn create_vector_storage_f32_multi(
         dim,
         distance,
         multivector_config,
+        ChunkedVectorsConfig::default(),
         &false.into(),
     )
     .unwrap();
@@ -661,6 +688,7 @@ fn create_vector_storage_f16_multi(
         dim,
         distance,
         multivector_config,
+        ChunkedVectorsConfig::default(),
         &false.into(),
     )
     .unwrap();
@@ -695,6 +723,7 @@ fn create_vector_storage_u8_multi(
         dim,
         distance,
         multivector_config,
+        ChunkedVectorsConfig::default(),
         &false.into(),
     )
     .unwrap();
@@ -1,12 +1,11 @@
 use std::borrow::Cow;
 use std::cmp::max;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use io::file_operations::{atomic_save, read_bin};
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use super::HnswM;
@@ -16,6 +15,7 @@ use crate::common::operation_error::{
     CancellableResult, OperationError, OperationResult, check_process_stopped,
 };
 use crate::common::utils::rev_range;
+use crate::index::buffer_pool::PooledBuffer;
 use crate::index::hnsw_index::graph_links::{GraphLinksFormatParam, serialize_graph_links};
 use crate::index::hnsw_index::point_scorer::{FilteredBytesScorer, FilteredScorer, ScorerFilters};
 use crate::index::hnsw_index::search_context::SearchContext;
@@ -47,6 +47,43 @@ pub struct GraphLayers {
     pub(super) links: GraphLinks,
     pub(super) entry_points: EntryPoints,
     pub(super) visited_pool: VisitedPool,
+    pub(super) search_counters: GraphSearchCounters,
+}
+
+/// Counters of searches through the graph, accumulated since it was loaded
+#[derive(Debug, Default)]
+pub struct GraphSearchCounters {
+    unfiltered: AtomicUsize,
+    filtered: AtomicUsize,
+    /// Total number of candidates, whose links were explored on the base layer
+    hops: AtomicUsize,
+}
+
+impl GraphSearchCounters {
+    fn record(&self, filtered: bool, hops: usize) {
+        let searches = if filtered {
+            &self.filtered
+        } else {
+            &self.unfiltered
+        };
+        searches.fetch_add(1, Ordering::Relaxed);
+        self.hops.fetch_add(hops, Ordering::Relaxed);
+    }
+
+    pub fn unfiltered(&self) -> usize {
+        self.unfiltered.load(Ordering::Relaxed)
+    }
+
+    pub fn filtered(&self) -> usize {
+        self.filtered.load(Ordering::Relaxed)
+    }
+
+    /// Average number of candidates explored on the base layer per search,
+    /// `None` if there were no searches
+    pub fn avg_hops(&self) -> Option<f64> {
+        let searches = self.unfiltered() + self.filtered();
+        (searches > 0).then(|| self.hops.load(Ordering::Relaxed) as f64 / searches as f64)
+    }
 }
 
 pub trait GraphLayersBase {
@@ -69,7 +106,7 @@ pub trait GraphLayersBase {
         is_stopped: &AtomicBool,
     ) -> CancellableResult<()> {
         let limit = self.get_m(level);
-        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut points_ids = PooledBuffer::<Vec<PointOffsetType>>::get();
 
         while let Some(candidate) = searcher.candidates.pop() {
             check_process_stopped(is_stopped)?;
@@ -77,6 +114,7 @@ pub trait GraphLayersBase {
             if candidate.score < searcher.lower_bound() {
                 break;
             }
+            searcher.hops += 1;
 
             points_ids.clear();
             self.for_each_link(candidate.idx, level, |link| {
@@ -91,6 +129,84 @@ pub trait GraphLayersBase {
                     searcher.process_candidate(score_point);
                     visited_list.check_and_update_visited(score_point.idx);
                 });
+
+            // Following candidates can't be scored anyway
+            if points_scorer.is_budget_exhausted() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Greedy search for closest points within a single graph layer, ACORN-1 style.
+    ///
+    /// Links rejected by the filter are not dead ends: their own links are explored as well.
+    /// This keeps the filtered subgraph connected when the filter rejects most of the points.
+    ///
+    /// Each link is checked against the filter once. Accepted links are marked visited as soon
+    /// as they are collected, so links reachable through several rejected points are scored once.
+    fn _search_on_level_acorn(
+        &self,
+        searcher: &mut SearchContext,
+        level: usize,
+        visited_list: &mut VisitedListHandle,
+        points_scorer: &mut FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<()> {
+        let limit = self.get_m(level);
+        let mut points_ids = PooledBuffer::<Vec<PointOffsetType>>::get();
+        let mut rejected_ids = PooledBuffer::<Vec<PointOffsetType>>::get();
+
+        while let Some(candidate) = searcher.candidates.pop() {
+            check_process_stopped(is_stopped)?;
+
+            if candidate.score < searcher.lower_bound() {
+                break;
+            }
+            searcher.hops += 1;
+
+            points_ids.clear();
+            rejected_ids.clear();
+            let filters = points_scorer.filters();
+            self.for_each_link(candidate.idx, level, |link| {
+                if visited_list.check(link) {
+                    return;
+                }
+                if !filters.check_vector(link) {
+                    rejected_ids.push(link);
+                } else if points_ids.len() < limit {
+                    visited_list.check_and_update_visited(link);
+                    points_ids.push(link);
+                }
+            });
+
+            // Second hop, through the rejected points
+            for &rejected in rejected_ids.iter() {
+                if points_ids.len() >= limit {
+                    break;
+                }
+                // Rejected points are never scored, mark them to expand each of them only once
+                visited_list.check_and_update_visited(rejected);
+                self.for_each_link(rejected, level, |link| {
+                    if points_ids.len() < limit
+                        && !visited_list.check(link)
+                        && filters.check_vector(link)
+                    {
+                        visited_list.check_and_update_visited(link);
+                        points_ids.push(link);
+                    }
+                });
+            }
+
+            points_scorer
+                .score_checked_points(&mut points_ids)
+                .for_each(|score_point| searcher.process_candidate(score_point));
+
+            // Following candidates can't be scored anyway
+            if points_scorer.is_budget_exhausted() {
+                break;
+            }
         }
 
         Ok(())
@@ -104,19 +220,43 @@ pub trait GraphLayersBase {
         points_scorer: &mut FilteredScorer,
         is_stopped: &AtomicBool,
     ) -> CancellableResult<FixedLengthPriorityQueue<ScoredPointOffset>> {
+        self.search_on_level_impl(level_entry, level, ef, points_scorer, false, is_stopped)
+            .map(|search_context| search_context.nearest)
+    }
+
+    /// Same as [`Self::search_on_level`], but uses ACORN traversal if `acorn` is set.
+    fn search_on_level_impl(
+        &self,
+        level_entry: ScoredPointOffset,
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+        acorn: bool,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<SearchContext> {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
         let mut search_context = SearchContext::new(ef);
         search_context.process_candidate(level_entry);
 
-        self._search_on_level(
-            &mut search_context,
-            level,
-            &mut visited_list,
-            points_scorer,
-            is_stopped,
-        )?;
-        Ok(search_context.nearest)
+        if acorn {
+            self._search_on_level_acorn(
+                &mut search_context,
+                level,
+                &mut visited_list,
+                points_scorer,
+                is_stopped,
+            )?;
+        } else {
+            self._search_on_level(
+                &mut search_context,
+                level,
+                &mut visited_list,
+                points_scorer,
+                is_stopped,
+            )?;
+        }
+        Ok(search_context)
     }
 
     /// Greedy searches for entry point of level `target_level`.
@@ -129,7 +269,7 @@ pub trait GraphLayersBase {
         points_scorer: &mut FilteredScorer,
         is_stopped: &AtomicBool,
     ) -> CancellableResult<ScoredPointOffset> {
-        let mut links_buffer = Vec::new();
+        let mut links_buffer = PooledBuffer::<Vec<PointOffsetType>>::get();
         let mut result = None;
         let mut level_entry = entry_point;
         for level in rev_range(top_level, target_level) {
@@ -231,6 +371,7 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
                 break;
             }
 
+            links_searcher.hops += 1;
             points.clear();
             let (base_vector, links_iter) = self.links_with_vectors(candidate.idx, level);
             links_iter.for_each(|(link, link_vector)| {
@@ -255,6 +396,8 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
     }
 
     /// Similar to [`GraphLayersBase::search_on_level`].
+    ///
+    /// Returns context of the search by base vectors, with hops of the search by link vectors.
     fn search_on_level_with_vectors(
         &self,
         level_entry: ScoredPointOffset,
@@ -263,7 +406,7 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
         links_scorer: &FilteredBytesScorer,
         base_scorer: &dyn QueryScorerBytes,
         is_stopped: &AtomicBool,
-    ) -> CancellableResult<FixedLengthPriorityQueue<ScoredPointOffset>> {
+    ) -> CancellableResult<SearchContext> {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
         let mut links_search_context = SearchContext::new(ef);
@@ -279,7 +422,8 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
             base_scorer,
             is_stopped,
         )?;
-        Ok(base_search_context.nearest)
+        base_search_context.hops = links_search_context.hops;
+        Ok(base_search_context)
     }
 
     /// Similar to [`GraphLayersBase::search_entry`].
@@ -409,11 +553,52 @@ impl GraphLayers {
     }
 
     pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        self.search_impl(
+            top,
+            ef,
+            points_scorer,
+            custom_entry_points,
+            false,
+            is_stopped,
+        )
+    }
+
+    /// Filtered search, which also explores links of points rejected by the filter.
+    ///
+    /// Improves recall for filters, which select a small share of points, at the cost of more
+    /// links traversed. See [`GraphLayersBase::_search_on_level_acorn`].
+    pub fn search_acorn(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        self.search_impl(
+            top,
+            ef,
+            points_scorer,
+            custom_entry_points,
+            true,
+            is_stopped,
+        )
+    }
+
+    fn search_impl(
         &self,
         top: usize,
         ef: usize,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
+        acorn: bool,
         is_stopped: &AtomicBool,
     ) -> CancellableResult<Vec<ScoredPointOffset>> {
         let Some(entry_point) = self.get_entry_point(points_scorer.filters(), custom_entry_points)
@@ -428,14 +613,21 @@ impl GraphLayers {
             &mut points_scorer,
             is_stopped,
         )?;
-        let nearest = self.search_on_level(
+        let search_context = self.search_on_level_impl(
             zero_level_entry,
             0,
             max(top, ef),
             &mut points_scorer,
+            acorn,
             is_stopped,
         )?;
-        Ok(nearest.into_iter_sorted().take(top).collect_vec())
+        self.search_counters.record(
+            points_scorer.filters().has_payload_filter(),
+            search_context.hops,
+        );
+        let mut result = search_context.nearest.into_sorted_vec();
+        result.truncate(top);
+        Ok(result)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -462,7 +654,7 @@ impl GraphLayers {
             links_scorer_bytes,
             is_stopped,
         )?;
-        let nearest = self.search_on_level_with_vectors(
+        let search_context = self.search_on_level_with_vectors(
             zero_level_entry,
             0,
             max(top, ef),
@@ -470,7 +662,13 @@ impl GraphLayers {
             base_scorer,
             is_stopped,
         )?;
-        Ok(nearest.into_iter_sorted().take(top).collect_vec())
+        self.search_counters.record(
+            links_scorer.filters().has_payload_filter(),
+            search_context.hops,
+        );
+        let mut result = search_context.nearest.into_sorted_vec();
+        result.truncate(top);
+        Ok(result)
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -487,6 +685,13 @@ impl GraphLayers {
         }
     }
 
+    /// Path of healed links, until they are applied in place of the current ones
+    pub fn get_healed_links_path(path: &Path, format: GraphLinksFormat) -> PathBuf {
+        let mut links_path = Self::get_links_path(path, format).into_os_string();
+        links_path.push(".healed");
+        PathBuf::from(links_path)
+    }
+
     pub fn files(&self, path: &Path) -> Vec<PathBuf> {
         vec![
             GraphLayers::get_path(path),
@@ -512,6 +717,7 @@ impl GraphLayers {
             links: Self::load_links(dir, on_disk)?,
             entry_points: graph_data.entry_points.into_owned(),
             visited_pool: VisitedPool::new(),
+            search_counters: GraphSearchCounters::default(),
         })
     }
 
@@ -583,6 +789,34 @@ impl GraphLayers {
         .unwrap();
     }
 
+    /// Graph with the same entry points, and links replaced with `edges`
+    ///
+    /// New links are saved in place of the current links file.
+    pub(super) fn with_edges(
+        &self,
+        dir: &Path,
+        edges: Vec<Vec<Vec<PointOffsetType>>>,
+        format_param: GraphLinksFormatParam,
+        on_disk: bool,
+    ) -> OperationResult<Self> {
+        let format = format_param.as_format();
+        debug_assert_eq!(format, self.links.format());
+
+        // Current links are still in use, until the healed graph is applied
+        let links_path = Self::get_healed_links_path(dir, format);
+        atomic_save(&links_path, |writer| {
+            serialize_graph_links(edges, format_param, self.hnsw_m, writer)
+        })?;
+
+        Ok(Self {
+            hnsw_m: self.hnsw_m,
+            links: GraphLinks::load_from_file(&links_path, on_disk, format)?,
+            entry_points: self.entry_points.clone(),
+            visited_pool: VisitedPool::new(),
+            search_counters: GraphSearchCounters::default(),
+        })
+    }
+
     pub fn populate(&self) -> OperationResult<()> {
         self.links.populate()?;
         Ok(())
@@ -596,12 +830,15 @@ mod tests {
     use rstest::rstest;
     use tempfile::Builder;
 
+    use itertools::Itertools as _;
+
     use super::*;
     use crate::data_types::vectors::VectorElementType;
     use crate::fixtures::index_fixtures::{TestRawScorerProducer, random_vector};
     use crate::index::hnsw_index::tests::{
         create_graph_layer_builder_fixture, create_graph_layer_fixture,
     };
+    use crate::payload_storage::FilterContext;
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::CosineMetric;
     use crate::types::Distance;
@@ -653,6 +890,7 @@ mod tests {
             links: GraphLinks::new_from_edges(graph_links.clone(), format_param, hnsw_m).unwrap(),
             entry_points: EntryPoints::new(entry_points_num),
             visited_pool: VisitedPool::new(),
+            search_counters: GraphSearchCounters::default(),
         };
 
         let linking_idx: PointOffsetType = 7;
@@ -683,6 +921,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_on_level_acorn() {
+        struct EvenPoints;
+
+        impl FilterContext for EvenPoints {
+            fn check(&self, point_id: PointOffsetType) -> bool {
+                point_id % 2 == 0
+            }
+        }
+
+        let dim = 8;
+        let hnsw_m = HnswM::new2(8);
+        let num_vectors = 20;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder =
+            TestRawScorerProducer::new(dim, Distance::Dot, num_vectors, false, &mut rng);
+
+        // A chain, in which every other point is rejected by the filter
+        let graph_links = (0..num_vectors as PointOffsetType)
+            .map(|idx| vec![(idx + 1..num_vectors as PointOffsetType).take(1).collect()])
+            .collect();
+
+        let graph_links_vectors = vector_holder.graph_links_vectors();
+        let format_param =
+            GraphLinksFormat::Plain.with_param_for_tests(graph_links_vectors.as_ref());
+        let graph_layers = GraphLayers {
+            hnsw_m,
+            links: GraphLinks::new_from_edges(graph_links, format_param, hnsw_m).unwrap(),
+            entry_points: EntryPoints::new(10),
+            visited_pool: VisitedPool::new(),
+            search_counters: GraphSearchCounters::default(),
+        };
+
+        let query = random_vector(&mut rng, dim);
+        let search = |acorn| {
+            let mut scorer = vector_holder.filtered_scorer(query.clone(), &EvenPoints);
+            let entry = ScoredPointOffset {
+                idx: 0,
+                score: scorer.score_point(0),
+            };
+            let mut found = graph_layers
+                .search_on_level_impl(entry, 0, 32, &mut scorer, acorn, &DEFAULT_STOPPED)
+                .unwrap()
+                .nearest
+                .into_sorted_vec()
+                .into_iter()
+                .map(|point| point.idx)
+                .collect_vec();
+            found.sort_unstable();
+            found
+        };
+
+        // Plain traversal is stuck at the first rejected point
+        assert_eq!(search(false), vec![0]);
+
+        // ACORN traversal steps over rejected points and reaches all accepted ones
+        let expected = (0..num_vectors as PointOffsetType).step_by(2).collect_vec();
+        assert_eq!(search(true), expected);
+    }
+
     #[rstest]
     #[case::uncompressed((GraphLinksFormat::Plain, false))]
     #[case::converted((GraphLinksFormat::Plain, true))]
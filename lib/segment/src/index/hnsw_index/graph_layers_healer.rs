@@ -35,9 +35,12 @@ impl<'a> GraphLayersHealer<'a> {
                 let level_m = graph_layers.hnsw_m.level_m(level);
                 let mut container = LinksContainer::with_capacity(level_m);
                 container.fill_from(graph_layers.links.links(point_id, level).take(level_m));
-                if container
-                    .iter()
-                    .any(|neighbor| old_to_new[neighbor as usize].is_none())
+                // Links of deleted points are never used, no need to heal them
+                let is_deleted = old_to_new[point_id as usize].is_none();
+                if !is_deleted
+                    && container
+                        .iter()
+                        .any(|neighbor| old_to_new[neighbor as usize].is_none())
                 {
                     to_heal.push((point_id, level));
                 }
@@ -232,6 +235,27 @@ impl<'a> GraphLayersHealer<'a> {
         })
     }
 
+    /// Whether any active point links to a deleted one
+    pub fn has_links_to_heal(&self) -> bool {
+        !self.to_heal.is_empty()
+    }
+
+    /// Links of all points, keeping their offsets
+    ///
+    /// Deleted points keep their links, so they still lead to active points if used as entry
+    /// points.
+    pub fn into_edges(self) -> Vec<Vec<Vec<PointOffsetType>>> {
+        self.links_layers
+            .into_iter()
+            .map(|layers| {
+                layers
+                    .into_iter()
+                    .map(|layer| layer.into_inner().into_vec())
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn save_into_builder(self, builder: &GraphLayersBuilder) {
         for (old_offset, layers) in self.links_layers.into_iter().enumerate() {
             let Some(new_offset) = self.old_to_new[old_offset] else {
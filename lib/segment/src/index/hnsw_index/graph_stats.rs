@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::graph_layers::{GraphLayers, GraphLayersBase};
+use crate::common::anonymize::Anonymize;
+
+/// Structural statistics of an HNSW graph, used to diagnose recall issues.
+///
+/// Only active points are taken into account, that is points which are neither deleted
+/// nor have their vector deleted.
+#[derive(Serialize, Clone, Debug, Default, JsonSchema, Anonymize)]
+#[anonymize(false)]
+pub struct HnswGraphStats {
+    /// Internal offset of the point the search starts from, if the graph has any active points
+    pub entry_point: Option<PointOffsetType>,
+    /// Level of the entry point
+    pub entry_point_level: Option<usize>,
+    /// Number of active points on each level, starting with the base layer
+    pub layer_sizes: Vec<usize>,
+    /// Average number of links of active points on each level, starting with the base layer
+    pub avg_out_degree: Vec<f64>,
+    /// Number of points deleted since the graph was built
+    pub deleted_points: usize,
+    /// Number of active points reachable from the entry point on the base layer,
+    /// walking through active points only, like the search does
+    pub reachable_points: usize,
+    /// Active points without links to any other active point on the base layer
+    pub orphaned_points: usize,
+    /// Time it took to build the graph, in milliseconds.
+    /// Unknown for graphs built by older versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_duration_ms: Option<u64>,
+}
+
+impl GraphLayers {
+    /// Collect structural statistics of the graph.
+    ///
+    /// Walks the whole base layer, so it is as expensive as a full scan of the links.
+    pub fn graph_stats(&self, is_active: impl Fn(PointOffsetType) -> bool) -> HnswGraphStats {
+        let num_points = self.num_points();
+
+        let mut layer_sizes: Vec<usize> = Vec::new();
+        let mut layer_links: Vec<usize> = Vec::new();
+        let mut deleted_points = 0;
+        let mut orphaned_points = 0;
+
+        for point_id in 0..num_points as PointOffsetType {
+            if !is_active(point_id) {
+                deleted_points += 1;
+                continue;
+            }
+
+            let point_level = self.point_level(point_id);
+            if layer_sizes.len() <= point_level {
+                layer_sizes.resize(point_level + 1, 0);
+                layer_links.resize(point_level + 1, 0);
+            }
+
+            for level in 0..=point_level {
+                layer_sizes[level] += 1;
+                let mut has_active_links = false;
+                self.for_each_link(point_id, level, |link| {
+                    layer_links[level] += 1;
+                    has_active_links |= is_active(link);
+                });
+                if level == 0 && !has_active_links {
+                    orphaned_points += 1;
+                }
+            }
+        }
+
+        let avg_out_degree = layer_sizes
+            .iter()
+            .zip(&layer_links)
+            .map(|(&size, &links)| links as f64 / size as f64)
+            .collect();
+
+        let entry_point = self.entry_points.get_entry_point(&is_active);
+
+        let reachable_points = entry_point
+            .as_ref()
+            .map(|entry_point| self.count_reachable(entry_point.point_id, &is_active))
+            .unwrap_or(0);
+
+        HnswGraphStats {
+            entry_point: entry_point.as_ref().map(|entry| entry.point_id),
+            entry_point_level: entry_point.as_ref().map(|entry| entry.level),
+            layer_sizes,
+            avg_out_degree,
+            deleted_points,
+            reachable_points,
+            orphaned_points,
+            build_duration_ms: None,
+        }
+    }
+
+    /// Breadth-first walk of the base layer through active points
+    fn count_reachable(
+        &self,
+        start: PointOffsetType,
+        is_active: impl Fn(PointOffsetType) -> bool,
+    ) -> usize {
+        let mut visited = BitVec::<usize>::repeat(false, self.num_points());
+        let mut queue = VecDeque::from([start]);
+        visited.set(start as usize, true);
+
+        let mut reachable = 0;
+        while let Some(point_id) = queue.pop_front() {
+            reachable += 1;
+            self.for_each_link(point_id, 0, |link| {
+                if !visited[link as usize] && is_active(link) {
+                    visited.set(link as usize, true);
+                    queue.push_back(link);
+                }
+            });
+        }
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::index::hnsw_index::graph_links::GraphLinksFormat;
+    use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+    use crate::types::Distance;
+
+    #[test]
+    fn test_graph_stats() {
+        let num_points = 500;
+        let mut rng = StdRng::seed_from_u64(42);
+        let (_vector_holder, graph_layers) = create_graph_layer_fixture(
+            num_points,
+            8,
+            16,
+            GraphLinksFormat::Plain,
+            true,
+            false,
+            Distance::Cosine,
+            &mut rng,
+        );
+
+        let stats = graph_layers.graph_stats(|_| true);
+        assert_eq!(stats.layer_sizes[0], num_points);
+        assert_eq!(stats.deleted_points, 0);
+        // Graph built on random vectors is expected to be (almost) fully connected
+        assert!(stats.reachable_points > num_points * 95 / 100);
+        assert_eq!(stats.orphaned_points, 0);
+        assert!(stats.avg_out_degree[0] > 0.0);
+        assert!(stats.layer_sizes.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(stats.entry_point_level, Some(stats.layer_sizes.len() - 1));
+
+        // Deleted points are excluded
+        let stats = graph_layers.graph_stats(|point_id| point_id % 2 == 0);
+        assert_eq!(stats.layer_sizes[0], num_points / 2);
+        assert_eq!(stats.deleted_points, num_points / 2);
+        assert!(stats.reachable_points <= num_points / 2);
+    }
+}
@@ -14,12 +14,13 @@ use common::cow::BoxCow;
 use common::cpu::linux_low_thread_priority;
 use common::ext::BitSliceExt as _;
 use common::flags::FeatureFlags;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{DetailsLevel, PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use itertools::EitherOrBoth;
 use log::{debug, trace};
 use memory::fadvise::clear_disk_cache;
 use parking_lot::Mutex;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::ThreadPool;
 use rayon::prelude::*;
 
@@ -46,8 +47,11 @@ use crate::index::hnsw_index::gpu::{get_gpu_groups_count, gpu_graph_builder::bui
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersWithVectors};
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use crate::index::hnsw_index::graph_layers_healer::GraphLayersHealer;
-use crate::index::hnsw_index::graph_links::{GraphLinksFormatParam, StorageGraphLinksVectors};
-use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::graph_links::{
+    GraphLinksFormat, GraphLinksFormatParam, StorageGraphLinksVectors,
+};
+use crate::index::hnsw_index::graph_stats::HnswGraphStats;
+use crate::index::hnsw_index::point_scorer::{FilteredScorer, ScoringBudget};
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
@@ -58,14 +62,16 @@ use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::payload_storage::FilterContext;
 use crate::segment_constructor::VectorIndexBuildArgs;
-use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::telemetry::{HnswIndexTelemetry, VectorIndexSearchesTelemetry};
 use crate::types::Condition::Field;
 use crate::types::{
     FieldCondition, Filter, HnswConfig, HnswGlobalConfig, QuantizationSearchParams, SearchParams,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoveryQuery;
-use crate::vector_storage::{VectorStorage, VectorStorageEnum, new_raw_scorer};
+use crate::vector_storage::{
+    VectorStorage, VectorStorageEnum, check_deleted_condition, new_raw_scorer,
+};
 
 const HNSW_USE_HEURISTIC: bool = true;
 const FINISH_MAIN_GRAPH_LOG_MESSAGE: &str = "Finish main graph in time";
@@ -92,6 +98,15 @@ pub struct HNSWIndex {
     is_on_disk: bool,
 }
 
+/// Graph of [`HNSWIndex`], healed by [`HNSWIndex::heal_graph`]
+#[derive(Debug)]
+pub struct HealedGraph {
+    /// `None` if no links had to be rerouted
+    graph: Option<GraphLayers>,
+    /// Number of deleted points at the time of healing
+    deleted_count: usize,
+}
+
 #[derive(Debug)]
 struct HNSWSearchesTelemetry {
     unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
@@ -170,6 +185,12 @@ impl HNSWIndex {
 
         let graph = GraphLayers::load(path, is_on_disk, do_convert)?;
 
+        // Healed links, which were not applied before shutdown
+        let healed_links_path = GraphLayers::get_healed_links_path(path, graph.links.format());
+        if healed_links_path.exists() {
+            std::fs::remove_file(&healed_links_path)?;
+        }
+
         Ok(HNSWIndex {
             id_tracker,
             vector_storage,
@@ -192,6 +213,142 @@ impl HNSWIndex {
         &self.graph
     }
 
+    /// Structural statistics of the graph, taking into account points deleted after it was built
+    pub fn graph_stats(&self) -> HnswGraphStats {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let point_deleted = id_tracker.deleted_point_bitslice();
+        let vec_deleted = vector_storage.deleted_vector_bitslice();
+
+        HnswGraphStats {
+            build_duration_ms: self.config.build_duration_ms,
+            ..self.graph.graph_stats(|point_id| {
+                check_deleted_condition(point_id, vec_deleted, point_deleted)
+            })
+        }
+    }
+
+    /// Number of indexed points deleted since the graph was built, or last healed
+    pub fn unhealed_deleted_count(&self) -> usize {
+        self.deleted_count()
+            .saturating_sub(self.config.healed_deleted_count)
+    }
+
+    fn deleted_count(&self) -> usize {
+        self.indexed_vector_count()
+            .saturating_sub(self.vector_storage.borrow().available_vector_count())
+    }
+
+    /// Reroute links of active points around points deleted since the graph was built
+    ///
+    /// Links to deleted points are replaced with links to the closest active points reachable
+    /// through them, the same way an old graph is healed when it is reused on optimization.
+    /// This keeps search recall from degrading on delete-heavy workloads until the segment is
+    /// optimized.
+    ///
+    /// The healed graph is saved next to the current one. It replaces the current graph, on disk
+    /// and in memory, once applied with [`Self::apply_healed_graph`].
+    pub fn heal_graph(
+        &self,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<HealedGraph> {
+        let deleted_count = self.deleted_count();
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let point_deleted = id_tracker.deleted_point_bitslice();
+        let vec_deleted = vector_storage.deleted_vector_bitslice();
+        // Active points keep their offsets
+        let old_to_new: Vec<_> = (0..self.graph.num_points() as PointOffsetType)
+            .map(|point_id| {
+                check_deleted_condition(point_id, vec_deleted, point_deleted).then_some(point_id)
+            })
+            .collect();
+
+        let mut healer = GraphLayersHealer::new(&self.graph, &old_to_new, self.config.ef_construct);
+        if !healer.has_links_to_heal() {
+            return Ok(HealedGraph {
+                graph: None,
+                deleted_count,
+            });
+        }
+
+        // Keep the format of links, so the healed links file replaces the current one
+        let graph_links_vectors;
+        let format_param = match self.graph.links.format() {
+            GraphLinksFormat::Plain => GraphLinksFormatParam::Plain,
+            GraphLinksFormat::Compressed => GraphLinksFormatParam::Compressed,
+            GraphLinksFormat::CompressedWithVectors => {
+                graph_links_vectors =
+                    StorageGraphLinksVectors::try_new(&vector_storage, quantized_vectors.as_ref())
+                        .ok_or_else(|| {
+                            OperationError::service_error(
+                                "Vectors of HNSW graph links are not available",
+                            )
+                        })?;
+                GraphLinksFormatParam::CompressedWithVectors(&graph_links_vectors)
+            }
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-heal-{idx}"))
+            .num_threads(max_threads)
+            .build()?;
+
+        check_process_stopped(stopped)?;
+        healer.heal(&pool, &vector_storage, quantized_vectors.as_ref())?;
+        check_process_stopped(stopped)?;
+
+        let graph = self.graph.with_edges(
+            &self.path,
+            healer.into_edges(),
+            format_param,
+            self.is_on_disk,
+        )?;
+
+        Ok(HealedGraph {
+            graph: Some(graph),
+            deleted_count,
+        })
+    }
+
+    fn get_hnsw_telemetry(&self) -> HnswIndexTelemetry {
+        let search_counters = &self.graph.search_counters;
+        HnswIndexTelemetry {
+            graph: self.graph_stats(),
+            unfiltered_searches: search_counters.unfiltered(),
+            filtered_searches: search_counters.filtered(),
+            avg_hops: search_counters.avg_hops(),
+        }
+    }
+
+    pub fn apply_healed_graph(&mut self, healed: HealedGraph) -> OperationResult<()> {
+        let HealedGraph {
+            graph,
+            deleted_count,
+        } = healed;
+        if let Some(mut graph) = graph {
+            // Loaded healed links stay mapped after the rename
+            let format = graph.links.format();
+            std::fs::rename(
+                GraphLayers::get_healed_links_path(&self.path, format),
+                GraphLayers::get_links_path(&self.path, format),
+            )?;
+
+            // Keep counters of searches, structure of the graph has changed though
+            graph.search_counters = std::mem::take(&mut self.graph.search_counters);
+            self.graph = graph;
+        }
+
+        self.config.healed_deleted_count = deleted_count;
+        self.config
+            .save(&HnswGraphConfig::get_config_path(&self.path))?;
+        Ok(())
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -229,6 +386,15 @@ impl HNSWIndex {
             feature_flags,
         } = build_args;
 
+        let build_timer = std::time::Instant::now();
+
+        // Seeded builds are reproducible: all randomness comes from the seed, points are inserted
+        // in a fixed order on a single thread, and graphs of other segments are not reused.
+        let seeded = hnsw_config.seed.is_some();
+        let mut rng = StdRng::seed_from_u64(hnsw_config.seed.unwrap_or_else(|| rng.random()));
+        let old_indices = if seeded { &[][..] } else { old_indices };
+        let gpu_device = gpu_device.filter(|_| !seeded);
+
         create_dir_all(path)?;
 
         let id_tracker_ref = id_tracker.borrow();
@@ -308,7 +474,7 @@ impl HNSWIndex {
 
         let pool = rayon::ThreadPoolBuilder::new()
             .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(permit.num_cpus as usize)
+            .num_threads(if seeded { 1 } else { permit.num_cpus as usize })
             .spawn_handler(|thread| {
                 let mut b = thread::Builder::new();
                 if let Some(name) = thread.name() {
@@ -342,7 +508,7 @@ impl HNSWIndex {
             let level = old_index
                 .as_ref()
                 .and_then(|old_index| old_index.point_level(vector_id))
-                .unwrap_or_else(|| graph_layers_builder.get_random_layer(rng));
+                .unwrap_or_else(|| graph_layers_builder.get_random_layer(&mut rng));
             graph_layers_builder.set_levels(vector_id, level);
         }
 
@@ -481,9 +647,9 @@ impl HNSWIndex {
 
             let required_connectivity = if average_links_per_0_level_int >= 4 {
                 let global_graph_connectivity = [
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, &mut rng),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, &mut rng),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, &mut rng),
                 ];
 
                 debug!("graph connectivity: {global_graph_connectivity:?} @ {percolation}");
@@ -558,8 +724,11 @@ impl HNSWIndex {
                         && let Some(required_connectivity) = required_connectivity
                     {
                         // Always build for tenants
-                        let graph_connectivity = graph_layers_builder
-                            .subgraph_connectivity(&points_to_index, percolation);
+                        let graph_connectivity = graph_layers_builder.subgraph_connectivity(
+                            &points_to_index,
+                            percolation,
+                            &mut rng,
+                        );
 
                         if graph_connectivity >= required_connectivity {
                             trace!(
@@ -644,6 +813,7 @@ impl HNSWIndex {
 
         debug!("finish additional payload field indexing");
 
+        config.build_duration_ms = Some(build_timer.elapsed().as_millis() as u64);
         config.save(&HnswGraphConfig::get_config_path(path))?;
 
         drop(id_tracker_ref);
@@ -965,9 +1135,18 @@ impl HNSWIndex {
 
         let hw_counter = vector_query_context.hardware_counter();
         let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
+        let budget = ScoringBudget::from_params(params);
+        let acorn = filter.is_some()
+            && params
+                .and_then(|params| params.acorn)
+                .is_some_and(|acorn| acorn.enable);
 
         let search_with_vectors = || -> OperationResult<Option<Vec<ScoredPointOffset>>> {
-            if !self.graph.has_vectors() || !is_quantized_search(quantized_vectors.as_ref(), params)
+            // Scoring budget and ACORN traversal are only supported by the regular graph search
+            if budget.is_some()
+                || acorn
+                || !self.graph.has_vectors()
+                || !is_quantized_search(quantized_vectors.as_ref(), params)
             {
                 return Ok(None);
             }
@@ -1019,15 +1198,26 @@ impl HNSWIndex {
                 params,
                 vector_query_context.hardware_counter(),
                 filter_context,
-            )?;
-
-            let search_result = self.graph.search(
-                oversampled_top,
-                ef,
-                points_scorer,
-                custom_entry_points,
-                &is_stopped,
-            )?;
+            )?
+            .with_budget(budget.as_ref());
+
+            let search_result = if acorn {
+                self.graph.search_acorn(
+                    oversampled_top,
+                    ef,
+                    points_scorer,
+                    custom_entry_points,
+                    &is_stopped,
+                )?
+            } else {
+                self.graph.search(
+                    oversampled_top,
+                    ef,
+                    points_scorer,
+                    custom_entry_points,
+                    &is_stopped,
+                )?
+            };
 
             postprocess_search_result(
                 search_result,
@@ -1042,12 +1232,18 @@ impl HNSWIndex {
         };
 
         // Try to use graph with vectors first.
-        if let Some(search_result) = search_with_vectors()? {
-            Ok(search_result)
+        let search_result = if let Some(search_result) = search_with_vectors()? {
+            search_result
         } else {
             // Graph with vectors is not available, fallback to regular graph search.
-            regular_search()
+            regular_search()?
+        };
+
+        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+            vector_query_context.mark_search_truncated();
         }
+
+        Ok(search_result)
     }
 
     fn search_vectors_with_graph(
@@ -1092,6 +1288,7 @@ impl HNSWIndex {
             .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
 
         let is_stopped = vector_query_context.is_stopped();
+        let budget = ScoringBudget::from_params(params);
 
         let points_scorer = Self::construct_search_scorer(
             vector,
@@ -1101,10 +1298,14 @@ impl HNSWIndex {
             params,
             vector_query_context.hardware_counter(),
             None,
-        )?;
+        )?
+        .with_budget(budget.as_ref());
         let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
         let search_result = points_scorer.peek_top_iter(points, oversampled_top, &is_stopped)?;
+        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+            vector_query_context.mark_search_truncated();
+        }
 
         let res = postprocess_search_result(
             search_result,
@@ -1261,6 +1462,7 @@ impl VectorIndex for HNSWIndex {
                     ignore: true,
                     rescore: Some(false),
                     oversampling: None,
+                    adaptive_oversampling: None,
                 }); // disable quantization for exact search
                 params
             })
@@ -1341,6 +1543,18 @@ impl VectorIndex for HNSWIndex {
                     id_tracker.available_point_count(),
                 );
 
+                // ACORN traversal is only worth its cost for selective filters
+                let graph_params = params.map(|params| {
+                    let mut params = *params;
+                    if let Some(acorn) = params.acorn.as_mut() {
+                        let selectivity =
+                            query_cardinality.exp as f64 / available_vector_count.max(1) as f64;
+                        acorn.enable &= selectivity <= acorn.max_selectivity();
+                    }
+                    params
+                });
+                let graph_params = graph_params.as_ref();
+
                 if query_cardinality.max < self.config.full_scan_threshold {
                     // if cardinality is small - use plain index
                     let _timer =
@@ -1362,7 +1576,7 @@ impl VectorIndex for HNSWIndex {
                         vectors,
                         filter,
                         top,
-                        params,
+                        graph_params,
                         query_context,
                     );
                 }
@@ -1380,7 +1594,13 @@ impl VectorIndex for HNSWIndex {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, query_context)
+                    self.search_vectors_with_graph(
+                        vectors,
+                        filter,
+                        top,
+                        graph_params,
+                        query_context,
+                    )
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
@@ -1404,6 +1624,12 @@ impl VectorIndex for HNSWIndex {
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
             unfiltered_sparse: Default::default(),
+            unfiltered_ivf: Default::default(),
+            filtered_ivf: Default::default(),
+            unfiltered_disk_ann: Default::default(),
+            filtered_disk_ann: Default::default(),
+            // Walks the whole graph, only collected for detailed telemetry
+            hnsw: (detail.level >= DetailsLevel::Level4).then(|| self.get_hnsw_telemetry()),
         }
     }
 
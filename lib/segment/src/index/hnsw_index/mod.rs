@@ -8,10 +8,11 @@ pub mod graph_layers;
 pub mod graph_layers_builder;
 mod graph_layers_healer;
 pub mod graph_links;
+pub mod graph_stats;
 pub mod hnsw;
 mod links_container;
 pub mod point_scorer;
-mod search_context;
+pub mod search_context;
 
 #[cfg(feature = "gpu")]
 pub mod gpu;
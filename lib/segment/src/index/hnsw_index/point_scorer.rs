@@ -1,4 +1,4 @@
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use bitvec::slice::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
@@ -8,7 +8,9 @@ use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
 use crate::common::operation_error::{CancellableResult, OperationResult, check_process_stopped};
 use crate::data_types::vectors::QueryVector;
+use crate::index::buffer_pool::PooledBuffer;
 use crate::payload_storage::FilterContext;
+use crate::types::SearchParams;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
 use crate::vector_storage::quantized::quantized_query_scorer::InternalScorerUnsupported;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
@@ -40,8 +42,55 @@ use crate::vector_storage::{
 pub struct FilteredScorer<'a> {
     raw_scorer: Box<dyn RawScorer + 'a>,
     filters: ScorerFilters<'a>,
-    /// Temporary buffer for scores.
-    scores_buffer: Vec<ScoreType>,
+    /// Temporary buffer for scores, reused among searches.
+    scores_buffer: PooledBuffer<Vec<ScoreType>>,
+    /// Limits the number of points this scorer may score, if set.
+    budget: Option<&'a ScoringBudget>,
+}
+
+/// Max number of points a single search may score, see [`SearchParams::max_scored_points`].
+///
+/// Points, which do not pass the filters, are not counted.
+#[derive(Debug)]
+pub struct ScoringBudget {
+    remaining: AtomicUsize,
+    exhausted: AtomicBool,
+}
+
+impl ScoringBudget {
+    pub fn new(max_scored_points: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(max_scored_points),
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    pub fn from_params(params: Option<&SearchParams>) -> Option<Self> {
+        params
+            .and_then(|params| params.max_scored_points)
+            .map(Self::new)
+    }
+
+    /// Take up to `count` points out of the budget.
+    ///
+    /// Returns how many of them may be scored.
+    pub fn take(&self, count: usize) -> usize {
+        let remaining = self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                Some(remaining.saturating_sub(count))
+            })
+            .unwrap_or_else(|remaining| remaining);
+        if remaining < count {
+            self.exhausted.store(true, Ordering::Relaxed);
+        }
+        count.min(remaining)
+    }
+
+    /// Whether some points were left unscored, because the budget was spent.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
 }
 
 pub struct ScorerFilters<'a> {
@@ -66,6 +115,11 @@ impl<'a> ScorerFilters<'a> {
                 .is_none_or(|f| f.check(point_id))
     }
 
+    /// Whether points are filtered by a payload filter, besides deleted points and vectors
+    pub fn has_payload_filter(&self) -> bool {
+        self.filter_context.is_some()
+    }
+
     fn as_borrowed(&'a self) -> Self {
         ScorerFilters {
             filter_context: self.filter_context.as_ref().map(BoxCow::as_borrowed),
@@ -121,7 +175,8 @@ impl<'a> FilteredScorer<'a> {
                 point_deleted,
                 vec_deleted: vectors.deleted_vector_bitslice(),
             },
-            scores_buffer: Vec::new(),
+            scores_buffer: PooledBuffer::get(),
+            budget: None,
         })
     }
 
@@ -158,7 +213,8 @@ impl<'a> FilteredScorer<'a> {
                 point_deleted,
                 vec_deleted: vectors.deleted_vector_bitslice(),
             },
-            scores_buffer: Vec::new(),
+            scores_buffer: PooledBuffer::get(),
+            budget: None,
         })
     }
 
@@ -180,10 +236,22 @@ impl<'a> FilteredScorer<'a> {
                 point_deleted,
                 vec_deleted: vector_storage.deleted_vector_bitslice(),
             },
-            scores_buffer: Vec::new(),
+            scores_buffer: PooledBuffer::get(),
+            budget: None,
         }
     }
 
+    /// Stop scoring points once the `budget` is spent.
+    pub fn with_budget(mut self, budget: Option<&'a ScoringBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Whether the budget is spent, so no more points will be scored.
+    pub fn is_budget_exhausted(&self) -> bool {
+        self.budget.is_some_and(ScoringBudget::is_exhausted)
+    }
+
     pub fn raw_scorer(&self) -> &dyn RawScorer {
         self.raw_scorer.as_ref()
     }
@@ -219,6 +287,23 @@ impl<'a> FilteredScorer<'a> {
         if limit != 0 {
             point_ids.truncate(limit);
         }
+        self.score_checked_points(point_ids)
+    }
+
+    /// Same as [`Self::score_points`], for points which are already checked against
+    /// [`Self::filters`], so they are not checked again.
+    ///
+    /// # Arguments
+    ///
+    /// * `point_ids` - list of points to score.
+    ///   **Warning**: This input will be wrecked during the execution.
+    pub fn score_checked_points(
+        &mut self,
+        point_ids: &mut Vec<PointOffsetType>,
+    ) -> impl Iterator<Item = ScoredPointOffset> {
+        if let Some(budget) = self.budget {
+            point_ids.truncate(budget.take(point_ids.len()));
+        }
 
         if self.scores_buffer.len() < point_ids.len() {
             self.scores_buffer.resize(point_ids.len(), 0.0);
@@ -227,7 +312,7 @@ impl<'a> FilteredScorer<'a> {
         self.raw_scorer
             .score_points(point_ids, &mut self.scores_buffer[..point_ids.len()]);
 
-        std::iter::zip(&*point_ids, &self.scores_buffer)
+        std::iter::zip(&*point_ids, self.scores_buffer.iter())
             .map(|(&idx, &score)| ScoredPointOffset { idx, score })
     }
 
@@ -288,6 +373,11 @@ impl<'a> FilteredScorer<'a> {
                 break;
             }
 
+            let budget_exhausted = self.budget.is_some_and(|budget| {
+                chunk_size = budget.take(chunk_size);
+                budget.is_exhausted()
+            });
+
             self.raw_scorer
                 .score_points(&chunk[..chunk_size], &mut scores_buffer[..chunk_size]);
 
@@ -297,6 +387,10 @@ impl<'a> FilteredScorer<'a> {
                     score: scores_buffer[i],
                 });
             }
+
+            if budget_exhausted {
+                break;
+            }
         }
 
         Ok(pq.into_sorted_vec())
@@ -4,19 +4,24 @@ use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{ScoreType, ScoredPointOffset};
 use num_traits::float::FloatCore;
 
+use crate::index::buffer_pool::PooledBuffer;
+
 /// Structure that holds context of the search
 pub struct SearchContext {
     /// Overall nearest points found so far
     pub nearest: FixedLengthPriorityQueue<ScoredPointOffset>,
-    /// Current candidates to process
-    pub candidates: BinaryHeap<ScoredPointOffset>,
+    /// Current candidates to process, memory of the heap is reused among searches
+    pub candidates: PooledBuffer<BinaryHeap<ScoredPointOffset>>,
+    /// Number of candidates, whose links were explored
+    pub hops: usize,
 }
 
 impl SearchContext {
     pub fn new(ef: usize) -> Self {
         SearchContext {
             nearest: FixedLengthPriorityQueue::new(ef),
-            candidates: BinaryHeap::new(),
+            candidates: PooledBuffer::get(),
+            hops: 0,
         }
     }
 
@@ -1,5 +1,6 @@
 mod test_compact_graph_layer;
 mod test_graph_connectivity;
+mod test_seeded_build;
 
 use common::types::PointOffsetType;
 use rand::Rng;
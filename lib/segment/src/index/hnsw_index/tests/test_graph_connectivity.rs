@@ -60,6 +60,8 @@ fn test_graph_connectivity() {
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
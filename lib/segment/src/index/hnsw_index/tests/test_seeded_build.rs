@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::flags::FeatureFlags;
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::{Builder, TempDir};
+
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::segment::Segment;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{Distance, HnswConfig, HnswGlobalConfig, SeqNumberType};
+
+fn build_index(segment: &Segment, hnsw_config: HnswConfig, rng_seed: u64) -> (TempDir, HNSWIndex) {
+    let stopped = AtomicBool::new(false);
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let permit = Arc::new(ResourcePermit::dummy(4));
+
+    let index = HNSWIndex::build(
+        HnswIndexOpenArgs {
+            path: hnsw_dir.path(),
+            id_tracker: segment.id_tracker.clone(),
+            vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            quantized_vectors: Default::default(),
+            payload_index: segment.payload_index.clone(),
+            hnsw_config,
+        },
+        VectorIndexBuildArgs {
+            permit,
+            old_indices: &[],
+            gpu_device: None,
+            rng: &mut StdRng::seed_from_u64(rng_seed),
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+        },
+    )
+    .unwrap();
+    (hnsw_dir, index)
+}
+
+#[test]
+fn test_seeded_build_is_reproducible() {
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 10,
+        max_indexing_threads: 4,
+        on_disk: Some(false),
+        payload_m: None,
+        copy_vectors: None,
+        gpu_indexing: None,
+        seed: Some(1234),
+    };
+
+    // Build seed overrides the randomness of the caller
+    let (_dir_a, index_a) = build_index(&segment, hnsw_config.clone(), 1);
+    let (_dir_b, index_b) = build_index(&segment, hnsw_config, 2);
+
+    for point_id in 0..num_vectors as PointOffsetType {
+        let level = index_a.graph().point_level(point_id);
+        assert_eq!(level, index_b.graph().point_level(point_id));
+        for level in 0..=level {
+            assert_eq!(
+                index_a.graph().links.links(point_id, level).collect_vec(),
+                index_b.graph().links.links(point_id, level).collect_vec(),
+                "links of point {point_id} differ on level {level}",
+            );
+        }
+    }
+}
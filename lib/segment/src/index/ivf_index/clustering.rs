@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+    RawCosineMetric,
+};
+use crate::spaces::wasm_metric::WasmMetricModule;
+use crate::types::Distance;
+use crate::vector_storage::{Random, VectorStorage, VectorStorageEnum};
+
+/// Number of vectors sampled per centroid to train centroids
+pub const SAMPLES_PER_CENTROID: usize = 64;
+
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Similarity of two vectors with the metric of `distance`, greater the value - closer the vectors
+///
+/// [`Distance::Custom`] scores with `custom_metric`, the module of the indexed storage.
+pub fn similarity(
+    distance: Distance,
+    custom_metric: Option<&Arc<WasmMetricModule>>,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    match distance {
+        Distance::Cosine => CosineMetric::similarity(v1, v2),
+        Distance::Euclid => EuclidMetric::similarity(v1, v2),
+        Distance::Dot => DotProductMetric::similarity(v1, v2),
+        Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+        Distance::Hamming => HammingMetric::similarity(v1, v2),
+        Distance::Jaccard => JaccardMetric::similarity(v1, v2),
+        Distance::Custom => custom_metric.map_or(ScoreType::MIN, |module| module.score(v1, v2)),
+        Distance::RawCosine => <RawCosineMetric as Metric<VectorElementType>>::similarity(v1, v2),
+    }
+}
+
+/// Index of the centroid closest to `vector`
+pub fn nearest_centroid(
+    distance: Distance,
+    custom_metric: Option<&Arc<WasmMetricModule>>,
+    centroids: &[DenseVector],
+    vector: &[VectorElementType],
+) -> usize {
+    centroids
+        .iter()
+        .map(|centroid| similarity(distance, custom_metric, centroid, vector))
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(idx, _)| idx)
+}
+
+pub fn get_dense_vector(
+    vector_storage: &VectorStorageEnum,
+    point_id: PointOffsetType,
+) -> OperationResult<Cow<'_, [VectorElementType]>> {
+    match vector_storage.get_vector::<Random>(point_id) {
+        CowVector::Dense(vector) => Ok(vector),
+        CowVector::Sparse(_) | CowVector::MultiDense(_) => Err(OperationError::service_error(
+            "IVF index supports only dense vectors",
+        )),
+    }
+}
+
+/// Train `count` centroids on vectors of `sample` with k-means
+///
+/// Centroids are initialized with random sampled vectors. Centroids of empty clusters are
+/// re-initialized the same way.
+pub fn train_centroids<R: Rng + ?Sized>(
+    pool: &ThreadPool,
+    vector_storage: &VectorStorageEnum,
+    sample: &[PointOffsetType],
+    count: usize,
+    rng: &mut R,
+    stopped: &AtomicBool,
+) -> OperationResult<Vec<DenseVector>> {
+    let distance = vector_storage.distance();
+    let custom_metric = vector_storage.custom_metric();
+
+    let mut centroids = sample
+        .choose_multiple(rng, count)
+        .map(|&point_id| get_dense_vector(vector_storage, point_id).map(Cow::into_owned))
+        .collect::<OperationResult<Vec<_>>>()?;
+
+    for _ in 0..KMEANS_ITERATIONS {
+        check_process_stopped(stopped)?;
+
+        let assignments = pool.install(|| {
+            sample
+                .par_iter()
+                .map(|&point_id| {
+                    let vector = get_dense_vector(vector_storage, point_id)?;
+                    Ok(nearest_centroid(
+                        distance,
+                        custom_metric,
+                        &centroids,
+                        &vector,
+                    ))
+                })
+                .collect::<OperationResult<Vec<_>>>()
+        })?;
+
+        let mut sums = vec![vec![0.0f64; centroids[0].len()]; centroids.len()];
+        let mut sizes = vec![0usize; centroids.len()];
+        for (&point_id, &cluster) in sample.iter().zip(&assignments) {
+            let vector = get_dense_vector(vector_storage, point_id)?;
+            for (sum, &value) in sums[cluster].iter_mut().zip(vector.iter()) {
+                *sum += f64::from(value);
+            }
+            sizes[cluster] += 1;
+        }
+
+        for ((centroid, sum), size) in centroids.iter_mut().zip(sums).zip(sizes) {
+            if size == 0 {
+                let point_id = sample[rng.random_range(0..sample.len())];
+                *centroid = get_dense_vector(vector_storage, point_id)?.into_owned();
+                continue;
+            }
+            let mean = sum
+                .into_iter()
+                .map(|sum| (sum / size as f64) as VectorElementType)
+                .collect();
+            *centroid = distance.preprocess_vector::<VectorElementType>(mean);
+        }
+    }
+
+    Ok(centroids)
+}
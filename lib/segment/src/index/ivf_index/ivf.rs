@@ -0,0 +1,461 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
+use io::file_operations::{atomic_save_bin, read_bin};
+use itertools::Itertools;
+use log::debug;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::clustering::{
+    SAMPLES_PER_CENTROID, get_dense_vector, nearest_centroid, similarity, train_centroids,
+};
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{DenseVector, QueryVector, VectorInternal, VectorRef};
+use crate::id_tracker::IdTrackerSS;
+use crate::index::hnsw_index::point_scorer::ScoringBudget;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_search_common::{
+    IndexSearchesTelemetry, IndexedStorages, get_oversampled_top, new_search_scorer,
+    postprocess_search_result,
+};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::spaces::wasm_metric::WasmMetricModule;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{Distance, Filter, IvfConfig, SearchParams};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::query::{Query, RecoBestScoreQuery, RecoSumScoresQuery};
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+const IVF_LISTS_FILE: &str = "ivf_lists.bin";
+
+/// Centroids of clusters and inverted lists of points, assigned to them
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IvfLists {
+    centroids: Vec<DenseVector>,
+    lists: Vec<Vec<PointOffsetType>>,
+}
+
+/// Inverted file index
+///
+/// Vectors are clustered with k-means. Search scores the centroids against the query first,
+/// and then only scans points of the closest clusters.
+#[derive(Debug)]
+pub struct IvfIndex {
+    storages: IndexedStorages,
+    config: IvfConfig,
+    path: PathBuf,
+    distance: Distance,
+    lists: IvfLists,
+    searches_telemetry: IndexSearchesTelemetry,
+}
+
+pub struct IvfIndexOpenArgs<'a> {
+    pub path: &'a Path,
+    pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    pub ivf_config: IvfConfig,
+}
+
+impl IvfIndex {
+    pub fn open(args: IvfIndexOpenArgs<'_>) -> OperationResult<Self> {
+        let IvfIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            ivf_config,
+        } = args;
+
+        let lists = read_bin(&Self::get_lists_path(path))?;
+        let distance = vector_storage.borrow().distance();
+
+        Ok(IvfIndex {
+            storages: IndexedStorages {
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+            },
+            config: ivf_config,
+            path: path.to_owned(),
+            distance,
+            lists,
+            searches_telemetry: IndexSearchesTelemetry::new(),
+        })
+    }
+
+    pub fn build<R: Rng + ?Sized>(
+        open_args: IvfIndexOpenArgs<'_>,
+        build_args: VectorIndexBuildArgs<'_, R>,
+    ) -> OperationResult<Self> {
+        if Self::get_lists_path(open_args.path).exists() {
+            log::warn!(
+                "IVF index already exists at {:?}, skipping building",
+                open_args.path
+            );
+            debug_assert!(false);
+            return Self::open(open_args);
+        }
+
+        let IvfIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            ivf_config,
+        } = open_args;
+        let VectorIndexBuildArgs {
+            permit,
+            old_indices,
+            gpu_device: _,
+            rng,
+            stopped,
+            hnsw_global_config: _,
+            feature_flags: _,
+        } = build_args;
+
+        create_dir_all(path)?;
+
+        let id_tracker_ref = id_tracker.borrow();
+        let vector_storage_ref = vector_storage.borrow();
+
+        let distance = vector_storage_ref.distance();
+        let custom_metric = vector_storage_ref.custom_metric();
+        if !IvfConfig::supports_distance(distance) {
+            return Err(OperationError::service_error(format!(
+                "IVF index does not support {distance:?} distance",
+            )));
+        }
+
+        let point_ids = id_tracker_ref
+            .iter_ids_excluding(vector_storage_ref.deleted_vector_bitslice())
+            .collect_vec();
+        let lists_count = ivf_config
+            .lists
+            .unwrap_or_else(|| (point_ids.len() as f64).sqrt().ceil() as usize)
+            .clamp(1, point_ids.len().max(1));
+
+        debug!(
+            "building IVF with {lists_count} lists for {} vectors with {} CPUs",
+            point_ids.len(),
+            permit.num_cpus,
+        );
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("ivf-build-{idx}"))
+            .num_threads(permit.num_cpus as usize)
+            .build()?;
+
+        // Centroids of optimized segments are reused, so only points have to be assigned to
+        // lists. It keeps rebuilding the index after updates cheap, as no k-means is needed.
+        // Number of lists must match if configured explicitly. Otherwise it follows the number
+        // of points, and the closest number of lists within a factor of two is accepted.
+        let old_centroids = old_indices
+            .iter()
+            .filter_map(|old_index| match &*old_index.borrow() {
+                VectorIndexEnum::Ivf(old_index) if old_index.distance == distance => {
+                    let old_lists_count = old_index.lists.centroids.len();
+                    let acceptable = match ivf_config.lists {
+                        Some(_) => old_lists_count == lists_count,
+                        None => {
+                            old_lists_count > 0
+                                && old_lists_count <= lists_count.saturating_mul(2)
+                                && lists_count <= old_lists_count.saturating_mul(2)
+                        }
+                    };
+                    acceptable.then(|| old_index.lists.centroids.clone())
+                }
+                _ => None,
+            })
+            .min_by_key(|old_centroids| old_centroids.len().abs_diff(lists_count));
+
+        let centroids = match old_centroids {
+            Some(centroids) => centroids,
+            None if point_ids.is_empty() => Vec::new(),
+            None => {
+                let sample = point_ids
+                    .choose_multiple(rng, lists_count * SAMPLES_PER_CENTROID)
+                    .copied()
+                    .collect_vec();
+                train_centroids(
+                    &pool,
+                    &vector_storage_ref,
+                    &sample,
+                    lists_count,
+                    rng,
+                    stopped,
+                )?
+            }
+        };
+
+        check_process_stopped(stopped)?;
+
+        let assignments = pool.install(|| {
+            point_ids
+                .par_iter()
+                .map(|&point_id| {
+                    let vector = get_dense_vector(&vector_storage_ref, point_id)?;
+                    Ok(nearest_centroid(
+                        distance,
+                        custom_metric,
+                        &centroids,
+                        &vector,
+                    ))
+                })
+                .collect::<OperationResult<Vec<_>>>()
+        })?;
+
+        let mut lists = vec![Vec::new(); centroids.len()];
+        for (point_id, list) in point_ids.into_iter().zip(assignments) {
+            lists[list].push(point_id);
+        }
+
+        let lists = IvfLists { centroids, lists };
+        atomic_save_bin(&Self::get_lists_path(path), &lists)?;
+
+        drop(vector_storage_ref);
+        drop(id_tracker_ref);
+
+        Ok(IvfIndex {
+            storages: IndexedStorages {
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+            },
+            config: ivf_config,
+            path: path.to_owned(),
+            distance,
+            lists,
+            searches_telemetry: IndexSearchesTelemetry::new(),
+        })
+    }
+
+    fn get_lists_path(path: &Path) -> PathBuf {
+        path.join(IVF_LISTS_FILE)
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    /// Expected number of points scanned by a search, which probes `probes` lists
+    fn expected_scanned_points(&self, probes: usize) -> usize {
+        let lists_count = self.lists.lists.len().max(1);
+        self.indexed_vector_count()
+            .saturating_mul(probes.min(lists_count))
+            / lists_count
+    }
+
+    fn probes(&self, params: Option<&SearchParams>) -> usize {
+        params
+            .and_then(|params| params.ivf_probes)
+            .unwrap_or_else(|| self.config.probes())
+    }
+
+    /// Lists ordered by the score of their centroids against the query, best first
+    ///
+    /// Centroids stand in for the vectors of their lists, so recommendation, discovery and
+    /// context queries select lists with the same formula they score points with.
+    fn closest_lists(
+        &self,
+        vector: &QueryVector,
+        custom_metric: Option<&Arc<WasmMetricModule>>,
+    ) -> impl Iterator<Item = usize> {
+        let score_centroids =
+            |score: &dyn Fn(&dyn Fn(&VectorInternal) -> ScoreType) -> ScoreType| {
+                self.lists
+                    .centroids
+                    .iter()
+                    .map(|centroid| {
+                        score(&|example: &VectorInternal| match example {
+                            VectorInternal::Dense(example) => {
+                                similarity(self.distance, custom_metric, centroid, example)
+                            }
+                            // Query vectors are checked against the storage by the scorer
+                            VectorInternal::Sparse(_) | VectorInternal::MultiDense(_) => 0.0,
+                        })
+                    })
+                    .collect_vec()
+            };
+
+        let scores = match vector {
+            QueryVector::Nearest(example) => {
+                score_centroids(&|score_example| score_example(example))
+            }
+            QueryVector::RecommendBestScore(query) => {
+                let query = RecoBestScoreQuery::from(query.clone());
+                score_centroids(&|score_example| query.score_by(score_example))
+            }
+            QueryVector::RecommendSumScores(query) => {
+                let query = RecoSumScoresQuery::from(query.clone());
+                score_centroids(&|score_example| query.score_by(score_example))
+            }
+            QueryVector::Discovery(query) => {
+                score_centroids(&|score_example| query.score_by(score_example))
+            }
+            QueryVector::Context(query) => {
+                score_centroids(&|score_example| query.score_by(score_example))
+            }
+        };
+
+        scores
+            .into_iter()
+            .enumerate()
+            .sorted_unstable_by(|(_, a), (_, b)| b.total_cmp(a))
+            .map(|(list, _)| list)
+    }
+
+    /// Search points of the lists closest to the query
+    ///
+    /// At least `probes` lists are scanned. More lists are scanned, until enough points
+    /// satisfying the filter are found.
+    fn search_lists(
+        &self,
+        vector: &QueryVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let id_tracker = self.storages.id_tracker.borrow();
+        let vector_storage = self.storages.vector_storage.borrow();
+        let quantized_vectors = self.storages.quantized_vectors.borrow();
+        let payload_index = self.storages.payload_index.borrow();
+
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+        let budget = ScoringBudget::from_params(params);
+
+        // Filter is checked while collecting candidates, to know when enough of them are found
+        let filter_context = filter.map(|f| payload_index.filter_context(f, &hw_counter));
+
+        let scorer = new_search_scorer(
+            vector,
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            deleted_points,
+            params,
+            query_context.hardware_counter(),
+        )?
+        .with_budget(budget.as_ref());
+        let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
+        let probes = self.probes(params);
+
+        let closest_lists = self.closest_lists(vector, vector_storage.custom_metric());
+
+        let mut candidates = Vec::new();
+        for (probed, list) in closest_lists.enumerate() {
+            if probed >= probes && candidates.len() >= oversampled_top {
+                break;
+            }
+            check_process_stopped(&is_stopped)?;
+            candidates.extend(self.lists.lists[list].iter().copied().filter(|&point_id| {
+                scorer.filters().check_vector(point_id)
+                    && filter_context
+                        .as_ref()
+                        .is_none_or(|filter_context| filter_context.check(point_id))
+            }));
+        }
+
+        let search_result =
+            scorer.peek_top_iter(candidates.into_iter(), oversampled_top, &is_stopped)?;
+        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+            query_context.mark_search_truncated();
+        }
+
+        postprocess_search_result(
+            search_result,
+            id_tracker.deleted_point_bitslice(),
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            vector,
+            params,
+            top,
+            query_context.hardware_counter(),
+        )
+    }
+}
+
+impl VectorIndex for IvfIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        self.storages.search_with_plain_fallback(
+            &self.searches_telemetry,
+            vectors,
+            filter,
+            top,
+            params,
+            query_context,
+            self.expected_scanned_points(self.probes(params)),
+            |vector, filter| {
+                self.search_lists(
+                    vector,
+                    filter.map(|(filter, _)| filter),
+                    top,
+                    params,
+                    query_context,
+                )
+            },
+        )
+    }
+
+    fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            unfiltered_ivf: tm.unfiltered_index.lock().get_statistics(detail),
+            filtered_ivf: tm.filtered_index.lock().get_statistics(detail),
+            ..tm.plain_telemetry_data(detail)
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![Self::get_lists_path(&self.path)]
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        self.files()
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.lists.lists.iter().map(Vec::len).sum()
+    }
+
+    fn size_of_searchable_vectors_in_bytes(&self) -> usize {
+        self.storages
+            .vector_storage
+            .borrow()
+            .size_of_available_vectors_in_bytes()
+    }
+
+    fn update_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: Option<VectorRef>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update IVF index"))
+    }
+}
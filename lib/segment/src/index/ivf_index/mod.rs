@@ -0,0 +1,5 @@
+mod clustering;
+pub mod ivf;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::flags::FeatureFlags;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::{Builder, TempDir};
+
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, QueryVector, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::id_tracker::IdTracker;
+use crate::index::VectorIndex;
+use crate::index::ivf_index::ivf::{IvfIndex, IvfIndexOpenArgs};
+use crate::segment::Segment;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{
+    Condition, Distance, ExtendedPointId, Filter, HasIdCondition, HnswGlobalConfig, IvfConfig,
+    SearchParams, SeqNumberType,
+};
+use crate::vector_storage::query::RecoQuery;
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 2_000;
+
+fn build_segment(dir: &TempDir, rng: &mut StdRng) -> Segment {
+    let hw_counter = HardwareCounterCell::new();
+    let mut segment = build_simple_segment(dir.path(), DIM, Distance::Cosine).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(rng, DIM);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+    segment
+}
+
+fn build_index(segment: &Segment, ivf_config: IvfConfig, rng: &mut StdRng) -> (TempDir, IvfIndex) {
+    let stopped = AtomicBool::new(false);
+    let ivf_dir = Builder::new().prefix("ivf_dir").tempdir().unwrap();
+    let permit = Arc::new(ResourcePermit::dummy(4));
+
+    let index = IvfIndex::build(
+        IvfIndexOpenArgs {
+            path: ivf_dir.path(),
+            id_tracker: segment.id_tracker.clone(),
+            vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            quantized_vectors: Default::default(),
+            payload_index: segment.payload_index.clone(),
+            ivf_config,
+        },
+        VectorIndexBuildArgs {
+            permit,
+            old_indices: &[],
+            gpu_device: None,
+            rng,
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+        },
+    )
+    .unwrap();
+    (ivf_dir, index)
+}
+
+#[test]
+fn test_ivf_search() {
+    let top = 10;
+    let lists = 20;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment = build_segment(&dir, &mut rng);
+
+    let ivf_config = IvfConfig {
+        lists: Some(lists),
+        probes: None,
+    };
+    let (_ivf_dir, index) = build_index(&segment, ivf_config, &mut rng);
+    assert_eq!(index.indexed_vector_count(), NUM_VECTORS as usize);
+
+    let probe_all = SearchParams {
+        ivf_probes: Some(lists),
+        ..Default::default()
+    };
+    let exact = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+
+    for _ in 0..10 {
+        let query = QueryVector::from(random_vector(&mut rng, DIM));
+
+        // Scanning all lists is exhaustive
+        let probed = index
+            .search(&[&query], None, top, Some(&probe_all), &Default::default())
+            .unwrap();
+        let expected = index
+            .search(&[&query], None, top, Some(&exact), &Default::default())
+            .unwrap();
+        assert_eq!(probed, expected);
+
+        let approximate = index
+            .search(&[&query], None, top, None, &Default::default())
+            .unwrap();
+        assert_eq!(approximate[0].len(), top);
+    }
+
+    // Recommendation queries select lists by their own scoring
+    let reco_query = QueryVector::RecommendBestScore(RecoQuery::new(
+        vec![random_vector(&mut rng, DIM).into()],
+        vec![random_vector(&mut rng, DIM).into()],
+    ));
+    let probed = index
+        .search(
+            &[&reco_query],
+            None,
+            top,
+            Some(&probe_all),
+            &Default::default(),
+        )
+        .unwrap();
+    let expected = index
+        .search(&[&reco_query], None, top, Some(&exact), &Default::default())
+        .unwrap();
+    assert_eq!(probed, expected);
+    let approximate = index
+        .search(&[&reco_query], None, top, None, &Default::default())
+        .unwrap();
+    assert_eq!(approximate[0].len(), top);
+
+    // Filtered search only returns matching points
+    let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+        (0..NUM_VECTORS)
+            .step_by(3)
+            .map(Into::into)
+            .collect::<ahash::AHashSet<_>>(),
+    )));
+    let query = QueryVector::from(random_vector(&mut rng, DIM));
+    let filtered = index
+        .search(&[&query], Some(&filter), top, None, &Default::default())
+        .unwrap();
+    assert_eq!(filtered[0].len(), top);
+    let id_tracker = segment.id_tracker.borrow();
+    for scored in &filtered[0] {
+        let ExtendedPointId::NumId(external_id) = id_tracker.external_id(scored.idx).unwrap()
+        else {
+            panic!("unexpected point id");
+        };
+        assert_eq!(external_id % 3, 0);
+    }
+}
@@ -1,5 +1,8 @@
+pub mod buffer_pool;
+pub mod disk_ann_index;
 pub mod field_index;
 pub mod hnsw_index;
+pub mod ivf_index;
 mod key_encoding;
 pub mod payload_config;
 mod payload_index_base;
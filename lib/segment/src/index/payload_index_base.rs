@@ -15,10 +15,13 @@ use crate::types::{Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadK
 
 pub enum BuildIndexResult {
     /// Index was built
+    ///
+    /// If the field was already indexed with another schema, the new index is built
+    /// next to the old one, and replaces it when applied.
     Built(Vec<FieldIndex>),
     /// Index was already built
     AlreadyBuilt,
-    /// Field Index already exists, but incompatible schema
+    /// Field Index already exists, but incompatible schema, and can't be rebuilt next to it
     /// Requires extra actions to remove the old index.
     IncompatibleSchema,
 }
@@ -35,7 +38,7 @@ pub trait PayloadIndex {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<BuildIndexResult>;
 
-    /// Apply already built indexes
+    /// Apply already built indexes, replacing the rebuilt ones
     fn apply_index(
         &mut self,
         field: PayloadKeyType,
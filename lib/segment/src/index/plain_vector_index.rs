@@ -6,7 +6,7 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use parking_lot::Mutex;
 
-use super::hnsw_index::point_scorer::FilteredScorer;
+use super::hnsw_index::point_scorer::{FilteredScorer, ScoringBudget};
 use crate::common::BYTES_IN_KB;
 use crate::common::operation_error::OperationResult;
 use crate::common::operation_time_statistics::{
@@ -120,6 +120,7 @@ impl VectorIndex for PlainVectorIndex {
                     .map(|&vector| {
                         let quantization_enabled =
                             is_quantized_search(quantized_storage.as_ref(), params);
+                        let budget = ScoringBudget::from_params(params);
                         let scorer = FilteredScorer::new(
                             vector.to_owned(),
                             &vector_storage,
@@ -129,7 +130,8 @@ impl VectorIndex for PlainVectorIndex {
                             None,
                             deleted_points,
                             query_context.hardware_counter(),
-                        )?;
+                        )?
+                        .with_budget(budget.as_ref());
                         let oversampled_top =
                             get_oversampled_top(quantized_storage.as_ref(), params, top);
                         let search_result = scorer.peek_top_iter(
@@ -137,6 +139,9 @@ impl VectorIndex for PlainVectorIndex {
                             oversampled_top,
                             &is_stopped,
                         )?;
+                        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+                            query_context.mark_search_truncated();
+                        }
                         let res = postprocess_search_result(
                             search_result,
                             id_tracker.deleted_point_bitslice(),
@@ -164,6 +169,7 @@ impl VectorIndex for PlainVectorIndex {
                     .map(|&vector| {
                         let quantization_enabled =
                             is_quantized_search(quantized_storage.as_ref(), params);
+                        let budget = ScoringBudget::from_params(params);
                         let scorer = FilteredScorer::new(
                             vector.to_owned(),
                             &vector_storage,
@@ -173,10 +179,14 @@ impl VectorIndex for PlainVectorIndex {
                             None,
                             deleted_points,
                             query_context.hardware_counter(),
-                        )?;
+                        )?
+                        .with_budget(budget.as_ref());
                         let oversampled_top =
                             get_oversampled_top(quantized_storage.as_ref(), params, top);
                         let search_result = scorer.peek_top_all(oversampled_top, &is_stopped)?;
+                        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+                            query_context.mark_search_truncated();
+                        }
                         let res = postprocess_search_result(
                             search_result,
                             id_tracker.deleted_point_bitslice(),
@@ -212,6 +222,11 @@ impl VectorIndex for PlainVectorIndex {
             filtered_sparse: Default::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
             unfiltered_sparse: OperationDurationStatistics::default(),
+            unfiltered_ivf: OperationDurationStatistics::default(),
+            filtered_ivf: OperationDurationStatistics::default(),
+            unfiltered_disk_ann: OperationDurationStatistics::default(),
+            filtered_disk_ann: OperationDurationStatistics::default(),
+            hnsw: None,
         }
     }
 
@@ -0,0 +1,155 @@
+use std::borrow::Cow;
+
+use roaring::RoaringBitmap;
+
+use crate::index::field_index::FieldIndex;
+use crate::index::field_index::map_index::MapIndex;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::types::{
+    AnyVariants, Condition, FieldCondition, Filter, Match, MatchAny, MatchValue, MinShould,
+    ValueVariants,
+};
+
+impl StructPayloadIndex {
+    /// Points matching the `filter`, evaluated with set operations on bitmap posting lists
+    ///
+    /// Clauses are combined as AND (`must`), OR (`should`) and NOT (`must_not`) of the bitmaps,
+    /// so no point has to be checked individually.
+    ///
+    /// Only filters made of keyword `value` and `any` matches, on fields which keep postings as
+    /// bitmaps, can be evaluated this way. Returns `None` for any other filter.
+    pub fn filter_bitmap(&self, filter: &Filter) -> Option<RoaringBitmap> {
+        if !self.is_bitmap_filter(filter) {
+            return None;
+        }
+        self.evaluate_filter_bitmap(filter)
+    }
+
+    /// Check the whole filter upfront, to not compute bitmaps of a filter which can't be evaluated
+    fn is_bitmap_filter(&self, filter: &Filter) -> bool {
+        let Filter {
+            should,
+            min_should,
+            must,
+            must_not,
+        } = filter;
+
+        let has_min_should = min_should
+            .as_ref()
+            .is_some_and(|MinShould { conditions, .. }| !conditions.is_empty());
+        if has_min_should {
+            return false;
+        }
+
+        let conditions = [should, must, must_not]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        !conditions.is_empty()
+            && conditions.into_iter().all(|condition| match condition {
+                Condition::Filter(filter) => self.is_bitmap_filter(filter),
+                Condition::Field(field_condition) => {
+                    self.bitmap_keyword_index(field_condition).is_some()
+                }
+                _ => false,
+            })
+    }
+
+    fn evaluate_filter_bitmap(&self, filter: &Filter) -> Option<RoaringBitmap> {
+        // `None` stands for all points
+        let mut points: Option<RoaringBitmap> = None;
+
+        for condition in filter.must.iter().flatten() {
+            let condition_points = self.condition_bitmap(condition)?;
+            points = Some(match points {
+                Some(points) => points & condition_points.as_ref(),
+                None => condition_points.into_owned(),
+            });
+        }
+
+        if let Some(should) = filter.should.as_ref().filter(|should| !should.is_empty()) {
+            let mut any_points = RoaringBitmap::new();
+            for condition in should {
+                any_points |= self.condition_bitmap(condition)?.as_ref();
+            }
+            points = Some(match points {
+                Some(points) => points & any_points,
+                None => any_points,
+            });
+        }
+
+        let mut points = points.unwrap_or_else(|| self.id_tracker.borrow().iter_ids().collect());
+
+        for condition in filter.must_not.iter().flatten() {
+            points -= self.condition_bitmap(condition)?.as_ref();
+        }
+
+        Some(points)
+    }
+
+    fn condition_bitmap(&self, condition: &Condition) -> Option<Cow<'_, RoaringBitmap>> {
+        match condition {
+            Condition::Filter(filter) => self.evaluate_filter_bitmap(filter).map(Cow::Owned),
+            Condition::Field(field_condition) => {
+                let index = self.bitmap_keyword_index(field_condition)?;
+                match field_condition.r#match.as_ref()? {
+                    Match::Value(MatchValue {
+                        value: ValueVariants::String(value),
+                    }) => index.get_bitmap(value),
+                    Match::Any(MatchAny {
+                        any: AnyVariants::Strings(values),
+                    }) => {
+                        let mut points = RoaringBitmap::new();
+                        for value in values {
+                            points |= index.get_bitmap(value)?.as_ref();
+                        }
+                        Some(Cow::Owned(points))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Keyword index with bitmap postings, which resolves the `condition` on its own
+    fn bitmap_keyword_index(&self, condition: &FieldCondition) -> Option<&MapIndex<str>> {
+        let FieldCondition {
+            key,
+            r#match: Some(r#match),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+            is_empty: None,
+            is_null: None,
+        } = condition
+        else {
+            return None;
+        };
+
+        let is_keyword_match = matches!(
+            r#match,
+            Match::Value(MatchValue {
+                value: ValueVariants::String(_),
+            }) | Match::Any(MatchAny {
+                any: AnyVariants::Strings(_),
+            }),
+        );
+        if !is_keyword_match {
+            return None;
+        }
+
+        self.field_indexes
+            .get(key)?
+            .iter()
+            .find_map(|index| match index {
+                FieldIndex::KeywordIndex(index) => Some(index),
+                _ => None,
+            })
+            .filter(|index| index.has_bitmap_postings())
+    }
+}
@@ -14,8 +14,9 @@ use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::payload_storage::query_checker::{
     check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
-    select_nested_indexes,
+    check_wasm_condition, select_nested_indexes,
 };
+use crate::payload_storage::wasm_condition;
 use crate::types::{
     Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPolygon,
     GeoRadius, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range, RangeInterface,
@@ -182,6 +183,19 @@ impl StructPayloadIndex {
                     )
                 })
             }
+            Condition::Wasm(wasm) => {
+                let Some(module) = wasm_condition::get(&wasm.wasm.name) else {
+                    return Box::new(|_point_id| false);
+                };
+                let hw = hw_counter.fork();
+                Box::new(move |point_id| {
+                    payload_provider.with_payload(
+                        point_id,
+                        |payload| check_wasm_condition(&module, &wasm.wasm, &payload),
+                        &hw,
+                    )
+                })
+            }
             Condition::CustomIdChecker(cond) => {
                 let segment_ids: AHashSet<_> = id_tracker
                     .iter_external()
@@ -1,3 +1,6 @@
+use std::cell::OnceCell;
+
+use bitvec::vec::BitVec;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::PointOffsetType;
 use indexmap::IndexSet;
@@ -7,10 +10,18 @@ use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::types::{
-    AnyVariants, Match, MatchAny, MatchExcept, MatchPhrase, MatchText, MatchTextAny, MatchValue,
-    ValueVariants,
+    AnyVariants, Match, MatchAny, MatchExcept, MatchPattern, MatchPhrase, MatchText, MatchTextAny,
+    MatchValue, ValueVariants,
 };
 
+/// Number of values of `MatchAny`, from which the condition is compiled into a bitmap of matching
+/// points, by unioning posting lists of the values in the index
+///
+/// Checking a point is then a single bit lookup, instead of hashing each value of the point.
+/// The bitmap is built on the first check and lives as long as the checker, so it is built at most
+/// once per request.
+const MATCH_ANY_BITMAP_THRESHOLD: usize = 1000;
+
 pub fn get_match_checkers(
     index: &FieldIndex,
     cond_match: Match,
@@ -27,6 +38,7 @@ pub fn get_match_checkers(
         Match::Phrase(MatchPhrase { phrase }) => {
             get_match_text_checker(phrase, TextQueryType::Phrase, index, hw_acc)
         }
+        Match::Pattern(pattern) => get_match_pattern_checker(pattern, index, hw_acc),
         Match::Any(MatchAny { any }) => get_match_any_checker(any, index, hw_acc),
         Match::Except(MatchExcept { except }) => get_match_except_checker(except, index, hw_acc),
     }
@@ -95,6 +107,31 @@ fn get_match_value_checker(
     }
 }
 
+fn get_match_pattern_checker(
+    pattern: MatchPattern,
+    index: &FieldIndex,
+    hw_acc: HwMeasurementAcc,
+) -> Option<ConditionCheckerFn<'_>> {
+    match index {
+        FieldIndex::KeywordIndex(index) => {
+            let hw_counter = hw_acc.get_counter_cell();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                index.check_values_any(point_id, &hw_counter, |value| pattern.is_match(value))
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::NullIndex(_) => None,
+    }
+}
+
 fn get_match_any_checker(
     any_variant: AnyVariants,
     index: &FieldIndex,
@@ -102,15 +139,18 @@ fn get_match_any_checker(
 ) -> Option<ConditionCheckerFn<'_>> {
     match (any_variant, index) {
         (AnyVariants::Strings(list), FieldIndex::KeywordIndex(index)) => {
-            if list.len() < INDEXSET_ITER_THRESHOLD {
-                let hw_counter = hw_acc.get_counter_cell();
+            let hw_counter = hw_acc.get_counter_cell();
+            if list.len() >= MATCH_ANY_BITMAP_THRESHOLD {
+                Some(lazy_bitmap_checker(move || {
+                    index.get_any_bitmap(list.iter().map(String::as_str), &hw_counter)
+                }))
+            } else if list.len() < INDEXSET_ITER_THRESHOLD {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, &hw_counter, |value| {
                         list.iter().any(|s| s.as_str() == value)
                     })
                 }))
             } else {
-                let hw_counter = hw_acc.get_counter_cell();
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, &hw_counter, |value| list.contains(value))
                 }))
@@ -123,7 +163,11 @@ fn get_match_any_checker(
                 .collect::<Option<IndexSet<_>>>()?;
 
             let hw_counter = hw_acc.get_counter_cell();
-            if list.len() < INDEXSET_ITER_THRESHOLD {
+            if list.len() >= MATCH_ANY_BITMAP_THRESHOLD {
+                Some(lazy_bitmap_checker(move || {
+                    index.get_any_bitmap(&list, &hw_counter)
+                }))
+            } else if list.len() < INDEXSET_ITER_THRESHOLD {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, &hw_counter, |value| {
                         list.iter().any(|i| i == value)
@@ -137,7 +181,11 @@ fn get_match_any_checker(
         }
         (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
             let hw_counter = hw_acc.get_counter_cell();
-            if list.len() < INDEXSET_ITER_THRESHOLD {
+            if list.len() >= MATCH_ANY_BITMAP_THRESHOLD {
+                Some(lazy_bitmap_checker(move || {
+                    index.get_any_bitmap(&list, &hw_counter)
+                }))
+            } else if list.len() < INDEXSET_ITER_THRESHOLD {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, &hw_counter, |value| {
                         list.iter().any(|i| i == value)
@@ -171,6 +219,17 @@ fn get_match_any_checker(
     }
 }
 
+/// Checker of points in the bitmap, which is only built once the first point is checked
+fn lazy_bitmap_checker<'a>(build: impl Fn() -> BitVec + 'a) -> ConditionCheckerFn<'a> {
+    let bitmap = OnceCell::new();
+    Box::new(move |point_id: PointOffsetType| {
+        bitmap
+            .get_or_init(&build)
+            .get(point_id as usize)
+            .is_some_and(|bit| *bit)
+    })
+}
+
 fn get_match_except_checker(
     except: AnyVariants,
     index: &FieldIndex,
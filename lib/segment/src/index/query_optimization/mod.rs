@@ -1,3 +1,4 @@
+pub mod bitmap_filter;
 pub mod condition_converter;
 pub mod optimized_filter;
 pub mod optimizer;
@@ -9,12 +9,21 @@ use crate::index::query_estimator::{
     invert_estimation,
 };
 use crate::index::query_optimization::optimized_filter::{
-    OptimizedCondition, OptimizedFilter, OptimizedMinShould,
+    ConditionCheckerFn, OptimizedCondition, OptimizedFilter, OptimizedMinShould,
 };
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::types::{Condition, Filter, MinShould};
 
+/// Relative cost of checking a point with a condition, which is resolved by an index
+const INDEX_CHECK_COST: f64 = 1.0;
+
+/// Relative cost of checking a point with a condition, which reads the point payload
+const PAYLOAD_CHECK_COST: f64 = 10.0;
+
+/// Relative cost of running a WASM condition for a point
+const WASM_CHECK_COST: f64 = 100.0;
+
 impl StructPayloadIndex {
     /// Converts user-provided filtering condition into optimized representation
     ///
@@ -22,7 +31,8 @@ impl StructPayloadIndex {
     ///
     /// * Convert each condition into a checker function
     /// * Use column index, avoid reading Payload, if possible
-    /// * Re-order operations using estimated cardinalities
+    /// * Resolve keyword matches with set operations on bitmap postings, if possible
+    /// * Re-order operations using estimated cardinalities and costs of checks
     ///
     /// ToDo: Add optimizations between clauses
     ///
@@ -42,6 +52,18 @@ impl StructPayloadIndex {
         total: usize,
         hw_counter: &HardwareCounterCell,
     ) -> (OptimizedFilter<'a>, CardinalityEstimation) {
+        if let Some(points) = self.filter_bitmap(filter) {
+            let estimation = CardinalityEstimation::exact(points.len() as usize);
+            let checker: ConditionCheckerFn = Box::new(move |point_id| points.contains(point_id));
+            let optimized_filter = OptimizedFilter {
+                should: None,
+                min_should: None,
+                must: Some(vec![OptimizedCondition::Checker(checker)]),
+                must_not: None,
+            };
+            return (optimized_filter, estimation);
+        }
+
         let mut filter_estimations: Vec<CardinalityEstimation> = vec![];
 
         let optimized_filter = OptimizedFilter {
@@ -183,11 +205,11 @@ impl StructPayloadIndex {
         total: usize,
         hw_counter: &HardwareCounterCell,
     ) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation) {
-        let mut converted =
-            self.convert_conditions(conditions, payload_provider, total, hw_counter);
-        // Less probable conditions first
-        converted.sort_by_key(|(_, estimation)| estimation.exp);
-        let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+        let converted = self.convert_conditions(conditions, payload_provider, total, hw_counter);
+        // Cheap and less probable conditions first, the chain stops at the first failed check
+        let (conditions, estimations) = sort_short_circuit(conditions, converted, |estimation| {
+            1.0 - match_probability(estimation, total)
+        });
 
         (conditions, combine_must_estimations(&estimations, total))
     }
@@ -199,11 +221,11 @@ impl StructPayloadIndex {
         total: usize,
         hw_counter: &HardwareCounterCell,
     ) -> (Vec<OptimizedCondition<'a>>, CardinalityEstimation) {
-        let mut converted =
-            self.convert_conditions(conditions, payload_provider, total, hw_counter);
-        // More probable conditions first, as it will be reverted
-        converted.sort_by_key(|(_, estimation)| estimation.exp);
-        let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+        let converted = self.convert_conditions(conditions, payload_provider, total, hw_counter);
+        // Cheap and more probable conditions first, as it will be reverted
+        let (conditions, estimations) = sort_short_circuit(conditions, converted, |estimation| {
+            match_probability(estimation, total)
+        });
 
         (
             conditions,
@@ -217,3 +239,125 @@ impl StructPayloadIndex {
         )
     }
 }
+
+/// Probability of a point to match the condition, based on its expected cardinality
+fn match_probability(estimation: &CardinalityEstimation, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (estimation.exp as f64 / total as f64).clamp(0.0, 1.0)
+}
+
+/// Relative cost of checking a single point with the condition
+///
+/// Conditions, resolved by an index, have primary clauses in their estimation.
+fn condition_check_cost(condition: &Condition, estimation: &CardinalityEstimation) -> f64 {
+    match condition {
+        Condition::HasId(_) | Condition::HasVector(_) | Condition::CustomIdChecker(_) => {
+            INDEX_CHECK_COST
+        }
+        Condition::Field(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => {
+            if estimation.primary_clauses.is_empty() {
+                PAYLOAD_CHECK_COST
+            } else {
+                INDEX_CHECK_COST
+            }
+        }
+        Condition::Nested(_) | Condition::Filter(_) => PAYLOAD_CHECK_COST,
+        Condition::Wasm(_) => WASM_CHECK_COST,
+    }
+}
+
+/// Order of conditions in a chain, which stops at the first check with a decisive result
+///
+/// Sorting by `cost / P(stop)` minimizes the expected cost of the whole chain.
+/// With equal costs, conditions with higher stop probability go first,
+/// and conditions with equal rank keep the order of the request.
+fn sort_short_circuit<'a>(
+    conditions: &[Condition],
+    converted: Vec<(OptimizedCondition<'a>, CardinalityEstimation)>,
+    stop_probability: impl Fn(&CardinalityEstimation) -> f64,
+) -> (Vec<OptimizedCondition<'a>>, Vec<CardinalityEstimation>) {
+    let mut ranked = conditions
+        .iter()
+        .zip(converted)
+        .map(|(condition, (optimized, estimation))| {
+            let cost = condition_check_cost(condition, &estimation);
+            let probability = stop_probability(&estimation);
+            let rank = if probability > 0.0 {
+                cost / probability
+            } else {
+                f64::INFINITY
+            };
+            (rank, optimized, estimation)
+        })
+        .collect_vec();
+
+    ranked.sort_by(|(rank_a, ..), (rank_b, ..)| rank_a.total_cmp(rank_b));
+
+    ranked
+        .into_iter()
+        .map(|(_, optimized, estimation)| (optimized, estimation))
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ahash::AHashSet;
+
+    use super::*;
+    use crate::index::field_index::PrimaryCondition;
+    use crate::json_path::JsonPath;
+    use crate::types::{FieldCondition, HasIdCondition, Match, ValueVariants};
+
+    #[test]
+    fn test_sort_short_circuit() {
+        let total = 100;
+        let field_condition = FieldCondition::new_match(
+            JsonPath::from_str("a").unwrap(),
+            Match::new_value(ValueVariants::Integer(1)),
+        );
+        let has_id = HasIdCondition::from([1.into()].into_iter().collect::<AHashSet<_>>());
+
+        let conditions = vec![
+            Condition::Field(field_condition.clone()),
+            Condition::HasId(has_id),
+            Condition::Field(field_condition.clone()),
+        ];
+        let estimations = vec![
+            // Selective, but reads payload
+            CardinalityEstimation::exact(10),
+            // Cheap, but less selective
+            CardinalityEstimation::exact(30),
+            // Indexed and selective
+            CardinalityEstimation::exact(10)
+                .with_primary_clause(PrimaryCondition::Condition(Box::new(field_condition))),
+        ];
+        let convert = || {
+            estimations
+                .iter()
+                .map(|estimation| {
+                    let checker: ConditionCheckerFn = Box::new(|_| true);
+                    (OptimizedCondition::Checker(checker), estimation.clone())
+                })
+                .collect_vec()
+        };
+
+        let (_, sorted) = sort_short_circuit(&conditions, convert(), |estimation| {
+            1.0 - match_probability(estimation, total)
+        });
+        let sorted = sorted.iter().map(|estimation| estimation.exp).collect_vec();
+        assert_eq!(sorted, vec![10, 30, 10]);
+
+        let (_, sorted) = sort_short_circuit(&conditions, convert(), |estimation| {
+            match_probability(estimation, total)
+        });
+        let sorted = sorted
+            .iter()
+            .map(|estimation| (estimation.exp, estimation.primary_clauses.is_empty()))
+            .collect_vec();
+        assert_eq!(sorted, vec![(30, true), (10, false), (10, true)]);
+    }
+}
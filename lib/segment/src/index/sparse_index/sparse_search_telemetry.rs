@@ -38,6 +38,11 @@ impl SparseSearchesTelemetry {
             filtered_sparse: self.filtered_sparse.lock().get_statistics(detail),
             unfiltered_sparse: self.unfiltered_sparse.lock().get_statistics(detail),
             unfiltered_exact: Default::default(),
+            unfiltered_ivf: Default::default(),
+            filtered_ivf: Default::default(),
+            unfiltered_disk_ann: Default::default(),
+            filtered_disk_ann: Default::default(),
+            hnsw: None,
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::fs::create_dir_all;
+use std::fs::{self, create_dir_all};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -8,7 +8,10 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::counter::iterator_hw_measurement::HwMeasurementIteratorExt;
 use common::either_variant::EitherVariant;
 use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use itertools::{Either, Itertools as _};
 use schemars::_serde_json::Value;
+use serde::{Deserialize, Serialize};
 
 use super::field_index::facet_index::FacetIndexEnum;
 #[cfg(feature = "rocksdb")]
@@ -41,6 +44,34 @@ use crate::types::{
 };
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
+/// Directory inside of the payload index, in which indexes are rebuilt with a new schema
+const REBUILD_DIR: &str = "rebuild";
+
+/// File in the rebuild directory of a field, written once the rebuilt index is complete
+const REBUILD_STATE_FILE: &str = "rebuild.json";
+
+/// State of a complete rebuild, enough to finish replacing the old index after a restart
+#[derive(Debug, Deserialize, Serialize)]
+struct RebuildState {
+    field: PayloadKeyType,
+    schema: PayloadFieldSchema,
+    types: Vec<FullPayloadIndexType>,
+    /// Entries of the payload index directory, which belong to the old index
+    old_entries: Vec<PathBuf>,
+    /// Entries of the rebuild directory, which belong to the new index
+    new_entries: Vec<PathBuf>,
+}
+
+/// Remove a file or a directory, if it exists
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
 #[derive(Debug)]
 #[allow(clippy::enum_variant_names)]
 enum StorageType {
@@ -306,9 +337,12 @@ impl StructPayloadIndex {
         create: bool,
     ) -> OperationResult<Self> {
         create_dir_all(path)?;
+
         let config_path = PayloadConfig::get_config_path(path);
         let config = if config_path.exists() {
-            PayloadConfig::load(&config_path)?
+            let config = PayloadConfig::load(&config_path)?;
+            Self::recover_rebuilds(path, &config)?;
+            config
         } else {
             #[cfg(feature = "rocksdb")]
             {
@@ -449,14 +483,29 @@ impl StructPayloadIndex {
         field: PayloadKeyTypeRef,
         payload_schema: &PayloadFieldSchema,
         hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Vec<FieldIndex>> {
+        self.build_field_indexes_with(
+            self.selector(payload_schema),
+            &self.path,
+            field,
+            payload_schema,
+            hw_counter,
+        )
+    }
+
+    fn build_field_indexes_with(
+        &self,
+        selector: IndexSelector<'_>,
+        dir: &Path,
+        field: PayloadKeyTypeRef,
+        payload_schema: &PayloadFieldSchema,
+        hw_counter: &HardwareCounterCell,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut builders = self
-            .selector(payload_schema)
-            .index_builder(field, payload_schema)?;
+        let mut builders = selector.index_builder(field, payload_schema)?;
 
         // Special null index complements every index.
-        let null_index = IndexSelector::null_builder(&self.path, field)?;
+        let null_index = IndexSelector::null_builder(dir, field)?;
         builders.push(null_index);
 
         for index in &mut builders {
@@ -563,6 +612,7 @@ impl StructPayloadIndex {
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path, hw_counter)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
+            Condition::Wasm(_) => CardinalityEstimation::unknown(self.available_point_count()),
 
             Condition::CustomIdChecker(cond) => {
                 cond.estimate_cardinality(self.id_tracker.borrow().available_point_count())
@@ -617,6 +667,22 @@ impl StructPayloadIndex {
         id_tracker: &'a IdTrackerSS,
         query_cardinality: &'a CardinalityEstimation,
         hw_counter: &'a HardwareCounterCell,
+    ) -> impl Iterator<Item = PointOffsetType> + 'a {
+        // Filter is resolved with set operations on bitmaps, matched points are known upfront
+        if let Some(points) = self.filter_bitmap(filter) {
+            return Either::Left(points.into_iter());
+        }
+
+        Either::Right(self.iter_checked_points(filter, id_tracker, query_cardinality, hw_counter))
+    }
+
+    /// Iterate points of the primary clauses, or all points, and check them against the filter
+    fn iter_checked_points<'a>(
+        &'a self,
+        filter: &'a Filter,
+        id_tracker: &'a IdTrackerSS,
+        query_cardinality: &'a CardinalityEstimation,
+        hw_counter: &'a HardwareCounterCell,
     ) -> impl Iterator<Item = PointOffsetType> + 'a {
         if query_cardinality.primary_clauses.is_empty() {
             let full_scan_iterator = id_tracker.iter_ids();
@@ -714,6 +780,207 @@ impl StructPayloadIndex {
         }
     }
 
+    /// Directory, in which a new index for the field is built while the old one is still in use
+    fn rebuild_dir(&self, field: PayloadKeyTypeRef) -> PathBuf {
+        self.path.join(REBUILD_DIR).join(field.filename())
+    }
+
+    /// Selector for building a new index in `dir`, next to the existing one
+    ///
+    /// Returns `None` if storage doesn't allow to keep both indexes at the same time.
+    /// Indexes in RocksDB are stored by field name, so the old index must be dropped first.
+    fn rebuild_selector<'a>(
+        &self,
+        dir: &'a Path,
+        payload_schema: &PayloadFieldSchema,
+    ) -> Option<IndexSelector<'a>> {
+        match self.selector(payload_schema) {
+            #[cfg(feature = "rocksdb")]
+            IndexSelector::RocksDb(_) => None,
+            IndexSelector::Mmap(IndexSelectorMmap { dir: _, is_on_disk }) => {
+                Some(IndexSelector::Mmap(IndexSelectorMmap { dir, is_on_disk }))
+            }
+            IndexSelector::Gridstore(IndexSelectorGridstore { dir: _ }) => {
+                Some(IndexSelector::Gridstore(IndexSelectorGridstore { dir }))
+            }
+        }
+    }
+
+    /// Build a new index for the already indexed field, while the old index keeps serving reads
+    fn rebuild_field_indexes(
+        &self,
+        field: PayloadKeyTypeRef,
+        payload_schema: &PayloadFieldSchema,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<BuildIndexResult> {
+        let rebuild_dir = self.rebuild_dir(field);
+        let Some(selector) = self.rebuild_selector(&rebuild_dir, payload_schema) else {
+            return Ok(BuildIndexResult::IncompatibleSchema);
+        };
+
+        if rebuild_dir.exists() {
+            fs::remove_dir_all(&rebuild_dir)?;
+        }
+        create_dir_all(&rebuild_dir)?;
+
+        let indexes = self
+            .build_field_indexes_with(selector, &rebuild_dir, field, payload_schema, hw_counter)
+            .inspect_err(|_| {
+                if let Err(err) = fs::remove_dir_all(&rebuild_dir) {
+                    log::warn!("Failed to remove payload index rebuild directory: {err}");
+                }
+            })?;
+
+        Ok(BuildIndexResult::Built(indexes))
+    }
+
+    /// Replace the index of the field with the one, built by [`Self::rebuild_field_indexes`]
+    ///
+    /// Files of the new index are moved in place of the old ones, so it is reopened from there.
+    fn swap_rebuilt_index(
+        &mut self,
+        field: PayloadKeyType,
+        payload_schema: PayloadFieldSchema,
+        field_index: Vec<FieldIndex>,
+    ) -> OperationResult<()> {
+        let state = self.commit_rebuild(&field, payload_schema, field_index)?;
+
+        if let Some(old_indexes) = self.field_indexes.remove(&field) {
+            for index in old_indexes {
+                index.cleanup()?;
+            }
+        }
+
+        Self::finish_rebuild(&self.path, &self.rebuild_dir(&field), &state)?;
+
+        let mut payload_schema = self.config.indices[&field].clone();
+        let (indexes, is_dirty) = self.load_from_db(&field, &mut payload_schema, false)?;
+        self.field_indexes.insert(field.clone(), indexes);
+
+        if is_dirty {
+            self.config.indices.insert(field, payload_schema);
+            self.save_config()?;
+        }
+
+        Ok(())
+    }
+
+    /// Switch the config of the field to the rebuilt index, before the old index is removed
+    ///
+    /// From here on, the rebuild is finished by [`Self::recover_rebuilds`] if interrupted.
+    fn commit_rebuild(
+        &mut self,
+        field: &PayloadKeyType,
+        payload_schema: PayloadFieldSchema,
+        field_index: Vec<FieldIndex>,
+    ) -> OperationResult<RebuildState> {
+        let rebuild_dir = self.rebuild_dir(field);
+
+        let types: Vec<_> = field_index
+            .iter()
+            .map(|i| i.get_full_index_type())
+            .collect();
+        for index in &field_index {
+            index.flusher()()?;
+        }
+        drop(field_index);
+
+        let old_entries = self
+            .field_indexes
+            .get(field)
+            .into_iter()
+            .flatten()
+            .flat_map(|index| index.files())
+            .filter_map(|file| {
+                let entry = file.strip_prefix(&self.path).ok()?.components().next()?;
+                Some(PathBuf::from(entry.as_os_str()))
+            })
+            .unique()
+            .collect();
+        let new_entries = fs::read_dir(&rebuild_dir)?
+            .map(|entry| entry.map(|entry| PathBuf::from(entry.file_name())))
+            .collect::<Result<_, _>>()?;
+
+        let state = RebuildState {
+            field: field.clone(),
+            schema: payload_schema,
+            types,
+            old_entries,
+            new_entries,
+        };
+        atomic_save_json(&rebuild_dir.join(REBUILD_STATE_FILE), &state)?;
+
+        self.config.indices.insert(
+            field.clone(),
+            PayloadFieldSchemaWithIndexType::new(state.schema.clone(), state.types.clone()),
+        );
+        self.save_config()?;
+
+        Ok(state)
+    }
+
+    /// Move files of the rebuilt index in place of the old ones
+    ///
+    /// Safe to repeat, if interrupted.
+    fn finish_rebuild(
+        path: &Path,
+        rebuild_dir: &Path,
+        state: &RebuildState,
+    ) -> OperationResult<()> {
+        for entry in &state.old_entries {
+            if !state.new_entries.contains(entry) {
+                remove_entry(&path.join(entry))?;
+            }
+        }
+
+        for entry in &state.new_entries {
+            let source = rebuild_dir.join(entry);
+            // Already moved before the interruption
+            if !source.exists() {
+                continue;
+            }
+            let target = path.join(entry);
+            remove_entry(&target)?;
+            fs::rename(source, target)?;
+        }
+
+        fs::remove_dir_all(rebuild_dir)?;
+        Ok(())
+    }
+
+    /// Finish rebuilds, which were interrupted after the config was switched to the new index
+    ///
+    /// Rebuilds interrupted before that are discarded, the config still refers to the old index.
+    fn recover_rebuilds(path: &Path, config: &PayloadConfig) -> OperationResult<()> {
+        let rebuild_root = path.join(REBUILD_DIR);
+        if !rebuild_root.exists() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&rebuild_root)? {
+            let rebuild_dir = entry?.path();
+            let state: Option<RebuildState> = read_json(&rebuild_dir.join(REBUILD_STATE_FILE)).ok();
+
+            match state {
+                Some(state)
+                    if config.indices.get(&state.field).is_some_and(|schema| {
+                        schema.schema == state.schema && schema.types == state.types
+                    }) =>
+                {
+                    log::debug!(
+                        "Finishing interrupted rebuild of payload index for field `{}`",
+                        state.field,
+                    );
+                    Self::finish_rebuild(path, &rebuild_dir, &state)?;
+                }
+                _ => fs::remove_dir_all(&rebuild_dir)?,
+            }
+        }
+
+        fs::remove_dir_all(&rebuild_root)?;
+        Ok(())
+    }
+
     fn selector_with_type(
         &self,
         index_type: &FullPayloadIndexType,
@@ -823,7 +1090,7 @@ impl PayloadIndex for StructPayloadIndex {
             return if prev_schema.schema == *payload_schema {
                 Ok(BuildIndexResult::AlreadyBuilt)
             } else {
-                Ok(BuildIndexResult::IncompatibleSchema)
+                self.rebuild_field_indexes(field, payload_schema, hw_counter)
             };
         }
         let indexes = self.build_field_indexes(field, payload_schema, hw_counter)?;
@@ -836,6 +1103,10 @@ impl PayloadIndex for StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
         field_index: Vec<FieldIndex>,
     ) -> OperationResult<()> {
+        if self.config.indices.contains_key(&field) && self.rebuild_dir(&field).exists() {
+            return self.swap_rebuilt_index(field, payload_schema, field_index);
+        }
+
         let index_types: Vec<_> = field_index
             .iter()
             .map(|i| i.get_full_index_type())
@@ -1167,6 +1438,7 @@ mod tests {
     use std::str::FromStr;
     use std::sync::atomic::AtomicBool;
 
+    use itertools::Itertools as _;
     use tempfile::Builder;
 
     use super::*;
@@ -1175,7 +1447,9 @@ mod tests {
     use crate::index::payload_config::{IndexMutability, PayloadIndexType};
     use crate::segment_constructor::load_segment;
     use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
-    use crate::types::{Distance, PayloadSchemaType};
+    use crate::types::{
+        AnyVariants, Distance, Match, PayloadSchemaType, SeqNumberType, ValueVariants,
+    };
 
     #[test]
     fn test_load_payload_index() {
@@ -1251,4 +1525,265 @@ mod tests {
         let schema = payload_config.indices.get(&key).unwrap();
         check_index_types(&schema.types);
     }
+
+    #[test]
+    fn test_filter_bitmap() {
+        let dir = Builder::new().prefix("payload_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+        let payloads = [
+            serde_json::json!({"color": "red"}),
+            serde_json::json!({"color": "blue"}),
+            serde_json::json!({"color": ["red", "green"]}),
+            serde_json::json!({"color": "green", "size": 3}),
+            serde_json::json!({"size": 5}),
+        ];
+        for (idx, payload) in payloads.into_iter().enumerate() {
+            let op_num = idx as SeqNumberType;
+            let point_id = (idx as u64).into();
+            segment
+                .upsert_point(
+                    op_num,
+                    point_id,
+                    only_default_vector(&[1.0, 1.0]),
+                    &hw_counter,
+                )
+                .unwrap();
+            let payload: Payload = serde_json::from_value(payload).unwrap();
+            segment
+                .set_full_payload(op_num, point_id, &payload, &hw_counter)
+                .unwrap();
+        }
+
+        let key = JsonPath::from_str("color").unwrap();
+        segment
+            .create_field_index(
+                10,
+                &key,
+                Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+                &hw_counter,
+            )
+            .unwrap();
+
+        let color = |value: &str| {
+            Condition::Field(FieldCondition::new_match(
+                key.clone(),
+                Match::new_value(ValueVariants::String(value.to_string())),
+            ))
+        };
+        let payload_index = segment.payload_index.borrow();
+        let filter_points = |filter: &Filter| {
+            payload_index
+                .filter_bitmap(filter)
+                .map(|points| points.into_iter().collect_vec())
+        };
+
+        assert_eq!(
+            filter_points(&Filter::new_must(color("red"))),
+            Some(vec![0, 2]),
+        );
+        assert_eq!(
+            filter_points(&Filter {
+                should: Some(vec![color("red"), color("blue")]),
+                ..Default::default()
+            }),
+            Some(vec![0, 1, 2]),
+        );
+        assert_eq!(
+            filter_points(&Filter::new_must(Condition::Field(
+                FieldCondition::new_match(
+                    key.clone(),
+                    Match::new_any(AnyVariants::Strings(
+                        ["blue", "green"].into_iter().map(String::from).collect(),
+                    )),
+                )
+            ))),
+            Some(vec![1, 2, 3]),
+        );
+
+        // Points without the field don't match the condition, so they pass `must_not`
+        assert_eq!(
+            filter_points(&Filter::new_must_not(color("green"))),
+            Some(vec![0, 1, 4]),
+        );
+        assert_eq!(
+            filter_points(&Filter {
+                must: Some(vec![color("red")]),
+                must_not: Some(vec![color("green")]),
+                ..Default::default()
+            }),
+            Some(vec![0]),
+        );
+        assert_eq!(
+            filter_points(&Filter {
+                must: Some(vec![Condition::Filter(Filter {
+                    should: Some(vec![color("blue"), color("green")]),
+                    ..Default::default()
+                })]),
+                must_not: Some(vec![color("red")]),
+                ..Default::default()
+            }),
+            Some(vec![1, 3]),
+        );
+
+        // Conditions on other fields have to be checked point by point
+        let size = Condition::Field(FieldCondition::new_match(
+            JsonPath::from_str("size").unwrap(),
+            Match::new_value(ValueVariants::Integer(3)),
+        ));
+        let mixed_filter = Filter {
+            must: Some(vec![color("green"), size]),
+            ..Default::default()
+        };
+        assert_eq!(filter_points(&mixed_filter), None);
+
+        // Both ways of filtering agree
+        let matched = payload_index
+            .query_points(&Filter::new_must_not(color("green")), &hw_counter)
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        assert_eq!(matched, vec![0, 1, 4]);
+        let matched = payload_index
+            .query_points(&mixed_filter, &hw_counter)
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        assert_eq!(matched, vec![3]);
+    }
+
+    #[test]
+    fn test_rebuild_payload_index() {
+        let dir = Builder::new().prefix("payload_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+        let key = JsonPath::from_str("name").unwrap();
+
+        let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+        segment
+            .upsert_point(0, 0.into(), only_default_vector(&[1.0, 1.0]), &hw_counter)
+            .unwrap();
+        let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
+        segment
+            .set_full_payload(1, 0.into(), &payload, &hw_counter)
+            .unwrap();
+
+        segment
+            .create_field_index(
+                2,
+                &key,
+                Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+                &hw_counter,
+            )
+            .unwrap();
+
+        // Change schema of the existing index
+        segment
+            .create_field_index(
+                3,
+                &key,
+                Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Text)),
+                &hw_counter,
+            )
+            .unwrap();
+
+        let payload_index_path = segment.current_path.join("payload_index");
+        assert!(!payload_index_path.join(REBUILD_DIR).exists());
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.clone(),
+            Match::new_text("john"),
+        )));
+        let is_stopped = AtomicBool::new(false);
+        let points = segment.read_filtered(None, None, Some(&filter), &is_stopped, &hw_counter);
+        assert_eq!(points, vec![0.into()]);
+
+        let check_index_types = |index_types: &[FullPayloadIndexType]| {
+            assert_eq!(index_types.len(), 2);
+            assert_eq!(index_types[0].index_type, PayloadIndexType::FullTextIndex);
+            assert_eq!(index_types[1].index_type, PayloadIndexType::NullIndex);
+        };
+
+        let payload_config =
+            PayloadConfig::load(&PayloadConfig::get_config_path(&payload_index_path)).unwrap();
+        check_index_types(&payload_config.indices.get(&key).unwrap().types);
+
+        // Rebuilt index is loaded from its final location
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+        let segment = load_segment(&segment_path, &is_stopped).unwrap().unwrap();
+        let points = segment.read_filtered(None, None, Some(&filter), &is_stopped, &hw_counter);
+        assert_eq!(points, vec![0.into()]);
+        check_index_types(
+            &segment
+                .payload_index
+                .borrow()
+                .config()
+                .indices
+                .get(&key)
+                .unwrap()
+                .types,
+        );
+    }
+
+    #[test]
+    fn test_finish_interrupted_rebuild() {
+        let dir = Builder::new().prefix("payload_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+        let key = JsonPath::from_str("name").unwrap();
+
+        let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+        segment
+            .upsert_point(0, 0.into(), only_default_vector(&[1.0, 1.0]), &hw_counter)
+            .unwrap();
+        let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
+        segment
+            .set_full_payload(1, 0.into(), &payload, &hw_counter)
+            .unwrap();
+        segment
+            .create_field_index(
+                2,
+                &key,
+                Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+                &hw_counter,
+            )
+            .unwrap();
+
+        // Rebuild with a new schema, interrupted right after switching the config
+        {
+            let mut payload_index = segment.payload_index.borrow_mut();
+            let schema = PayloadFieldSchema::FieldType(PayloadSchemaType::Text);
+            let BuildIndexResult::Built(field_index) = payload_index
+                .build_index(&key, &schema, &hw_counter)
+                .unwrap()
+            else {
+                panic!("index with a new schema must be built");
+            };
+            payload_index
+                .commit_rebuild(&key, schema, field_index)
+                .unwrap();
+        }
+
+        let payload_index_path = segment.current_path.join("payload_index");
+        assert!(payload_index_path.join(REBUILD_DIR).exists());
+
+        let segment_path = segment.current_path.clone();
+        drop(segment);
+
+        // Rebuild is finished on load
+        let is_stopped = AtomicBool::new(false);
+        let segment = load_segment(&segment_path, &is_stopped).unwrap().unwrap();
+        assert!(!payload_index_path.join(REBUILD_DIR).exists());
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            key.clone(),
+            Match::new_text("john"),
+        )));
+        let points = segment.read_filtered(None, None, Some(&filter), &is_stopped, &hw_counter);
+        assert_eq!(points, vec![0.into()]);
+
+        let payload_index = segment.payload_index.borrow();
+        let types = &payload_index.config().indices.get(&key).unwrap().types;
+        assert_eq!(types[0].index_type, PayloadIndexType::FullTextIndex);
+    }
 }
@@ -12,7 +12,9 @@ use sparse::index::inverted_index::inverted_index_immutable_ram::InvertedIndexIm
 use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 
+use super::disk_ann_index::disk_ann::DiskAnnIndex;
 use super::hnsw_index::hnsw::HNSWIndex;
+use super::ivf_index::ivf::IvfIndex;
 use super::plain_vector_index::PlainVectorIndex;
 use super::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::common::operation_error::OperationResult;
@@ -68,6 +70,8 @@ pub trait VectorIndex {
 pub enum VectorIndexEnum {
     Plain(PlainVectorIndex),
     Hnsw(HNSWIndex),
+    Ivf(IvfIndex),
+    DiskAnn(DiskAnnIndex),
     SparseRam(SparseVectorIndex<InvertedIndexRam>),
     SparseImmutableRam(SparseVectorIndex<InvertedIndexImmutableRam>),
     SparseMmap(SparseVectorIndex<InvertedIndexMmap>),
@@ -86,6 +90,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => false,
             Self::Hnsw(_) => true,
+            Self::Ivf(_) => true,
+            Self::DiskAnn(_) => true,
             Self::SparseRam(_) => true,
             Self::SparseImmutableRam(_) => true,
             Self::SparseMmap(_) => true,
@@ -104,6 +110,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => false,
             Self::Hnsw(index) => index.is_on_disk(),
+            Self::Ivf(index) => index.is_on_disk(),
+            Self::DiskAnn(index) => index.is_on_disk(),
             Self::SparseRam(index) => index.inverted_index().is_on_disk(),
             Self::SparseImmutableRam(index) => index.inverted_index().is_on_disk(),
             Self::SparseMmap(index) => index.inverted_index().is_on_disk(),
@@ -120,6 +128,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => {}
             Self::Hnsw(index) => index.populate()?,
+            Self::Ivf(_) => {}
+            Self::DiskAnn(index) => index.populate()?,
             Self::SparseRam(_) => {}
             Self::SparseImmutableRam(_) => {}
             Self::SparseMmap(index) => index.inverted_index().populate()?,
@@ -137,6 +147,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => {}
             Self::Hnsw(index) => index.clear_cache()?,
+            Self::Ivf(_) => {}
+            Self::DiskAnn(index) => index.clear_cache()?,
             Self::SparseRam(_) => {}
             Self::SparseImmutableRam(_) => {}
             Self::SparseMmap(index) => index.inverted_index().clear_cache()?,
@@ -156,7 +168,7 @@ impl VectorIndexEnum {
         hw_counter: &HardwareCounterCell,
     ) {
         match self {
-            Self::Plain(_) | Self::Hnsw(_) => (),
+            Self::Plain(_) | Self::Hnsw(_) | Self::Ivf(_) | Self::DiskAnn(_) => (),
             Self::SparseRam(index) => index.fill_idf_statistics(idf, hw_counter),
             Self::SparseImmutableRam(index) => index.fill_idf_statistics(idf, hw_counter),
             Self::SparseMmap(index) => index.fill_idf_statistics(idf, hw_counter),
@@ -179,6 +191,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::Hnsw(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
+            Self::DiskAnn(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.inverted_index().vector_count(),
             Self::SparseImmutableRam(index) => index.inverted_index().vector_count(),
             Self::SparseMmap(index) => index.inverted_index().vector_count(),
@@ -208,6 +222,12 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Hnsw(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
+            VectorIndexEnum::Ivf(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::DiskAnn(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
             VectorIndexEnum::SparseRam(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
@@ -242,6 +262,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::Hnsw(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::Ivf(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::DiskAnn(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseImmutableRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseMmap(index) => index.get_telemetry_data(detail),
@@ -264,6 +286,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.files(),
             VectorIndexEnum::Hnsw(index) => index.files(),
+            VectorIndexEnum::Ivf(index) => index.files(),
+            VectorIndexEnum::DiskAnn(index) => index.files(),
             VectorIndexEnum::SparseRam(index) => index.files(),
             VectorIndexEnum::SparseImmutableRam(index) => index.files(),
             VectorIndexEnum::SparseMmap(index) => index.files(),
@@ -280,6 +304,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.immutable_files(),
             VectorIndexEnum::Hnsw(index) => index.immutable_files(),
+            VectorIndexEnum::Ivf(index) => index.immutable_files(),
+            VectorIndexEnum::DiskAnn(index) => index.immutable_files(),
             VectorIndexEnum::SparseRam(index) => index.immutable_files(),
             VectorIndexEnum::SparseImmutableRam(index) => index.immutable_files(),
             VectorIndexEnum::SparseMmap(index) => index.immutable_files(),
@@ -296,6 +322,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::Hnsw(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
+            Self::DiskAnn(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.indexed_vector_count(),
             Self::SparseImmutableRam(index) => index.indexed_vector_count(),
             Self::SparseMmap(index) => index.indexed_vector_count(),
@@ -312,6 +340,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::Hnsw(index) => index.size_of_searchable_vectors_in_bytes(),
+            Self::Ivf(index) => index.size_of_searchable_vectors_in_bytes(),
+            Self::DiskAnn(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseRam(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseImmutableRam(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseMmap(index) => index.size_of_searchable_vectors_in_bytes(),
@@ -339,6 +369,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.update_vector(id, vector, hw_counter),
             Self::Hnsw(index) => index.update_vector(id, vector, hw_counter),
+            Self::Ivf(index) => index.update_vector(id, vector, hw_counter),
+            Self::DiskAnn(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseRam(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseImmutableRam(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseMmap(index) => index.update_vector(id, vector, hw_counter),
@@ -1,16 +1,31 @@
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
 use bitvec::slice::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::types::ScoredPointOffset;
+use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use itertools::Itertools;
+use parking_lot::Mutex;
 
 use crate::common::operation_error::OperationResult;
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::QueryVector;
-use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::id_tracker::IdTrackerSS;
+use crate::index::PayloadIndex;
+use crate::index::field_index::CardinalityEstimation;
+use crate::index::hnsw_index::point_scorer::{FilteredScorer, ScoringBudget};
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::{
-    SearchParams, default_quantization_ignore_value, default_quantization_oversampling_value,
+    Filter, SearchParams, default_quantization_ignore_value,
+    default_quantization_oversampling_value,
 };
-use crate::vector_storage::VectorStorageEnum;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub fn is_quantized_search(
     quantized_storage: Option<&QuantizedVectors>,
@@ -36,6 +51,22 @@ pub fn get_oversampled_top(
         .map(|q| q.oversampling)
         .unwrap_or(default_quantization_oversampling_value());
 
+    let adaptive_oversampling = params
+        .and_then(|p| p.quantization)
+        .and_then(|q| q.adaptive_oversampling)
+        .unwrap_or(false);
+
+    // Explicit oversampling value is the upper bound of the adaptive one
+    let oversampling_value = match quantized_storage {
+        Some(quantized_storage)
+            if adaptive_oversampling && is_rescoring(quantized_storage, params) =>
+        {
+            let adaptive = quantized_storage.error_stats().oversampling();
+            Some(oversampling_value.map_or(adaptive, |max| adaptive.min(max)))
+        }
+        _ => oversampling_value,
+    };
+
     match oversampling_value {
         Some(oversampling) if quantization_enabled && oversampling > 1.0 => {
             (oversampling * top as f64) as usize
@@ -44,6 +75,13 @@ pub fn get_oversampled_top(
     }
 }
 
+fn is_rescoring(quantized_vectors: &QuantizedVectors, params: Option<&SearchParams>) -> bool {
+    params
+        .and_then(|p| p.quantization)
+        .and_then(|q| q.rescore)
+        .unwrap_or_else(|| quantized_vectors.default_rescoring())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn postprocess_search_result(
     mut search_result: Vec<ScoredPointOffset>,
@@ -57,16 +95,10 @@ pub fn postprocess_search_result(
 ) -> OperationResult<Vec<ScoredPointOffset>> {
     let quantization_enabled = is_quantized_search(quantized_vectors, params);
 
-    let default_rescoring = quantized_vectors
-        .as_ref()
-        .map(|q| q.default_rescoring())
-        .unwrap_or(false);
-    let rescore = quantization_enabled
-        && params
-            .and_then(|p| p.quantization)
-            .and_then(|q| q.rescore)
-            .unwrap_or(default_rescoring);
-    if rescore {
+    let rescoring_quantized_vectors = quantized_vectors.filter(|quantized_vectors| {
+        quantization_enabled && is_rescoring(quantized_vectors, params)
+    });
+    if let Some(quantized_vectors) = rescoring_quantized_vectors {
         let mut scorer = FilteredScorer::new(
             vector.to_owned(),
             vector_storage,
@@ -76,12 +108,228 @@ pub fn postprocess_search_result(
             hardware_counter,
         )?;
 
-        search_result = scorer
+        let rescored: Vec<_> = scorer
             .score_points(&mut search_result.iter().map(|x| x.idx).collect_vec(), 0)
             .collect();
+        quantized_vectors
+            .error_stats()
+            .observe(&search_result, &rescored, top);
+
+        search_result = rescored;
         search_result.sort_unstable();
         search_result.reverse();
     }
     search_result.truncate(top);
     Ok(search_result)
 }
+
+/// Scorer of the query, which uses quantized vectors if they are enabled by `params`
+pub fn new_search_scorer<'a>(
+    vector: &QueryVector,
+    vector_storage: &'a VectorStorageEnum,
+    quantized_vectors: Option<&'a QuantizedVectors>,
+    deleted_points: &'a BitSlice,
+    params: Option<&SearchParams>,
+    hardware_counter: HardwareCounterCell,
+) -> OperationResult<FilteredScorer<'a>> {
+    let quantization_enabled = is_quantized_search(quantized_vectors, params);
+    FilteredScorer::new(
+        vector.to_owned(),
+        vector_storage,
+        quantization_enabled.then_some(quantized_vectors).flatten(),
+        None,
+        deleted_points,
+        hardware_counter,
+    )
+}
+
+/// Storages of a segment, searched by a vector index which scores points without HNSW graph
+///
+/// Used by IVF and DiskANN indexes, which fall back to plain search in the same cases.
+#[derive(Debug)]
+pub struct IndexedStorages {
+    pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+}
+
+impl IndexedStorages {
+    /// Score all `points` against the query
+    pub fn search_plain(
+        &self,
+        vector: &QueryVector,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let is_stopped = query_context.is_stopped();
+        let budget = ScoringBudget::from_params(params);
+
+        let scorer = new_search_scorer(
+            vector,
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            deleted_points,
+            params,
+            query_context.hardware_counter(),
+        )?
+        .with_budget(budget.as_ref());
+        let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
+
+        let search_result = scorer.peek_top_iter(points, oversampled_top, &is_stopped)?;
+        if budget.as_ref().is_some_and(ScoringBudget::is_exhausted) {
+            query_context.mark_search_truncated();
+        }
+
+        postprocess_search_result(
+            search_result,
+            id_tracker.deleted_point_bitslice(),
+            &vector_storage,
+            quantized_vectors.as_ref(),
+            vector,
+            params,
+            top,
+            query_context.hardware_counter(),
+        )
+    }
+
+    /// Search with the index, or with plain search if it is exact or cheaper
+    ///
+    /// Filtered search is plain, if the filter matches fewer than `index_scored_points`,
+    /// the number of points the index is expected to score. Otherwise `search_index` is called
+    /// with the filter and the estimation of its cardinality.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_with_plain_fallback(
+        &self,
+        telemetry: &IndexSearchesTelemetry,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+        index_scored_points: usize,
+        search_index: impl Fn(
+            &QueryVector,
+            Option<(&Filter, &CardinalityEstimation)>,
+        ) -> OperationResult<Vec<ScoredPointOffset>>,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+
+        match filter {
+            None if exact => {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.exact_unfiltered);
+                let id_tracker = self.id_tracker.borrow();
+                vectors
+                    .iter()
+                    .map(|&vector| {
+                        self.search_plain(
+                            vector,
+                            &mut id_tracker.iter_internal(),
+                            top,
+                            params,
+                            query_context,
+                        )
+                    })
+                    .collect()
+            }
+            None => {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_index);
+                vectors
+                    .iter()
+                    .map(|&vector| search_index(vector, None))
+                    .collect()
+            }
+            Some(query_filter) => {
+                let payload_index = self.payload_index.borrow();
+                let hw_counter = query_context.hardware_counter();
+
+                let query_cardinality = (!exact).then(|| {
+                    let vector_storage = self.vector_storage.borrow();
+                    let id_tracker = self.id_tracker.borrow();
+                    adjust_to_available_vectors(
+                        payload_index.estimate_cardinality(query_filter, &hw_counter),
+                        vector_storage.available_vector_count(),
+                        id_tracker.available_point_count(),
+                    )
+                });
+
+                // If the filter matches fewer points than the index would score,
+                // scoring all of them is both cheaper and exact
+                match query_cardinality {
+                    Some(query_cardinality) if query_cardinality.max >= index_scored_points => {
+                        drop(payload_index);
+                        let _timer = ScopeDurationMeasurer::new(&telemetry.filtered_index);
+                        vectors
+                            .iter()
+                            .map(|&vector| {
+                                search_index(vector, Some((query_filter, &query_cardinality)))
+                            })
+                            .collect()
+                    }
+                    _ => {
+                        let _timer = ScopeDurationMeasurer::new(if exact {
+                            &telemetry.exact_filtered
+                        } else {
+                            &telemetry.filtered_plain
+                        });
+                        // share filtered points for all query vectors
+                        let filtered_points = payload_index.query_points(query_filter, &hw_counter);
+                        vectors
+                            .iter()
+                            .map(|&vector| {
+                                self.search_plain(
+                                    vector,
+                                    &mut filtered_points.iter().copied(),
+                                    top,
+                                    params,
+                                    query_context,
+                                )
+                            })
+                            .collect()
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Durations of searches, made by [`IndexedStorages::search_with_plain_fallback`]
+#[derive(Debug)]
+pub struct IndexSearchesTelemetry {
+    pub unfiltered_index: Arc<Mutex<OperationDurationsAggregator>>,
+    pub filtered_index: Arc<Mutex<OperationDurationsAggregator>>,
+    pub filtered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    pub exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+    pub exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl IndexSearchesTelemetry {
+    pub fn new() -> Self {
+        Self {
+            unfiltered_index: OperationDurationsAggregator::new(),
+            filtered_index: OperationDurationsAggregator::new(),
+            filtered_plain: OperationDurationsAggregator::new(),
+            exact_unfiltered: OperationDurationsAggregator::new(),
+            exact_filtered: OperationDurationsAggregator::new(),
+        }
+    }
+
+    /// Telemetry of plain searches, statistics of the index searches are set by the index
+    pub fn plain_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
+        VectorIndexSearchesTelemetry {
+            filtered_plain: self.filtered_plain.lock().get_statistics(detail),
+            filtered_exact: self.exact_filtered.lock().get_statistics(detail),
+            unfiltered_exact: self.exact_unfiltered.lock().get_statistics(detail),
+            ..Default::default()
+        }
+    }
+}
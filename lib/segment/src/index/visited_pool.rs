@@ -4,6 +4,10 @@ use common::defaults::POOL_KEEP_LIMIT;
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 
+use crate::index::buffer_pool::PoolCounters;
+
+pub(super) static VISITED_LISTS_COUNTERS: PoolCounters = PoolCounters::new();
+
 /// Visited list handle is an owner of the `VisitedList`, which is returned by `VisitedPool` and returned back to it
 #[derive(Debug)]
 pub struct VisitedListHandle<'a> {
@@ -109,8 +113,12 @@ impl VisitedPool {
         // If there are more concurrent requests, a new temporary list is created dynamically.
         // This limit is implemented to prevent memory leakage.
         match self.pool.write().pop() {
-            None => VisitedListHandle::new(self, VisitedList::new(num_points)),
+            None => {
+                VISITED_LISTS_COUNTERS.allocated();
+                VisitedListHandle::new(self, VisitedList::new(num_points))
+            }
             Some(data) => {
+                VISITED_LISTS_COUNTERS.reused();
                 let mut visited_list = VisitedListHandle::new(self, data);
                 visited_list.resize(num_points);
                 visited_list.next_iteration();
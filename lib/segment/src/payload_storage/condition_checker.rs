@@ -165,6 +165,10 @@ impl ValueChecker for Match {
                     _ => false,
                 }
             }
+            Match::Pattern(pattern) => match payload {
+                Value::String(stored) => pattern.is_match(stored),
+                _ => false,
+            },
             Match::TextAny(MatchTextAny { text_any }) => match payload {
                 Value::String(stored) => text_any
                     .split_whitespace()
@@ -15,5 +15,6 @@ pub mod simple_payload_storage;
 pub mod simple_payload_storage_impl;
 #[cfg(test)]
 mod tests;
+pub mod wasm_condition;
 
 pub use payload_storage_base::*;
@@ -8,16 +8,18 @@ use std::sync::Arc;
 use atomic_refcell::AtomicRefCell;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::PointOffsetType;
+use serde_json::{Map, Value};
 
 use crate::common::utils::{IndexesMap, check_is_empty, check_is_null};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
+use crate::payload_storage::wasm_condition::{self, WasmConditionModule};
 use crate::payload_storage::{ConditionChecker, PayloadStorage};
 use crate::types::{
     Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
-    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, VectorNameBuf,
+    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, VectorNameBuf, WasmPredicate,
 };
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
@@ -162,6 +164,8 @@ where
                     )
                 })
         }
+        Condition::Wasm(wasm) => wasm_condition::get(&wasm.wasm.name)
+            .is_some_and(|module| check_wasm_condition(&module, &wasm.wasm, get_payload().deref())),
 
         Condition::CustomIdChecker(cond) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
@@ -184,6 +188,17 @@ pub fn check_is_null_condition(is_null: &IsNullCondition, payload: &impl Payload
     check_is_null(payload.get_value(&is_null.is_null.key).iter().copied())
 }
 
+pub fn check_wasm_condition(
+    module: &WasmConditionModule,
+    predicate: &WasmPredicate,
+    payload: &Map<String, Value>,
+) -> bool {
+    match &predicate.key {
+        Some(key) => module.check(payload.get_value(key).as_slice()),
+        None => module.check(payload),
+    }
+}
+
 pub fn check_field_condition<R>(
     field_condition: &FieldCondition,
     payload: &impl PayloadContainer,
@@ -0,0 +1,198 @@
+//! Custom payload conditions, implemented as WebAssembly modules
+//!
+//! A module must export its linear `memory` and two functions:
+//!
+//! - `alloc(len: i32) -> i32` - reserve `len` bytes of memory and return their offset
+//! - `check(ptr: i32, len: i32) -> i32` - check the JSON document written at `ptr`, return
+//!   non-zero if it matches
+//!
+//! Modules are registered per node. Requests referencing a module, which is not registered on a
+//! peer, are rejected by that peer. See [`wasm_module`] for sandboxing and limits.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::wasm_module::{self, WasmLimits, WasmModule};
+
+/// Registered condition modules, by names referenced in filters
+static REGISTRY: LazyLock<RwLock<HashMap<String, Arc<WasmConditionModule>>>> =
+    LazyLock::new(Default::default);
+
+thread_local! {
+    /// Buffer to encode inputs of checks, reused between checks
+    static INPUT_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Configuration of custom payload conditions, implemented as WebAssembly modules
+#[derive(Clone, Debug, Deserialize)]
+pub struct WasmConditionsConfig {
+    /// Paths to module files, by names referenced in filters
+    pub modules: HashMap<String, PathBuf>,
+    /// Maximum amount of fuel, roughly a number of executed instructions, a single check may use
+    #[serde(default = "default_fuel_limit")]
+    pub fuel_limit: u64,
+    /// Maximum size of linear memory of a module instance, in bytes
+    #[serde(default = "default_memory_limit_bytes")]
+    pub memory_limit_bytes: usize,
+}
+
+const fn default_fuel_limit() -> u64 {
+    wasm_module::DEFAULT_FUEL_LIMIT
+}
+
+const fn default_memory_limit_bytes() -> usize {
+    wasm_module::DEFAULT_MEMORY_LIMIT_BYTES
+}
+
+/// Load and register all condition modules of the config
+pub fn register_modules(config: &WasmConditionsConfig) -> OperationResult<()> {
+    let limits = WasmLimits {
+        fuel_limit: config.fuel_limit,
+        memory_limit_bytes: config.memory_limit_bytes,
+    };
+    for (name, path) in &config.modules {
+        let wasm = std::fs::read(path)?;
+        let module = WasmConditionModule::new(&wasm, limits).map_err(|err| {
+            OperationError::service_error(format!("Failed to load condition module {name}: {err}"))
+        })?;
+        register(name.clone(), module);
+    }
+    Ok(())
+}
+
+pub fn register(name: String, module: WasmConditionModule) {
+    REGISTRY.write().insert(name, Arc::new(module));
+}
+
+pub fn get(name: &str) -> Option<Arc<WasmConditionModule>> {
+    REGISTRY.read().get(name).cloned()
+}
+
+/// Check that the module is registered on this peer
+pub fn check_registered(name: &str) -> OperationResult<()> {
+    if REGISTRY.read().contains_key(name) {
+        Ok(())
+    } else {
+        Err(OperationError::validation_error(format!(
+            "Condition module {name} is not registered on this peer",
+        )))
+    }
+}
+
+/// Compiled condition module
+#[derive(Debug)]
+pub struct WasmConditionModule {
+    module: WasmModule<(i32, i32), i32>,
+}
+
+impl WasmConditionModule {
+    pub fn new(wasm: &[u8], limits: WasmLimits) -> OperationResult<Self> {
+        Ok(Self {
+            module: WasmModule::new(wasm, "check", limits)?,
+        })
+    }
+
+    /// Check JSON encoded `input` with the module
+    ///
+    /// Failed checks are considered not matching.
+    pub fn check(&self, input: &(impl Serialize + ?Sized)) -> bool {
+        match self.check_impl(input) {
+            Ok(matches) => matches,
+            Err(err) => {
+                log::debug!("Condition module check failed: {err}");
+                false
+            }
+        }
+    }
+
+    fn check_impl(&self, input: &(impl Serialize + ?Sized)) -> OperationResult<bool> {
+        INPUT_BUFFER.with_borrow_mut(|buffer| {
+            buffer.clear();
+            serde_json::to_writer(&mut *buffer, input)?;
+            let len = i32::try_from(buffer.len())
+                .map_err(|_| OperationError::service_error("Condition input is too large"))?;
+
+            let result = self.module.call(
+                buffer.len(),
+                |memory| memory.copy_from_slice(buffer),
+                |ptr| (ptr, len),
+            )?;
+            Ok(result != 0)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::common::wasm_module::DEFAULT_FUEL_LIMIT;
+
+    fn limits(fuel_limit: u64, memory_limit_bytes: usize) -> WasmLimits {
+        WasmLimits {
+            fuel_limit,
+            memory_limit_bytes,
+        }
+    }
+
+    /// Matches JSON documents longer than 8 bytes
+    const LONG_INPUT_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "check") (param $ptr i32) (param $len i32) (result i32)
+                (i32.gt_s (local.get $len) (i32.const 8))))
+    "#;
+
+    const INFINITE_LOOP_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "check") (param $ptr i32) (param $len i32) (result i32)
+                (loop $forever (br $forever))
+                (i32.const 1)))
+    "#;
+
+    #[test]
+    fn test_wasm_condition_check() {
+        let wasm = wat::parse_str(LONG_INPUT_MODULE).unwrap();
+        let module = WasmConditionModule::new(&wasm, limits(DEFAULT_FUEL_LIMIT, 1 << 16)).unwrap();
+
+        assert!(module.check(&json!({"city": "Berlin"})));
+        assert!(!module.check(&json!([1])));
+        assert!(module.check(&json!(["London", "Moscow"])));
+    }
+
+    #[test]
+    fn test_wasm_condition_limits() {
+        let wasm = wat::parse_str(INFINITE_LOOP_MODULE).unwrap();
+        let module = WasmConditionModule::new(&wasm, limits(10_000, 1 << 16)).unwrap();
+        assert!(!module.check(&json!({"city": "Berlin"})));
+
+        // Memory larger than the limit is rejected on instantiation
+        let wasm = wat::parse_str(LONG_INPUT_MODULE).unwrap();
+        assert!(WasmConditionModule::new(&wasm, limits(DEFAULT_FUEL_LIMIT, 1024)).is_err());
+
+        let wasm = wat::parse_str("(module)").unwrap();
+        assert!(WasmConditionModule::new(&wasm, limits(DEFAULT_FUEL_LIMIT, 1 << 16)).is_err());
+    }
+
+    #[test]
+    fn test_wasm_condition_registered() {
+        let wasm = wat::parse_str(LONG_INPUT_MODULE).unwrap();
+        let module = WasmConditionModule::new(&wasm, limits(DEFAULT_FUEL_LIMIT, 1 << 16)).unwrap();
+        register("registered_condition".to_string(), module);
+
+        assert!(check_registered("registered_condition").is_ok());
+        assert!(check_registered("unknown_condition").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::hash::Hasher as _;
+use std::io::{self, Read as _};
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use seahash::SeaHasher;
+use serde::{Deserialize, Serialize};
+
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::entry::snapshot_entry::SnapshotEntry as _;
+use crate::utils::path::strip_prefix;
+
+pub const SEGMENT_CHECKSUMS_FILE: &str = "checksums.json";
+
+const CHECKSUM_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Checksums of the immutable files of a segment, relative to the segment directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SegmentChecksums {
+    files: BTreeMap<PathBuf, u64>,
+}
+
+impl Segment {
+    /// Store checksums of all immutable segment files, so corruption can be detected on load.
+    pub fn save_checksums(&self) -> OperationResult<()> {
+        save_checksums(&self.current_path, &self.immutable_files())
+    }
+
+    /// Refresh stored checksums after immutable files were replaced, if there are any.
+    pub(super) fn refresh_checksums(&self) -> OperationResult<()> {
+        if self.current_path.join(SEGMENT_CHECKSUMS_FILE).exists() {
+            self.save_checksums()?;
+        }
+        Ok(())
+    }
+}
+
+fn save_checksums(segment_path: &Path, files: &[PathBuf]) -> OperationResult<()> {
+    let mut checksums = SegmentChecksums::default();
+    for path in files.iter().filter(|path| path.is_file()) {
+        let relative_path = strip_prefix(path, segment_path)?;
+        checksums
+            .files
+            .insert(relative_path.to_path_buf(), file_checksum(path)?);
+    }
+    atomic_save_json(&segment_path.join(SEGMENT_CHECKSUMS_FILE), &checksums)?;
+    Ok(())
+}
+
+/// Verify segment files against stored checksums.
+///
+/// Segments without stored checksums are not verified.
+pub fn verify_segment_checksums(segment_path: &Path) -> OperationResult<()> {
+    let checksums_path = segment_path.join(SEGMENT_CHECKSUMS_FILE);
+    if !checksums_path.exists() {
+        return Ok(());
+    }
+
+    let checksums: SegmentChecksums = read_json(&checksums_path)?;
+    for (relative_path, expected) in checksums.files {
+        let path = segment_path.join(&relative_path);
+        let actual = match file_checksum(&path) {
+            Ok(checksum) => checksum,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(OperationError::corrupted_segment(format!(
+                    "file {} is missing",
+                    path.display(),
+                )));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if actual != expected {
+            return Err(OperationError::corrupted_segment(format!(
+                "checksum mismatch for file {}",
+                path.display(),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn file_checksum(path: &Path) -> io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = SeaHasher::new();
+    let mut buffer = vec![0; CHECKSUM_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_segment_checksums() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let data_path = dir.path().join("vectors.dat");
+        fs::write(&data_path, [1u8; 4096]).unwrap();
+
+        // Nothing to verify without stored checksums
+        verify_segment_checksums(dir.path()).unwrap();
+
+        save_checksums(dir.path(), std::slice::from_ref(&data_path)).unwrap();
+        verify_segment_checksums(dir.path()).unwrap();
+
+        let mut data = fs::read(&data_path).unwrap();
+        data[1234] ^= 0xFF;
+        fs::write(&data_path, data).unwrap();
+        assert!(matches!(
+            verify_segment_checksums(dir.path()),
+            Err(OperationError::CorruptedSegment { .. }),
+        ));
+
+        fs::remove_file(&data_path).unwrap();
+        assert!(matches!(
+            verify_segment_checksums(dir.path()),
+            Err(OperationError::CorruptedSegment { .. }),
+        ));
+    }
+}
@@ -7,7 +7,8 @@ use std::{fs, thread};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
 
-use super::Segment;
+use super::{Segment, VectorData};
+use crate::common::determinism::with_deterministic;
 use crate::common::operation_error::{OperationError, OperationResult, SegmentFailedState};
 use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
 use crate::data_types::build_index_result::BuildFieldIndexResult;
@@ -20,16 +21,19 @@ use crate::data_types::query_context::{
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
-use crate::index::{BuildIndexResult, PayloadIndex, VectorIndex};
+use crate::index::hnsw_index::graph_stats::HnswGraphStats;
+use crate::index::hnsw_index::hnsw::HealedGraph;
+use crate::index::{BuildIndexResult, PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::payload_storage::PayloadStorage;
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{QuantizationTelemetry, SegmentTelemetry};
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorDataInfo, VectorName, VectorNameBuf, WithPayload, WithVector,
+    PointIdType, QuantizationConfig, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
+    SegmentType, SeqNumberType, VectorDataInfo, VectorName, VectorNameBuf, WithPayload, WithVector,
 };
 use crate::vector_storage::VectorStorage;
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
 /// This is a basic implementation of `SegmentEntry`,
 /// meaning that it implements the _actual_ operations with data and not any kind of proxy or wrapping
@@ -59,13 +63,19 @@ impl SegmentEntry for Segment {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
         let vector_query_context = query_context.get_vector_context(vector_name);
-        let internal_results = vector_data.vector_index.borrow().search(
-            query_vectors,
-            filter,
-            top,
-            params,
-            &vector_query_context,
-        )?;
+        let params = params.map(|params| {
+            self.segment_search_params(params, query_context.available_point_count())
+        });
+        let deterministic = params.is_some_and(|params| params.deterministic);
+        let internal_results = with_deterministic(deterministic, || {
+            vector_data.vector_index.borrow().search(
+                query_vectors,
+                filter,
+                top,
+                params.as_ref(),
+                &vector_query_context,
+            )
+        })?;
 
         check_stopped(&vector_query_context.is_stopped())?;
 
@@ -819,7 +829,7 @@ impl SegmentEntry for Segment {
                 return Ok(BuildFieldIndexResult::AlreadyExists);
             }
             BuildIndexResult::IncompatibleSchema => {
-                // This function expects that incompatible schema is already removed
+                // Old index can't be rebuilt in place, it must be removed first
                 return Ok(BuildFieldIndexResult::IncompatibleSchema);
             }
         };
@@ -879,9 +889,116 @@ impl SegmentEntry for Segment {
             config: self.config().clone(),
             vector_index_searches,
             payload_field_indices: self.payload_index.borrow().get_telemetry_data(),
+            quantization: self.quantization_telemetry(),
         }
     }
 
+    fn hnsw_graph_stats(&self) -> HashMap<VectorNameBuf, HnswGraphStats> {
+        self.vector_data
+            .iter()
+            .filter_map(
+                |(vector_name, vector_data)| match &*vector_data.vector_index.borrow() {
+                    VectorIndexEnum::Hnsw(index) => {
+                        Some((vector_name.clone(), index.graph_stats()))
+                    }
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
+    fn warmup_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for vector_data in self.vector_data.values() {
+            let VectorData {
+                vector_index,
+                vector_storage,
+                quantized_vectors,
+            } = vector_data;
+
+            let vector_storage = vector_storage.borrow();
+            if vector_storage.is_on_disk() {
+                files.extend(vector_storage.files());
+            }
+
+            if let Some(quantized_vectors) = quantized_vectors.borrow().as_ref()
+                && quantized_vectors.is_on_disk()
+            {
+                files.extend(quantized_vectors.files());
+            }
+
+            let vector_index = vector_index.borrow();
+            if vector_index.is_on_disk() {
+                files.extend(vector_index.files());
+            }
+        }
+        files
+    }
+
+    fn quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        self.vector_data
+            .iter()
+            .filter_map(|(vector_name, vector_data)| {
+                let quantized_vectors = vector_data.quantized_vectors.borrow();
+                let quantized_vectors = quantized_vectors.as_ref()?;
+                let vector_storage = vector_data.vector_storage.borrow();
+                let error_stats = quantized_vectors.error_stats();
+                Some(QuantizationTelemetry::new(
+                    vector_name.clone(),
+                    vector_storage.size_of_available_vectors_in_bytes(),
+                    quantized_vectors.size_of_available_vectors_in_bytes(
+                        vector_storage.available_vector_count(),
+                        vector_storage.total_vector_count(),
+                    ),
+                    error_stats.estimated_recall(),
+                    error_stats.recall_queries(),
+                ))
+            })
+            .collect()
+    }
+
+    fn build_quantization(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectors> {
+        self.build_quantization_impl(vector_name, quantization_config, max_threads, stopped)
+    }
+
+    fn apply_quantization(
+        &mut self,
+        vector_name: &VectorName,
+        quantized_vectors: Option<QuantizedVectors>,
+    ) -> OperationResult<()> {
+        self.apply_quantization_impl(vector_name, quantized_vectors)
+    }
+
+    fn hnsw_graphs_to_heal(
+        &self,
+        deleted_threshold: f64,
+        min_deleted_count: usize,
+    ) -> Vec<VectorNameBuf> {
+        self.hnsw_graphs_to_heal_impl(deleted_threshold, min_deleted_count)
+    }
+
+    fn heal_hnsw_graphs(
+        &self,
+        vector_names: &[VectorNameBuf],
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Vec<(VectorNameBuf, HealedGraph)>> {
+        self.heal_hnsw_graphs_impl(vector_names, max_threads, stopped)
+    }
+
+    fn apply_healed_hnsw_graphs(
+        &mut self,
+        healed: Vec<(VectorNameBuf, HealedGraph)>,
+    ) -> OperationResult<()> {
+        self.apply_healed_hnsw_graphs_impl(healed)
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         query_context.add_available_point_count(self.available_point_count());
         let hw_acc = query_context.hardware_usage_accumulator();
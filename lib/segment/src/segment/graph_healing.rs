@@ -0,0 +1,88 @@
+use std::sync::atomic::AtomicBool;
+
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::index::hnsw_index::hnsw::HealedGraph;
+use crate::index::{VectorIndex, VectorIndexEnum};
+use crate::types::{VectorName, VectorNameBuf};
+
+impl Segment {
+    pub(super) fn hnsw_graphs_to_heal_impl(
+        &self,
+        deleted_threshold: f64,
+        min_deleted_count: usize,
+    ) -> Vec<VectorNameBuf> {
+        if deleted_threshold <= 0.0 {
+            return Vec::new();
+        }
+
+        self.vector_data
+            .iter()
+            .filter(
+                |(_, vector_data)| match &*vector_data.vector_index.borrow() {
+                    VectorIndexEnum::Hnsw(index) => {
+                        let indexed_count = index.indexed_vector_count();
+                        let unhealed_count = index.unhealed_deleted_count();
+                        indexed_count > 0
+                            && unhealed_count >= min_deleted_count
+                            && unhealed_count as f64 / indexed_count as f64 >= deleted_threshold
+                    }
+                    _ => false,
+                },
+            )
+            .map(|(vector_name, _)| vector_name.clone())
+            .collect()
+    }
+
+    pub(super) fn heal_hnsw_graphs_impl(
+        &self,
+        vector_names: &[VectorNameBuf],
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Vec<(VectorNameBuf, HealedGraph)>> {
+        let healed = vector_names
+            .iter()
+            .map(|vector_name| {
+                check_process_stopped(stopped)?;
+                let vector_data = self.vector_data.get(vector_name).ok_or_else(|| {
+                    OperationError::VectorNameNotExists {
+                        received_name: vector_name.clone(),
+                    }
+                })?;
+                let healed = match &*vector_data.vector_index.borrow() {
+                    VectorIndexEnum::Hnsw(index) => index.heal_graph(max_threads, stopped)?,
+                    _ => return Err(not_hnsw_error(vector_name)),
+                };
+                Ok((vector_name.clone(), healed))
+            })
+            .collect::<OperationResult<Vec<_>>>()?;
+
+        Ok(healed)
+    }
+
+    pub(super) fn apply_healed_hnsw_graphs_impl(
+        &mut self,
+        healed: Vec<(VectorNameBuf, HealedGraph)>,
+    ) -> OperationResult<()> {
+        for (vector_name, healed_graph) in healed {
+            let vector_data = self.vector_data.get(&vector_name).ok_or_else(|| {
+                OperationError::VectorNameNotExists {
+                    received_name: vector_name.clone(),
+                }
+            })?;
+            match &mut *vector_data.vector_index.borrow_mut() {
+                VectorIndexEnum::Hnsw(index) => index.apply_healed_graph(healed_graph)?,
+                _ => return Err(not_hnsw_error(&vector_name)),
+            }
+        }
+
+        // Healed links and graph config are written in place of the current ones
+        self.refresh_checksums()?;
+
+        Ok(())
+    }
+}
+
+fn not_hnsw_error(vector_name: &VectorName) -> OperationError {
+    OperationError::service_error(format!("Vector {vector_name} has no HNSW graph to heal"))
+}
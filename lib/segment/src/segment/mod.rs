@@ -1,7 +1,10 @@
+mod checksums;
 mod entry;
 mod facet;
 mod formula_rescore;
+mod graph_healing;
 mod order_by;
+mod requantization;
 mod sampling;
 mod scroll;
 mod search;
@@ -25,7 +28,9 @@ use parking_lot::Mutex;
 #[cfg(feature = "rocksdb")]
 use rocksdb::DB;
 
+pub use self::checksums::{SEGMENT_CHECKSUMS_FILE, verify_segment_checksums};
 use self::version_tracker::VersionTracker;
+use crate::common::hot_points_cache::HotPointsCache;
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::VectorIndexEnum;
@@ -88,6 +93,8 @@ pub struct Segment {
     #[cfg(feature = "rocksdb")]
     pub database: Option<Arc<parking_lot::RwLock<DB>>>,
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<SeqNumberType>>>>,
+    /// Vectors and payloads of frequently retrieved points of on-disk storages
+    pub hot_points_cache: HotPointsCache,
 }
 
 pub struct VectorData {
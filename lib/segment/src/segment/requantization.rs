@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::segment_constructor::get_vector_storage_path;
+use crate::types::{QuantizationConfig, VectorName};
+use crate::vector_storage::quantized::quantized_vectors::{
+    QUANTIZED_CONFIG_PATH, QuantizedVectors, QuantizedVectorsStorageType,
+};
+
+/// Directory inside of the vector storage, in which new quantized vectors are built
+const REQUANTIZATION_PATH: &str = "requantization";
+
+impl Segment {
+    pub(super) fn build_quantization_impl(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectors> {
+        let vector_data = self.vector_data.get(vector_name).ok_or_else(|| {
+            OperationError::VectorNameNotExists {
+                received_name: vector_name.to_owned(),
+            }
+        })?;
+        let is_appendable = self.segment_config.vector_data[vector_name].is_appendable();
+
+        let path =
+            get_vector_storage_path(&self.current_path, vector_name).join(REQUANTIZATION_PATH);
+        // Leftover of an interrupted re-quantization
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir_all(&path)?;
+
+        let storage_type = if is_appendable {
+            QuantizedVectorsStorageType::Mutable
+        } else {
+            QuantizedVectorsStorageType::Immutable
+        };
+        QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            quantization_config,
+            storage_type,
+            &path,
+            max_threads,
+            stopped,
+        )
+    }
+
+    /// Replace quantized vectors of the vector with ones built by `build_quantization_impl`
+    ///
+    /// Files are swapped so that an interrupted swap leaves the vector without quantization,
+    /// which is then detected as a config mismatch and re-quantized again.
+    pub(super) fn apply_quantization_impl(
+        &mut self,
+        vector_name: &VectorName,
+        quantized_vectors: Option<QuantizedVectors>,
+    ) -> OperationResult<()> {
+        let vector_data = self.vector_data.get(vector_name).ok_or_else(|| {
+            OperationError::VectorNameNotExists {
+                received_name: vector_name.to_owned(),
+            }
+        })?;
+        let vector_storage_path = get_vector_storage_path(&self.current_path, vector_name);
+
+        let mut current = vector_data.quantized_vectors.borrow_mut();
+        if let Some(old_quantized_vectors) = current.take() {
+            let old_files = old_quantized_vectors.files();
+            drop(old_quantized_vectors);
+            // Config first, data without config is never loaded
+            remove_path(&vector_storage_path.join(QUANTIZED_CONFIG_PATH))?;
+            for entry in top_level_entries(&vector_storage_path, &old_files) {
+                remove_path(&vector_storage_path.join(entry))?;
+            }
+        }
+
+        let quantization_config = match quantized_vectors {
+            Some(new_quantized_vectors) => {
+                let config = new_quantized_vectors.config().clone();
+                let build_path = vector_storage_path.join(REQUANTIZATION_PATH);
+                let new_files = new_quantized_vectors.files();
+                drop(new_quantized_vectors);
+
+                // Config last, so data is complete once config is there
+                let config_file = OsString::from(QUANTIZED_CONFIG_PATH);
+                let entries = top_level_entries(&build_path, &new_files);
+                for entry in entries.iter().filter(|entry| **entry != config_file) {
+                    fs::rename(build_path.join(entry), vector_storage_path.join(entry))?;
+                }
+                fs::rename(
+                    build_path.join(&config_file),
+                    vector_storage_path.join(&config_file),
+                )?;
+                fs::remove_dir_all(&build_path)?;
+
+                let quantization_config = config.quantization_config.clone();
+                *current = Some(QuantizedVectors::load_impl(
+                    config,
+                    &vector_data.vector_storage.borrow(),
+                    &vector_storage_path,
+                )?);
+                Some(quantization_config)
+            }
+            None => None,
+        };
+        drop(current);
+
+        if let Some(vector_config) = self.segment_config.vector_data.get_mut(vector_name) {
+            vector_config.quantization_config = quantization_config;
+        }
+        self.refresh_checksums()?;
+        self.save_current_state()
+    }
+}
+
+/// Names of the top level files and directories of `files` within `path`
+fn top_level_entries(path: &Path, files: &[PathBuf]) -> BTreeSet<OsString> {
+    files
+        .iter()
+        .filter_map(|file| file.strip_prefix(path).ok()?.components().next())
+        .map(|component| component.as_os_str().to_owned())
+        .collect()
+}
+
+fn remove_path(path: &Path) -> OperationResult<()> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match result {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
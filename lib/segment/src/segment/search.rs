@@ -12,12 +12,32 @@ use crate::data_types::vectors::VectorStructInternal;
 #[cfg(feature = "testing")]
 use crate::entry::entry_point::SegmentEntry;
 #[cfg(feature = "testing")]
-use crate::types::VectorName;
+use crate::types::Filter;
 #[cfg(feature = "testing")]
-use crate::types::{Filter, SearchParams};
-use crate::types::{ScoredPoint, WithPayload, WithVector};
+use crate::types::VectorName;
+use crate::types::{ScoredPoint, SearchParams, WithPayload, WithVector};
 
 impl Segment {
+    /// Search params for this segment, with its share of the `max_scored_points` budget.
+    ///
+    /// The budget is split between segments in proportion to their point count.
+    pub(super) fn segment_search_params(
+        &self,
+        params: &SearchParams,
+        total_point_count: usize,
+    ) -> SearchParams {
+        let mut params = *params;
+        let segment_point_count = self.id_tracker.borrow().available_point_count();
+        if let Some(max_scored_points) = params.max_scored_points.as_mut()
+            && total_point_count > segment_point_count
+        {
+            let share = *max_scored_points as u128 * segment_point_count as u128
+                / total_point_count as u128;
+            *max_scored_points = (share as usize).max(1);
+        }
+        params
+    }
+
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
     pub(super) fn process_search_result(
         &self,
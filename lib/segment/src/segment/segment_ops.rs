@@ -19,6 +19,7 @@ use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::VectorInternal;
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::PayloadStorage;
 use crate::types::{
     Payload, PayloadFieldSchema, PayloadKeyType, PointIdType, SegmentState, SeqNumberType,
     SnapshotFormat, VectorName,
@@ -289,6 +290,10 @@ impl Segment {
 
         let (applied, internal_id) = operation(self)?;
 
+        for point_offset in op_point_offset.into_iter().chain(internal_id) {
+            self.hot_points_cache.invalidate(point_offset);
+        }
+
         self.bump_segment_version(op_num);
         if let Some(internal_id) = internal_id {
             self.id_tracker
@@ -377,14 +382,20 @@ impl Segment {
                         point_offset
                     ),
                 })
-            } else {
+            } else if !vector_storage.is_on_disk() {
                 let vector = vector_storage.get_vector::<Random>(point_offset);
-                if vector_storage.is_on_disk() {
-                    hw_counter
-                        .vector_io_read()
-                        .incr_delta(vector.estimate_size_in_bytes());
-                }
                 Ok(Some(vector.to_owned()))
+            } else {
+                if let Some(vector) = self.hot_points_cache.get_vector(point_offset, vector_name) {
+                    return Ok(Some(vector));
+                }
+                let vector = vector_storage.get_vector::<Random>(point_offset);
+                let size_bytes = vector.estimate_size_in_bytes();
+                hw_counter.vector_io_read().incr_delta(size_bytes);
+                let vector = vector.to_owned();
+                self.hot_points_cache
+                    .put_vector(point_offset, vector_name, &vector, size_bytes);
+                Ok(Some(vector))
             }
         } else {
             Ok(None)
@@ -412,9 +423,17 @@ impl Segment {
         point_offset: PointOffsetType,
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<Payload> {
-        self.payload_index
-            .borrow()
-            .get_payload(point_offset, hw_counter)
+        let payload_index = self.payload_index.borrow();
+        if !self.payload_storage.borrow().is_on_disk() {
+            return payload_index.get_payload(point_offset, hw_counter);
+        }
+
+        if let Some(payload) = self.hot_points_cache.get_payload(point_offset) {
+            return Ok(payload);
+        }
+        let payload = payload_index.get_payload(point_offset, hw_counter)?;
+        self.hot_points_cache.put_payload(point_offset, &payload);
+        Ok(payload)
     }
 
     pub fn save_current_state(&self) -> OperationResult<()> {
@@ -3,6 +3,7 @@ use std::iter::Iterator;
 
 use ahash::AHashMap;
 use atomic_refcell::AtomicRef;
+use common::search_pressure::IoThrottle;
 use common::small_uint::U24;
 use common::types::PointOffsetType;
 
@@ -29,6 +30,8 @@ pub struct PointData {
 pub struct BatchedVectorReader<'a> {
     points_to_insert: &'a [PointData],
     source_vector_storages: &'a [AtomicRef<'a, VectorStorageEnum>],
+    /// Paused before reading each batch, while searches are slow
+    io_throttle: &'a IoThrottle,
     buffer: Vec<(CowVector<'a>, bool)>,
     seg_to_points_buffer: AHashMap<U24, Vec<(&'a PointData, usize)>>,
     /// Global position of the iterator.
@@ -40,6 +43,7 @@ impl<'a> BatchedVectorReader<'a> {
     pub fn new(
         points_to_insert: &'a [PointData],
         source_vector_storages: &'a [AtomicRef<'a, VectorStorageEnum>],
+        io_throttle: &'a IoThrottle,
     ) -> BatchedVectorReader<'a> {
         // We need to allocate the buffer with the size of the batch,
         // but we don't know the size of the vectors.
@@ -49,6 +53,7 @@ impl<'a> BatchedVectorReader<'a> {
         BatchedVectorReader {
             points_to_insert,
             source_vector_storages,
+            io_throttle,
             buffer,
             seg_to_points_buffer: AHashMap::default(),
             position: 0,
@@ -66,6 +71,8 @@ impl<'a> BatchedVectorReader<'a> {
     ///  (vec, vector_deleted)
     /// ```
     fn refill_buffer(&mut self) {
+        self.io_throttle.pause();
+
         let start_pos = self.position;
         let end_pos = min(self.position + BATCH_SIZE, self.points_to_insert.len());
 
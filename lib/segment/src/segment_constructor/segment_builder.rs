@@ -12,6 +12,7 @@ use bitvec::macros::internal::funty::Integral;
 use common::budget::ResourcePermit;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::flags::feature_flags;
+use common::search_pressure::IoThrottle;
 use common::small_uint::U24;
 use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
@@ -43,17 +44,24 @@ use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::batched_reader::{BatchedVectorReader, PointData};
 use crate::segment_constructor::{
-    VectorIndexBuildArgs, VectorIndexOpenArgs, build_vector_index, load_segment,
+    VectorIndexBuildArgs, VectorIndexOpenArgs, build_vector_index,
+    load_segment_with_chunked_vectors_config,
 };
+use crate::spaces::wasm_metric;
 use crate::types::{
-    CompactExtendedPointId, ExtendedPointId, HnswGlobalConfig, PayloadFieldSchema, PayloadKeyType,
-    SegmentConfig, SegmentState, SeqNumberType, VectorNameBuf,
+    CompactExtendedPointId, Distance, ExtendedPointId, HnswGlobalConfig, Indexes,
+    PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    VectorDataConfig, VectorNameBuf,
 };
+use crate::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use crate::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
 };
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
+/// Number of points, which payloads are copied between checks of the IO throttle
+const IO_THROTTLE_CHUNK_SIZE: usize = 1024;
+
 /// Structure for constructing segment out of several other segments
 pub struct SegmentBuilder {
     version: SeqNumberType,
@@ -62,6 +70,7 @@ pub struct SegmentBuilder {
     vector_data: HashMap<VectorNameBuf, VectorData>,
     segment_config: SegmentConfig,
     hnsw_global_config: HnswGlobalConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
 
     // The path, where fully created segment will be moved
     destination_path: PathBuf,
@@ -71,6 +80,9 @@ pub struct SegmentBuilder {
 
     // Payload key to defragment data to
     defragment_keys: Vec<PayloadKeyType>,
+
+    // Slows down copying of data from other segments while searches are slow
+    io_throttle: IoThrottle,
 }
 
 struct VectorData {
@@ -84,6 +96,7 @@ impl SegmentBuilder {
         temp_dir: &Path,
         segment_config: &SegmentConfig,
         hnsw_global_config: &HnswGlobalConfig,
+        chunked_vectors_config: ChunkedVectorsConfig,
     ) -> OperationResult<Self> {
         let temp_dir = create_temp_dir(temp_dir)?;
 
@@ -107,17 +120,25 @@ impl SegmentBuilder {
 
         for (vector_name, vector_config) in &segment_config.vector_data {
             let vector_storage_path = get_vector_storage_path(temp_dir.path(), vector_name);
-            let vector_storage = open_vector_storage(
+            let mut vector_storage = open_vector_storage(
                 #[cfg(feature = "rocksdb")]
                 &mut db_builder,
                 vector_config,
                 #[cfg(feature = "rocksdb")]
+                chunked_vectors_config,
+                #[cfg(feature = "rocksdb")]
                 &Default::default(),
                 &vector_storage_path,
                 #[cfg(feature = "rocksdb")]
                 vector_name,
             )?;
 
+            // Temporary directory may be outside of the collection, index is built with the
+            // module of the collection owning the segments
+            if vector_config.distance == Distance::Custom {
+                vector_storage.set_custom_metric(wasm_metric::find(segments_path));
+            }
+
             vector_data.insert(
                 vector_name.to_owned(),
                 VectorData {
@@ -159,10 +180,12 @@ impl SegmentBuilder {
             vector_data,
             segment_config: segment_config.clone(),
             hnsw_global_config: hnsw_global_config.clone(),
+            chunked_vectors_config,
             destination_path,
             temp_dir,
             indexed_fields: Default::default(),
             defragment_keys: vec![],
+            io_throttle: IoThrottle::disabled(),
         })
     }
 
@@ -170,6 +193,10 @@ impl SegmentBuilder {
         self.defragment_keys = keys;
     }
 
+    pub fn set_io_throttle(&mut self, io_throttle: IoThrottle) {
+        self.io_throttle = io_throttle;
+    }
+
     pub fn remove_indexed_field(&mut self, field: &PayloadKeyType) {
         self.indexed_fields.remove(field);
     }
@@ -359,8 +386,11 @@ impl SegmentBuilder {
                 })
                 .collect::<Result<Vec<_>, OperationError>>()?;
 
-            let mut vectors_iter: BatchedVectorReader =
-                BatchedVectorReader::new(&points_to_insert, &other_vector_storages);
+            let mut vectors_iter: BatchedVectorReader = BatchedVectorReader::new(
+                &points_to_insert,
+                &other_vector_storages,
+                &self.io_throttle,
+            );
 
             let internal_range = vector_data
                 .vector_storage
@@ -386,6 +416,10 @@ impl SegmentBuilder {
         for (new_internal_id, point_data) in internal_id_iter {
             check_process_stopped(stopped)?;
 
+            if (new_internal_id - internal_range_start) as usize % IO_THROTTLE_CHUNK_SIZE == 0 {
+                self.io_throttle.pause();
+            }
+
             let old_internal_id = point_data.internal_id;
 
             let other_payload = payloads[point_data.segment_index.get() as usize]
@@ -465,6 +499,8 @@ impl SegmentBuilder {
         rng: &mut R,
         hw_counter: &HardwareCounterCell,
     ) -> Result<Segment, OperationError> {
+        let chunked_vectors_config = self.chunked_vectors_config;
+
         let (temp_dir, destination_path) = {
             let SegmentBuilder {
                 version,
@@ -473,6 +509,7 @@ impl SegmentBuilder {
                 mut vector_data,
                 segment_config,
                 hnsw_global_config,
+                chunked_vectors_config: _,
                 destination_path,
                 temp_dir,
                 indexed_fields,
@@ -563,12 +600,13 @@ impl SegmentBuilder {
             payload_index.flusher()()?;
             let payload_index_arc = Arc::new(AtomicRefCell::new(payload_index));
 
-            // Try to lock GPU device.
+            // Try to lock GPU device, if any of the indexes is allowed to use it.
             #[cfg(feature = "gpu")]
             let gpu_devices_manager = crate::index::hnsw_index::gpu::GPU_DEVICES_MANAGER.read();
             #[cfg(feature = "gpu")]
             let gpu_device = gpu_devices_manager
                 .as_ref()
+                .filter(|_| segment_config.vector_data.values().any(use_gpu_indexing))
                 .map(|devices_manager| devices_manager.lock_device(stopped))
                 .transpose()?
                 .flatten();
@@ -595,7 +633,9 @@ impl SegmentBuilder {
                     VectorIndexBuildArgs {
                         permit: permit.clone(),
                         old_indices: &old_indices.remove(vector_name).unwrap(),
-                        gpu_device: gpu_device.as_ref(),
+                        gpu_device: gpu_device
+                            .as_ref()
+                            .filter(|_| use_gpu_indexing(vector_config)),
                         stopped,
                         rng,
                         hnsw_global_config: &hnsw_global_config,
@@ -681,12 +721,22 @@ impl SegmentBuilder {
         std::fs::rename(temp_dir.keep(), &destination_path)
             .describe("Moving segment data after optimization")?;
 
-        let loaded_segment = load_segment(&destination_path, stopped)?.ok_or_else(|| {
+        let loaded_segment = load_segment_with_chunked_vectors_config(
+            &destination_path,
+            chunked_vectors_config,
+            stopped,
+        )?
+        .ok_or_else(|| {
             OperationError::service_error(format!(
                 "Segment loading error: {}",
                 destination_path.display()
             ))
         })?;
+
+        if common::flags::feature_flags().segment_checksums {
+            loaded_segment.save_checksums()?;
+        }
+
         Ok(loaded_segment)
     }
 
@@ -777,6 +827,17 @@ where
     }
 }
 
+/// Whether the index of this vector may be built on GPU, if GPU indexing is enabled
+fn use_gpu_indexing(vector_config: &VectorDataConfig) -> bool {
+    match &vector_config.index {
+        Indexes::Plain {} | Indexes::Ivf(_) | Indexes::DiskAnn(_) => false,
+        // Seeded builds are always done on CPU
+        Indexes::Hnsw(hnsw_config) => {
+            hnsw_config.gpu_indexing.unwrap_or(true) && hnsw_config.seed.is_none()
+        }
+    }
+}
+
 fn create_temp_dir(parent_path: &Path) -> Result<TempDir, OperationError> {
     // Ensure parent path exists
     std::fs::create_dir_all(parent_path)
@@ -21,6 +21,7 @@ use uuid::Uuid;
 
 #[cfg(feature = "rocksdb")]
 use super::rocksdb_builder::RocksDbBuilder;
+use crate::common::hot_points_cache::HotPointsCache;
 use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
 use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
@@ -29,8 +30,10 @@ use crate::id_tracker::mutable_id_tracker::MutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, IdTrackerSS};
 use crate::index::VectorIndexEnum;
+use crate::index::disk_ann_index::disk_ann::{DiskAnnIndex, DiskAnnIndexOpenArgs};
 use crate::index::hnsw_index::gpu::gpu_devices_manager::LockedGpuDevice;
 use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::index::ivf_index::ivf::{IvfIndex, IvfIndexOpenArgs};
 use crate::index::plain_vector_index::PlainVectorIndex;
 use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::{
@@ -43,7 +46,10 @@ use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 #[cfg(feature = "rocksdb")]
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
-use crate::segment::{SEGMENT_STATE_FILE, Segment, SegmentVersion, VectorData};
+use crate::segment::{
+    SEGMENT_STATE_FILE, Segment, SegmentVersion, VectorData, verify_segment_checksums,
+};
+use crate::spaces::wasm_metric;
 #[cfg(feature = "rocksdb")]
 use crate::types::MultiVectorConfig;
 use crate::types::{
@@ -51,6 +57,7 @@ use crate::types::{
     SegmentType, SeqNumberType, SparseVectorStorageType, VectorDataConfig, VectorName,
     VectorStorageDatatype, VectorStorageType,
 };
+use crate::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use crate::vector_storage::dense::appendable_dense_vector_storage::{
     open_appendable_in_ram_vector_storage, open_appendable_memmap_vector_storage,
     open_appendable_memmap_vector_storage_byte, open_appendable_memmap_vector_storage_half,
@@ -99,13 +106,14 @@ pub fn get_vector_index_path(segment_path: &Path, vector_name: &VectorName) -> P
 pub(crate) fn open_vector_storage(
     #[cfg(feature = "rocksdb")] db_builder: &mut RocksDbBuilder,
     vector_config: &VectorDataConfig,
+    #[cfg(feature = "rocksdb")] chunked_vectors_config: ChunkedVectorsConfig,
     #[cfg(feature = "rocksdb")] stopped: &AtomicBool,
     vector_storage_path: &Path,
     #[cfg(feature = "rocksdb")] vector_name: &VectorName,
 ) -> OperationResult<VectorStorageEnum> {
     let storage_element_type = vector_config.datatype.unwrap_or_default();
 
-    match vector_config.storage_type {
+    let mut vector_storage = match vector_config.storage_type {
         // In memory - RocksDB disabled
         #[cfg(not(feature = "rocksdb"))]
         VectorStorageType::Memory => Err(OperationError::service_error(
@@ -127,6 +135,7 @@ pub(crate) fn open_vector_storage(
                     vector_config.size,
                     vector_config.distance,
                     *multi_vec_config,
+                    chunked_vectors_config,
                     stopped,
                 )
             } else {
@@ -136,6 +145,7 @@ pub(crate) fn open_vector_storage(
                     &db_column_name,
                     vector_config.size,
                     vector_config.distance,
+                    chunked_vectors_config,
                     stopped,
                 )
             }
@@ -219,7 +229,14 @@ pub(crate) fn open_vector_storage(
                 )
             }
         }
+    }?;
+
+    // Storages within a collection directory score with the module of the collection
+    if vector_config.distance == Distance::Custom {
+        vector_storage.set_custom_metric(wasm_metric::find(vector_storage_path));
     }
+
+    Ok(vector_storage)
 }
 
 pub(crate) fn create_payload_storage(
@@ -314,6 +331,24 @@ pub(crate) fn open_vector_index(
             payload_index,
             hnsw_config: hnsw_config.clone(),
         })?),
+        Indexes::Ivf(ivf_config) => VectorIndexEnum::Ivf(IvfIndex::open(IvfIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            ivf_config: *ivf_config,
+        })?),
+        Indexes::DiskAnn(disk_ann_config) => {
+            VectorIndexEnum::DiskAnn(DiskAnnIndex::open(DiskAnnIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                disk_ann_config: *disk_ann_config,
+            })?)
+        }
     })
 }
 
@@ -347,6 +382,28 @@ pub(crate) fn build_vector_index<R: Rng + ?Sized>(
             },
             build_args,
         )?),
+        Indexes::Ivf(ivf_config) => VectorIndexEnum::Ivf(IvfIndex::build(
+            IvfIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                ivf_config: *ivf_config,
+            },
+            build_args,
+        )?),
+        Indexes::DiskAnn(disk_ann_config) => VectorIndexEnum::DiskAnn(DiskAnnIndex::build(
+            DiskAnnIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                disk_ann_config: *disk_ann_config,
+            },
+            build_args,
+        )?),
     })
 }
 
@@ -438,6 +495,8 @@ fn create_segment(
     version: Option<SeqNumberType>,
     segment_path: &Path,
     config: &SegmentConfig,
+    #[cfg_attr(not(feature = "rocksdb"), expect(unused_variables))]
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
     create: bool,
 ) -> OperationResult<Segment> {
@@ -473,6 +532,8 @@ fn create_segment(
             &mut db_builder,
             vector_config,
             #[cfg(feature = "rocksdb")]
+            chunked_vectors_config,
+            #[cfg(feature = "rocksdb")]
             stopped,
             &vector_storage_path,
             #[cfg(feature = "rocksdb")]
@@ -623,6 +684,7 @@ fn create_segment(
         #[cfg(feature = "rocksdb")]
         database: db_builder.build(),
         flush_thread: Mutex::new(None),
+        hot_points_cache: HotPointsCache::default(),
     })
 }
 
@@ -684,7 +746,17 @@ fn create_segment_id_tracker(
     )))
 }
 
+/// Load segment with the default [`ChunkedVectorsConfig`] of in-memory vector storages.
 pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option<Segment>> {
+    load_segment_with_chunked_vectors_config(path, ChunkedVectorsConfig::default(), stopped)
+}
+
+/// Load segment, in-memory vector storages are laid out with the given [`ChunkedVectorsConfig`].
+pub fn load_segment_with_chunked_vectors_config(
+    path: &Path,
+    chunked_vectors_config: ChunkedVectorsConfig,
+    stopped: &AtomicBool,
+) -> OperationResult<Option<Segment>> {
     if path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -738,12 +810,17 @@ pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option
     #[cfg_attr(not(feature = "rocksdb"), expect(unused_mut))]
     let mut segment_state = Segment::load_state(path)?;
 
+    if common::flags::feature_flags().verify_segment_checksums {
+        verify_segment_checksums(path)?;
+    }
+
     #[cfg_attr(not(feature = "rocksdb"), expect(unused_mut))]
     let mut segment = create_segment(
         segment_state.initial_version,
         segment_state.version,
         path,
         &segment_state.config,
+        chunked_vectors_config,
         stopped,
         false,
     )?;
@@ -779,10 +856,28 @@ pub fn new_segment_path(segments_path: &Path) -> PathBuf {
 /// To load a segment, saving the segment version is required. If `ready` is false, the version
 /// will not be stored. Then the segment is skipped on restart when trying to load it again. In
 /// that case, the segment version must be stored manually to make it ready.
+///
+/// In-memory vector storages are laid out with the default [`ChunkedVectorsConfig`].
 pub fn build_segment(
     segments_path: &Path,
     config: &SegmentConfig,
     ready: bool,
+) -> OperationResult<Segment> {
+    build_segment_with_chunked_vectors_config(
+        segments_path,
+        config,
+        ChunkedVectorsConfig::default(),
+        ready,
+    )
+}
+
+/// Same as [`build_segment`], but in-memory vector storages are laid out with the given
+/// [`ChunkedVectorsConfig`].
+pub fn build_segment_with_chunked_vectors_config(
+    segments_path: &Path,
+    config: &SegmentConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
+    ready: bool,
 ) -> OperationResult<Segment> {
     let segment_path = new_segment_path(segments_path);
 
@@ -793,6 +888,7 @@ pub fn build_segment(
         None,
         &segment_path,
         config,
+        chunked_vectors_config,
         &AtomicBool::new(false),
         true,
     )?;
@@ -1072,6 +1168,7 @@ pub fn migrate_rocksdb_dense_vector_storage_to_mmap(
             dim,
             old_storage.distance(),
         )?;
+        new_storage.set_custom_metric(old_storage.custom_metric().cloned());
         debug_assert_eq!(
             new_storage.total_vector_count(),
             0,
@@ -1163,6 +1260,7 @@ pub fn migrate_rocksdb_multi_dense_vector_storage_to_mmap(
             old_storage.distance(),
             multi_vector_config,
         )?;
+        new_storage.set_custom_metric(old_storage.custom_metric().cloned());
         debug_assert_eq!(
             new_storage.total_vector_count(),
             0,
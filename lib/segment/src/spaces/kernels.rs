@@ -0,0 +1,312 @@
+//! Runtime dispatch of metric kernels
+//!
+//! Kernels are selected once, by the widest instruction set supported by the CPU, and stored in
+//! a dispatch table. Kernels of each instruction set fall back to narrower ones for vectors,
+//! which are too short to benefit from wide registers.
+
+use std::sync::LazyLock;
+
+use common::types::ScoreType;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+use super::simple::MIN_DIM_SIZE_SIMD;
+#[cfg(target_arch = "x86_64")]
+use super::simple::{MIN_DIM_SIZE_AVX, MIN_DIM_SIZE_AVX512};
+use super::simple::{cosine_preprocess, dot_similarity, euclid_similarity, manhattan_similarity};
+#[cfg(target_arch = "x86_64")]
+use super::simple_avx::{
+    cosine_preprocess_avx, dot_similarity_avx, euclid_similarity_avx, manhattan_similarity_avx,
+};
+#[cfg(target_arch = "x86_64")]
+use super::simple_avx512::{
+    cosine_preprocess_avx512, dot_similarity_avx512, euclid_similarity_avx512,
+    manhattan_similarity_avx512,
+};
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use super::simple_neon::{
+    cosine_preprocess_neon, dot_similarity_neon, euclid_similarity_neon, manhattan_similarity_neon,
+};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use super::simple_sse::{
+    cosine_preprocess_sse, dot_similarity_sse, euclid_similarity_sse, manhattan_similarity_sse,
+};
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+
+static KERNELS: LazyLock<MetricKernels> = LazyLock::new(MetricKernels::detect);
+
+/// Kernels selected for the CPU of this process
+pub fn kernels() -> &'static MetricKernels {
+    &KERNELS
+}
+
+/// SIMD instruction set used by metric kernels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SimdInstructionSet {
+    Avx512,
+    Avx,
+    Sse,
+    Neon,
+    Scalar,
+}
+
+type SimilarityKernel = fn(&[VectorElementType], &[VectorElementType]) -> ScoreType;
+
+type PreprocessKernel = fn(DenseVector) -> DenseVector;
+
+pub struct MetricKernels {
+    pub instruction_set: SimdInstructionSet,
+    pub dot: SimilarityKernel,
+    pub euclid: SimilarityKernel,
+    pub manhattan: SimilarityKernel,
+    pub cosine_preprocess: PreprocessKernel,
+}
+
+impl MetricKernels {
+    const SCALAR: Self = Self {
+        instruction_set: SimdInstructionSet::Scalar,
+        dot: dot_similarity,
+        euclid: euclid_similarity,
+        manhattan: manhattan_similarity,
+        cosine_preprocess,
+    };
+
+    fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            let is_avx_supported = is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && is_x86_feature_detected!("sse");
+
+            if is_avx_supported && is_x86_feature_detected!("avx512f") {
+                return Self {
+                    instruction_set: SimdInstructionSet::Avx512,
+                    dot: dot_avx512,
+                    euclid: euclid_avx512,
+                    manhattan: manhattan_avx512,
+                    cosine_preprocess: cosine_preprocess_avx512_kernel,
+                };
+            }
+
+            if is_avx_supported {
+                return Self {
+                    instruction_set: SimdInstructionSet::Avx,
+                    dot: dot_avx,
+                    euclid: euclid_avx,
+                    manhattan: manhattan_avx,
+                    cosine_preprocess: cosine_preprocess_avx_kernel,
+                };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse") {
+                return Self {
+                    instruction_set: SimdInstructionSet::Sse,
+                    dot: dot_sse,
+                    euclid: euclid_sse,
+                    manhattan: manhattan_sse,
+                    cosine_preprocess: cosine_preprocess_sse_kernel,
+                };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return Self {
+                    instruction_set: SimdInstructionSet::Neon,
+                    dot: dot_neon,
+                    euclid: euclid_neon,
+                    manhattan: manhattan_neon,
+                    cosine_preprocess: cosine_preprocess_neon_kernel,
+                };
+            }
+        }
+
+        Self::SCALAR
+    }
+}
+
+/// Define a kernel, which uses `$simd` for vectors of at least `$min_dim` elements, and
+/// `$fallback` otherwise
+///
+/// Kernels are only selected into the dispatch table if their instruction set is supported.
+macro_rules! similarity_kernel {
+    ($name:ident, $simd:ident, $min_dim:expr, $fallback:ident) => {
+        fn $name(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+            if v1.len() >= $min_dim {
+                unsafe { $simd(v1, v2) }
+            } else {
+                $fallback(v1, v2)
+            }
+        }
+    };
+}
+
+/// Same as [`similarity_kernel`], for vector preprocessing
+macro_rules! preprocess_kernel {
+    ($name:ident, $simd:ident, $min_dim:expr, $fallback:ident) => {
+        fn $name(vector: DenseVector) -> DenseVector {
+            if vector.len() >= $min_dim {
+                unsafe { $simd(vector) }
+            } else {
+                $fallback(vector)
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(
+    dot_avx512,
+    dot_similarity_avx512,
+    MIN_DIM_SIZE_AVX512,
+    dot_avx
+);
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(
+    euclid_avx512,
+    euclid_similarity_avx512,
+    MIN_DIM_SIZE_AVX512,
+    euclid_avx
+);
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(
+    manhattan_avx512,
+    manhattan_similarity_avx512,
+    MIN_DIM_SIZE_AVX512,
+    manhattan_avx
+);
+#[cfg(target_arch = "x86_64")]
+preprocess_kernel!(
+    cosine_preprocess_avx512_kernel,
+    cosine_preprocess_avx512,
+    MIN_DIM_SIZE_AVX512,
+    cosine_preprocess_avx_kernel
+);
+
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(dot_avx, dot_similarity_avx, MIN_DIM_SIZE_AVX, dot_sse);
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(
+    euclid_avx,
+    euclid_similarity_avx,
+    MIN_DIM_SIZE_AVX,
+    euclid_sse
+);
+#[cfg(target_arch = "x86_64")]
+similarity_kernel!(
+    manhattan_avx,
+    manhattan_similarity_avx,
+    MIN_DIM_SIZE_AVX,
+    manhattan_sse
+);
+#[cfg(target_arch = "x86_64")]
+preprocess_kernel!(
+    cosine_preprocess_avx_kernel,
+    cosine_preprocess_avx,
+    MIN_DIM_SIZE_AVX,
+    cosine_preprocess_sse_kernel
+);
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+similarity_kernel!(
+    dot_sse,
+    dot_similarity_sse,
+    MIN_DIM_SIZE_SIMD,
+    dot_similarity
+);
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+similarity_kernel!(
+    euclid_sse,
+    euclid_similarity_sse,
+    MIN_DIM_SIZE_SIMD,
+    euclid_similarity
+);
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+similarity_kernel!(
+    manhattan_sse,
+    manhattan_similarity_sse,
+    MIN_DIM_SIZE_SIMD,
+    manhattan_similarity
+);
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+preprocess_kernel!(
+    cosine_preprocess_sse_kernel,
+    cosine_preprocess_sse,
+    MIN_DIM_SIZE_SIMD,
+    cosine_preprocess
+);
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+similarity_kernel!(
+    dot_neon,
+    dot_similarity_neon,
+    MIN_DIM_SIZE_SIMD,
+    dot_similarity
+);
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+similarity_kernel!(
+    euclid_neon,
+    euclid_similarity_neon,
+    MIN_DIM_SIZE_SIMD,
+    euclid_similarity
+);
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+similarity_kernel!(
+    manhattan_neon,
+    manhattan_similarity_neon,
+    MIN_DIM_SIZE_SIMD,
+    manhattan_similarity
+);
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+preprocess_kernel!(
+    cosine_preprocess_neon_kernel,
+    cosine_preprocess_neon,
+    MIN_DIM_SIZE_SIMD,
+    cosine_preprocess
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernels_match_scalar() {
+        let kernels = kernels();
+        let scalar = MetricKernels::SCALAR;
+
+        // Cover both short vectors, handled by fallbacks, and long ones
+        for dim in [3, 17, 40, 100] {
+            let v1: DenseVector = (0..dim).map(|i| (i as f32 * 0.37).sin()).collect();
+            let v2: DenseVector = (0..dim).map(|i| (i as f32 * 0.11).cos()).collect();
+
+            let pairs = [
+                ((kernels.dot)(&v1, &v2), (scalar.dot)(&v1, &v2)),
+                ((kernels.euclid)(&v1, &v2), (scalar.euclid)(&v1, &v2)),
+                ((kernels.manhattan)(&v1, &v2), (scalar.manhattan)(&v1, &v2)),
+            ];
+            for (simd, scalar) in pairs {
+                assert!(
+                    (simd - scalar).abs() < 1e-3,
+                    "{:?} kernel mismatch for dim {dim}: {simd} != {scalar}",
+                    kernels.instruction_set,
+                );
+            }
+
+            let simd = (kernels.cosine_preprocess)(v1.clone());
+            let scalar = (scalar.cosine_preprocess)(v1);
+            for (simd, scalar) in simd.iter().zip(&scalar) {
+                assert!((simd - scalar).abs() < 1e-5);
+            }
+        }
+    }
+}
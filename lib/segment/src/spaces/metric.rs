@@ -14,6 +14,18 @@ pub trait Metric<T: PrimitiveVectorElement> {
     /// Necessary vector transformations performed before adding it to the collection (like normalization)
     /// If no transformation is needed - returns the same vector
     fn preprocess(vector: DenseVector) -> DenseVector;
+
+    /// Transformation of a query vector, performed once before comparing it to stored vectors
+    /// with [`Metric::query_similarity`]. Same as [`Metric::preprocess`] by default.
+    fn preprocess_query(vector: DenseVector) -> DenseVector {
+        Self::preprocess(vector)
+    }
+
+    /// Similarity of a query vector, transformed with [`Metric::preprocess_query`], to a stored
+    /// vector. Same as [`Metric::similarity`] by default.
+    fn query_similarity(query: &[T], stored: &[T]) -> ScoreType {
+        Self::similarity(query, stored)
+    }
 }
 
 pub trait MetricPostProcessing {
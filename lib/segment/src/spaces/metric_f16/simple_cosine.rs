@@ -1,6 +1,7 @@
 use common::types::ScoreType;
 
 use super::simple_dot::dot_similarity_half;
+use crate::common::determinism::is_deterministic;
 use crate::data_types::vectors::{DenseVector, VectorElementTypeHalf};
 use crate::spaces::metric::Metric;
 #[cfg(target_arch = "x86_64")]
@@ -26,6 +27,10 @@ impl Metric<VectorElementTypeHalf> for CosineMetric {
     }
 
     fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        if is_deterministic() {
+            return dot_similarity_half(v1, v2);
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -58,6 +63,10 @@ impl Metric<VectorElementTypeHalf> for CosineMetric {
     }
 
     fn preprocess(vector: DenseVector) -> DenseVector {
+        if is_deterministic() {
+            return cosine_preprocess(vector);
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
@@ -2,6 +2,7 @@ use common::types::ScoreType;
 use half::f16;
 use num_traits::Float;
 
+use crate::common::determinism::is_deterministic;
 use crate::data_types::vectors::{DenseVector, VectorElementTypeHalf};
 use crate::spaces::metric::Metric;
 #[cfg(target_arch = "x86_64")]
@@ -21,6 +22,10 @@ impl Metric<VectorElementTypeHalf> for EuclidMetric {
     }
 
     fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        if is_deterministic() {
+            return euclid_similarity_half(v1, v2);
+        }
+
         #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx")
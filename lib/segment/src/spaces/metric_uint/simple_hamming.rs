@@ -0,0 +1,84 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeByte};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::HammingMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeByte> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("popcnt") {
+                return unsafe { popcnt_hamming_similarity_bytes(v1, v2) };
+            }
+        }
+
+        hamming_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn hamming_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(hamming_distance_bytes(v1, v2) as ScoreType)
+}
+
+/// # Safety
+///
+/// The CPU must support the `popcnt` instruction.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+pub unsafe fn popcnt_hamming_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(hamming_distance_bytes(v1, v2) as ScoreType)
+}
+
+/// Number of differing bits, compared in 64-bit words
+#[inline(always)]
+fn hamming_distance_bytes(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> u32 {
+    let words1 = v1.chunks_exact(size_of::<u64>());
+    let words2 = v2.chunks_exact(size_of::<u64>());
+
+    let remainder: u32 = words1
+        .remainder()
+        .iter()
+        .zip(words2.remainder())
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum();
+
+    let word = |bytes: &[u8]| u64::from_ne_bytes(bytes.try_into().expect("chunk is a word"));
+    words1
+        .zip(words2)
+        .map(|(a, b)| (word(a) ^ word(b)).count_ones())
+        .sum::<u32>()
+        + remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::metric::MetricPostProcessing;
+
+    #[test]
+    fn test_hamming_similarity_bytes() {
+        let v1: Vec<u8> = (0..21).collect();
+        let v2: Vec<u8> = (0..21).map(|x| x ^ 0b1010_0001).collect();
+
+        assert_eq!(hamming_similarity_bytes(&v1, &v1), 0.0);
+        assert_eq!(hamming_similarity_bytes(&v1, &v2), -63.0);
+        assert_eq!(HammingMetric::similarity(&v1, &v2), -63.0);
+        assert_eq!(HammingMetric::postprocess(-63.0), 63.0);
+    }
+}
@@ -0,0 +1,95 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeByte};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{JaccardMetric, jaccard_similarity_from_counts};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeByte> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("popcnt") {
+                return unsafe { popcnt_jaccard_similarity_bytes(v1, v2) };
+            }
+        }
+
+        jaccard_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn jaccard_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let (intersection, union) = intersection_and_union_bytes(v1, v2);
+    jaccard_similarity_from_counts(intersection, union)
+}
+
+/// # Safety
+///
+/// The CPU must support the `popcnt` instruction.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+pub unsafe fn popcnt_jaccard_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    let (intersection, union) = intersection_and_union_bytes(v1, v2);
+    jaccard_similarity_from_counts(intersection, union)
+}
+
+/// Numbers of bits set in both and in any of the vectors, counted in 64-bit words
+#[inline(always)]
+fn intersection_and_union_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> (u32, u32) {
+    let words1 = v1.chunks_exact(size_of::<u64>());
+    let words2 = v2.chunks_exact(size_of::<u64>());
+
+    let remainder = words1
+        .remainder()
+        .iter()
+        .zip(words2.remainder())
+        .map(|(a, b)| (u64::from(*a), u64::from(*b)));
+
+    let word = |bytes: &[u8]| u64::from_ne_bytes(bytes.try_into().expect("chunk is a word"));
+    words1
+        .zip(words2)
+        .map(|(a, b)| (word(a), word(b)))
+        .chain(remainder)
+        .fold((0, 0), |(intersection, union), (a, b)| {
+            (
+                intersection + (a & b).count_ones(),
+                union + (a | b).count_ones(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::metric::MetricPostProcessing;
+
+    #[test]
+    fn test_jaccard_similarity_bytes() {
+        let v1: Vec<u8> = vec![0b1111_0000; 12];
+        let v2: Vec<u8> = vec![0b0011_1100; 12];
+
+        // 2 common bits of 6 set bits in each byte
+        assert_eq!(jaccard_similarity_bytes(&v1, &v1), 0.0);
+        assert_eq!(jaccard_similarity_bytes(&v1, &v2), 2.0 / 6.0 - 1.0);
+        assert_eq!(JaccardMetric::similarity(&v1, &v2), 2.0 / 6.0 - 1.0);
+        assert_eq!(JaccardMetric::postprocess(-0.5), 0.5);
+        assert_eq!(jaccard_similarity_bytes(&[0; 3], &[0; 3]), 0.0);
+    }
+}
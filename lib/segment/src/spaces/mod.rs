@@ -1,6 +1,8 @@
+pub mod kernels;
 pub mod metric;
 pub mod simple;
 pub mod tools;
+pub mod wasm_metric;
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 pub mod simple_sse;
@@ -8,6 +10,9 @@ pub mod simple_sse;
 #[cfg(target_arch = "x86_64")]
 pub mod simple_avx;
 
+#[cfg(target_arch = "x86_64")]
+pub mod simple_avx512;
+
 pub mod metric_f16;
 pub mod metric_uint;
 
@@ -1,15 +1,17 @@
+use std::borrow::Cow;
+
 use common::types::ScoreType;
 
+use super::kernels::kernels;
 use super::metric::{Metric, MetricPostProcessing};
-#[cfg(target_arch = "x86_64")]
-use super::simple_avx::*;
-#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-use super::simple_neon::*;
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-use super::simple_sse::*;
 use super::tools::is_length_zero_or_normalized;
+use crate::common::determinism::is_deterministic;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{DenseVector, VectorElementType};
-use crate::types::Distance;
+use crate::types::{Distance, VectorStorageDatatype};
+
+#[cfg(target_arch = "x86_64")]
+pub(crate) const MIN_DIM_SIZE_AVX512: usize = 64;
 
 #[cfg(target_arch = "x86_64")]
 pub(crate) const MIN_DIM_SIZE_AVX: usize = 32;
@@ -33,37 +35,26 @@ pub struct EuclidMetric;
 #[derive(Clone)]
 pub struct ManhattanMetric;
 
+#[derive(Clone)]
+pub struct HammingMetric;
+
+#[derive(Clone)]
+pub struct JaccardMetric;
+
+#[derive(Clone)]
+pub struct RawCosineMetric;
+
 impl Metric<VectorElementType> for EuclidMetric {
     fn distance() -> Distance {
         Distance::Euclid
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
-        #[cfg(target_arch = "x86_64")]
-        {
-            if is_x86_feature_detected!("avx")
-                && is_x86_feature_detected!("fma")
-                && v1.len() >= MIN_DIM_SIZE_AVX
-            {
-                return unsafe { euclid_similarity_avx(v1, v2) };
-            }
-        }
-
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            if is_x86_feature_detected!("sse") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { euclid_similarity_sse(v1, v2) };
-            }
+        if is_deterministic() {
+            return euclid_similarity(v1, v2);
         }
 
-        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-        {
-            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { euclid_similarity_neon(v1, v2) };
-            }
-        }
-
-        euclid_similarity(v1, v2)
+        (kernels().euclid)(v1, v2)
     }
 
     fn preprocess(vector: DenseVector) -> DenseVector {
@@ -83,31 +74,11 @@ impl Metric<VectorElementType> for ManhattanMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
-        #[cfg(target_arch = "x86_64")]
-        {
-            if is_x86_feature_detected!("avx")
-                && is_x86_feature_detected!("fma")
-                && v1.len() >= MIN_DIM_SIZE_AVX
-            {
-                return unsafe { manhattan_similarity_avx(v1, v2) };
-            }
-        }
-
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            if is_x86_feature_detected!("sse") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { manhattan_similarity_sse(v1, v2) };
-            }
-        }
-
-        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-        {
-            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { manhattan_similarity_neon(v1, v2) };
-            }
+        if is_deterministic() {
+            return manhattan_similarity(v1, v2);
         }
 
-        manhattan_similarity(v1, v2)
+        (kernels().manhattan)(v1, v2)
     }
 
     fn preprocess(vector: DenseVector) -> DenseVector {
@@ -127,31 +98,11 @@ impl Metric<VectorElementType> for DotProductMetric {
     }
 
     fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
-        #[cfg(target_arch = "x86_64")]
-        {
-            if is_x86_feature_detected!("avx")
-                && is_x86_feature_detected!("fma")
-                && v1.len() >= MIN_DIM_SIZE_AVX
-            {
-                return unsafe { dot_similarity_avx(v1, v2) };
-            }
+        if is_deterministic() {
+            return dot_similarity(v1, v2);
         }
 
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            if is_x86_feature_detected!("sse") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { dot_similarity_sse(v1, v2) };
-            }
-        }
-
-        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-        {
-            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { dot_similarity_neon(v1, v2) };
-            }
-        }
-
-        dot_similarity(v1, v2)
+        (kernels().dot)(v1, v2)
     }
 
     fn preprocess(vector: DenseVector) -> DenseVector {
@@ -176,36 +127,101 @@ impl Metric<VectorElementType> for CosineMetric {
     }
 
     fn preprocess(vector: DenseVector) -> DenseVector {
-        #[cfg(target_arch = "x86_64")]
-        {
-            if is_x86_feature_detected!("avx")
-                && is_x86_feature_detected!("fma")
-                && vector.len() >= MIN_DIM_SIZE_AVX
-            {
-                return unsafe { cosine_preprocess_avx(vector) };
-            }
+        if is_deterministic() {
+            return cosine_preprocess(vector);
         }
 
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-        {
-            if is_x86_feature_detected!("sse") && vector.len() >= MIN_DIM_SIZE_SIMD {
-                return unsafe { cosine_preprocess_sse(vector) };
-            }
-        }
+        (kernels().cosine_preprocess)(vector)
+    }
+}
+
+impl MetricPostProcessing for CosineMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score
+    }
+}
+
+/// Elements are converted into bytes of a bit-packed binary vector, storage with `uint8` datatype
+/// is preferable.
+impl Metric<VectorElementType> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        hamming_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl MetricPostProcessing for HammingMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+/// Elements are converted into bytes of a bit-packed binary vector, same as for [`HammingMetric`].
+impl Metric<VectorElementType> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        jaccard_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl MetricPostProcessing for JaccardMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+/// Same as [`CosineMetric`], but vectors are stored as is and normalized at scoring time.
+///
+/// Keeps original vectors retrievable, at the cost of computing norms of stored vectors on each
+/// score. Queries are normalized once, except for byte vectors, which can't hold normalized values.
+/// Elements of any datatype are converted into `f32`.
+impl<T: PrimitiveVectorElement> Metric<T> for RawCosineMetric {
+    fn distance() -> Distance {
+        Distance::RawCosine
+    }
+
+    fn similarity(v1: &[T], v2: &[T]) -> ScoreType {
+        let v1 = T::slice_to_float_cow(Cow::Borrowed(v1));
+        let v2 = T::slice_to_float_cow(Cow::Borrowed(v2));
+        raw_cosine_similarity(&v1, &v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
 
-        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
-        {
-            if std::arch::is_aarch64_feature_detected!("neon") && vector.len() >= MIN_DIM_SIZE_SIMD
-            {
-                return unsafe { cosine_preprocess_neon(vector) };
-            }
+    fn preprocess_query(vector: DenseVector) -> DenseVector {
+        if T::datatype() == VectorStorageDatatype::Uint8 {
+            return vector;
         }
+        <CosineMetric as Metric<VectorElementType>>::preprocess(vector)
+    }
 
-        cosine_preprocess(vector)
+    fn query_similarity(query: &[T], stored: &[T]) -> ScoreType {
+        if T::datatype() == VectorStorageDatatype::Uint8 {
+            return Self::similarity(query, stored);
+        }
+        let query = T::slice_to_float_cow(Cow::Borrowed(query));
+        let stored = T::slice_to_float_cow(Cow::Borrowed(stored));
+        normalized_query_cosine_similarity(&query, &stored)
     }
 }
 
-impl MetricPostProcessing for CosineMetric {
+impl MetricPostProcessing for RawCosineMetric {
     fn postprocess(score: ScoreType) -> ScoreType {
         score
     }
@@ -238,6 +254,61 @@ pub fn dot_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> Sco
     v1.iter().zip(v2).map(|(a, b)| a * b).sum()
 }
 
+/// Cosine similarity of vectors, which are not normalized
+///
+/// Zero vectors are considered orthogonal to any vector.
+pub fn raw_cosine_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let norms =
+        (DotProductMetric::similarity(v1, v1) * DotProductMetric::similarity(v2, v2)).sqrt();
+    if norms == 0.0 {
+        return 0.0;
+    }
+    DotProductMetric::similarity(v1, v2) / norms
+}
+
+/// Cosine similarity of a normalized query to a vector, which is not normalized
+pub fn normalized_query_cosine_similarity(
+    query: &[VectorElementType],
+    stored: &[VectorElementType],
+) -> ScoreType {
+    let stored_norm = DotProductMetric::similarity(stored, stored).sqrt();
+    if stored_norm == 0.0 {
+        return 0.0;
+    }
+    DotProductMetric::similarity(query, stored) / stored_norm
+}
+
+pub fn hamming_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (*a as u8 ^ *b as u8).count_ones())
+        .sum::<u32>() as ScoreType)
+}
+
+pub fn jaccard_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let (intersection, union) = v1.iter().zip(v2).map(|(a, b)| (*a as u8, *b as u8)).fold(
+        (0, 0),
+        |(intersection, union), (a, b)| {
+            (
+                intersection + (a & b).count_ones(),
+                union + (a | b).count_ones(),
+            )
+        },
+    );
+    jaccard_similarity_from_counts(intersection, union)
+}
+
+/// Negated Jaccard distance between sets of set bits, from sizes of their intersection and union
+///
+/// Two empty sets are considered identical.
+#[inline]
+pub fn jaccard_similarity_from_counts(intersection: u32, union: u32) -> ScoreType {
+    if union == 0 {
+        return 0.0;
+    }
+    intersection as ScoreType / union as ScoreType - 1.0
+}
+
 #[cfg(test)]
 mod tests {
     use rand::Rng;
@@ -275,4 +346,30 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_raw_cosine_similarity() {
+        let vector = vec![3.0, 4.0, 0.0];
+        let preprocessed =
+            <RawCosineMetric as Metric<VectorElementType>>::preprocess(vector.clone());
+        assert_eq!(preprocessed, vector);
+
+        let score =
+            <RawCosineMetric as Metric<VectorElementType>>::similarity(&vector, &[0.0, 8.0, 6.0]);
+        assert!((score - 0.64).abs() < 1e-6);
+
+        let normalized = <CosineMetric as Metric<VectorElementType>>::preprocess(vector.clone());
+        let query = <CosineMetric as Metric<VectorElementType>>::preprocess(vec![0.0, 8.0, 6.0]);
+        assert!((CosineMetric::similarity(&normalized, &query) - score).abs() < 1e-6);
+
+        assert_eq!(raw_cosine_similarity(&vector, &[0.0, 0.0, 0.0]), 0.0);
+
+        // Query is normalized once, stored vectors are normalized on each score
+        let query =
+            <RawCosineMetric as Metric<VectorElementType>>::preprocess_query(vec![0.0, 8.0, 6.0]);
+        assert!((dot_similarity(&query, &query) - 1.0).abs() < 1e-6);
+        let query_score =
+            <RawCosineMetric as Metric<VectorElementType>>::query_similarity(&query, &vector);
+        assert!((query_score - score).abs() < 1e-6);
+    }
 }
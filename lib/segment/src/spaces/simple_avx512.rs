@@ -0,0 +1,227 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use super::tools::is_length_zero_or_normalized;
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+
+/// Calculates the hsum (horizontal sum) of four 64 byte registers.
+#[target_feature(enable = "avx512f")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn four_way_hsum_avx512(a: __m512, b: __m512, c: __m512, d: __m512) -> f32 {
+    let sum1 = _mm512_add_ps(a, b);
+    let sum2 = _mm512_add_ps(c, d);
+    _mm512_reduce_add_ps(_mm512_add_ps(sum1, sum2))
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn euclid_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    unsafe {
+        let n = v1.len();
+        let m = n - (n % 64);
+        let mut ptr1: *const f32 = v1.as_ptr();
+        let mut ptr2: *const f32 = v2.as_ptr();
+        let mut sum512_1: __m512 = _mm512_setzero_ps();
+        let mut sum512_2: __m512 = _mm512_setzero_ps();
+        let mut sum512_3: __m512 = _mm512_setzero_ps();
+        let mut sum512_4: __m512 = _mm512_setzero_ps();
+        let mut i: usize = 0;
+        while i < m {
+            let sub512_1: __m512 = _mm512_sub_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2));
+            sum512_1 = _mm512_fmadd_ps(sub512_1, sub512_1, sum512_1);
+
+            let sub512_2: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(16)), _mm512_loadu_ps(ptr2.add(16)));
+            sum512_2 = _mm512_fmadd_ps(sub512_2, sub512_2, sum512_2);
+
+            let sub512_3: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(32)), _mm512_loadu_ps(ptr2.add(32)));
+            sum512_3 = _mm512_fmadd_ps(sub512_3, sub512_3, sum512_3);
+
+            let sub512_4: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(48)), _mm512_loadu_ps(ptr2.add(48)));
+            sum512_4 = _mm512_fmadd_ps(sub512_4, sub512_4, sum512_4);
+
+            ptr1 = ptr1.add(64);
+            ptr2 = ptr2.add(64);
+            i += 64;
+        }
+
+        let mut result = four_way_hsum_avx512(sum512_1, sum512_2, sum512_3, sum512_4);
+
+        for i in 0..n - m {
+            result += (*ptr1.add(i) - *ptr2.add(i)).powi(2);
+        }
+        -result
+    }
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn manhattan_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    unsafe {
+        let n = v1.len();
+        let m = n - (n % 64);
+        let mut ptr1: *const f32 = v1.as_ptr();
+        let mut ptr2: *const f32 = v2.as_ptr();
+        let mut sum512_1: __m512 = _mm512_setzero_ps();
+        let mut sum512_2: __m512 = _mm512_setzero_ps();
+        let mut sum512_3: __m512 = _mm512_setzero_ps();
+        let mut sum512_4: __m512 = _mm512_setzero_ps();
+        let mut i: usize = 0;
+        while i < m {
+            let sub512_1: __m512 = _mm512_sub_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2));
+            sum512_1 = _mm512_add_ps(_mm512_abs_ps(sub512_1), sum512_1);
+
+            let sub512_2: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(16)), _mm512_loadu_ps(ptr2.add(16)));
+            sum512_2 = _mm512_add_ps(_mm512_abs_ps(sub512_2), sum512_2);
+
+            let sub512_3: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(32)), _mm512_loadu_ps(ptr2.add(32)));
+            sum512_3 = _mm512_add_ps(_mm512_abs_ps(sub512_3), sum512_3);
+
+            let sub512_4: __m512 =
+                _mm512_sub_ps(_mm512_loadu_ps(ptr1.add(48)), _mm512_loadu_ps(ptr2.add(48)));
+            sum512_4 = _mm512_add_ps(_mm512_abs_ps(sub512_4), sum512_4);
+
+            ptr1 = ptr1.add(64);
+            ptr2 = ptr2.add(64);
+            i += 64;
+        }
+
+        let mut result = four_way_hsum_avx512(sum512_1, sum512_2, sum512_3, sum512_4);
+
+        for i in 0..n - m {
+            result += (*ptr1.add(i) - *ptr2.add(i)).abs();
+        }
+        -result
+    }
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn cosine_preprocess_avx512(vector: DenseVector) -> DenseVector {
+    unsafe {
+        let n = vector.len();
+        let m = n - (n % 64);
+        let mut ptr: *const f32 = vector.as_ptr();
+        let mut sum512_1: __m512 = _mm512_setzero_ps();
+        let mut sum512_2: __m512 = _mm512_setzero_ps();
+        let mut sum512_3: __m512 = _mm512_setzero_ps();
+        let mut sum512_4: __m512 = _mm512_setzero_ps();
+        let mut i: usize = 0;
+        while i < m {
+            let m512_1 = _mm512_loadu_ps(ptr);
+            sum512_1 = _mm512_fmadd_ps(m512_1, m512_1, sum512_1);
+
+            let m512_2 = _mm512_loadu_ps(ptr.add(16));
+            sum512_2 = _mm512_fmadd_ps(m512_2, m512_2, sum512_2);
+
+            let m512_3 = _mm512_loadu_ps(ptr.add(32));
+            sum512_3 = _mm512_fmadd_ps(m512_3, m512_3, sum512_3);
+
+            let m512_4 = _mm512_loadu_ps(ptr.add(48));
+            sum512_4 = _mm512_fmadd_ps(m512_4, m512_4, sum512_4);
+
+            ptr = ptr.add(64);
+            i += 64;
+        }
+
+        let mut length = four_way_hsum_avx512(sum512_1, sum512_2, sum512_3, sum512_4);
+
+        for i in 0..n - m {
+            length += (*ptr.add(i)).powi(2);
+        }
+        if is_length_zero_or_normalized(length) {
+            return vector;
+        }
+        length = length.sqrt();
+        vector.into_iter().map(|x| x / length).collect()
+    }
+}
+
+#[target_feature(enable = "avx512f")]
+pub(crate) unsafe fn dot_similarity_avx512(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    unsafe {
+        let n = v1.len();
+        let m = n - (n % 64);
+        let mut ptr1: *const f32 = v1.as_ptr();
+        let mut ptr2: *const f32 = v2.as_ptr();
+        let mut sum512_1: __m512 = _mm512_setzero_ps();
+        let mut sum512_2: __m512 = _mm512_setzero_ps();
+        let mut sum512_3: __m512 = _mm512_setzero_ps();
+        let mut sum512_4: __m512 = _mm512_setzero_ps();
+        let mut i: usize = 0;
+        while i < m {
+            sum512_1 = _mm512_fmadd_ps(_mm512_loadu_ps(ptr1), _mm512_loadu_ps(ptr2), sum512_1);
+            sum512_2 = _mm512_fmadd_ps(
+                _mm512_loadu_ps(ptr1.add(16)),
+                _mm512_loadu_ps(ptr2.add(16)),
+                sum512_2,
+            );
+            sum512_3 = _mm512_fmadd_ps(
+                _mm512_loadu_ps(ptr1.add(32)),
+                _mm512_loadu_ps(ptr2.add(32)),
+                sum512_3,
+            );
+            sum512_4 = _mm512_fmadd_ps(
+                _mm512_loadu_ps(ptr1.add(48)),
+                _mm512_loadu_ps(ptr2.add(48)),
+                sum512_4,
+            );
+
+            ptr1 = ptr1.add(64);
+            ptr2 = ptr2.add(64);
+            i += 64;
+        }
+
+        let mut result = four_way_hsum_avx512(sum512_1, sum512_2, sum512_3, sum512_4);
+
+        for i in 0..n - m {
+            result += (*ptr1.add(i)) * (*ptr2.add(i));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_avx512() {
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if is_x86_feature_detected!("avx512f") {
+            let v1: Vec<f32> = (0..150).map(|i| (i % 23) as f32 - 11.0).collect();
+            let v2: Vec<f32> = (0..150).map(|i| (i % 17) as f32 * 0.5).collect();
+
+            let euclid_simd = unsafe { euclid_similarity_avx512(&v1, &v2) };
+            let euclid = euclid_similarity(&v1, &v2);
+            assert_eq!(euclid_simd, euclid);
+
+            let manhattan_simd = unsafe { manhattan_similarity_avx512(&v1, &v2) };
+            let manhattan = manhattan_similarity(&v1, &v2);
+            assert_eq!(manhattan_simd, manhattan);
+
+            let dot_simd = unsafe { dot_similarity_avx512(&v1, &v2) };
+            let dot = dot_similarity(&v1, &v2);
+            assert_eq!(dot_simd, dot);
+
+            let cosine_simd = unsafe { cosine_preprocess_avx512(v1.clone()) };
+            let cosine = cosine_preprocess(v1);
+            for (a, b) in cosine_simd.iter().zip(&cosine) {
+                assert!((a - b).abs() < 1e-6);
+            }
+        } else {
+            println!("avx512 test skipped");
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! Custom similarity function, implemented as a WebAssembly module
+//!
+//! A module must export its linear `memory` and two functions:
+//!
+//! - `alloc(len: i32) -> i32` - reserve `len` bytes of memory and return their offset
+//! - `score(query_ptr: i32, vector_ptr: i32, dim: i32) -> f32` - compare two vectors of `dim`
+//!   little-endian `f32` elements, greater the value - closer the vectors
+//!
+//! The module is used by vectors configured with [`Distance::Custom`]. The module configured on
+//! the peer creating a collection becomes part of the collection config, so all peers score with
+//! the same module. Each collection registers its own module, vector storages of the collection
+//! score with it. See [`wasm_module`] for sandboxing and limits.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+use arc_swap::ArcSwapOption;
+use common::types::ScoreType;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use zerocopy::IntoBytes as _;
+
+use super::metric::{Metric, MetricPostProcessing};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::wasm_module::{self, WasmLimits, WasmModule};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::DenseVector;
+use crate::types::{Distance, VectorStorageDatatype};
+
+/// Custom metric modules of collections, by collection directory
+static REGISTRY: LazyLock<RwLock<HashMap<PathBuf, Arc<WasmMetricModule>>>> =
+    LazyLock::new(Default::default);
+
+/// Custom metric module configured on this peer, attached to collections created through it
+static CONFIGURED: ArcSwapOption<WasmMetricModule> = ArcSwapOption::const_empty();
+
+thread_local! {
+    /// Module of the vector storage scored on this thread, see [`with_module`]
+    static CURRENT: RefCell<Option<Arc<WasmMetricModule>>> = const { RefCell::new(None) };
+}
+
+/// Configuration of the custom similarity function, implemented as a WebAssembly module
+#[derive(Clone, Debug, Deserialize)]
+pub struct WasmMetricConfig {
+    /// Path to the module file
+    pub module: PathBuf,
+    /// Maximum amount of fuel, roughly a number of executed instructions, a single score may use
+    #[serde(default = "default_fuel_limit")]
+    pub fuel_limit: u64,
+    /// Maximum size of linear memory of a module instance, in bytes
+    #[serde(default = "default_memory_limit_bytes")]
+    pub memory_limit_bytes: usize,
+}
+
+const fn default_fuel_limit() -> u64 {
+    wasm_module::DEFAULT_FUEL_LIMIT
+}
+
+const fn default_memory_limit_bytes() -> usize {
+    wasm_module::DEFAULT_MEMORY_LIMIT_BYTES
+}
+
+/// Custom metric module, as stored in configs of collections using it
+#[derive(Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct WasmMetricSource {
+    /// Binary of the module, base64 encoded in configs
+    #[serde(with = "base64_bytes")]
+    pub wasm: Vec<u8>,
+    pub fuel_limit: u64,
+    pub memory_limit_bytes: usize,
+}
+
+impl WasmMetricSource {
+    pub fn load(config: &WasmMetricConfig) -> OperationResult<Self> {
+        Ok(Self {
+            wasm: std::fs::read(&config.module)?,
+            fuel_limit: config.fuel_limit,
+            memory_limit_bytes: config.memory_limit_bytes,
+        })
+    }
+}
+
+impl std::fmt::Debug for WasmMetricSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmMetricSource")
+            .field("wasm_len", &self.wasm.len())
+            .field("fuel_limit", &self.fuel_limit)
+            .field("memory_limit_bytes", &self.memory_limit_bytes)
+            .finish()
+    }
+}
+
+mod base64_bytes {
+    use data_encoding::BASE64;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        BASE64
+            .decode(encoded.as_bytes())
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Load the metric module of the config, to be used by collections created through this peer
+pub fn register_module(config: &WasmMetricConfig) -> OperationResult<()> {
+    let module = compile(WasmMetricSource::load(config)?)?;
+    CONFIGURED.store(Some(module));
+    Ok(())
+}
+
+pub fn configured() -> Option<Arc<WasmMetricModule>> {
+    CONFIGURED.load_full()
+}
+
+/// Register the module of the collection stored at `collection_path`
+///
+/// Vector storages opened within the collection directory score with this module.
+/// Collections using the same module share its compiled instance.
+pub fn register(collection_path: &Path, source: &WasmMetricSource) -> OperationResult<()> {
+    let mut registry = REGISTRY.write();
+    let module = match registry.values().find(|module| module.source == *source) {
+        Some(module) => module.clone(),
+        None => compile(source.clone())?,
+    };
+    registry.insert(collection_path.to_path_buf(), module);
+    Ok(())
+}
+
+/// Remove the module of a deleted collection
+///
+/// Vector storages, which are still open, keep using the module until dropped.
+pub fn unregister(collection_path: &Path) {
+    REGISTRY.write().remove(collection_path);
+}
+
+/// Module of the collection, which contains the given `path`
+pub fn find(path: &Path) -> Option<Arc<WasmMetricModule>> {
+    let registry = REGISTRY.read();
+    path.ancestors()
+        .find_map(|ancestor| registry.get(ancestor))
+        .cloned()
+}
+
+/// Score with `module` on this thread while `f` runs
+///
+/// [`CustomMetric`] has no state of its own, it scores with the module of the storage currently
+/// scored on the calling thread.
+pub fn with_module<R>(module: &Arc<WasmMetricModule>, f: impl FnOnce() -> R) -> R {
+    struct RestoreGuard(Option<Arc<WasmMetricModule>>);
+
+    impl Drop for RestoreGuard {
+        fn drop(&mut self) {
+            CURRENT.set(self.0.take());
+        }
+    }
+
+    let _guard = RestoreGuard(CURRENT.replace(Some(module.clone())));
+    f()
+}
+
+fn compile(source: WasmMetricSource) -> OperationResult<Arc<WasmMetricModule>> {
+    let module = WasmMetricModule::new(source).map_err(|err| {
+        OperationError::service_error(format!("Failed to load custom metric module: {err}"))
+    })?;
+    Ok(Arc::new(module))
+}
+
+/// Compiled metric module
+pub struct WasmMetricModule {
+    source: WasmMetricSource,
+    module: WasmModule<(i32, i32, i32), f32>,
+}
+
+impl WasmMetricModule {
+    pub fn new(source: WasmMetricSource) -> OperationResult<Self> {
+        let limits = WasmLimits {
+            fuel_limit: source.fuel_limit,
+            memory_limit_bytes: source.memory_limit_bytes,
+        };
+        let module = WasmModule::new(&source.wasm, "score", limits)?;
+        Ok(Self { source, module })
+    }
+
+    pub fn source(&self) -> &WasmMetricSource {
+        &self.source
+    }
+
+    /// Score `vector` against `query` with the module
+    ///
+    /// Failed scores are considered the farthest possible.
+    pub fn score(&self, query: &[f32], vector: &[f32]) -> ScoreType {
+        self.score_bytes(query.as_bytes(), vector.as_bytes())
+    }
+
+    /// Score vectors given as bytes of `f32` elements, in native byte order
+    ///
+    /// All supported platforms are little-endian, so bytes are passed to the module as is.
+    fn score_bytes(&self, query: &[u8], vector: &[u8]) -> ScoreType {
+        match self.score_impl(query, vector) {
+            Ok(score) => score,
+            Err(err) => {
+                log::debug!("Custom metric module score failed: {err}");
+                ScoreType::MIN
+            }
+        }
+    }
+
+    fn score_impl(&self, query: &[u8], vector: &[u8]) -> OperationResult<ScoreType> {
+        let len = query.len().min(vector.len());
+        let dim = i32::try_from(len / size_of::<f32>())
+            .map_err(|_| OperationError::service_error("Vectors are too large"))?;
+        let offset = i32::try_from(len)
+            .map_err(|_| OperationError::service_error("Vectors are too large"))?;
+
+        self.module.call(
+            2 * len,
+            |memory| {
+                let (query_memory, vector_memory) = memory.split_at_mut(len);
+                query_memory.copy_from_slice(&query[..len]);
+                vector_memory.copy_from_slice(&vector[..len]);
+            },
+            |ptr| (ptr, ptr.wrapping_add(offset), dim),
+        )
+    }
+}
+
+impl std::fmt::Debug for WasmMetricModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmMetricModule")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Similarity computed by the custom metric module of the scored storage, see [`with_module`]
+///
+/// Custom distance vectors are `f32`, which are passed to the module without intermediate
+/// buffers. Elements of other datatypes are converted into `f32` first.
+#[derive(Clone)]
+pub struct CustomMetric;
+
+impl<T: PrimitiveVectorElement> Metric<T> for CustomMetric {
+    fn distance() -> Distance {
+        Distance::Custom
+    }
+
+    fn similarity(v1: &[T], v2: &[T]) -> ScoreType {
+        CURRENT.with_borrow(|module| {
+            let Some(module) = module else {
+                return ScoreType::MIN;
+            };
+            if T::datatype() == VectorStorageDatatype::Float32 {
+                return module.score_bytes(v1.as_bytes(), v2.as_bytes());
+            }
+            let v1 = T::slice_to_float_cow(Cow::Borrowed(v1));
+            let v2 = T::slice_to_float_cow(Cow::Borrowed(v2));
+            module.score(&v1, &v2)
+        })
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+impl MetricPostProcessing for CustomMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::wasm_module::DEFAULT_FUEL_LIMIT;
+
+    fn source(wat: &str, fuel_limit: u64, memory_limit_bytes: usize) -> WasmMetricSource {
+        WasmMetricSource {
+            wasm: wat::parse_str(wat).unwrap(),
+            fuel_limit,
+            memory_limit_bytes,
+        }
+    }
+
+    /// Multiplies the first elements of the vectors
+    const FIRST_ELEMENT_PRODUCT_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "score") (param $query i32) (param $vector i32) (param $dim i32) (result f32)
+                (f32.mul (f32.load (local.get $query)) (f32.load (local.get $vector)))))
+    "#;
+
+    const INFINITE_LOOP_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "score") (param $query i32) (param $vector i32) (param $dim i32) (result f32)
+                (loop $forever (br $forever))
+                (f32.const 1)))
+    "#;
+
+    #[test]
+    fn test_wasm_metric_score() {
+        let source = source(FIRST_ELEMENT_PRODUCT_MODULE, DEFAULT_FUEL_LIMIT, 1 << 16);
+        let module = WasmMetricModule::new(source).unwrap();
+
+        assert_eq!(module.score(&[2.0, 1.0, 0.0], &[3.0, 5.0, 7.0]), 6.0);
+        assert_eq!(module.score(&[-1.5, 1.0], &[2.0, 1.0]), -3.0);
+    }
+
+    #[test]
+    fn test_wasm_metric_limits() {
+        let module = WasmMetricModule::new(source(INFINITE_LOOP_MODULE, 10_000, 1 << 16)).unwrap();
+        assert_eq!(module.score(&[1.0], &[1.0]), ScoreType::MIN);
+
+        // Memory larger than the limit is rejected on instantiation
+        let large_memory = source(FIRST_ELEMENT_PRODUCT_MODULE, DEFAULT_FUEL_LIMIT, 1024);
+        assert!(WasmMetricModule::new(large_memory).is_err());
+
+        assert!(WasmMetricModule::new(source("(module)", DEFAULT_FUEL_LIMIT, 1 << 16)).is_err());
+    }
+
+    /// Adds the first elements of the vectors
+    const FIRST_ELEMENT_SUM_MODULE: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 0))
+            (func (export "score") (param $query i32) (param $vector i32) (param $dim i32) (result f32)
+                (f32.add (f32.load (local.get $query)) (f32.load (local.get $vector)))))
+    "#;
+
+    #[test]
+    fn test_wasm_metric_module_per_collection() {
+        let product = source(FIRST_ELEMENT_PRODUCT_MODULE, DEFAULT_FUEL_LIMIT, 1 << 16);
+        let sum = source(FIRST_ELEMENT_SUM_MODULE, DEFAULT_FUEL_LIMIT, 1 << 16);
+
+        let first_path = Path::new("/test_wasm_metric/collections/first");
+        let second_path = Path::new("/test_wasm_metric/collections/second");
+        register(first_path, &product).unwrap();
+        register(second_path, &sum).unwrap();
+
+        let first = find(&first_path.join("0/segments/segment")).unwrap();
+        let second = find(&second_path.join("0/segments/segment")).unwrap();
+        assert_eq!(first.source(), &product);
+        assert_eq!(second.source(), &sum);
+        assert!(find(Path::new("/test_wasm_metric/collections/other")).is_none());
+
+        // Scores with the module of the storage scored on this thread
+        let similarity = || <CustomMetric as Metric<f32>>::similarity(&[2.0, 1.0], &[3.0, 5.0]);
+        assert_eq!(with_module(&first, similarity), 6.0);
+        assert_eq!(with_module(&second, similarity), 5.0);
+        assert_eq!(
+            with_module(&first, || (with_module(&second, similarity), similarity())),
+            (5.0, 6.0),
+        );
+        assert_eq!(similarity(), ScoreType::MIN);
+
+        // Open storages keep the module of a deleted collection
+        unregister(first_path);
+        assert!(find(&first_path.join("0/segments/segment")).is_none());
+        assert_eq!(with_module(&first, similarity), 6.0);
+        unregister(second_path);
+
+        let json = serde_json::to_string(&product).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WasmMetricSource>(&json).unwrap(),
+            product,
+        );
+    }
+}
@@ -3,6 +3,7 @@ use serde::Serialize;
 
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
+use crate::index::hnsw_index::graph_stats::HnswGraphStats;
 use crate::types::{SegmentConfig, SegmentInfo, VectorNameBuf};
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -11,6 +12,8 @@ pub struct SegmentTelemetry {
     pub config: SegmentConfig,
     pub vector_index_searches: Vec<VectorIndexSearchesTelemetry>,
     pub payload_field_indices: Vec<PayloadIndexTelemetry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quantization: Vec<QuantizationTelemetry>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -71,4 +74,146 @@ pub struct VectorIndexSearchesTelemetry {
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub unfiltered_ivf: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_ivf: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub unfiltered_disk_ann: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_disk_ann: OperationDurationStatistics,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hnsw: Option<HnswIndexTelemetry>,
+}
+
+/// Structure of an HNSW graph and statistics of searches through it
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize, Default)]
+#[anonymize(false)]
+pub struct HnswIndexTelemetry {
+    /// Structure of the graph
+    pub graph: HnswGraphStats,
+    /// Number of searches through the graph without a payload filter, since the segment was loaded
+    pub unfiltered_searches: usize,
+    /// Number of searches through the graph with a payload filter, since the segment was loaded
+    pub filtered_searches: usize,
+    /// Average number of candidates explored on the base layer per search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_hops: Option<f64>,
+}
+
+/// Reuse of temporary buffers of vector index searches, accumulated over the whole process
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize, Default)]
+#[anonymize(false)]
+pub struct SearchPoolsTelemetry {
+    /// Number of visited lists, taken from the pool of an index
+    pub visited_lists_reused: usize,
+    /// Number of visited lists, allocated because the pool of an index was empty
+    pub visited_lists_allocated: usize,
+    /// Number of search buffers (heaps of candidates, lists of points and scores),
+    /// taken from the pool of a thread
+    pub buffers_reused: usize,
+    /// Number of search buffers, allocated because the pool of a thread was empty
+    pub buffers_allocated: usize,
+}
+
+/// Cost and quality of quantization of a vector
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize, Default)]
+pub struct QuantizationTelemetry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[anonymize(value = None)]
+    pub vector_name: Option<VectorNameBuf>,
+
+    /// Size of original vectors, in bytes
+    pub original_size_bytes: usize,
+
+    /// Estimated size of quantized vectors, in bytes
+    pub quantized_size_bytes: usize,
+
+    /// Size of original vectors divided by size of quantized vectors
+    #[anonymize(false)]
+    pub compression_ratio: f64,
+
+    /// Recall of the top by quantized scores, relative to the top by original scores of the same
+    /// candidates, in `[0, 1]`. Estimated from sampled rescored queries.
+    /// Absent until enough rescored queries are observed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[anonymize(false)]
+    pub estimated_recall: Option<f64>,
+
+    /// Number of rescored queries, the recall is estimated from
+    pub rescored_queries: usize,
+}
+
+impl QuantizationTelemetry {
+    pub fn new(
+        vector_name: VectorNameBuf,
+        original_size_bytes: usize,
+        quantized_size_bytes: usize,
+        estimated_recall: Option<f64>,
+        rescored_queries: usize,
+    ) -> Self {
+        Self {
+            vector_name: Some(vector_name),
+            original_size_bytes,
+            quantized_size_bytes,
+            compression_ratio: Self::compression_ratio(original_size_bytes, quantized_size_bytes),
+            estimated_recall,
+            rescored_queries,
+        }
+    }
+
+    fn compression_ratio(original_size_bytes: usize, quantized_size_bytes: usize) -> f64 {
+        if quantized_size_bytes == 0 {
+            return 1.0;
+        }
+        original_size_bytes as f64 / quantized_size_bytes as f64
+    }
+
+    /// Aggregate telemetry of the same vectors from multiple segments, by vector name
+    ///
+    /// Estimated recalls are weighted by the number of rescored queries.
+    pub fn aggregate(telemetry: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut aggregated: Vec<(Self, f64)> = Vec::new();
+        for item in telemetry {
+            let index = match aggregated
+                .iter()
+                .position(|(total, _)| total.vector_name == item.vector_name)
+            {
+                Some(index) => index,
+                None => {
+                    aggregated.push((
+                        Self {
+                            vector_name: item.vector_name.clone(),
+                            ..Default::default()
+                        },
+                        0.0,
+                    ));
+                    aggregated.len() - 1
+                }
+            };
+            let (total, weighted_recall_sum) = &mut aggregated[index];
+            total.original_size_bytes += item.original_size_bytes;
+            total.quantized_size_bytes += item.quantized_size_bytes;
+            if let Some(recall) = item.estimated_recall {
+                total.rescored_queries += item.rescored_queries;
+                *weighted_recall_sum += recall * item.rescored_queries as f64;
+            }
+        }
+
+        aggregated
+            .into_iter()
+            .map(|(mut total, weighted_recall_sum)| {
+                total.compression_ratio =
+                    Self::compression_ratio(total.original_size_bytes, total.quantized_size_bytes);
+                total.estimated_recall = (total.rescored_queries > 0)
+                    .then(|| weighted_recall_sum / total.rescored_queries as f64);
+                total
+            })
+            .collect()
+    }
 }
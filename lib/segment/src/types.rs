@@ -40,8 +40,13 @@ use crate::data_types::vectors::{DenseVector, VectorStructInternal};
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::JsonPath;
+use crate::payload_storage::wasm_condition;
 use crate::spaces::metric::{Metric, MetricPostProcessing};
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+    RawCosineMetric,
+};
+use crate::spaces::wasm_metric::CustomMetric;
 use crate::types::utils::unordered_hash_unique;
 use crate::utils::maybe_arc::MaybeArc;
 
@@ -297,6 +302,17 @@ pub enum Distance {
     Dot,
     // <https://simple.wikipedia.org/wiki/Manhattan_distance>
     Manhattan,
+    // <https://en.wikipedia.org/wiki/Hamming_distance>
+    // Number of differing bits, each vector element holds 8 bits of a bit-packed binary vector
+    Hamming,
+    // <https://en.wikipedia.org/wiki/Jaccard_index>
+    // One minus the ratio of common set bits to all set bits, of bit-packed binary vectors
+    Jaccard,
+    // Similarity computed by the custom metric module, registered on the node
+    Custom,
+    // Cosine similarity of vectors stored as is, without normalization.
+    // Original vectors can be retrieved, but vectors are normalized on each score.
+    RawCosine,
 }
 
 impl Distance {
@@ -306,6 +322,10 @@ impl Distance {
             Distance::Euclid => EuclidMetric::postprocess(score),
             Distance::Dot => DotProductMetric::postprocess(score),
             Distance::Manhattan => ManhattanMetric::postprocess(score),
+            Distance::Hamming => HammingMetric::postprocess(score),
+            Distance::Jaccard => JaccardMetric::postprocess(score),
+            Distance::Custom => CustomMetric::postprocess(score),
+            Distance::RawCosine => RawCosineMetric::postprocess(score),
         }
     }
 
@@ -315,19 +335,29 @@ impl Distance {
         EuclidMetric: Metric<T>,
         DotProductMetric: Metric<T>,
         ManhattanMetric: Metric<T>,
+        HammingMetric: Metric<T>,
+        JaccardMetric: Metric<T>,
     {
         match self {
             Distance::Cosine => CosineMetric::preprocess(vector),
             Distance::Euclid => EuclidMetric::preprocess(vector),
             Distance::Dot => DotProductMetric::preprocess(vector),
             Distance::Manhattan => ManhattanMetric::preprocess(vector),
+            Distance::Hamming => HammingMetric::preprocess(vector),
+            Distance::Jaccard => JaccardMetric::preprocess(vector),
+            Distance::Custom => <CustomMetric as Metric<T>>::preprocess(vector),
+            Distance::RawCosine => <RawCosineMetric as Metric<T>>::preprocess(vector),
         }
     }
 
     pub fn distance_order(&self) -> Order {
         match self {
-            Distance::Cosine | Distance::Dot => Order::LargeBetter,
-            Distance::Euclid | Distance::Manhattan => Order::SmallBetter,
+            Distance::Cosine | Distance::Dot | Distance::Custom | Distance::RawCosine => {
+                Order::LargeBetter
+            }
+            Distance::Euclid | Distance::Manhattan | Distance::Hamming | Distance::Jaccard => {
+                Order::SmallBetter
+            }
         }
     }
 
@@ -494,6 +524,15 @@ pub struct QuantizationSearchParams {
     #[validate(range(min = 1.0))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oversampling: Option<f64>,
+
+    /// If true, oversampling factor is chosen automatically for each segment,
+    /// based on the difference between quantized and original scores observed during rescoring.
+    /// If `oversampling` is set as well, it is used as the upper bound. Default is false.
+    ///
+    /// Only applies if rescoring is enabled.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive_oversampling: Option<bool>,
 }
 
 impl Hash for QuantizationSearchParams {
@@ -502,10 +541,12 @@ impl Hash for QuantizationSearchParams {
             ignore,
             rescore,
             oversampling,
+            adaptive_oversampling,
         } = self;
         ignore.hash(state);
         rescore.hash(state);
         oversampling.map(OrderedFloat).hash(state);
+        adaptive_oversampling.hash(state);
     }
 }
 
@@ -517,6 +558,45 @@ pub const fn default_quantization_oversampling_value() -> Option<f64> {
     None
 }
 
+pub const DEFAULT_ACORN_MAX_SELECTIVITY: f64 = 0.4;
+
+/// Parameters of ACORN-style filtered search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct AcornSearchParams {
+    /// If true, filtered HNSW search also explores links of points, which are rejected by the
+    /// filter. Improves recall for selective filters, at the cost of more vector comparisons.
+    /// Default is false.
+    #[serde(default)]
+    pub enable: bool,
+
+    /// Max share of points, matching the filter, for which ACORN search is used.
+    /// Based on the cardinality estimation of the filter. Less selective filters are searched
+    /// with the regular filtered HNSW search. Default is 0.4.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub max_selectivity: Option<f64>,
+}
+
+impl AcornSearchParams {
+    pub fn max_selectivity(&self) -> f64 {
+        self.max_selectivity
+            .unwrap_or(DEFAULT_ACORN_MAX_SELECTIVITY)
+    }
+}
+
+impl Hash for AcornSearchParams {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Self {
+            enable,
+            max_selectivity,
+        } = self;
+        enable.hash(state);
+        max_selectivity.map(OrderedFloat).hash(state);
+    }
+}
+
 /// Additional parameters of the search
 #[derive(
     Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Default, Hash,
@@ -543,6 +623,50 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// Max number of candidate points to score per query. The budget is split evenly between
+    /// shards, and between segments of a shard in proportion to their size. Once it is spent,
+    /// the search returns the best points found so far and the response is marked as truncated.
+    /// Allows predictable latency on huge filtered sets, at the cost of accuracy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_scored_points: Option<usize>,
+
+    /// ACORN-style search for filtered queries. Explores links of points rejected by the filter,
+    /// which keeps recall for filters of medium selectivity.
+    #[serde(default)]
+    #[validate(nested)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acorn: Option<AcornSearchParams>,
+
+    /// Params relevant to IVF index
+    /// Number of nearest lists to scan. Larger the value - more accurate the result, more time required for search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ivf_probes: Option<usize>,
+
+    /// Params relevant to DiskANN index
+    /// Number of candidates kept during graph search. Larger the value - more accurate the result, more time required for search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub disk_ann_list_size: Option<usize>,
+
+    /// Score dense vectors with plain implementations of distance functions instead of SIMD
+    /// kernels. Identical data and queries then yield bit-identical scores across runs and
+    /// machines, at the cost of slower search. Points with equal scores are ordered by ID.
+    /// Scores of quantized vectors are not affected, enable rescoring to get deterministic scores.
+    #[serde(default)]
+    pub deterministic: bool,
+}
+
+impl SearchParams {
+    /// Split the `max_scored_points` budget into a share for each of `parts` equal parts of
+    /// the data, such as shards of a collection.
+    pub fn split_scoring_budget(&mut self, parts: usize) {
+        if let Some(max_scored_points) = self.max_scored_points.as_mut() {
+            *max_scored_points = max_scored_points.div_ceil(parts.max(1));
+        }
+    }
 }
 
 /// Configuration for vectors.
@@ -563,6 +687,12 @@ pub enum Indexes {
     /// Use filterable HNSW index for approximate search. Is very fast even on a very huge collections,
     /// but require additional space to store index and additional time to build it.
     Hnsw(HnswConfig),
+    /// Use inverted file index for approximate search. Vectors are clustered, and search only scans
+    /// clusters nearest to the query. Much cheaper to build than HNSW, but slower to search.
+    Ivf(IvfConfig),
+    /// Use DiskANN (Vamana) graph index for approximate search. The graph is always kept on disk,
+    /// and only pages touched by searches are cached in RAM. Suitable for datasets much larger than RAM.
+    DiskAnn(DiskAnnConfig),
 }
 
 impl Indexes {
@@ -570,6 +700,8 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(_) => true,
+            Indexes::Ivf(_) => true,
+            Indexes::DiskAnn(_) => true,
         }
     }
 
@@ -577,10 +709,142 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(config) => config.on_disk.unwrap_or_default(),
+            Indexes::Ivf(_) => false,
+            Indexes::DiskAnn(_) => true,
+        }
+    }
+}
+
+pub const DEFAULT_IVF_PROBES: usize = 8;
+
+/// Config of IVF index
+#[derive(
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    Validate,
+    Anonymize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct IvfConfig {
+    /// Number of clusters (inverted lists) vectors are partitioned into.
+    /// If not set - square root of the number of vectors in the segment is used,
+    /// and clusters of optimized segments are reused if their number is within a factor of two.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub lists: Option<usize>,
+    /// Number of nearest lists to scan during search. Larger the value - more accurate the search, more time required.
+    /// Can be overridden per request. Default: 8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub probes: Option<usize>,
+}
+
+impl IvfConfig {
+    pub fn probes(&self) -> usize {
+        self.probes.unwrap_or(DEFAULT_IVF_PROBES)
+    }
+
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Number of probes is only used at search time, changing it doesn't require a rebuild.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        let IvfConfig { lists, probes: _ } = *self;
+        lists != other.lists
+    }
+
+    /// Whether vectors of `distance` can be clustered by IVF index
+    ///
+    /// Clusters are represented by the mean of their vectors, which is not meaningful for
+    /// bit-packed and custom metrics.
+    pub fn supports_distance(distance: Distance) -> bool {
+        match distance {
+            Distance::Cosine
+            | Distance::Euclid
+            | Distance::Dot
+            | Distance::Manhattan
+            | Distance::RawCosine => true,
+            Distance::Hamming | Distance::Jaccard | Distance::Custom => false,
         }
     }
 }
 
+pub const DEFAULT_DISK_ANN_MAX_DEGREE: usize = 64;
+
+pub const DEFAULT_DISK_ANN_LIST_SIZE: usize = 100;
+
+/// Config of DiskANN index
+///
+/// To keep RAM usage low, vectors should be stored on disk as well, with quantized vectors
+/// used for search in RAM.
+#[derive(
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    Validate,
+    Anonymize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct DiskAnnConfig {
+    /// Max number of links of each point in the graph.
+    /// Larger the value - more accurate the search, more space required. Default: 64
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 4))]
+    pub max_degree: Option<usize>,
+    /// Number of candidates considered as links of each point during construction.
+    /// Larger the value - more accurate the search, more time required to build the index. Default: 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 4))]
+    pub build_list_size: Option<usize>,
+    /// Number of candidates kept during search. Larger the value - more accurate the search, more time required.
+    /// Can be overridden per request with `disk_ann_list_size`. Default: 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub search_list_size: Option<usize>,
+}
+
+impl DiskAnnConfig {
+    pub fn max_degree(&self) -> usize {
+        self.max_degree.unwrap_or(DEFAULT_DISK_ANN_MAX_DEGREE)
+    }
+
+    pub fn build_list_size(&self) -> usize {
+        self.build_list_size.unwrap_or(DEFAULT_DISK_ANN_LIST_SIZE)
+    }
+
+    pub fn search_list_size(&self) -> usize {
+        self.search_list_size.unwrap_or(DEFAULT_DISK_ANN_LIST_SIZE)
+    }
+
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Size of the search list is only used at search time, changing it doesn't require a rebuild.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        let DiskAnnConfig {
+            max_degree,
+            build_list_size,
+            search_list_size: _,
+        } = *self;
+        max_degree != other.max_degree || build_list_size != other.build_list_size
+    }
+}
+
 /// Config of HNSW index
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Anonymize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -617,6 +881,16 @@ pub struct HnswConfig {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub copy_vectors: Option<bool>,
+    /// Use GPU to build the index, if GPU indexing is enabled on the node. Default: true.
+    /// Distances for link selection are computed on GPU, while the graph is assembled on CPU.
+    /// Set to false to always build this index on CPU.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_indexing: Option<bool>,
+    /// Seed for randomized steps of the index building. If set, the index is built
+    /// deterministically: same data and config produce the same graph on every node.
+    /// Seeded builds run on a single thread, on CPU, and don't reuse existing graphs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 impl HnswConfig {
@@ -626,8 +900,8 @@ impl HnswConfig {
     /// - this configuration does not match `other`
     /// - to effectively change the configuration, a HNSW rebuild is required
     ///
-    /// For example, a change in `max_indexing_threads` will not require rebuilding because it
-    /// doesn't affect the final index, and thus this would return false.
+    /// For example, a change in `max_indexing_threads` or `gpu_indexing` will not require
+    /// rebuilding because it doesn't affect the final index, and thus this would return false.
     pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
         let HnswConfig {
             m,
@@ -637,6 +911,8 @@ impl HnswConfig {
             payload_m,
             on_disk,
             copy_vectors,
+            gpu_indexing: _,
+            seed,
         } = self.clone();
 
         m != other.m
@@ -648,6 +924,8 @@ impl HnswConfig {
             // to flip this flag
             || on_disk != other.on_disk
             || copy_vectors != other.copy_vectors
+            // Seeded builds are deterministic, an existing graph is not reproducible with a new seed
+            || seed != other.seed
     }
 }
 
@@ -659,12 +937,19 @@ pub struct HnswGlobalConfig {
     /// To disable healing completely, set this value to `0.0`.
     #[validate(range(min = 0.0, max = 1.0))]
     pub healing_threshold: f64,
+    /// Heal HNSW graphs of optimized segments in place, once this ratio of their indexed points
+    /// is deleted since they were built or last healed. Links are rerouted around deleted points,
+    /// so search quality doesn't degrade until the segment is vacuumed.
+    /// Disabled with `0.0`, which is the default.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub deleted_healing_threshold: f64,
 }
 
 impl Default for HnswGlobalConfig {
     fn default() -> Self {
         Self {
             healing_threshold: 0.3,
+            deleted_healing_threshold: 0.0,
         }
     }
 }
@@ -690,6 +975,21 @@ pub enum ScalarType {
     Int8,
 }
 
+/// Placement of quantized vectors
+///
+/// If not set, quantized vectors are kept in RAM if `always_ram` is true or original vectors are
+/// kept in RAM, and memory-mapped from disk otherwise.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantizationPlacement {
+    /// Keep quantized vectors in RAM
+    Ram,
+    /// Memory-map quantized vectors from disk, regardless of the original vectors storage
+    Mmap,
+    /// Memory-map quantized vectors from disk and lock them in RAM, so they are never swapped out
+    MmapLocked,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct ScalarQuantizationConfig {
@@ -703,6 +1003,13 @@ pub struct ScalarQuantizationConfig {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<QuantizationPlacement>,
+    /// If true - choose the range of quantized values automatically, minimizing the quantization
+    /// error on a sample of vectors. `quantile` is ignored in this case. Default is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_quantile: Option<bool>,
 }
 
 impl ScalarQuantizationConfig {
@@ -729,6 +1036,9 @@ pub struct ProductQuantizationConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<QuantizationPlacement>,
 }
 
 impl ProductQuantizationConfig {
@@ -751,6 +1061,7 @@ pub struct ProductQuantization {
 impl Hash for ScalarQuantizationConfig {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.always_ram.hash(state);
+        self.placement.hash(state);
         self.r#type.hash(state);
     }
 }
@@ -777,6 +1088,9 @@ impl BinaryQuantizationEncoding {
 pub struct BinaryQuantizationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<QuantizationPlacement>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<BinaryQuantizationEncoding>,
@@ -794,6 +1108,25 @@ pub struct BinaryQuantization {
     pub binary: BinaryQuantizationConfig,
 }
 
+/// Residual quantization: vectors are encoded as the nearest of coarse centroids plus 8 bit
+/// quantized residual, which is more precise than scalar quantization of the same size.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ResidualQuantizationConfig {
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_ram: Option<bool>,
+    /// Placement of quantized vectors, takes precedence over `always_ram`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<QuantizationPlacement>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+pub struct ResidualQuantization {
+    #[validate(nested)]
+    pub residual: ResidualQuantizationConfig,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Anonymize, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged, rename_all = "snake_case")]
 #[anonymize(false)]
@@ -801,6 +1134,7 @@ pub enum QuantizationConfig {
     Scalar(ScalarQuantization),
     Product(ProductQuantization),
     Binary(BinaryQuantization),
+    Residual(ResidualQuantization),
 }
 
 impl QuantizationConfig {
@@ -816,6 +1150,16 @@ impl QuantizationConfig {
     pub fn supports_appendable(&self) -> bool {
         matches!(self, QuantizationConfig::Binary(_))
     }
+
+    /// Placement of quantized vectors, if set explicitly
+    pub fn placement(&self) -> Option<QuantizationPlacement> {
+        match self {
+            QuantizationConfig::Scalar(ScalarQuantization { scalar }) => scalar.placement,
+            QuantizationConfig::Product(ProductQuantization { product }) => product.placement,
+            QuantizationConfig::Binary(BinaryQuantization { binary }) => binary.placement,
+            QuantizationConfig::Residual(ResidualQuantization { residual }) => residual.placement,
+        }
+    }
 }
 
 impl Validate for QuantizationConfig {
@@ -824,6 +1168,7 @@ impl Validate for QuantizationConfig {
             QuantizationConfig::Scalar(scalar) => scalar.validate(),
             QuantizationConfig::Product(product) => product.validate(),
             QuantizationConfig::Binary(binary) => binary.validate(),
+            QuantizationConfig::Residual(residual) => residual.validate(),
         }
     }
 }
@@ -859,6 +1204,12 @@ impl From<BinaryQuantizationConfig> for QuantizationConfig {
     }
 }
 
+impl From<ResidualQuantizationConfig> for QuantizationConfig {
+    fn from(config: ResidualQuantizationConfig) -> Self {
+        QuantizationConfig::Residual(ResidualQuantization { residual: config })
+    }
+}
+
 #[derive(
     Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default, Merge, Hash,
 )]
@@ -1069,6 +1420,11 @@ pub struct StrictModeConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(range(min = 0))]
     pub max_payload_index_count: Option<usize>,
+
+    /// Reject payloads with keys, which don't have a payload index,
+    /// or with values, which don't match the type of their payload index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_payload_schema: Option<bool>,
 }
 
 impl Eq for StrictModeConfig {}
@@ -1096,6 +1452,7 @@ impl Hash for StrictModeConfig {
             multivector_config,
             sparse_config,
             max_payload_index_count,
+            enforce_payload_schema,
         } = self;
         enabled.hash(state);
         max_query_limit.hash(state);
@@ -1115,6 +1472,7 @@ impl Hash for StrictModeConfig {
         multivector_config.hash(state);
         sparse_config.hash(state);
         max_payload_index_count.hash(state);
+        enforce_payload_schema.hash(state);
     }
 }
 
@@ -1213,6 +1571,11 @@ pub struct StrictModeConfigOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(range(min = 0))]
     pub max_payload_index_count: Option<usize>,
+
+    /// Reject payloads with keys, which don't have a payload index,
+    /// or with values, which don't match the type of their payload index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_payload_schema: Option<bool>,
 }
 
 impl From<StrictModeConfig> for StrictModeConfigOutput {
@@ -1237,6 +1600,7 @@ impl From<StrictModeConfig> for StrictModeConfigOutput {
             multivector_config,
             sparse_config,
             max_payload_index_count,
+            enforce_payload_schema,
         } = config;
 
         Self {
@@ -1259,6 +1623,7 @@ impl From<StrictModeConfig> for StrictModeConfigOutput {
             multivector_config: multivector_config.map(StrictModeMultivectorConfigOutput::from),
             sparse_config: sparse_config.map(StrictModeSparseConfigOutput::from),
             max_payload_index_count,
+            enforce_payload_schema,
         }
     }
 }
@@ -1275,6 +1640,8 @@ impl Default for HnswConfig {
             on_disk: Some(false),
             payload_m: None,
             copy_vectors: None,
+            gpu_indexing: None,
+            seed: None,
         }
     }
 }
@@ -1345,10 +1712,18 @@ impl SegmentConfig {
     ///
     /// This grabs the quantization config for the given vector name if it exists.
     ///
-    /// If no quantization is configured, `None` is returned.
+    /// If no quantization is configured, or the vector can't be quantized, `None` is returned.
     pub fn quantization_config(&self, vector_name: &VectorName) -> Option<&QuantizationConfig> {
         self.vector_data
             .get(vector_name)
+            // Bit-packed Jaccard vectors are compact already, they are never quantized.
+            // Quantized scorers can't compute custom metrics or normalize vectors either.
+            .filter(|v| {
+                !matches!(
+                    v.distance,
+                    Distance::Jaccard | Distance::Custom | Distance::RawCosine
+                )
+            })
             .and_then(|v| v.quantization_config.as_ref())
     }
 
@@ -1510,6 +1885,8 @@ impl VectorDataConfig {
         let is_index_appendable = match self.index {
             Indexes::Plain {} => true,
             Indexes::Hnsw(_) => false,
+            Indexes::Ivf(_) => false,
+            Indexes::DiskAnn(_) => false,
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -2280,6 +2657,112 @@ impl<S: Into<String>> From<S> for MatchPhrase {
     }
 }
 
+/// Match of the keyword against a glob pattern.
+/// `*` matches any sequence of characters, `?` matches a single character,
+/// `\` escapes the following character.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPattern {
+    pub pattern: String,
+}
+
+impl<S: Into<String>> From<S> for MatchPattern {
+    fn from(pattern: S) -> Self {
+        MatchPattern {
+            pattern: pattern.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GlobToken {
+    Literal(char),
+    AnyChar,
+    AnySequence,
+}
+
+/// Parse glob token, which starts at byte `pos` of the `pattern`.
+/// Returns the token and the position of the next one.
+fn next_glob_token(pattern: &str, pos: usize) -> Option<(GlobToken, usize)> {
+    let mut chars = pattern[pos..].chars();
+    let c = chars.next()?;
+    let next_pos = pos + c.len_utf8();
+    let token = match c {
+        '*' => GlobToken::AnySequence,
+        '?' => GlobToken::AnyChar,
+        '\\' => match chars.next() {
+            Some(escaped) => {
+                return Some((GlobToken::Literal(escaped), next_pos + escaped.len_utf8()));
+            }
+            // Trailing backslash matches itself
+            None => GlobToken::Literal(c),
+        },
+        c => GlobToken::Literal(c),
+    };
+    Some((token, next_pos))
+}
+
+impl MatchPattern {
+    /// Characters, which any matching value has to start with
+    pub fn literal_prefix(&self) -> String {
+        let mut prefix = String::new();
+        let mut pos = 0;
+        while let Some((GlobToken::Literal(c), next_pos)) = next_glob_token(&self.pattern, pos) {
+            prefix.push(c);
+            pos = next_pos;
+        }
+        prefix
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        let pattern = self.pattern.as_str();
+        let (mut pattern_pos, mut value_pos) = (0, 0);
+        // Positions right after the last `*` and of the value it currently stands for
+        let mut backtrack: Option<(usize, usize)> = None;
+
+        loop {
+            let next_char = value[value_pos..].chars().next();
+            match next_glob_token(pattern, pattern_pos) {
+                Some((GlobToken::AnySequence, next_pos)) => {
+                    backtrack = Some((next_pos, value_pos));
+                    pattern_pos = next_pos;
+                    continue;
+                }
+                Some((GlobToken::AnyChar, next_pos)) => {
+                    if let Some(c) = next_char {
+                        pattern_pos = next_pos;
+                        value_pos += c.len_utf8();
+                        continue;
+                    }
+                }
+                Some((GlobToken::Literal(expected), next_pos)) => {
+                    if next_char == Some(expected) {
+                        pattern_pos = next_pos;
+                        value_pos += expected.len_utf8();
+                        continue;
+                    }
+                }
+                None => {
+                    if next_char.is_none() {
+                        return true;
+                    }
+                }
+            }
+
+            // Mismatch, let the last `*` consume one more character
+            let Some((star_pattern_pos, star_value_pos)) = backtrack else {
+                return false;
+            };
+            let Some(c) = value[star_value_pos..].chars().next() else {
+                return false;
+            };
+            pattern_pos = star_pattern_pos;
+            value_pos = star_value_pos + c.len_utf8();
+            backtrack = Some((pattern_pos, value_pos));
+        }
+    }
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -2302,6 +2785,7 @@ pub enum MatchInterface {
     Text(MatchText),
     TextAny(MatchTextAny),
     Phrase(MatchPhrase),
+    Pattern(MatchPattern),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -2314,6 +2798,7 @@ pub enum Match {
     Text(MatchText),
     TextAny(MatchTextAny),
     Phrase(MatchPhrase),
+    Pattern(MatchPattern),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -2333,6 +2818,12 @@ impl Match {
         })
     }
 
+    pub fn new_pattern(pattern: &str) -> Self {
+        Self::Pattern(MatchPattern {
+            pattern: pattern.into(),
+        })
+    }
+
     pub fn new_any(any: AnyVariants) -> Self {
         Self::Any(MatchAny { any })
     }
@@ -2361,6 +2852,9 @@ impl From<MatchInterface> for Match {
                 except: except.except,
             }),
             MatchInterface::Phrase(MatchPhrase { phrase }) => Self::Phrase(MatchPhrase { phrase }),
+            MatchInterface::Pattern(MatchPattern { pattern }) => {
+                Self::Pattern(MatchPattern { pattern })
+            }
         }
     }
 }
@@ -2985,6 +3479,34 @@ impl From<VectorNameBuf> for HasVectorCondition {
     }
 }
 
+/// Select points matching a custom condition, implemented as a registered WebAssembly module
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash, Validate)]
+pub struct WasmPredicate {
+    /// Name of the registered condition module
+    #[validate(custom(function = "validate_wasm_condition_name"))]
+    pub name: String,
+    /// Payload field to check. Module receives a JSON array of values of the field.
+    /// If not set - module receives the whole payload as a JSON object.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<PayloadKeyType>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash, Validate)]
+pub struct WasmCondition {
+    #[validate(nested)]
+    pub wasm: WasmPredicate,
+}
+
+fn validate_wasm_condition_name(name: &str) -> Result<(), ValidationError> {
+    if wasm_condition::get(name).is_some() {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("unknown_wasm_condition");
+        error.message = Some(format!("Condition module {name} is not registered").into());
+        Err(error)
+    }
+}
+
 /// Threshold determining when to use an `Arc` in `HasIdCondition` if the condition includes many points.
 /// Since we're cloning filters quite a lot, using an Arc for larger conditions reduces risk of memory leaks
 /// and potentially improves performance in some places.
@@ -3066,6 +3588,8 @@ pub enum Condition {
     HasVector(HasVectorCondition),
     /// Nested filters
     Nested(NestedCondition),
+    /// Custom condition, implemented as a WebAssembly module
+    Wasm(WasmCondition),
     /// Nested filter
     Filter(Filter),
 
@@ -3095,6 +3619,9 @@ impl Hash for Condition {
             Condition::Nested(nested_condition) => {
                 nested_condition.hash(state);
             }
+            Condition::Wasm(wasm_condition) => {
+                wasm_condition.hash(state);
+            }
             Condition::Filter(filter) => {
                 filter.hash(state);
             }
@@ -3117,6 +3644,7 @@ impl PartialEq for Condition {
             (Self::HasId(this), Self::HasId(other)) => this == other,
             (Self::HasVector(this), Self::HasVector(other)) => this == other,
             (Self::Nested(this), Self::Nested(other)) => this == other,
+            (Self::Wasm(this), Self::Wasm(other)) => this == other,
             (Self::Filter(this), Self::Filter(other)) => this == other,
             (Self::CustomIdChecker(_), Self::CustomIdChecker(_)) => false,
             _ => false,
@@ -3140,6 +3668,7 @@ impl Condition {
             Condition::IsEmpty(_)
             | Condition::IsNull(_)
             | Condition::HasVector(_)
+            | Condition::Wasm(_)
             | Condition::CustomIdChecker(_) => 0,
         }
     }
@@ -3155,7 +3684,8 @@ impl Condition {
             | Condition::IsNull(_)
             | Condition::CustomIdChecker(_)
             | Condition::HasId(_)
-            | Condition::HasVector(_) => 1,
+            | Condition::HasVector(_)
+            | Condition::Wasm(_) => 1,
         }
     }
 
@@ -3165,6 +3695,7 @@ impl Condition {
             Condition::IsEmpty(is_empty_condition) => Some(is_empty_condition.is_empty.key.clone()),
             Condition::IsNull(is_null_condition) => Some(is_null_condition.is_null.key.clone()),
             Condition::Nested(nested_condition) => Some(nested_condition.array_key()),
+            Condition::Wasm(wasm_condition) => wasm_condition.wasm.key.clone(),
             Condition::Filter(filter) => filter.iter_conditions().find_map(|c| c.targeted_key()),
             Condition::HasId(_) | Condition::HasVector(_) | Condition::CustomIdChecker(_) => None,
         }
@@ -3181,6 +3712,7 @@ impl Validate for Condition {
             | Condition::HasVector(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
+            Condition::Wasm(wasm_condition) => wasm_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
             Condition::CustomIdChecker(_) => Ok(()),
         }
@@ -3776,6 +4308,30 @@ mod tests {
         assert_eq!(expected, transcoded);
     }
 
+    #[test]
+    fn test_hnsw_config_mismatch_requires_rebuild() {
+        let config = HnswConfig::default();
+
+        let more_threads = HnswConfig {
+            max_indexing_threads: 4,
+            ..config.clone()
+        };
+        assert!(!config.mismatch_requires_rebuild(&more_threads));
+
+        let seeded = HnswConfig {
+            seed: Some(42),
+            ..config.clone()
+        };
+        assert!(config.mismatch_requires_rebuild(&seeded));
+
+        let reseeded = HnswConfig {
+            seed: Some(43),
+            ..seeded.clone()
+        };
+        assert!(seeded.mismatch_requires_rebuild(&reseeded));
+        assert!(!seeded.mismatch_requires_rebuild(&seeded.clone()));
+    }
+
     #[test]
     fn test_timezone_ordering() {
         let datetimes = [
@@ -4060,6 +4616,39 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case::exact("neuro", "neuro", true)]
+    #[case::prefix("neuro*", "neuroscience", true)]
+    #[case::prefix_empty_rest("neuro*", "neuro", true)]
+    #[case::suffix("*science", "neuroscience", true)]
+    #[case::infix("n*o*e", "neuroscience", true)]
+    #[case::single_char("ne?ro", "neuro", true)]
+    #[case::single_char_unicode("caf?", "café", true)]
+    #[case::escaped_star(r"a\*b", "a*b", true)]
+    #[case::escaped_star_mismatch(r"a\*b", "axb", false)]
+    #[case::too_short("neuro?", "neuro", false)]
+    #[case::wrong_prefix("neuro*", "euro", false)]
+    #[case::backtracking("*ab*abc", "abxabababc", true)]
+    fn test_match_pattern(#[case] pattern: &str, #[case] value: &str, #[case] expected: bool) {
+        assert_eq!(MatchPattern::from(pattern).is_match(value), expected);
+    }
+
+    #[test]
+    fn test_match_pattern_literal_prefix() {
+        assert_eq!(MatchPattern::from("neuro*").literal_prefix(), "neuro");
+        assert_eq!(MatchPattern::from(r"a\?b?c").literal_prefix(), "a?b");
+        assert_eq!(MatchPattern::from("*neuro").literal_prefix(), "");
+
+        let filter: Filter = serde_json::from_str(
+            r#"{ "must": [{ "key": "title", "match": { "pattern": "neuro*" } }] }"#,
+        )
+        .unwrap();
+        let Some(Condition::Field(c)) = filter.must.unwrap().first().cloned() else {
+            panic!("Condition::Field expected")
+        };
+        assert_eq!(c.r#match, Some(Match::new_pattern("neuro*")));
+    }
+
     #[test]
     fn test_parse_match_any_mixed_types() {
         let query = r#"
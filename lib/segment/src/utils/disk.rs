@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use sysinfo::Disks;
+
+/// Kind of the storage device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskKind {
+    Ssd,
+    Hdd,
+}
+
+/// Detect the kind of the disk, holding the given path
+///
+/// The disk is selected by the longest mount point, containing the path.
+/// Returns `None` if the disk or its kind can't be detected, e.g. on network storage.
+pub fn disk_kind(path: &Path) -> Option<DiskKind> {
+    let path = path.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+
+    let disk = disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())?;
+
+    match disk.kind() {
+        sysinfo::DiskKind::SSD => Some(DiskKind::Ssd),
+        sysinfo::DiskKind::HDD => Some(DiskKind::Hdd),
+        sysinfo::DiskKind::Unknown(_) => None,
+    }
+}
@@ -1,3 +1,4 @@
+pub mod disk;
 pub mod fmt;
 pub mod fs;
 pub mod maybe_arc;
@@ -6,6 +6,48 @@ use crate::common::vector_utils::{TrySetCapacity, TrySetCapacityExact};
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
 use crate::vector_storage::common::CHUNK_SIZE;
 
+/// Memory layout of [`ChunkedVectors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkedVectorsConfig {
+    /// Size of each chunk in bytes.
+    pub chunk_size_bytes: usize,
+    /// Alignment of each chunk in bytes, must be a power of two.
+    /// If not set, the natural alignment of the element type is used.
+    ///
+    /// Vectors are aligned as well if their size is a multiple of the alignment.
+    pub alignment_bytes: Option<usize>,
+    /// Advise the OS to back chunks with transparent huge pages. Only supported on Linux.
+    pub huge_pages: bool,
+}
+
+impl ChunkedVectorsConfig {
+    pub const DEFAULT: Self = Self {
+        chunk_size_bytes: CHUNK_SIZE,
+        alignment_bytes: None,
+        huge_pages: false,
+    };
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.chunk_size_bytes == 0 {
+            return Err("Chunk size must be positive".to_string());
+        }
+        if let Some(alignment) = self.alignment_bytes
+            && !alignment.is_power_of_two()
+        {
+            return Err(format!(
+                "Chunk alignment must be a power of two, got {alignment}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ChunkedVectorsConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 #[derive(Debug)]
 pub struct ChunkedVectors<T> {
     /// Vector's dimension.
@@ -16,23 +58,88 @@ pub struct ChunkedVectors<T> {
     len: usize,
     /// Maximum number of vectors in each chunk.
     chunk_capacity: usize,
+    /// Number of extra elements allocated in each chunk, to be able to shift its data
+    /// to an aligned address. Zero if no extra alignment is required.
+    ///
+    /// Aligned chunks are allocated in full on first use and never reallocated,
+    /// so the shift of the data stays the same for the whole life of the chunk.
+    chunk_padding: usize,
+    /// Alignment of the chunk data in bytes.
+    alignment: usize,
+    /// Advise the OS to back chunks with huge pages.
+    huge_pages: bool,
     chunks: Vec<Vec<T>>,
 }
 
 impl<T: Copy + Clone + Default> ChunkedVectors<T> {
+    /// Create storage with the default [`ChunkedVectorsConfig`].
     pub fn new(dim: usize) -> Self {
+        Self::with_config(dim, ChunkedVectorsConfig::DEFAULT)
+    }
+
+    pub fn with_config(dim: usize, config: ChunkedVectorsConfig) -> Self {
         assert_ne!(dim, 0, "The vector's dimension cannot be 0");
         let vector_size = dim * mem::size_of::<T>();
-        let chunk_capacity = CHUNK_SIZE / vector_size;
+        let chunk_capacity = config.chunk_size_bytes / vector_size;
         assert_ne!(chunk_capacity, 0, "The vector's size is too big");
+
+        let alignment = config
+            .alignment_bytes
+            .map_or(mem::align_of::<T>(), |alignment| {
+                alignment.max(mem::align_of::<T>())
+            });
+        let chunk_padding = if alignment > mem::align_of::<T>() {
+            alignment.div_ceil(mem::size_of::<T>())
+        } else {
+            0
+        };
+
         Self {
             dim,
             len: 0,
             chunk_capacity,
+            chunk_padding,
+            alignment,
+            huge_pages: config.huge_pages,
             chunks: Vec::new(),
         }
     }
 
+    /// Maximum size of data in bytes, which can be stored in a single chunk.
+    pub fn chunk_size_bytes(&self) -> usize {
+        self.chunk_capacity * self.dim * mem::size_of::<T>()
+    }
+
+    /// Offset of the first element of the chunk data, which is aligned as configured.
+    fn data_offset(&self, chunk: &[T]) -> usize {
+        if self.chunk_padding == 0 {
+            0
+        } else {
+            chunk.as_ptr().align_offset(self.alignment)
+        }
+    }
+
+    /// Full length of a chunk, including the padding.
+    fn full_chunk_len(&self) -> usize {
+        self.chunk_capacity * self.dim + self.chunk_padding
+    }
+
+    fn reserve_chunk(
+        chunk: &mut Vec<T>,
+        capacity: usize,
+        huge_pages: bool,
+    ) -> Result<(), TryReserveError> {
+        let reallocated = chunk.capacity() < capacity;
+        chunk.try_set_capacity_exact(capacity)?;
+        if huge_pages && reallocated {
+            memory::madvise::advise_huge_pages(
+                chunk.as_ptr().cast(),
+                chunk.capacity() * mem::size_of::<T>(),
+            );
+        }
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         self.len
     }
@@ -46,31 +153,35 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
     }
 
     pub fn get_opt(&self, key: VectorOffsetType) -> Option<&[T]> {
-        if self.chunks.is_empty() {
-            return None;
-        }
-        self.chunks
-            .get(key / self.chunk_capacity)
-            .and_then(|chunk_data| {
-                let idx = (key % self.chunk_capacity) * self.dim;
-                let range = idx..idx + self.dim;
-                chunk_data.get(range)
-            })
+        self.get_many(key, 1)
     }
 
     pub fn get_many(&self, key: VectorOffsetType, count: usize) -> Option<&[T]> {
-        if self.chunks.is_empty() {
+        // Aligned chunks are allocated in full, so bounds of the chunk are not enough
+        if self.chunks.is_empty() || key + count > self.len {
             return None;
         }
         self.chunks
             .get(key / self.chunk_capacity)
             .and_then(|chunk_data| {
-                let idx = (key % self.chunk_capacity) * self.dim;
+                let idx = self.data_offset(chunk_data) + (key % self.chunk_capacity) * self.dim;
                 let range = idx..idx + count * self.dim;
                 chunk_data.get(range)
             })
     }
 
+    /// Iterate over all vectors in order of their keys, one chunk at a time
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> + Clone {
+        (0..self.len)
+            .step_by(self.chunk_capacity)
+            .flat_map(move |start| {
+                let count = self.chunk_capacity.min(self.len - start);
+                self.get_many(start, count)
+                    .expect("vector not found")
+                    .chunks_exact(self.dim)
+            })
+    }
+
     pub fn push(&mut self, vector: &[T]) -> Result<VectorOffsetType, TryReserveError> {
         let new_id = self.len;
         self.insert(new_id, vector)?;
@@ -103,7 +214,8 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
             "Index out of bounds"
         );
 
-        let desired_capacity = self.chunk_capacity * self.dim;
+        let desired_capacity = self.full_chunk_len();
+        let huge_pages = self.huge_pages;
         let new_len = max(self.len, key + vectors_count);
         let chunks_len = new_len.div_ceil(self.chunk_capacity);
 
@@ -111,7 +223,7 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
             // All chunks except the last one should be fully allocated.
             // If we are going to add new chunks, resize last one which may be partially allocated.
             if let Some(last_chunk) = self.chunks.last_mut() {
-                last_chunk.try_set_capacity_exact(desired_capacity)?;
+                Self::reserve_chunk(last_chunk, desired_capacity, huge_pages)?;
                 last_chunk.resize_with(desired_capacity, T::default);
             }
 
@@ -123,7 +235,7 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
             // All skipped chunks should be fully allocated.
             for _ in 0..skipped_chunks {
                 let mut chunk = Vec::new();
-                chunk.try_set_capacity_exact(desired_capacity)?;
+                Self::reserve_chunk(&mut chunk, desired_capacity, huge_pages)?;
                 chunk.resize_with(desired_capacity, T::default);
                 self.chunks.push(chunk);
             }
@@ -134,6 +246,7 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
         }
 
         let chunk_idx = key / self.chunk_capacity;
+        let is_aligned = self.chunk_padding != 0;
         let chunk_data = &mut self.chunks[chunk_idx];
         let idx = (key % self.chunk_capacity) * self.dim;
 
@@ -147,15 +260,23 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
         // <https://doc.rust-lang.org/std/vec/struct.Vec.html#capacity-and-reallocation>).
         // All other chunks allocate their capacity in full on first use to prevent expensive
         // reallocations when their data grows.
+        // Aligned chunks, including the first one, are always allocated and filled in full,
+        // as reallocation would shift their data.
         if chunk_data.len() < idx + vectors.len() {
-            // If the chunk is not the first one, allocate it fully on first use
-            if chunk_idx != 0 {
-                chunk_data.try_set_capacity_exact(desired_capacity)?;
+            if is_aligned {
+                Self::reserve_chunk(chunk_data, desired_capacity, huge_pages)?;
+                chunk_data.resize_with(desired_capacity, T::default);
+            } else {
+                // If the chunk is not the first one, allocate it fully on first use
+                if chunk_idx != 0 {
+                    Self::reserve_chunk(chunk_data, desired_capacity, huge_pages)?;
+                }
+                chunk_data.resize_with(idx + vectors.len(), T::default);
             }
-            chunk_data.resize_with(idx + vectors.len(), T::default);
         }
 
-        let data = &mut chunk_data[idx..idx + vectors.len()];
+        let idx = self.data_offset(&self.chunks[chunk_idx]) + idx;
+        let data = &mut self.chunks[chunk_idx][idx..idx + vectors.len()];
         data.copy_from_slice(vectors);
 
         // Update `self.len` only after the vector is successfully inserted.
@@ -166,20 +287,22 @@ impl<T: Copy + Clone + Default> ChunkedVectors<T> {
     }
 }
 
-impl<T: Clone> TrySetCapacityExact for ChunkedVectors<T> {
+impl<T: Copy + Clone + Default> TrySetCapacityExact for ChunkedVectors<T> {
     fn try_set_capacity_exact(&mut self, capacity: usize) -> Result<(), TryReserveError> {
         let num_chunks = capacity.div_ceil(self.chunk_capacity);
         let last_chunk_idx = capacity / self.chunk_capacity;
+        let full_chunk_len = self.full_chunk_len();
+        let huge_pages = self.huge_pages;
         self.chunks.try_set_capacity_exact(num_chunks)?;
         self.chunks.resize_with(num_chunks, Vec::new);
         for chunk_idx in 0..num_chunks {
-            if chunk_idx == last_chunk_idx {
-                let desired_capacity = (capacity % self.chunk_capacity) * self.dim;
-                self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
+            // Aligned chunks are always allocated in full
+            let desired_capacity = if chunk_idx == last_chunk_idx && self.chunk_padding == 0 {
+                (capacity % self.chunk_capacity) * self.dim
             } else {
-                let desired_capacity = self.chunk_capacity * self.dim;
-                self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
-            }
+                full_chunk_len
+            };
+            Self::reserve_chunk(&mut self.chunks[chunk_idx], desired_capacity, huge_pages)?;
         }
         Ok(())
     }
@@ -209,4 +332,37 @@ mod tests {
         // check if middle chunk is fully allocated
         assert_eq!(vectors.get(5_000_000), &[0, 0, 0]);
     }
+
+    #[test]
+    fn test_chunked_vectors_aligned() {
+        let config = ChunkedVectorsConfig {
+            chunk_size_bytes: 64 * 100,
+            alignment_bytes: Some(64),
+            huge_pages: false,
+        };
+        // 16 * 4 bytes, each vector is aligned
+        let dim = 16;
+        let mut vectors = ChunkedVectors::<f32>::with_config(dim, config);
+        assert_eq!(vectors.chunk_size_bytes(), 64 * 100);
+
+        let total = 350;
+        for i in 0..total {
+            vectors.push(&vec![i as f32; dim]).unwrap();
+        }
+        assert_eq!(vectors.chunks.len(), 4);
+
+        for i in 0..total {
+            let vector = vectors.get(i);
+            assert_eq!(vector, vec![i as f32; dim].as_slice());
+            assert_eq!(vector.as_ptr().addr() % 64, 0);
+        }
+        assert_eq!(vectors.get_opt(total), None);
+        assert_eq!(vectors.get_many(total - 1, 2), None);
+
+        // Iterates across chunk boundaries, skipping the padding
+        assert_eq!(vectors.iter().count(), total);
+        for (i, vector) in vectors.iter().enumerate() {
+            assert_eq!(vector, vec![i as f32; dim].as_slice());
+        }
+    }
 }
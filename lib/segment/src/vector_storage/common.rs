@@ -3,6 +3,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(feature = "rocksdb")]
 use serde::{Deserialize, Serialize};
 
+use crate::common::operation_error::{OperationError, OperationResult};
+
 static ASYNC_SCORER: AtomicBool = AtomicBool::new(false);
 
 pub fn set_async_scorer(async_scorer: bool) {
@@ -21,6 +23,20 @@ pub struct StoredRecord<T> {
     pub vector: T,
 }
 
+/// Check that a vector matches the dimensionality of the storage
+///
+/// Storages must not rely on upper-level validation, a mismatch is reported as an error
+/// instead of corrupting the storage or panicking.
+pub fn check_vector_dim(expected_dim: usize, received_dim: usize) -> OperationResult<()> {
+    if expected_dim != received_dim {
+        return Err(OperationError::WrongVectorDimension {
+            expected_dim,
+            received_dim,
+        });
+    }
+    Ok(())
+}
+
 /// Minimal number of bytes we read from disk in one go
 /// WARN: this might be system dependent, so we assume 4Kb, which might be wrong
 /// ToDo: read this from system
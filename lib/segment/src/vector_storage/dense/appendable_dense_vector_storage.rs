@@ -3,6 +3,7 @@ use std::fs::create_dir_all;
 use std::mem::MaybeUninit;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
@@ -18,12 +19,15 @@ use crate::common::operation_error::{OperationResult, check_process_stopped};
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::chunked_vector_storage::{ChunkedVectorStorage, VectorOffsetType};
-use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::common::{VECTOR_READ_BATCH_SIZE, check_vector_dim};
 use crate::vector_storage::in_ram_persisted_vectors::InRamPersistedVectors;
-use crate::vector_storage::{AccessPattern, DenseVectorStorage, VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{
+    AccessPattern, DenseVectorStorage, Sequential, VectorStorage, VectorStorageEnum,
+};
 
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
@@ -37,6 +41,7 @@ pub struct AppendableMmapDenseVectorStorage<T: PrimitiveVectorElement, S: Chunke
     /// depend on its length.
     deleted: BitvecFlags,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     deleted_count: usize,
     _phantom: std::marker::PhantomData<T>,
 }
@@ -104,6 +109,19 @@ impl<T: PrimitiveVectorElement, S: ChunkedVectorStorage<T>> DenseVectorStorage<T
         .0;
         self.vectors.get_batch(vector_offsets, vectors)
     }
+
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &[T])> + Clone + Send
+    where
+        Self: Sync,
+    {
+        (0..self.vectors.len() as PointOffsetType).map(|key| {
+            let vector = self
+                .vectors
+                .get::<Sequential>(key as VectorOffsetType)
+                .expect("mmap vector not found");
+            (key, vector)
+        })
+    }
 }
 
 impl<T: PrimitiveVectorElement, S: ChunkedVectorStorage<T>> VectorStorage
@@ -113,6 +131,14 @@ impl<T: PrimitiveVectorElement, S: ChunkedVectorStorage<T>> VectorStorage
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         T::datatype()
     }
@@ -145,6 +171,7 @@ impl<T: PrimitiveVectorElement, S: ChunkedVectorStorage<T>> VectorStorage
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let vector: &[VectorElementType] = vector.try_into()?;
+        check_vector_dim(self.vectors.dim(), vector.len())?;
         let vector = T::slice_from_float_cow(Cow::from(vector));
         self.vectors
             .insert(key as VectorOffsetType, vector.as_ref(), hw_counter)?;
@@ -163,6 +190,7 @@ impl<T: PrimitiveVectorElement, S: ChunkedVectorStorage<T>> VectorStorage
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            check_vector_dim(self.vectors.dim(), other_vector.len())?;
             let new_id = self.vectors.push(other_vector.as_ref(), &disposed_hw)?;
             self.set_deleted(new_id as PointOffsetType, other_deleted);
         }
@@ -266,6 +294,7 @@ pub fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
         vectors,
         deleted,
         distance,
+        custom_metric: None,
         deleted_count,
         _phantom: Default::default(),
     })
@@ -345,6 +374,7 @@ pub fn open_appendable_in_ram_vector_storage_impl<T: PrimitiveVectorElement>(
         vectors,
         deleted,
         distance,
+        custom_metric: None,
         deleted_count,
         _phantom: Default::default(),
     })
@@ -17,10 +17,13 @@ use crate::common::operation_error::{OperationError, OperationResult, check_proc
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::dense::mmap_dense_vectors::MmapDenseVectors;
-use crate::vector_storage::{AccessPattern, DenseVectorStorage, VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{
+    AccessPattern, DenseVectorStorage, Sequential, VectorStorage, VectorStorageEnum,
+};
 
 const VECTORS_PATH: &str = "matrix.dat";
 const DELETED_PATH: &str = "deleted.dat";
@@ -37,6 +40,7 @@ pub struct MemmapDenseVectorStorage<T: PrimitiveVectorElement> {
     deleted_path: PathBuf,
     mmap_store: Option<MmapDenseVectors<T>>,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
 }
 
 impl<T: PrimitiveVectorElement> MemmapDenseVectorStorage<T> {
@@ -123,6 +127,7 @@ fn open_memmap_vector_storage_with_async_io_impl<T: PrimitiveVectorElement>(
         deleted_path,
         mmap_store: Some(mmap_store),
         distance,
+        custom_metric: None,
     }))
 }
 
@@ -160,6 +165,14 @@ impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for MemmapDenseVectorStora
         let mmap_store = self.mmap_store.as_ref().unwrap();
         mmap_store.get_vectors(keys, vectors)
     }
+
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &[T])> + Clone + Send
+    where
+        Self: Sync,
+    {
+        (0..self.total_vector_count() as PointOffsetType)
+            .map(|key| (key, self.get_dense::<Sequential>(key)))
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
@@ -167,6 +180,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         T::datatype()
     }
@@ -733,6 +754,8 @@ mod tests {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into();
 
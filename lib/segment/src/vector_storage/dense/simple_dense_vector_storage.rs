@@ -18,23 +18,27 @@ use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
-use crate::vector_storage::chunked_vectors::ChunkedVectors;
-use crate::vector_storage::common::StoredRecord;
+use crate::vector_storage::chunked_vectors::{ChunkedVectors, ChunkedVectorsConfig};
+use crate::vector_storage::common::{StoredRecord, check_vector_dim};
 use crate::vector_storage::{AccessPattern, DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 type StoredDenseVector<T> = StoredRecord<Vec<T>>;
 
+/// Number of records written to RocksDB with a single write batch in `update_from`
+const UPDATE_FROM_WRITE_BATCH_SIZE: usize = 1024;
+
 /// In-memory vector storage with on-update persistence using `store`
 #[derive(Debug)]
 pub struct SimpleDenseVectorStorage<T: PrimitiveVectorElement> {
     dim: usize,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     vectors: ChunkedVectors<T>,
     db_wrapper: DatabaseColumnWrapper,
-    update_buffer: StoredDenseVector<T>,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
     /// Current number of deleted vectors.
@@ -46,9 +50,10 @@ fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     database_column_name: &str,
     dim: usize,
     distance: Distance,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<SimpleDenseVectorStorage<T>> {
-    let mut vectors = ChunkedVectors::new(dim);
+    let mut vectors = ChunkedVectors::with_config(dim, chunked_vectors_config);
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
 
     let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
@@ -78,12 +83,9 @@ fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     Ok(SimpleDenseVectorStorage {
         dim,
         distance,
+        custom_metric: None,
         vectors,
         db_wrapper,
-        update_buffer: StoredRecord {
-            deleted: false,
-            vector: vec![T::default(); dim],
-        },
         deleted,
         deleted_count,
     })
@@ -95,6 +97,7 @@ pub fn open_simple_dense_vector_storage(
     database_column_name: &str,
     dim: usize,
     distance: Distance,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     match storage_element_type {
@@ -103,6 +106,7 @@ pub fn open_simple_dense_vector_storage(
             database_column_name,
             dim,
             distance,
+            chunked_vectors_config,
             stopped,
         ),
         VectorStorageDatatype::Float16 => open_simple_dense_half_vector_storage(
@@ -110,6 +114,7 @@ pub fn open_simple_dense_vector_storage(
             database_column_name,
             dim,
             distance,
+            chunked_vectors_config,
             stopped,
         ),
         VectorStorageDatatype::Uint8 => open_simple_dense_byte_vector_storage(
@@ -117,6 +122,7 @@ pub fn open_simple_dense_vector_storage(
             database_column_name,
             dim,
             distance,
+            chunked_vectors_config,
             stopped,
         ),
     }
@@ -127,6 +133,7 @@ pub fn open_simple_dense_full_vector_storage(
     database_column_name: &str,
     dim: usize,
     distance: Distance,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_dense_vector_storage_impl::<VectorElementType>(
@@ -134,6 +141,7 @@ pub fn open_simple_dense_full_vector_storage(
         database_column_name,
         dim,
         distance,
+        chunked_vectors_config,
         stopped,
     )?;
 
@@ -145,6 +153,7 @@ pub fn open_simple_dense_byte_vector_storage(
     database_column_name: &str,
     dim: usize,
     distance: Distance,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_dense_vector_storage_impl(
@@ -152,6 +161,7 @@ pub fn open_simple_dense_byte_vector_storage(
         database_column_name,
         dim,
         distance,
+        chunked_vectors_config,
         stopped,
     )?;
 
@@ -163,6 +173,7 @@ pub fn open_simple_dense_half_vector_storage(
     database_column_name: &str,
     dim: usize,
     distance: Distance,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_dense_vector_storage_impl(
@@ -170,6 +181,7 @@ pub fn open_simple_dense_half_vector_storage(
         database_column_name,
         dim,
         distance,
+        chunked_vectors_config,
         stopped,
     )?;
 
@@ -194,6 +206,19 @@ impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
         was_deleted
     }
 
+    /// Serialize the record directly from the vector slice
+    ///
+    /// Borrowed slice is encoded the same way as `StoredDenseVector`, so it can be read back as such.
+    fn encode_stored(key: PointOffsetType, deleted: bool, vector: &[T]) -> (Vec<u8>, Vec<u8>) {
+        let record = StoredRecord { deleted, vector };
+        let key_enc = bincode::serialize(&key).unwrap();
+        let record_enc = bincode::serialize(&record).unwrap();
+        (key_enc, record_enc)
+    }
+
+    /// Persist record for given key
+    ///
+    /// If `vector` is not given, the current in-memory vector is stored along with the flag.
     fn update_stored(
         &mut self,
         key: PointOffsetType,
@@ -201,15 +226,11 @@ impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
         vector: Option<&[T]>,
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
-        // Write vector state to buffer record
-        let record = &mut self.update_buffer;
-        record.deleted = deleted;
-        if let Some(vector) = vector {
-            record.vector.copy_from_slice(vector);
-        }
-
-        let key_enc = bincode::serialize(&key).unwrap();
-        let record_enc = bincode::serialize(&record).unwrap();
+        // Points deleted before their vector is inserted have nothing to persist
+        let Some(vector) = vector.or_else(|| self.vectors.get_opt(key as VectorOffsetType)) else {
+            return Ok(());
+        };
+        let (key_enc, record_enc) = Self::encode_stored(key, deleted, vector);
 
         hw_counter
             .vector_io_write_counter()
@@ -236,6 +257,13 @@ impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for SimpleDenseVectorStora
     fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key as VectorOffsetType)
     }
+
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &[T])> + Clone + Send
+    where
+        Self: Sync,
+    {
+        (0..).zip(self.vectors.iter())
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
@@ -243,6 +271,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         T::datatype()
     }
@@ -274,6 +310,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let vector: &[VectorElementType] = vector.try_into()?;
+        check_vector_dim(self.dim, vector.len())?;
         let vector = T::slice_from_float_cow(Cow::from(vector));
         self.vectors
             .insert(key as VectorOffsetType, vector.as_ref())?;
@@ -288,20 +325,25 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>> {
         let start_index = self.vectors.len() as PointOffsetType;
-        let dispose_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+        // This function is only used for internal operations, hardware is not measured.
+        let mut write_batch = Vec::with_capacity(UPDATE_FROM_WRITE_BATCH_SIZE);
         for (other_vector, other_deleted) in other_vectors {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            check_vector_dim(self.dim, other_vector.len())?;
             let new_id = self.vectors.push(other_vector.as_ref())? as PointOffsetType;
             self.set_deleted(new_id, other_deleted);
-            self.update_stored(
+            write_batch.push(Self::encode_stored(
                 new_id,
                 other_deleted,
-                Some(other_vector.as_ref()),
-                &dispose_hw,
-            )?;
+                other_vector.as_ref(),
+            ));
+            if write_batch.len() >= UPDATE_FROM_WRITE_BATCH_SIZE {
+                self.db_wrapper.put_batch(write_batch.drain(..))?;
+            }
         }
+        self.db_wrapper.put_batch(write_batch)?;
         let end_index = self.vectors.len() as PointOffsetType;
         Ok(start_index..end_index)
     }
@@ -369,6 +411,7 @@ mod tests {
             DB_VECTOR_CF,
             DIM,
             Distance::Dot,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
@@ -426,4 +469,115 @@ mod tests {
             );
         }
     }
+
+    /// Deleted flags are persisted within vector records, vectors must stay intact after reopening.
+    #[test]
+    fn test_persist_deleted_flags() {
+        const DIM: usize = 4;
+
+        let db_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::disposable();
+        let points: [[VectorElementType; DIM]; 3] = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ];
+
+        {
+            let db = open_db(db_dir.path(), &[DB_VECTOR_CF]).unwrap();
+            let mut storage = open_simple_dense_full_vector_storage(
+                db,
+                DB_VECTOR_CF,
+                DIM,
+                Distance::Dot,
+                ChunkedVectorsConfig::default(),
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+            for (internal_id, point) in points.iter().enumerate() {
+                storage
+                    .insert_vector(
+                        internal_id as PointOffsetType,
+                        VectorRef::from(point.as_slice()),
+                        &hw_counter,
+                    )
+                    .unwrap();
+            }
+            storage.delete_vector(0).unwrap();
+            storage.delete_vector(1).unwrap();
+            // Deleting a point without a stored vector persists nothing
+            storage
+                .delete_vector(points.len() as PointOffsetType)
+                .unwrap();
+            // Re-inserting a vector clears its deleted flag
+            storage
+                .insert_vector(1, VectorRef::from(points[1].as_slice()), &hw_counter)
+                .unwrap();
+            storage.flusher()().unwrap();
+        }
+
+        let db = open_db(db_dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = open_simple_dense_full_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            DIM,
+            Distance::Dot,
+            ChunkedVectorsConfig::default(),
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+
+        assert_eq!(storage.total_vector_count(), points.len());
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert!(storage.is_deleted_vector(0));
+        assert!(!storage.is_deleted_vector(1));
+        assert!(!storage.is_deleted_vector(2));
+        for (internal_id, point) in points.iter().enumerate() {
+            assert_eq!(
+                storage.get_vector::<Sequential>(internal_id as PointOffsetType),
+                CowVector::from(point.to_vec()),
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_stored_from_slice() {
+        let vector = vec![1.0, -2.5, 3.25];
+        let (key_enc, record_enc) =
+            SimpleDenseVectorStorage::<VectorElementType>::encode_stored(7, true, &vector);
+
+        let key: PointOffsetType = bincode::deserialize(&key_enc).unwrap();
+        let record: StoredDenseVector<VectorElementType> =
+            bincode::deserialize(&record_enc).unwrap();
+        assert_eq!(key, 7);
+        assert!(record.deleted);
+        assert_eq!(record.vector, vector);
+    }
+
+    #[test]
+    fn test_insert_wrong_dimension() {
+        let db_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(db_dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let mut storage = open_simple_dense_full_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            4,
+            Distance::Dot,
+            ChunkedVectorsConfig::default(),
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+        let result =
+            storage.insert_vector(0, VectorRef::from([1.0, 2.0, 3.0].as_slice()), &hw_counter);
+        assert_eq!(
+            result,
+            Err(OperationError::WrongVectorDimension {
+                expected_dim: 4,
+                received_dim: 3,
+            }),
+        );
+        assert_eq!(storage.total_vector_count(), 0);
+    }
 }
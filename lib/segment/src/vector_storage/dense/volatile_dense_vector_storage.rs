@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::ops::Range;
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::{BitSlice, BitVec};
@@ -12,10 +13,12 @@ use crate::common::operation_error::{OperationResult, check_process_stopped};
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::common::check_vector_dim;
 use crate::vector_storage::{AccessPattern, DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 /// In-memory vector storage that is volatile
@@ -25,6 +28,7 @@ use crate::vector_storage::{AccessPattern, DenseVectorStorage, VectorStorage, Ve
 pub struct VolatileDenseVectorStorage<T: PrimitiveVectorElement> {
     dim: usize,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     vectors: ChunkedVectors<T>,
     /// BitVec for deleted flags. Grows dynamically upto last set flag.
     deleted: BitVec,
@@ -51,6 +55,7 @@ impl<T: PrimitiveVectorElement> VolatileDenseVectorStorage<T> {
         Self {
             dim,
             distance,
+            custom_metric: None,
             vectors: ChunkedVectors::new(dim),
             deleted: BitVec::new(),
             deleted_count: 0,
@@ -83,6 +88,13 @@ impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for VolatileDenseVectorSto
     fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> &[T] {
         self.vectors.get(key as VectorOffsetType)
     }
+
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &[T])> + Clone + Send
+    where
+        Self: Sync,
+    {
+        (0..).zip(self.vectors.iter())
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T> {
@@ -90,6 +102,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T>
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         T::datatype()
     }
@@ -121,6 +141,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T>
         _hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let vector: &[VectorElementType] = vector.try_into()?;
+        check_vector_dim(self.dim, vector.len())?;
         let vector = T::slice_from_float_cow(Cow::from(vector));
         self.vectors
             .insert(key as VectorOffsetType, vector.as_ref())?;
@@ -138,6 +159,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T>
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            check_vector_dim(self.dim, other_vector.len())?;
             let new_id = self.vectors.push(other_vector.as_ref())? as PointOffsetType;
             self.set_deleted(new_id, other_deleted);
         }
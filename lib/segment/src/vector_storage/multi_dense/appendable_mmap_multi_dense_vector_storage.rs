@@ -1,6 +1,7 @@
 use std::fs::create_dir_all;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
@@ -15,9 +16,11 @@ use crate::common::operation_error::{OperationError, OperationResult, check_proc
 use crate::data_types::named_vectors::{CowMultiVector, CowVector};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{TypedMultiDenseVectorRef, VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::chunked_vector_storage::{ChunkedVectorStorage, VectorOffsetType};
+use crate::vector_storage::common::check_vector_dim;
 use crate::vector_storage::in_ram_persisted_vectors::InRamPersistedVectors;
 use crate::vector_storage::{
     AccessPattern, MultiVectorStorage, Random, Sequential, VectorStorage, VectorStorageEnum,
@@ -48,6 +51,7 @@ pub struct AppendableMmapMultiDenseVectorStorage<
     /// depend on its length.
     deleted: BitvecFlags,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     multi_vector_config: MultiVectorConfig,
     deleted_count: usize,
     _phantom: std::marker::PhantomData<T>,
@@ -172,6 +176,14 @@ impl<
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         T::datatype()
     }
@@ -205,7 +217,7 @@ impl<
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
         let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
         let multi_vector = multi_vector.as_vec_ref();
-        assert_eq!(multi_vector.dim, self.vectors.dim());
+        check_vector_dim(self.vectors.dim(), multi_vector.dim)?;
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
         let max_vector_size_bytes = self.vectors.max_vector_size_bytes();
         if multivector_size_in_bytes >= max_vector_size_bytes {
@@ -423,6 +435,7 @@ pub fn open_appendable_memmap_multi_vector_storage_impl<T: PrimitiveVectorElemen
         offsets,
         deleted,
         distance,
+        custom_metric: None,
         multi_vector_config,
         deleted_count,
         _phantom: Default::default(),
@@ -535,6 +548,7 @@ pub fn open_appendable_in_ram_multi_vector_storage_impl<T: PrimitiveVectorElemen
         offsets,
         deleted,
         distance,
+        custom_metric: None,
         multi_vector_config,
         deleted_count,
         _phantom: Default::default(),
@@ -18,11 +18,12 @@ use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     TypedMultiDenseVector, TypedMultiDenseVectorRef, VectorElementType, VectorRef,
 };
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
-use crate::vector_storage::chunked_vectors::ChunkedVectors;
-use crate::vector_storage::common::{CHUNK_SIZE, StoredRecord};
+use crate::vector_storage::chunked_vectors::{ChunkedVectors, ChunkedVectorsConfig};
+use crate::vector_storage::common::{StoredRecord, check_vector_dim};
 use crate::vector_storage::{AccessPattern, MultiVectorStorage, VectorStorage, VectorStorageEnum};
 
 type StoredMultiDenseVector<T> = StoredRecord<TypedMultiDenseVector<T>>;
@@ -40,6 +41,7 @@ struct MultiVectorMetadata {
 pub struct SimpleMultiDenseVectorStorage<T: PrimitiveVectorElement> {
     dim: usize,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     multi_vector_config: MultiVectorConfig,
     /// Keep vectors in memory
     vectors: ChunkedVectors<T>,
@@ -72,6 +74,7 @@ pub fn open_simple_multi_dense_vector_storage(
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     match storage_element_type {
@@ -81,6 +84,7 @@ pub fn open_simple_multi_dense_vector_storage(
             dim,
             distance,
             multi_vector_config,
+            chunked_vectors_config,
             stopped,
         ),
         VectorStorageDatatype::Uint8 => open_simple_multi_dense_vector_storage_byte(
@@ -89,6 +93,7 @@ pub fn open_simple_multi_dense_vector_storage(
             dim,
             distance,
             multi_vector_config,
+            chunked_vectors_config,
             stopped,
         ),
         VectorStorageDatatype::Float16 => open_simple_multi_dense_vector_storage_half(
@@ -97,6 +102,7 @@ pub fn open_simple_multi_dense_vector_storage(
             dim,
             distance,
             multi_vector_config,
+            chunked_vectors_config,
             stopped,
         ),
     }
@@ -108,6 +114,7 @@ pub fn open_simple_multi_dense_vector_storage_full(
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_multi_dense_vector_storage_impl(
@@ -116,6 +123,7 @@ pub fn open_simple_multi_dense_vector_storage_full(
         dim,
         distance,
         multi_vector_config,
+        chunked_vectors_config,
         stopped,
     )?;
     Ok(VectorStorageEnum::MultiDenseSimple(storage))
@@ -127,6 +135,7 @@ pub fn open_simple_multi_dense_vector_storage_byte(
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_multi_dense_vector_storage_impl(
@@ -135,6 +144,7 @@ pub fn open_simple_multi_dense_vector_storage_byte(
         dim,
         distance,
         multi_vector_config,
+        chunked_vectors_config,
         stopped,
     )?;
     Ok(VectorStorageEnum::MultiDenseSimpleByte(storage))
@@ -146,6 +156,7 @@ pub fn open_simple_multi_dense_vector_storage_half(
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let storage = open_simple_multi_dense_vector_storage_impl(
@@ -154,6 +165,7 @@ pub fn open_simple_multi_dense_vector_storage_half(
         dim,
         distance,
         multi_vector_config,
+        chunked_vectors_config,
         stopped,
     )?;
     Ok(VectorStorageEnum::MultiDenseSimpleHalf(storage))
@@ -165,9 +177,10 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
+    chunked_vectors_config: ChunkedVectorsConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<SimpleMultiDenseVectorStorage<T>> {
-    let mut vectors = ChunkedVectors::new(dim);
+    let mut vectors = ChunkedVectors::with_config(dim, chunked_vectors_config);
     let mut vectors_metadata = Vec::<MultiVectorMetadata>::new();
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
     let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
@@ -210,6 +223,7 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     Ok(SimpleMultiDenseVectorStorage {
         dim,
         distance,
+        custom_metric: None,
         multi_vector_config,
         vectors,
         vectors_metadata,
@@ -280,11 +294,12 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
         let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
         let multi_vector = multi_vector.as_vec_ref();
-        assert_eq!(multi_vector.dim, self.dim);
+        check_vector_dim(self.dim, multi_vector.dim)?;
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
-        if multivector_size_in_bytes >= CHUNK_SIZE {
+        let max_size_in_bytes = self.vectors.chunk_size_bytes();
+        if multivector_size_in_bytes > max_size_in_bytes {
             return Err(OperationError::service_error(format!(
-                "Cannot insert multi vector of size {multivector_size_in_bytes} to the vector storage. It's too large, maximum size is {CHUNK_SIZE}.",
+                "Cannot insert multi vector of size {multivector_size_in_bytes} to the vector storage. It's too large, maximum size is {max_size_in_bytes}.",
             )));
         }
 
@@ -384,6 +399,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleMultiDenseVectorStorage<
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         VectorStorageDatatype::Float32
     }
@@ -505,6 +528,7 @@ mod tests {
             DIM,
             Distance::Dot,
             multi_vector_config,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
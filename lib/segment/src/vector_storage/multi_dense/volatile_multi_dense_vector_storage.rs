@@ -1,5 +1,6 @@
 use std::fmt;
 use std::ops::Range;
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::{BitSlice, BitVec};
@@ -12,11 +13,12 @@ use crate::common::operation_error::{OperationError, OperationResult, check_proc
 use crate::data_types::named_vectors::{CowMultiVector, CowVector};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{TypedMultiDenseVectorRef, VectorElementType, VectorRef};
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
-use crate::vector_storage::common::CHUNK_SIZE;
+use crate::vector_storage::common::check_vector_dim;
 use crate::vector_storage::{AccessPattern, MultiVectorStorage, VectorStorage, VectorStorageEnum};
 
 /// All fields are counting vectors and not dimensions.
@@ -32,6 +34,7 @@ struct MultiVectorMetadata {
 pub struct VolatileMultiDenseVectorStorage<T: PrimitiveVectorElement> {
     dim: usize,
     distance: Distance,
+    custom_metric: Option<Arc<WasmMetricModule>>,
     multi_vector_config: MultiVectorConfig,
     /// Keep vectors in memory
     vectors: ChunkedVectors<T>,
@@ -98,6 +101,7 @@ impl<T: PrimitiveVectorElement> VolatileMultiDenseVectorStorage<T> {
         Self {
             dim,
             distance,
+            custom_metric: None,
             multi_vector_config,
             vectors: ChunkedVectors::new(dim),
             vectors_metadata: vec![],
@@ -133,11 +137,12 @@ impl<T: PrimitiveVectorElement> VolatileMultiDenseVectorStorage<T> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
         let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
         let multi_vector = multi_vector.as_vec_ref();
-        assert_eq!(multi_vector.dim, self.dim);
+        check_vector_dim(self.dim, multi_vector.dim)?;
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
-        if multivector_size_in_bytes >= CHUNK_SIZE {
+        let max_size_in_bytes = self.vectors.chunk_size_bytes();
+        if multivector_size_in_bytes > max_size_in_bytes {
             return Err(OperationError::service_error(format!(
-                "Cannot insert multi vector of size {multivector_size_in_bytes} to the vector storage. It's too large, maximum size is {CHUNK_SIZE}.",
+                "Cannot insert multi vector of size {multivector_size_in_bytes} to the vector storage. It's too large, maximum size is {max_size_in_bytes}.",
             )));
         }
 
@@ -230,6 +235,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileMultiDenseVectorStorag
         self.distance
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        self.custom_metric.as_ref()
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        self.custom_metric = module;
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         VectorStorageDatatype::Float32
     }
@@ -1,3 +1,4 @@
+pub mod quantization_error;
 mod quantized_chunked_mmap_storage;
 mod quantized_custom_query_scorer;
 mod quantized_mmap_storage;
@@ -0,0 +1,285 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ahash::{AHashMap, AHashSet};
+use common::types::{ScoreType, ScoredPointOffset};
+use parking_lot::Mutex;
+
+/// Weight of the latest query in the moving averages.
+const SMOOTHING_FACTOR: f64 = 0.05;
+
+/// Number of rescored queries required before the observed error is trusted.
+const MIN_OBSERVED_QUERIES: usize = 10;
+
+/// Once the first queries are observed, only one of this many rescored queries is observed,
+/// so most searches don't pay for updating the statistics.
+const SAMPLING_INTERVAL: usize = 16;
+
+/// Oversampling used until enough queries are observed.
+pub const INITIAL_OVERSAMPLING: f64 = 2.0;
+
+/// Upper bound of the adaptive oversampling.
+pub const MAX_ADAPTIVE_OVERSAMPLING: f64 = 8.0;
+
+/// How many standard deviations of the error are considered, when estimating
+/// which candidates could still get into the top after rescoring.
+const ERROR_MARGIN_SIGMAS: f64 = 2.0;
+
+/// Growth of the oversampling if all pre-selected candidates were within the error margin,
+/// so the real need could not be observed.
+const SATURATED_GROWTH: f64 = 1.5;
+
+/// Distribution of the difference between quantized and original scores, observed during rescoring.
+///
+/// Used to choose oversampling automatically: a candidate ranked below the top by the quantized
+/// scores can only make it into the final top, if its score is within the error margin of the
+/// last quantized top score.
+#[derive(Debug, Default)]
+pub struct QuantizationErrorStats {
+    /// Number of rescored queries, observed or not
+    queries: AtomicUsize,
+    inner: Mutex<ErrorStatsInner>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ErrorStatsInner {
+    observed_queries: usize,
+    /// Moving average of `|quantized - original|`
+    mean_error: f64,
+    /// Moving average of `(quantized - original)^2`
+    mean_squared_error: f64,
+    /// Moving average of the oversampling which previous queries required
+    required_oversampling: f64,
+    recall_queries: usize,
+    /// Moving average of the fraction of the top by original scores, which is also in the top
+    /// by quantized scores
+    recall: f64,
+}
+
+impl ErrorStatsInner {
+    fn update(average: &mut f64, value: f64, is_first: bool) {
+        if is_first {
+            *average = value;
+        } else {
+            *average += SMOOTHING_FACTOR * (value - *average);
+        }
+    }
+
+    fn error_margin(&self) -> f64 {
+        let variance = (self.mean_squared_error - self.mean_error.powi(2)).max(0.0);
+        self.mean_error + ERROR_MARGIN_SIGMAS * variance.sqrt()
+    }
+}
+
+impl QuantizationErrorStats {
+    /// Register rescoring of a single query.
+    ///
+    /// `quantized` are the pre-selected candidates with quantized scores,
+    /// `rescored` are the same candidates with original scores.
+    ///
+    /// Queries are sampled, see [`SAMPLING_INTERVAL`]. Observation is also skipped if another
+    /// search thread is updating the statistics, so searches never wait for each other.
+    pub fn observe(
+        &self,
+        quantized: &[ScoredPointOffset],
+        rescored: &[ScoredPointOffset],
+        top: usize,
+    ) {
+        if quantized.is_empty() || top == 0 {
+            return;
+        }
+
+        if !self.is_sampled() {
+            return;
+        }
+
+        let original_scores: AHashMap<_, _> = rescored
+            .iter()
+            .map(|point| (point.idx, point.score))
+            .collect();
+
+        let (errors_sum, squared_errors_sum, count) = quantized
+            .iter()
+            .filter_map(|point| {
+                let original = original_scores.get(&point.idx)?;
+                Some(f64::from((point.score - original).abs()))
+            })
+            .fold((0.0, 0.0, 0usize), |(sum, squared_sum, count), error| {
+                (sum + error, squared_sum + error * error, count + 1)
+            });
+        if count == 0 {
+            return;
+        }
+
+        let Some(mut inner) = self.inner.try_lock() else {
+            return;
+        };
+
+        let is_first = inner.observed_queries == 0;
+        ErrorStatsInner::update(&mut inner.mean_error, errors_sum / count as f64, is_first);
+        ErrorStatsInner::update(
+            &mut inner.mean_squared_error,
+            squared_errors_sum / count as f64,
+            is_first,
+        );
+
+        let recall_top = top.min(quantized.len());
+        let recall = top_recall(quantized, rescored, recall_top);
+        ErrorStatsInner::update(&mut inner.recall, recall, inner.recall_queries == 0);
+        inner.recall_queries += 1;
+
+        // Not enough candidates to tell how many of them were actually needed
+        if quantized.len() < top {
+            return;
+        }
+
+        let mut quantized_scores: Vec<ScoreType> =
+            quantized.iter().map(|point| point.score).collect();
+        quantized_scores.sort_unstable_by(|a, b| b.total_cmp(a));
+
+        // Both the last top point and the candidate may be misestimated, so the margin is doubled
+        let threshold = f64::from(quantized_scores[top - 1]) - 2.0 * inner.error_margin();
+        let within_margin = quantized_scores
+            .iter()
+            .take_while(|&&score| f64::from(score) >= threshold)
+            .count();
+
+        let mut required = within_margin as f64 / top as f64;
+        if within_margin == quantized_scores.len() {
+            required *= SATURATED_GROWTH;
+        }
+
+        ErrorStatsInner::update(&mut inner.required_oversampling, required, is_first);
+        inner.observed_queries += 1;
+    }
+
+    /// Whether to observe the next query
+    ///
+    /// The first queries are all observed, so the estimations are available soon.
+    fn is_sampled(&self) -> bool {
+        let query = self.queries.fetch_add(1, Ordering::Relaxed);
+        query < MIN_OBSERVED_QUERIES || query % SAMPLING_INTERVAL == 0
+    }
+
+    /// Oversampling factor, estimated from the previously observed queries.
+    pub fn oversampling(&self) -> f64 {
+        let inner = *self.inner.lock();
+        if inner.observed_queries < MIN_OBSERVED_QUERIES {
+            return INITIAL_OVERSAMPLING;
+        }
+        inner
+            .required_oversampling
+            .clamp(1.0, MAX_ADAPTIVE_OVERSAMPLING)
+    }
+
+    /// Number of rescored queries observed so far
+    pub fn recall_queries(&self) -> usize {
+        self.inner.lock().recall_queries
+    }
+
+    /// Recall of the top by quantized scores, relative to the top by original scores of the
+    /// same candidates, estimated from the previously observed queries.
+    ///
+    /// I.e. the expected recall of searches with this quantization, if rescoring is disabled.
+    /// Returns `None` until enough queries are observed.
+    pub fn estimated_recall(&self) -> Option<f64> {
+        let inner = *self.inner.lock();
+        (inner.recall_queries >= MIN_OBSERVED_QUERIES).then_some(inner.recall)
+    }
+}
+
+/// Fraction of the `top` points by `rescored` scores, which are also in the `top` by `quantized`
+/// scores
+fn top_recall(quantized: &[ScoredPointOffset], rescored: &[ScoredPointOffset], top: usize) -> f64 {
+    if top == 0 {
+        return 1.0;
+    }
+    let top_ids = |points: &[ScoredPointOffset]| -> AHashSet<_> {
+        let mut points = points.to_vec();
+        points.sort_unstable_by(|a, b| b.score.total_cmp(&a.score));
+        points
+            .into_iter()
+            .take(top)
+            .map(|point| point.idx)
+            .collect()
+    };
+    let quantized_top = top_ids(quantized);
+    let found = top_ids(rescored)
+        .iter()
+        .filter(|idx| quantized_top.contains(idx))
+        .count();
+    found as f64 / top as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(scores: impl IntoIterator<Item = ScoreType>) -> Vec<ScoredPointOffset> {
+        scores
+            .into_iter()
+            .enumerate()
+            .map(|(idx, score)| ScoredPointOffset {
+                idx: idx as _,
+                score,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quantization_error_stats() {
+        let stats = QuantizationErrorStats::default();
+        assert_eq!(stats.oversampling(), INITIAL_OVERSAMPLING);
+
+        // Exact quantization, only the top is needed
+        let scores: Vec<_> = (0..40).map(|i| 1.0 - i as ScoreType * 0.01).collect();
+        for _ in 0..MIN_OBSERVED_QUERIES {
+            stats.observe(&points(scores.clone()), &points(scores.clone()), 10);
+        }
+        assert_eq!(stats.oversampling(), 1.0);
+        assert_eq!(stats.estimated_recall(), Some(1.0));
+
+        // Quantized scores are off by up to 0.1, so candidates within 0.2 of the top are needed
+        let stats = QuantizationErrorStats::default();
+        let rescored: Vec<_> = scores
+            .iter()
+            .enumerate()
+            .map(|(i, score)| if i % 2 == 0 { score + 0.1 } else { *score })
+            .collect();
+        for _ in 0..100 {
+            stats.observe(&points(scores.clone()), &points(rescored.clone()), 10);
+        }
+        let oversampling = stats.oversampling();
+        assert!(oversampling > 2.0, "{oversampling}");
+        assert!(oversampling <= MAX_ADAPTIVE_OVERSAMPLING);
+        // Half of the top is shifted out by the misestimated candidates
+        let recall = stats.estimated_recall().unwrap();
+        assert!(recall < 1.0, "{recall}");
+    }
+
+    #[test]
+    fn test_quantization_error_sampling() {
+        let stats = QuantizationErrorStats::default();
+        let scores: Vec<_> = (0..40).map(|i| 1.0 - i as ScoreType * 0.01).collect();
+
+        // The first queries are all observed
+        for _ in 0..MIN_OBSERVED_QUERIES {
+            stats.observe(&points(scores.clone()), &points(scores.clone()), 10);
+        }
+        assert_eq!(stats.recall_queries(), MIN_OBSERVED_QUERIES);
+
+        // Then only one of `SAMPLING_INTERVAL` queries
+        for _ in MIN_OBSERVED_QUERIES..SAMPLING_INTERVAL * 10 {
+            stats.observe(&points(scores.clone()), &points(scores.clone()), 10);
+        }
+        assert_eq!(stats.recall_queries(), MIN_OBSERVED_QUERIES + 9);
+    }
+
+    #[test]
+    fn test_top_recall() {
+        let quantized = points([0.9, 0.8, 0.7, 0.6]);
+        let rescored = points([0.5, 0.8, 0.7, 0.9]);
+        assert_eq!(top_recall(&quantized, &rescored, 2), 0.5);
+        assert_eq!(top_recall(&quantized, &rescored, 4), 1.0);
+        assert_eq!(top_recall(&quantized, &rescored, 0), 1.0);
+    }
+}
@@ -19,6 +19,18 @@ impl QuantizedMmapStorage {
     pub fn populate(&self) {
         self.mmap.populate();
     }
+
+    /// Lock the memory map in RAM, so it is never swapped out
+    ///
+    /// Fails if the data doesn't fit into the amount of memory the process is allowed to lock
+    /// (`RLIMIT_MEMLOCK`).
+    pub fn lock(&self) -> std::io::Result<()> {
+        #[cfg(unix)]
+        self.mmap.lock()?;
+        #[cfg(not(unix))]
+        log::debug!("Ignore locking of {} on this platform", self.path.display());
+        Ok(())
+    }
 }
 
 pub struct QuantizedMmapStorageBuilder {
@@ -4,7 +4,7 @@ use quantization::EncodedVectors;
 use super::quantized_custom_query_scorer::QuantizedCustomQueryScorer;
 use super::quantized_query_scorer::QuantizedQueryScorer;
 use super::quantized_vectors::QuantizedVectorStorage;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
@@ -30,6 +30,12 @@ pub(super) struct QuantizedScorerBuilder<'a> {
     hardware_counter: HardwareCounterCell,
 }
 
+fn quantization_not_supported(distance: &Distance) -> OperationError {
+    OperationError::service_error(format!(
+        "Quantization is not supported for {distance:?} distance",
+    ))
+}
+
 impl<'a> QuantizedScorerBuilder<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -61,6 +67,10 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementType, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming
+                | Distance::Jaccard
+                | Distance::Custom
+                | Distance::RawCosine) => Err(quantization_not_supported(distance)),
             },
             VectorStorageDatatype::Uint8 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeByte, CosineMetric>(),
@@ -71,6 +81,10 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeByte, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming
+                | Distance::Jaccard
+                | Distance::Custom
+                | Distance::RawCosine) => Err(quantization_not_supported(distance)),
             },
             VectorStorageDatatype::Float16 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeHalf, CosineMetric>(),
@@ -81,6 +95,10 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeHalf, ManhattanMetric>()
                 }
+                distance @ (Distance::Hamming
+                | Distance::Jaccard
+                | Distance::Custom
+                | Distance::RawCosine) => Err(quantization_not_supported(distance)),
             },
         }
     }
@@ -118,6 +136,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric>(storage)
             }
+            QuantizedVectorStorage::ResidualRam(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric>(storage)
+            }
+            QuantizedVectorStorage::ResidualMmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric>(storage)
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric>(storage)
             }
@@ -8,7 +8,8 @@ use common::types::PointOffsetType;
 use io::file_operations::{atomic_save_json, read_json};
 use memory::fadvise::clear_disk_cache;
 use quantization::encoded_vectors_binary::EncodedVectorsBin;
-use quantization::{EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8};
+use quantization::quantile::Quantile;
+use quantization::{EncodedVectors, EncodedVectorsPQ, EncodedVectorsResidual, EncodedVectorsU8};
 use serde::{Deserialize, Serialize};
 
 use super::quantized_multivector_storage::{
@@ -22,9 +23,11 @@ use crate::data_types::vectors::{QueryVector, VectorElementType, VectorRef};
 use crate::types::{
     BinaryQuantization, BinaryQuantizationConfig, BinaryQuantizationEncoding,
     BinaryQuantizationQueryEncoding, CompressionRatio, Distance, MultiVectorConfig,
-    ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
-    ScalarQuantizationConfig, VectorStorageDatatype,
+    ProductQuantization, ProductQuantizationConfig, QuantizationConfig, QuantizationPlacement,
+    ResidualQuantization, ResidualQuantizationConfig, ScalarQuantization, ScalarQuantizationConfig,
+    VectorStorageDatatype,
 };
+use crate::vector_storage::quantized::quantization_error::QuantizationErrorStats;
 use crate::vector_storage::quantized::quantized_chunked_mmap_storage::{
     QuantizedChunkedMmapStorage, QuantizedChunkedMmapStorageBuilder,
 };
@@ -41,8 +44,8 @@ use crate::vector_storage::quantized::quantized_ram_storage::{
     QuantizedRamStorage, QuantizedRamStorageBuilder,
 };
 use crate::vector_storage::{
-    DenseVectorStorage, MultiVectorStorage, Random, RawScorer, RawScorerImpl, Sequential,
-    VectorStorage, VectorStorageEnum,
+    DenseVectorStorage, MultiVectorStorage, Random, RawScorer, RawScorerImpl, VectorStorage,
+    VectorStorageEnum,
 };
 
 pub const QUANTIZED_CONFIG_PATH: &str = "quantized.config.json";
@@ -134,6 +137,8 @@ pub enum QuantizedVectorStorage {
     BinaryRam(EncodedVectorsBin<u128, QuantizedRamStorage>),
     BinaryMmap(EncodedVectorsBin<u128, QuantizedMmapStorage>),
     BinaryChunkedMmap(EncodedVectorsBin<u128, QuantizedChunkedMmapStorage>),
+    ResidualRam(EncodedVectorsResidual<QuantizedRamStorage>),
+    ResidualMmap(EncodedVectorsResidual<QuantizedMmapStorage>),
     ScalarRamMulti(ScalarRamMulti),
     ScalarMmapMulti(ScalarMmapMulti),
     ScalarChunkedMmapMulti(ScalarChunkedMmapMulti),
@@ -157,6 +162,8 @@ impl QuantizedVectorStorage {
             QuantizedVectorStorage::BinaryRam(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryMmap(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.is_on_disk(),
+            QuantizedVectorStorage::ResidualRam(q) => q.is_on_disk(),
+            QuantizedVectorStorage::ResidualMmap(q) => q.is_on_disk(),
             QuantizedVectorStorage::ScalarRamMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::ScalarMmapMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.is_on_disk(),
@@ -183,6 +190,8 @@ pub struct QuantizedVectors {
     path: PathBuf,
     distance: Distance,
     datatype: VectorStorageDatatype,
+    /// Quantization error observed during rescoring, not persisted
+    error_stats: QuantizationErrorStats,
 }
 
 impl QuantizedVectors {
@@ -190,6 +199,10 @@ impl QuantizedVectors {
         &self.config
     }
 
+    pub fn error_stats(&self) -> &QuantizationErrorStats {
+        &self.error_stats
+    }
+
     pub fn default_rescoring(&self) -> bool {
         matches!(
             self.storage_impl,
@@ -208,6 +221,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(_) => false,
             QuantizedVectorStorage::BinaryMmap(_) => false,
             QuantizedVectorStorage::BinaryChunkedMmap(_) => false,
+            QuantizedVectorStorage::ResidualRam(_) => false,
+            QuantizedVectorStorage::ResidualMmap(_) => false,
             QuantizedVectorStorage::ScalarRamMulti(_) => true,
             QuantizedVectorStorage::ScalarMmapMulti(_) => true,
             QuantizedVectorStorage::ScalarChunkedMmapMulti(_) => true,
@@ -234,6 +249,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::BinaryMmap(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => Ok(storage.layout()),
+            QuantizedVectorStorage::ResidualRam(storage) => Ok(storage.layout()),
+            QuantizedVectorStorage::ResidualMmap(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::ScalarRamMulti(_)
             | QuantizedVectorStorage::ScalarMmapMulti(_)
             | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
@@ -261,6 +278,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::BinaryMmap(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => storage.get_quantized_vector(id),
+            QuantizedVectorStorage::ResidualRam(storage) => storage.get_quantized_vector(id),
+            QuantizedVectorStorage::ResidualMmap(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::ScalarRamMulti(_)
             | QuantizedVectorStorage::ScalarMmapMulti(_)
             | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
@@ -332,6 +351,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => {
                 build(point_id, storage, hardware_counter)
             }
+            QuantizedVectorStorage::ResidualRam(storage) => {
+                build(point_id, storage, hardware_counter)
+            }
+            QuantizedVectorStorage::ResidualMmap(storage) => {
+                build(point_id, storage, hardware_counter)
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 build(point_id, storage, hardware_counter)
             }
@@ -395,6 +420,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(q) => q.files(),
             QuantizedVectorStorage::BinaryMmap(q) => q.files(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.files(),
+            QuantizedVectorStorage::ResidualRam(q) => q.files(),
+            QuantizedVectorStorage::ResidualMmap(q) => q.files(),
             QuantizedVectorStorage::ScalarRamMulti(q) => q.files(),
             QuantizedVectorStorage::ScalarMmapMulti(q) => q.files(),
             QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.files(),
@@ -420,6 +447,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryMmap(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.immutable_files(),
+            QuantizedVectorStorage::ResidualRam(q) => q.immutable_files(),
+            QuantizedVectorStorage::ResidualMmap(q) => q.immutable_files(),
             QuantizedVectorStorage::ScalarRamMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::ScalarMmapMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.immutable_files(),
@@ -434,6 +463,24 @@ impl QuantizedVectors {
         files
     }
 
+    /// Estimated size of quantized data of `available` out of `total` stored vectors, in bytes
+    ///
+    /// Quantized data of deleted vectors is not freed until the segment is optimized,
+    /// so it is excluded proportionally.
+    pub fn size_of_available_vectors_in_bytes(&self, available: usize, total: usize) -> usize {
+        let files_size: u64 = self
+            .files()
+            .iter()
+            .filter(|file| !file.ends_with(QUANTIZED_CONFIG_PATH))
+            .filter_map(|file| file.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        if total == 0 {
+            return 0;
+        }
+        (files_size as u128 * available as u128 / total as u128) as usize
+    }
+
     pub fn create(
         vector_storage: &VectorStorageEnum,
         quantization_config: &QuantizationConfig,
@@ -691,17 +738,13 @@ impl QuantizedVectors {
         let count = vector_storage.total_vector_count();
         let distance = vector_storage.distance();
         let datatype = vector_storage.datatype();
-        let vectors = (0..count as PointOffsetType).map(|i| {
-            PrimitiveVectorElement::quantization_preprocess(
-                quantization_config,
-                distance,
-                vector_storage.get_dense::<Sequential>(i),
-            )
+        let vectors = vector_storage.iter_vectors().map(|(_, vector)| {
+            PrimitiveVectorElement::quantization_preprocess(quantization_config, distance, vector)
         });
         let on_disk_vector_storage = vector_storage.is_on_disk();
 
         let vector_parameters =
-            Self::construct_vector_parameters(distance, dim, count, storage_type);
+            Self::construct_vector_parameters(distance, dim, count, storage_type)?;
 
         let quantized_storage = match quantization_config {
             QuantizationConfig::Scalar(ScalarQuantization {
@@ -741,6 +784,19 @@ impl QuantizedVectors {
                 on_disk_vector_storage,
                 stopped,
             )?,
+            QuantizationConfig::Residual(ResidualQuantization {
+                residual: residual_config,
+            }) => Self::create_residual(
+                vectors,
+                &vector_parameters,
+                count,
+                residual_config,
+                storage_type,
+                path,
+                on_disk_vector_storage,
+                max_threads,
+                stopped,
+            )?,
         };
 
         let quantized_vectors_config = QuantizedVectorsConfig {
@@ -755,8 +811,11 @@ impl QuantizedVectors {
             path: path.to_path_buf(),
             distance,
             datatype,
+            error_stats: Default::default(),
         };
 
+        quantized_vectors.lock_in_ram();
+
         atomic_save_json(&path.join(QUANTIZED_CONFIG_PATH), &quantized_vectors.config)?;
         Ok(quantized_vectors)
     }
@@ -784,7 +843,7 @@ impl QuantizedVectors {
         let on_disk_vector_storage = vector_storage.is_on_disk();
 
         let vector_parameters =
-            Self::construct_vector_parameters(distance, dim, inner_vectors_count, storage_type);
+            Self::construct_vector_parameters(distance, dim, inner_vectors_count, storage_type)?;
 
         let offsets = (0..vectors_count as PointOffsetType)
             .map(|idx| vector_storage.get_multi::<Random>(idx).vectors_count() as PointOffsetType)
@@ -844,6 +903,11 @@ impl QuantizedVectors {
                 on_disk_vector_storage,
                 stopped,
             )?,
+            QuantizationConfig::Residual(_) => {
+                return Err(OperationError::service_error(
+                    "Residual quantization is not supported for multivectors",
+                ));
+            }
         };
 
         let quantized_vectors_config = QuantizedVectorsConfig {
@@ -858,8 +922,11 @@ impl QuantizedVectors {
             path: path.to_path_buf(),
             distance,
             datatype,
+            error_stats: Default::default(),
         };
 
+        quantized_vectors.lock_in_ram();
+
         atomic_save_json(&path.join(QUANTIZED_CONFIG_PATH), &quantized_vectors.config)?;
         Ok(quantized_vectors)
     }
@@ -922,6 +989,11 @@ impl QuantizedVectors {
                         multivector_config,
                     )?
                 }
+                QuantizationConfig::Residual(_) => {
+                    return Err(OperationError::service_error(
+                        "Residual quantization is not supported for multivectors",
+                    ));
+                }
             }
         } else {
             match &config.quantization_config {
@@ -934,18 +1006,26 @@ impl QuantizedVectors {
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     Self::load_binary(vector_storage, path, &config, binary)?
                 }
+                QuantizationConfig::Residual(ResidualQuantization { residual }) => {
+                    Self::load_residual(vector_storage, path, &config, residual)?
+                }
             }
         };
 
         let distance = vector_storage.distance();
         let datatype = vector_storage.datatype();
-        Ok(QuantizedVectors {
+        let quantized_vectors = QuantizedVectors {
             storage_impl: quantized_store,
             config,
             path: path.to_path_buf(),
             distance,
             datatype,
-        })
+            error_stats: Default::default(),
+        };
+
+        quantized_vectors.lock_in_ram();
+
+        Ok(quantized_vectors)
     }
 
     fn load_scalar(
@@ -963,7 +1043,11 @@ impl QuantizedVectors {
         let on_disk_vector_storage = vector_storage.is_on_disk();
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
-        if Self::is_ram(scalar_config.always_ram, on_disk_vector_storage) {
+        if Self::is_ram(
+            scalar_config.always_ram,
+            scalar_config.placement,
+            on_disk_vector_storage,
+        ) {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedRamStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
@@ -1005,7 +1089,11 @@ impl QuantizedVectors {
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
         let offsets_path = Self::get_offsets_path(path, config.storage_type);
-        if Self::is_ram(scalar_config.always_ram, on_disk_vector_storage) {
+        if Self::is_ram(
+            scalar_config.always_ram,
+            scalar_config.placement,
+            on_disk_vector_storage,
+        ) {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedRamStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
@@ -1057,7 +1145,11 @@ impl QuantizedVectors {
         let on_disk_vector_storage = vector_storage.is_on_disk();
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
-        if Self::is_ram(pq_config.always_ram, on_disk_vector_storage) {
+        if Self::is_ram(
+            pq_config.always_ram,
+            pq_config.placement,
+            on_disk_vector_storage,
+        ) {
             let bucket_size = Self::get_bucket_size(pq_config.compression);
             let quantized_vector_size =
                 EncodedVectorsPQ::<QuantizedRamStorage>::get_quantized_vector_size(
@@ -1103,7 +1195,11 @@ impl QuantizedVectors {
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
         let offsets_path = Self::get_offsets_path(path, config.storage_type);
-        if Self::is_ram(pq_config.always_ram, on_disk_vector_storage) {
+        if Self::is_ram(
+            pq_config.always_ram,
+            pq_config.placement,
+            on_disk_vector_storage,
+        ) {
             let bucket_size = Self::get_bucket_size(pq_config.compression);
             let quantized_vector_size =
                 EncodedVectorsPQ::<QuantizedRamStorage>::get_quantized_vector_size(
@@ -1144,6 +1240,44 @@ impl QuantizedVectors {
         }
     }
 
+    fn load_residual(
+        vector_storage: &VectorStorageEnum,
+        path: &Path,
+        config: &QuantizedVectorsConfig,
+        residual_config: &ResidualQuantizationConfig,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !config.storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable quantized storage is not supported for Residual Quantization",
+            ));
+        }
+
+        let on_disk_vector_storage = vector_storage.is_on_disk();
+        let data_path = Self::get_data_path(path, config.storage_type);
+        let meta_path = Self::get_meta_path(path);
+        let quantized_vector_size =
+            EncodedVectorsResidual::<QuantizedRamStorage>::get_quantized_vector_size(
+                &config.vector_parameters,
+            );
+        if Self::is_ram(
+            residual_config.always_ram,
+            residual_config.placement,
+            on_disk_vector_storage,
+        ) {
+            let quantized_vectors_storage =
+                QuantizedRamStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            Ok(QuantizedVectorStorage::ResidualRam(
+                EncodedVectorsResidual::load(quantized_vectors_storage, &meta_path)?,
+            ))
+        } else {
+            let quantized_vectors_storage =
+                QuantizedMmapStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            Ok(QuantizedVectorStorage::ResidualMmap(
+                EncodedVectorsResidual::load(quantized_vectors_storage, &meta_path)?,
+            ))
+        }
+    }
+
     fn load_binary(
         vector_storage: &VectorStorageEnum,
         path: &Path,
@@ -1153,7 +1287,11 @@ impl QuantizedVectors {
         let on_disk_vector_storage = vector_storage.is_on_disk();
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
-        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            binary_config.always_ram,
+            binary_config.placement,
+            on_disk_vector_storage,
+        );
 
         match (in_ram, config.storage_type) {
             (_, QuantizedVectorsStorageType::Mutable) => {
@@ -1211,7 +1349,11 @@ impl QuantizedVectors {
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
         let offsets_path = Self::get_offsets_path(path, config.storage_type);
-        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            binary_config.always_ram,
+            binary_config.placement,
+            on_disk_vector_storage,
+        );
 
         match (in_ram, config.storage_type) {
             (_, QuantizedVectorsStorageType::Mutable) => {
@@ -1303,7 +1445,11 @@ impl QuantizedVectors {
             EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
-        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            scalar_config.always_ram,
+            scalar_config.placement,
+            on_disk_vector_storage,
+        );
         if in_ram {
             let storage_builder = QuantizedRamStorageBuilder::new(
                 data_path.as_path(),
@@ -1315,7 +1461,7 @@ impl QuantizedVectors {
                 storage_builder,
                 vector_parameters,
                 vectors_count,
-                scalar_config.quantile,
+                Self::scalar_quantile(scalar_config),
                 Some(meta_path.as_path()),
                 stopped,
             )?))
@@ -1331,7 +1477,7 @@ impl QuantizedVectors {
                     storage_builder,
                     vector_parameters,
                     vectors_count,
-                    scalar_config.quantile,
+                    Self::scalar_quantile(scalar_config),
                     Some(meta_path.as_path()),
                     stopped,
                 )?,
@@ -1340,6 +1486,14 @@ impl QuantizedVectors {
     }
 
     #[allow(clippy::too_many_arguments)]
+    fn scalar_quantile(scalar_config: &ScalarQuantizationConfig) -> Option<Quantile> {
+        if scalar_config.auto_quantile == Some(true) {
+            Some(Quantile::Auto)
+        } else {
+            scalar_config.quantile.map(Quantile::Fixed)
+        }
+    }
+
     fn create_scalar_multi<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
         offsets: impl Iterator<Item = MultivectorOffset>,
@@ -1364,7 +1518,11 @@ impl QuantizedVectors {
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
         let offsets_path = Self::get_offsets_path(path, storage_type);
-        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            scalar_config.always_ram,
+            scalar_config.placement,
+            on_disk_vector_storage,
+        );
         if in_ram {
             let storage_builder = QuantizedRamStorageBuilder::new(
                 data_path.as_path(),
@@ -1376,7 +1534,7 @@ impl QuantizedVectors {
                 storage_builder,
                 vector_parameters,
                 inner_vectors_count,
-                scalar_config.quantile,
+                Self::scalar_quantile(scalar_config),
                 Some(meta_path.as_path()),
                 stopped,
             )?;
@@ -1400,7 +1558,7 @@ impl QuantizedVectors {
                 storage_builder,
                 vector_parameters,
                 inner_vectors_count,
-                scalar_config.quantile,
+                Self::scalar_quantile(scalar_config),
                 Some(meta_path.as_path()),
                 stopped,
             )?;
@@ -1443,7 +1601,11 @@ impl QuantizedVectors {
             );
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
-        let in_ram = Self::is_ram(pq_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            pq_config.always_ram,
+            pq_config.placement,
+            on_disk_vector_storage,
+        );
         if in_ram {
             let storage_builder = QuantizedRamStorageBuilder::new(
                 data_path.as_path(),
@@ -1509,7 +1671,11 @@ impl QuantizedVectors {
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
         let offsets_path = Self::get_offsets_path(path, storage_type);
-        let in_ram = Self::is_ram(pq_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            pq_config.always_ram,
+            pq_config.placement,
+            on_disk_vector_storage,
+        );
         if in_ram {
             let storage_builder = QuantizedRamStorageBuilder::new(
                 data_path.as_path(),
@@ -1564,6 +1730,72 @@ impl QuantizedVectors {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn create_residual<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + Send + Sync + 'a> + Clone,
+        vector_parameters: &quantization::VectorParameters,
+        vectors_count: usize,
+        residual_config: &ResidualQuantizationConfig,
+        storage_type: QuantizedVectorsStorageType,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable residual quantization is not supported",
+            ));
+        }
+
+        let quantized_vector_size =
+            EncodedVectorsResidual::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+            );
+        let meta_path = Self::get_meta_path(path);
+        let data_path = Self::get_data_path(path, storage_type);
+        let in_ram = Self::is_ram(
+            residual_config.always_ram,
+            residual_config.placement,
+            on_disk_vector_storage,
+        );
+        if in_ram {
+            let storage_builder = QuantizedRamStorageBuilder::new(
+                data_path.as_path(),
+                vectors_count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::ResidualRam(
+                EncodedVectorsResidual::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    vectors_count,
+                    max_threads,
+                    Some(meta_path.as_path()),
+                    stopped,
+                )?,
+            ))
+        } else {
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                data_path.as_path(),
+                vectors_count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::ResidualMmap(
+                EncodedVectorsResidual::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    vectors_count,
+                    max_threads,
+                    Some(meta_path.as_path()),
+                    stopped,
+                )?,
+            ))
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_binary<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
@@ -1584,7 +1816,11 @@ impl QuantizedVectors {
             );
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
-        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            binary_config.always_ram,
+            binary_config.placement,
+            on_disk_vector_storage,
+        );
 
         match (in_ram, storage_type) {
             (_, QuantizedVectorsStorageType::Mutable) => {
@@ -1668,7 +1904,11 @@ impl QuantizedVectors {
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
         let offsets_path = Self::get_offsets_path(path, storage_type);
-        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        let in_ram = Self::is_ram(
+            binary_config.always_ram,
+            binary_config.placement,
+            on_disk_vector_storage,
+        );
 
         match (in_ram, storage_type) {
             (_, QuantizedVectorsStorageType::Mutable) => {
@@ -1751,8 +1991,16 @@ impl QuantizedVectors {
         }
     }
 
-    fn is_ram(always_ram: Option<bool>, on_disk_vector_storage: bool) -> bool {
-        !on_disk_vector_storage || always_ram == Some(true)
+    fn is_ram(
+        always_ram: Option<bool>,
+        placement: Option<QuantizationPlacement>,
+        on_disk_vector_storage: bool,
+    ) -> bool {
+        match placement {
+            Some(QuantizationPlacement::Ram) => true,
+            Some(QuantizationPlacement::Mmap | QuantizationPlacement::MmapLocked) => false,
+            None => !on_disk_vector_storage || always_ram == Some(true),
+        }
     }
 
     fn convert_binary_encoding(
@@ -1797,8 +2045,8 @@ impl QuantizedVectors {
         dim: usize,
         deprecated_count: usize,
         storage_type: QuantizedVectorsStorageType,
-    ) -> quantization::VectorParameters {
-        quantization::VectorParameters {
+    ) -> OperationResult<quantization::VectorParameters> {
+        Ok(quantization::VectorParameters {
             dim,
             deprecated_count: match storage_type {
                 QuantizedVectorsStorageType::Mutable => None,
@@ -1809,9 +2057,14 @@ impl QuantizedVectors {
                 Distance::Euclid => quantization::DistanceType::L2,
                 Distance::Dot => quantization::DistanceType::Dot,
                 Distance::Manhattan => quantization::DistanceType::L1,
+                Distance::Hamming | Distance::Jaccard | Distance::Custom | Distance::RawCosine => {
+                    return Err(OperationError::validation_error(format!(
+                        "Quantization is not supported for {distance:?} distance",
+                    )));
+                }
             },
             invert: distance == Distance::Euclid || distance == Distance::Manhattan,
-        }
+        })
     }
 
     fn get_bucket_size(compression: CompressionRatio) -> usize {
@@ -1839,6 +2092,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(_) => {}
             QuantizedVectorStorage::BinaryMmap(storage) => storage.storage().populate(),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => storage.storage().populate()?,
+            QuantizedVectorStorage::ResidualRam(_) => {}
+            QuantizedVectorStorage::ResidualMmap(storage) => storage.storage().populate(),
             QuantizedVectorStorage::ScalarRamMulti(_) => {}
             QuantizedVectorStorage::ScalarMmapMulti(storage) => {
                 storage.storage().storage().populate();
@@ -1870,6 +2125,46 @@ impl QuantizedVectors {
         Ok(())
     }
 
+    /// Lock memory-mapped quantized vectors in RAM, if configured
+    ///
+    /// Chunked storages of mutable quantization grow with new points and are not locked.
+    /// Failure to lock is not fatal, quantized vectors stay memory-mapped.
+    fn lock_in_ram(&self) {
+        if self.config.quantization_config.placement() != Some(QuantizationPlacement::MmapLocked) {
+            return;
+        }
+
+        let result = match &self.storage_impl {
+            QuantizedVectorStorage::ScalarMmap(storage) => storage.storage().lock(),
+            QuantizedVectorStorage::PQMmap(storage) => storage.storage().lock(),
+            QuantizedVectorStorage::BinaryMmap(storage) => storage.storage().lock(),
+            QuantizedVectorStorage::ResidualMmap(storage) => storage.storage().lock(),
+            QuantizedVectorStorage::ScalarMmapMulti(storage) => storage.storage().storage().lock(),
+            QuantizedVectorStorage::PQMmapMulti(storage) => storage.storage().storage().lock(),
+            QuantizedVectorStorage::BinaryMmapMulti(storage) => storage.storage().storage().lock(),
+            QuantizedVectorStorage::ScalarRam(_)
+            | QuantizedVectorStorage::PQRam(_)
+            | QuantizedVectorStorage::BinaryRam(_)
+            | QuantizedVectorStorage::ResidualRam(_)
+            | QuantizedVectorStorage::ScalarRamMulti(_)
+            | QuantizedVectorStorage::PQRamMulti(_)
+            | QuantizedVectorStorage::BinaryRamMulti(_)
+            | QuantizedVectorStorage::ScalarChunkedMmap(_)
+            | QuantizedVectorStorage::PQChunkedMmap(_)
+            | QuantizedVectorStorage::BinaryChunkedMmap(_)
+            | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
+            | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => Ok(()),
+        };
+
+        if let Err(err) = result {
+            log::warn!(
+                "Failed to lock quantized vectors at {} in RAM: {err}",
+                self.path.display(),
+            );
+        }
+    }
+
     pub fn clear_cache(&self) -> OperationResult<()> {
         for file in self.files() {
             clear_disk_cache(&file)?;
@@ -1888,6 +2183,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryRam(q) => q.flusher(),
             QuantizedVectorStorage::BinaryMmap(q) => q.flusher(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.flusher(),
+            QuantizedVectorStorage::ResidualRam(q) => q.flusher(),
+            QuantizedVectorStorage::ResidualMmap(q) => q.flusher(),
             QuantizedVectorStorage::ScalarRamMulti(q) => q.flusher(),
             QuantizedVectorStorage::ScalarMmapMulti(q) => q.flusher(),
             QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.flusher(),
@@ -1935,6 +2232,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryChunkedMmap(q) => {
                 Self::upsert_vector_dense(q, id, vector, hw_counter)
             }
+            QuantizedVectorStorage::ResidualRam(q) => {
+                Self::upsert_vector_dense(q, id, vector, hw_counter)
+            }
+            QuantizedVectorStorage::ResidualMmap(q) => {
+                Self::upsert_vector_dense(q, id, vector, hw_counter)
+            }
             QuantizedVectorStorage::ScalarRamMulti(q) => {
                 Self::upsert_vector_multi(q, id, vector, hw_counter)
             }
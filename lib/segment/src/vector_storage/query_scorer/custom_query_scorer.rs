@@ -50,7 +50,7 @@ impl<
         let query = query
             .transform(|vector| {
                 dim = vector.len();
-                let preprocessed_vector = TMetric::preprocess(vector);
+                let preprocessed_vector = TMetric::preprocess_query(vector);
                 Ok(TypedDenseVector::from(TElement::slice_from_float_cow(
                     Cow::from(preprocessed_vector),
                 )))
@@ -113,7 +113,7 @@ impl<
 
         self.query.score_by(|example| {
             cpu_counter.incr();
-            TMetric::similarity(example, against)
+            TMetric::query_similarity(example, against)
         })
     }
 
@@ -39,7 +39,7 @@ impl<
         mut hardware_counter: HardwareCounterCell,
     ) -> Self {
         let dim = query.len();
-        let preprocessed_vector = TMetric::preprocess(query);
+        let preprocessed_vector = TMetric::preprocess_query(query);
 
         hardware_counter.set_cpu_multiplier(dim * size_of::<TElement>());
         if vector_storage.is_on_disk() {
@@ -71,7 +71,7 @@ impl<
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
         self.hardware_counter.cpu_counter().incr();
         self.hardware_counter.vector_io_read().incr();
-        TMetric::similarity(&self.query, self.vector_storage.get_dense::<Random>(idx))
+        TMetric::query_similarity(&self.query, self.vector_storage.get_dense::<Random>(idx))
     }
 
     fn score_stored_batch(&self, ids: &[PointOffsetType], scores: &mut [ScoreType]) {
@@ -87,14 +87,14 @@ impl<
         self.hardware_counter.vector_io_read().incr_delta(ids.len());
 
         for idx in 0..ids.len() {
-            scores[idx] = TMetric::similarity(&self.query, vectors[idx]);
+            scores[idx] = TMetric::query_similarity(&self.query, vectors[idx]);
         }
     }
 
     #[inline]
     fn score(&self, v2: &[TElement]) -> ScoreType {
         self.hardware_counter.cpu_counter().incr();
-        TMetric::similarity(&self.query, v2)
+        TMetric::query_similarity(&self.query, v2)
     }
 
     fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
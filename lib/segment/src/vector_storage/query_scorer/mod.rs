@@ -74,6 +74,14 @@ impl<TQueryScorer: QueryScorer> QueryScorerBytes for QueryScorerBytesImpl<TQuery
 pub fn score_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     multi_dense_a: TypedMultiDenseVectorRef<'_, T>,
     multi_dense_b: TypedMultiDenseVectorRef<'_, T>,
+) -> ScoreType {
+    score_max_similarity_by(multi_dense_a, multi_dense_b, TMetric::similarity)
+}
+
+fn score_max_similarity_by<T: PrimitiveVectorElement>(
+    multi_dense_a: TypedMultiDenseVectorRef<'_, T>,
+    multi_dense_b: TypedMultiDenseVectorRef<'_, T>,
+    similarity: impl Fn(&[T], &[T]) -> ScoreType,
 ) -> ScoreType {
     debug_assert!(!multi_dense_a.is_empty());
     debug_assert!(!multi_dense_b.is_empty());
@@ -82,7 +90,7 @@ pub fn score_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
         let mut max_sim = ScoreType::NEG_INFINITY;
         // manual `max_by` for performance
         for dense_b in multi_dense_b.multi_vectors() {
-            let sim = TMetric::similarity(dense_a, dense_b);
+            let sim = similarity(dense_a, dense_b);
             if sim > max_sim {
                 max_sim = sim;
             }
@@ -105,6 +113,19 @@ fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     }
 }
 
+/// Same as [`score_multi`], for a query transformed with [`Metric::preprocess_query`]
+fn score_multi_query<T: PrimitiveVectorElement, TMetric: Metric<T>>(
+    multi_vector_config: &MultiVectorConfig,
+    query: TypedMultiDenseVectorRef<'_, T>,
+    stored: TypedMultiDenseVectorRef<'_, T>,
+) -> ScoreType {
+    match multi_vector_config.comparator {
+        MultiVectorComparator::MaxSim => {
+            score_max_similarity_by(query, stored, TMetric::query_similarity)
+        }
+    }
+}
+
 /// Check if ids are rather contiguous to enable further optimizations
 /// TODO: this can be smarter, but requires experiments with actual mmap behaviour
 /// TODO: For example
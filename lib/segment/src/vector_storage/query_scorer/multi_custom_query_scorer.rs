@@ -5,7 +5,7 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::typelevel::False;
 use common::types::{PointOffsetType, ScoreType};
 
-use super::score_multi;
+use super::score_multi_query;
 use crate::data_types::named_vectors::CowMultiVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
@@ -54,7 +54,7 @@ impl<
                 dim = vector.dim;
                 let mut preprocessed = DenseVector::new();
                 for slice in vector.multi_vectors() {
-                    preprocessed.extend_from_slice(&TMetric::preprocess(slice.to_vec()));
+                    preprocessed.extend_from_slice(&TMetric::preprocess_query(slice.to_vec()));
                 }
                 let preprocessed = MultiDenseVectorInternal::new(preprocessed, vector.dim);
                 let converted =
@@ -97,7 +97,7 @@ impl<
         self.query.score_by(|example| {
             cpu_counter.incr_delta(example.vectors_count() * against_vector_count);
 
-            score_multi::<TElement, TMetric>(
+            score_multi_query::<TElement, TMetric>(
                 self.vector_storage.multi_vector_config(),
                 TypedMultiDenseVectorRef::from(example),
                 against,
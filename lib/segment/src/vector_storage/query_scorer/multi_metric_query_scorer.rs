@@ -5,7 +5,7 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::typelevel::False;
 use common::types::{PointOffsetType, ScoreType};
 
-use super::score_multi;
+use super::{score_multi, score_multi_query};
 use crate::data_types::named_vectors::CowMultiVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
@@ -42,7 +42,7 @@ impl<
     ) -> Self {
         let mut preprocessed = DenseVector::new();
         for slice in query.multi_vectors() {
-            preprocessed.extend_from_slice(&TMetric::preprocess(slice.to_vec()));
+            preprocessed.extend_from_slice(&TMetric::preprocess_query(slice.to_vec()));
         }
         let preprocessed = MultiDenseVectorInternal::new(preprocessed, query.dim);
 
@@ -80,7 +80,12 @@ impl<
     }
 
     fn score_ref(&self, v2: TypedMultiDenseVectorRef<TElement>) -> ScoreType {
-        self.score_multi(TypedMultiDenseVectorRef::from(&self.query), v2)
+        let query = TypedMultiDenseVectorRef::from(&self.query);
+        self.hardware_counter
+            .cpu_counter()
+            .incr_delta(query.vectors_count() * v2.vectors_count());
+
+        score_multi_query::<TElement, TMetric>(self.vector_storage.multi_vector_config(), query, v2)
     }
 }
 
@@ -99,15 +104,12 @@ impl<
             .vector_io_read()
             .incr_delta(stored.vectors_count());
 
-        self.score_multi(TypedMultiDenseVectorRef::from(&self.query), stored)
+        self.score_ref(stored)
     }
 
     #[inline]
     fn score(&self, v2: &TypedMultiDenseVector<TElement>) -> ScoreType {
-        self.score_multi(
-            TypedMultiDenseVectorRef::from(&self.query),
-            TypedMultiDenseVectorRef::from(v2),
-        )
+        self.score_ref(TypedMultiDenseVectorRef::from(v2))
     }
 
     fn score_stored_batch(&self, ids: &[PointOffsetType], scores: &mut [ScoreType]) {
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
@@ -13,14 +14,20 @@ use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::query_scorer::{QueryScorerBytes, QueryScorerBytesImpl};
-use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
+use super::{
+    DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorage, VectorStorageEnum,
+};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     DenseVector, MultiDenseVectorInternal, QueryVector, VectorInternal,
 };
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+    RawCosineMetric,
+};
+use crate::spaces::wasm_metric::{self, CustomMetric, WasmMetricModule};
 use crate::types::Distance;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
 use crate::vector_storage::query_scorer::QueryScorer;
@@ -54,6 +61,21 @@ pub fn new_raw_scorer<'a>(
     query: QueryVector,
     vector_storage: &'a VectorStorageEnum,
     hc: HardwareCounterCell,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let raw_scorer = new_storage_raw_scorer(query, vector_storage, hc)?;
+    match vector_storage.custom_metric() {
+        Some(module) => Ok(Box::new(CustomMetricRawScorer {
+            raw_scorer,
+            module: module.clone(),
+        })),
+        None => Ok(raw_scorer),
+    }
+}
+
+fn new_storage_raw_scorer<'a>(
+    query: QueryVector,
+    vector_storage: &'a VectorStorageEnum,
+    hc: HardwareCounterCell,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match vector_storage {
         #[cfg(feature = "rocksdb")]
@@ -132,6 +154,35 @@ pub fn new_raw_scorer<'a>(
     }
 }
 
+/// Raw scorer of a storage with [`Distance::Custom`], which scores with the module of the storage
+struct CustomMetricRawScorer<'a> {
+    raw_scorer: Box<dyn RawScorer + 'a>,
+    module: Arc<WasmMetricModule>,
+}
+
+impl RawScorer for CustomMetricRawScorer<'_> {
+    fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoreType]) {
+        wasm_metric::with_module(&self.module, || {
+            self.raw_scorer.score_points(points, scores)
+        })
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        wasm_metric::with_module(&self.module, || self.raw_scorer.score_point(point))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        wasm_metric::with_module(&self.module, || {
+            self.raw_scorer.score_internal(point_a, point_b)
+        })
+    }
+
+    fn scorer_bytes(&self) -> Option<&dyn QueryScorerBytes> {
+        // Bytes are scored outside of the module scope
+        None
+    }
+}
+
 pub static DEFAULT_STOPPED: AtomicBool = AtomicBool::new(false);
 
 pub fn raw_sparse_scorer_volatile<'a>(
@@ -224,6 +275,8 @@ where
     EuclidMetric: Metric<TElement>,
     DotProductMetric: Metric<TElement>,
     ManhattanMetric: Metric<TElement>,
+    HammingMetric: Metric<TElement>,
+    JaccardMetric: Metric<TElement>,
 {
     match vector_storage.distance() {
         Distance::Cosine => new_scorer_with_metric::<TElement, CosineMetric, _>(
@@ -246,6 +299,26 @@ where
             vector_storage,
             hardware_counter,
         ),
+        Distance::Hamming => new_scorer_with_metric::<TElement, HammingMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::Jaccard => new_scorer_with_metric::<TElement, JaccardMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::Custom => new_scorer_with_metric::<TElement, CustomMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::RawCosine => new_scorer_with_metric::<TElement, RawCosineMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
     }
 }
 
@@ -327,6 +400,8 @@ where
     EuclidMetric: Metric<TElement>,
     DotProductMetric: Metric<TElement>,
     ManhattanMetric: Metric<TElement>,
+    HammingMetric: Metric<TElement>,
+    JaccardMetric: Metric<TElement>,
 {
     match vector_storage.distance() {
         Distance::Cosine => new_multi_scorer_with_metric::<_, CosineMetric, _>(
@@ -349,6 +424,26 @@ where
             vector_storage,
             hardware_counter,
         ),
+        Distance::Hamming => new_multi_scorer_with_metric::<_, HammingMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::Jaccard => new_multi_scorer_with_metric::<_, JaccardMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::Custom => new_multi_scorer_with_metric::<_, CustomMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
+        Distance::RawCosine => new_multi_scorer_with_metric::<_, RawCosineMetric, _>(
+            query,
+            vector_storage,
+            hardware_counter,
+        ),
     }
 }
 
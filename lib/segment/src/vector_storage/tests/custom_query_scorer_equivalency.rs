@@ -69,6 +69,8 @@ fn scalar_u8() -> WithQuantization {
         r#type: crate::types::ScalarType::Int8,
         quantile: Some(0.5),
         always_ram: Some(true),
+        placement: None,
+        auto_quantile: None,
     }
     .into();
 
@@ -83,6 +85,7 @@ fn product_x4() -> WithQuantization {
     let config = ProductQuantizationConfig {
         compression: crate::types::CompressionRatio::X4,
         always_ram: Some(true),
+        placement: None,
     }
     .into();
 
@@ -95,6 +98,7 @@ fn product_x4() -> WithQuantization {
 fn binary() -> WithQuantization {
     let config = BinaryQuantizationConfig {
         always_ram: Some(true),
+        placement: None,
         encoding: None,
         query_encoding: None,
     }
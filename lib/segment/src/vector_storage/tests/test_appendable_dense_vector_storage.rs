@@ -12,16 +12,22 @@ use crate::data_types::vectors::QueryVector;
 use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
-use crate::types::{Distance, PointIdType, QuantizationConfig, ScalarQuantizationConfig};
+use crate::types::{
+    Distance, PointIdType, QuantizationConfig, QuantizationPlacement, ScalarQuantizationConfig,
+};
+#[cfg(feature = "rocksdb")]
+use crate::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use crate::vector_storage::dense::appendable_dense_vector_storage::open_appendable_memmap_vector_storage;
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_full_vector_storage;
-use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
+use crate::vector_storage::dense::volatile_dense_vector_storage::{
+    VolatileDenseVectorStorage, new_volatile_dense_vector_storage,
+};
 use crate::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
 };
 use crate::vector_storage::{
-    DEFAULT_STOPPED, Random, VectorStorage, VectorStorageEnum, new_raw_scorer,
+    DEFAULT_STOPPED, DenseVectorStorage, Random, VectorStorage, VectorStorageEnum, new_raw_scorer,
 };
 
 fn do_test_delete_points(storage: &mut VectorStorageEnum) {
@@ -283,6 +289,8 @@ fn test_score_quantized_points(storage: &mut VectorStorageEnum) {
         r#type: Default::default(),
         quantile: None,
         always_ram: None,
+        placement: None,
+        auto_quantile: None,
     }
     .into();
 
@@ -355,6 +363,7 @@ fn test_delete_points_in_simple_vector_storages() {
             DB_VECTOR_CF,
             4,
             Distance::Dot,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
@@ -368,6 +377,7 @@ fn test_delete_points_in_simple_vector_storages() {
         DB_VECTOR_CF,
         4,
         Distance::Dot,
+        ChunkedVectorsConfig::default(),
         &AtomicBool::new(false),
     )
     .unwrap();
@@ -384,6 +394,7 @@ fn test_update_from_delete_points_simple_vector_storages() {
             DB_VECTOR_CF,
             4,
             Distance::Dot,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
@@ -397,6 +408,7 @@ fn test_update_from_delete_points_simple_vector_storages() {
         DB_VECTOR_CF,
         4,
         Distance::Dot,
+        ChunkedVectorsConfig::default(),
         &AtomicBool::new(false),
     )
     .unwrap();
@@ -413,6 +425,7 @@ fn test_score_points_in_simple_vector_storages() {
             DB_VECTOR_CF,
             4,
             Distance::Dot,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
@@ -426,6 +439,7 @@ fn test_score_points_in_simple_vector_storages() {
         DB_VECTOR_CF,
         4,
         Distance::Dot,
+        ChunkedVectorsConfig::default(),
         &AtomicBool::new(false),
     )
     .unwrap();
@@ -442,6 +456,7 @@ fn test_score_quantized_points_simple_vector_storages() {
             DB_VECTOR_CF,
             4,
             Distance::Dot,
+            ChunkedVectorsConfig::default(),
             &AtomicBool::new(false),
         )
         .unwrap();
@@ -455,6 +470,7 @@ fn test_score_quantized_points_simple_vector_storages() {
         DB_VECTOR_CF,
         4,
         Distance::Dot,
+        ChunkedVectorsConfig::default(),
         &AtomicBool::new(false),
     )
     .unwrap();
@@ -486,6 +502,79 @@ fn test_score_quantized_points_volatile_vector_storages() {
     test_score_quantized_points(&mut storage);
 }
 
+#[test]
+fn test_quantized_vectors_placement_volatile_vector_storages() {
+    let hw_counter = HardwareCounterCell::new();
+    let mut storage = new_volatile_dense_vector_storage(4, Distance::Dot);
+    for i in 0..5 {
+        storage
+            .insert_vector(i, [1.0, 0.0, 1.0, i as f32].as_slice().into(), &hw_counter)
+            .unwrap();
+    }
+
+    let stopped = AtomicBool::new(false);
+    for (placement, expected_on_disk) in [
+        (None, false),
+        (Some(QuantizationPlacement::Ram), false),
+        (Some(QuantizationPlacement::Mmap), true),
+        (Some(QuantizationPlacement::MmapLocked), true),
+    ] {
+        let dir = Builder::new()
+            .prefix("quantization_path")
+            .tempdir()
+            .unwrap();
+        let config: QuantizationConfig = ScalarQuantizationConfig {
+            r#type: Default::default(),
+            quantile: None,
+            always_ram: None,
+            placement,
+            auto_quantile: None,
+        }
+        .into();
+        let quantized_vectors = QuantizedVectors::create(
+            &storage,
+            &config,
+            QuantizedVectorsStorageType::Immutable,
+            dir.path(),
+            1,
+            &stopped,
+        )
+        .unwrap();
+        assert_eq!(
+            quantized_vectors.get_storage().is_on_disk(),
+            expected_on_disk,
+            "placement: {placement:?}",
+        );
+    }
+}
+
+#[test]
+fn test_iter_vectors_volatile_vector_storages() {
+    let points = [
+        vec![1.0, 0.0, 1.0, 1.0],
+        vec![1.0, 0.0, 1.0, 0.0],
+        vec![1.0, 1.0, 1.0, 1.0],
+    ];
+    let hw_counter = HardwareCounterCell::new();
+    let mut storage = VolatileDenseVectorStorage::<f32>::new(4, Distance::Dot);
+    for (i, vec) in points.iter().enumerate() {
+        storage
+            .insert_vector(i as PointOffsetType, vec.as_slice().into(), &hw_counter)
+            .unwrap();
+    }
+    // Deleted vectors are still iterated
+    storage.delete_vector(1).unwrap();
+
+    let vectors: Vec<_> = storage.iter_vectors().collect();
+    assert_eq!(vectors.len(), points.len());
+    for ((id, vector), (expected_id, expected)) in
+        vectors.into_iter().zip(points.iter().enumerate())
+    {
+        assert_eq!(id, expected_id as PointOffsetType);
+        assert_eq!(vector, expected.as_slice());
+    }
+}
+
 // ----------------------------------------------
 
 #[test]
@@ -271,6 +271,7 @@ fn create_vector_storage(
         #[cfg(feature = "rocksdb")]
         MultiDenseStorageType::RocksDbFloat => {
             use crate::common::rocksdb_wrapper::{DB_VECTOR_CF, open_db};
+            use crate::vector_storage::chunked_vectors::ChunkedVectorsConfig;
             use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage_full;
 
             let db = open_db(path, &[DB_VECTOR_CF]).unwrap();
@@ -280,6 +281,7 @@ fn create_vector_storage(
                 vec_dim,
                 Distance::Dot,
                 MultiVectorConfig::default(),
+                ChunkedVectorsConfig::default(),
                 &Default::default(),
             )
             .unwrap()
@@ -2,6 +2,7 @@ use std::alloc::Layout;
 use std::mem::MaybeUninit;
 use std::ops::Range;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
@@ -31,6 +32,7 @@ use crate::data_types::vectors::{
     MultiDenseVectorInternal, TypedMultiDenseVectorRef, VectorElementType, VectorElementTypeByte,
     VectorElementTypeHalf, VectorInternal, VectorRef,
 };
+use crate::spaces::wasm_metric::WasmMetricModule;
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
@@ -63,6 +65,13 @@ impl AccessPattern for Sequential {
 pub trait VectorStorage {
     fn distance(&self) -> Distance;
 
+    /// Custom metric module, which scores vectors of [`Distance::Custom`]
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        None
+    }
+
+    fn set_custom_metric(&mut self, _module: Option<Arc<WasmMetricModule>>) {}
+
     fn datatype(&self) -> VectorStorageDatatype;
 
     fn is_on_disk(&self) -> bool;
@@ -177,6 +186,13 @@ pub trait DenseVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
         maybe_uninit_fill_from(vectors, iter).0
     }
 
+    /// Iterate over all stored vectors in the order of internal IDs, without copying them
+    ///
+    /// Includes soft deleted vectors, consumers are expected to skip them if needed.
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, &[T])> + Clone + Send
+    where
+        Self: Sync;
+
     fn size_of_available_vectors_in_bytes(&self) -> usize {
         self.available_vector_count() * self.vector_dim() * std::mem::size_of::<T>()
     }
@@ -798,6 +814,98 @@ impl VectorStorage for VectorStorageEnum {
         }
     }
 
+    fn custom_metric(&self) -> Option<&Arc<WasmMetricModule>> {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(v) => v.custom_metric(),
+            VectorStorageEnum::DenseVolatile(v) => v.custom_metric(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(v) => v.custom_metric(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(v) => v.custom_metric(),
+            VectorStorageEnum::DenseMemmap(v) => v.custom_metric(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.custom_metric(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableInRam(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableInRamByte(v) => v.custom_metric(),
+            VectorStorageEnum::DenseAppendableInRamHalf(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(v) => v.custom_metric(),
+            VectorStorageEnum::SparseVolatile(v) => v.custom_metric(),
+            VectorStorageEnum::SparseMmap(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.custom_metric(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseVolatile(v) => v.custom_metric(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(v) => v.custom_metric(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableInRam(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.custom_metric(),
+            VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.custom_metric(),
+        }
+    }
+
+    fn set_custom_metric(&mut self, module: Option<Arc<WasmMetricModule>>) {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseVolatile(v) => v.set_custom_metric(module),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(v) => v.set_custom_metric(module),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseMemmap(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseMemmapByte(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableInRam(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableInRamByte(v) => v.set_custom_metric(module),
+            VectorStorageEnum::DenseAppendableInRamHalf(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(v) => v.set_custom_metric(module),
+            VectorStorageEnum::SparseVolatile(v) => v.set_custom_metric(module),
+            VectorStorageEnum::SparseMmap(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.set_custom_metric(module),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseVolatile(v) => v.set_custom_metric(module),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(v) => v.set_custom_metric(module),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableInRam(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.set_custom_metric(module),
+            VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.set_custom_metric(module),
+        }
+    }
+
     fn datatype(&self) -> VectorStorageDatatype {
         match self {
             #[cfg(feature = "rocksdb")]
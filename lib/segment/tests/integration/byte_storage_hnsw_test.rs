@@ -179,6 +179,8 @@ fn test_byte_storage_hnsw(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
@@ -292,15 +292,19 @@ fn test_byte_storage_binary_quantization_hnsw(
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
         QuantizationVariant::PQ => ProductQuantizationConfig {
             compression: CompressionRatio::X8,
             always_ram: None,
+            placement: None,
         }
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
             always_ram: None,
+            placement: None,
             encoding: None,
             query_encoding: None,
         }
@@ -332,6 +336,8 @@ fn test_byte_storage_binary_quantization_hnsw(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
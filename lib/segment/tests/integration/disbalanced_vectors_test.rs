@@ -15,6 +15,7 @@ use segment::segment_constructor::simple_segment_constructor::{
 };
 use segment::types::{Distance, HnswGlobalConfig};
 use segment::vector_storage::VectorStorage;
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use tempfile::Builder;
 
 #[test]
@@ -95,6 +96,7 @@ fn test_rebuild_with_removed_vectors() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -79,6 +79,8 @@ fn exact_search_test() {
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     payload_index_ptr
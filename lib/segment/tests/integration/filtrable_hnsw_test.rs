@@ -5,7 +5,7 @@ use std::sync::atomic::AtomicBool;
 use common::budget::ResourcePermit;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::flags::FeatureFlags;
-use common::types::{PointOffsetType, TelemetryDetail};
+use common::types::{DetailsLevel, PointOffsetType, TelemetryDetail};
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
@@ -21,8 +21,8 @@ use segment::payload_json;
 use segment::segment_constructor::VectorIndexBuildArgs;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
 use segment::types::{
-    Condition, Distance, FieldCondition, Filter, HnswConfig, HnswGlobalConfig, PayloadSchemaType,
-    Range, SearchParams, SeqNumberType,
+    AcornSearchParams, Condition, Distance, FieldCondition, Filter, HnswConfig, HnswGlobalConfig,
+    PayloadSchemaType, Range, SearchParams, SeqNumberType,
 };
 use tempfile::Builder;
 
@@ -33,22 +33,25 @@ fn random_query<R: Rng + ?Sized>(variant: &QueryVariant, rng: &mut R, dim: usize
 }
 
 #[rstest]
-#[case::nearest(QueryVariant::Nearest, 32, 5)]
-#[case::discovery(QueryVariant::Discovery, 128, 10)] // tests that check better precision are in `hnsw_discover_test.rs`
-#[case::reco_best_score(QueryVariant::RecoBestScore, 64, 10)]
-#[case::reco_sum_scores(QueryVariant::RecoSumScores, 64, 10)]
+#[case::nearest(QueryVariant::Nearest, 32, 5, false)]
+#[case::nearest_acorn(QueryVariant::Nearest, 32, 5, true)] // recall gain is checked in `graph_layers.rs` tests
+#[case::discovery(QueryVariant::Discovery, 128, 10, false)] // tests that check better precision are in `hnsw_discover_test.rs`
+#[case::reco_best_score(QueryVariant::RecoBestScore, 64, 10, false)]
+#[case::reco_sum_scores(QueryVariant::RecoSumScores, 64, 10, false)]
 fn test_filterable_hnsw(
     #[case] query_variant: QueryVariant,
     #[case] ef: usize,
     #[case] max_failures: usize, // out of 100
+    #[case] acorn: bool,
 ) {
-    _test_filterable_hnsw(query_variant, ef, max_failures);
+    _test_filterable_hnsw(query_variant, ef, max_failures, acorn);
 }
 
 fn _test_filterable_hnsw(
     query_variant: QueryVariant,
     ef: usize,
     max_failures: usize, // out of 100
+    acorn: bool,
 ) {
     let stopped = AtomicBool::new(false);
 
@@ -100,6 +103,8 @@ fn _test_filterable_hnsw(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -199,6 +204,10 @@ fn _test_filterable_hnsw(
                 top,
                 Some(&SearchParams {
                     hnsw_ef: Some(ef),
+                    acorn: acorn.then_some(AcornSearchParams {
+                        enable: true,
+                        max_selectivity: Some(1.0),
+                    }),
                     ..Default::default()
                 }),
                 &Default::default(),
@@ -229,4 +238,25 @@ fn _test_filterable_hnsw(
         "hits: {hits} of {attempts}"
     ); // Not more than X% failures
     eprintln!("hits = {hits:#?} out of {attempts}");
+
+    // Graph telemetry is only collected at the detailed level
+    assert!(
+        hnsw_index
+            .get_telemetry_data(TelemetryDetail::default())
+            .hnsw
+            .is_none()
+    );
+
+    let hnsw_telemetry = hnsw_index
+        .get_telemetry_data(TelemetryDetail {
+            level: DetailsLevel::Level4,
+            histograms: false,
+        })
+        .hnsw
+        .unwrap();
+    assert_eq!(hnsw_telemetry.graph.layer_sizes[0], num_vectors as usize);
+    assert!(hnsw_telemetry.graph.build_duration_ms.is_some());
+    assert_eq!(hnsw_telemetry.filtered_searches, attempts);
+    assert_eq!(hnsw_telemetry.unfiltered_searches, 0);
+    assert!(hnsw_telemetry.avg_hops.is_some_and(|hops| hops > 0.0));
 }
@@ -26,8 +26,9 @@ use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{
     CompressionRatio, Condition, Distance, FieldCondition, Filter, HnswConfig, HnswGlobalConfig,
     Indexes, ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams,
-    ScalarQuantizationConfig, SearchParams,
+    ResidualQuantizationConfig, ScalarQuantizationConfig, SearchParams,
 };
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use segment::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
 };
@@ -117,6 +118,8 @@ fn hnsw_quantized_search_test(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
@@ -325,6 +328,8 @@ fn hnsw_quantized_search_cosine_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
     );
@@ -339,6 +344,8 @@ fn hnsw_quantized_search_euclid_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
     );
@@ -353,6 +360,8 @@ fn hnsw_quantized_search_manhattan_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
     );
@@ -366,6 +375,7 @@ fn hnsw_product_quantization_cosine_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            placement: None,
         }
         .into(),
     );
@@ -379,6 +389,7 @@ fn hnsw_product_quantization_euclid_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            placement: None,
         }
         .into(),
     );
@@ -392,6 +403,46 @@ fn hnsw_product_quantization_manhattan_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            placement: None,
+        }
+        .into(),
+    );
+}
+
+#[test]
+fn hnsw_residual_quantization_cosine_test() {
+    hnsw_quantized_search_test(
+        Distance::Cosine,
+        1003,
+        ResidualQuantizationConfig {
+            always_ram: Some(true),
+            placement: None,
+        }
+        .into(),
+    );
+}
+
+#[test]
+fn hnsw_residual_quantization_euclid_test() {
+    hnsw_quantized_search_test(
+        Distance::Euclid,
+        1003,
+        ResidualQuantizationConfig {
+            always_ram: Some(true),
+            placement: None,
+        }
+        .into(),
+    );
+}
+
+#[test]
+fn hnsw_residual_quantization_manhattan_test() {
+    hnsw_quantized_search_test(
+        Distance::Manhattan,
+        1003,
+        ResidualQuantizationConfig {
+            always_ram: Some(true),
+            placement: None,
         }
         .into(),
     );
@@ -413,6 +464,8 @@ fn test_build_hnsw_using_quantization() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
     );
@@ -424,6 +477,8 @@ fn test_build_hnsw_using_quantization() {
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     });
 
     let permit_cpu_count = num_rayon_threads(0);
@@ -435,6 +490,7 @@ fn test_build_hnsw_using_quantization() {
         temp_dir.path(),
         &config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -267,15 +267,19 @@ fn test_multivector_quantization_hnsw(
             r#type: Default::default(),
             quantile: None,
             always_ram: Some(false),
+            placement: None,
+            auto_quantile: None,
         }
         .into(),
         QuantizationVariant::PQ => ProductQuantizationConfig {
             compression: CompressionRatio::X8,
             always_ram: Some(false),
+            placement: None,
         }
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
             always_ram: Some(false),
+            placement: None,
             encoding: None,
             query_encoding: None,
         }
@@ -315,6 +319,8 @@ fn test_multivector_quantization_hnsw(
         on_disk: Some(false),
         payload_m: None,
         copy_vectors: None,
+        gpu_indexing: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
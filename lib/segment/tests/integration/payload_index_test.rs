@@ -47,6 +47,7 @@ use segment::types::{
     Range, SegmentConfig, ValueVariants, VectorDataConfig, VectorStorageType, WithPayload,
 };
 use segment::utils::scored_point_ties::ScoredPointTies;
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use tempfile::{Builder, TempDir};
 
 macro_rules! here {
@@ -285,6 +286,7 @@ impl TestSegments {
             &path.with_extension("tmp"),
             &Self::make_simple_config(false),
             &HnswGlobalConfig::default(),
+            ChunkedVectorsConfig::default(),
         )
         .unwrap();
 
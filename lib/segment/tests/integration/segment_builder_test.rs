@@ -20,6 +20,7 @@ use segment::types::{
     Distance, HnswGlobalConfig, Indexes, PayloadContainer, PayloadFieldSchema, PayloadKeyType,
     PayloadSchemaType, PayloadStorageType, SegmentConfig, VectorDataConfig, VectorStorageType,
 };
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use serde_json::Value;
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
@@ -45,6 +46,7 @@ fn test_building_new_segment() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -133,6 +135,7 @@ fn test_building_new_defragmented_segment() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -265,6 +268,7 @@ fn test_building_new_sparse_segment() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -352,6 +356,7 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u6
         temp_dir.path(),
         &segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -408,6 +413,7 @@ fn test_building_new_segment_bug_5614() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
@@ -573,6 +579,7 @@ fn test_building_new_segment_with_mmap_payload() {
         temp_dir.path(),
         &segment1.segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
 
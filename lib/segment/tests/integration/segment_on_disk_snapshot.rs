@@ -19,6 +19,7 @@ use segment::types::{
     Distance, HnswConfig, Indexes, PayloadFieldSchema, PayloadSchemaParams, PayloadStorageType,
     SegmentConfig, SnapshotFormat, VectorDataConfig, VectorStorageType,
 };
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use tempfile::Builder;
 
 /// This test tests snapshotting and restoring a segment with all on-disk components.
@@ -117,6 +118,8 @@ fn test_on_disk_segment_snapshot(#[case] format: SnapshotFormat) {
                     on_disk: Some(true), // mmap index
                     payload_m: None,
                     copy_vectors: None,
+                    gpu_indexing: None,
+                    seed: None,
                 }),
                 quantization_config: None,
                 multivector_config: None,
@@ -134,6 +137,7 @@ fn test_on_disk_segment_snapshot(#[case] format: SnapshotFormat) {
         segment_builder_dir.path(),
         &segment_config,
         &HnswGlobalConfig::default(),
+        ChunkedVectorsConfig::default(),
     )
     .unwrap();
     segment_builder.update(&[&segment], &false.into()).unwrap();
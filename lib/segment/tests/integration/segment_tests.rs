@@ -2,10 +2,12 @@ use std::iter::FromIterator;
 use std::sync::atomic::AtomicBool;
 
 use ahash::AHashSet;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_counter::HardwareCounterCell;
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::query_context::QueryContext;
 use segment::data_types::vectors::{
     DEFAULT_VECTOR_NAME, VectorRef, VectorStructInternal, only_default_vector,
 };
@@ -275,6 +277,10 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        max_scored_points: None,
+        acorn: None,
+        ivf_probes: None,
+        disk_ann_list_size: None,
     };
     let nearest_upsert = segment
         .search(
@@ -347,3 +353,62 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_search_scoring_budget() {
+    let num_points = 100;
+    let dim = 4;
+    let mut rng = rand::rng();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Dot).unwrap();
+
+    for i in 0..num_points {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(i, i.into(), only_default_vector(&vector), &hw_counter)
+            .unwrap();
+    }
+
+    let query_vector = random_vector(&mut rng, dim).into();
+
+    let search = |max_scored_points| {
+        let hw_acc = HwMeasurementAcc::new();
+        let query_context = QueryContext::new(usize::MAX, hw_acc.clone());
+        let search_params = SearchParams {
+            max_scored_points,
+            ..Default::default()
+        };
+        let result = segment
+            .search_batch(
+                DEFAULT_VECTOR_NAME,
+                &[&query_vector],
+                &false.into(),
+                &false.into(),
+                None,
+                10,
+                Some(&search_params),
+                &query_context.get_segment_query_context(),
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+        (result, hw_acc.is_search_truncated())
+    };
+
+    let (full_result, truncated) = search(None);
+    assert_eq!(full_result.len(), 10);
+    assert!(!truncated);
+
+    // Budget covers all points, result is complete
+    let (result, truncated) = search(Some(num_points as usize));
+    assert_eq!(result, full_result);
+    assert!(!truncated);
+
+    // Only the best of the scored points are returned
+    let (result, truncated) = search(Some(5));
+    assert_eq!(result.len(), 5);
+    assert!(truncated);
+}
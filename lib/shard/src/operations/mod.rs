@@ -104,6 +104,19 @@ pub struct OperationWithClockTag {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub clock_tag: Option<ClockTag>,
+
+    /// Time the operation was written to WAL, in milliseconds since the Unix epoch
+    ///
+    /// Only set if point history is enabled, to record the same time on WAL replay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+
+    /// Clock tags of upserts merged into this operation
+    ///
+    /// Concurrent upserts may be written to WAL as a single record, which keeps the clock tag of
+    /// each of them. Such a record has no `clock_tag` of its own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_clock_tags: Vec<ClockTag>,
 }
 
 impl OperationWithClockTag {
@@ -114,8 +127,44 @@ impl OperationWithClockTag {
         Self {
             operation: operation.into(),
             clock_tag,
+            timestamp: None,
+            merged_clock_tags: Vec::new(),
         }
     }
+
+    /// Clock tags of all operations in this record
+    pub fn clock_tags(&self) -> impl Iterator<Item = ClockTag> + '_ {
+        self.clock_tag
+            .into_iter()
+            .chain(self.merged_clock_tags.iter().copied())
+    }
+
+    /// Split a record of merged upserts into records with a single clock tag each
+    ///
+    /// The first record holds the whole operation. Others are empty upserts, which only advance
+    /// clocks of the merged operations.
+    pub fn split_merged(mut self) -> Vec<Self> {
+        if self.merged_clock_tags.is_empty() {
+            return vec![self];
+        }
+
+        let clock_tags = std::mem::take(&mut self.merged_clock_tags);
+        let mut clock_tags = clock_tags.into_iter();
+
+        self.clock_tag = clock_tags.next();
+
+        let mut operations = Vec::with_capacity(clock_tags.len() + 1);
+        operations.push(self);
+        operations.extend(clock_tags.map(|clock_tag| {
+            let operation = CollectionUpdateOperations::PointOperation(
+                point_ops::PointOperations::UpsertPoints(
+                    point_ops::PointInsertOperationsInternal::PointsList(Vec::new()),
+                ),
+            );
+            Self::new(operation, Some(clock_tag))
+        }));
+        operations
+    }
 }
 
 impl From<CollectionUpdateOperations> for OperationWithClockTag {
@@ -395,6 +395,9 @@ pub struct ConditionalInsertOperationInternal {
     pub points_op: PointInsertOperationsInternal,
     /// Condition to check, if the point already exists
     pub condition: Filter,
+    /// Skip existing points, if their vectors and payload are identical to the upserted ones
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_unchanged: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Hash)]
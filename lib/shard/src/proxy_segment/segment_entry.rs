@@ -6,7 +6,7 @@ use std::sync::atomic::AtomicBool;
 
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::TelemetryDetail;
-use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::common::operation_error::{OperationError, OperationResult, SegmentFailedState};
 use segment::data_types::build_index_result::BuildFieldIndexResult;
 use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
@@ -15,9 +15,12 @@ use segment::data_types::query_context::{FormulaContext, QueryContext, SegmentQu
 use segment::data_types::vectors::{QueryVector, VectorInternal};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::{CardinalityEstimation, FieldIndex};
+use segment::index::hnsw_index::graph_stats::HnswGraphStats;
+use segment::index::hnsw_index::hnsw::HealedGraph;
 use segment::json_path::JsonPath;
-use segment::telemetry::SegmentTelemetry;
+use segment::telemetry::{QuantizationTelemetry, SegmentTelemetry};
 use segment::types::*;
+use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
 use super::{ProxyDeletedPoint, ProxyIndexChange, ProxySegment};
 use crate::locked_segment::LockedSegment;
@@ -955,6 +958,72 @@ impl SegmentEntry for ProxySegment {
         self.wrapped_segment.get().read().get_telemetry_data(detail)
     }
 
+    fn hnsw_graph_stats(&self) -> HashMap<VectorNameBuf, HnswGraphStats> {
+        // Write segment is never indexed, only report the wrapped one
+        self.wrapped_segment.get().read().hnsw_graph_stats()
+    }
+
+    fn warmup_files(&self) -> Vec<PathBuf> {
+        // Write segment only holds recent changes, only warm up the wrapped one
+        self.wrapped_segment.get().read().warmup_files()
+    }
+
+    fn quantization_telemetry(&self) -> Vec<QuantizationTelemetry> {
+        // Write segment is never quantized, only report the wrapped one
+        self.wrapped_segment.get().read().quantization_telemetry()
+    }
+
+    fn build_quantization(
+        &self,
+        _vector_name: &VectorName,
+        _quantization_config: &QuantizationConfig,
+        _max_threads: usize,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectors> {
+        Err(OperationError::service_error(
+            "Re-quantization of proxy segments is not supported",
+        ))
+    }
+
+    fn apply_quantization(
+        &mut self,
+        _vector_name: &VectorName,
+        _quantized_vectors: Option<QuantizedVectors>,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(
+            "Re-quantization of proxy segments is not supported",
+        ))
+    }
+
+    fn hnsw_graphs_to_heal(
+        &self,
+        _deleted_threshold: f64,
+        _min_deleted_count: usize,
+    ) -> Vec<VectorNameBuf> {
+        // Proxied segment is being optimized already
+        Vec::new()
+    }
+
+    fn heal_hnsw_graphs(
+        &self,
+        _vector_names: &[VectorNameBuf],
+        _max_threads: usize,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<Vec<(VectorNameBuf, HealedGraph)>> {
+        Err(OperationError::service_error(
+            "Healing HNSW graphs of proxy segments is not supported",
+        ))
+    }
+
+    fn apply_healed_hnsw_graphs(
+        &mut self,
+        _healed: Vec<(VectorNameBuf, HealedGraph)>,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(
+            "Healing HNSW graphs of proxy segments is not supported",
+        ))
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         // Information from temporary segment is not too important for query context
         self.wrapped_segment
@@ -1,28 +1,32 @@
 //! A collection of functions for updating points and payloads stored in segments
 
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use ahash::{AHashMap, AHashSet};
 use common::counter::hardware_counter::HardwareCounterCell;
 use itertools::iproduct;
-use parking_lot::{RwLock, RwLockWriteGuard};
+use parking_lot::{RwLock, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::build_index_result::BuildFieldIndexResult;
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::VectorRef;
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
+use segment::segment_constructor::load_segment;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     SeqNumberType, VectorNameBuf,
 };
 
+use crate::locked_segment::LockedSegment;
 use crate::operations::FieldIndexOperations;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{
     ConditionalInsertOperationInternal, PointOperations, PointStructPersisted,
 };
 use crate::operations::vector_ops::{PointVectorsPersisted, UpdateVectorsOp, VectorOperations};
-use crate::segment_holder::SegmentHolder;
+use crate::segment_holder::{SegmentHolder, SegmentId};
 
 pub fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
@@ -43,7 +47,9 @@ pub fn process_point_operation(
             delete_points(&segments.read(), op_num, &ids, hw_counter)
         }
         PointOperations::DeletePointsByFilter(filter) => {
-            delete_points_by_filter(&segments.read(), op_num, &filter, hw_counter)
+            let dropped = drop_segments_matching_filter(segments, op_num, &filter, hw_counter)?;
+            let deleted = delete_points_by_filter(&segments.read(), op_num, &filter, hw_counter)?;
+            Ok(dropped + deleted)
         }
         PointOperations::SyncPoints(operation) => {
             let (deleted, new, updated) = sync_points(
@@ -264,15 +270,24 @@ pub fn conditional_upsert(
     let ConditionalInsertOperationInternal {
         mut points_op,
         condition,
+        skip_unchanged,
     } = operation;
 
-    let point_ids = points_op.point_ids();
+    // Empty condition is used if only unchanged points are skipped, it matches all points
+    if condition != Filter::default() {
+        let point_ids = points_op.point_ids();
+        let points_to_exclude =
+            select_excluded_by_filter_ids(segments, point_ids, condition, hw_counter)?;
+        points_op.retain_point_ids(|idx| !points_to_exclude.contains(idx));
+    }
 
-    let points_to_exclude =
-        select_excluded_by_filter_ids(segments, point_ids, condition, hw_counter)?;
+    let mut points = points_op.into_point_vec();
+
+    if skip_unchanged {
+        let unchanged_points = select_unchanged_ids(segments, &points, hw_counter)?;
+        points.retain(|point| !unchanged_points.contains(&point.id));
+    }
 
-    points_op.retain_point_ids(|idx| !points_to_exclude.contains(idx));
-    let points = points_op.into_point_vec();
     let upserted_points = upsert_points(segments, op_num, points.iter(), hw_counter)?;
 
     if upserted_points == 0 {
@@ -396,6 +411,110 @@ pub fn delete_points_by_filter(
     Ok(total_deleted)
 }
 
+/// Drops segments in which all points match the given filter as a whole
+///
+/// Much cheaper than deleting points one by one, e.g. when a whole tenant is removed.
+/// Cardinality estimation selects candidate segments, which are then checked exactly.
+/// Segments under optimization are skipped, as is the last appendable segment.
+///
+/// Segments with a version newer than `op_num` may contain points written after this delete,
+/// e.g. during WAL replay, so they are skipped too. Skipped segments are left for the versioned
+/// per-point delete.
+///
+/// Returns number of points in the dropped segments.
+pub fn drop_segments_matching_filter(
+    segments: &RwLock<SegmentHolder>,
+    op_num: SeqNumberType,
+    filter: &Filter,
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<usize> {
+    // Upgradable lock prevents optimizers from proxying the candidate segments meanwhile
+    let segments_guard = segments.upgradable_read();
+
+    // we don’t want to cancel this filtered read
+    let is_stopped = AtomicBool::new(false);
+    let mut droppable_appendable = segments_guard.appendable_segments_ids().len();
+    let to_drop: Vec<SegmentId> = segments_guard
+        .iter()
+        .filter_map(|(segment_id, segment)| {
+            let LockedSegment::Original(segment) = segment else {
+                return None;
+            };
+            let segment = segment.read();
+            if segment.version() > op_num {
+                return None;
+            }
+            let available = segment.available_point_count();
+            if available == 0 || (segment.is_appendable() && droppable_appendable <= 1) {
+                return None;
+            }
+            let estimation = segment.estimate_point_count(Some(filter), hw_counter);
+            if estimation.exp < available {
+                return None;
+            }
+            let matching = segment
+                .read_filtered(None, None, Some(filter), &is_stopped, hw_counter)
+                .len();
+            if matching < available {
+                return None;
+            }
+            if segment.is_appendable() {
+                droppable_appendable -= 1;
+            }
+            Some(*segment_id)
+        })
+        .collect();
+
+    if to_drop.is_empty() {
+        return Ok(0);
+    }
+
+    // Take exclusive ownership of the segments. Segments, which are still used by concurrent
+    // reads, are kept in the holder and left for the per-point delete.
+    let mut taken = Vec::with_capacity(to_drop.len());
+    {
+        let mut segments_guard = RwLockUpgradableReadGuard::upgrade(segments_guard);
+        for segment_id in to_drop {
+            let Some(LockedSegment::Original(segment)) = segments_guard.remove(&[segment_id]).pop()
+            else {
+                continue;
+            };
+            match Arc::try_unwrap(segment) {
+                Ok(segment) => taken.push((segment_id, segment.into_inner())),
+                Err(segment) => {
+                    segments_guard.add_existing_locked(segment_id, LockedSegment::Original(segment))
+                }
+            }
+        }
+    }
+
+    let mut dropped_points = 0;
+    for (segment_id, segment) in taken {
+        let available = segment.available_point_count();
+        let segment_path = segment.data_path();
+        log::debug!("Dropping segment with all {available} points matching delete filter");
+
+        if let Err(err) = segment.drop_data() {
+            // Segment data is still in place, if it could not be moved away. Load it back,
+            // so its points are not lost from the shard.
+            if segment_path.exists() {
+                let segment =
+                    load_segment(&segment_path, &AtomicBool::new(false))?.ok_or_else(|| {
+                        OperationError::service_error(format!(
+                            "Failed to reload segment {} after failed drop: {err}",
+                            segment_path.display(),
+                        ))
+                    })?;
+                segments.write().add_existing(segment_id, segment);
+            }
+            return Err(err);
+        }
+        dropped_points += available;
+    }
+
+    Ok(dropped_points)
+}
+
 /// Sync points within a given [from_id; to_id) range.
 ///
 /// 1. Retrieve existing points for a range
@@ -818,27 +937,34 @@ pub fn create_field_index(
     };
 
     segments.apply_segments(|write_segment| {
-        write_segment.with_upgraded(|segment| {
-            segment.delete_field_index_if_incompatible(op_num, field_name, field_schema)
-        })?;
+        // Existing index with another schema is rebuilt next to the old one, which keeps serving
+        // reads until the new index is applied. If storage doesn't allow it, drop the old one first.
+        let mut build_result =
+            write_segment.build_field_index(op_num, field_name, field_schema, hw_counter)?;
+        if let BuildFieldIndexResult::IncompatibleSchema = build_result {
+            write_segment.with_upgraded(|segment| {
+                segment.delete_field_index_if_incompatible(op_num, field_name, field_schema)
+            })?;
+            build_result =
+                write_segment.build_field_index(op_num, field_name, field_schema, hw_counter)?;
+        }
 
-        let (schema, indexes) =
-            match write_segment.build_field_index(op_num, field_name, field_schema, hw_counter)? {
-                BuildFieldIndexResult::SkippedByVersion => {
-                    return Ok(false);
-                }
-                BuildFieldIndexResult::AlreadyExists => {
-                    return Ok(false);
-                }
-                BuildFieldIndexResult::IncompatibleSchema => {
-                    // This is a service error, as we should have just removed the old index
-                    // So it should not be possible to get this error
-                    return Err(OperationError::service_error(format!(
-                        "Incompatible schema for field index on field {field_name}",
-                    )));
-                }
-                BuildFieldIndexResult::Built { schema, indexes } => (schema, indexes),
-            };
+        let (schema, indexes) = match build_result {
+            BuildFieldIndexResult::SkippedByVersion => {
+                return Ok(false);
+            }
+            BuildFieldIndexResult::AlreadyExists => {
+                return Ok(false);
+            }
+            BuildFieldIndexResult::IncompatibleSchema => {
+                // This is a service error, as we should have just removed the old index
+                // So it should not be possible to get this error
+                return Err(OperationError::service_error(format!(
+                    "Incompatible schema for field index on field {field_name}",
+                )));
+            }
+            BuildFieldIndexResult::Built { schema, indexes } => (schema, indexes),
+        };
 
         write_segment.with_upgraded(|segment| {
             segment.apply_field_index(op_num, field_name.to_owned(), schema, indexes)
@@ -871,6 +997,56 @@ fn select_excluded_by_filter_ids(
         .collect())
 }
 
+/// Select points, which are already stored with the same vectors and payload
+///
+/// Vectors are preprocessed with the config of the segment holding the point, the same way as on
+/// insertion, before being compared with the stored ones.
+fn select_unchanged_ids(
+    segments: &SegmentHolder,
+    points: &[PointStructPersisted],
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<AHashSet<PointIdType>> {
+    let id_to_point: AHashMap<PointIdType, _> = points.iter().map(|p| (p.id, p)).collect();
+    let point_ids: Vec<_> = id_to_point.keys().copied().collect();
+
+    let mut unchanged_points = AHashSet::new();
+    // we don’t want to cancel this filtered read
+    let is_stopped = AtomicBool::new(false);
+    segments.read_points(&point_ids, &is_stopped, |id, segment| {
+        let point = id_to_point[&id];
+
+        let payload = segment.payload(id, hw_counter)?;
+        if point.payload.as_ref().unwrap_or(&Payload::default()) != &payload {
+            return Ok(true);
+        }
+
+        let vector_data = &segment.config().vector_data;
+        let mut vectors = point.get_vectors();
+
+        // Unknown vectors are rejected by the upsert itself
+        let all_known = vectors.iter().all(|(name, vector)| match vector {
+            VectorRef::Sparse(_) => true,
+            VectorRef::Dense(_) | VectorRef::MultiDense(_) => vector_data.contains_key(name),
+        });
+        if !all_known {
+            return Ok(true);
+        }
+        vectors.preprocess(|name| &vector_data[name]);
+
+        let stored_vectors = match segment.all_vectors(id, hw_counter) {
+            Ok(v) => v,
+            Err(OperationError::InconsistentStorage { .. }) => return Ok(true),
+            Err(e) => return Err(e),
+        };
+        if vectors == stored_vectors {
+            unchanged_points.insert(id);
+        }
+        Ok(true)
+    })?;
+
+    Ok(unchanged_points)
+}
+
 fn points_by_filter(
     segments: &SegmentHolder,
     filter: &Filter,
@@ -903,14 +1079,26 @@ fn check_unprocessed_points(
 mod test {
     use std::sync::Arc;
 
+    use ahash::AHashSet;
     use common::counter::hardware_counter::HardwareCounterCell;
     use parking_lot::RwLock;
-    use segment::types::{Condition, FieldCondition, Filter, Match, MatchValue, ValueVariants};
+    use segment::payload_json;
+    use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
+    use segment::types::{
+        Condition, Distance, FieldCondition, Filter, HasIdCondition, Match, MatchValue,
+        ValueVariants,
+    };
     use tempfile::Builder;
 
     use crate::fixtures::{build_segment_1, build_segment_2};
+    use crate::operations::point_ops::{
+        ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+        PointStructPersisted, VectorStructPersisted,
+    };
     use crate::segment_holder::SegmentHolder;
-    use crate::update::delete_points_by_filter;
+    use crate::update::{
+        delete_points_by_filter, drop_segments_matching_filter, process_point_operation,
+    };
 
     #[test]
     fn test_delete_by_filter_version_bump() {
@@ -962,4 +1150,114 @@ mod test {
         assert_eq!(old_version + 1, new_version);
         assert_eq!(new_version, DELETE_OP_NUM);
     }
+
+    #[test]
+    fn test_delete_by_filter_drops_segment() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(build_segment_1(dir.path()));
+        let sid2 = holder.add_new(build_segment_2(dir.path()));
+        let segments = RwLock::new(holder);
+
+        // Matches all points of the first segment, but only some of the second one
+        let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            (1..=5).map(|id: u64| id.into()).collect::<AHashSet<_>>(),
+        )));
+
+        let deleted_count = process_point_operation(
+            &segments,
+            16,
+            PointOperations::DeletePointsByFilter(filter),
+            &hw_counter,
+        )
+        .unwrap();
+        assert_eq!(deleted_count, 7);
+
+        {
+            let holder = segments.read();
+            assert!(holder.get(sid1).is_none());
+            let segment2 = holder.get(sid2).unwrap().get().read();
+            assert_eq!(segment2.available_point_count(), 5);
+            assert!(!segment2.has_point(4.into()));
+        }
+
+        // The last appendable segment is never dropped
+        let all_points = Filter::new_must_not(Condition::HasId(HasIdCondition::from(
+            AHashSet::from_iter([100.into()]),
+        )));
+        let dropped =
+            drop_segments_matching_filter(&segments, 17, &all_points, &hw_counter).unwrap();
+        assert_eq!(dropped, 0);
+        assert_eq!(segments.read().len(), 1);
+    }
+
+    #[test]
+    fn test_delete_by_filter_keeps_newer_segments() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(build_segment_1(dir.path()));
+        holder.add_new(build_segment_2(dir.path()));
+        let segments = RwLock::new(holder);
+
+        let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            (1..=5).map(|id: u64| id.into()).collect::<AHashSet<_>>(),
+        )));
+
+        // Replayed delete is older than the points in the segment, it must not drop them
+        let segment_version = segments.read().get(sid1).unwrap().get().read().version();
+        let dropped =
+            drop_segments_matching_filter(&segments, segment_version - 1, &filter, &hw_counter)
+                .unwrap();
+        assert_eq!(dropped, 0);
+        assert!(segments.read().get(sid1).is_some());
+    }
+
+    #[test]
+    fn test_upsert_skip_unchanged() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut holder = SegmentHolder::default();
+        let sid = holder.add_new(build_simple_segment(dir.path(), 4, Distance::Cosine).unwrap());
+        let segments = RwLock::new(holder);
+
+        let point = |id: u64, color: &str| PointStructPersisted {
+            id: id.into(),
+            // Not normalized, so the stored vector differs from the upserted one
+            vector: VectorStructPersisted::Single(vec![1.0, 2.0, 3.0, 4.0]),
+            payload: Some(payload_json! {"color": color}),
+        };
+        let upsert = |op_num, points| {
+            let operation = ConditionalInsertOperationInternal {
+                points_op: PointInsertOperationsInternal::PointsList(points),
+                condition: Filter::default(),
+                skip_unchanged: true,
+            };
+            process_point_operation(
+                &segments,
+                op_num,
+                PointOperations::UpsertPointsConditional(operation),
+                &hw_counter,
+            )
+            .unwrap()
+        };
+
+        upsert(1, vec![point(1, "red"), point(2, "red")]);
+
+        // Point 1 is unchanged, point 2 gets a new payload and point 3 is new
+        upsert(2, vec![point(1, "red"), point(2, "blue"), point(3, "red")]);
+
+        let holder = segments.read();
+        let segment = holder.get(sid).unwrap().get().read();
+        assert_eq!(segment.point_version(1.into()), Some(1));
+        assert_eq!(segment.point_version(2.into()), Some(2));
+        assert_eq!(segment.point_version(3.into()), Some(2));
+    }
 }
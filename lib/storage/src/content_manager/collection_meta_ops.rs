@@ -14,6 +14,7 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{CollectionId, replica_set};
 use schemars::JsonSchema;
+use segment::spaces::wasm_metric::WasmMetricSource;
 use segment::types::{
     Payload, PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey, StrictModeConfig,
     VectorNameBuf,
@@ -187,6 +188,10 @@ pub struct CreateCollection {
     /// such as creation time, migration data, inference model info, etc.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Payload>,
+    /// Custom metric module of the peer proposing the collection, if custom distance is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub custom_metric: Option<WasmMetricSource>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -438,6 +443,7 @@ impl From<CollectionConfigInternal> for CreateCollection {
             strict_mode_config,
             uuid,
             metadata,
+            custom_metric,
         } = value;
 
         let CollectionParams {
@@ -468,6 +474,7 @@ impl From<CollectionConfigInternal> for CreateCollection {
             #[expect(deprecated)]
             init_from: None,
             metadata,
+            custom_metric,
         }
     }
 }
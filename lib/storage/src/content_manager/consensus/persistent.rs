@@ -307,7 +307,7 @@ impl Persistent {
         self.peer_metadata_by_id
             .read()
             .get(&self.this_peer_id())
-            .is_none_or(|metadata| metadata.is_different_version())
+            .is_none_or(|metadata| metadata.is_outdated())
     }
 
     pub fn this_peer_id(&self) -> PeerId {
@@ -22,7 +22,7 @@ use crate::content_manager::errors::StorageError;
 impl From<StorageError> for Status {
     fn from(error: StorageError) -> Self {
         let mut metadata_headers = HashMap::new();
-        let error_code = match &error {
+        let error_code = match error.origin() {
             StorageError::BadInput { .. } => tonic::Code::InvalidArgument,
             StorageError::NotFound { .. } => tonic::Code::NotFound,
             StorageError::ServiceError { .. } => tonic::Code::Internal,
@@ -48,10 +48,16 @@ impl From<StorageError> for Status {
             }
             StorageError::ShardUnavailable { .. } => tonic::Code::Unavailable,
             StorageError::EmptyPartialSnapshot { .. } => tonic::Code::FailedPrecondition,
+            StorageError::RemoteShardError { .. } => {
+                unreachable!("origin of an error is never a remote shard error")
+            }
         };
         let mut status = Status::new(error_code, format!("{error}"));
         // add metadata headers
-        for (header_key, header_value) in metadata_headers {
+        for (header_key, header_value) in metadata_headers
+            .into_iter()
+            .chain(error.details().to_metadata())
+        {
             if let Ok(metadata) = MetadataValue::from_str(&header_value) {
                 status.metadata_mut().insert(header_key, metadata);
             } else {
@@ -115,6 +121,7 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 } else {
                     Some(json::proto_to_payloads(metadata)?)
                 },
+                custom_metric: None,
             },
         )?;
         Ok(CollectionMetaOperations::CreateCollection(op))
@@ -142,6 +149,7 @@ pub fn strict_mode_from_api(value: api::grpc::qdrant::StrictModeConfig) -> Stric
         multivector_config,
         sparse_config,
         max_payload_index_count,
+        enforce_payload_schema,
     } = value;
     StrictModeConfig {
         enabled,
@@ -163,6 +171,7 @@ pub fn strict_mode_from_api(value: api::grpc::qdrant::StrictModeConfig) -> Stric
         multivector_config: multivector_config.map(StrictModeMultivectorConfig::from),
         sparse_config: sparse_config.map(StrictModeSparseConfig::from),
         max_payload_index_count: max_payload_index_count.map(|i| i as usize),
+        enforce_payload_schema,
     }
 }
 
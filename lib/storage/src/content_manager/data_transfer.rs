@@ -90,6 +90,7 @@ async fn replicate_shard_data(
     loop {
         let request = ScrollRequestInternal {
             offset,
+            after_id: None,
             limit: Some(limit),
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -2,8 +2,9 @@ use std::backtrace::Backtrace;
 use std::io::Error as IoError;
 use std::time::Duration;
 
+use api::rest::models::{ErrorCode, ErrorDetails};
 use collection::operations::types::CollectionError;
-use collection::shards::shard::ShardId;
+use collection::shards::shard::{PeerId, ShardId};
 use io::file_operations::FileStorageError;
 use tempfile::PersistError;
 use thiserror::Error;
@@ -47,6 +48,13 @@ pub enum StorageError {
     ShardUnavailable { description: String },
     #[error("Partial snapshot for shard {shard_id} contains no changes")]
     EmptyPartialSnapshot { shard_id: ShardId },
+    /// Error of a shard replica on a remote peer, the description already mentions where it happened
+    #[error("{error}")]
+    RemoteShardError {
+        peer_id: PeerId,
+        shard_id: ShardId,
+        error: Box<StorageError>,
+    },
 }
 
 impl StorageError {
@@ -100,6 +108,46 @@ impl StorageError {
         }
     }
 
+    /// Error as it was raised by the shard, without the context of where it happened
+    pub fn origin(&self) -> &Self {
+        match self {
+            StorageError::RemoteShardError { error, .. } => error.origin(),
+            _ => self,
+        }
+    }
+
+    /// Structured details of the error, for clients to handle it programmatically
+    pub fn details(&self) -> ErrorDetails {
+        match self {
+            StorageError::BadInput { .. } => ErrorDetails::new(ErrorCode::BadInput),
+            StorageError::AlreadyExists { .. } => ErrorDetails::new(ErrorCode::AlreadyExists),
+            StorageError::NotFound { .. } => ErrorDetails::new(ErrorCode::NotFound),
+            StorageError::ServiceError { .. } => ErrorDetails::new(ErrorCode::Internal),
+            StorageError::BadRequest { .. } => ErrorDetails::new(ErrorCode::BadRequest),
+            StorageError::Locked { .. } => ErrorDetails::new(ErrorCode::Locked),
+            StorageError::Timeout { .. } => ErrorDetails::new(ErrorCode::Timeout),
+            StorageError::ChecksumMismatch { .. } => ErrorDetails::new(ErrorCode::ChecksumMismatch),
+            StorageError::Forbidden { .. } => ErrorDetails::new(ErrorCode::Forbidden),
+            StorageError::PreconditionFailed { .. } => {
+                ErrorDetails::new(ErrorCode::PreconditionFailed)
+            }
+            StorageError::InferenceError { .. } => ErrorDetails::new(ErrorCode::InferenceFailed),
+            StorageError::RateLimitExceeded { .. } => ErrorDetails::new(ErrorCode::RateLimited),
+            StorageError::ShardUnavailable { .. } => ErrorDetails::new(ErrorCode::ShardUnavailable),
+            StorageError::EmptyPartialSnapshot { shard_id } => {
+                ErrorDetails::new(ErrorCode::PreconditionFailed).with_shard_id(*shard_id)
+            }
+            StorageError::RemoteShardError {
+                peer_id,
+                shard_id,
+                error,
+            } => error
+                .details()
+                .with_peer_id(*peer_id)
+                .with_shard_id(*shard_id),
+        }
+    }
+
     /// Used to override the `description` field of the resulting `StorageError`
     pub fn from_inconsistent_shard_failure(
         err: CollectionError,
@@ -138,6 +186,18 @@ impl StorageError {
             CollectionError::ForwardProxyError { error, .. } => {
                 Self::from_inconsistent_shard_failure(*error, overriding_description)
             }
+            CollectionError::RemoteShardError {
+                peer_id,
+                shard_id,
+                error,
+            } => StorageError::RemoteShardError {
+                peer_id,
+                shard_id,
+                error: Box::new(Self::from_inconsistent_shard_failure(
+                    *error,
+                    overriding_description,
+                )),
+            },
             CollectionError::OutOfMemory { .. } => StorageError::ServiceError {
                 description: overriding_description,
                 backtrace: None,
@@ -166,6 +226,10 @@ impl StorageError {
             CollectionError::ShardUnavailable { .. } => StorageError::ShardUnavailable {
                 description: overriding_description,
             },
+            CollectionError::CorruptedSegment { .. } => StorageError::ServiceError {
+                description: overriding_description,
+                backtrace: None,
+            },
         }
     }
 }
@@ -200,6 +264,10 @@ impl From<CollectionError> for StorageError {
                 let full_description = format!("{error}");
                 StorageError::from_inconsistent_shard_failure(*error, full_description)
             }
+            CollectionError::RemoteShardError { .. } => {
+                let full_description = format!("{err}");
+                StorageError::from_inconsistent_shard_failure(err, full_description)
+            }
             CollectionError::OutOfMemory { .. } => StorageError::ServiceError {
                 description: format!("{err}"),
                 backtrace: None,
@@ -228,6 +296,10 @@ impl From<CollectionError> for StorageError {
             CollectionError::ShardUnavailable { description } => {
                 StorageError::ShardUnavailable { description }
             }
+            CollectionError::CorruptedSegment { .. } => StorageError::ServiceError {
+                description: format!("{err}"),
+                backtrace: None,
+            },
         }
     }
 }
@@ -183,7 +183,7 @@ impl TableOfContent {
                         &snapshots_path,
                         &state.config,
                         self.storage_config
-                            .to_shared_storage_config(self.is_distributed())
+                            .to_shared_storage_config(self.is_distributed(), self.this_peer_id)
                             .into(),
                         shard_distribution,
                         Some(state.shards_key_mapping.clone()),
@@ -10,6 +10,7 @@ use collection::shards::replica_set::ReplicaState;
 use collection::shards::transfer::ShardTransfer;
 use collection::shards::{CollectionId, transfer};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::spaces::wasm_metric;
 use tempfile::Builder;
 
 use super::TableOfContent;
@@ -211,6 +212,9 @@ impl TableOfContent {
 
             drop(removed);
 
+            // A new collection with the same name may use another module
+            wasm_metric::unregister(&path);
+
             // Move collection to ".deleted" folder to prevent accidental reuse
             // the original collection path will be moved atomically within this
             // directory.
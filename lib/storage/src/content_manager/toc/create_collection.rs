@@ -1,17 +1,20 @@
 use std::collections::BTreeMap;
 use std::num::NonZeroU32;
+use std::path::Path;
 
 use collection::collection::Collection;
 use collection::config::{self, CollectionConfigInternal, CollectionParams, ShardingMethod};
-use collection::operations::config_diff::DiffConfig as _;
+use collection::operations::config_diff::{DiffConfig as _, OptimizersConfigDiff};
 use collection::operations::types::{
     CollectionResult, SparseVectorParams, VectorsConfig, check_sparse_compatible,
 };
+use collection::optimizers_builder::NodeResources;
 use collection::shards::CollectionId;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
-use segment::types::VectorNameBuf;
+use segment::spaces::wasm_metric::{self, WasmMetricModule};
+use segment::types::{Distance, VectorNameBuf};
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -21,6 +24,54 @@ use crate::content_manager::data_transfer;
 use crate::content_manager::errors::StorageError;
 
 impl TableOfContent {
+    /// Fill optimizer parameters, not set explicitly at collection creation, with values tuned
+    /// for the resources of this node
+    ///
+    /// Tuned values become part of the operation, so all peers create the collection with the
+    /// same config.
+    pub(crate) fn auto_tune_optimizers(
+        &self,
+        collection_name: &str,
+        operation: &mut CreateCollection,
+    ) {
+        let resources = NodeResources::detect(Path::new(&self.storage_config.storage_path));
+        let tuned = self.storage_config.optimizers.auto_tuned(&resources);
+
+        let diff = operation
+            .optimizers_config
+            .get_or_insert_with(|| OptimizersConfigDiff::from(tuned.clone()));
+        diff.default_segment_number
+            .get_or_insert(tuned.default_segment_number);
+        diff.max_segment_size = diff.max_segment_size.or(tuned.max_segment_size);
+        diff.indexing_threshold = diff.indexing_threshold.or(tuned.indexing_threshold);
+
+        log::info!(
+            "Auto-tuned optimizers of collection {collection_name} for {resources:?}: \
+             default_segment_number: {:?}, max_segment_size_kb: {:?}, indexing_threshold_kb: {:?}",
+            diff.default_segment_number,
+            diff.max_segment_size,
+            diff.indexing_threshold,
+        );
+    }
+
+    /// Attach the custom metric module of this node, if the collection uses custom distance
+    ///
+    /// The module becomes part of the operation, so all peers score with the same module.
+    pub(crate) fn attach_custom_metric(
+        operation: &mut CreateCollection,
+    ) -> Result<(), StorageError> {
+        operation.custom_metric = None;
+        if !operation.vectors.uses_distance(Distance::Custom) {
+            return Ok(());
+        }
+
+        let module = wasm_metric::configured().ok_or_else(|| {
+            StorageError::bad_input("Custom metric module is not registered on this node")
+        })?;
+        operation.custom_metric = Some(module.source().clone());
+        Ok(())
+    }
+
     pub(super) async fn create_collection(
         &self,
         collection_name: &str,
@@ -49,6 +100,7 @@ impl TableOfContent {
             strict_mode_config,
             uuid,
             metadata,
+            custom_metric,
         } = operation;
 
         {
@@ -75,6 +127,22 @@ impl TableOfContent {
             )));
         }
 
+        vectors.check_quantization_support(quantization_config.as_ref())?;
+
+        // All peers must score with the module of the proposing peer
+        let custom_metric = custom_metric.filter(|_| vectors.uses_distance(Distance::Custom));
+        if vectors.uses_distance(Distance::Custom) {
+            let source = custom_metric.as_ref().ok_or_else(|| {
+                StorageError::bad_input("Custom metric module is missing for custom distance")
+            })?;
+            // Module is registered by the collection, check it upfront to not leave files behind
+            WasmMetricModule::new(source.clone()).map_err(|err| {
+                StorageError::bad_input(format!(
+                    "Can't create collection with name {collection_name}: {err}",
+                ))
+            })?;
+        }
+
         if let Some(init_from) = &init_from {
             self.check_collections_compatibility(&vectors, &sparse_vectors, &init_from.collection)
                 .await?;
@@ -208,7 +276,7 @@ impl TableOfContent {
 
         let storage_config = self
             .storage_config
-            .to_shared_storage_config(self.is_distributed())
+            .to_shared_storage_config(self.is_distributed(), self.this_peer_id)
             .into();
 
         let collection_config = CollectionConfigInternal {
@@ -220,6 +288,7 @@ impl TableOfContent {
             strict_mode_config,
             uuid,
             metadata,
+            custom_metric,
         };
 
         // No shard key mapping on creation, shard keys are set up after creating the collection
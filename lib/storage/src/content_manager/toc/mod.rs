@@ -5,7 +5,9 @@ pub mod dispatcher;
 mod locks;
 mod point_ops;
 mod point_ops_internal;
+mod recall_probe;
 pub mod request_hw_counter;
+mod search_shadowing;
 mod snapshots;
 mod telemetry;
 mod temp_directories;
@@ -26,6 +28,7 @@ use collection::config::{
 };
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
+use collection::shards::internal_features::InternalFeature;
 use collection::shards::replica_set::{AbortShardTransfer, ReplicaState};
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::{CollectionId, replica_set};
@@ -109,6 +112,7 @@ impl TableOfContent {
         let collection_paths =
             read_dir(&collections_path).expect("Can't read Collections directory");
         let mut collections: HashMap<String, Collection> = Default::default();
+        let mut recall_probes = Vec::new();
         let is_distributed = consensus_proposal_sender.is_some();
         for entry in collection_paths {
             let collection_path = entry
@@ -141,7 +145,7 @@ impl TableOfContent {
                 &collection_path,
                 &collection_snapshots_path,
                 storage_config
-                    .to_shared_storage_config(is_distributed)
+                    .to_shared_storage_config(is_distributed, this_peer_id)
                     .into(),
                 channel_service.clone(),
                 Self::change_peer_from_state_callback(
@@ -163,6 +167,10 @@ impl TableOfContent {
                 storage_config.optimizers_overwrite.clone(),
             ));
 
+            if let Some((generation, interval)) = collection.recall_probe_schedule() {
+                recall_probes.push((collection_name.clone(), generation, interval));
+            }
+
             collections.insert(collection_name, collection);
         }
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
@@ -186,7 +194,7 @@ impl TableOfContent {
             }
         };
 
-        TableOfContent {
+        let toc = TableOfContent {
             collections: Arc::new(RwLock::new(collections)),
             storage_config: Arc::new(storage_config.clone()),
             search_runtime,
@@ -203,7 +211,14 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             collection_hw_metrics: DashMap::new(),
+        };
+
+        // Continue recall probes with ground truths saved before the restart
+        for (collection_name, generation, interval) in recall_probes {
+            toc.spawn_recall_evaluation(collection_name, generation, interval, None);
         }
+
+        toc
     }
 
     /// Return `true` if service is working in distributed mode.
@@ -211,6 +226,11 @@ impl TableOfContent {
         self.consensus_proposal_sender.is_some()
     }
 
+    /// Return `true` if all peers of the cluster support the given internal API feature.
+    pub fn all_peers_support(&self, feature: InternalFeature) -> bool {
+        self.channel_service.all_peers_support(feature)
+    }
+
     pub fn storage_path(&self) -> &str {
         &self.storage_config.storage_path
     }
@@ -256,6 +276,13 @@ impl TableOfContent {
             .collect()
     }
 
+    /// Persist in-memory state of all collections, before they are dropped on shutdown
+    pub async fn before_drop(&self) {
+        for collection in self.collections.read().await.values() {
+            collection.before_drop().await;
+        }
+    }
+
     /// Same as `get_collection`, but does not check access rights.
     /// Intended for internal use only.
     ///
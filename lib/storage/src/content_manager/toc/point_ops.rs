@@ -1,9 +1,13 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use api::rest::{AggregateResponse, ScoreHistogramResponse};
 use collection::collection::Collection;
+use collection::collection::aggregate::CollectionAggregateRequest;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
+use collection::collection::score_histogram::CollectionScoreHistogramRequest;
+use collection::common::partial_results::PartialResults;
 use collection::grouping::GroupBy;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
@@ -17,11 +21,11 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::TryStreamExt as _;
 use futures::stream::FuturesUnordered;
 use segment::data_types::facets::{FacetParams, FacetResponse};
-use segment::types::{ScoredPoint, ShardKey};
+use segment::types::{PointIdType, ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -135,16 +139,36 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        collection.record_recent_searches(&request, &shard_selection);
+        let shadow = collection.shadow_search_batch(&request);
+
+        let start = Instant::now();
+        let result = collection
             .core_search_batch(
                 request,
                 read_consistency,
-                shard_selection,
+                shard_selection.clone(),
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+        drop(collection);
+
+        if let Some((shadowing, shadow_request)) = shadow {
+            self.spawn_shadow_search(
+                collection_pass.name(),
+                shadowing,
+                shadow_request,
+                &result,
+                start.elapsed(),
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await;
+        }
+
+        Ok(result)
     }
 
     /// Count points in the collection.
@@ -222,6 +246,31 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Versions of the requested points, which exist in the collection
+    ///
+    /// # Result
+    ///
+    /// Existing points in the same order as requested, with versions of their last changes
+    pub async fn point_versions(
+        &self,
+        collection_name: &str,
+        ids: Vec<PointIdType>,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> StorageResult<Vec<PointVersion>> {
+        // Same requirements as for retrieving the points
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .point_versions(ids, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn group(
         &self,
@@ -346,6 +395,8 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// If `partial` is given, results of unresponsive shards are skipped and reported there.
+    #[allow(clippy::too_many_arguments)]
     pub async fn query_batch(
         &self,
         collection_name: &str,
@@ -354,6 +405,7 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
+        partial: Option<&PartialResults>,
     ) -> StorageResult<Vec<Vec<ScoredPoint>>> {
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
@@ -365,17 +417,36 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        collection.record_recent_queries(&requests);
+        let shadow = collection.shadow_query_batch(&requests);
 
-        collection
+        let start = Instant::now();
+        let result = collection
             .query_batch(
                 requests,
                 |name| self.get_collection_opt(name),
                 read_consistency,
                 timeout,
                 hw_measurement_acc,
+                partial,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+        drop(collection);
+
+        if let Some((shadowing, shadow_requests)) = shadow {
+            self.spawn_shadow_query(
+                collection_pass.name(),
+                shadowing,
+                shadow_requests,
+                &result,
+                start.elapsed(),
+                read_consistency,
+                timeout,
+            )
+            .await;
+        }
+
+        Ok(result)
     }
 
     // Return unique values for a payload key, and a count of points for each value.
@@ -406,6 +477,34 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    /// Aggregate values of a numeric payload key over points matching a filter.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn aggregate(
+        &self,
+        collection_name: &str,
+        mut request: CollectionAggregateRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<AggregateResponse> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .aggregate(
+                request,
+                shard_selection,
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await
+            .map_err(StorageError::from)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn search_points_matrix(
         &self,
@@ -433,6 +532,33 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn score_histogram(
+        &self,
+        collection_name: &str,
+        mut request: CollectionScoreHistogramRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> Result<ScoreHistogramResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .score_histogram(
+                request,
+                shard_selection,
+                read_consistency,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await
+            .map_err(StorageError::from)
+    }
+
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -3,11 +3,13 @@
 use std::time::Duration;
 
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::UpdateResult;
+use collection::operations::types::{PointVersion, UpdateResult};
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use collection::shards::local_shard::point_history::PointHistoryEntry;
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::types::PointIdType;
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageResult;
@@ -48,6 +50,35 @@ impl TableOfContent {
         Ok(res)
     }
 
+    pub async fn point_history_internal(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        point_id: PointIdType,
+    ) -> StorageResult<Vec<PointHistoryEntry>> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection.local_point_history(shard_id, point_id).await?;
+
+        Ok(res)
+    }
+
+    pub async fn point_versions_internal(
+        &self,
+        collection_name: &str,
+        ids: Vec<PointIdType>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> StorageResult<Vec<PointVersion>> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .point_versions(ids, None, &shard_selection, timeout)
+            .await?;
+
+        Ok(res)
+    }
+
     pub async fn cleanup_local_shard(
         &self,
         collection_name: &str,
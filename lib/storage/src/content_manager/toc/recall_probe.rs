@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use collection::collection::recall::{CollectionRecallProbeRequest, RecallTelemetry};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::CollectionPass;
+
+impl TableOfContent {
+    /// Build the recall ground truth of the collection and keep evaluating recall against it
+    ///
+    /// Recall is evaluated right away and then every `interval` of the request in background,
+    /// until the ground truth is replaced or removed, or the collection is deleted.
+    /// Returns the first evaluation, or `None` if the request removed the ground truth.
+    pub async fn start_recall_probe(
+        &self,
+        collection: &CollectionPass<'_>,
+        request: CollectionRecallProbeRequest,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> Result<Option<RecallTelemetry>, StorageError> {
+        let interval = request.interval;
+        let (collection_name, generation, recall) = {
+            let collection = self.get_collection(collection).await?;
+            let generation = collection
+                .build_recall_ground_truth(request, None, timeout, hw_measurement_acc.clone())
+                .await?;
+            let recall = collection
+                .evaluate_recall(generation, None, timeout, hw_measurement_acc)
+                .await?;
+            (collection.name(), generation, recall)
+        };
+
+        if recall.is_none() {
+            return Ok(None);
+        }
+
+        self.spawn_recall_evaluation(collection_name, generation, interval, timeout);
+
+        Ok(recall)
+    }
+
+    /// Keep evaluating recall of the ground truth of the given generation every `interval`
+    pub(super) fn spawn_recall_evaluation(
+        &self,
+        collection_name: String,
+        generation: u64,
+        interval: Duration,
+        timeout: Option<Duration>,
+    ) {
+        let collections = self.collections.clone();
+        self.search_runtime.spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let collections = collections.read().await;
+                let Some(collection) = collections.get(&collection_name) else {
+                    return;
+                };
+
+                match collection
+                    .evaluate_recall(generation, None, timeout, HwMeasurementAcc::disposable())
+                    .await
+                {
+                    Ok(Some(_)) => {}
+                    // Ground truth was replaced or removed, another probe takes over
+                    Ok(None) => return,
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to evaluate recall of collection {collection_name}: {err}"
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
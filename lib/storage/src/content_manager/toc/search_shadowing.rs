@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use collection::collection::search_shadowing::SearchShadowing;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CollectionError, CoreSearchRequestBatch};
+use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::types::{PointIdType, ScoredPoint};
+use tokio::sync::RwLockReadGuard;
+
+use super::TableOfContent;
+
+fn result_ids(results: &[Vec<ScoredPoint>]) -> Vec<Vec<PointIdType>> {
+    results
+        .iter()
+        .map(|points| points.iter().map(|point| point.id).collect())
+        .collect()
+}
+
+impl TableOfContent {
+    /// Name of the collection to run shadow searches of `collection_name` against
+    ///
+    /// Aliases are resolved, so shadow searches do not follow alias switches made after that.
+    async fn shadow_collection_name(
+        &self,
+        shadowing: &SearchShadowing,
+        collection_name: &str,
+    ) -> Option<String> {
+        let collection_name = shadowing.shadow_collection().unwrap_or(collection_name);
+        let collections = self.collections.read().await;
+        let aliases = self.alias_persistence.read().await;
+        Self::resolve_name(collection_name, &collections, &aliases).ok()
+    }
+
+    /// Run the shadow copy of a search request in background and compare it to the original
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn spawn_shadow_search(
+        &self,
+        collection_name: &str,
+        shadowing: Arc<SearchShadowing>,
+        request: CoreSearchRequestBatch,
+        original: &[Vec<ScoredPoint>],
+        original_duration: Duration,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) {
+        let original = result_ids(original);
+        let Some(shadow_collection) = self
+            .shadow_collection_name(&shadowing, collection_name)
+            .await
+        else {
+            let err = CollectionError::not_found(format!("Shadow collection of {collection_name}"));
+            shadowing.record(&original, original_duration, Err(err), Duration::ZERO);
+            return;
+        };
+
+        let collections = self.collections.clone();
+        self.search_runtime.spawn(async move {
+            let collections = collections.read().await;
+            let Some(collection) = collections.get(&shadow_collection) else {
+                return;
+            };
+            let start = Instant::now();
+            let result = collection
+                .core_search_batch(
+                    request,
+                    read_consistency,
+                    shard_selection,
+                    timeout,
+                    HwMeasurementAcc::disposable(),
+                )
+                .await;
+            shadowing.record(&original, original_duration, result, start.elapsed());
+        });
+    }
+
+    /// Run the shadow copy of query requests in background and compare it to the original
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn spawn_shadow_query(
+        &self,
+        collection_name: &str,
+        shadowing: Arc<SearchShadowing>,
+        requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        original: &[Vec<ScoredPoint>],
+        original_duration: Duration,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) {
+        let original = result_ids(original);
+        let Some(shadow_collection) = self
+            .shadow_collection_name(&shadowing, collection_name)
+            .await
+        else {
+            let err = CollectionError::not_found(format!("Shadow collection of {collection_name}"));
+            shadowing.record(&original, original_duration, Err(err), Duration::ZERO);
+            return;
+        };
+
+        let collections = self.collections.clone();
+        self.search_runtime.spawn(async move {
+            let collections = &collections;
+            // Lookups from other collections are resolved by their real names only
+            let collection_by_name = move |name: String| async move {
+                RwLockReadGuard::try_map(collections.read().await, |collections| {
+                    collections.get(&name)
+                })
+                .ok()
+            };
+            let Some(collection) = collection_by_name(shadow_collection).await else {
+                return;
+            };
+            let start = Instant::now();
+            let result = collection
+                .query_batch(
+                    requests,
+                    collection_by_name,
+                    read_consistency,
+                    timeout,
+                    HwMeasurementAcc::disposable(),
+                    None,
+                )
+                .await;
+            shadowing.record(&original, original_duration, result, start.elapsed());
+        });
+    }
+}
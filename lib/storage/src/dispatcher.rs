@@ -74,6 +74,20 @@ impl Dispatcher {
     ) -> Result<bool, StorageError> {
         access.check_collection_meta_operation(&operation)?;
 
+        // Tune optimizers and attach custom metric module on the proposing peer,
+        // so chosen values are the same on all peers
+        let operation = match operation {
+            CollectionMetaOperations::CreateCollection(mut op) => {
+                if self.toc.storage_config.auto_tune_optimizers {
+                    self.toc
+                        .auto_tune_optimizers(&op.collection_name, &mut op.create_collection);
+                }
+                TableOfContent::attach_custom_metric(&mut op.create_collection)?;
+                CollectionMetaOperations::CreateCollection(op)
+            }
+            operation => operation,
+        };
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             let start = Instant::now();
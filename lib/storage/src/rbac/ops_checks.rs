@@ -3,7 +3,9 @@ use std::mem::take;
 
 use ahash::AHashSet;
 use api::rest::LookupLocation;
+use collection::collection::aggregate::CollectionAggregateRequest;
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
+use collection::collection::score_histogram::CollectionScoreHistogramRequest;
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
 use collection::operations::CollectionUpdateOperations;
@@ -154,6 +156,11 @@ impl CollectionAccessView<'_> {
             VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr: _ }) => {
                 self.check_vector_input(nearest)?
             }
+            VectorQuery::Combine(terms) => {
+                for term in terms {
+                    self.check_vector_input(&term.vector)?
+                }
+            }
         };
 
         Ok(())
@@ -401,6 +408,26 @@ impl CheckableCollectionOperation for FacetParams {
     }
 }
 
+impl CheckableCollectionOperation for CollectionAggregateRequest {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+            extras: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> StorageResult<()> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -421,6 +448,26 @@ impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
     }
 }
 
+impl CheckableCollectionOperation for CollectionScoreHistogramRequest {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+            extras: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> StorageResult<()> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for CollectionUpdateOperations {
     fn access_requirements(&self) -> AccessRequirements {
         match self {
@@ -1107,6 +1154,7 @@ mod tests_ops {
     fn test_scroll_request_internal() {
         let op = ScrollRequestInternal {
             offset: Some(ExtendedPointId::NumId(12345)),
+            after_id: None,
             limit: Some(100),
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
@@ -1196,6 +1244,7 @@ mod tests_ops {
                     PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
                         points_op: inner,
                         condition: filter,
+                        skip_unchanged: false,
                     }),
                 );
 
@@ -3,6 +3,8 @@ use std::num::NonZeroUsize;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::collection::recent_queries::RecentQueriesConfig;
+use collection::collection::search_shadowing::SearchShadowingConfig;
 use collection::common::snapshots_manager::SnapshotsConfig;
 use collection::config::{WalConfig, default_on_disk_payload};
 use collection::operations::config_diff::OptimizersConfigDiff;
@@ -11,13 +13,18 @@ use collection::operations::shared_storage_config::{
 };
 use collection::operations::types::{NodeType, PeerMetadata};
 use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::local_shard::wal_offload::WalOffloadConfig;
 use collection::shards::shard::PeerId;
 use collection::shards::transfer::ShardTransferMethod;
+use common::search_pressure::IoPriority;
 use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::{Anonymize, anonymize_collection_values};
 use segment::data_types::collection_defaults::CollectionConfigDefaults;
+use segment::payload_storage::wasm_condition::WasmConditionsConfig;
+use segment::spaces::wasm_metric::WasmMetricConfig;
 use segment::types::{HnswConfig, HnswGlobalConfig};
+use segment::vector_storage::chunked_vectors::ChunkedVectorsConfig;
 use serde::{Deserialize, Serialize};
 use tonic::transport::Uri;
 use validator::Validate;
@@ -46,18 +53,65 @@ pub struct PerformanceConfig {
     /// Otherwise - use this exact number of IO operations.
     #[serde(default)]
     pub optimizer_io_budget: usize,
+    /// 99th percentile of search latency in milliseconds, above which IO of low priority is throttled.
+    /// If not set - IO is not throttled based on search latency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub io_throttle_search_latency_ms: Option<u64>,
+    /// Priority of IO of optimizations relative to searches.
+    /// If low - optimizations get the minimal IO budget, and running ones are paused, while searches are slow.
+    #[serde(default)]
+    pub optimizer_io_priority: IoPriority,
+    /// Priority of IO of updates relative to searches.
+    /// If low - applying updates is paused while searches are slow, and updates are queued meanwhile.
+    #[serde(default)]
+    pub update_io_priority: IoPriority,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub outgoing_shard_transfers_limit: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub async_scorer: Option<bool>,
+    /// Size of chunks of in-memory vector storages in bytes.
+    /// If not set - 32MiB chunks are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_chunk_size_bytes: Option<usize>,
+    /// Alignment of chunks of in-memory vector storages in bytes, must be a power of two.
+    /// If not set - natural alignment of vector elements is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_chunk_alignment_bytes: Option<usize>,
+    /// Back chunks of in-memory vector storages with transparent huge pages. Linux only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_chunk_huge_pages: Option<bool>,
+    /// Maximum number of concurrent searches per collection, scheduled fairly across shard keys.
+    /// Queued searches of different tenants are executed in round-robin order, so a burst of one
+    /// tenant can't monopolize the search pool.
+    /// If not set - searches are not scheduled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fair_search_concurrency: Option<usize>,
+    /// RAM budget in bytes of each segment for caching vectors and payloads of frequently
+    /// retrieved points of on-disk storages.
+    /// If not set - hot points are not cached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hot_points_cache_bytes: Option<usize>,
 }
 
 const fn default_io_shard_transfers_limit() -> Option<usize> {
     DEFAULT_IO_SHARD_TRANSFER_LIMIT
 }
 
+impl PerformanceConfig {
+    /// Memory layout of in-memory vector storages
+    pub fn chunked_vectors_config(&self) -> ChunkedVectorsConfig {
+        ChunkedVectorsConfig {
+            chunk_size_bytes: self
+                .vector_chunk_size_bytes
+                .unwrap_or(ChunkedVectorsConfig::DEFAULT.chunk_size_bytes),
+            alignment_bytes: self.vector_chunk_alignment_bytes,
+            huge_pages: self.vector_chunk_huge_pages.unwrap_or_default(),
+        }
+    }
+}
+
 /// Global configuration of the storage, loaded on the service launch, default stored in ./config
 #[derive(Clone, Debug, Deserialize, Validate)]
 pub struct StorageConfig {
@@ -78,6 +132,10 @@ pub struct StorageConfig {
     #[validate(nested)]
     #[serde(default)]
     pub optimizers_overwrite: Option<OptimizersConfigDiff>,
+    /// Pick segment number, max segment size and indexing threshold of new collections
+    /// from the detected resources of the node, instead of the `optimizers` defaults
+    #[serde(default)]
+    pub auto_tune_optimizers: bool,
     #[validate(nested)]
     pub wal: WalConfig,
     pub performance: PerformanceConfig,
@@ -111,10 +169,36 @@ pub struct StorageConfig {
     /// Maximum number of collections to allow in the cluster.
     #[serde(default)]
     pub max_collections: Option<usize>,
+    /// Record sampled recent search queries of each collection.
+    /// If not set - recent queries are not recorded.
+    #[validate(nested)]
+    #[serde(default)]
+    pub recent_queries: Option<RecentQueriesConfig>,
+    /// Shadow sampled searches of collections with alternative search params or against a
+    /// candidate collection, comparing results in telemetry.
+    #[validate(nested)]
+    #[serde(default)]
+    pub search_shadowing: Vec<SearchShadowingConfig>,
+    /// Custom payload conditions, implemented as WebAssembly modules and referenced by name in
+    /// filters.
+    #[serde(default)]
+    pub wasm_conditions: Option<WasmConditionsConfig>,
+    /// Offload closed WAL segments to object storage, before truncating them locally.
+    /// If not set - WAL segments are only kept on local disk.
+    #[serde(default)]
+    pub wal_offload: Option<WalOffloadConfig>,
+    /// Custom similarity function, implemented as a WebAssembly module and used by vectors with
+    /// the `Custom` distance.
+    #[serde(default)]
+    pub custom_metric: Option<WasmMetricConfig>,
 }
 
 impl StorageConfig {
-    pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
+    pub fn to_shared_storage_config(
+        &self,
+        is_distributed: bool,
+        this_peer_id: PeerId,
+    ) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
             self.node_type,
@@ -131,7 +215,14 @@ impl StorageConfig {
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
             self.hnsw_global_config.clone(),
+            self.performance.chunked_vectors_config(),
             common::defaults::search_thread_count(self.performance.max_search_threads),
+            self.performance.fair_search_concurrency,
+            self.recent_queries.clone(),
+            self.search_shadowing.clone(),
+            self.wal_offload
+                .clone()
+                .map(|wal_offload| wal_offload.with_peer_id(this_peer_id)),
         )
     }
 }
@@ -49,17 +49,26 @@ fn test_alias_operation() {
             max_optimization_threads: Some(2),
         },
         optimizers_overwrite: None,
+        auto_tune_optimizers: false,
         wal: Default::default(),
         performance: PerformanceConfig {
             max_search_threads: 1,
             max_optimization_runtime_threads: 1,
             optimizer_cpu_budget: 0,
             optimizer_io_budget: 0,
+            io_throttle_search_latency_ms: None,
+            optimizer_io_priority: Default::default(),
+            update_io_priority: Default::default(),
             update_rate_limit: None,
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
             async_scorer: None,
+            vector_chunk_size_bytes: None,
+            vector_chunk_alignment_bytes: None,
+            vector_chunk_huge_pages: None,
+            fair_search_concurrency: None,
+            hot_points_cache_bytes: None,
         },
         hnsw_index: Default::default(),
         hnsw_global_config: Default::default(),
@@ -73,6 +82,11 @@ fn test_alias_operation() {
         shard_transfer_method: None,
         collection: None,
         max_collections: None,
+        recent_queries: None,
+        search_shadowing: Vec::new(),
+        wasm_conditions: None,
+        wal_offload: None,
+        custom_metric: None,
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -122,6 +136,7 @@ fn test_alias_operation() {
                             strict_mode_config: None,
                             uuid: None,
                             metadata: None,
+                            custom_metric: None,
                         },
                     )
                     .unwrap(),
@@ -3,6 +3,8 @@ use std::time::Duration;
 use actix_web::rt::time::Instant;
 use actix_web::{HttpResponse, Responder, delete, get, patch, post, put, web};
 use actix_web_validator::{Json, Path, Query};
+use api::rest::RecallProbeRequest;
+use collection::collection::recall::CollectionRecallProbeRequest;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::verification::new_unchecked_verification_pass;
 use serde::Deserialize;
@@ -209,6 +211,85 @@ async fn get_cluster_info(
     .await
 }
 
+#[get("/collections/{name}/hnsw_stats")]
+async fn get_hnsw_stats(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    // No request to verify
+    let pass = new_unchecked_verification_pass();
+
+    helpers::time(do_get_collection_hnsw_stats(
+        dispatcher.toc(&access, &pass),
+        access,
+        &collection.name,
+    ))
+    .await
+}
+
+#[get("/collections/{name}/recent_queries")]
+async fn get_recent_queries(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    // No request to verify
+    let pass = new_unchecked_verification_pass();
+
+    helpers::time(do_get_recent_queries(
+        dispatcher.toc(&access, &pass),
+        access,
+        &collection.name,
+    ))
+    .await
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct WarmupParams {
+    lock: Option<bool>,
+}
+
+#[post("/collections/{name}/warmup")]
+async fn warmup_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    Query(params): Query<WarmupParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    // No request to verify
+    let pass = new_unchecked_verification_pass();
+
+    helpers::time(do_warmup_collection(
+        dispatcher.toc(&access, &pass),
+        access,
+        &collection.name,
+        params.lock.unwrap_or(false),
+    ))
+    .await
+}
+
+#[post("/collections/{name}/recall_probe")]
+async fn start_recall_probe(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RecallProbeRequest>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    // No request to verify
+    let pass = new_unchecked_verification_pass();
+
+    helpers::time(do_start_recall_probe(
+        dispatcher.toc(&access, &pass),
+        access,
+        &collection.name,
+        CollectionRecallProbeRequest::from(request.into_inner()),
+        query.timeout(),
+    ))
+    .await
+}
+
 #[post("/collections/{name}/cluster")]
 async fn update_collection_cluster(
     dispatcher: web::Data<Dispatcher>,
@@ -244,6 +325,10 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(get_cluster_info)
+        .service(get_hnsw_stats)
+        .service(get_recent_queries)
+        .service(warmup_collection)
+        .service(start_recall_probe)
         .service(update_collection_cluster);
 }
 
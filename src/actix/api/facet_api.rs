@@ -1,6 +1,7 @@
 use actix_web::{Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{FacetRequest, FacetResponse};
+use api::rest::{AggregateRequest, FacetRequest, FacetResponse};
+use collection::collection::aggregate::CollectionAggregateRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use storage::content_manager::collection_verification::check_strict_mode;
 use storage::dispatcher::Dispatcher;
@@ -74,6 +75,63 @@ async fn facet(
     process_response(response, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/aggregate")]
+async fn aggregate(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<AggregateRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let AggregateRequest {
+        aggregate_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &aggregate_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, timing, None),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+        None,
+    );
+
+    let response = dispatcher
+        .toc(&access, &pass)
+        .aggregate(
+            &collection.name,
+            CollectionAggregateRequest::from(aggregate_request),
+            shard_selection,
+            params.consistency,
+            access,
+            params.timeout(),
+            request_hw_counter.get_counter(),
+        )
+        .await;
+
+    process_response(response, timing, request_hw_counter.to_rest_api())
+}
+
 pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
-    cfg.service(facet);
+    cfg.service(facet).service(aggregate);
 }
@@ -2,7 +2,9 @@ use actix_web::{Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
 use api::rest::models::InferenceUsage;
 use api::rest::{QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse};
+use collection::common::partial_results::PartialResults;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use storage::content_manager::collection_verification::{
     check_strict_mode, check_strict_mode_batch,
@@ -12,7 +14,7 @@ use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
 
 use super::CollectionPath;
-use super::read_params::ReadParams;
+use super::read_params::{PartialReadParams, ReadParams};
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, get_request_hardware_counter};
 use crate::common::inference::InferenceToken;
@@ -29,6 +31,7 @@ async fn query_points(
     collection: Path<CollectionPath>,
     request: Json<QueryRequest>,
     params: Query<ReadParams>,
+    partial_params: Query<PartialReadParams>,
     service_config: web::Data<ServiceConfig>,
     ActixAccess(access): ActixAccess,
     inference_token: InferenceToken,
@@ -68,6 +71,7 @@ async fn query_points(
         )
         .await?;
 
+        let partial = partial_params.partial_results();
         let points = dispatcher
             .toc(&access, &pass)
             .query_batch(
@@ -77,6 +81,7 @@ async fn query_points(
                 access,
                 params.timeout(),
                 hw_measurement_acc,
+                partial.as_ref(),
             )
             .await?
             .pop()
@@ -87,7 +92,13 @@ async fn query_points(
             .map(api::rest::ScoredPoint::from)
             .collect_vec();
 
-        Ok(QueryResponse { points })
+        let (degraded, unreachable_shards) = degraded_markers(partial.as_ref());
+        Ok(QueryResponse {
+            points,
+            degraded,
+            unreachable_shards,
+            truncated: truncated_marker(&request_hw_counter.get_counter()),
+        })
     }
     .await;
 
@@ -105,6 +116,7 @@ async fn query_points_batch(
     collection: Path<CollectionPath>,
     request: Json<QueryRequestBatch>,
     params: Query<ReadParams>,
+    partial_params: Query<PartialReadParams>,
     service_config: web::Data<ServiceConfig>,
     ActixAccess(access): ActixAccess,
     inference_token: InferenceToken,
@@ -153,6 +165,7 @@ async fn query_points_batch(
         )
         .await?;
 
+        let partial = partial_params.partial_results();
         let res = dispatcher
             .toc(&access, &pass)
             .query_batch(
@@ -162,14 +175,21 @@ async fn query_points_batch(
                 access,
                 params.timeout(),
                 hw_measurement_acc,
+                partial.as_ref(),
             )
-            .await?
+            .await?;
+        let (degraded, unreachable_shards) = degraded_markers(partial.as_ref());
+        let truncated = truncated_marker(&request_hw_counter.get_counter());
+        let res = res
             .into_iter()
             .map(|response| QueryResponse {
                 points: response
                     .into_iter()
                     .map(api::rest::ScoredPoint::from)
                     .collect_vec(),
+                degraded,
+                unreachable_shards: unreachable_shards.clone(),
+                truncated,
             })
             .collect_vec();
         Ok(res)
@@ -256,3 +276,16 @@ pub fn config_query_api(cfg: &mut web::ServiceConfig) {
     cfg.service(query_points_batch);
     cfg.service(query_points_groups);
 }
+
+/// Response markers of a partial read, only present if some shards did not respond
+fn degraded_markers(partial: Option<&PartialResults>) -> (Option<bool>, Option<Vec<u32>>) {
+    match partial {
+        Some(partial) if partial.is_degraded() => (Some(true), Some(partial.unreachable_shards())),
+        _ => (None, None),
+    }
+}
+
+/// Response marker of a search cut by its scoring budget, only present if it happened
+fn truncated_marker(hw_measurement_acc: &HwMeasurementAcc) -> Option<bool> {
+    hw_measurement_acc.is_search_truncated().then_some(true)
+}
@@ -1,6 +1,7 @@
 use std::num::NonZeroU64;
 use std::time::Duration;
 
+use collection::common::partial_results::PartialResults;
 use collection::operations::consistency_params::ReadConsistency;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -25,6 +26,21 @@ impl ReadParams {
     }
 }
 
+/// Parameters of reads which are allowed to return results of responsive shards only
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+pub struct PartialReadParams {
+    /// If true, return results from responsive shards instead of failing the request if some
+    /// shards time out or are unavailable. Such response is marked as `degraded`.
+    #[serde(default)]
+    pub allow_partial: bool,
+}
+
+impl PartialReadParams {
+    pub fn partial_results(&self) -> Option<PartialResults> {
+        self.allow_partial.then(PartialResults::default)
+    }
+}
+
 fn deserialize_read_consistency<'de, D>(
     deserializer: D,
 ) -> Result<Option<ReadConsistency>, D::Error>
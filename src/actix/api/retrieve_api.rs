@@ -5,8 +5,9 @@ use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    PointRequest, PointRequestInternal, RecordInternal, ScrollRequest,
+    PointRequest, PointRequestInternal, PointsExistRequest, RecordInternal, ScrollRequest,
 };
+use collection::operations::verification::new_unchecked_verification_pass;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::TryFutureExt;
 use itertools::Itertools;
@@ -26,9 +27,10 @@ use super::CollectionPath;
 use super::read_params::ReadParams;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{
-    get_request_hardware_counter, process_response, process_response_error,
+    self, get_request_hardware_counter, process_response, process_response_error,
 };
-use crate::common::query::do_get_points;
+use crate::common::collections::do_get_point_history;
+use crate::common::query::{do_get_points, do_points_exist};
 use crate::settings::ServiceConfig;
 
 #[derive(Deserialize, Validate)]
@@ -124,6 +126,32 @@ async fn get_point(
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
 
+#[get("/collections/{name}/points/{id}/history")]
+async fn get_point_history(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    // No request to verify
+    let pass = new_unchecked_verification_pass();
+
+    let Ok(point_id) = point.id.parse::<PointIdType>() else {
+        let err = StorageError::BadInput {
+            description: format!("Can not recognize \"{}\" as point id", point.id),
+        };
+        return process_response_error(err, Instant::now(), None);
+    };
+
+    helpers::time(do_get_point_history(
+        dispatcher.toc(&access, &pass),
+        access,
+        &collection.name,
+        point_id,
+    ))
+    .await
+}
+
 #[post("/collections/{name}/points")]
 async fn get_points(
     dispatcher: web::Data<Dispatcher>,
@@ -184,6 +212,56 @@ async fn get_points(
     process_response(res, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/points/exists")]
+async fn points_exist(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<PointsExistRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let pass = match check_strict_mode_timeout(
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(p) => p,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let PointsExistRequest { ids, shard_key } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+        None,
+    );
+    let timing = Instant::now();
+
+    let res = do_points_exist(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        ids,
+        params.consistency,
+        params.timeout(),
+        shard_selection,
+        access,
+    )
+    .await;
+
+    process_response(res, timing, request_hw_counter.to_rest_api())
+}
+
 #[post("/collections/{name}/points/scroll")]
 async fn scroll_points(
     dispatcher: web::Data<Dispatcher>,
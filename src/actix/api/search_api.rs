@@ -1,7 +1,11 @@
 use actix_web::{HttpResponse, Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest};
+use api::rest::{
+    ScoreHistogramRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest,
+};
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
+use collection::collection::score_histogram::CollectionScoreHistogramRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
@@ -20,7 +24,8 @@ use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_error,
 };
 use crate::common::query::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups, do_search_points_matrix,
+    do_core_search_points, do_score_histogram, do_search_batch_points, do_search_point_groups,
+    do_search_points_matrix,
 };
 use crate::settings::ServiceConfig;
 
@@ -328,11 +333,67 @@ async fn search_points_matrix_offsets(
     process_response(response, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/points/search/score_histogram")]
+async fn score_histogram(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ScoreHistogramRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let ScoreHistogramRequest {
+        score_histogram_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &score_histogram_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+        None,
+    );
+    let timing = Instant::now();
+
+    let response = do_score_histogram(
+        dispatcher.toc(&access, &pass),
+        &collection.name,
+        CollectionScoreHistogramRequest::from(score_histogram_request),
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    process_response(response, timing, request_hw_counter.to_rest_api())
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
         .service(search_point_groups)
         .service(search_points_matrix_pairs)
-        .service(search_points_matrix_offsets);
+        .service(search_points_matrix_offsets)
+        .service(score_histogram);
 }
@@ -46,6 +46,7 @@ pub fn accepted_response(
         status: ApiStatus::Accepted,
         time: timing.elapsed().as_secs_f64(),
         usage,
+        error_details: None,
     })
 }
 
@@ -67,6 +68,7 @@ where
                 hardware: hardware_usage,
                 inference: inference_usage,
             }),
+            error_details: None,
         }),
         Err(err) => process_response_error_with_inference_usage(
             err,
@@ -107,6 +109,7 @@ pub fn process_response_error_with_inference_usage(
             hardware: hardware_usage,
             inference: inference_usage,
         }),
+        error_details: Some(error.0.details()),
     };
 
     let mut response_builder = HttpResponse::build(http_code);
@@ -130,6 +133,7 @@ pub fn already_in_progress_response() -> HttpResponse {
         status: ApiStatus::AlreadyInProgress,
         time: 0.0,
         usage: None,
+        error_details: None,
     })
 }
 
@@ -206,7 +210,7 @@ pub struct HttpError(StorageError);
 impl HttpError {
     fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
-        match &self.0 {
+        match self.0.origin() {
             StorageError::RateLimitExceeded {
                 description: _,
                 retry_after,
@@ -234,6 +238,7 @@ impl HttpError {
             StorageError::InferenceError { .. } => {}
             StorageError::ShardUnavailable { .. } => {}
             StorageError::EmptyPartialSnapshot { .. } => {}
+            StorageError::RemoteShardError { .. } => {}
         }
         headers
     }
@@ -241,7 +246,7 @@ impl HttpError {
 
 impl ResponseError for HttpError {
     fn status_code(&self) -> http::StatusCode {
-        match &self.0 {
+        match self.0.origin() {
             StorageError::BadInput { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::NotFound { .. } => http::StatusCode::NOT_FOUND,
             StorageError::ServiceError { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -256,6 +261,9 @@ impl ResponseError for HttpError {
             StorageError::RateLimitExceeded { .. } => http::StatusCode::TOO_MANY_REQUESTS,
             StorageError::ShardUnavailable { .. } => http::StatusCode::SERVICE_UNAVAILABLE,
             StorageError::EmptyPartialSnapshot { .. } => http::StatusCode::NOT_MODIFIED,
+            StorageError::RemoteShardError { .. } => {
+                unreachable!("origin of an error is never a remote shard error")
+            }
         }
     }
 }
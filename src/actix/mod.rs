@@ -8,7 +8,7 @@ pub mod web_ui;
 use std::io;
 use std::sync::Arc;
 
-use ::api::rest::models::{ApiResponse, ApiStatus, VersionInfo};
+use ::api::rest::models::{ApiResponse, ApiStatus, ErrorCode, ErrorDetails, VersionInfo};
 use actix_cors::Cors;
 use actix_multipart::form::MultipartFormConfig;
 use actix_multipart::form::tempfile::TempFileConfig;
@@ -31,7 +31,9 @@ use crate::actix::api::local_shard_api::config_local_shard_api;
 use crate::actix::api::profiler_api::config_profiler_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{
+    get_point, get_point_history, get_points, points_exist, scroll_points,
+};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -163,8 +165,10 @@ pub fn init(
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(points_exist)
                 .service(count_points)
                 .service(get_point)
+                .service(get_point_history)
                 .service(get_points);
 
             if let Some(static_folder) = web_ui_available.as_deref() {
@@ -234,6 +238,14 @@ fn validation_error_handler(
         err => err.to_string(),
     };
 
+    let error_details = ErrorDetails {
+        parameter: match &err {
+            actix_web_validator::Error::Validate(errs) => validation::first_invalid_field(errs),
+            _ => None,
+        },
+        ..ErrorDetails::new(ErrorCode::BadInput)
+    };
+
     // Build fitting response
     let response = match &err {
         actix_web_validator::Error::Validate(_) => HttpResponse::UnprocessableEntity(),
@@ -244,6 +256,7 @@ fn validation_error_handler(
         status: ApiStatus::Error(msg),
         time: 0.0,
         usage: None,
+        error_details: Some(error_details),
     });
     error::InternalError::from_response(err, response).into()
 }
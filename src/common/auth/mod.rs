@@ -121,6 +121,7 @@ impl AuthKeys {
     async fn validate_value_exists(&self, value_exists: &ValueExists) -> Result<(), AuthError> {
         let scroll_req = ScrollRequestInternal {
             offset: None,
+            after_id: None,
             limit: Some(1),
             filter: Some(value_exists.to_filter()),
             with_payload: Some(WithPayloadInterface::Bool(false)),
@@ -140,7 +141,7 @@ impl AuthKeys {
                 HwMeasurementAcc::disposable(),
             )
             .await
-            .map_err(|e| match e {
+            .map_err(|e| match e.origin() {
                 StorageError::NotFound { .. } => {
                     AuthError::Forbidden("Invalid JWT, stateful validation failed".to_string())
                 }
@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use api::grpc::qdrant::CollectionExists;
 use api::rest::models::{CollectionDescription, CollectionsResponse};
+use collection::collection::recall::{CollectionRecallProbeRequest, RecallTelemetry};
+use collection::collection::recent_queries::RecentQuery;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
@@ -16,13 +18,17 @@ use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
 };
 use collection::operations::verification::new_unchecked_verification_pass;
+use collection::shards::local_shard::point_history::PointHistoryEntry;
 use collection::shards::replica_set;
 use collection::shards::resharding::ReshardKey;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
+use collection::shards::telemetry::ShardHnswGraphStats;
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use rand::seq::IteratorRandom;
+use segment::types::PointIdType;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
@@ -195,6 +201,71 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_get_collection_hnsw_stats(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<Vec<ShardHnswGraphStats>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole().extras())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.get_hnsw_graph_stats().await)
+}
+
+pub async fn do_warmup_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    lock: bool,
+) -> Result<bool, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole().manage())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    collection.warmup(lock).await?;
+    Ok(true)
+}
+
+pub async fn do_start_recall_probe(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    request: CollectionRecallProbeRequest,
+    timeout: Option<Duration>,
+) -> Result<Option<RecallTelemetry>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole().manage())?;
+    toc.start_recall_probe(
+        &collection_pass,
+        request,
+        timeout,
+        HwMeasurementAcc::disposable(), // Internal operation
+    )
+    .await
+}
+
+pub async fn do_get_point_history(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    point_id: PointIdType,
+) -> Result<Vec<PointHistoryEntry>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole().extras())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.point_history(point_id).await?)
+}
+
+pub async fn do_get_recent_queries(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<Vec<RecentQuery>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole().extras())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.recent_queries())
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -98,6 +98,11 @@ fn collect_query(query: &Query, batch: &mut BatchAccum) {
                 }
             }
         }
+        Query::Combine(combine) => {
+            for term in &combine.combine {
+                collect_vector_input(&term.vector, batch);
+            }
+        }
         Query::OrderBy(_)
         | Query::Fusion(_)
         | Query::Rrf(_)
@@ -170,6 +170,13 @@ pub(crate) fn collect_query(query: &Query, batch: &mut BatchAccumGrpc) -> Result
                 .map(|vector| collect_vector_input(vector, batch))
                 .transpose()?;
         }
+        query::Variant::Combine(combine) => {
+            for weighted in &combine.vectors {
+                if let Some(vector) = &weighted.vector {
+                    collect_vector_input(vector, batch)?;
+                }
+            }
+        }
     }
 
     Ok(())
@@ -131,6 +131,7 @@ fn new_token_processor_from_config(value: TextPreprocessingConfig) -> TokensProc
         lowercase,
         Arc::new(StopwordsFilter::new(&stopwords_config, lowercase)),
         stemmer,
+        false,
         min_token_len,
         max_token_len,
     )
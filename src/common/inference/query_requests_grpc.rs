@@ -5,7 +5,7 @@ use api::grpc::{InferenceUsage, qdrant as grpc};
 use api::rest::{self, LookupLocation, RecommendStrategy};
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr, NearestWithMmr,
-    Query, VectorInputInternal, VectorQuery,
+    Query, VectorInputInternal, VectorQuery, WeightedVector,
 };
 use collection::operations::universal_query::formula::FormulaInternal;
 use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
@@ -125,6 +125,7 @@ pub async fn convert_query_points_from_grpc(
         shard_key_selector: _,
         lookup_from,
         timeout: _,
+        allow_partial: _,
     } = query;
 
     let mut batch = BatchAccumGrpc::new();
@@ -311,6 +312,21 @@ fn convert_query_with_inferred(
 
             Query::Vector(VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr }))
         }
+        Variant::Combine(grpc::CombineInput { vectors }) => {
+            let terms = vectors
+                .into_iter()
+                .map(|grpc::WeightedVectorInput { vector, weight }| {
+                    let vector = vector
+                        .ok_or_else(|| Status::invalid_argument("combined vector is missing"))?;
+                    Ok(WeightedVector {
+                        vector: convert_vector_input_with_inferred(vector, inferred)?,
+                        weight: weight.unwrap_or(1.0),
+                    })
+                })
+                .collect::<Result<Vec<_>, Status>>()?;
+
+            Query::Vector(VectorQuery::Combine(terms))
+        }
     };
 
     Ok(query)
@@ -566,4 +582,45 @@ mod tests {
                 .contains("positive is missing"),
         );
     }
+
+    #[test]
+    fn test_convert_combine_query_with_inferred() {
+        let inferred = create_test_inferred_batch();
+        let query = grpc::Query {
+            variant: Some(grpc::query::Variant::Combine(grpc::CombineInput {
+                vectors: vec![
+                    grpc::WeightedVectorInput {
+                        vector: Some(grpc::VectorInput {
+                            variant: Some(Variant::Id(PointIdType::NumId(1).into())),
+                        }),
+                        weight: None,
+                    },
+                    grpc::WeightedVectorInput {
+                        vector: Some(grpc::VectorInput {
+                            variant: Some(Variant::Document(create_test_document())),
+                        }),
+                        weight: Some(-0.5),
+                    },
+                ],
+            })),
+        };
+
+        let result = convert_query_with_inferred(query, &inferred).unwrap();
+        let Query::Vector(VectorQuery::Combine(terms)) = result else {
+            panic!("Expected combine query");
+        };
+        assert_eq!(
+            terms,
+            vec![
+                WeightedVector {
+                    vector: VectorInputInternal::Id(PointIdType::NumId(1)),
+                    weight: 1.0,
+                },
+                WeightedVector {
+                    vector: VectorInputInternal::Vector(VectorInternal::Dense(vec![1.0, 2.0, 3.0])),
+                    weight: -0.5,
+                },
+            ],
+        );
+    }
 }
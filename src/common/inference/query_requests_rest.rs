@@ -3,7 +3,7 @@ use api::rest::schema as rest;
 use collection::lookup::WithLookup;
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Mmr, NearestWithMmr,
-    Query, VectorInputInternal, VectorQuery,
+    Query, VectorInputInternal, VectorQuery, WeightedVector,
 };
 use collection::operations::universal_query::formula::FormulaInternal;
 use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
@@ -265,6 +265,19 @@ fn convert_query_with_inferred(
                 context,
             ))))
         }
+        rest::Query::Combine(combine) => {
+            let terms = combine
+                .combine
+                .into_iter()
+                .map(|rest::WeightedVectorInput { vector, weight }| {
+                    Ok(WeightedVector {
+                        vector: convert_vector_input_with_inferred(vector, inferred)?,
+                        weight: weight.unwrap_or(1.0),
+                    })
+                })
+                .collect::<Result<Vec<_>, StorageError>>()?;
+            Ok(Query::Vector(VectorQuery::Combine(terms)))
+        }
         rest::Query::OrderBy(order_by) => Ok(Query::OrderBy(OrderBy::from(order_by.order_by))),
         rest::Query::Fusion(fusion) => Ok(Query::Fusion(FusionInternal::from(fusion.fusion))),
         rest::Query::Rrf(rrf) => Ok(Query::Fusion(FusionInternal::from(rrf.rrf))),
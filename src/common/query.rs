@@ -1,15 +1,18 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
-use api::rest::SearchGroupsRequestInternal;
+use api::rest::{ScoreHistogramResponse, SearchGroupsRequestInternal};
 use collection::collection::distance_matrix::*;
+use collection::collection::score_histogram::CollectionScoreHistogramRequest;
 use collection::common::batching::batch_requests;
+use collection::common::partial_results::PartialResults;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::*;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use segment::types::ScoredPoint;
+use segment::types::{PointIdType, ScoredPoint};
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
@@ -241,6 +244,64 @@ pub async fn do_get_points(
     .await
 }
 
+/// Maximum number of ids in a single request to check existence of points
+pub const MAX_POINTS_EXIST_IDS: usize = 10_000;
+
+/// Check which of the given points exist, without reading their payloads or vectors
+///
+/// Existence and versions of the points are looked up in id trackers of the shards.
+pub async fn do_points_exist(
+    toc: &TableOfContent,
+    collection_name: &str,
+    ids: Vec<PointIdType>,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<PointsExistResult, StorageError> {
+    if ids.len() > MAX_POINTS_EXIST_IDS {
+        return Err(StorageError::bad_request(format!(
+            "Too many ids to check: {}, at most {MAX_POINTS_EXIST_IDS} are allowed per request",
+            ids.len(),
+        )));
+    }
+
+    let point_versions = toc
+        .point_versions(
+            collection_name,
+            ids.clone(),
+            read_consistency,
+            timeout,
+            shard_selection,
+            access,
+        )
+        .await?;
+
+    let found: HashMap<_, _> = point_versions
+        .into_iter()
+        .map(|PointVersion { id, version }| (id, version))
+        .collect();
+
+    let mut existing = Vec::with_capacity(found.len());
+    let mut versions = Vec::with_capacity(found.len());
+    let mut missing = Vec::new();
+    for id in ids {
+        match found.get(&id) {
+            Some(&version) => {
+                existing.push(id);
+                versions.push(version);
+            }
+            None => missing.push(id),
+        }
+    }
+
+    Ok(PointsExistResult {
+        existing,
+        missing,
+        versions,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_scroll_points(
     toc: &TableOfContent,
@@ -274,6 +335,7 @@ pub async fn do_query_points(
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
+    partial: Option<&PartialResults>,
 ) -> Result<Vec<ScoredPoint>, StorageError> {
     let requests = vec![(request, shard_selection)];
     let batch_res = toc
@@ -284,6 +346,7 @@ pub async fn do_query_points(
             access,
             timeout,
             hw_measurement_acc,
+            partial,
         )
         .await?;
     batch_res
@@ -301,6 +364,7 @@ pub async fn do_query_batch_points(
     access: Access,
     timeout: Option<Duration>,
     hw_measurement_acc: HwMeasurementAcc,
+    partial: Option<&PartialResults>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
     toc.query_batch(
         collection_name,
@@ -309,6 +373,7 @@ pub async fn do_query_batch_points(
         access,
         timeout,
         hw_measurement_acc,
+        partial,
     )
     .await
 }
@@ -358,3 +423,26 @@ pub async fn do_search_points_matrix(
     )
     .await
 }
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_score_histogram(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CollectionScoreHistogramRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ScoreHistogramResponse, StorageError> {
+    toc.score_histogram(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+        hw_measurement_acc,
+    )
+    .await
+}
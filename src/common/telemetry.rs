@@ -5,9 +5,11 @@ use common::types::{DetailsLevel, TelemetryDetail};
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::index::buffer_pool;
+use segment::telemetry::SearchPoolsTelemetry;
 use serde::Serialize;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::Access;
+use storage::rbac::{Access, AccessRequirements};
 use uuid::Uuid;
 
 use crate::common::telemetry_ops::app_telemetry::{AppBuildTelemetry, AppBuildTelemetryCollector};
@@ -44,6 +46,8 @@ pub struct TelemetryData {
     pub(crate) memory: Option<MemoryTelemetry>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) hardware: Option<HardwareTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) search_pools: Option<SearchPoolsTelemetry>,
 }
 
 impl TelemetryCollector {
@@ -93,6 +97,16 @@ impl TelemetryCollector {
             .map_err(|err| log::error!("Failed to generate collection telemetry {err}"))
             .unwrap_or_default();
 
+        let search_pools = (detail.level > DetailsLevel::Level0)
+            .then(|| {
+                let required_access = AccessRequirements::new().whole();
+                access
+                    .check_global_access(required_access)
+                    .ok()
+                    .map(|_| buffer_pool::get_telemetry_data())
+            })
+            .flatten();
+
         TelemetryData {
             id: self.process_id.to_string(),
             collections: collections_telemetry,
@@ -109,6 +123,7 @@ impl TelemetryCollector {
                 .flatten(),
             hardware: (detail.level > DetailsLevel::Level0)
                 .then(|| HardwareTelemetry::new(&self.dispatcher, access)),
+            search_pools,
         }
     }
 }
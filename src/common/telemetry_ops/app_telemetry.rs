@@ -5,6 +5,7 @@ use common::flags::FeatureFlags;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::spaces::kernels::{SimdInstructionSet, kernels};
 use segment::types::HnswGlobalConfig;
 use serde::Serialize;
 
@@ -44,6 +45,9 @@ pub struct RunningEnvironmentTelemetry {
     disk_size: Option<usize>,
     #[anonymize(false)]
     cpu_flags: String,
+    /// Instruction set of metric kernels, selected for this CPU
+    #[anonymize(false)]
+    simd_instruction_set: SimdInstructionSet,
     #[serde(skip_serializing_if = "Option::is_none")]
     cpu_endian: Option<CpuEndian>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -169,6 +173,7 @@ fn get_system_data() -> RunningEnvironmentTelemetry {
         ram_size: sys_info::mem_info().ok().map(|x| x.total as usize),
         disk_size: sys_info::disk_info().ok().map(|x| x.total as usize),
         cpu_flags: cpu_flags.join(","),
+        simd_instruction_set: kernels().instruction_set,
         cpu_endian: Some(CpuEndian::current()),
         gpu_devices,
     }
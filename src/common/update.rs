@@ -289,35 +289,41 @@ pub async fn do_upsert_points(
         .check_strict_mode(&operation, &collection_name, None, &access)
         .await?;
 
-    let (operation, shard_key, usage, update_filter) = match operation {
+    let (operation, shard_key, usage, update_filter, skip_unchanged) = match operation {
         PointInsertOperations::PointsBatch(batch) => {
             let PointsBatch {
                 batch,
                 shard_key,
                 update_filter,
+                skip_unchanged,
             } = batch;
             let (batch, usage) = convert_batch(batch, inference_token).await?;
             let operation = PointInsertOperationsInternal::PointsBatch(batch);
-            (operation, shard_key, usage, update_filter)
+            (operation, shard_key, usage, update_filter, skip_unchanged)
         }
         PointInsertOperations::PointsList(list) => {
             let PointsList {
                 points,
                 shard_key,
                 update_filter,
+                skip_unchanged,
             } = list;
             let (list, usage) =
                 convert_point_struct(points, InferenceType::Update, inference_token).await?;
             let operation = PointInsertOperationsInternal::PointsList(list);
-            (operation, shard_key, usage, update_filter)
+            (operation, shard_key, usage, update_filter, skip_unchanged)
         }
     };
 
-    let operation = if let Some(condition) = update_filter {
+    // Unchanged points are skipped while applying the operation, so that comparing them with
+    // the stored points and writing happen under the same segment lock
+    let skip_unchanged = skip_unchanged.unwrap_or(false);
+    let operation = if update_filter.is_some() || skip_unchanged {
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
             ConditionalInsertOperationInternal {
                 points_op: operation,
-                condition,
+                condition: update_filter.unwrap_or_default(),
+                skip_unchanged,
             },
         ))
     } else {
@@ -1537,6 +1537,7 @@ mod tests {
                                 strict_mode_config: None,
                                 uuid: None,
                                 metadata: None,
+                                custom_metric: None,
                             },
                         )
                         .unwrap(),
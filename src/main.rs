@@ -28,6 +28,9 @@ use collection::profiling::interface::init_requests_profile_collector;
 use collection::shards::channel_service::ChannelService;
 use consensus::Consensus;
 use memory::checkfs::{check_fs_info, check_mmap_functionality};
+use segment::common::hot_points_cache;
+use segment::payload_storage::wasm_condition;
+use segment::spaces::wasm_metric;
 use slog::Drain;
 use startup::setup_panic_hook;
 use storage::content_manager::consensus::operation_sender::OperationSender;
@@ -178,6 +181,28 @@ fn main() -> anyhow::Result<()> {
             .unwrap_or_default(),
     );
 
+    let performance_config = &settings.storage.performance;
+    performance_config
+        .chunked_vectors_config()
+        .validate()
+        .map_err(|err| anyhow::anyhow!("Invalid vector chunk configuration: {err}"))?;
+
+    hot_points_cache::set_budget(
+        performance_config
+            .hot_points_cache_bytes
+            .unwrap_or_default(),
+    );
+
+    if let Some(wasm_conditions) = &settings.storage.wasm_conditions {
+        wasm_condition::register_modules(wasm_conditions)
+            .map_err(|err| anyhow::anyhow!("Invalid custom condition configuration: {err}"))?;
+    }
+
+    if let Some(custom_metric) = &settings.storage.custom_metric {
+        wasm_metric::register_module(custom_metric)
+            .map_err(|err| anyhow::anyhow!("Invalid custom metric configuration: {err}"))?;
+    }
+
     welcome(&settings);
 
     #[cfg(feature = "gpu")]
@@ -324,7 +349,15 @@ fn main() -> anyhow::Result<()> {
     // Use global CPU budget for optimizations based on settings
     let cpu_budget = get_cpu_budget(settings.storage.performance.optimizer_cpu_budget);
     let io_budget = get_io_budget(settings.storage.performance.optimizer_io_budget, cpu_budget);
-    let optimizer_resource_budget = ResourceBudget::new(cpu_budget, io_budget);
+    let optimizer_resource_budget = ResourceBudget::new(cpu_budget, io_budget).with_io_throttling(
+        settings
+            .storage
+            .performance
+            .io_throttle_search_latency_ms
+            .map(Duration::from_millis),
+        settings.storage.performance.optimizer_io_priority,
+        settings.storage.performance.update_io_priority,
+    );
 
     // Create a signal sender and receiver. It is used to communicate with the consensus thread.
     let (propose_sender, propose_receiver) = std::sync::mpsc::channel();
@@ -639,6 +672,9 @@ fn main() -> anyhow::Result<()> {
         );
         handle.join().expect("thread is not panicking")?;
     }
+    toc_arc
+        .general_runtime_handle()
+        .block_on(toc_arc.before_drop());
     drop(toc_arc);
     drop(settings);
     Ok(())
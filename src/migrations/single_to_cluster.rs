@@ -56,6 +56,7 @@ pub async fn handle_existing_collections(
             strict_mode_config,
             uuid,
             metadata,
+            custom_metric,
         } = config;
 
         let shards_number = params.shard_number.get();
@@ -80,6 +81,7 @@ pub async fn handle_existing_collections(
                 #[expect(deprecated)]
                 init_from: None,
                 metadata,
+                custom_metric,
             },
         )
         .expect("Failed to create collection operation");
@@ -1,12 +1,14 @@
 #![allow(dead_code)]
 
-use api::rest::models::{CollectionsResponse, Usage, VersionInfo};
+use api::rest::models::{CollectionsResponse, ErrorDetails, Usage, VersionInfo};
 use api::rest::schema::PointInsertOperations;
 use api::rest::{
-    FacetRequest, FacetResponse, QueryGroupsRequest, QueryRequest, QueryRequestBatch,
-    QueryResponse, Record, ScoredPoint, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
-    SearchMatrixRequest, UpdateVectors,
+    AggregateRequest, AggregateResponse, FacetRequest, FacetResponse, QueryGroupsRequest,
+    QueryRequest, QueryRequestBatch, QueryResponse, RecallProbeRequest, Record,
+    ScoreHistogramRequest, ScoreHistogramResponse, ScoredPoint, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchMatrixRequest, UpdateVectors,
 };
+use collection::collection::recent_queries::RecentQuery;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
@@ -17,11 +19,13 @@ use collection::operations::snapshot_ops::{
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    GroupsResult, PointGroup, PointRequest, PointsExistRequest, PointsExistResult,
+    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, ScrollRequest, ScrollResult,
+    SearchGroupsRequest, SearchRequest, SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::DeleteVectors;
+use collection::shards::local_shard::point_history::PointHistoryEntry;
+use collection::shards::telemetry::ShardHnswGraphStats;
 use schemars::JsonSchema;
 use schemars::r#gen::SchemaSettings;
 use serde::Serialize;
@@ -98,6 +102,17 @@ struct AllDefinitions {
     bl: FacetRequest,
     bm: FacetResponse,
     bn: Usage,
+    bo: PointsExistRequest,
+    bp: PointsExistResult,
+    bq: ShardHnswGraphStats,
+    br: ScoreHistogramRequest,
+    bs: ScoreHistogramResponse,
+    bt: RecentQuery,
+    bu: ErrorDetails,
+    bv: AggregateRequest,
+    bw: AggregateResponse,
+    bx: PointHistoryEntry,
+    by: RecallProbeRequest,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -10,6 +10,7 @@ use api::grpc::qdrant::{
     GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
     ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse,
     UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
+    WarmupCollectionRequest, WarmupCollectionResponse,
 };
 use collection::operations::cluster_ops::{
     ClusterOperations, CreateShardingKeyOperation, DropShardingKeyOperation,
@@ -314,6 +315,36 @@ impl Collections for CollectionsService {
 
         Ok(Response::new(DeleteShardKeyResponse { result }))
     }
+
+    async fn warmup(
+        &self,
+        mut request: Request<WarmupCollectionRequest>,
+    ) -> Result<Response<WarmupCollectionResponse>, Status> {
+        let timing = Instant::now();
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+
+        // Nothing to verify here.
+        let pass = new_unchecked_verification_pass();
+
+        let WarmupCollectionRequest {
+            collection_name,
+            lock,
+        } = request.into_inner();
+        let result = do_warmup_collection(
+            self.dispatcher.toc(&access, &pass),
+            access,
+            &collection_name,
+            lock.unwrap_or(false),
+        )
+        .await?;
+        let response = WarmupCollectionResponse {
+            result,
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
 }
 
 trait WithTimeout {
@@ -9,8 +9,12 @@ mod collections_common;
 mod query_common;
 mod update_common;
 
+use std::str::FromStr;
+
+use api::rest::models::{ErrorCode, ErrorDetails};
 use collection::operations::validation;
 use tonic::Status;
+use tonic::metadata::MetadataValue;
 use validator::Validate;
 
 /// Validate the given request and fail on error.
@@ -18,7 +22,18 @@ use validator::Validate;
 /// Returns validation error on failure.
 fn validate(request: &impl Validate) -> Result<(), Status> {
     request.validate().map_err(|ref err| {
-        Status::invalid_argument(validation::label_errors("Validation error in body", err))
+        let mut status =
+            Status::invalid_argument(validation::label_errors("Validation error in body", err));
+        let details = ErrorDetails {
+            parameter: validation::first_invalid_field(err),
+            ..ErrorDetails::new(ErrorCode::BadInput)
+        };
+        for (key, value) in details.to_metadata() {
+            if let Ok(value) = MetadataValue::from_str(&value) {
+                status.metadata_mut().insert(key, value);
+            }
+        }
+        status
     })
 }
 
@@ -61,6 +76,51 @@ mod tests {
         assert_eq!(
             validation.message(),
             "Validation error in body: [things[0].idx: value 0 invalid, must be 1 or larger]"
-        )
+        );
+        let metadata = validation.metadata();
+        assert_eq!(
+            metadata.get(ErrorDetails::CODE_METADATA_KEY).unwrap(),
+            "bad_input",
+        );
+        assert_eq!(
+            metadata.get(ErrorDetails::RETRYABLE_METADATA_KEY).unwrap(),
+            "false",
+        );
+        assert_eq!(
+            metadata.get(ErrorDetails::PARAMETER_METADATA_KEY).unwrap(),
+            "things[0].idx",
+        );
+    }
+
+    #[test]
+    fn test_remote_shard_error_details() {
+        use collection::operations::types::CollectionError;
+        use storage::content_manager::errors::StorageError;
+        use tonic::Code;
+
+        let error = CollectionError::remote_shard_error(
+            2,
+            1,
+            CollectionError::bad_request("missing vector"),
+        );
+        let status = Status::from(StorageError::from(error));
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(
+            status.message(),
+            "Bad request: Shard 1 on peer 2 failed: Bad request: missing vector",
+        );
+        let metadata = status.metadata();
+        assert_eq!(
+            metadata.get(ErrorDetails::CODE_METADATA_KEY).unwrap(),
+            "bad_request",
+        );
+        assert_eq!(
+            metadata.get(ErrorDetails::SHARD_ID_METADATA_KEY).unwrap(),
+            "1",
+        );
+        assert_eq!(
+            metadata.get(ErrorDetails::PEER_ID_METADATA_KEY).unwrap(),
+            "2",
+        );
     }
 }
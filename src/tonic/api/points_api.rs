@@ -4,20 +4,24 @@ use std::time::{Duration, Instant};
 use api::grpc::Usage;
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
-    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
-    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
-    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
-    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
-    SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsets, SearchMatrixOffsetsResponse,
-    SearchMatrixPairs, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse,
-    UpdatePointVectors, UpsertPoints,
+    AggregatePoints, AggregateResponse, ClearPayloadPoints, CountPoints, CountResponse,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
+    DeletePointVectors, DeletePoints, DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints,
+    DiscoverResponse, ExistsPoints, ExistsResponse, FacetCounts, FacetResponse, GetPoints,
+    GetResponse, PointsOperationResponse, QueryBatchPoints, QueryBatchResponse,
+    QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse, RecallProbePoints,
+    RecallProbeResponse, RecallProbeResult, RecommendBatchPoints, RecommendBatchResponse,
+    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
+    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsets, SearchMatrixOffsetsResponse, SearchMatrixPairs, SearchMatrixPairsResponse,
+    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
+use collection::collection::recall::CollectionRecallProbeRequest;
 use collection::operations::types::CoreSearchRequest;
+use collection::operations::verification::new_unchecked_verification_pass;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use storage::content_manager::toc::request_hw_counter::RequestHwCounter;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
@@ -25,6 +29,7 @@ use tonic::{Request, Response, Status};
 use super::query_common::*;
 use super::update_common::*;
 use super::validate;
+use crate::common::collections::do_start_recall_probe;
 use crate::common::inference::extract_token;
 use crate::common::strict_mode::*;
 use crate::common::update::InternalUpdateParams;
@@ -128,6 +133,29 @@ impl Points for PointsService {
         .await
     }
 
+    async fn exists(
+        &self,
+        mut request: Request<ExistsPoints>,
+    ) -> Result<Response<ExistsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        let inner_request = request.into_inner();
+
+        let hw_metrics = self
+            .get_request_collection_hw_usage_counter(inner_request.collection_name.clone(), None);
+
+        exists(
+            StrictModeCheckedTocProvider::new(&self.dispatcher),
+            inner_request,
+            None,
+            access,
+            hw_metrics,
+        )
+        .await
+    }
+
     async fn update_vectors(
         &self,
         mut request: Request<UpdatePointVectors>,
@@ -620,6 +648,7 @@ impl Points for PointsService {
             query_points,
             read_consistency,
             timeout,
+            allow_partial,
         } = request;
         let timeout = timeout.map(Duration::from_secs);
         let hw_metrics =
@@ -631,6 +660,7 @@ impl Points for PointsService {
             read_consistency,
             access,
             timeout,
+            allow_partial.unwrap_or_default(),
             hw_metrics,
             inference_token,
         )
@@ -731,4 +761,79 @@ impl Points for PointsService {
 
         Ok(Response::new(offsets_response))
     }
+
+    async fn recall_probe(
+        &self,
+        mut request: Request<RecallProbePoints>,
+    ) -> Result<Response<RecallProbeResponse>, Status> {
+        let timing = Instant::now();
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+
+        // Nothing to verify here.
+        let pass = new_unchecked_verification_pass();
+
+        let RecallProbePoints {
+            collection_name,
+            filter,
+            sample,
+            limit,
+            using,
+            interval,
+            timeout,
+        } = request.into_inner();
+
+        let recall_probe_request = CollectionRecallProbeRequest {
+            sample_size: sample
+                .map(usize::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("could not parse 'sample' param into usize"))?
+                .unwrap_or(CollectionRecallProbeRequest::DEFAULT_SAMPLE),
+            limit: limit
+                .map(usize::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("could not parse 'limit' param into usize"))?
+                .unwrap_or(CollectionRecallProbeRequest::DEFAULT_LIMIT),
+            filter: filter.map(TryInto::try_into).transpose()?,
+            using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned()),
+            interval: interval
+                .map(Duration::from_secs)
+                .unwrap_or(CollectionRecallProbeRequest::DEFAULT_INTERVAL),
+        };
+
+        let result = do_start_recall_probe(
+            self.dispatcher.toc(&access, &pass),
+            access,
+            &collection_name,
+            recall_probe_request,
+            timeout.map(Duration::from_secs),
+        )
+        .await?;
+
+        let response = RecallProbeResponse {
+            result: result.map(RecallProbeResult::from),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn aggregate(
+        &self,
+        mut request: Request<AggregatePoints>,
+    ) -> Result<Response<AggregateResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(
+            request.get_ref().collection_name.clone(),
+            None,
+        );
+        aggregate(
+            StrictModeCheckedTocProvider::new(&self.dispatcher),
+            request.into_inner(),
+            access,
+            hw_metrics,
+        )
+        .await
+    }
 }
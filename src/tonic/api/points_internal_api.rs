@@ -1,4 +1,5 @@
 use std::any;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -10,20 +11,25 @@ use api::grpc::qdrant::{
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
     DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
     FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
-    PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
-    QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateBatchInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    PointHistoryInternal, PointHistoryResponseInternal, PointVersionsInternal,
+    PointVersionsResponseInternal, PointsOperationResponseInternal, QueryBatchPointsInternal,
+    QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints, RecommendPointsInternal,
+    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
+    SetPayloadPointsInternal, SyncPointsInternal, UpdateBatchInternal, UpdateVectorsInternal,
+    UpsertPointsInternal, Value,
 };
 use api::grpc::update_operation::Update;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
+use collection::shards::internal_features::{InternalFeature, downgrade_unsigned_values};
+use collection::shards::local_shard::point_history::serialize_entries;
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::json_path::JsonPath;
 use segment::types::Filter;
+use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::content_manager::toc::request_hw_counter::RequestHwCounter;
 use storage::rbac::Access;
@@ -364,7 +370,8 @@ pub async fn query_batch_internal(
         )
         .await?;
 
-    let response = QueryBatchResponseInternal {
+    let truncated = request_hw_data.get_counter().is_search_truncated();
+    let mut response = QueryBatchResponseInternal {
         results: batch_response
             .into_iter()
             .map(|response| QueryResultInternal {
@@ -379,11 +386,33 @@ pub async fn query_batch_internal(
         time: timing.elapsed().as_secs_f64(),
         hardware_usage: request_hw_data.to_grpc_api(),
         inference_usage: None, // No inference in internal API
+        truncated: truncated.then_some(true),
     };
 
+    downgrade_payloads_for_peers(
+        toc,
+        response
+            .results
+            .iter_mut()
+            .flat_map(|result| &mut result.intermediate_results)
+            .flat_map(|intermediate| &mut intermediate.result)
+            .map(|point| &mut point.payload),
+    );
+
     Ok(Response::new(response))
 }
 
+/// Internal responses are read by other peers, which may not support unsigned payload values
+/// during a rolling upgrade
+fn downgrade_payloads_for_peers<'a>(
+    toc: &TableOfContent,
+    payloads: impl IntoIterator<Item = &'a mut HashMap<String, Value>>,
+) {
+    if !toc.all_peers_support(InternalFeature::UnsignedPayloadValues) {
+        downgrade_unsigned_values(payloads);
+    }
+}
+
 async fn facet_counts_internal(
     toc: &TableOfContent,
     request: FacetCountsInternal,
@@ -648,7 +677,7 @@ impl PointsInternal for PointsInternalService {
 
         let hw_data =
             self.get_request_collection_hw_usage_counter_for_internal(collection_name.clone());
-        let res = core_search_list(
+        let mut res = core_search_list(
             self.toc.as_ref(),
             collection_name,
             search_points,
@@ -660,6 +689,15 @@ impl PointsInternal for PointsInternalService {
         )
         .await?;
 
+        downgrade_payloads_for_peers(
+            &self.toc,
+            res.get_mut()
+                .result
+                .iter_mut()
+                .flat_map(|batch| &mut batch.result)
+                .map(|point| &mut point.payload),
+        );
+
         Ok(res)
     }
 
@@ -683,7 +721,7 @@ impl PointsInternal for PointsInternalService {
         let collection_name = recommend_points.collection_name.clone();
 
         let hw_data = self.get_request_collection_hw_usage_counter_for_internal(collection_name);
-        let res = recommend(
+        let mut res = recommend(
             UncheckedTocProvider::new_unchecked(&self.toc),
             recommend_points,
             FULL_ACCESS.clone(),
@@ -691,6 +729,14 @@ impl PointsInternal for PointsInternalService {
         )
         .await?;
 
+        downgrade_payloads_for_peers(
+            &self.toc,
+            res.get_mut()
+                .result
+                .iter_mut()
+                .map(|point| &mut point.payload),
+        );
+
         Ok(res)
     }
 
@@ -714,14 +760,24 @@ impl PointsInternal for PointsInternalService {
             scroll_points.collection_name.clone(),
         );
 
-        scroll(
+        let mut res = scroll(
             UncheckedTocProvider::new_unchecked(&self.toc),
             scroll_points,
             shard_id,
             FULL_ACCESS.clone(),
             hw_data,
         )
-        .await
+        .await?;
+
+        downgrade_payloads_for_peers(
+            &self.toc,
+            res.get_mut()
+                .result
+                .iter_mut()
+                .map(|point| &mut point.payload),
+        );
+
+        Ok(res)
     }
 
     async fn get(
@@ -744,14 +800,24 @@ impl PointsInternal for PointsInternalService {
             get_points.collection_name.clone(),
         );
 
-        get(
+        let mut res = get(
             UncheckedTocProvider::new_unchecked(&self.toc),
             get_points,
             shard_id,
             FULL_ACCESS.clone(),
             hw_data,
         )
-        .await
+        .await?;
+
+        downgrade_payloads_for_peers(
+            &self.toc,
+            res.get_mut()
+                .result
+                .iter_mut()
+                .map(|point| &mut point.payload),
+        );
+
+        Ok(res)
     }
 
     async fn count(
@@ -833,6 +899,70 @@ impl PointsInternal for PointsInternalService {
         );
         facet_counts_internal(self.toc.as_ref(), request_inner, hw_data).await
     }
+
+    async fn point_history(
+        &self,
+        request: Request<PointHistoryInternal>,
+    ) -> Result<Response<PointHistoryResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+
+        let PointHistoryInternal {
+            collection_name,
+            id,
+            shard_id,
+        } = request.into_inner();
+
+        let point_id = extract_internal_request(id)?.try_into()?;
+
+        let entries = self
+            .toc
+            .point_history_internal(&collection_name, shard_id, point_id)
+            .await?;
+        let entries = serialize_entries(&entries).map_err(StorageError::from)?;
+
+        Ok(Response::new(PointHistoryResponseInternal {
+            entries,
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
+
+    async fn point_versions(
+        &self,
+        request: Request<PointVersionsInternal>,
+    ) -> Result<Response<PointVersionsResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+
+        let PointVersionsInternal {
+            collection_name,
+            ids,
+            shard_id,
+            timeout,
+        } = request.into_inner();
+
+        let ids = ids
+            .into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let versions = self
+            .toc
+            .point_versions_internal(
+                &collection_name,
+                ids,
+                ShardSelectorInternal::ShardId(shard_id),
+                timeout.map(Duration::from_secs),
+            )
+            .await?;
+
+        Ok(Response::new(PointVersionsResponseInternal {
+            points: versions.into_iter().map(From::from).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
 }
 
 fn extract_internal_request<T>(request: Option<T>) -> Result<T, tonic::Status> {
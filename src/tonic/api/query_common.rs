@@ -2,8 +2,9 @@ use std::time::{Duration, Instant};
 
 use api::conversions::json::json_path_from_proto;
 use api::grpc::qdrant::{
-    BatchResult, CoreSearchPoints, CountPoints, CountResponse, DiscoverBatchResponse,
-    DiscoverPoints, DiscoverResponse, FacetCounts, FacetResponse, GetPoints, GetResponse,
+    AggregatePoints, AggregateResponse, AggregateResult, BatchResult, CoreSearchPoints,
+    CountPoints, CountResponse, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
+    ExistsPoints, ExistsResponse, ExistsResult, FacetCounts, FacetResponse, GetPoints, GetResponse,
     GroupsResult, QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints,
     QueryResponse, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
     RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
@@ -11,10 +12,12 @@ use api::grpc::qdrant::{
     SearchPointGroups, SearchPoints, SearchResponse,
 };
 use api::grpc::{InferenceUsage, Usage};
-use api::rest::OrderByInterface;
+use api::rest::{AggregateRequestInternal, OrderByInterface};
+use collection::collection::aggregate::CollectionAggregateRequest;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
+use collection::common::partial_results::PartialResults;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::try_discover_request_from_grpc;
 use collection::operations::query_enum::QueryEnum;
@@ -588,10 +591,12 @@ pub async fn scroll(
         shard_key_selector,
         order_by,
         timeout,
+        after_id,
     } = scroll_points;
 
     let scroll_request = ScrollRequestInternal {
         offset: offset.map(|o| o.try_into()).transpose()?,
+        after_id: after_id.map(|id| id.try_into()).transpose()?,
         limit: limit.map(|l| l as usize),
         filter: filter.map(|f| f.try_into()).transpose()?,
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
@@ -772,6 +777,72 @@ pub async fn get(
     Ok(Response::new(response))
 }
 
+pub async fn exists(
+    toc_provider: impl CheckedTocProvider,
+    exists_points: ExistsPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+    request_hw_counter: RequestHwCounter,
+) -> Result<Response<ExistsResponse>, Status> {
+    let ExistsPoints {
+        collection_name,
+        ids,
+        read_consistency,
+        shard_key_selector,
+        timeout,
+    } = exists_points;
+
+    let ids: Vec<_> = ids
+        .into_iter()
+        .map(|p| p.try_into())
+        .collect::<Result<_, _>>()?;
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timing = Instant::now();
+
+    // Same checks as for retrieving the points without payload and vectors
+    let point_request = PointRequestInternal {
+        ids: ids.clone(),
+        with_payload: None,
+        with_vector: Default::default(),
+    };
+    let toc = toc_provider
+        .check_strict_mode(
+            &point_request,
+            &collection_name,
+            timeout.map(|i| i as usize),
+            &access,
+        )
+        .await?;
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    let result = do_points_exist(
+        toc,
+        &collection_name,
+        ids,
+        read_consistency,
+        timeout,
+        shard_selector,
+        access,
+    )
+    .await?;
+
+    let response = ExistsResponse {
+        result: Some(ExistsResult {
+            existing: result.existing.into_iter().map(|id| id.into()).collect(),
+            missing: result.missing.into_iter().map(|id| id.into()).collect(),
+            versions: result.versions,
+        }),
+        time: timing.elapsed().as_secs_f64(),
+        usage: Usage::from_hardware_usage(request_hw_counter.to_grpc_api()).into_non_empty(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn query(
     toc_provider: impl CheckedTocProvider,
     query_points: QueryPoints,
@@ -789,6 +860,10 @@ pub async fn query(
         .transpose()?;
     let collection_name = query_points.collection_name.clone();
     let timeout = query_points.timeout;
+    let partial = query_points
+        .allow_partial
+        .unwrap_or_default()
+        .then(PartialResults::default);
     let (request, inference_usage) =
         convert_query_points_from_grpc(query_points, inference_token).await?;
 
@@ -813,9 +888,11 @@ pub async fn query(
         access,
         timeout,
         request_hw_counter.get_counter(),
+        partial.as_ref(),
     )
     .await?;
 
+    let (degraded, unreachable_shards) = degraded_markers(partial.as_ref());
     let response = QueryResponse {
         result: scored_points
             .into_iter()
@@ -823,6 +900,12 @@ pub async fn query(
             .collect(),
         time: timing.elapsed().as_secs_f64(),
         usage: Usage::new(request_hw_counter.to_grpc_api(), Some(inference_usage)).into_non_empty(),
+        truncated: request_hw_counter
+            .get_counter()
+            .is_search_truncated()
+            .then_some(true),
+        degraded,
+        unreachable_shards,
     };
 
     Ok(Response::new(response))
@@ -836,6 +919,7 @@ pub async fn query_batch(
     read_consistency: Option<ReadConsistencyGrpc>,
     access: Access,
     timeout: Option<Duration>,
+    allow_partial: bool,
     request_hw_counter: RequestHwCounter,
     inference_token: InferenceToken,
 ) -> Result<Response<QueryBatchResponse>, Status> {
@@ -863,6 +947,7 @@ pub async fn query_batch(
         .await?;
 
     let timing = Instant::now();
+    let partial = allow_partial.then(PartialResults::default);
     let scored_points = do_query_batch_points(
         toc,
         collection_name,
@@ -871,9 +956,11 @@ pub async fn query_batch(
         access,
         timeout,
         request_hw_counter.get_counter(),
+        partial.as_ref(),
     )
     .await?;
 
+    let (degraded, unreachable_shards) = degraded_markers(partial.as_ref());
     let response = QueryBatchResponse {
         result: scored_points
             .into_iter()
@@ -887,11 +974,25 @@ pub async fn query_batch(
             total_inference_usage.into_non_empty(),
         )
         .into_non_empty(),
+        truncated: request_hw_counter
+            .get_counter()
+            .is_search_truncated()
+            .then_some(true),
+        degraded,
+        unreachable_shards,
     };
 
     Ok(Response::new(response))
 }
 
+/// Response markers of a partial read, only present if some shards did not respond
+fn degraded_markers(partial: Option<&PartialResults>) -> (Option<bool>, Vec<u32>) {
+    match partial {
+        Some(partial) if partial.is_degraded() => (Some(true), partial.unreachable_shards()),
+        _ => (None, Vec::new()),
+    }
+}
+
 pub async fn query_groups(
     toc_provider: impl CheckedTocProvider,
     query_points: QueryPointGroups,
@@ -1014,6 +1115,64 @@ pub async fn facet(
     Ok(Response::new(response))
 }
 
+pub async fn aggregate(
+    toc_provider: impl CheckedTocProvider,
+    aggregate_points: AggregatePoints,
+    access: Access,
+    request_hw_counter: RequestHwCounter,
+) -> Result<Response<AggregateResponse>, Status> {
+    let AggregatePoints {
+        collection_name,
+        key,
+        filter,
+        percentiles,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = aggregate_points;
+
+    let aggregate_request = AggregateRequestInternal {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(TryInto::try_into).transpose()?,
+        percentiles: Some(percentiles),
+    };
+
+    let toc = toc_provider
+        .check_strict_mode(
+            &aggregate_request,
+            &collection_name,
+            timeout.map(|i| i as usize),
+            &access,
+        )
+        .await?;
+
+    let timeout = timeout.map(Duration::from_secs);
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timing = Instant::now();
+    let aggregate_response = toc
+        .aggregate(
+            &collection_name,
+            CollectionAggregateRequest::from(aggregate_request),
+            shard_selector,
+            read_consistency,
+            access,
+            timeout,
+            request_hw_counter.get_counter(),
+        )
+        .await?;
+
+    let response = AggregateResponse {
+        result: Some(AggregateResult::from(aggregate_response)),
+        time: timing.elapsed().as_secs_f64(),
+        usage: Usage::from_hardware_usage(request_hw_counter.to_grpc_api()).into_non_empty(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn search_points_matrix(
     toc_provider: impl CheckedTocProvider,
     search_matrix_points: SearchMatrixPoints,
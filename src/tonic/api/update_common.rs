@@ -52,6 +52,7 @@ pub async fn upsert(
         ordering,
         shard_key_selector,
         update_filter,
+        skip_unchanged,
     } = upsert_points;
 
     let points: Result<_, _> = points.into_iter().map(PointStruct::try_from).collect();
@@ -62,6 +63,7 @@ pub async fn upsert(
         update_filter: update_filter
             .map(segment::types::Filter::try_from)
             .transpose()?,
+        skip_unchanged,
     });
 
     let timing = Instant::now();
@@ -427,6 +429,7 @@ pub async fn update_batch(
                 points,
                 shard_key_selector,
                 update_filter,
+                skip_unchanged,
             }) => {
                 upsert(
                     StrictModeCheckedTocProvider::new(dispatcher),
@@ -437,6 +440,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         update_filter,
+                        skip_unchanged,
                     },
                     internal_params,
                     access.clone(),
@@ -396,6 +396,15 @@ ACTION_ACCESS = {
         coll_rw_payload=False,
         coll_prw=True,
     ),
+    "points_exist": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/exists",
+        "qdrant.Points/Exists",
+        coll_rw_payload=False,
+        coll_prw=True,
+    ),
     "upsert_points": EndpointAccess(
         False,
         True,
@@ -581,9 +590,23 @@ ACTION_ACCESS = {
         True,
         "POST /collections/{collection_name}/points/search/matrix/pairs", "qdrant.Points/SearchMatrixPairs"
     ),
+    "start_recall_probe": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/recall_probe",
+        "qdrant.Points/RecallProbe",
+    ),
     "facet": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/facet", "qdrant.Points/Facet"
     ),
+    "aggregate": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/aggregate",
+        "qdrant.Points/Aggregate",
+    ),
     ### Service ###
     "root": EndpointAccess(True, True, True, "GET /", "qdrant.Qdrant/HealthCheck"),
     "readyz": EndpointAccess(True, True, True, "GET /readyz", "grpc.health.v1.Health/Check"),
@@ -1461,6 +1484,15 @@ def test_get_points():
     )
 
 
+def test_points_exist():
+    check_access(
+        "points_exist",
+        rest_request={"ids": [1]},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "ids": [{"num": 1}]},
+    )
+
+
 def test_upsert_points():
     check_access(
         "upsert_points",
@@ -1862,6 +1894,15 @@ def test_search_points_matrix_pairs():
     )
 
 
+def test_start_recall_probe():
+    check_access(
+        "start_recall_probe",
+        rest_request={"sample": 0},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME, "sample": 0},
+    )
+
+
 def test_facet():
     check_access(
         "facet",
@@ -1876,6 +1917,20 @@ def test_facet():
     )
 
 
+def test_aggregate():
+    check_access(
+        "aggregate",
+        path_params={"collection_name": COLL_NAME},
+        rest_request={
+            "key": FACET_KEY,
+        },
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "key": FACET_KEY,
+        },
+    )
+
+
 def test_root():
     check_access("root")
 
@@ -0,0 +1,87 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_aggregate(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/aggregate",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "key": "price",
+            "percentiles": [0, 50, 100],
+        },
+    )
+    assert response.ok, response.text
+    result = response.json()["result"]
+
+    assert result["count"] == 4
+    assert result["sum"] == 39.5
+    assert result["avg"] == 9.875
+    assert result["min"] == 9
+    assert result["max"] == 11
+    assert result["percentiles"] == [
+        {"percentile": 0, "value": 9},
+        {"percentile": 50, "value": 9.75},
+        {"percentile": 100, "value": 11},
+    ]
+
+
+def test_aggregate_filtered(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/aggregate",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "key": "price",
+            "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+        },
+    )
+    assert response.ok, response.text
+    result = response.json()["result"]
+
+    assert result["count"] == 3
+    assert result["sum"] == 30.5
+    assert result["min"] == 9.5
+    assert result["max"] == 11
+    assert result["percentiles"] == []
+
+
+def test_aggregate_no_values(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/aggregate",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "key": "city",
+            "percentiles": [90],
+        },
+    )
+    assert response.ok, response.text
+    result = response.json()["result"]
+
+    assert result["count"] == 0
+    assert result["avg"] is None
+    assert result["percentiles"] == [{"percentile": 90, "value": None}]
+
+
+def test_aggregate_invalid_percentile(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/aggregate",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "key": "price",
+            "percentiles": [101],
+        },
+    )
+    assert response.status_code == 422, response.text
@@ -82,6 +82,25 @@ def test_points_retrieve(collection_name):
     assert response.ok
     assert len(response.json()['result']['points']) == 2
 
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"after_id": 2, "limit": 2}
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result['points']] == [3, 4]
+    assert result['next_page_offset'] == 5
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"offset": 2, "after_id": 2}
+    )
+    assert response.status_code == 400
+
 
 def test_exclude_payload(collection_name):
     response = request_with_validation(
@@ -458,3 +477,31 @@ def test_with_vectors_alias_of_with_vector(collection_name):
 
     scroll_with_vector("with_vector")
     scroll_with_vector("with_vectors")
+
+
+def test_points_exist(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/exists',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "ids": [1, 2, 100, 3]
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['existing'] == [1, 2, 3]
+    assert result['missing'] == [100]
+    assert len(result['versions']) == 3
+
+
+def test_points_exist_too_many_ids(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/exists',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "ids": list(range(10_001))
+        }
+    )
+    assert response.status_code == 400
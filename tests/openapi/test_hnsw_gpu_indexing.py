@@ -0,0 +1,44 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(collection_name):
+    drop_collection(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_hnsw_config(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    return response.json()["result"]["config"]["hnsw_config"]
+
+
+def test_hnsw_gpu_indexing_config(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "hnsw_config": {"gpu_indexing": False},
+        },
+    )
+    assert response.ok
+    assert get_hnsw_config(collection_name)["gpu_indexing"] is False
+
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"hnsw_config": {"gpu_indexing": True}},
+    )
+    assert response.ok
+    assert get_hnsw_config(collection_name)["gpu_indexing"] is True
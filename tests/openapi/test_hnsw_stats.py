@@ -0,0 +1,35 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_hnsw_stats(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/hnsw_stats",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    result = response.json()["result"]
+
+    assert [shard["shard_id"] for shard in result] == [0]
+
+    # Collection is too small to be indexed, so there are no graphs yet
+    assert result[0]["segments"] == []
+
+
+def test_hnsw_stats_missing_collection():
+    response = request_with_validation(
+        api="/collections/{collection_name}/hnsw_stats",
+        method="GET",
+        path_params={"collection_name": "no_such_collection"},
+    )
+    assert response.status_code == 404
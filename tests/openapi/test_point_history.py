@@ -0,0 +1,95 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(collection_name):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "wal_config": {"wal_point_history": 2},
+        },
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_point_history(collection_name, point_id):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/{id}/history",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": point_id},
+    )
+
+
+def test_point_history(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"city": "Berlin"}},
+                {"id": 2, "vector": [0.4, 0.3, 0.2, 0.1]},
+            ],
+        },
+        query_params={"wait": "true"},
+    )
+    assert response.ok
+
+    response = get_point_history(collection_name, 1)
+    assert response.ok
+    history = response.json()["result"]
+    assert len(history) == 1
+    assert history[0]["change"]["type"] == "upsert"
+    assert history[0]["change"]["payload"] == {"city": "Berlin"}
+    assert set(history[0]["change"]["vectors"]) == {""}
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/payload",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"payload": {"city": "London"}, "filter": {"must": [{"has_id": [1]}]}},
+        query_params={"wait": "true"},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/delete",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"points": [1]},
+        query_params={"wait": "true"},
+    )
+    assert response.ok
+
+    # Only the last 2 changes are kept, the newest first
+    response = get_point_history(collection_name, 1)
+    assert response.ok
+    history = response.json()["result"]
+    assert [entry["change"]["type"] for entry in history] == ["delete", "set_payload"]
+    assert history[1]["change"]["payload"] == {"city": "London"}
+    assert history[0]["version"] > history[1]["version"]
+
+    # Point not changed by the filtered update
+    response = get_point_history(collection_name, 2)
+    assert response.ok
+    assert [entry["change"]["type"] for entry in response.json()["result"]] == ["upsert"]
+
+    response = get_point_history(collection_name, 3)
+    assert response.ok
+    assert response.json()["result"] == []
+
+
+def test_point_history_disabled(collection_name):
+    basic_collection_setup(collection_name=collection_name)
+
+    response = get_point_history(collection_name, 1)
+    assert response.status_code == 400
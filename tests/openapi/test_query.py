@@ -339,6 +339,42 @@ def test_basic_context(collection_name):
     assert set([p["id"] for p in context_result]) == set([p["id"] for p in query_result])
 
 
+def test_basic_combine(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": {
+                "combine": [
+                    {"vector": 1},  # ids
+                    {"vector": 2, "weight": -1.0},  # ids
+                    {"vector": [0.1, 0.2, 0.3, 0.4]},
+                ],
+            },
+            "limit": 100,
+        },
+    )
+    assert response.ok
+    combine_result = response.json()["result"]["points"]
+
+    # vector(1) - vector(2) + [0.1, 0.2, 0.3, 0.4]
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [-0.04, 0.0, 0.31, 1.03],
+            "limit": 100,
+        },
+    )
+    assert response.ok
+    # referenced points are excluded from the combined query results
+    nearest_result = [p for p in response.json()["result"]["points"] if p["id"] not in (1, 2)]
+
+    assert [p["id"] for p in combine_result] == [p["id"] for p in nearest_result]
+
+
 def test_basic_order_by(collection_name):
     response = request_with_validation(
         api="/collections/{collection_name}/points/scroll",
@@ -0,0 +1,41 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def query(collection_name, params=None):
+    body = {"query": [0.2, 0.1, 0.9, 0.7], "limit": 8}
+    if params is not None:
+        body["params"] = params
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()["result"]
+
+
+def test_query_scoring_budget(collection_name):
+    result = query(collection_name)
+    assert len(result["points"]) == 8
+    assert "truncated" not in result
+
+    # Budget covers all points
+    result = query(collection_name, {"max_scored_points": 100})
+    assert len(result["points"]) == 8
+    assert "truncated" not in result
+
+    result = query(collection_name, {"max_scored_points": 1})
+    assert len(result["points"]) < 8
+    assert result["truncated"] is True
+
@@ -0,0 +1,42 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_recent_queries(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"query": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/recent_queries",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+
+    # Recording is disabled by default, queries are only recorded if enabled in the config
+    for query in response.json()["result"]:
+        assert query["using"] == ""
+        assert query["limit"] == 3
+
+
+def test_recent_queries_missing_collection():
+    response = request_with_validation(
+        api="/collections/{collection_name}/recent_queries",
+        method="GET",
+        path_params={"collection_name": "no_such_collection"},
+    )
+    assert response.status_code == 404
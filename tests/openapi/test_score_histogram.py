@@ -0,0 +1,49 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_score_histogram(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search/score_histogram",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "buckets": 3,
+        },
+    )
+    assert response.ok, response.text
+    result = response.json()["result"]
+
+    # All points of the collection are sampled
+    assert result["sampled"] == 6
+    assert len(result["buckets"]) == 3
+    assert sum(bucket["count"] for bucket in result["buckets"]) == 6
+    assert result["buckets"][0]["from"] == result["min_score"]
+    assert result["buckets"][-1]["to"] == result["max_score"]
+
+
+def test_score_histogram_filtered(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search/score_histogram",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "filter": {"must": [{"has_id": [1, 2]}]},
+            "sample": 10,
+        },
+    )
+    assert response.ok, response.text
+    result = response.json()["result"]
+    assert result["sampled"] == 2
+    assert sum(bucket["count"] for bucket in result["buckets"]) == 2
@@ -0,0 +1,65 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, on_disk_payload, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors,
+                           on_disk_payload=on_disk_payload)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(collection_name, point):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "points": [point],
+            "skip_unchanged": True,
+        },
+        query_params={"wait": "true"},
+    )
+    assert response.ok
+    return response.json()["result"]
+
+
+def test_skip_unchanged_upsert(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 2},
+    )
+    assert response.ok
+    stored = response.json()["result"]
+
+    point = {
+        "id": stored["id"],
+        "vector": stored["vector"],
+        "payload": stored["payload"],
+    }
+
+    # Identical content is accepted, the stored point is left as it is
+    result = upsert(collection_name, point)
+    assert result["status"] == "completed"
+
+    # Changed payload is applied
+    point["payload"] = {**stored["payload"], "extra": 42}
+    result = upsert(collection_name, point)
+    assert result["status"] == "completed"
+    assert result["operation_id"] is not None
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 2},
+    )
+    assert response.ok
+    assert response.json()["result"]["payload"]["extra"] == 42
+
+    # New points are always inserted
+    result = upsert(collection_name, {"id": 1000, "vector": [0.1, 0.2, 0.3, 0.4]})
+    assert result["operation_id"] is not None
@@ -2564,4 +2564,70 @@ def test_strict_mode_payload_index_count(collection_name):
                 "type": "integer",
             }
         }
-    ).raise_for_status()
\ No newline at end of file
+    ).raise_for_status()
+
+def test_strict_mode_enforce_payload_schema(collection_name):
+    for field_name, field_type in [("city", "keyword"), ("count", "integer")]:
+        request_with_validation(
+            api='/collections/{collection_name}/index',
+            method="PUT",
+            path_params={'collection_name': collection_name},
+            query_params={'wait': 'true'},
+            body={
+                "field_name": field_name,
+                "field_schema": field_type,
+            }
+        ).raise_for_status()
+
+    set_strict_mode(collection_name, {
+        "enabled": True,
+        "enforce_payload_schema": True,
+    })
+
+    def upsert_point(payload):
+        return request_with_validation(
+            api='/collections/{collection_name}/points',
+            method="PUT",
+            path_params={'collection_name': collection_name},
+            query_params={'wait': 'true'},
+            body={
+                "points": [
+                    {
+                        "id": 1,
+                        "vector": [0.05, 0.61, 0.76, 0.74],
+                        "payload": payload,
+                    }
+                ]
+            }
+        )
+
+    upsert_point({"city": ["Berlin", "London"], "count": 42}).raise_for_status()
+
+    # Key without a payload index
+    response = upsert_point({"city": "Berlin", "country": "Germany"})
+    assert response.status_code == 400
+    assert "Payload key 'country' has no payload index" in response.json()['status']['error']
+
+    # Value of a wrong type
+    response = upsert_point({"count": "42"})
+    assert response.status_code == 400
+    assert "Value \"42\" of payload key 'count' doesn't match its index type 'integer'" in response.json()['status']['error']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"city": 42},
+            "points": [1],
+        }
+    )
+    assert response.status_code == 400
+    assert "Value 42 of payload key 'city' doesn't match its index type 'keyword'" in response.json()['status']['error']
+
+    set_strict_mode(collection_name, {
+        "enforce_payload_schema": False,
+    })
+
+    upsert_point({"country": "Germany", "count": "42"}).raise_for_status()
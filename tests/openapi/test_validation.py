@@ -51,6 +51,24 @@ def test_validation_body_param(collection_name):
     assert not response.ok
     assert 'Validation error' in response.json()["status"]["error"]
     assert 'hnsw_config.ef_construct' in response.json()["status"]["error"]
+    assert response.json()["error_details"] == {
+        "code": "bad_input",
+        "retryable": False,
+        "parameter": "hnsw_config.ef_construct",
+    }
+
+
+def test_error_details_not_found():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': 'no_such_collection', 'id': 1},
+    )
+    assert response.status_code == 404
+    assert response.json()["error_details"] == {
+        "code": "not_found",
+        "retryable": False,
+    }
 
 
 def test_validation_query_param(collection_name):
@@ -0,0 +1,56 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(collection_name):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        body={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "wal_config": {"wal_batch_window_ms": 5},
+        },
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_batched_updates(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    assert response.json()["result"]["config"]["wal_config"]["wal_batch_window_ms"] == 5
+
+    # Updates without waiting are collected into batches with the following ones
+    for point_id in range(1, 11):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points",
+            method="PUT",
+            path_params={"collection_name": collection_name},
+            body={
+                "points": [
+                    {"id": point_id, "vector": [0.1, 0.2, 0.3, point_id / 10]},
+                ],
+            },
+            query_params={"wait": "true" if point_id == 10 else "false"},
+        )
+        assert response.ok
+
+    # Operations are applied in order, so the last waiting one implies all previous are applied
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/count",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    assert response.json()["result"]["count"] == 10
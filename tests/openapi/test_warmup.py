@@ -0,0 +1,40 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_warmup(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/warmup",
+        method="POST",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    assert response.json()["result"] is True
+
+    # Collection is still searchable after warmup
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+    )
+    assert response.ok
+    assert len(response.json()["result"]) == 3
+
+
+def test_warmup_missing_collection():
+    response = request_with_validation(
+        api="/collections/{collection_name}/warmup",
+        method="POST",
+        path_params={"collection_name": "no_such_collection"},
+    )
+    assert response.status_code == 404